@@ -19,13 +19,13 @@ use symphonia_core::errors::{
 };
 use symphonia_core::formats::prelude::*;
 use symphonia_core::formats::probe::{ProbeFormatData, ProbeableFormat, Score, Scoreable};
-use symphonia_core::formats::util::{SeekIndex, SeekSearchResult};
+use symphonia_core::formats::util::{SeekIndex, SeekSearchResult, TrackFilter};
 use symphonia_core::formats::well_known::FORMAT_ID_FLAC;
 use symphonia_core::io::*;
-use symphonia_core::meta::{Metadata, MetadataBuilder, MetadataLog};
+use symphonia_core::meta::{Metadata, MetadataBuilder, MetadataError, MetadataLog};
 use symphonia_metadata::embedded::flac::*;
 
-use log::{debug, info};
+use log::{debug, info, warn};
 
 use super::parser::PacketParser;
 
@@ -46,6 +46,7 @@ pub struct FlacReader<'s> {
     attachments: Vec<Attachment>,
     chapters: Option<ChapterGroup>,
     metadata: MetadataLog,
+    track_filter: TrackFilter,
     index: Option<SeekIndex>,
     first_frame_offset: u64,
     parser: PacketParser,
@@ -77,6 +78,7 @@ impl<'s> FlacReader<'s> {
     /// Reads all the metadata blocks, returning a fully populated `FlacReader`.
     fn init_with_metadata(mss: MediaSourceStream<'s>, opts: FormatOptions) -> Result<Self> {
         let mut metadata_builder = MetadataBuilder::new(FLAC_METADATA_INFO);
+        let mut metadata_errors = Vec::new();
 
         let mut reader = mss;
         let mut track = None;
@@ -119,9 +121,22 @@ impl<'s> FlacReader<'s> {
                         return decode_error("flac: found more than one seek table block");
                     }
                 }
-                // VorbisComment blocks are parsed into Tags.
+                // VorbisComment blocks are parsed into Tags. Unless in strict mode, a malformed block
+                // does not prevent the stream from being read, so record the error and keep any
+                // tags read before it.
                 MetadataBlockType::VorbisComment => {
-                    read_flac_comment_block(&mut block_stream, &mut metadata_builder)?;
+                    if let Err(err) = read_flac_comment_block(
+                        &mut block_stream,
+                        &mut metadata_builder,
+                        opts.limit_element_bytes,
+                        opts.strict,
+                    ) {
+                        if opts.strict {
+                            return Err(err);
+                        }
+                        warn!("flac: malformed comment block ({err}), skipping");
+                        metadata_errors.push(MetadataError::new(FLAC_METADATA_INFO, &err));
+                    }
                 }
                 // Cuesheet blocks are parsed into Cues.
                 MetadataBlockType::Cuesheet => {
@@ -135,9 +150,19 @@ impl<'s> FlacReader<'s> {
                         return decode_error("flac: cuesheet block before stream info");
                     }
                 }
-                // Picture blocks are read as Visuals.
+                // Picture blocks are read as Visuals. Like comment blocks, a malformed block is
+                // recorded and skipped.
                 MetadataBlockType::Picture => {
-                    metadata_builder.add_visual(read_flac_picture_block(&mut block_stream)?);
+                    match read_flac_picture_block(&mut block_stream, opts.limit_element_bytes) {
+                        Ok(visual) => {
+                            metadata_builder.add_visual(visual);
+                        }
+                        Err(err) if opts.strict => return Err(err),
+                        Err(err) => {
+                            warn!("flac: malformed picture block ({err}), skipping");
+                            metadata_errors.push(MetadataError::new(FLAC_METADATA_INFO, &err));
+                        }
+                    }
                 }
                 // Padding blocks are skipped.
                 MetadataBlockType::Padding => {
@@ -177,6 +202,10 @@ impl<'s> FlacReader<'s> {
         let mut metadata = opts.external_data.metadata.unwrap_or_default();
         metadata.push(metadata_builder.build());
 
+        for err in metadata_errors {
+            metadata.push_error(err);
+        }
+
         // Synchronize the packet parser to the first audio frame.
         let _ = parser.resync(&mut reader)?;
 
@@ -191,6 +220,7 @@ impl<'s> FlacReader<'s> {
             attachments,
             chapters,
             metadata,
+            track_filter: Default::default(),
             index,
             first_frame_offset,
             parser,
@@ -231,7 +261,13 @@ impl FormatReader for FlacReader<'_> {
     }
 
     fn next_packet(&mut self) -> Result<Option<Packet>> {
-        self.parser.parse(&mut self.reader)
+        // The packets of unselected tracks are discarded after reading them.
+        loop {
+            match self.parser.parse(&mut self.reader)? {
+                Some(packet) if !self.track_filter.is_selected(packet.track_id) => (),
+                packet => return Ok(packet),
+            }
+        }
     }
 
     fn metadata(&mut self) -> Metadata<'_> {
@@ -246,10 +282,18 @@ impl FormatReader for FlacReader<'_> {
         &self.tracks
     }
 
+    fn track_filter(&self) -> Option<&TrackFilter> {
+        Some(&self.track_filter)
+    }
+
+    fn track_filter_mut(&mut self) -> Option<&mut TrackFilter> {
+        Some(&mut self.track_filter)
+    }
+
     fn seek(&mut self, _mode: SeekMode, to: SeekTo) -> Result<SeekedTo> {
         let Some(track) = self.tracks.first()
         else {
-            return seek_error(SeekErrorKind::Unseekable);
+            return seek_error(SeekErrorKind::Unsupported);
         };
 
         // Get the timestamp of the desired audio frame.
@@ -259,7 +303,7 @@ impl FormatReader for FlacReader<'_> {
             // Time value given, calculate frame timestamp using the track's timebase.
             SeekTo::Time { time, .. } => {
                 // The timebase is required to calculate the timestamp.
-                let tb = track.time_base.ok_or(Error::SeekError(SeekErrorKind::Unseekable))?;
+                let tb = track.time_base.ok_or(Error::SeekError(SeekErrorKind::Unsupported))?;
 
                 // If the timestamp overflows, the seek if out-of-range.
                 tb.calc_timestamp(time).ok_or(Error::SeekError(SeekErrorKind::OutOfRange))?
@@ -392,6 +436,10 @@ impl FormatReader for FlacReader<'_> {
         Ok(SeekedTo { track_id: 0, actual_ts: packet.ts, required_ts: ts })
     }
 
+    fn source_position(&self) -> Option<u64> {
+        self.reader.is_seekable().then(|| self.reader.pos())
+    }
+
     fn into_inner<'s>(self: Box<Self>) -> MediaSourceStream<'s>
     where
         Self: 's,
@@ -502,12 +502,15 @@ impl PacketParser {
         // Update the frame size moving average.
         self.fsma.push(parsed.buf.len());
 
-        Ok(Some(Packet::new(
+        let mut packet = Packet::new(
             0,
             parsed.sync.ts.try_into().expect("flac timestamp is 36-bits maximum"),
             Duration::from(parsed.sync.dur),
             parsed.buf,
-        )))
+        );
+        packet.num_frames = Some(u64::from(parsed.sync.dur));
+
+        Ok(Some(packet))
     }
 
     /// Resync the reader to the start of the next frame.
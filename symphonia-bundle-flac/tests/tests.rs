@@ -0,0 +1,123 @@
+use symphonia_bundle_flac::FlacReader;
+use symphonia_core::checksum::Crc8Ccitt;
+use symphonia_core::codecs::CodecParameters;
+use symphonia_core::codecs::audio::VerificationCheck;
+use symphonia_core::formats::{FormatOptions, FormatReader};
+use symphonia_core::io::{MediaSourceStream, Monitor};
+use symphonia_core::meta::StandardTag;
+
+const BLOCK_TYPE_STREAM_INFO: u8 = 0;
+const BLOCK_TYPE_VORBIS_COMMENT: u8 = 4;
+
+/// Write a metadata block with the given type and payload.
+fn block(block_type: u8, is_last: bool, payload: &[u8]) -> Vec<u8> {
+    let mut buf = vec![(u8::from(is_last) << 7) | block_type];
+    buf.extend_from_slice(&(payload.len() as u32).to_be_bytes()[1..]);
+    buf.extend_from_slice(payload);
+    buf
+}
+
+/// Write a stream information block payload for 4096 sample blocks of 44.1 kHz, 16-bit, stereo
+/// audio.
+fn stream_info() -> Vec<u8> {
+    let mut buf = Vec::new();
+    // Minimum and maximum block sizes, and the unknown minimum and maximum frame sizes.
+    buf.extend_from_slice(&4096u16.to_be_bytes());
+    buf.extend_from_slice(&4096u16.to_be_bytes());
+    buf.extend_from_slice(&[0; 6]);
+    // Sample rate, channels - 1, bits per sample - 1, and the total number of samples.
+    let packed = (44100u64 << 44) | (1 << 41) | (15 << 36) | 4096;
+    buf.extend_from_slice(&packed.to_be_bytes());
+    // The MD5 checksum of the decoded audio.
+    buf.extend_from_slice(&[0; 16]);
+    buf
+}
+
+/// Write the header of the first frame of the stream described by `stream_info`.
+fn frame_header() -> Vec<u8> {
+    // Sync code, 4096 sample block size, 44.1 kHz, stereo, 16-bit, and frame number 0.
+    let mut buf = vec![0xff, 0xf8, 0xc9, 0x18, 0x00];
+    let mut crc = Crc8Ccitt::new(0);
+    crc.process_buf_bytes(&buf);
+    buf.push(crc.crc());
+    buf
+}
+
+/// Build a FLAC file with the given Vorbis comment block payload.
+fn make_flac(comment: &[u8]) -> Vec<u8> {
+    let mut buf = b"fLaC".to_vec();
+    buf.extend(block(BLOCK_TYPE_STREAM_INFO, false, &stream_info()));
+    buf.extend(block(BLOCK_TYPE_VORBIS_COMMENT, true, comment));
+    buf.extend(frame_header());
+    buf
+}
+
+/// Build a FLAC file with a Vorbis comment block containing an empty vendor string and two
+/// comments. The second comment is truncated by the end of the block.
+fn make_flac_with_truncated_comment() -> Vec<u8> {
+    let mut comment = vec![0; 4];
+    comment.extend_from_slice(&2u32.to_le_bytes());
+    comment.extend_from_slice(&11u32.to_le_bytes());
+    comment.extend_from_slice(b"TITLE=Title");
+    comment.extend_from_slice(&100u32.to_le_bytes());
+    comment.extend_from_slice(b"ARTIST=");
+    make_flac(&comment)
+}
+
+#[test]
+fn read_truncated_comment_block() {
+    let data = make_flac_with_truncated_comment();
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+
+    // The stream is readable despite the malformed comment block.
+    let mut reader = FlacReader::try_new(mss, Default::default()).unwrap();
+    assert_eq!(reader.tracks().len(), 1);
+
+    // Comments read before the error are kept, and the error is recorded.
+    let metadata = reader.metadata();
+    let rev = metadata.current().unwrap();
+    assert_eq!(rev.media.tags.len(), 1);
+    assert!(matches!(
+        &rev.media.tags[0].std,
+        Some(StandardTag::TrackTitle(value)) if **value == "Title"
+    ));
+
+    assert_eq!(metadata.errors().len(), 1);
+    assert_eq!(metadata.errors()[0].info.short_name, "flac");
+}
+
+#[test]
+fn read_truncated_comment_block_strict() {
+    let data = make_flac_with_truncated_comment();
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+
+    // In strict mode, the malformed comment block is an error.
+    let opts = FormatOptions::default().strict(true);
+    assert!(FlacReader::try_new(mss, opts).is_err());
+}
+
+fn verification_check(data: Vec<u8>) -> Option<VerificationCheck> {
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let reader = FlacReader::try_new(mss, Default::default()).unwrap();
+
+    match &reader.tracks()[0].codec_params {
+        Some(CodecParameters::Audio(params)) => params.verification_check,
+        _ => panic!("expected audio codec parameters"),
+    }
+}
+
+#[test]
+fn read_md5_verification_check() {
+    // An all-zero MD5 checksum means the checksum was not computed.
+    assert!(verification_check(make_flac(&[0; 8])).is_none());
+
+    // The MD5 checksum is the last field of the stream information block.
+    let md5: [u8; 16] = std::array::from_fn(|i| i as u8 + 1);
+
+    let mut data = make_flac(&[0; 8]);
+    data[26..42].copy_from_slice(&md5);
+
+    assert!(
+        matches!(verification_check(data), Some(VerificationCheck::Md5(value)) if value == md5)
+    );
+}
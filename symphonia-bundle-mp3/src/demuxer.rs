@@ -14,6 +14,7 @@ use symphonia_core::codecs::audio::well_known::{CODEC_ID_MP1, CODEC_ID_MP2, CODE
 use symphonia_core::errors::{Error, Result, SeekErrorKind, seek_error};
 use symphonia_core::formats::prelude::*;
 use symphonia_core::formats::probe::{ProbeFormatData, ProbeableFormat, Score, Scoreable};
+use symphonia_core::formats::util::TrackFilter;
 use symphonia_core::formats::well_known::{FORMAT_ID_MP1, FORMAT_ID_MP2, FORMAT_ID_MP3};
 use symphonia_core::io::*;
 use symphonia_core::meta::{Metadata, MetadataLog};
@@ -22,6 +23,7 @@ use crate::common::{FrameHeader, MpegLayer};
 use crate::header::{self, MAX_MPEG_FRAME_SIZE, MPEG_HEADER_LEN};
 
 use std::io::{Seek, SeekFrom};
+use std::num::NonZero;
 
 use log::{debug, info, warn};
 
@@ -43,8 +45,12 @@ pub struct MpaReader<'s> {
     tracks: Vec<Track>,
     chapters: Option<ChapterGroup>,
     metadata: MetadataLog,
+    track_filter: TrackFilter,
     first_packet_pos: u64,
     next_packet_ts: Timestamp,
+    seek_toc: Option<SeekToc>,
+    /// After a seek, the required timestamp. Frames preceding it are trimmed from the packets.
+    seek_ts: Option<Timestamp>,
 }
 
 impl Scoreable for MpaReader<'_> {
@@ -151,63 +157,13 @@ impl FormatReader for MpaReader<'_> {
     }
 
     fn next_packet(&mut self) -> Result<Option<Packet>> {
-        let (header, data) = loop {
-            // Read the next MPEG frame.
-            let (header, data) = match read_mpeg_frame(&mut self.reader) {
-                Ok(frame) => frame,
-                Err(Error::IoError(err)) if err.kind() == std::io::ErrorKind::UnexpectedEof => {
-                    // MPEG streams have no well-defined end, so when no more frames can be read,
-                    // consider the stream ended.
-                    return Ok(None);
-                }
-                Err(err) => return Err(err),
-            };
-
-            // Check if the packet contains a Xing, Info, or VBRI tag.
-            if is_maybe_info_tag(&data, &header) {
-                if try_read_info_tag(&data, &header).is_some() {
-                    // Discard the packet and tag since it was not at the start of the stream.
-                    warn!("found an unexpected xing tag, discarding");
-                    continue;
-                }
-            }
-            else if is_maybe_vbri_tag(&data, &header)
-                && try_read_vbri_tag(&data, &header).is_some()
-            {
-                // Discard the packet and tag since it was not at the start of the stream.
-                warn!("found an unexpected vbri tag, discarding");
-                continue;
+        // The packets of unselected tracks are discarded after reading them.
+        loop {
+            match self.read_packet()? {
+                Some(packet) if !self.track_filter.is_selected(packet.track_id) => (),
+                packet => return Ok(packet),
             }
-
-            break (header, data);
-        };
-
-        // The timestamp and duration for this packet.
-        let pts = self.next_packet_ts;
-        let dur = header.duration();
-
-        // Advance the next packet timestamp based on this packet's duration. If it saturates, then
-        // it is not possible to read further.
-        self.next_packet_ts = match self.next_packet_ts.checked_add(dur) {
-            Some(ts) => ts,
-            None => return Ok(None),
-        };
-
-        // Build the packet.
-        let packet = PacketBuilder::new()
-            .track_id(0)
-            .pts(pts)
-            .trimmed_dur(
-                dur,
-                self.tracks[0]
-                    .num_frames
-                    .map(Duration::from)
-                    .and_then(|dur| dur.timestamp_from(Timestamp::ZERO)),
-            )
-            .data(data)
-            .build();
-
-        Ok(Some(packet))
+        }
     }
 
     fn metadata(&mut self) -> Metadata<'_> {
@@ -222,6 +178,14 @@ impl FormatReader for MpaReader<'_> {
         &self.tracks
     }
 
+    fn track_filter(&self) -> Option<&TrackFilter> {
+        Some(&self.track_filter)
+    }
+
+    fn track_filter_mut(&mut self) -> Option<&mut TrackFilter> {
+        Some(&mut self.track_filter)
+    }
+
     fn seek(&mut self, mode: SeekMode, to: SeekTo) -> Result<SeekedTo> {
         const MAX_REF_FRAMES: usize = 4;
         const REF_FRAMES_MASK: usize = MAX_REF_FRAMES - 1;
@@ -234,7 +198,7 @@ impl FormatReader for MpaReader<'_> {
             SeekTo::Time { time, .. } => {
                 // The timebase is required to calculate the timestamp.
                 let tb =
-                    self.tracks[0].time_base.ok_or(Error::SeekError(SeekErrorKind::Unseekable))?;
+                    self.tracks[0].time_base.ok_or(Error::SeekError(SeekErrorKind::Unsupported))?;
 
                 // If the timestamp overflows, the seek if out-of-range.
                 tb.calc_timestamp(time).ok_or(Error::SeekError(SeekErrorKind::OutOfRange))?
@@ -392,9 +356,15 @@ impl FormatReader for MpaReader<'_> {
             self.next_packet_ts.saturating_delta(required_ts),
         );
 
+        self.seek_ts = Some(required_ts);
+
         Ok(SeekedTo { track_id: 0, required_ts, actual_ts: self.next_packet_ts })
     }
 
+    fn source_position(&self) -> Option<u64> {
+        self.reader.is_seekable().then(|| self.reader.pos())
+    }
+
     fn into_inner<'s>(self: Box<Self>) -> MediaSourceStream<'s>
     where
         Self: 's,
@@ -404,6 +374,81 @@ impl FormatReader for MpaReader<'_> {
 }
 
 impl<'s> MpaReader<'s> {
+    /// Read the next packet, regardless of the track filter.
+    fn read_packet(&mut self) -> Result<Option<Packet>> {
+        let (header, data) = loop {
+            // Read the next MPEG frame.
+            let (header, data) = match read_mpeg_frame(&mut self.reader) {
+                Ok(frame) => frame,
+                Err(Error::IoError(err)) if err.kind() == std::io::ErrorKind::UnexpectedEof => {
+                    // MPEG streams have no well-defined end, so when no more frames can be read,
+                    // consider the stream ended.
+                    return Ok(None);
+                }
+                Err(err) => return Err(err),
+            };
+
+            // Check if the packet contains a Xing, Info, or VBRI tag.
+            if is_maybe_info_tag(&data, &header) {
+                if try_read_info_tag(&data, &header).is_some() {
+                    // Discard the packet and tag since it was not at the start of the stream.
+                    warn!("found an unexpected xing tag, discarding");
+                    continue;
+                }
+            }
+            else if is_maybe_vbri_tag(&data, &header)
+                && try_read_vbri_tag(&data, &header).is_some()
+            {
+                // Discard the packet and tag since it was not at the start of the stream.
+                warn!("found an unexpected vbri tag, discarding");
+                continue;
+            }
+
+            break (header, data);
+        };
+
+        // The timestamp and duration for this packet.
+        let pts = self.next_packet_ts;
+        let dur = header.duration();
+
+        // Advance the next packet timestamp based on this packet's duration. If it saturates, then
+        // it is not possible to read further.
+        self.next_packet_ts = match self.next_packet_ts.checked_add(dur) {
+            Some(ts) => ts,
+            None => return Ok(None),
+        };
+
+        // Build the packet.
+        let mut packet = PacketBuilder::new()
+            .track_id(0)
+            .pts(pts)
+            .trimmed_dur(
+                dur,
+                self.tracks[0]
+                    .num_frames
+                    .map(Duration::from)
+                    .and_then(|dur| dur.timestamp_from(Timestamp::ZERO)),
+            )
+            .data(data)
+            .num_frames(dur.get())
+            .build();
+
+        // After a seek, trim the frames preceding the required timestamp, including those of any
+        // reference frames, such that the decoded audio starts exactly at the seeked position.
+        if let Some(seek_ts) = self.seek_ts {
+            // The time base is the reciprocal of the sample rate.
+            if let Some(sample_rate) = NonZero::new(header.sample_rate) {
+                packet.trim_start_to(seek_ts, TimeBase::from_recip(sample_rate), sample_rate);
+            }
+
+            if self.next_packet_ts >= seek_ts {
+                self.seek_ts = None;
+            }
+        }
+
+        Ok(Some(packet))
+    }
+
     pub fn try_new(mut mss: MediaSourceStream<'s>, opts: FormatOptions) -> Result<Self> {
         // Try to read the first MPEG frame.
         let (header, packet) = read_mpeg_frame_strict(&mut mss)?;
@@ -414,13 +459,20 @@ impl<'s> MpaReader<'s> {
         codec_params
             .for_codec(header.codec())
             .with_sample_rate(header.sample_rate)
-            .with_channels(header.channel_mode.channels());
+            .with_channels(header.channel_mode.channels())
+            .with_max_frames_per_packet(u64::from(header.num_frames()));
 
         // Create the track.
         let mut track = Track::new(0);
 
         track.with_codec_params(CodecParameters::Audio(codec_params));
 
+        // The seek table of contents, if provided by a Xing/Info or VBRI tag.
+        let mut seek_toc = None;
+
+        // The byte position of the first MPEG frame.
+        let tag_pos = mss.pos() - packet.len() as u64;
+
         // Check if there is a Xing/Info tag contained in the first frame.
         if let Some(info_tag) = try_read_info_tag(&packet, &header) {
             // The LAME tag contains ReplayGain and padding information.
@@ -439,6 +491,12 @@ impl<'s> MpaReader<'s> {
                 let discard = track.delay.unwrap_or(0) + track.padding.unwrap_or(0);
 
                 track.with_num_frames(num_frames.saturating_sub(u64::from(discard)));
+
+                // A seek table requires the total number of MPEG frames and bytes.
+                if let (Some(toc), Some(num_bytes)) = (&info_tag.toc, info_tag.num_bytes) {
+                    debug!("using xing header toc for seeking");
+                    seek_toc = Some(SeekToc::from_xing(tag_pos, toc, num_bytes, num_frames));
+                }
             }
         }
         else if let Some(vbri_tag) = try_read_vbri_tag(&packet, &header) {
@@ -448,6 +506,12 @@ impl<'s> MpaReader<'s> {
 
             // Check if there is a VBRI tag.
             track.with_num_frames(num_frames);
+
+            if !vbri_tag.toc.is_empty() {
+                debug!("using vbri header toc for seeking");
+                let frames_per_entry = vbri_tag.frames_per_entry * u64::from(header.num_frames());
+                seek_toc = Some(SeekToc::from_vbri(tag_pos, &vbri_tag, frames_per_entry));
+            }
         }
         else {
             // The first frame was not a Xing/Info header, rewind back to the start of the frame so
@@ -478,8 +542,11 @@ impl<'s> MpaReader<'s> {
             tracks: vec![track],
             chapters: opts.external_data.chapters,
             metadata: opts.external_data.metadata.unwrap_or_default(),
+            track_filter: Default::default(),
             first_packet_pos,
             next_packet_ts,
+            seek_toc,
+            seek_ts: None,
         })
     }
 
@@ -491,24 +558,29 @@ impl<'s> MpaReader<'s> {
         min_ts: Timestamp,
         max_ts: Option<Timestamp>,
     ) -> Result<()> {
-        // Get the length in bytes of the stream's audio data. It is not possible to coarsely seek
-        // without knowing this.
-        let audio_byte_len = match self.reader.byte_len() {
-            Some(byte_len) => u128::from(byte_len - self.first_packet_pos),
-            None => return seek_error(SeekErrorKind::Unseekable),
-        };
-
         // It is not possible to coarse seek without knowing both the upper and lower timestamp
         // bounds, and the lower timestamp bound must be <= the upper timestamp bound.
         let max_ts = match max_ts {
             Some(max_ts) if max_ts >= min_ts => max_ts,
-            _ => return seek_error(SeekErrorKind::Unseekable),
+            _ => return seek_error(SeekErrorKind::Unsupported),
         };
 
         // Call invariants.
         debug_assert!(min_ts <= required_ts);
         debug_assert!(required_ts <= max_ts);
 
+        // If a seek table is available, use it instead of assuming a constant bitrate.
+        if self.seek_toc.is_some() {
+            return self.preseek_coarse_toc(required_ts, min_ts, max_ts);
+        }
+
+        // Get the length in bytes of the stream's audio data. It is not possible to coarsely seek
+        // without knowing this.
+        let audio_byte_len = match self.reader.byte_len() {
+            Some(byte_len) => u128::from(byte_len - self.first_packet_pos),
+            None => return seek_error(SeekErrorKind::Unseekable),
+        };
+
         // The total duration of the track.
         let total_dur = u128::from(max_ts.abs_delta(min_ts).get());
 
@@ -552,6 +624,45 @@ impl<'s> MpaReader<'s> {
         Ok(())
     }
 
+    /// Seeks the media source stream to a byte position roughly where the packet with the required
+    /// timestamp should be located using the seek table of contents.
+    fn preseek_coarse_toc(
+        &mut self,
+        required_ts: Timestamp,
+        min_ts: Timestamp,
+        max_ts: Timestamp,
+    ) -> Result<()> {
+        // UNWRAP: The caller checks that the seek table exists.
+        let toc = self.seek_toc.as_ref().unwrap();
+
+        // Lookup the byte position of the required timestamp, relative to the start of the first
+        // MPEG frame.
+        let seek_pos_rel = toc.pos_for_dur(required_ts.abs_delta(min_ts).get());
+
+        // It is preferable to return a packet with a timestamp before the requested timestamp.
+        // Therefore, subtract the maximum packet size from the position found above to ensure
+        // this, but do not seek before the first packet.
+        let seek_pos = (toc.base_pos + seek_pos_rel)
+            .saturating_sub(MAX_MPEG_FRAME_SIZE as u64)
+            .max(self.first_packet_pos);
+
+        self.reader.seek(SeekFrom::Start(seek_pos))?;
+
+        // Resync to the start of the next MPEG frame.
+        let (header, _) = read_mpeg_frame_strict(&mut self.reader)?;
+
+        // Lookup the duration from the start of the audio of the packet based on the byte position
+        // after resync.
+        let audio_byte_pos = self.reader.pos() - toc.base_pos;
+        let dur_to_pkt = Duration::from(toc.dur_for_pos(audio_byte_pos));
+
+        // UNWRAP: The packet duration is always non-zero, and no larger than 1152.
+        self.next_packet_ts =
+            min_ts.checked_add(dur_to_pkt.align_down(header.duration()).unwrap()).unwrap_or(max_ts);
+
+        Ok(())
+    }
+
     /// Seeks the media source stream back to the start of the first packet if the required
     /// timestamp is in the past.
     fn preseek_accurate(&mut self, required_ts: Timestamp, min_ts: Timestamp) -> Result<()> {
@@ -723,6 +834,92 @@ fn estimate_num_mpeg_frames(reader: &mut MediaSourceStream<'_>) -> Option<u64> {
     num_mpeg_frames
 }
 
+/// A seek table of contents mapping the duration from the start of the stream to a byte
+/// position.
+struct SeekToc {
+    /// The byte position of the first MPEG frame. All byte positions in the table are relative to
+    /// this position.
+    base_pos: u64,
+    /// An ordered list of (duration, byte position) pairs. The final pair is the total duration
+    /// and length of the stream.
+    points: Vec<(u64, u64)>,
+}
+
+impl SeekToc {
+    /// Build a seek table from the 100-point Xing table of contents. Each entry in the table is
+    /// the byte position, as a fraction of the total byte length scaled to 256, of each 1% of
+    /// the total duration.
+    fn from_xing(base_pos: u64, toc: &[u8; 100], num_bytes: u32, total_dur: u64) -> Self {
+        let num_bytes = u64::from(num_bytes);
+
+        let mut points: Vec<(u64, u64)> = toc
+            .iter()
+            .enumerate()
+            .map(|(i, &pos)| ((i as u64 * total_dur) / 100, (u64::from(pos) * num_bytes) / 256))
+            .collect();
+
+        points.push((total_dur, num_bytes));
+
+        SeekToc { base_pos, points }
+    }
+
+    /// Build a seek table from the VBRI table of contents. Each entry in the table is the length
+    /// in bytes of a segment of the stream spanning the provided duration.
+    fn from_vbri(base_pos: u64, tag: &VbriTag, dur_per_entry: u64) -> Self {
+        let mut points = Vec::with_capacity(tag.toc.len() + 1);
+
+        let mut dur = 0;
+        let mut pos = 0;
+
+        points.push((dur, pos));
+
+        for &len in tag.toc.iter() {
+            dur += dur_per_entry;
+            pos += u64::from(len);
+            points.push((dur, pos));
+        }
+
+        SeekToc { base_pos, points }
+    }
+
+    /// Get the byte position for the provided duration from the start of the stream.
+    fn pos_for_dur(&self, dur: u64) -> u64 {
+        let i = self.points.partition_point(|&(d, _)| d <= dur);
+        interpolate(&self.points, i, dur, |p| p.0, |p| p.1)
+    }
+
+    /// Get the duration from the start of the stream for the provided byte position.
+    fn dur_for_pos(&self, pos: u64) -> u64 {
+        let i = self.points.partition_point(|&(_, p)| p <= pos);
+        interpolate(&self.points, i, pos, |p| p.1, |p| p.0)
+    }
+}
+
+/// Linearly interpolate between the points at index `i - 1` and `i` to find the value of `y` at
+/// `x`. Clamps to the first and last points.
+fn interpolate<X, Y>(points: &[(u64, u64)], i: usize, x: u64, get_x: X, get_y: Y) -> u64
+where
+    X: Fn(&(u64, u64)) -> u64,
+    Y: Fn(&(u64, u64)) -> u64,
+{
+    match (i.checked_sub(1).and_then(|i| points.get(i)), points.get(i)) {
+        (Some(p0), Some(p1)) => {
+            let (x0, x1) = (get_x(p0), get_x(p1));
+            let (y0, y1) = (get_y(p0), get_y(p1));
+
+            if x1 > x0 && y1 >= y0 {
+                y0 + ((u128::from(x - x0) * u128::from(y1 - y0)) / u128::from(x1 - x0)) as u64
+            }
+            else {
+                y0
+            }
+        }
+        (Some(p0), None) => get_y(p0),
+        (None, Some(p1)) => get_y(p1),
+        (None, None) => 0,
+    }
+}
+
 const XING_TAG_ID: [u8; 4] = *b"Xing";
 const INFO_TAG_ID: [u8; 4] = *b"Info";
 
@@ -965,6 +1162,10 @@ const VBRI_TAG_ID: [u8; 4] = *b"VBRI";
 struct VbriTag {
     num_bytes: u32,
     num_mpeg_frames: u32,
+    /// The number of MPEG frames spanned by each table of contents entry.
+    frames_per_entry: u64,
+    /// The table of contents. Each entry is the length in bytes of a segment of the stream.
+    toc: Vec<u32>,
 }
 
 /// Try to read a VBRI tag from the provided MPEG frame.
@@ -1006,7 +1207,33 @@ fn try_read_vbri_tag_inner(buf: &[u8], header: &FrameHeader) -> Result<Option<Vb
     let num_bytes = reader.read_be_u32()?;
     let num_mpeg_frames = reader.read_be_u32()?;
 
-    Ok(Some(VbriTag { num_bytes, num_mpeg_frames }))
+    // The table of contents.
+    let num_entries = reader.read_be_u16()?;
+    let scale = u32::from(reader.read_be_u16()?);
+    let entry_size = reader.read_be_u16()?;
+    let frames_per_entry = u64::from(reader.read_be_u16()?);
+
+    // Entries are big-endian and 1 to 4 bytes in size. If the table of contents is invalid or
+    // truncated, then ignore it since the rest of the tag is still useful.
+    let mut toc = Vec::new();
+
+    if (1..=4).contains(&entry_size) && frames_per_entry > 0 {
+        let mut entry = [0; 4];
+
+        for _ in 0..num_entries {
+            let entry_buf = &mut entry[4 - usize::from(entry_size)..];
+
+            if reader.read_buf_exact(entry_buf).is_err() {
+                warn!("vbri tag toc is truncated");
+                toc.clear();
+                break;
+            }
+
+            toc.push(u32::from_be_bytes(entry).saturating_mul(scale));
+        }
+    }
+
+    Ok(Some(VbriTag { num_bytes, num_mpeg_frames, frames_per_entry, toc }))
 }
 
 /// Perform a fast check to see if the packet contains a VBRI tag. If this returns true, the
@@ -1035,3 +1262,336 @@ fn is_maybe_vbri_tag(buf: &[u8], header: &FrameHeader) -> bool {
     // The bytes preceeding the VBRI tag (mostly the side information) should be all 0.
     !buf[MPEG_HEADER_LEN..VBRI_TAG_OFFSET].iter().any(|&b| b != 0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// MPEG1 layer 3, 128 kbps, 44.1 kHz, joint stereo.
+    const HEADER_WORD: u32 = 0xfffb_9064;
+
+    fn make_tag_frame(tag: &[u8], offset: usize) -> (FrameHeader, Vec<u8>) {
+        let header = header::parse_frame_header(HEADER_WORD).unwrap();
+
+        let mut buf = vec![0; MPEG_HEADER_LEN + header.frame_size];
+        buf[..MPEG_HEADER_LEN].copy_from_slice(&HEADER_WORD.to_be_bytes());
+        buf[offset..offset + tag.len()].copy_from_slice(tag);
+
+        (header, buf)
+    }
+
+    /// MPEG1 layer 3, 320 kbps, 44.1 kHz, joint stereo.
+    const HEADER_WORD_320K: u32 = 0xfffb_e064;
+
+    /// Append 5 frames at 128 kbps, followed by 5 frames at 320 kbps, to `buf`. The body of each
+    /// frame is filled with the frame number. Returns the byte position of each frame, and the end
+    /// of the last frame.
+    fn append_vbr_frames(buf: &mut Vec<u8>) -> Vec<u64> {
+        let mut positions = vec![buf.len() as u64];
+
+        for (i, header_word) in
+            [HEADER_WORD; 5].into_iter().chain([HEADER_WORD_320K; 5]).enumerate()
+        {
+            let header = header::parse_frame_header(header_word).unwrap();
+
+            let mut frame = vec![i as u8; MPEG_HEADER_LEN + header.frame_size];
+            frame[..MPEG_HEADER_LEN].copy_from_slice(&header_word.to_be_bytes());
+            buf.extend_from_slice(&frame);
+
+            positions.push(buf.len() as u64);
+        }
+
+        positions
+    }
+
+    /// Seek coarsely to `ts`, and return the first packet that is not entirely trimmed.
+    fn seek_coarse_to_first_packet(reader: &mut MpaReader<'_>, ts: i64) -> Packet {
+        let required_ts = Timestamp::new(ts);
+        let seeked = reader
+            .seek(SeekMode::Coarse, SeekTo::Timestamp { ts: required_ts, track_id: 0 })
+            .unwrap();
+        assert_eq!(seeked.required_ts, required_ts);
+
+        loop {
+            let packet = reader.next_packet().unwrap().unwrap();
+            if !packet.dur.is_zero() {
+                return packet;
+            }
+        }
+    }
+
+    #[test]
+    fn verify_xing_toc() {
+        let mut tag = Vec::new();
+        tag.extend_from_slice(b"Xing");
+        tag.extend_from_slice(&0x7u32.to_be_bytes()); // Frames, bytes, and TOC flags.
+        tag.extend_from_slice(&1000u32.to_be_bytes()); // Number of frames.
+        tag.extend_from_slice(&256_000u32.to_be_bytes()); // Number of bytes.
+        tag.extend((0..100).map(|i| (i * 256 / 100) as u8)); // Linear TOC.
+
+        let (header, buf) = make_tag_frame(&tag, MPEG_HEADER_LEN + 32);
+
+        let xing = try_read_info_tag(&buf, &header).unwrap();
+
+        assert!(!xing.is_cbr);
+        assert_eq!(xing.num_frames, Some(1000));
+        assert_eq!(xing.num_bytes, Some(256_000));
+
+        let total_dur = 1000 * u64::from(header.num_frames());
+        let toc = SeekToc::from_xing(100, xing.toc.as_ref().unwrap(), 256_000, total_dur);
+
+        assert_eq!(toc.base_pos, 100);
+        assert_eq!(toc.pos_for_dur(0), 0);
+        assert_eq!(toc.pos_for_dur(total_dur / 2), 128_000);
+        assert_eq!(toc.pos_for_dur(total_dur), 256_000);
+        assert_eq!(toc.pos_for_dur(2 * total_dur), 256_000);
+        assert_eq!(toc.dur_for_pos(0), 0);
+        assert_eq!(toc.dur_for_pos(128_000), total_dur / 2);
+        assert_eq!(toc.dur_for_pos(256_000), total_dur);
+    }
+
+    #[test]
+    fn verify_vbri_toc() {
+        let mut tag = Vec::new();
+        tag.extend_from_slice(b"VBRI");
+        tag.extend_from_slice(&1u16.to_be_bytes()); // Version.
+        tag.extend_from_slice(&0u16.to_be_bytes()); // Delay.
+        tag.extend_from_slice(&0u16.to_be_bytes()); // Quality.
+        tag.extend_from_slice(&4000u32.to_be_bytes()); // Number of bytes.
+        tag.extend_from_slice(&40u32.to_be_bytes()); // Number of frames.
+        tag.extend_from_slice(&4u16.to_be_bytes()); // Number of TOC entries.
+        tag.extend_from_slice(&10u16.to_be_bytes()); // Scale.
+        tag.extend_from_slice(&2u16.to_be_bytes()); // Entry size.
+        tag.extend_from_slice(&10u16.to_be_bytes()); // Frames per entry.
+        for len in [50u16, 100, 150, 100] {
+            tag.extend_from_slice(&len.to_be_bytes());
+        }
+
+        let (header, buf) = make_tag_frame(&tag, 36);
+
+        let vbri = try_read_vbri_tag(&buf, &header).unwrap();
+
+        assert_eq!(vbri.num_mpeg_frames, 40);
+        assert_eq!(vbri.toc, [500, 1000, 1500, 1000]);
+
+        let dur_per_entry = vbri.frames_per_entry * u64::from(header.num_frames());
+        let toc = SeekToc::from_vbri(0, &vbri, dur_per_entry);
+
+        assert_eq!(toc.pos_for_dur(0), 0);
+        assert_eq!(toc.pos_for_dur(dur_per_entry), 500);
+        assert_eq!(toc.pos_for_dur(dur_per_entry + dur_per_entry / 2), 1000);
+        assert_eq!(toc.pos_for_dur(4 * dur_per_entry), 4000);
+        assert_eq!(toc.dur_for_pos(3000), 3 * dur_per_entry);
+    }
+
+    #[test]
+    fn verify_lame_gapless_trim() {
+        const NUM_MPEG_FRAMES: u32 = 5;
+
+        let mut tag = Vec::new();
+        tag.extend_from_slice(b"Xing");
+        tag.extend_from_slice(&0x1u32.to_be_bytes()); // Frames flag.
+        tag.extend_from_slice(&NUM_MPEG_FRAMES.to_be_bytes()); // Number of frames.
+        tag.extend_from_slice(b"Lavf58.76"); // Encoder.
+        tag.extend_from_slice(&[0; 10]); // Revision, lowpass, and replaygain.
+        tag.extend_from_slice(&[0, 0]); // Encoding flags and ABR.
+        tag.extend_from_slice(&((576u32 << 12) | (529 + 600)).to_be_bytes()[1..]); // Delay & padding.
+        tag.extend_from_slice(&[0; 12]); // Remainder of the LAME extension.
+
+        let (header, mut buf) = make_tag_frame(&tag, MPEG_HEADER_LEN + 32);
+
+        // Append the audio frames.
+        for _ in 0..NUM_MPEG_FRAMES {
+            let (_, frame) = make_tag_frame(&[], MPEG_HEADER_LEN);
+            buf.extend_from_slice(&frame);
+        }
+
+        let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(buf)), Default::default());
+        let mut reader = MpaReader::try_new(mss, Default::default()).unwrap();
+
+        let frame_len = u64::from(header.num_frames());
+        let total_len = u64::from(NUM_MPEG_FRAMES) * frame_len;
+
+        let track = &reader.tracks()[0];
+        assert_eq!(track.delay, Some(1105));
+        assert_eq!(track.padding, Some(600));
+        assert_eq!(track.num_frames, Some(total_len - 1105 - 600));
+
+        let mut packets = Vec::new();
+        while let Some(packet) = reader.next_packet().unwrap() {
+            packets.push(packet);
+        }
+
+        // The Xing frame is not returned as a packet.
+        assert_eq!(packets.len(), NUM_MPEG_FRAMES as usize);
+
+        // The first packet trims the encoder delay.
+        assert_eq!(packets[0].pts, Timestamp::new(-1105));
+        assert_eq!(packets[0].trim_start, Duration::new(1105));
+        assert_eq!(packets[0].dur, Duration::new(frame_len - 1105));
+
+        // The last packet trims the encoder padding.
+        let last = packets.last().unwrap();
+        assert_eq!(last.trim_start, Duration::ZERO);
+        assert_eq!(last.trim_end, Duration::new(600));
+        assert_eq!(last.dur, Duration::new(frame_len - 600));
+
+        // The sum of all packet durations is the number of playable frames.
+        let dur = packets.iter().map(|p| p.dur.get()).sum::<u64>();
+        assert_eq!(dur, total_len - 1105 - 600);
+    }
+
+    #[test]
+    fn verify_seek_trim() {
+        const NUM_MPEG_FRAMES: u64 = 5;
+
+        let mut buf = Vec::new();
+
+        for _ in 0..NUM_MPEG_FRAMES {
+            let (_, frame) = make_tag_frame(&[], MPEG_HEADER_LEN);
+            buf.extend_from_slice(&frame);
+        }
+
+        let (header, _) = make_tag_frame(&[], MPEG_HEADER_LEN);
+        let frame_len = u64::from(header.num_frames());
+
+        let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(buf)), Default::default());
+        let mut reader = MpaReader::try_new(mss, Default::default()).unwrap();
+
+        // Seek to a timestamp within the third packet.
+        let required_ts = Timestamp::new(2 * frame_len as i64 + 100);
+        let seeked = reader
+            .seek(SeekMode::Accurate, SeekTo::Timestamp { ts: required_ts, track_id: 0 })
+            .unwrap();
+        assert_eq!(seeked.required_ts, required_ts);
+
+        let mut packets = Vec::new();
+        while let Some(packet) = reader.next_packet().unwrap() {
+            packets.push(packet);
+        }
+
+        // Frames preceding the required timestamp are trimmed, such that the first valid frame is
+        // the frame seeked to.
+        let first = packets.iter().find(|packet| !packet.dur.is_zero()).unwrap();
+        assert_eq!(first.pts, Timestamp::new(2 * frame_len as i64));
+        assert_eq!(first.trim_start, Duration::new(100));
+        assert_eq!(first.dur, Duration::new(frame_len - 100));
+
+        // Packets preceding it are entirely trimmed, and packets following it are untrimmed.
+        for packet in &packets {
+            if packet.pts < first.pts {
+                assert_eq!(packet.trim_start, Duration::new(frame_len));
+            }
+            else if packet.pts > first.pts {
+                assert_eq!(packet.trim_start, Duration::ZERO);
+            }
+        }
+
+        // The sum of all packet durations is the number of frames following the seek position.
+        let dur = packets.iter().map(|p| p.dur.get()).sum::<u64>();
+        assert_eq!(dur, NUM_MPEG_FRAMES * frame_len - 2 * frame_len - 100);
+    }
+
+    #[test]
+    fn verify_vbr_xing() {
+        // The Xing frame is followed by 10 frames. The number of bytes includes the Xing frame.
+        let (_, mut audio) = make_tag_frame(&[], MPEG_HEADER_LEN);
+        let positions = append_vbr_frames(&mut audio);
+        let num_bytes = audio.len() as u64;
+
+        // The byte position, as a fraction of the total byte length scaled to 256, of each 1% of
+        // the total duration.
+        let toc = (0..100).map(|i| {
+            let (frame, part) = (i / 10, i % 10);
+            let len = positions[frame + 1] - positions[frame];
+            let pos = positions[frame] + (len * part as u64) / 10;
+            ((pos * 256) / num_bytes) as u8
+        });
+
+        let mut tag = Vec::new();
+        tag.extend_from_slice(b"Xing");
+        tag.extend_from_slice(&0x7u32.to_be_bytes()); // Frames, bytes, and TOC flags.
+        tag.extend_from_slice(&10u32.to_be_bytes()); // Number of frames.
+        tag.extend_from_slice(&(num_bytes as u32).to_be_bytes()); // Number of bytes.
+        tag.extend(toc); // TOC.
+        tag.extend_from_slice(b"LAME3.100"); // Encoder.
+        tag.extend_from_slice(&[0; 10]); // Revision, lowpass, and replaygain.
+        tag.extend_from_slice(&[0, 0]); // Encoding flags and ABR.
+        tag.extend_from_slice(&((576u32 << 12) | (529 + 600)).to_be_bytes()[1..]); // Delay & padding.
+        tag.extend_from_slice(&[0; 12]); // Remainder of the LAME extension, with a CRC of 0.
+
+        let (header, xing_frame) = make_tag_frame(&tag, MPEG_HEADER_LEN + 32);
+        audio[..xing_frame.len()].copy_from_slice(&xing_frame);
+
+        let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(audio)), Default::default());
+        let mut reader = MpaReader::try_new(mss, Default::default()).unwrap();
+
+        let frame_len = u64::from(header.num_frames());
+
+        // The duration is given by the Xing header, less the encoder delay and padding.
+        let track = &reader.tracks()[0];
+        assert_eq!(track.delay, Some(1105));
+        assert_eq!(track.padding, Some(600));
+        assert_eq!(track.num_frames, Some(10 * frame_len - 1105 - 600));
+
+        // The first packet trims the encoder delay.
+        let packet = reader.next_packet().unwrap().unwrap();
+        assert_eq!(packet.pts, Timestamp::new(-1105));
+        assert_eq!(packet.trim_start, Duration::new(1105));
+        assert_eq!(packet.dur, Duration::new(frame_len - 1105));
+
+        // Seek to a timestamp within the eighth frame, a 320 kbps frame, using the TOC.
+        let packet = seek_coarse_to_first_packet(&mut reader, 7 * frame_len as i64 + 100 - 1105);
+        assert_eq!(packet.pts, Timestamp::new(7 * frame_len as i64 - 1105));
+        assert_eq!(packet.trim_start, Duration::new(100));
+        assert_eq!(packet.data.len() as u64, positions[8] - positions[7]);
+        assert_eq!(packet.data.last(), Some(&7));
+    }
+
+    #[test]
+    fn verify_vbr_vbri() {
+        let (header, mut audio) = make_tag_frame(&[], MPEG_HEADER_LEN);
+        let positions = append_vbr_frames(&mut audio);
+
+        let mut tag = Vec::new();
+        tag.extend_from_slice(b"VBRI");
+        tag.extend_from_slice(&1u16.to_be_bytes()); // Version.
+        tag.extend_from_slice(&0u16.to_be_bytes()); // Delay.
+        tag.extend_from_slice(&0u16.to_be_bytes()); // Quality.
+        tag.extend_from_slice(&(audio.len() as u32).to_be_bytes()); // Number of bytes.
+        tag.extend_from_slice(&10u32.to_be_bytes()); // Number of frames.
+        tag.extend_from_slice(&5u16.to_be_bytes()); // Number of TOC entries.
+        tag.extend_from_slice(&1u16.to_be_bytes()); // Scale.
+        tag.extend_from_slice(&2u16.to_be_bytes()); // Entry size.
+        tag.extend_from_slice(&2u16.to_be_bytes()); // Frames per entry.
+
+        // Each entry spans 2 frames. The first entry includes the VBRI frame.
+        for i in 0..5 {
+            let start = if i == 0 { 0 } else { positions[2 * i] };
+            tag.extend_from_slice(&((positions[2 * i + 2] - start) as u16).to_be_bytes());
+        }
+
+        let (_, vbri_frame) = make_tag_frame(&tag, 36);
+        audio[..vbri_frame.len()].copy_from_slice(&vbri_frame);
+
+        let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(audio)), Default::default());
+        let mut reader = MpaReader::try_new(mss, Default::default()).unwrap();
+
+        let frame_len = u64::from(header.num_frames());
+
+        // The duration is given by the VBRI header.
+        assert_eq!(reader.tracks()[0].num_frames, Some(10 * frame_len));
+
+        // The VBRI frame is not returned as a packet, and no frames are trimmed.
+        let mut packets = Vec::new();
+        while let Some(packet) = reader.next_packet().unwrap() {
+            assert_eq!(packet.trim_start, Duration::ZERO);
+            packets.push((packet.pts.get(), packet.data.len() as u64));
+        }
+
+        let expected: Vec<_> = (0..10)
+            .map(|i| (i as i64 * frame_len as i64, positions[i + 1] - positions[i]))
+            .collect();
+        assert_eq!(packets, expected);
+    }
+}
@@ -0,0 +1,49 @@
+use symphonia_bundle_mp3::MpaReader;
+use symphonia_core::codecs::CodecParameters;
+use symphonia_core::codecs::audio::well_known::CODEC_ID_MP3;
+use symphonia_core::formats::FormatReader;
+use symphonia_core::io::MediaSourceStream;
+
+/// Build a stream of silent MPEG audio layer 3 frames with the given 4-byte frame header and
+/// frame length.
+fn make_mp3_frames(header: [u8; 4], frame_len: usize, count: usize) -> Vec<u8> {
+    let mut data = Vec::new();
+
+    for _ in 0..count {
+        data.extend_from_slice(&header);
+        data.extend(std::iter::repeat_n(0, frame_len - header.len()));
+    }
+
+    data
+}
+
+fn read_max_frames_per_packet(data: Vec<u8>) -> Option<u64> {
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let mut reader = MpaReader::try_new(mss, Default::default()).unwrap();
+
+    let max_frames_per_packet = match &reader.tracks()[0].codec_params {
+        Some(CodecParameters::Audio(params)) => {
+            assert_eq!(params.codec, CODEC_ID_MP3);
+            params.max_frames_per_packet
+        }
+        _ => panic!("expected audio codec parameters"),
+    };
+
+    // Every packet contains the maximum number of frames per packet.
+    while let Some(packet) = reader.next_packet().unwrap() {
+        assert_eq!(Some(packet.dur.get()), max_frames_per_packet);
+    }
+
+    max_frames_per_packet
+}
+
+#[test]
+fn read_max_frames_per_packet_mp3() {
+    // MPEG-1, 128 kbps, 44.1 kHz, joint stereo. Each frame codes 2 granules of 576 frames.
+    let data = make_mp3_frames([0xff, 0xfb, 0x90, 0x64], 417, 4);
+    assert_eq!(read_max_frames_per_packet(data), Some(1152));
+
+    // MPEG-2, 64 kbps, 22.05 kHz, joint stereo. Each frame codes 1 granule of 576 frames.
+    let data = make_mp3_frames([0xff, 0xf3, 0x80, 0x64], 208, 4);
+    assert_eq!(read_max_frames_per_packet(data), Some(576));
+}
@@ -87,9 +87,10 @@ impl AacDecoder {
             _ => return unsupported_error("aac: channels or channel layout is required"),
         };
 
-        // Check complexity.
+        // Check complexity. If SBR is signalled in a backward-compatible way, the core can still be
+        // decoded.
         if asc.object_type != AudioObjectType::Lc
-            || asc.sbr_present
+            || asc.sbr_ps_info.is_some()
             || channels.count() > 2
             || asc.samples != 1024
         {
@@ -99,7 +100,10 @@ impl AacDecoder {
         // Clone and amend the codec parameters with information from the extra data.
         let mut params = params.clone();
 
-        params.with_channels(channels.clone()).with_sample_rate(asc.sample_rate);
+        params
+            .with_channels(channels.clone())
+            .with_sample_rate(asc.sample_rate)
+            .with_max_frames_per_packet(asc.samples as u64);
 
         let sbinfo = GASubbandInfo::find(asc.sample_rate);
 
@@ -15,15 +15,19 @@ use symphonia_core::codecs::audio::well_known::CODEC_ID_AAC;
 use symphonia_core::errors::{Result, SeekErrorKind, decode_error, seek_error};
 use symphonia_core::formats::prelude::*;
 use symphonia_core::formats::probe::{ProbeFormatData, ProbeableFormat, Score, Scoreable};
+use symphonia_core::formats::util::TrackFilter;
 use symphonia_core::formats::well_known::FORMAT_ID_ADTS;
 use symphonia_core::io::*;
-use symphonia_core::meta::{Metadata, MetadataLog};
+use symphonia_core::meta::{
+    Metadata, MetadataLog, MetadataOptions, MetadataReader, MetadataSideData,
+};
 
 use symphonia_common::mpeg::audio::*;
+use symphonia_metadata::id3v2::Id3v2Reader;
 
 use std::io::{Seek, SeekFrom};
 
-use log::{debug, info};
+use log::{debug, info, warn};
 
 const SAMPLES_PER_AAC_PACKET: Duration = Duration::new(1024);
 
@@ -42,12 +46,81 @@ pub struct AdtsReader<'s> {
     tracks: Vec<Track>,
     chapters: Option<ChapterGroup>,
     metadata: MetadataLog,
+    track_filter: TrackFilter,
     first_frame_pos: u64,
     next_packet_ts: Timestamp,
 }
 
 impl<'s> AdtsReader<'s> {
+    /// Read the next packet, regardless of the track filter.
+    fn read_packet(&mut self) -> Result<Option<Packet>> {
+        // Parse the header to get the calculated frame size.
+        let header = match AdtsHeader::read(&mut self.reader) {
+            Ok(header) => header,
+            Err(Error::IoError(err)) if err.kind() == std::io::ErrorKind::UnexpectedEof => {
+                // ADTS streams have no well-defined end, so when no more frames can be read,
+                // consider the stream ended.
+                return Ok(None);
+            }
+            Err(err) => return Err(err),
+        };
+
+        // TODO: Support multiple AAC packets per ADTS packet.
+
+        let ts = self.next_packet_ts;
+
+        self.next_packet_ts = match self.next_packet_ts.checked_add(SAMPLES_PER_AAC_PACKET) {
+            Some(ts) => ts,
+            None => return Ok(None),
+        };
+
+        let mut packet = Packet::new(
+            0,
+            ts,
+            SAMPLES_PER_AAC_PACKET,
+            self.reader.read_boxed_slice_exact(usize::from(header.payload_len()))?,
+        );
+        packet.num_frames = Some(SAMPLES_PER_AAC_PACKET.get());
+
+        Ok(Some(packet))
+    }
+
     pub fn try_new(mut mss: MediaSourceStream<'s>, opts: FormatOptions) -> Result<Self> {
+        let mut metadata = opts.external_data.metadata.unwrap_or_default();
+        let mut chapters = opts.external_data.chapters;
+
+        // Many ADTS streams are prefixed with one or more ID3v2 tags. When the reader is
+        // instantiated via. a probe these tags are usually consumed by the probe, but if not, they
+        // must be read here. Otherwise, the search for the first sync word could find a false sync
+        // word within the tag.
+        let meta_opts = MetadataOptions::default().strict(opts.strict);
+
+        while starts_with_id3v2_tag(&mut mss)? {
+            let mut reader = Box::new(Id3v2Reader::try_new(mss, meta_opts)?);
+
+            let result = reader.read_all();
+
+            mss = reader.into_inner();
+
+            match result {
+                Ok(buf) => {
+                    metadata.push(buf.revision);
+
+                    for side_data in buf.side_data {
+                        if let MetadataSideData::Chapters(group) = side_data {
+                            chapters = Some(group);
+                        }
+                    }
+                }
+                Err(err) if opts.strict => return Err(err),
+                Err(err) => {
+                    // The tag may be partially read. Fallback to searching for the first sync word.
+                    warn!("adts: ignoring id3v2 tag due to error: {err}");
+                    break;
+                }
+            }
+        }
+
         let header = AdtsHeader::read(&mut mss)?;
 
         // Rewind back to the start of the frame.
@@ -56,7 +129,10 @@ impl<'s> AdtsReader<'s> {
         // Use the header to populate the codec parameters.
         let mut codec_params = AudioCodecParameters::new();
 
-        codec_params.for_codec(CODEC_ID_AAC).with_sample_rate(header.sample_rate);
+        codec_params
+            .for_codec(CODEC_ID_AAC)
+            .with_sample_rate(header.sample_rate)
+            .with_max_frames_per_packet(1024);
 
         if let Some(channels) = header.channels {
             codec_params.with_channels(channels);
@@ -79,14 +155,26 @@ impl<'s> AdtsReader<'s> {
             reader: mss,
             media_info: MediaInfo::from_track(&track),
             tracks: vec![track],
-            chapters: opts.external_data.chapters,
-            metadata: opts.external_data.metadata.unwrap_or_default(),
+            chapters,
+            metadata,
+            track_filter: Default::default(),
             first_frame_pos,
             next_packet_ts: Timestamp::new(0),
         })
     }
 }
 
+/// Returns `true` if the stream is positioned at the start of an ID3v2 tag.
+fn starts_with_id3v2_tag(mss: &mut MediaSourceStream<'_>) -> Result<bool> {
+    let mut marker = [0; 3];
+    let len = mss.read_buf(&mut marker)?;
+
+    // Rewind back to the initial position.
+    mss.seek_buffered_rev(len);
+
+    Ok(len == marker.len() && marker == *b"ID3")
+}
+
 impl Scoreable for AdtsReader<'_> {
     fn score(mut src: ScopedStream<&mut MediaSourceStream<'_>>) -> Result<Score> {
         // Read the first (assumed) ADTS header.
@@ -279,32 +367,13 @@ impl FormatReader for AdtsReader<'_> {
     }
 
     fn next_packet(&mut self) -> Result<Option<Packet>> {
-        // Parse the header to get the calculated frame size.
-        let header = match AdtsHeader::read(&mut self.reader) {
-            Ok(header) => header,
-            Err(Error::IoError(err)) if err.kind() == std::io::ErrorKind::UnexpectedEof => {
-                // ADTS streams have no well-defined end, so when no more frames can be read,
-                // consider the stream ended.
-                return Ok(None);
+        // The packets of unselected tracks are discarded after reading them.
+        loop {
+            match self.read_packet()? {
+                Some(packet) if !self.track_filter.is_selected(packet.track_id) => (),
+                packet => return Ok(packet),
             }
-            Err(err) => return Err(err),
-        };
-
-        // TODO: Support multiple AAC packets per ADTS packet.
-
-        let ts = self.next_packet_ts;
-
-        self.next_packet_ts = match self.next_packet_ts.checked_add(SAMPLES_PER_AAC_PACKET) {
-            Some(ts) => ts,
-            None => return Ok(None),
-        };
-
-        Ok(Some(Packet::new(
-            0,
-            ts,
-            SAMPLES_PER_AAC_PACKET,
-            self.reader.read_boxed_slice_exact(usize::from(header.payload_len()))?,
-        )))
+        }
     }
 
     fn metadata(&mut self) -> Metadata<'_> {
@@ -319,6 +388,14 @@ impl FormatReader for AdtsReader<'_> {
         &self.tracks
     }
 
+    fn track_filter(&self) -> Option<&TrackFilter> {
+        Some(&self.track_filter)
+    }
+
+    fn track_filter_mut(&mut self) -> Option<&mut TrackFilter> {
+        Some(&mut self.track_filter)
+    }
+
     fn seek(&mut self, _mode: SeekMode, to: SeekTo) -> Result<SeekedTo> {
         // Get the timestamp of the desired audio frame.
         let required_ts = match to {
@@ -328,7 +405,7 @@ impl FormatReader for AdtsReader<'_> {
             SeekTo::Time { time, .. } => {
                 // The timebase is required to calculate the timestamp.
                 let tb =
-                    self.tracks[0].time_base.ok_or(Error::SeekError(SeekErrorKind::Unseekable))?;
+                    self.tracks[0].time_base.ok_or(Error::SeekError(SeekErrorKind::Unsupported))?;
 
                 // If the timestamp overflows, the seek if out-of-range.
                 tb.calc_timestamp(time).ok_or(Error::SeekError(SeekErrorKind::OutOfRange))?
@@ -401,6 +478,10 @@ impl FormatReader for AdtsReader<'_> {
         Ok(SeekedTo { track_id: 0, required_ts, actual_ts: self.next_packet_ts })
     }
 
+    fn source_position(&self) -> Option<u64> {
+        self.reader.is_seekable().then(|| self.reader.pos())
+    }
+
     fn into_inner<'s>(self: Box<Self>) -> MediaSourceStream<'s>
     where
         Self: 's,
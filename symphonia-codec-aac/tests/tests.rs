@@ -3,8 +3,11 @@ use symphonia_core::codecs::audio::{
     AudioCodecParameters, AudioDecoder, AudioDecoderOptions, well_known::CODEC_ID_AAC,
 };
 use symphonia_core::errors;
-use symphonia_core::formats::probe::ProbeableFormat;
+use symphonia_core::formats::probe::{Hint, Probe, ProbeableFormat};
+use symphonia_core::formats::{FormatOptions, FormatReader};
 use symphonia_core::io::MediaSourceStream;
+use symphonia_core::meta::StandardTag;
+use symphonia_metadata::id3v2::Id3v2Reader;
 
 fn test_decode(data: Vec<u8>) -> symphonia_core::errors::Result<()> {
     let data = std::io::Cursor::new(data);
@@ -41,3 +44,162 @@ fn invalid_channels_aac() {
 
     assert!(matches!(err, errors::Error::Unsupported(_)));
 }
+
+/// Build a minimal ID3v2.3 tag containing a track title, and a private frame containing a false
+/// ADTS sync word. The provided frames are appended to the tag.
+fn make_id3v2_tag(title: &str, extra_frames: &[u8]) -> Vec<u8> {
+    let mut frames = Vec::new();
+
+    // TIT2 frame with an ISO-8859-1 encoded title.
+    frames.extend_from_slice(b"TIT2");
+    frames.extend_from_slice(&(title.len() as u32 + 1).to_be_bytes());
+    frames.extend_from_slice(&[0, 0, 0]);
+    frames.extend_from_slice(title.as_bytes());
+
+    // PRIV frame with an owner and binary data resembling an ADTS header.
+    let priv_data = [b'x', 0, 0xff, 0xf1, 0x50, 0x80, 0x01, 0x1f, 0xfc];
+    frames.extend_from_slice(b"PRIV");
+    frames.extend_from_slice(&(priv_data.len() as u32).to_be_bytes());
+    frames.extend_from_slice(&[0, 0]);
+    frames.extend_from_slice(&priv_data);
+
+    frames.extend_from_slice(extra_frames);
+
+    // Tag header with a syncsafe size.
+    let size = frames.len() as u32;
+    let mut tag = vec![b'I', b'D', b'3', 3, 0, 0];
+    tag.extend_from_slice(&[
+        ((size >> 21) & 0x7f) as u8,
+        ((size >> 14) & 0x7f) as u8,
+        ((size >> 7) & 0x7f) as u8,
+        (size & 0x7f) as u8,
+    ]);
+    tag.extend_from_slice(&frames);
+    tag
+}
+
+/// Build a stream of empty MPEG-4 AAC-LC, 44.1 kHz, stereo ADTS frames.
+fn make_adts_frames(count: usize) -> Vec<u8> {
+    const PAYLOAD_LEN: u16 = 8;
+    const FRAME_LEN: u16 = 7 + PAYLOAD_LEN;
+
+    let mut data = Vec::new();
+
+    for _ in 0..count {
+        data.extend_from_slice(&[
+            0xff,
+            0xf1,
+            0x50,
+            0x80 | (FRAME_LEN >> 11) as u8,
+            (FRAME_LEN >> 3) as u8,
+            ((FRAME_LEN & 0x7) << 5) as u8 | 0x1f,
+            0xfc,
+        ]);
+        data.extend_from_slice(&[0; PAYLOAD_LEN as usize]);
+    }
+
+    data
+}
+
+fn make_tagged_adts() -> Vec<u8> {
+    let mut data = make_id3v2_tag("Tagged", &[]);
+    data.extend_from_slice(&make_adts_frames(4));
+    data
+}
+
+fn assert_title(reader: &mut dyn FormatReader, title: &str) {
+    let mut metadata = reader.metadata();
+    let rev = metadata.skip_to_latest().expect("metadata revision");
+
+    assert!(rev.media.tags.iter().any(|tag| {
+        matches!(&tag.std, Some(StandardTag::TrackTitle(value)) if value.as_str() == title)
+    }));
+}
+
+fn count_packets(reader: &mut dyn FormatReader) -> usize {
+    let mut count = 0;
+
+    while reader.next_packet().unwrap().is_some() {
+        count += 1;
+    }
+
+    count
+}
+
+#[test]
+fn probe_id3v2_tagged_adts() {
+    let mut probe = Probe::default();
+    probe.register_format::<AdtsReader<'_>>();
+    probe.register_metadata::<Id3v2Reader<'_>>();
+
+    let data = std::io::Cursor::new(make_tagged_adts());
+    let mss = MediaSourceStream::new(Box::new(data), Default::default());
+
+    let mut reader =
+        probe.probe(&Hint::new(), mss, Default::default(), Default::default()).unwrap();
+
+    assert_title(reader.as_mut(), "Tagged");
+    assert_eq!(count_packets(reader.as_mut()), 4);
+}
+
+#[test]
+fn read_id3v2_tagged_adts() {
+    let data = std::io::Cursor::new(make_tagged_adts());
+    let mss = MediaSourceStream::new(Box::new(data), Default::default());
+
+    let mut reader = AdtsReader::try_probe_new(mss, Default::default()).unwrap();
+
+    assert_title(reader.as_mut(), "Tagged");
+    assert_eq!(count_packets(reader.as_mut()), 4);
+}
+
+#[test]
+fn read_malformed_id3v2_tagged_adts() {
+    // A frame with a size of 0 is malformed.
+    let mut data = make_id3v2_tag("Tagged", b"TPE1\0\0\0\0\0\0");
+    data.extend_from_slice(&make_adts_frames(4));
+
+    let open = |strict| {
+        let mss = MediaSourceStream::new(
+            Box::new(std::io::Cursor::new(data.clone())),
+            Default::default(),
+        );
+        AdtsReader::try_new(mss, FormatOptions::default().strict(strict))
+    };
+
+    // The malformed frame is skipped in lenient mode, but is an error in strict mode.
+    let mut reader = open(false).unwrap();
+    assert_title(&mut reader, "Tagged");
+    assert_eq!(count_packets(&mut reader), 4);
+
+    assert!(open(true).is_err());
+}
+
+#[test]
+fn read_adts_max_frames_per_packet() {
+    let data = std::io::Cursor::new(make_adts_frames(1));
+    let mss = MediaSourceStream::new(Box::new(data), Default::default());
+
+    let reader = AdtsReader::try_probe_new(mss, Default::default()).unwrap();
+
+    // Each ADTS frame contains one AAC access unit of 1024 frames.
+    let params = reader.tracks()[0].codec_params.as_ref().and_then(|params| params.audio());
+    assert_eq!(params.and_then(|params| params.max_frames_per_packet), Some(1024));
+}
+
+#[test]
+fn read_adts_packet_num_frames() {
+    let data = std::io::Cursor::new(make_adts_frames(4));
+    let mss = MediaSourceStream::new(Box::new(data), Default::default());
+
+    let mut reader = AdtsReader::try_probe_new(mss, Default::default()).unwrap();
+
+    let mut num_packets = 0;
+
+    while let Some(packet) = reader.next_packet().unwrap() {
+        assert_eq!(packet.num_frames, Some(1024));
+        num_packets += 1;
+    }
+
+    assert_eq!(num_packets, 4);
+}
@@ -0,0 +1,143 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use symphonia_core::errors::{Result, decode_error};
+
+/// An iterator over the open bitstream units (OBUs) of an AV1 low overhead bitstream, as is the
+/// case for ISO/MP4 and Matroska samples.
+///
+/// Each item is the OBU type, and the payload of the OBU following the OBU header and size.
+pub struct ObuIter<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> ObuIter<'a> {
+    /// Instantiate an OBU iterator over the provided buffer.
+    pub fn new(buf: &'a [u8]) -> Self {
+        ObuIter { buf }
+    }
+
+    /// Get the next OBU.
+    fn next_obu(&mut self) -> Result<(u8, &'a [u8])> {
+        let Some((&header, mut rest)) = self.buf.split_first()
+        else {
+            return decode_error("common (av1): truncated obu header");
+        };
+
+        if header & 0x80 != 0 {
+            return decode_error("common (av1): obu forbidden bit is set");
+        }
+
+        let obu_type = (header >> 3) & 0xf;
+        let has_extension = header & 0x4 != 0;
+        let has_size_field = header & 0x2 != 0;
+
+        // Skip the extension header.
+        if has_extension {
+            let Some((_, after)) = rest.split_first()
+            else {
+                return decode_error("common (av1): truncated obu extension header");
+            };
+            rest = after;
+        }
+
+        // Without a size field, the OBU extends to the end of the buffer.
+        let size = if has_size_field {
+            let (size, len) = read_leb128(rest)?;
+            rest = &rest[len..];
+            size
+        }
+        else {
+            rest.len()
+        };
+
+        let Some((payload, rest)) = rest.split_at_checked(size)
+        else {
+            return decode_error("common (av1): truncated obu");
+        };
+
+        self.buf = rest;
+        Ok((obu_type, payload))
+    }
+}
+
+impl<'a> Iterator for ObuIter<'a> {
+    type Item = Result<(u8, &'a [u8])>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buf.is_empty() {
+            return None;
+        }
+
+        let obu = self.next_obu();
+
+        // Stop iterating after an error.
+        if obu.is_err() {
+            self.buf = &[];
+        }
+
+        Some(obu)
+    }
+}
+
+/// Read an unsigned LEB128 value of at most 8 bytes. Returns the value and the number of bytes
+/// read.
+fn read_leb128(buf: &[u8]) -> Result<(usize, usize)> {
+    let mut value = 0u64;
+
+    for (i, &byte) in buf.iter().take(8).enumerate() {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+
+        if byte & 0x80 == 0 {
+            return match usize::try_from(value) {
+                Ok(value) => Ok((value, i + 1)),
+                Err(_) => decode_error("common (av1): obu size is too large"),
+            };
+        }
+    }
+
+    decode_error("common (av1): invalid leb128 value")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_obu_iter() {
+        // A temporal delimiter, a sequence header with a 2-byte size, a frame with an extension
+        // header, and a padding OBU without a size field.
+        let buf = [
+            0x12, 0x00, //
+            0x0a, 0x82, 0x00, 0xaa, 0xbb, //
+            0x36, 0x00, 0x03, 0x01, 0x02, 0x03, //
+            0x78, 0xff, 0xff,
+        ];
+
+        let obus = ObuIter::new(&buf).map(Result::unwrap).collect::<Vec<_>>();
+
+        assert_eq!(
+            obus,
+            [
+                (2, [].as_slice()),
+                (1, [0xaa, 0xbb].as_slice()),
+                (6, [0x01, 0x02, 0x03].as_slice()),
+                (15, [0xff, 0xff].as_slice()),
+            ]
+        );
+
+        // A truncated OBU is an error, after which iteration stops.
+        let mut iter = ObuIter::new(&buf[..5]);
+
+        assert!(iter.next().unwrap().is_ok());
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+
+        // The forbidden bit must not be set.
+        assert!(ObuIter::new(&[0x92, 0x00]).next().unwrap().is_err());
+    }
+}
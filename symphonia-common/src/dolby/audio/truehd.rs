@@ -0,0 +1,131 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use symphonia_core::audio::{Channels, Position};
+use symphonia_core::errors::{Result, decode_error, unsupported_error};
+use symphonia_core::io::{BufReader, ReadBytes};
+
+/// The format sync word of a TrueHD major sync.
+const TRUEHD_FORMAT_SYNC: u32 = 0xf872_6fba;
+
+/// The format sync word of a MLP major sync.
+const MLP_FORMAT_SYNC: u32 = 0xf872_6fbb;
+
+/// The signature following the format information of a major sync.
+const MAJOR_SYNC_SIGNATURE: u16 = 0xb752;
+
+/// The channel positions signalled by each bit of a TrueHD channel assignment.
+const CHANNEL_ASSIGNMENT_POSITIONS: [Position; 13] = [
+    Position::FRONT_LEFT.union(Position::FRONT_RIGHT),
+    Position::FRONT_CENTER,
+    Position::LFE1,
+    Position::SIDE_LEFT.union(Position::SIDE_RIGHT),
+    Position::TOP_FRONT_LEFT.union(Position::TOP_FRONT_RIGHT),
+    Position::FRONT_LEFT_CENTER.union(Position::FRONT_RIGHT_CENTER),
+    Position::REAR_LEFT.union(Position::REAR_RIGHT),
+    Position::REAR_CENTER,
+    Position::TOP_CENTER,
+    // Surround direct left and right.
+    Position::SIDE_LEFT.union(Position::SIDE_RIGHT),
+    Position::FRONT_LEFT_WIDE.union(Position::FRONT_RIGHT_WIDE),
+    Position::TOP_FRONT_CENTER,
+    Position::LFE2,
+];
+
+/// The TrueHD stream format information (format_info).
+///
+/// The format information is stored in the major sync of a TrueHD access unit, and is repeated in
+/// the MLP specific box of a MP4 sample entry.
+#[derive(Debug, Clone)]
+pub struct FormatInfo {
+    /// The sample rate.
+    pub sample_rate: u32,
+    /// The channels of the largest presentation with a defined channel assignment.
+    pub channels: Channels,
+}
+
+impl FormatInfo {
+    /// Get the TrueHD format information from its packed representation.
+    pub fn from_u32(format_info: u32) -> Result<FormatInfo> {
+        let sample_rate = match format_info >> 28 {
+            rate_bits @ 0..=2 => 48000 << rate_bits,
+            rate_bits @ 8..=10 => 44100 << (rate_bits & 0x7),
+            _ => return decode_error("common (truehd): invalid sample rate"),
+        };
+
+        // Prefer the 8-channel presentation, and fallback to the 6-channel presentation.
+        let assignment_8ch = format_info & 0x1fff;
+        let assignment_6ch = (format_info >> 15) & 0x1f;
+
+        let assignment = if assignment_8ch != 0 { assignment_8ch } else { assignment_6ch };
+
+        let positions = CHANNEL_ASSIGNMENT_POSITIONS
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| assignment & (1 << i) != 0)
+            .fold(Position::empty(), |acc, (_, &pos)| acc | pos);
+
+        if positions.is_empty() {
+            return decode_error("common (truehd): invalid channel assignment");
+        }
+
+        Ok(FormatInfo { sample_rate, channels: Channels::Positioned(positions) })
+    }
+}
+
+/// The major sync of a TrueHD access unit.
+#[derive(Debug, Clone)]
+pub struct MajorSync {
+    /// The stream format information.
+    pub format_info: FormatInfo,
+    /// If true, the stream is variable rate.
+    pub is_vbr: bool,
+    /// The peak data rate.
+    pub peak_data_rate: u16,
+    /// The number of substreams.
+    pub num_substreams: u8,
+    /// If true, a 16-channel presentation is present. This presentation carries the objects of
+    /// Dolby Atmos.
+    pub has_16ch_presentation: bool,
+}
+
+impl MajorSync {
+    /// Read the major sync from the start of the provided TrueHD access unit.
+    pub fn read(buf: &[u8]) -> Result<MajorSync> {
+        let mut reader = BufReader::new(buf);
+
+        // Skip the check nibble, access unit length, and input timing.
+        reader.ignore_bytes(4)?;
+
+        match reader.read_be_u32()? {
+            TRUEHD_FORMAT_SYNC => (),
+            MLP_FORMAT_SYNC => return unsupported_error("common (truehd): mlp is not supported"),
+            _ => return decode_error("common (truehd): missing major sync"),
+        }
+
+        let format_info = FormatInfo::from_u32(reader.read_be_u32()?)?;
+
+        if reader.read_be_u16()? != MAJOR_SYNC_SIGNATURE {
+            return decode_error("common (truehd): invalid major sync signature");
+        }
+
+        // Skip the flags and a reserved field.
+        reader.ignore_bytes(4)?;
+
+        let data_rate = reader.read_be_u16()?;
+        let num_substreams = reader.read_u8()? >> 4;
+        let substream_info = reader.read_u8()?;
+
+        Ok(MajorSync {
+            format_info,
+            is_vbr: data_rate & 0x8000 != 0,
+            peak_data_rate: data_rate & 0x7fff,
+            num_substreams,
+            has_16ch_presentation: substream_info & 0x80 != 0,
+        })
+    }
+}
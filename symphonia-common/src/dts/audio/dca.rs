@@ -0,0 +1,761 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::ops::Range;
+use symphonia_core::audio::{Channels, Position};
+use symphonia_core::codecs::CodecProfile;
+use symphonia_core::codecs::audio::well_known::profiles::{
+    CODEC_PROFILE_DTS, CODEC_PROFILE_DTS_96_24, CODEC_PROFILE_DTS_ES, CODEC_PROFILE_DTS_EXPRESS,
+    CODEC_PROFILE_DTS_HD_HRA, CODEC_PROFILE_DTS_HD_MA, CODEC_PROFILE_DTS_HD_MA_X,
+    CODEC_PROFILE_DTS_HD_MA_X_IMAX,
+};
+
+use symphonia_core::errors::{Result, decode_error, unsupported_error};
+use symphonia_core::io::{BitReaderLtr, ReadBitsLtr};
+
+/// The sync word of a core frame (16-bit big-endian).
+const SYNC_CORE: u32 = 0x7ffe_8001;
+/// The sync word of an extension substream.
+const SYNC_SUBSTREAM: u32 = 0x6458_2025;
+/// The sync word of the DTS:X extension of the lossless extension.
+const SYNC_XLL_X: u32 = 0x0200_0850;
+/// The sync word of the IMAX Enhanced DTS:X extension of the lossless extension.
+const SYNC_XLL_X_IMAX: u32 = 0xf140_00d0;
+
+/// The core coding component of an audio asset.
+const EXSS_CORE: u32 = 0x010;
+/// The extended bit-rate (XBR) coding component of an audio asset.
+const EXSS_XBR: u32 = 0x020;
+/// The extended channels (XXCH) coding component of an audio asset.
+const EXSS_XXCH: u32 = 0x040;
+/// The extended sample rate (X96) coding component of an audio asset.
+const EXSS_X96: u32 = 0x080;
+/// The low bit-rate (LBR) coding component of an audio asset.
+const EXSS_LBR: u32 = 0x100;
+/// The lossless (XLL) coding component of an audio asset.
+const EXSS_XLL: u32 = 0x200;
+
+/// The core extension audio identifier of the extended sample rate (X96) extension.
+const EXT_AUDIO_ID_X96: u32 = 2;
+
+/// Core sample rates indexed by the sample rate code (SFREQ).
+const CORE_SAMPLE_RATES: [u32; 16] =
+    [0, 8000, 16000, 32000, 0, 0, 11025, 22050, 44100, 0, 0, 12000, 24000, 48000, 0, 0];
+
+/// Extension substream sample rates indexed by the maximum sample rate code of an audio asset.
+const EXSS_SAMPLE_RATES: [u32; 16] = [
+    8000, 16000, 32000, 64000, 128000, 22050, 44100, 88200, 176400, 352800, 12000, 24000, 48000,
+    96000, 192000, 384000,
+];
+
+/// Speaker positions indexed by the bit of a loudspeaker activity mask.
+const SPEAKER_POSITIONS: [Position; 16] = [
+    Position::FRONT_CENTER,
+    Position::FRONT_LEFT.union(Position::FRONT_RIGHT),
+    Position::SIDE_LEFT.union(Position::SIDE_RIGHT),
+    Position::LFE1,
+    Position::REAR_CENTER,
+    Position::TOP_FRONT_LEFT.union(Position::TOP_FRONT_RIGHT),
+    Position::REAR_LEFT.union(Position::REAR_RIGHT),
+    Position::TOP_FRONT_CENTER,
+    Position::TOP_CENTER,
+    Position::FRONT_LEFT_CENTER.union(Position::FRONT_RIGHT_CENTER),
+    Position::FRONT_LEFT_WIDE.union(Position::FRONT_RIGHT_WIDE),
+    Position::SIDE_LEFT.union(Position::SIDE_RIGHT),
+    Position::LFE2,
+    Position::TOP_SIDE_LEFT.union(Position::TOP_SIDE_RIGHT),
+    Position::TOP_REAR_CENTER,
+    Position::TOP_REAR_LEFT.union(Position::TOP_REAR_RIGHT),
+];
+
+/// The DTS profile of a stream, determined by the extensions present.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DtsProfile {
+    /// DTS core audio only.
+    Core,
+    /// DTS-ES: core audio with the extended channels (XCh or XXCH) extension.
+    Es,
+    /// DTS 96/24: core audio with the extended sample rate (X96) extension.
+    Core96_24,
+    /// DTS-HD High Resolution Audio: an extension substream with lossy extensions.
+    HdHra,
+    /// DTS-HD Master Audio: an extension substream with the lossless (XLL) extension.
+    HdMa,
+    /// DTS:X: DTS-HD Master Audio with object-based audio.
+    HdMaX,
+    /// IMAX Enhanced DTS:X: DTS-HD Master Audio with IMAX Enhanced object-based audio.
+    HdMaXImax,
+    /// DTS Express: an extension substream with the low bit-rate (LBR) extension.
+    Express,
+}
+
+impl From<DtsProfile> for CodecProfile {
+    fn from(profile: DtsProfile) -> Self {
+        match profile {
+            DtsProfile::Core => CODEC_PROFILE_DTS,
+            DtsProfile::Es => CODEC_PROFILE_DTS_ES,
+            DtsProfile::Core96_24 => CODEC_PROFILE_DTS_96_24,
+            DtsProfile::HdHra => CODEC_PROFILE_DTS_HD_HRA,
+            DtsProfile::HdMa => CODEC_PROFILE_DTS_HD_MA,
+            DtsProfile::HdMaX => CODEC_PROFILE_DTS_HD_MA_X,
+            DtsProfile::HdMaXImax => CODEC_PROFILE_DTS_HD_MA_X_IMAX,
+            DtsProfile::Express => CODEC_PROFILE_DTS_EXPRESS,
+        }
+    }
+}
+
+/// Information about a DTS stream read from a single frame.
+#[derive(Debug, Clone)]
+pub struct FrameInfo {
+    /// The sample rate. The maximum sample rate of the primary audio asset of the extension
+    /// substream takes precedence over the sample rate of the core audio.
+    pub sample_rate: Option<u32>,
+    /// The channels. The loudspeaker layout of the primary audio asset of the extension substream
+    /// takes precedence over the channels of the core audio.
+    pub channels: Option<Channels>,
+    /// The profile, determined by the extensions present in the frame.
+    pub profile: DtsProfile,
+}
+
+impl FrameInfo {
+    /// Read the frame information from a complete DTS frame, consisting of an optional core frame
+    /// followed by an optional extension substream.
+    pub fn read(buf: &[u8]) -> Result<FrameInfo> {
+        let mut info = match read_be_u32(buf) {
+            Some(SYNC_CORE) => read_core_header(buf)?,
+            Some(SYNC_SUBSTREAM) => {
+                FrameInfo { sample_rate: None, channels: None, profile: DtsProfile::Core }
+            }
+            _ => return decode_error("common (dca): missing sync word"),
+        };
+
+        // The extension substream, if present, follows the core frame.
+        let substream = match info.channels {
+            Some(_) => &buf[core_frame_size(buf)?.min(buf.len())..],
+            None => buf,
+        };
+
+        if read_be_u32(substream) == Some(SYNC_SUBSTREAM) {
+            let substream = &substream[..substream_size(substream)?.min(substream.len())];
+
+            let asset = read_primary_asset(substream)?;
+
+            if let Some(profile) = asset.profile(substream) {
+                info.profile = profile;
+            }
+
+            if let Some(sample_rate) = asset.sample_rate {
+                info.sample_rate = Some(sample_rate);
+            }
+
+            if let Some(channels) = asset.channels {
+                info.channels = Some(channels);
+            }
+        }
+        else if info.channels.is_none() {
+            return decode_error("common (dca): missing core audio and extension substream");
+        }
+
+        Ok(info)
+    }
+}
+
+fn read_be_u32(buf: &[u8]) -> Option<u32> {
+    Some(u32::from_be_bytes(buf.get(..4)?.try_into().unwrap()))
+}
+
+/// Get the size of the core frame in bytes.
+fn core_frame_size(buf: &[u8]) -> Result<usize> {
+    let mut bs = BitReaderLtr::new(buf);
+
+    // Sync word, frame type, deficit sample count, CRC present flag, and number of blocks.
+    bs.ignore_bits(32 + 1 + 5 + 1 + 7)?;
+
+    Ok(bs.read_bits_leq32(14)? as usize + 1)
+}
+
+/// Read the core frame header.
+fn read_core_header(buf: &[u8]) -> Result<FrameInfo> {
+    let mut bs = BitReaderLtr::new(buf);
+
+    // Sync word, frame type, deficit sample count, CRC present flag, number of blocks, and frame
+    // size.
+    bs.ignore_bits(32 + 1 + 5 + 1 + 7 + 14)?;
+
+    let amode = bs.read_bits_leq32(6)?;
+    let sfreq = bs.read_bits_leq32(4)?;
+
+    // Bit rate, a fixed bit, the embedded dynamic range, time stamp, and auxiliary data flags, and
+    // the HDCD flag.
+    bs.ignore_bits(5 + 1 + 1 + 1 + 1 + 1)?;
+
+    let ext_audio_id = bs.read_bits_leq32(3)?;
+    let ext_audio = bs.read_bool()?;
+
+    // Audio sync word insertion flag.
+    bs.ignore_bits(1)?;
+
+    let lff = bs.read_bits_leq32(2)?;
+
+    let sample_rate = match CORE_SAMPLE_RATES[sfreq as usize] {
+        0 => return decode_error("common (dca): invalid core sample rate"),
+        sample_rate => sample_rate,
+    };
+
+    let positions = match amode {
+        0 => Position::FRONT_CENTER,
+        // Dual mono, stereo, sum-difference stereo, and total stereo.
+        1..=4 => Position::FRONT_LEFT | Position::FRONT_RIGHT,
+        5 => Position::FRONT_LEFT | Position::FRONT_CENTER | Position::FRONT_RIGHT,
+        6 => Position::FRONT_LEFT | Position::FRONT_RIGHT | Position::REAR_CENTER,
+        7 => {
+            Position::FRONT_LEFT
+                | Position::FRONT_CENTER
+                | Position::FRONT_RIGHT
+                | Position::REAR_CENTER
+        }
+        8 => {
+            Position::FRONT_LEFT
+                | Position::FRONT_RIGHT
+                | Position::SIDE_LEFT
+                | Position::SIDE_RIGHT
+        }
+        9 => {
+            Position::FRONT_LEFT
+                | Position::FRONT_CENTER
+                | Position::FRONT_RIGHT
+                | Position::SIDE_LEFT
+                | Position::SIDE_RIGHT
+        }
+        _ => return unsupported_error("common (dca): unsupported core audio channel arrangement"),
+    };
+
+    let positions = if lff != 0 { positions | Position::LFE1 } else { positions };
+
+    let profile = match (ext_audio, ext_audio_id) {
+        (false, _) => DtsProfile::Core,
+        (true, EXT_AUDIO_ID_X96) => DtsProfile::Core96_24,
+        // The extended channels (XCh or XXCH) extensions.
+        (true, _) => DtsProfile::Es,
+    };
+
+    Ok(FrameInfo {
+        sample_rate: Some(sample_rate),
+        channels: Some(Channels::Positioned(positions)),
+        profile,
+    })
+}
+
+/// Get the size of the extension substream in bytes.
+fn substream_size(buf: &[u8]) -> Result<usize> {
+    let mut bs = BitReaderLtr::new(buf);
+
+    // Sync word, user defined bits, and extension substream index.
+    bs.ignore_bits(32 + 8 + 2)?;
+
+    let size = if bs.read_bool()? {
+        bs.ignore_bits(12)?;
+        bs.read_bits_leq32(20)?
+    }
+    else {
+        bs.ignore_bits(8)?;
+        bs.read_bits_leq32(16)?
+    };
+
+    Ok(size as usize + 1)
+}
+
+/// The primary audio asset of an extension substream.
+struct Asset {
+    /// The maximum sample rate, if signalled.
+    sample_rate: Option<u32>,
+    /// The loudspeaker layout, if signalled.
+    channels: Option<Channels>,
+    /// The coding components present in the asset.
+    ext_mask: u32,
+    /// The position of the lossless (XLL) component in the extension substream, if present.
+    xll: Option<Range<usize>>,
+}
+
+impl Asset {
+    /// Get the profile signalled by the coding components of the asset.
+    fn profile(&self, substream: &[u8]) -> Option<DtsProfile> {
+        if self.ext_mask & EXSS_XLL != 0 {
+            // DTS:X is signalled by an extension of the lossless component.
+            let xll = self
+                .xll
+                .as_ref()
+                .and_then(|xll| substream.get(xll.start..xll.end.min(substream.len())))
+                .unwrap_or_default();
+
+            let has_sync = |sync: u32| {
+                xll.windows(4).any(|word| u32::from_be_bytes(word.try_into().unwrap()) == sync)
+            };
+
+            let profile = if has_sync(SYNC_XLL_X_IMAX) {
+                DtsProfile::HdMaXImax
+            }
+            else if has_sync(SYNC_XLL_X) {
+                DtsProfile::HdMaX
+            }
+            else {
+                DtsProfile::HdMa
+            };
+
+            Some(profile)
+        }
+        else if self.ext_mask & (EXSS_XBR | EXSS_XXCH | EXSS_X96) != 0 {
+            Some(DtsProfile::HdHra)
+        }
+        else if self.ext_mask & EXSS_LBR != 0 {
+            Some(DtsProfile::Express)
+        }
+        else {
+            None
+        }
+    }
+}
+
+/// Count the channels of a loudspeaker activity mask. Some bits of the mask represent a pair of
+/// speakers.
+fn count_speakers(mask: u32) -> u32 {
+    mask.count_ones() + (mask & 0xae66).count_ones()
+}
+
+/// Read the extension substream header and the descriptor of the primary audio asset.
+fn read_primary_asset(substream: &[u8]) -> Result<Asset> {
+    let mut bs = BitReaderLtr::new(substream);
+
+    // Sync word and user defined bits.
+    bs.ignore_bits(32 + 8)?;
+
+    let exss_index = bs.read_bits_leq32(2)?;
+    let is_wide_header = bs.read_bool()?;
+
+    let header_size = bs.read_bits_leq32(if is_wide_header { 12 } else { 8 })? as usize + 1;
+    let size_bits = if is_wide_header { 20 } else { 16 };
+
+    // Extension substream size.
+    bs.ignore_bits(size_bits)?;
+
+    let has_static_fields = bs.read_bool()?;
+
+    let mut num_assets = 1;
+    let mut mix_out_channels = Vec::new();
+
+    if has_static_fields {
+        // Reference clock code and frame duration.
+        bs.ignore_bits(2 + 3)?;
+
+        // Timecode.
+        if bs.read_bool()? {
+            bs.ignore_bits(36)?;
+        }
+
+        let num_presentations = bs.read_bits_leq32(3)? + 1;
+        num_assets = bs.read_bits_leq32(3)? + 1;
+
+        let active_masks = (0..num_presentations)
+            .map(|_| bs.read_bits_leq32(exss_index + 1))
+            .collect::<std::io::Result<Vec<_>>>()?;
+
+        // The active asset masks of each active extension substream.
+        for mask in active_masks {
+            bs.ignore_bits(8 * mask.count_ones())?;
+        }
+
+        // Mixing metadata.
+        if bs.read_bool()? {
+            // Mixing metadata adjustment level.
+            bs.ignore_bits(2)?;
+
+            let mask_bits = (bs.read_bits_leq32(2)? + 1) << 2;
+            let num_configs = bs.read_bits_leq32(2)? + 1;
+
+            for _ in 0..num_configs {
+                mix_out_channels.push(count_speakers(bs.read_bits_leq32(mask_bits)?));
+            }
+        }
+    }
+
+    let first_asset_size = bs.read_bits_leq32(size_bits)? as usize + 1;
+
+    // The sizes of the remaining assets.
+    bs.ignore_bits(size_bits * (num_assets - 1))?;
+
+    // Size of the audio asset descriptor, and the asset index.
+    bs.ignore_bits(9 + 3)?;
+
+    let mut asset = Asset { sample_rate: None, channels: None, ext_mask: 0, xll: None };
+
+    let mut num_channels = 0;
+    let mut has_embedded_stereo = false;
+    let mut has_embedded_6ch = false;
+
+    if has_static_fields {
+        // Asset type descriptor.
+        if bs.read_bool()? {
+            bs.ignore_bits(4)?;
+        }
+
+        // Language descriptor.
+        if bs.read_bool()? {
+            bs.ignore_bits(24)?;
+        }
+
+        // Additional textual information.
+        if bs.read_bool()? {
+            let text_size = bs.read_bits_leq32(10)? + 1;
+            bs.ignore_bits(8 * text_size)?;
+        }
+
+        // PCM bit resolution.
+        bs.ignore_bits(5)?;
+
+        asset.sample_rate = Some(EXSS_SAMPLE_RATES[bs.read_bits_leq32(4)? as usize]);
+        num_channels = bs.read_bits_leq32(8)? + 1;
+
+        // Channels are mapped one-to-one to speakers.
+        if bs.read_bool()? {
+            has_embedded_stereo = num_channels > 2 && bs.read_bool()?;
+            has_embedded_6ch = num_channels > 6 && bs.read_bool()?;
+
+            let mut mask_bits = 0;
+
+            if bs.read_bool()? {
+                mask_bits = (bs.read_bits_leq32(2)? + 1) << 2;
+
+                let mask = bs.read_bits_leq32(mask_bits)?;
+
+                let positions = SPEAKER_POSITIONS
+                    .iter()
+                    .enumerate()
+                    .filter(|&(bit, _)| mask & (1 << bit) != 0)
+                    .fold(Position::empty(), |positions, (_, &pos)| positions | pos);
+
+                if !positions.is_empty() {
+                    asset.channels = Some(Channels::Positioned(positions));
+                }
+            }
+
+            let num_remap_sets = bs.read_bits_leq32(3)?;
+
+            if num_remap_sets > 0 && mask_bits == 0 {
+                return decode_error("common (dca): speaker remapping without a speaker mask");
+            }
+
+            let num_speakers = (0..num_remap_sets)
+                .map(|_| bs.read_bits_leq32(mask_bits).map(count_speakers))
+                .collect::<std::io::Result<Vec<_>>>()?;
+
+            for num_speakers in num_speakers {
+                let num_remap_channels = bs.read_bits_leq32(5)? + 1;
+
+                for _ in 0..num_speakers {
+                    // The decoded channels to speaker mapping mask, and the remapping codes.
+                    let remap_mask = bs.read_bits_leq32(num_remap_channels)?;
+                    bs.ignore_bits(5 * remap_mask.count_ones())?;
+                }
+            }
+        }
+        else {
+            // Representation type.
+            bs.ignore_bits(3)?;
+        }
+    }
+
+    // Dynamic range coefficient.
+    let has_drc = bs.read_bool()?;
+
+    if has_drc {
+        bs.ignore_bits(8)?;
+    }
+
+    // Dialog normalization.
+    if bs.read_bool()? {
+        bs.ignore_bits(5)?;
+    }
+
+    // Dynamic range coefficient for the stereo downmix.
+    if has_drc && has_embedded_stereo {
+        bs.ignore_bits(8)?;
+    }
+
+    // Mixing metadata.
+    if !mix_out_channels.is_empty() && bs.read_bool()? {
+        // External mixing flag, and post-mixing gain adjustment.
+        bs.ignore_bits(1 + 6)?;
+
+        // Mixing dynamic range control.
+        if bs.read_bits_leq32(2)? == 3 {
+            bs.ignore_bits(8)?;
+        }
+        else {
+            bs.ignore_bits(3)?;
+        }
+
+        // Mixing scale factors, either per channel of each mixing configuration, or per mixing
+        // configuration.
+        if bs.read_bool()? {
+            bs.ignore_bits(6 * mix_out_channels.iter().sum::<u32>())?;
+        }
+        else {
+            bs.ignore_bits(6 * mix_out_channels.len() as u32)?;
+        }
+
+        let num_downmix_channels = num_channels
+            + if has_embedded_6ch { 6 } else { 0 }
+            + if has_embedded_stereo { 2 } else { 0 };
+
+        for &num_out_channels in &mix_out_channels {
+            for _ in 0..num_downmix_channels {
+                // The mixing map mask, and the mixing coefficients.
+                let mix_mask = bs.read_bits_leq32(num_out_channels)?;
+                bs.ignore_bits(6 * mix_mask.count_ones())?;
+            }
+        }
+    }
+
+    // The sizes of the coding components preceding the lossless component.
+    let mut xll_offset = header_size;
+
+    let xll_size = match bs.read_bits_leq32(2)? {
+        0 => {
+            asset.ext_mask = bs.read_bits_leq32(12)?;
+
+            if asset.ext_mask & EXSS_CORE != 0 {
+                xll_offset += bs.read_bits_leq32(14)? as usize + 1;
+
+                // Core sync distance.
+                if bs.read_bool()? {
+                    bs.ignore_bits(2)?;
+                }
+            }
+
+            if asset.ext_mask & EXSS_XBR != 0 {
+                xll_offset += bs.read_bits_leq32(14)? as usize + 1;
+            }
+
+            if asset.ext_mask & EXSS_XXCH != 0 {
+                xll_offset += bs.read_bits_leq32(14)? as usize + 1;
+            }
+
+            if asset.ext_mask & EXSS_X96 != 0 {
+                xll_offset += bs.read_bits_leq32(12)? as usize + 1;
+            }
+
+            if asset.ext_mask & EXSS_LBR != 0 {
+                xll_offset += bs.read_bits_leq32(14)? as usize + 1;
+            }
+
+            match asset.ext_mask & EXSS_XLL {
+                0 => None,
+                _ => Some(bs.read_bits_leq32(size_bits)? as usize + 1),
+            }
+        }
+        // Lossless coding mode.
+        1 => {
+            asset.ext_mask = EXSS_XLL;
+            Some(bs.read_bits_leq32(size_bits)? as usize + 1)
+        }
+        // Low bit-rate coding mode.
+        2 => {
+            asset.ext_mask = EXSS_LBR;
+            None
+        }
+        // Auxiliary coding mode.
+        _ => None,
+    };
+
+    if let Some(xll_size) = xll_size {
+        asset.xll = Some(xll_offset..(xll_offset + xll_size).min(header_size + first_asset_size));
+    }
+
+    Ok(asset)
+}
+
+#[cfg(test)]
+mod tests {
+    use symphonia_core::audio::{Channels, Position, layouts};
+
+    use super::{DtsProfile, FrameInfo};
+
+    /// A 64 byte 48 kHz 5.1 core frame.
+    fn core_frame() -> Vec<u8> {
+        let mut frame = vec![0x7f, 0xfe, 0x80, 0x01, 0xfc, 0x3c, 0x03, 0xf2, 0x75, 0xe0, 0x02];
+        frame.resize(64, 0);
+        frame
+    }
+
+    /// Write fields of the given widths, most-significant bit first.
+    fn write_bits(fields: &[(u32, u32)]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        let mut pos = 0;
+
+        for &(value, width) in fields {
+            for bit in (0..width).rev() {
+                if pos % 8 == 0 {
+                    buf.push(0);
+                }
+
+                buf[pos / 8] |= (((value >> bit) & 1) as u8) << (7 - pos % 8);
+                pos += 1;
+            }
+        }
+
+        buf
+    }
+
+    /// Build an extension substream with a single 48 kHz audio asset with the given loudspeaker
+    /// mask. The descriptor of the asset ends with the given coding mode fields, and the asset data
+    /// is the given coding components.
+    fn make_substream(speaker_mask: u32, coding: &[(u32, u32)], components: &[u8]) -> Vec<u8> {
+        let header = |header_size: u32| {
+            let num_channels = super::count_speakers(speaker_mask);
+
+            let mut fields = vec![
+                // Sync word, user defined bits, substream index, and narrow header flag.
+                (0x6458_2025, 32),
+                (0, 8),
+                (0, 2),
+                (0, 1),
+                // Header size and substream size.
+                (header_size - 1, 8),
+                (header_size + components.len() as u32 - 1, 16),
+                // Static fields present, reference clock, frame duration, and no timecode.
+                (1, 1),
+                (0, 2),
+                (0, 3),
+                (0, 1),
+                // One presentation, one asset, and the active substream mask.
+                (0, 3),
+                (0, 3),
+                (1, 1),
+                (0, 8),
+                // No mixing metadata.
+                (0, 1),
+                // Asset size.
+                (components.len() as u32 - 1, 16),
+                // Descriptor size and asset index.
+                (0, 9),
+                (0, 3),
+                // No type, language, or text descriptors.
+                (0, 3),
+                // 24-bit, 48 kHz.
+                (23, 5),
+                (12, 4),
+                // Number of channels, and one-to-one channel to speaker mapping.
+                (num_channels - 1, 8),
+                (1, 1),
+            ];
+
+            // No embedded stereo and 6-channel downmixes.
+            if num_channels > 2 {
+                fields.push((0, 1));
+            }
+
+            if num_channels > 6 {
+                fields.push((0, 1));
+            }
+
+            fields.extend_from_slice(&[
+                // A 16-bit loudspeaker mask, and no remapping sets.
+                (1, 1),
+                (3, 2),
+                (speaker_mask, 16),
+                (0, 3),
+                // No dynamic range control or dialog normalization.
+                (0, 1),
+                (0, 1),
+            ]);
+
+            fields.extend_from_slice(coding);
+            write_bits(&fields)
+        };
+
+        let header_size = header(1).len() as u32;
+
+        [header(header_size), components.to_vec()].concat()
+    }
+
+    /// Build a lossless (XLL) coding component, optionally with the DTS:X sync word.
+    fn make_xll(has_x: bool) -> Vec<u8> {
+        let mut xll = vec![0x41, 0xa2, 0x95, 0x47];
+        xll.resize(16, 0);
+
+        if has_x {
+            xll.extend_from_slice(&[0x02, 0x00, 0x08, 0x50]);
+        }
+
+        xll.resize(32, 0);
+        xll
+    }
+
+    #[test]
+    fn verify_core_frame_info() {
+        let info = FrameInfo::read(&core_frame()).unwrap();
+
+        assert_eq!(info.profile, DtsProfile::Core);
+        assert_eq!(info.sample_rate, Some(48000));
+        assert_eq!(info.channels, Some(layouts::CHANNEL_LAYOUT_5P1_SIDE));
+    }
+
+    #[test]
+    fn verify_lossless_asset() {
+        // Lossless coding mode with a 32 byte component and no sync word, and a 7.1 asset.
+        let coding = [(1, 2), (31, 16), (0, 1)];
+
+        let substream = make_substream(0x004f, &coding, &make_xll(false));
+        let info = FrameInfo::read(&[core_frame(), substream].concat()).unwrap();
+
+        assert_eq!(info.profile, DtsProfile::HdMa);
+        assert_eq!(info.sample_rate, Some(48000));
+        assert_eq!(info.channels, Some(layouts::CHANNEL_LAYOUT_7P1));
+
+        // DTS:X signalled in the lossless component.
+        let substream = make_substream(0x004f, &coding, &make_xll(true));
+        let info = FrameInfo::read(&[core_frame(), substream].concat()).unwrap();
+
+        assert_eq!(info.profile, DtsProfile::HdMaX);
+    }
+
+    #[test]
+    fn verify_component_offsets() {
+        // An XBR component followed by a lossless component, in the multiple component coding
+        // mode. The DTS:X sync word in the XBR component must not be attributed to the lossless
+        // component.
+        let coding = [(0, 2), (0x220, 12), (31, 14), (31, 16), (0, 1)];
+
+        let components = [make_xll(true), make_xll(false)].concat();
+        let info = FrameInfo::read(&make_substream(0x000f, &coding, &components)).unwrap();
+
+        assert_eq!(info.profile, DtsProfile::HdMa);
+        assert_eq!(info.channels, Some(layouts::CHANNEL_LAYOUT_5P1_SIDE));
+
+        // Lossy extensions only.
+        let coding = [(0, 2), (0x020, 12), (31, 14)];
+
+        let info = FrameInfo::read(&make_substream(0x000f, &coding, &[0; 32])).unwrap();
+
+        assert_eq!(info.profile, DtsProfile::HdHra);
+    }
+
+    #[test]
+    fn verify_low_bit_rate_asset() {
+        // Low bit-rate coding mode with a 32 byte component and no sync word, and a stereo asset.
+        let coding = [(2, 2), (31, 14), (0, 1)];
+
+        let info = FrameInfo::read(&make_substream(0x0002, &coding, &[0; 32])).unwrap();
+
+        assert_eq!(info.profile, DtsProfile::Express);
+        assert_eq!(info.sample_rate, Some(48000));
+        assert_eq!(
+            info.channels,
+            Some(Channels::Positioned(Position::FRONT_LEFT | Position::FRONT_RIGHT))
+        );
+    }
+}
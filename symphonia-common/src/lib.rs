@@ -12,6 +12,10 @@
 #![allow(clippy::identity_op)]
 #![allow(clippy::manual_range_contains)]
 
+pub mod aomedia;
 pub mod apple;
+pub mod dolby;
+pub mod dts;
+pub mod microsoft;
 pub mod mpeg;
 pub mod xiph;
@@ -0,0 +1,208 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use symphonia_core::codecs::audio::AudioCodecId;
+use symphonia_core::codecs::audio::well_known::*;
+use symphonia_core::errors::{Result, decode_error};
+use symphonia_core::io::{BufReader, ReadBytes};
+
+const WAVE_FORMAT_PCM: u16 = 0x0001;
+const WAVE_FORMAT_ADPCM: u16 = 0x0002;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 0x0003;
+const WAVE_FORMAT_ALAW: u16 = 0x0006;
+const WAVE_FORMAT_MULAW: u16 = 0x0007;
+const WAVE_FORMAT_ADPCM_IMA: u16 = 0x0011;
+const WAVE_FORMAT_MPEG: u16 = 0x0050;
+const WAVE_FORMAT_MPEGLAYER3: u16 = 0x0055;
+const WAVE_FORMAT_DOLBY_AC3_SPDIF: u16 = 0x0092;
+const WAVE_FORMAT_RAW_AAC1: u16 = 0x00ff;
+const WAVE_FORMAT_MSAUDIO1: u16 = 0x0160;
+const WAVE_FORMAT_WMAUDIO2: u16 = 0x0161;
+const WAVE_FORMAT_MPEG_ADTS_AAC: u16 = 0x1600;
+const WAVE_FORMAT_DVM: u16 = 0x2000;
+const WAVE_FORMAT_DTS: u16 = 0x2001;
+const WAVE_FORMAT_AAC_PROGRESSIVE: u16 = 0x706d;
+const WAVE_FORMAT_FLAC: u16 = 0xf1ac;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xfffe;
+
+/// The Microsoft `WAVEFORMATEX` audio format structure, as embedded by AVI `strf` chunks and
+/// Matroska `A_MS/ACM` codec private data.
+#[derive(Debug, Clone)]
+pub struct WaveFormatEx {
+    /// The format tag. For `WAVE_FORMAT_EXTENSIBLE`, this is the format tag of the sub-format.
+    pub format_tag: u16,
+    pub num_channels: u16,
+    pub sample_rate: u32,
+    pub avg_bytes_per_sec: u32,
+    pub block_align: u16,
+    pub bits_per_sample: u16,
+    /// The channel mask, if the format is `WAVE_FORMAT_EXTENSIBLE`.
+    pub channel_mask: Option<u32>,
+    /// Format-specific extra data following the structure.
+    pub extra_data: Box<[u8]>,
+}
+
+impl WaveFormatEx {
+    /// Read a `WAVEFORMATEX` structure from the provided buffer.
+    pub fn read(buf: &[u8]) -> Result<WaveFormatEx> {
+        // The original WAVEFORMAT structure is 14 bytes long, and PCMWAVEFORMAT adds the bits per
+        // sample.
+        if buf.len() < 16 {
+            return decode_error("common (waveformatex): structure size too small");
+        }
+
+        let mut reader = BufReader::new(buf);
+
+        let mut format_tag = reader.read_u16()?;
+        let num_channels = reader.read_u16()?;
+        let sample_rate = reader.read_u32()?;
+        let avg_bytes_per_sec = reader.read_u32()?;
+        let block_align = reader.read_u16()?;
+        let bits_per_sample = reader.read_u16()?;
+
+        // The size of the extra data is optional. Some muxers write a size exceeding the remaining
+        // buffer, so clamp it.
+        let extra_size = if buf.len() >= 18 { usize::from(reader.read_u16()?) } else { 0 };
+        let mut extra_data = &buf[18.min(buf.len())..];
+        extra_data = &extra_data[..extra_size.min(extra_data.len())];
+
+        let mut channel_mask = None;
+
+        if format_tag == WAVE_FORMAT_EXTENSIBLE {
+            // WAVEFORMATEXTENSIBLE appends the valid bits per sample, the channel mask, and the
+            // sub-format GUID to the structure.
+            if extra_data.len() < 22 {
+                return decode_error("common (waveformatex): extensible structure size too small");
+            }
+
+            let mut reader = BufReader::new(extra_data);
+
+            let _valid_bits_per_sample = reader.read_u16()?;
+            channel_mask = Some(reader.read_u32()?);
+
+            // The first two bytes of the sub-format GUID are the format tag.
+            format_tag = reader.read_u16()?;
+
+            extra_data = &extra_data[22..];
+        }
+
+        Ok(WaveFormatEx {
+            format_tag,
+            num_channels,
+            sample_rate,
+            avg_bytes_per_sec,
+            block_align,
+            bits_per_sample,
+            channel_mask,
+            extra_data: Box::from(extra_data),
+        })
+    }
+
+    /// Get the codec ID for the format tag, if it is known.
+    pub fn codec_id(&self) -> Option<AudioCodecId> {
+        let codec_id = match self.format_tag {
+            WAVE_FORMAT_PCM => match self.bits_per_sample {
+                8 => CODEC_ID_PCM_U8,
+                16 => CODEC_ID_PCM_S16LE,
+                24 => CODEC_ID_PCM_S24LE,
+                32 => CODEC_ID_PCM_S32LE,
+                _ => return None,
+            },
+            WAVE_FORMAT_IEEE_FLOAT => match self.bits_per_sample {
+                32 => CODEC_ID_PCM_F32LE,
+                64 => CODEC_ID_PCM_F64LE,
+                _ => return None,
+            },
+            WAVE_FORMAT_ADPCM => CODEC_ID_ADPCM_MS,
+            WAVE_FORMAT_ALAW => CODEC_ID_PCM_ALAW,
+            WAVE_FORMAT_MULAW => CODEC_ID_PCM_MULAW,
+            WAVE_FORMAT_ADPCM_IMA => CODEC_ID_ADPCM_IMA_WAV,
+            // The layer is only signalled in the bitstream. Layer 2 is by far the most common.
+            WAVE_FORMAT_MPEG => CODEC_ID_MP2,
+            WAVE_FORMAT_MPEGLAYER3 => CODEC_ID_MP3,
+            WAVE_FORMAT_RAW_AAC1 | WAVE_FORMAT_MPEG_ADTS_AAC | WAVE_FORMAT_AAC_PROGRESSIVE => {
+                CODEC_ID_AAC
+            }
+            WAVE_FORMAT_MSAUDIO1 | WAVE_FORMAT_WMAUDIO2 => CODEC_ID_WMA,
+            // WAVE_FORMAT_DVM is the format tag commonly used for AC-3 in AVI and Matroska.
+            WAVE_FORMAT_DOLBY_AC3_SPDIF | WAVE_FORMAT_DVM => CODEC_ID_AC3,
+            WAVE_FORMAT_DTS => CODEC_ID_DCA,
+            WAVE_FORMAT_FLAC => CODEC_ID_FLAC,
+            _ => return None,
+        };
+
+        Some(codec_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_wave_format_ex(format_tag: u16, bits_per_sample: u16, extra: &[u8]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&format_tag.to_le_bytes());
+        buf.extend_from_slice(&2u16.to_le_bytes());
+        buf.extend_from_slice(&44100u32.to_le_bytes());
+        buf.extend_from_slice(&176400u32.to_le_bytes());
+        buf.extend_from_slice(&4u16.to_le_bytes());
+        buf.extend_from_slice(&bits_per_sample.to_le_bytes());
+        buf.extend_from_slice(&(extra.len() as u16).to_le_bytes());
+        buf.extend_from_slice(extra);
+        buf
+    }
+
+    #[test]
+    fn verify_read_wave_format_ex() {
+        // Original 16 byte PCMWAVEFORMAT structure without an extra data size.
+        let buf = make_wave_format_ex(WAVE_FORMAT_PCM, 16, &[]);
+        let wfx = WaveFormatEx::read(&buf[..16]).unwrap();
+        assert_eq!(wfx.num_channels, 2);
+        assert_eq!(wfx.sample_rate, 44100);
+        assert_eq!(wfx.codec_id(), Some(CODEC_ID_PCM_S16LE));
+        assert!(wfx.extra_data.is_empty());
+
+        // MPEGLAYER3WAVEFORMAT with 12 bytes of extra data.
+        let buf = make_wave_format_ex(WAVE_FORMAT_MPEGLAYER3, 0, &[1; 12]);
+        let wfx = WaveFormatEx::read(&buf).unwrap();
+        assert_eq!(wfx.codec_id(), Some(CODEC_ID_MP3));
+        assert_eq!(&*wfx.extra_data, &[1; 12]);
+
+        // AC-3 is signalled by either of two format tags.
+        for format_tag in [WAVE_FORMAT_DOLBY_AC3_SPDIF, WAVE_FORMAT_DVM] {
+            let buf = make_wave_format_ex(format_tag, 0, &[]);
+            assert_eq!(WaveFormatEx::read(&buf).unwrap().codec_id(), Some(CODEC_ID_AC3));
+        }
+
+        // Unknown format tag.
+        let buf = make_wave_format_ex(0x1234, 16, &[]);
+        assert_eq!(WaveFormatEx::read(&buf).unwrap().codec_id(), None);
+    }
+
+    #[test]
+    fn verify_read_wave_format_extensible() {
+        let mut extra = Vec::new();
+        extra.extend_from_slice(&24u16.to_le_bytes());
+        extra.extend_from_slice(&0x3u32.to_le_bytes());
+        // KSDATAFORMAT_SUBTYPE_IEEE_FLOAT.
+        extra.extend_from_slice(&[
+            0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0xaa, 0x00, 0x38,
+            0x9b, 0x71,
+        ]);
+
+        let buf = make_wave_format_ex(WAVE_FORMAT_EXTENSIBLE, 32, &extra);
+        let wfx = WaveFormatEx::read(&buf).unwrap();
+        assert_eq!(wfx.format_tag, WAVE_FORMAT_IEEE_FLOAT);
+        assert_eq!(wfx.channel_mask, Some(0x3));
+        assert_eq!(wfx.codec_id(), Some(CODEC_ID_PCM_F32LE));
+        assert!(wfx.extra_data.is_empty());
+
+        // Truncated extensible structure.
+        let buf = make_wave_format_ex(WAVE_FORMAT_EXTENSIBLE, 32, &extra[..6]);
+        assert!(WaveFormatEx::read(&buf).is_err());
+    }
+}
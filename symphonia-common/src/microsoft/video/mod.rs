@@ -0,0 +1,147 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use symphonia_core::codecs::video::VideoCodecId;
+use symphonia_core::codecs::video::well_known::*;
+use symphonia_core::common::FourCc;
+use symphonia_core::errors::{Result, decode_error};
+use symphonia_core::io::{BufReader, ReadBytes};
+
+pub mod vc1;
+
+/// The Microsoft `BITMAPINFOHEADER` video format structure, as embedded by AVI `strf` chunks and
+/// Matroska `V_MS/VFW/FOURCC` codec private data.
+#[derive(Debug, Clone)]
+pub struct BitmapInfoHeader {
+    pub width: i32,
+    /// The height of the bitmap. For uncompressed bitmaps, a negative height indicates a top-down
+    /// bitmap.
+    pub height: i32,
+    pub bit_count: u16,
+    /// The compression FourCC, or 0 for uncompressed RGB.
+    pub compression: [u8; 4],
+    pub size_image: u32,
+    /// Codec-specific extra data following the structure.
+    pub extra_data: Box<[u8]>,
+}
+
+impl BitmapInfoHeader {
+    /// Read a `BITMAPINFOHEADER` structure from the provided buffer.
+    pub fn read(buf: &[u8]) -> Result<BitmapInfoHeader> {
+        if buf.len() < 40 {
+            return decode_error("common (bitmapinfoheader): structure size too small");
+        }
+
+        let mut reader = BufReader::new(buf);
+
+        // The structure size includes the size of any extended header, but not the extra data.
+        let size = reader.read_u32()? as usize;
+
+        if size < 40 || size > buf.len() {
+            return decode_error("common (bitmapinfoheader): invalid structure size");
+        }
+
+        let width = reader.read_i32()?;
+        let height = reader.read_i32()?;
+        let _planes = reader.read_u16()?;
+        let bit_count = reader.read_u16()?;
+        let compression = reader.read_quad_bytes()?;
+        let size_image = reader.read_u32()?;
+
+        Ok(BitmapInfoHeader {
+            width,
+            height,
+            bit_count,
+            compression,
+            size_image,
+            extra_data: Box::from(&buf[size..]),
+        })
+    }
+
+    /// Get the codec ID for the compression FourCC. Unknown FourCCs are converted into a codec ID
+    /// directly. Returns `None` if the bitmap is uncompressed, or the FourCC is invalid.
+    pub fn codec_id(&self) -> Option<VideoCodecId> {
+        // FourCCs are matched case-insensitively.
+        let mut compression = self.compression;
+        compression.make_ascii_uppercase();
+
+        let codec_id = match &compression {
+            b"MJPG" => CODEC_ID_MJPEG,
+            b"DIVX" | b"DX50" | b"XVID" | b"FMP4" | b"MP4V" | b"3IV2" | b"M4S2" => CODEC_ID_MPEG4,
+            b"MPG4" | b"MP41" => CODEC_ID_MSMPEG4V1,
+            b"MP42" | b"DIV2" => CODEC_ID_MSMPEG4V2,
+            b"MP43" | b"DIV3" | b"DIV4" | b"DIV5" | b"DIV6" | b"AP41" => CODEC_ID_MSMPEG4V3,
+            b"WMV1" => CODEC_ID_WMV1,
+            b"WMV2" => CODEC_ID_WMV2,
+            b"WMV3" => CODEC_ID_WMV3,
+            b"WVC1" | b"WMVA" => CODEC_ID_VC1,
+            b"H264" | b"X264" | b"AVC1" | b"DAVC" => CODEC_ID_H264,
+            b"HEVC" | b"H265" | b"HVC1" => CODEC_ID_HEVC,
+            b"H263" | b"U263" | b"S263" => CODEC_ID_H263,
+            b"FLV1" => CODEC_ID_FLV,
+            b"MPG1" => CODEC_ID_MPEG1,
+            b"MPG2" | b"MPEG" => CODEC_ID_MPEG2,
+            b"CVID" => CODEC_ID_CINEPAK,
+            b"IV31" | b"IV32" => CODEC_ID_INDEO3,
+            b"IV41" => CODEC_ID_INDEO4,
+            b"IV50" => CODEC_ID_INDEO5,
+            b"SVQ1" => CODEC_ID_SVQ1,
+            b"SVQ3" => CODEC_ID_SVQ3,
+            b"VP30" | b"VP31" => CODEC_ID_VP3,
+            b"VP50" => CODEC_ID_VP5,
+            b"VP60" | b"VP61" | b"VP62" => CODEC_ID_VP6,
+            b"VP70" => CODEC_ID_VP7,
+            b"VP80" => CODEC_ID_VP8,
+            b"VP90" => CODEC_ID_VP9,
+            b"AV01" => CODEC_ID_AV1,
+            // Uncompressed RGB.
+            [0, 0, 0, 0] => return None,
+            _ => return FourCc::try_new(self.compression).map(VideoCodecId::from),
+        };
+
+        Some(codec_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_bitmap_info_header(compression: &[u8; 4], extra: &[u8]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&40u32.to_le_bytes());
+        buf.extend_from_slice(&640i32.to_le_bytes());
+        buf.extend_from_slice(&480i32.to_le_bytes());
+        buf.extend_from_slice(&1u16.to_le_bytes());
+        buf.extend_from_slice(&24u16.to_le_bytes());
+        buf.extend_from_slice(compression);
+        buf.extend_from_slice(&[0; 20]);
+        buf.extend_from_slice(extra);
+        buf
+    }
+
+    #[test]
+    fn verify_read_bitmap_info_header() {
+        let buf = make_bitmap_info_header(b"xvid", &[0, 0, 1, 0xb0]);
+        let bih = BitmapInfoHeader::read(&buf).unwrap();
+        assert_eq!((bih.width, bih.height), (640, 480));
+        assert_eq!(bih.codec_id(), Some(CODEC_ID_MPEG4));
+        assert_eq!(&*bih.extra_data, &[0, 0, 1, 0xb0]);
+
+        // Unknown FourCCs are passed through.
+        let buf = make_bitmap_info_header(b"ABCD", &[]);
+        let bih = BitmapInfoHeader::read(&buf).unwrap();
+        assert_eq!(bih.codec_id(), Some(VideoCodecId::from(FourCc::new(*b"ABCD"))));
+
+        // Uncompressed.
+        let buf = make_bitmap_info_header(&[0; 4], &[]);
+        assert_eq!(BitmapInfoHeader::read(&buf).unwrap().codec_id(), None);
+
+        // Truncated.
+        assert!(BitmapInfoHeader::read(&buf[..32]).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use symphonia_core::codecs::CodecProfile;
+use symphonia_core::codecs::video::well_known::profiles::{
+    CODEC_PROFILE_VC1_ADVANCED, CODEC_PROFILE_VC1_MAIN, CODEC_PROFILE_VC1_SIMPLE,
+};
+use symphonia_core::errors::{Result, decode_error};
+use symphonia_core::io::{BitReaderLtr, ReadBitsLtr};
+use symphonia_core::units::FrameRate;
+
+use crate::mpeg::video::nal::unescape_rbsp;
+
+/// The start code suffix of a sequence header.
+const START_CODE_SEQUENCE_HEADER: u8 = 0x0f;
+
+/// The value of the profile field for the Simple profile.
+const PROFILE_SIMPLE: u32 = 0;
+/// The value of the profile field for the Main profile.
+const PROFILE_MAIN: u32 = 1;
+/// The value of the profile field for the Advanced profile.
+const PROFILE_ADVANCED: u32 = 3;
+
+/// The frame rate numerators for values 1 to 7 of `FRAMERATENR`.
+const FRAME_RATE_NUMERATORS: [u32; 7] = [24, 25, 30, 50, 60, 48, 72];
+
+/// A VC-1 Advanced profile sequence header, as defined in SMPTE 421M section 6.1.
+#[derive(Debug, Clone)]
+pub struct SequenceHeader {
+    pub profile: CodecProfile,
+    pub level: u32,
+    /// The display width, or the maximum coded width if a display size is not signalled.
+    pub width: u32,
+    /// The display height, or the maximum coded height if a display size is not signalled.
+    pub height: u32,
+    /// The frame rate, if signalled.
+    pub frame_rate: Option<FrameRate>,
+}
+
+impl SequenceHeader {
+    /// Find and read the first sequence header in a buffer of start code prefixed VC-1 data, such
+    /// as the extra data of a `WVC1` stream.
+    pub fn find(buf: &[u8]) -> Result<SequenceHeader> {
+        let Some(pos) =
+            buf.windows(4).position(|word| word == [0, 0, 1, START_CODE_SEQUENCE_HEADER])
+        else {
+            return decode_error("common (vc1): sequence header not found");
+        };
+
+        let payload = &buf[pos + 4..];
+
+        // The sequence header ends at the next start code.
+        let end = payload.windows(3).position(|word| word == [0, 0, 1]).unwrap_or(payload.len());
+
+        SequenceHeader::read(&payload[..end])
+    }
+
+    /// Read a sequence header from the payload following its start code.
+    pub fn read(buf: &[u8]) -> Result<SequenceHeader> {
+        let payload = unescape_rbsp(buf);
+        let mut bs = BitReaderLtr::new(&payload);
+
+        // Only the Advanced profile has a sequence header in the bitstream. The Simple and Main
+        // profiles signal the sequence layer out-of-band.
+        if bs.read_bits_leq32(2)? != PROFILE_ADVANCED {
+            return decode_error("common (vc1): not an advanced profile sequence header");
+        }
+
+        let level = bs.read_bits_leq32(3)?;
+
+        // The chroma format, frame and bit rate quantizers for post-processing, and the
+        // post-processing flag.
+        bs.ignore_bits(11)?;
+
+        let mut width = 2 * (bs.read_bits_leq32(12)? + 1);
+        let mut height = 2 * (bs.read_bits_leq32(12)? + 1);
+
+        // The pulldown, interlace, frame counter, frame interpolation, reserved, and progressive
+        // segmented frame flags.
+        bs.ignore_bits(6)?;
+
+        let mut frame_rate = None;
+
+        // Display extension.
+        if bs.read_bool()? {
+            width = bs.read_bits_leq32(14)? + 1;
+            height = bs.read_bits_leq32(14)? + 1;
+
+            // Aspect ratio.
+            if bs.read_bool()? && bs.read_bits_leq32(4)? == 15 {
+                bs.ignore_bits(16)?;
+            }
+
+            // Frame rate.
+            if bs.read_bool()? {
+                frame_rate = if bs.read_bool()? {
+                    // The frame rate in units of 1/32 frames per second, minus one.
+                    let numer = bs.read_bits_leq32(16)? + 1;
+                    let shift = numer.trailing_zeros().min(5);
+
+                    FrameRate::try_new(numer >> shift, 32 >> shift)
+                }
+                else {
+                    let numer = bs.read_bits_leq32(8)? as usize;
+                    let denom = bs.read_bits_leq32(4)?;
+
+                    let fps = numer.checked_sub(1).and_then(|i| FRAME_RATE_NUMERATORS.get(i));
+
+                    match (fps, denom) {
+                        (Some(&fps), 1) => FrameRate::try_new(fps, 1),
+                        (Some(&fps), 2) => FrameRate::try_new(fps * 1000, 1001),
+                        _ => None,
+                    }
+                };
+            }
+        }
+
+        Ok(SequenceHeader { profile: CODEC_PROFILE_VC1_ADVANCED, level, width, height, frame_rate })
+    }
+}
+
+/// The sequence layer data (`STRUCT_C`) of a VC-1 Simple or Main profile stream, as defined in
+/// SMPTE 421M Annex J. It is the extra data of a `WMV3` stream.
+#[derive(Debug, Clone)]
+pub struct SequenceLayerData {
+    pub profile: CodecProfile,
+}
+
+impl SequenceLayerData {
+    /// Read the sequence layer data.
+    pub fn read(buf: &[u8]) -> Result<SequenceLayerData> {
+        let mut bs = BitReaderLtr::new(buf);
+
+        let profile = match bs.read_bits_leq32(2)? {
+            PROFILE_SIMPLE => CODEC_PROFILE_VC1_SIMPLE,
+            PROFILE_MAIN => CODEC_PROFILE_VC1_MAIN,
+            // The Advanced profile signals its sequence header in the bitstream, while the
+            // reserved profile is not used.
+            _ => return decode_error("common (vc1): not a simple or main profile sequence layer"),
+        };
+
+        // The remaining fields of the sequence layer data are coding tools for the decoder.
+        Ok(SequenceLayerData { profile })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_sequence_header() {
+        // A 1 byte prefix, and an Advanced profile, level 3, 1920x1080 (1920x1088 coded) sequence
+        // header signalling 29.97 fps, followed by an entry point header.
+        let buf = [
+            0x00, 0x00, 0x00, 0x01, 0x0f, 0xdb, 0xfe, 0x3b, 0xf2, 0x1f, 0x0a, 0x3b, 0xf8, 0x86,
+            0xf1, 0x80, 0xc8, 0x80, 0x00, 0x00, 0x01, 0x0e, 0x5a, 0x67, 0xf8,
+        ];
+
+        let seq = SequenceHeader::find(&buf).unwrap();
+
+        assert_eq!(seq.profile, CODEC_PROFILE_VC1_ADVANCED);
+        assert_eq!(seq.level, 3);
+        assert_eq!((seq.width, seq.height), (1920, 1080));
+        assert_eq!(seq.frame_rate, FrameRate::try_new(30000, 1001));
+
+        // No sequence header.
+        assert!(SequenceHeader::find(&buf[18..]).is_err());
+    }
+
+    #[test]
+    fn verify_sequence_layer_data() {
+        // Main profile.
+        let seq = SequenceLayerData::read(&[0x4e, 0x0a, 0xa8, 0x01]).unwrap();
+        assert_eq!(seq.profile, CODEC_PROFILE_VC1_MAIN);
+
+        // Simple profile.
+        let seq = SequenceLayerData::read(&[0x0e, 0x00, 0x00, 0x01]).unwrap();
+        assert_eq!(seq.profile, CODEC_PROFILE_VC1_SIMPLE);
+
+        // The Advanced profile has no sequence layer data.
+        assert!(SequenceLayerData::read(&[0xc8, 0x00, 0x00, 0x01]).is_err());
+    }
+}
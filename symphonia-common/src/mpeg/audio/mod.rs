@@ -54,6 +54,7 @@ pub enum AudioObjectType {
     ErAacEld,
     SmrSimple,
     SmrMain,
+    Usac,
     Reserved,
     Unknown,
 }
@@ -107,6 +108,7 @@ const AUDIO_OBJECT_TYPES: &[AudioObjectType] = &[
     AudioObjectType::ErAacEld,
     AudioObjectType::SmrSimple,
     AudioObjectType::SmrMain,
+    AudioObjectType::Usac,
 ];
 
 const AUDIO_OBJECT_TYPE_NAMES: &[&str] = &[
@@ -152,6 +154,7 @@ const AUDIO_OBJECT_TYPE_NAMES: &[&str] = &[
     "ER AAC ELD",
     "SMR Simple",
     "SMR Main",
+    "USAC",
     "(Reserved)",
     "(Unknown)",
 ];
@@ -224,6 +227,8 @@ pub struct AudioSpecificConfig {
     pub sbr_ps_info: Option<(u32, Option<Channels>)>,
     pub sbr_present: bool,
     pub ps_present: bool,
+    /// The sample rate after SBR, if SBR is signalled.
+    pub sbr_sample_rate: Option<u32>,
 }
 
 impl AudioSpecificConfig {
@@ -259,6 +264,7 @@ impl AudioSpecificConfig {
             };
 
             asc.sbr_ps_info = Some((ext_srate, ext_chans));
+            asc.sbr_sample_rate = Some(ext_srate);
         }
 
         match asc.object_type {
@@ -369,6 +375,10 @@ impl AudioSpecificConfig {
             AudioObjectType::SmrSimple | AudioObjectType::SmrMain => {
                 return unsupported_error("common (mp4a): symbolic music config");
             }
+            AudioObjectType::Usac => {
+                // The USAC configuration is not parsed, and no extensions may follow it.
+                return Ok(asc);
+            }
             _ => {}
         };
 
@@ -397,7 +407,9 @@ impl AudioSpecificConfig {
             _ => {}
         };
 
-        if asc.sbr_ps_info.is_some() && (bs.bits_left() >= 16) {
+        // Backward-compatible SBR and PS signalling follows the configuration if SBR was not
+        // explicitly signalled.
+        if asc.sbr_ps_info.is_none() && (bs.bits_left() >= 16) {
             let sync = bs.read_bits_leq32(11)?;
 
             if sync == 0x2B7 {
@@ -405,7 +417,7 @@ impl AudioSpecificConfig {
                 if ext_otype == AudioObjectType::Sbr {
                     asc.sbr_present = bs.read_bool()?;
                     if asc.sbr_present {
-                        let _ext_srate = Self::read_sampling_frequency(&mut bs)?;
+                        asc.sbr_sample_rate = Some(Self::read_sampling_frequency(&mut bs)?);
                         if bs.bits_left() >= 12 {
                             let sync = bs.read_bits_leq32(11)?;
                             if sync == 0x548 {
@@ -417,7 +429,7 @@ impl AudioSpecificConfig {
                 if ext_otype == AudioObjectType::Ps {
                     asc.sbr_present = bs.read_bool()?;
                     if asc.sbr_present {
-                        let _ext_srate = Self::read_sampling_frequency(&mut bs)?;
+                        asc.sbr_sample_rate = Some(Self::read_sampling_frequency(&mut bs)?);
                     }
                     let _ext_channels = bs.read_bits_leq32(4)?;
                 }
@@ -427,6 +439,36 @@ impl AudioSpecificConfig {
         Ok(asc)
     }
 
+    /// Get the sample rate of the decoded audio. If SBR is signalled, this is the extension sample
+    /// rate, otherwise it is the core sample rate.
+    ///
+    /// Note that the AAC decoder in `symphonia-codec-aac` does not apply SBR, and therefore always
+    /// outputs audio at the core sample rate.
+    pub fn output_sample_rate(&self) -> u32 {
+        match self.sbr_sample_rate {
+            Some(rate) if self.sbr_present => rate,
+            _ => self.sample_rate,
+        }
+    }
+
+    /// Get the channels of the decoded audio. If PS is signalled, the mono core is decoded to
+    /// stereo.
+    ///
+    /// Note that the AAC decoder in `symphonia-codec-aac` does not apply PS, and therefore always
+    /// outputs the channels of the core.
+    pub fn output_channels(&self) -> Option<Channels> {
+        if self.ps_present { Some(layouts::CHANNEL_LAYOUT_STEREO) } else { self.channels.clone() }
+    }
+
+    /// Get the number of audio frames the core decodes from each access unit, if known. If SBR is
+    /// applied, the number of frames is doubled.
+    pub fn frames_per_packet(&self) -> Option<u64> {
+        match self.samples {
+            0 => None,
+            samples => Some(samples as u64),
+        }
+    }
+
     fn read_audio_object_type<B: ReadBitsLtr>(bs: &mut B) -> Result<AudioObjectType> {
         let index = match bs.read_bits_leq32(5)? {
             index if index < 31 => index as usize,
@@ -469,6 +511,7 @@ pub fn get_audio_codec_profile(asc: &AudioSpecificConfig) -> Option<CodecProfile
         AudioObjectType::Main => Some(CODEC_PROFILE_AAC_MAIN),
         AudioObjectType::Ssr => Some(CODEC_PROFILE_AAC_SSR),
         AudioObjectType::Ltp => Some(CODEC_PROFILE_AAC_LTP),
+        AudioObjectType::Usac => Some(CODEC_PROFILE_AAC_USAC),
         AudioObjectType::Lc => {
             if asc.ps_present {
                 Some(CODEC_PROFILE_AAC_HE_V2)
@@ -483,3 +526,44 @@ pub fn get_audio_codec_profile(asc: &AudioSpecificConfig) -> Option<CodecProfile
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use symphonia_core::audio::layouts;
+    use symphonia_core::codecs::audio::well_known::profiles::*;
+
+    use super::{AudioObjectType, AudioSpecificConfig, get_audio_codec_profile};
+
+    #[test]
+    fn verify_audio_specific_config() {
+        // AAC-LC, 44.1 kHz, stereo.
+        let asc = AudioSpecificConfig::read(&[0x12, 0x10]).unwrap();
+        assert_eq!(asc.object_type, AudioObjectType::Lc);
+        assert_eq!(asc.output_sample_rate(), 44100);
+        assert_eq!(asc.output_channels(), Some(layouts::CHANNEL_LAYOUT_STEREO));
+        assert_eq!(get_audio_codec_profile(&asc), Some(CODEC_PROFILE_AAC_LC));
+
+        // HE-AAC with explicit SBR signalling, 24 kHz core, 48 kHz output, stereo.
+        let asc = AudioSpecificConfig::read(&[0x2b, 0x11, 0x88, 0x00]).unwrap();
+        assert_eq!(asc.object_type, AudioObjectType::Lc);
+        assert_eq!(asc.sample_rate, 24000);
+        assert_eq!(asc.output_sample_rate(), 48000);
+        assert_eq!(asc.output_channels(), Some(layouts::CHANNEL_LAYOUT_STEREO));
+        assert_eq!(get_audio_codec_profile(&asc), Some(CODEC_PROFILE_AAC_HE));
+
+        // HE-AAC v2 with backward-compatible SBR and PS signalling, 24 kHz core, 48 kHz output,
+        // mono core.
+        let asc = AudioSpecificConfig::read(&[0x13, 0x08, 0x56, 0xe5, 0x9d, 0x48, 0x80]).unwrap();
+        assert_eq!(asc.object_type, AudioObjectType::Lc);
+        assert_eq!(asc.channels, Some(layouts::CHANNEL_LAYOUT_MONO));
+        assert_eq!(asc.output_sample_rate(), 48000);
+        assert_eq!(asc.output_channels(), Some(layouts::CHANNEL_LAYOUT_STEREO));
+        assert_eq!(get_audio_codec_profile(&asc), Some(CODEC_PROFILE_AAC_HE_V2));
+
+        // USAC, 48 kHz, stereo.
+        let asc = AudioSpecificConfig::read(&[0xf9, 0x46, 0x40]).unwrap();
+        assert_eq!(asc.object_type, AudioObjectType::Usac);
+        assert_eq!(asc.output_sample_rate(), 48000);
+        assert_eq!(get_audio_codec_profile(&asc), Some(CODEC_PROFILE_AAC_USAC));
+    }
+}
@@ -5,7 +5,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use symphonia_core::codecs::CodecId;
+use symphonia_core::codecs::{CodecId, CodecProfile};
 use symphonia_core::errors::{Result, decode_error, unsupported_error};
 use symphonia_core::io::{FiniteStream, ReadBytes, ScopedStream};
 
@@ -58,16 +58,19 @@ pub fn read_object_descriptor_header<B: ReadBytes>(reader: &mut B) -> Result<(Cl
 /// Try to get a codec ID from from an object type indication.
 pub fn codec_id_from_object_type_indication(obj_type: u8) -> Option<CodecId> {
     use symphonia_core::codecs::audio::well_known::{
-        CODEC_ID_AAC, CODEC_ID_AC3, CODEC_ID_DCA, CODEC_ID_EAC3, CODEC_ID_MP3,
+        CODEC_ID_AAC, CODEC_ID_AC3, CODEC_ID_DCA, CODEC_ID_EAC3, CODEC_ID_MP3, CODEC_ID_OPUS,
     };
+    use symphonia_core::codecs::subtitle::well_known::CODEC_ID_VOBSUB;
     use symphonia_core::codecs::video::well_known::{
-        CODEC_ID_H264, CODEC_ID_HEVC, CODEC_ID_MPEG2, CODEC_ID_MPEG4, CODEC_ID_VP9,
+        CODEC_ID_H264, CODEC_ID_HEVC, CODEC_ID_MJPEG, CODEC_ID_MPEG1, CODEC_ID_MPEG2,
+        CODEC_ID_MPEG4, CODEC_ID_VC1, CODEC_ID_VP9,
     };
 
     // AAC
     const OBJ_TYPE_AUDIO_MPEG4_3: u8 = 0x40; // Audio ISO/IEC 14496-3
     const OBJ_TYPE_AUDIO_MPEG2_7_MAIN: u8 = 0x66; // Audio ISO/IEC 13818-7 Main Profile
     const OBJ_TYPE_AUDIO_MPEG2_7_LC: u8 = 0x67; // Audio ISO/IEC 13818-7 Low Complexity
+    const OBJ_TYPE_AUDIO_MPEG2_7_SSR: u8 = 0x68; // Audio ISO/IEC 13818-7 Scaleable Sampling Rate
 
     // MP3
     const OBJ_TYPE_AUDIO_MPEG2_3: u8 = 0x69; // Audio ISO/IEC 13818-3 (MP3)
@@ -76,6 +79,10 @@ pub fn codec_id_from_object_type_indication(obj_type: u8) -> Option<CodecId> {
     const OBJ_TYPE_AUDIO_AC3: u8 = 0xa5;
     const OBJ_TYPE_AUDIO_EAC3: u8 = 0xa6;
     const OBJ_TYPE_AUDIO_DTS: u8 = 0xa9;
+    const OBJ_TYPE_AUDIO_OPUS: u8 = 0xad;
+
+    // MPEG1 video
+    const OBJ_TYPE_VISUAL_MPEG1_2: u8 = 0x6a; // Visual ISO/IEC 11172-2
 
     // MPEG2 video
     const OBJ_TYPE_VISUAL_MPEG2_2_SP: u8 = 0x60; // Visual ISO/IEC 13818-2 Simple Profile
@@ -97,14 +104,26 @@ pub fn codec_id_from_object_type_indication(obj_type: u8) -> Option<CodecId> {
     // VP9
     const OBJ_TYPE_VISUAL_VP09: u8 = 0xb1;
 
+    // JPEG
+    const OBJ_TYPE_VISUAL_JPEG: u8 = 0x6c; // Visual ISO/IEC 10918-1
+
+    // VC-1
+    const OBJ_TYPE_VISUAL_VC1: u8 = 0xa3; // SMPTE VC-1
+
+    // DVD sub-pictures
+    const OBJ_TYPE_SUBPIC_VOBSUB: u8 = 0xe0;
+
     let codec_id = match obj_type {
-        OBJ_TYPE_AUDIO_MPEG4_3 | OBJ_TYPE_AUDIO_MPEG2_7_LC | OBJ_TYPE_AUDIO_MPEG2_7_MAIN => {
-            CodecId::Audio(CODEC_ID_AAC)
-        }
+        OBJ_TYPE_AUDIO_MPEG4_3
+        | OBJ_TYPE_AUDIO_MPEG2_7_LC
+        | OBJ_TYPE_AUDIO_MPEG2_7_MAIN
+        | OBJ_TYPE_AUDIO_MPEG2_7_SSR => CodecId::Audio(CODEC_ID_AAC),
         OBJ_TYPE_AUDIO_MPEG2_3 | OBJ_TYPE_AUDIO_MPEG1_3 => CodecId::Audio(CODEC_ID_MP3),
         OBJ_TYPE_AUDIO_AC3 => CodecId::Audio(CODEC_ID_AC3),
         OBJ_TYPE_AUDIO_EAC3 => CodecId::Audio(CODEC_ID_EAC3),
         OBJ_TYPE_AUDIO_DTS => CodecId::Audio(CODEC_ID_DCA),
+        OBJ_TYPE_AUDIO_OPUS => CodecId::Audio(CODEC_ID_OPUS),
+        OBJ_TYPE_VISUAL_MPEG1_2 => CodecId::Video(CODEC_ID_MPEG1),
         OBJ_TYPE_VISUAL_MPEG2_2_SP
         | OBJ_TYPE_VISUAL_MPEG2_2_MP
         | OBJ_TYPE_VISUAL_MPEG2_2_SNR
@@ -115,6 +134,9 @@ pub fn codec_id_from_object_type_indication(obj_type: u8) -> Option<CodecId> {
         OBJ_TYPE_VISUAL_AVC1 => CodecId::Video(CODEC_ID_H264),
         OBJ_TYPE_VISUAL_HEVC1 => CodecId::Video(CODEC_ID_HEVC),
         OBJ_TYPE_VISUAL_VP09 => CodecId::Video(CODEC_ID_VP9),
+        OBJ_TYPE_VISUAL_JPEG => CodecId::Video(CODEC_ID_MJPEG),
+        OBJ_TYPE_VISUAL_VC1 => CodecId::Video(CODEC_ID_VC1),
+        OBJ_TYPE_SUBPIC_VOBSUB => CodecId::Subtitle(CODEC_ID_VOBSUB),
         _ => {
             debug!("unknown object type indication {obj_type:#x} for decoder config descriptor");
             return None;
@@ -124,6 +146,21 @@ pub fn codec_id_from_object_type_indication(obj_type: u8) -> Option<CodecId> {
     Some(codec_id)
 }
 
+/// Try to get the codec profile from an object type indication. Only the MPEG-2 AAC object type
+/// indications state a profile.
+pub fn profile_from_object_type_indication(obj_type: u8) -> Option<CodecProfile> {
+    use symphonia_core::codecs::audio::well_known::profiles::{
+        CODEC_PROFILE_AAC_LC, CODEC_PROFILE_AAC_MAIN, CODEC_PROFILE_AAC_SSR,
+    };
+
+    match obj_type {
+        0x66 => Some(CODEC_PROFILE_AAC_MAIN),
+        0x67 => Some(CODEC_PROFILE_AAC_LC),
+        0x68 => Some(CODEC_PROFILE_AAC_SSR),
+        _ => None,
+    }
+}
+
 pub trait ObjectDescriptor: Sized {
     fn read<B: ReadBytes>(reader: &mut B, len: u64) -> Result<Self>;
 }
@@ -371,3 +408,41 @@ impl ObjectDescriptor for SLConfigDescriptor {
         Ok(SLConfigDescriptor {})
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use symphonia_core::codecs::CodecId;
+    use symphonia_core::codecs::audio::well_known::CODEC_ID_AAC;
+    use symphonia_core::io::BufReader;
+
+    use super::*;
+
+    #[test]
+    fn verify_es_descriptor() {
+        // An ES descriptor for AAC-LC, 44.1 kHz, stereo audio, as written by FFmpeg. The descriptor
+        // lengths use the 4 byte form.
+        let buf = [
+            0x03, 0x80, 0x80, 0x80, 0x22, 0x00, 0x01, 0x00, // ES descriptor
+            0x04, 0x80, 0x80, 0x80, 0x14, 0x40, 0x15, 0x00, 0x00, 0x00, 0x00, 0x01, 0xf4, 0x00,
+            0x00, 0x01, 0xf4, 0x00, // Decoder config descriptor
+            0x05, 0x80, 0x80, 0x80, 0x02, 0x12, 0x10, // Decoder specific info
+            0x06, 0x80, 0x80, 0x80, 0x01, 0x02, // SL config descriptor
+        ];
+
+        let mut reader = BufReader::new(&buf);
+
+        let (tag, len) = read_object_descriptor_header(&mut reader).unwrap();
+        assert_eq!(tag, ClassTag::EsDescriptor);
+        assert_eq!(len, 0x22);
+
+        let desc = ESDescriptor::read(&mut reader, len).unwrap();
+        assert_eq!(desc.es_id, 1);
+        assert_eq!(desc.dec_config.object_type_indication, 0x40);
+        assert_eq!(&*desc.dec_config.dec_specific_info.unwrap().extra_data, &[0x12, 0x10]);
+
+        assert_eq!(
+            codec_id_from_object_type_indication(desc.dec_config.object_type_indication),
+            Some(CodecId::Audio(CODEC_ID_AAC))
+        );
+    }
+}
@@ -6,12 +6,42 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use symphonia_core::codecs::CodecProfile;
+use symphonia_core::codecs::video::VideoCodecParameters;
+use symphonia_core::codecs::video::well_known::extra_data::{
+    VIDEO_EXTRA_DATA_ID_AVC_DECODER_CONFIG, VIDEO_EXTRA_DATA_ID_HEVC_DECODER_CONFIG,
+};
+use symphonia_core::codecs::video::well_known::{CODEC_ID_H264, CODEC_ID_HEVC};
 use symphonia_core::errors::{Result, decode_error};
-use symphonia_core::io::{BitReaderLtr, ReadBitsLtr};
+use symphonia_core::io::{BitReaderLtr, BufReader, FiniteStream, ReadBitsLtr, ReadBytes};
+
+pub mod nal;
+mod sps;
+
+use nal::{AnnexBConverter, NalUnitFraming, NalUnitIter, NalUnitSyntax, unescape_rbsp};
+pub use sps::{AVCSequenceParameterSet, HEVCSequenceParameterSet};
+
+/// The HEVC prefix supplemental enhancement information (SEI) NAL unit type.
+const HEVC_NAL_UNIT_PREFIX_SEI: u8 = 39;
+
+/// The HEVC sequence parameter set NAL unit type.
+const HEVC_NAL_UNIT_SPS: u8 = 33;
+
+/// The SEI payload type of user data registered by ITU-T Recommendation T.35.
+const SEI_PAYLOAD_USER_DATA_REGISTERED_ITU_T_T35: u32 = 4;
+
+/// The ITU-T T.35 country code, terminal provider code, terminal provider oriented code, and
+/// application identifier of HDR10+ (SMPTE ST 2094-40) dynamic metadata.
+const HDR10_PLUS_T35_PREFIX: [u8; 6] = [0xb5, 0x00, 0x3c, 0x00, 0x01, 0x04];
 
 pub struct AVCDecoderConfigurationRecord {
     pub profile: CodecProfile,
     pub level: u32,
+    /// The size, in bytes, of the length prefix of each NAL unit in a sample.
+    pub nal_length_size: usize,
+    /// The sequence and picture parameter set NAL units, in order.
+    pub parameter_sets: Vec<Box<[u8]>>,
+    /// The first sequence parameter set, if present and valid.
+    pub sps: Option<AVCSequenceParameterSet>,
 }
 
 impl AVCDecoderConfigurationRecord {
@@ -35,9 +65,23 @@ impl AVCDecoderConfigurationRecord {
         let _profile_compatibility = br.read_bits_leq32(8)?;
         let avc_level_indication = br.read_bits_leq32(8)?;
 
+        // The length size of NAL units, followed by the sequence parameter sets. Some records end
+        // after the level, in which case the common 4 byte length size is assumed.
+        let length_size_minus_one = br.read_bits_leq32(8).map(|value| value & 0x3).unwrap_or(3);
+        let parameter_sets = buf.get(5..).map(read_avc_parameter_sets).unwrap_or_default();
+
+        // The sequence parameter sets precede the picture parameter sets.
+        let sps = parameter_sets
+            .first()
+            .filter(|nal_unit| nal_unit.first().map(|&header| header & 0x1f) == Some(7))
+            .and_then(|nal_unit| AVCSequenceParameterSet::read(nal_unit).ok());
+
         Ok(AVCDecoderConfigurationRecord {
             profile: CodecProfile::new(avc_profile_indication),
             level: avc_level_indication,
+            nal_length_size: length_size_minus_one as usize + 1,
+            parameter_sets,
+            sps,
         })
     }
 }
@@ -45,6 +89,13 @@ impl AVCDecoderConfigurationRecord {
 pub struct HEVCDecoderConfigurationRecord {
     pub profile: CodecProfile,
     pub level: u32,
+    /// The size, in bytes, of the length prefix of each NAL unit in a sample.
+    pub nal_length_size: usize,
+    /// The NAL units of the parameter set arrays, such as the video, sequence, and picture
+    /// parameter sets, in order.
+    pub parameter_sets: Vec<Box<[u8]>>,
+    /// The first sequence parameter set, if present and valid.
+    pub sps: Option<HEVCSequenceParameterSet>,
 }
 
 impl HEVCDecoderConfigurationRecord {
@@ -70,9 +121,28 @@ impl HEVCDecoderConfigurationRecord {
         let _general_constraint_indicator_flags = br.read_bits_leq64(48)?;
         let general_level_idc = br.read_bits_leq32(8)?;
 
+        // The minimum spatial segmentation, parallelism type, chroma format, bit depths, average
+        // frame rate, constant frame rate, number of temporal layers, and temporal ID nesting
+        // fields precede the length size.
+        br.ignore_bits(70)?;
+        let length_size_minus_one = br.read_bits_leq32(2)?;
+
+        // The parameter set arrays follow the 22 byte header.
+        let parameter_sets = buf.get(22..).map(read_hevc_parameter_sets).unwrap_or_default();
+
+        let sps = parameter_sets
+            .iter()
+            .find(|nal_unit| {
+                nal_unit.first().map(|&header| (header >> 1) & 0x3f) == Some(HEVC_NAL_UNIT_SPS)
+            })
+            .and_then(|nal_unit| HEVCSequenceParameterSet::read(nal_unit).ok());
+
         Ok(HEVCDecoderConfigurationRecord {
             profile: CodecProfile::new(general_profile_idc),
             level: general_level_idc,
+            nal_length_size: length_size_minus_one as usize + 1,
+            parameter_sets,
+            sps,
         })
     }
 }
@@ -110,3 +180,265 @@ impl DOVIDecoderConfigurationRecord {
         Ok(config)
     }
 }
+
+/// Make an Annex B converter for the packets of a H.264 or HEVC track using the decoder
+/// configuration record in the extra data of the codec parameters.
+///
+/// Returns `None` if the track is not a H.264 or HEVC track, or does not have a decoder
+/// configuration record.
+pub fn make_annex_b_converter(
+    codec_params: &VideoCodecParameters,
+) -> Result<Option<AnnexBConverter>> {
+    let extra_data_id = match codec_params.codec {
+        CODEC_ID_H264 => VIDEO_EXTRA_DATA_ID_AVC_DECODER_CONFIG,
+        CODEC_ID_HEVC => VIDEO_EXTRA_DATA_ID_HEVC_DECODER_CONFIG,
+        _ => return Ok(None),
+    };
+
+    let Some(extra_data) = codec_params.extra_data.iter().find(|data| data.id == extra_data_id)
+    else {
+        return Ok(None);
+    };
+
+    let converter = if extra_data_id == VIDEO_EXTRA_DATA_ID_AVC_DECODER_CONFIG {
+        let record = AVCDecoderConfigurationRecord::read(&extra_data.data)?;
+        AnnexBConverter::new(record.nal_length_size, &record.parameter_sets)?
+    }
+    else {
+        let record = HEVCDecoderConfigurationRecord::read(&extra_data.data)?;
+        AnnexBConverter::new(record.nal_length_size, &record.parameter_sets)?
+    };
+
+    Ok(Some(converter))
+}
+
+/// Read the sequence and picture parameter sets of an AVCDecoderConfigurationRecord. Reading stops
+/// at the first truncated parameter set.
+fn read_avc_parameter_sets(buf: &[u8]) -> Vec<Box<[u8]>> {
+    let mut reader = BufReader::new(buf);
+    let mut parameter_sets = Vec::new();
+
+    // The number of sequence parameter sets is a 5-bit field, whereas the number of picture
+    // parameter sets is a full byte.
+    for mask in [0x1f, 0xff] {
+        let Ok(count) = reader.read_u8().map(|count| count & mask)
+        else {
+            break;
+        };
+
+        for _ in 0..count {
+            match read_parameter_set(&mut reader) {
+                Some(nal_unit) => parameter_sets.push(Box::from(nal_unit)),
+                None => return parameter_sets,
+            }
+        }
+    }
+
+    parameter_sets
+}
+
+/// Read the NAL units of the parameter set arrays of a HEVCDecoderConfigurationRecord. Reading
+/// stops at the first truncated NAL unit.
+fn read_hevc_parameter_sets(arrays: &[u8]) -> Vec<Box<[u8]>> {
+    let mut reader = BufReader::new(arrays);
+    let mut parameter_sets = Vec::new();
+
+    let num_arrays = reader.read_u8().unwrap_or(0);
+
+    for _ in 0..num_arrays {
+        // The array completeness flag, reserved bit, and NAL unit type.
+        let Ok(_) = reader.read_u8()
+        else {
+            break;
+        };
+        let Ok(num_nalus) = reader.read_be_u16()
+        else {
+            break;
+        };
+
+        for _ in 0..num_nalus {
+            match read_parameter_set(&mut reader) {
+                Some(nal_unit) => parameter_sets.push(Box::from(nal_unit)),
+                None => return parameter_sets,
+            }
+        }
+    }
+
+    parameter_sets
+}
+
+/// Read a parameter set NAL unit preceded by its 16-bit length.
+fn read_parameter_set<'a>(reader: &mut BufReader<'a>) -> Option<&'a [u8]> {
+    let len = reader.read_be_u16().ok()?;
+    reader.read_buf_bytes_ref(usize::from(len)).ok()
+}
+
+/// Returns true if a HEVC packet contains HDR10+ (SMPTE ST 2094-40) dynamic metadata.
+///
+/// The packet must consist of NAL units that are each prefixed by their big-endian length, as is
+/// the case for ISO/MP4 and Matroska. The size of the length prefix, `nal_length_size`, is
+/// signalled by the `lengthSizeMinusOne` field of the HEVCDecoderConfigurationRecord.
+///
+/// The metadata is only detected, not decoded.
+pub fn has_hevc_hdr10_plus_sei(buf: &[u8], nal_length_size: usize) -> Result<bool> {
+    let framing = NalUnitFraming::LengthPrefixed(nal_length_size);
+
+    for nal_unit in NalUnitIter::new(buf, NalUnitSyntax::Hevc, framing)? {
+        let (nal_unit_type, payload) = nal_unit?;
+
+        if nal_unit_type == HEVC_NAL_UNIT_PREFIX_SEI
+            && sei_rbsp_has_hdr10_plus(&unescape_rbsp(payload))?
+        {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Returns true if any SEI message in the SEI RBSP contains HDR10+ dynamic metadata.
+fn sei_rbsp_has_hdr10_plus(rbsp: &[u8]) -> Result<bool> {
+    let mut reader = BufReader::new(rbsp);
+
+    // SEI messages follow one another until the RBSP trailing bits.
+    while reader.bytes_available() > 1 {
+        let payload_type = read_sei_value(&mut reader)?;
+        let payload_size = read_sei_value(&mut reader)?;
+
+        let payload = reader.read_buf_bytes_ref(payload_size as usize)?;
+
+        if payload_type == SEI_PAYLOAD_USER_DATA_REGISTERED_ITU_T_T35
+            && payload.starts_with(&HDR10_PLUS_T35_PREFIX)
+        {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Read a SEI message payload type or size.
+fn read_sei_value(reader: &mut BufReader<'_>) -> Result<u32> {
+    let mut value = 0u32;
+
+    // The value is the sum of all bytes up-to and including the first byte that is not 0xff.
+    loop {
+        let byte = reader.read_u8()?;
+
+        let Some(sum) = value.checked_add(u32::from(byte))
+        else {
+            return decode_error("common (sei): payload type or size overflow");
+        };
+        value = sum;
+
+        if byte != 0xff {
+            break;
+        }
+    }
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Make a prefix SEI NAL unit, preceded by a 4-byte length, with the given SEI messages.
+    fn make_sei_nal_unit(messages: &[(u8, &[u8])]) -> Vec<u8> {
+        let mut nal_unit = vec![HEVC_NAL_UNIT_PREFIX_SEI << 1, 0x01];
+        for (payload_type, payload) in messages {
+            nal_unit.push(*payload_type);
+            nal_unit.push(payload.len() as u8);
+            nal_unit.extend_from_slice(payload);
+        }
+        // RBSP trailing bits.
+        nal_unit.push(0x80);
+
+        [&(nal_unit.len() as u32).to_be_bytes(), nal_unit.as_slice()].concat()
+    }
+
+    #[test]
+    fn verify_truncated_avc_decoder_configuration_record() {
+        // A High profile, level 4.0, record that ends after the level.
+        let record = AVCDecoderConfigurationRecord::read(&[0x01, 0x64, 0x00, 0x28]).unwrap();
+
+        assert_eq!(record.profile, CodecProfile::new(100));
+        assert_eq!(record.level, 40);
+        assert_eq!(record.nal_length_size, 4);
+        assert!(record.parameter_sets.is_empty());
+        assert!(record.sps.is_none());
+    }
+
+    #[test]
+    fn verify_hevc_decoder_configuration_record() {
+        // A Main profile, level 4.0, 1920x1080 SPS.
+        let sps = [
+            0x42, 0x01, 0x01, 0x01, 0x60, 0x00, 0x00, 0x03, 0x00, 0x90, 0x00, 0x00, 0x03, 0x00,
+            0x00, 0x03, 0x00, 0x78, 0xa0, 0x03, 0xc0, 0x80, 0x11, 0x07, 0xcb, 0x96, 0x57, 0x92,
+            0x4d, 0x9a, 0xf7, 0x70, 0x08, 0x00, 0x00, 0x03, 0x00, 0x08, 0x00, 0x00, 0x03, 0x00,
+            0xc8, 0x80,
+        ];
+        let vps = [0x40, 0x01, 0x0c];
+        let pps = [0x44, 0x01, 0xc1, 0x72];
+
+        let mut buf = vec![0x01, 0x01, 0x60, 0x00, 0x00, 0x00, 0x90, 0x00, 0x00, 0x00, 0x00, 0x00];
+        // Level, the fields preceding the length size, and a length size of 4 bytes.
+        buf.extend_from_slice(&[0x78, 0xf0, 0x00, 0xfc, 0xfd, 0xf8, 0xf8, 0x00, 0x00, 0x0f]);
+
+        // Parameter set arrays.
+        buf.push(3);
+        for (nal_unit_type, nal_unit) in [(32, vps.as_slice()), (33, &sps), (34, &pps)] {
+            buf.extend_from_slice(&[0x80 | nal_unit_type, 0, 1]);
+            buf.extend_from_slice(&(nal_unit.len() as u16).to_be_bytes());
+            buf.extend_from_slice(nal_unit);
+        }
+
+        let record = HEVCDecoderConfigurationRecord::read(&buf).unwrap();
+
+        assert_eq!(record.profile, CodecProfile::new(1));
+        assert_eq!(record.level, 120);
+        assert_eq!(record.nal_length_size, 4);
+        assert_eq!(record.parameter_sets.len(), 3);
+        assert_eq!(&*record.parameter_sets[2], pps.as_slice());
+
+        let sps = record.sps.unwrap();
+        assert_eq!((sps.width, sps.height), (1920, 1080));
+    }
+
+    #[test]
+    fn verify_has_hevc_hdr10_plus_sei() {
+        // A slice NAL unit (IDR_W_RADL).
+        let slice = [&[0, 0, 0, 3], [19 << 1, 0x01, 0xaf].as_slice()].concat();
+
+        // HDR10+ metadata, with an emulation prevention byte following the two zero bytes of the
+        // targeted system display maximum luminance.
+        let hdr10_plus = [0xb5, 0x00, 0x3c, 0x00, 0x01, 0x04, 0x01, 0x40, 0x00, 0x00, 0x03, 0x01];
+
+        // A mastering display colour volume SEI, followed by HDR10+ metadata.
+        let buf = [slice.clone(), make_sei_nal_unit(&[(137, &[0; 24]), (4, &hdr10_plus)])].concat();
+        assert!(has_hevc_hdr10_plus_sei(&buf, 4).unwrap());
+
+        // An empty NAL unit preceding the HDR10+ metadata.
+        let buf = [[0, 0, 0, 0].as_slice(), &make_sei_nal_unit(&[(4, &hdr10_plus)])].concat();
+        assert!(has_hevc_hdr10_plus_sei(&buf, 4).unwrap());
+
+        // User data registered by another provider.
+        let buf = [make_sei_nal_unit(&[(4, &[0xb5, 0x00, 0x31, 0x47, 0x41])]), slice].concat();
+        assert!(!has_hevc_hdr10_plus_sei(&buf, 4).unwrap());
+
+        // Truncated NAL unit.
+        let buf = make_sei_nal_unit(&[(4, &hdr10_plus)]);
+        assert!(has_hevc_hdr10_plus_sei(&buf[..8], 4).is_err());
+    }
+
+    #[test]
+    fn verify_read_sei_value() {
+        assert_eq!(read_sei_value(&mut BufReader::new(&[0x04])).unwrap(), 4);
+        assert_eq!(read_sei_value(&mut BufReader::new(&[0xff, 0xff, 0x10])).unwrap(), 526);
+
+        // A run of 0xff bytes long enough to overflow the value is an error.
+        let mut buf = vec![0xff; (u32::MAX / 0xff) as usize + 1];
+        buf.push(0);
+        assert!(read_sei_value(&mut BufReader::new(&buf)).is_err());
+    }
+}
@@ -0,0 +1,346 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Network abstraction layer (NAL) unit utilities for H.264/AVC and H.265/HEVC bitstreams.
+
+use symphonia_core::errors::{Result, decode_error};
+
+/// The NAL unit header syntax of a video coding standard.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NalUnitSyntax {
+    /// H.264/AVC: a 1-byte NAL unit header.
+    Avc,
+    /// H.265/HEVC: a 2-byte NAL unit header.
+    Hevc,
+}
+
+impl NalUnitSyntax {
+    /// Get the length of the NAL unit header in bytes.
+    pub fn header_len(&self) -> usize {
+        match self {
+            NalUnitSyntax::Avc => 1,
+            NalUnitSyntax::Hevc => 2,
+        }
+    }
+
+    /// Get the NAL unit type from the first byte of the NAL unit header.
+    fn nal_unit_type(&self, byte: u8) -> u8 {
+        match self {
+            NalUnitSyntax::Avc => byte & 0x1f,
+            NalUnitSyntax::Hevc => (byte >> 1) & 0x3f,
+        }
+    }
+}
+
+/// The framing of NAL units in a buffer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NalUnitFraming {
+    /// Each NAL unit is preceded by a start code (ITU-T H.264 and H.265, Annex B), as is the case
+    /// for elementary streams and MPEG transport streams.
+    AnnexB,
+    /// Each NAL unit is preceded by its big-endian length of the given size in bytes, as is the
+    /// case for ISO/MP4 and Matroska. The size is signalled by the `lengthSizeMinusOne` field of
+    /// the decoder configuration record.
+    LengthPrefixed(usize),
+}
+
+/// An iterator over the NAL units in a buffer.
+///
+/// Each item is the NAL unit type, and the payload of the NAL unit following the NAL unit header.
+/// The payload may contain emulation prevention bytes, which can be removed using
+/// [`unescape_rbsp`]. NAL units shorter than the NAL unit header, such as empty NAL units, are
+/// skipped.
+pub struct NalUnitIter<'a> {
+    buf: &'a [u8],
+    syntax: NalUnitSyntax,
+    framing: NalUnitFraming,
+}
+
+impl<'a> NalUnitIter<'a> {
+    /// Instantiate a NAL unit iterator over the provided buffer.
+    pub fn new(buf: &'a [u8], syntax: NalUnitSyntax, framing: NalUnitFraming) -> Result<Self> {
+        if let NalUnitFraming::LengthPrefixed(len_size) = framing {
+            if !(1..=4).contains(&len_size) {
+                return decode_error("common (nal): invalid nal unit length size");
+            }
+        }
+
+        Ok(NalUnitIter { buf, syntax, framing })
+    }
+
+    /// Get the next length-prefixed NAL unit.
+    fn next_length_prefixed(&mut self, len_size: usize) -> Result<Option<&'a [u8]>> {
+        if self.buf.is_empty() {
+            return Ok(None);
+        }
+
+        let Some((prefix, rest)) = self.buf.split_at_checked(len_size)
+        else {
+            return decode_error("common (nal): truncated nal unit length");
+        };
+
+        let len = prefix.iter().fold(0, |len, &byte| (len << 8) | usize::from(byte));
+
+        let Some((nal_unit, rest)) = rest.split_at_checked(len)
+        else {
+            return decode_error("common (nal): truncated nal unit");
+        };
+
+        self.buf = rest;
+        Ok(Some(nal_unit))
+    }
+
+    /// Get the next NAL unit following a start code.
+    fn next_annex_b(&mut self) -> Option<&'a [u8]> {
+        let start = find_start_code(self.buf)?;
+        let rest = &self.buf[start + 3..];
+
+        // The NAL unit ends at the next start code, or the end of the buffer.
+        let end = find_start_code(rest).unwrap_or(rest.len());
+        self.buf = &rest[end..];
+
+        // A NAL unit never ends with a zero byte. Trailing zero bytes are either the leading zero
+        // byte of a 4-byte start code, or trailing zero bytes between NAL units.
+        let mut nal_unit = &rest[..end];
+
+        while let [head @ .., 0] = nal_unit {
+            nal_unit = head;
+        }
+
+        Some(nal_unit)
+    }
+}
+
+impl<'a> Iterator for NalUnitIter<'a> {
+    type Item = Result<(u8, &'a [u8])>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let header_len = self.syntax.header_len();
+
+        loop {
+            let nal_unit = match self.framing {
+                NalUnitFraming::AnnexB => self.next_annex_b(),
+                NalUnitFraming::LengthPrefixed(len_size) => {
+                    match self.next_length_prefixed(len_size) {
+                        Ok(nal_unit) => nal_unit,
+                        Err(err) => {
+                            // Stop iterating after an error.
+                            self.buf = &[];
+                            return Some(Err(err));
+                        }
+                    }
+                }
+            }?;
+
+            // A NAL unit without a complete header carries nothing, skip it.
+            if nal_unit.len() >= header_len {
+                return Some(Ok((self.syntax.nal_unit_type(nal_unit[0]), &nal_unit[header_len..])));
+            }
+        }
+    }
+}
+
+/// The 4-byte start code prefixed to each NAL unit by [`AnnexBConverter`].
+const ANNEX_B_START_CODE: [u8; 4] = [0, 0, 0, 1];
+
+/// Converts length-prefixed NAL units, as stored by ISO/MP4 and Matroska, into an Annex B byte
+/// stream.
+///
+/// Each NAL unit is prefixed by a 4-byte start code. Since the parameter sets are not usually
+/// repeated in-band, the parameter sets of the decoder configuration record are inserted before
+/// the NAL units of each keyframe.
+#[derive(Clone, Debug)]
+pub struct AnnexBConverter {
+    nal_length_size: usize,
+    /// The start code prefixed parameter sets.
+    parameter_sets: Vec<u8>,
+}
+
+impl AnnexBConverter {
+    /// Instantiate a converter for NAL units with a length prefix of `nal_length_size` bytes. The
+    /// parameter sets are the parameter set NAL units of the decoder configuration record.
+    pub fn new(nal_length_size: usize, parameter_sets: &[Box<[u8]>]) -> Result<Self> {
+        if !(1..=4).contains(&nal_length_size) {
+            return decode_error("common (nal): invalid nal unit length size");
+        }
+
+        let parameter_sets = parameter_sets
+            .iter()
+            .flat_map(|nal_unit| [ANNEX_B_START_CODE.as_slice(), nal_unit])
+            .flatten()
+            .copied()
+            .collect();
+
+        Ok(AnnexBConverter { nal_length_size, parameter_sets })
+    }
+
+    /// Convert the length-prefixed NAL units in `buf` into an Annex B byte stream written to `out`.
+    /// The contents of `out` are replaced.
+    pub fn convert(&self, buf: &[u8], is_keyframe: bool, out: &mut Vec<u8>) -> Result<()> {
+        out.clear();
+
+        if is_keyframe {
+            out.extend_from_slice(&self.parameter_sets);
+        }
+
+        // The NAL unit headers are not inspected, therefore the syntax is irrelevant.
+        let framing = NalUnitFraming::LengthPrefixed(self.nal_length_size);
+        let mut iter = NalUnitIter::new(buf, NalUnitSyntax::Avc, framing)?;
+
+        while let Some(nal_unit) = iter.next_length_prefixed(self.nal_length_size)? {
+            out.extend_from_slice(&ANNEX_B_START_CODE);
+            out.extend_from_slice(nal_unit);
+        }
+
+        Ok(())
+    }
+}
+
+/// Find the position of the first 3-byte start code (0x000001) in the buffer.
+fn find_start_code(buf: &[u8]) -> Option<usize> {
+    buf.windows(3).position(|word| word == [0, 0, 1])
+}
+
+/// Remove the emulation prevention bytes from a NAL unit payload to get the raw byte sequence
+/// payload (RBSP).
+pub fn unescape_rbsp(buf: &[u8]) -> Vec<u8> {
+    let mut rbsp = Vec::with_capacity(buf.len());
+    let mut zeros = 0;
+
+    for &byte in buf {
+        // An emulation prevention byte (0x03) follows every two consecutive zero bytes.
+        if zeros >= 2 && byte == 0x03 {
+            zeros = 0;
+            continue;
+        }
+
+        zeros = if byte == 0 { zeros + 1 } else { 0 };
+        rbsp.push(byte);
+    }
+
+    rbsp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(iter: NalUnitIter<'_>) -> Vec<(u8, Vec<u8>)> {
+        iter.map(|item| item.map(|(nal_type, payload)| (nal_type, payload.to_vec())).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn verify_annex_b_nal_units() {
+        // A 4-byte start code, a SPS, a 3-byte start code, a PPS followed by a trailing zero byte,
+        // and an IDR slice with an emulation prevention byte.
+        let buf = [
+            0x00, 0x00, 0x00, 0x01, 0x67, 0x64, 0x00, 0x1f, //
+            0x00, 0x00, 0x01, 0x68, 0xee, 0x3c, 0x80, 0x00, //
+            0x00, 0x00, 0x01, 0x65, 0x88, 0x00, 0x00, 0x03, 0x01, 0x80,
+        ];
+
+        let iter = NalUnitIter::new(&buf, NalUnitSyntax::Avc, NalUnitFraming::AnnexB).unwrap();
+
+        assert_eq!(
+            collect(iter),
+            [
+                (7, vec![0x64, 0x00, 0x1f]),
+                (8, vec![0xee, 0x3c, 0x80]),
+                (5, vec![0x88, 0x00, 0x00, 0x03, 0x01, 0x80]),
+            ]
+        );
+
+        // Without a start code, there are no NAL units.
+        let iter = NalUnitIter::new(&[0x65, 0x88], NalUnitSyntax::Avc, NalUnitFraming::AnnexB);
+        assert_eq!(iter.unwrap().count(), 0);
+    }
+
+    #[test]
+    fn verify_length_prefixed_nal_units() {
+        // A 2-byte length prefixed VPS and IDR_W_RADL slice.
+        let buf = [0x00, 0x03, 0x40, 0x01, 0x0c, 0x00, 0x04, 0x26, 0x01, 0xaf, 0x80];
+
+        let iter =
+            NalUnitIter::new(&buf, NalUnitSyntax::Hevc, NalUnitFraming::LengthPrefixed(2)).unwrap();
+
+        assert_eq!(collect(iter), [(32, vec![0x0c]), (19, vec![0xaf, 0x80])]);
+
+        // A truncated NAL unit is an error, after which iteration stops.
+        let mut iter =
+            NalUnitIter::new(&buf[..9], NalUnitSyntax::Hevc, NalUnitFraming::LengthPrefixed(2))
+                .unwrap();
+
+        assert!(iter.next().unwrap().is_ok());
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+
+        // The length size must be 1 to 4 bytes.
+        assert!(
+            NalUnitIter::new(&buf, NalUnitSyntax::Hevc, NalUnitFraming::LengthPrefixed(0)).is_err()
+        );
+        assert!(
+            NalUnitIter::new(&buf, NalUnitSyntax::Hevc, NalUnitFraming::LengthPrefixed(5)).is_err()
+        );
+
+        // Empty NAL units, and NAL units shorter than the header, are skipped.
+        let buf = [0x00, 0x00, 0x00, 0x01, 0x40, 0x00, 0x03, 0x40, 0x01, 0x0c];
+
+        let iter =
+            NalUnitIter::new(&buf, NalUnitSyntax::Hevc, NalUnitFraming::LengthPrefixed(2)).unwrap();
+
+        assert_eq!(collect(iter), [(32, vec![0x0c])]);
+    }
+
+    #[test]
+    fn verify_annex_b_converter() {
+        let parameter_sets: [Box<[u8]>; 2] = [Box::new([0x67, 0x42]), Box::new([0x68, 0xce])];
+        let converter = AnnexBConverter::new(2, &parameter_sets).unwrap();
+
+        // A SEI and an IDR slice NAL unit, each with a 2-byte length prefix.
+        let buf = [0, 2, 0x06, 0x05, 0, 3, 0x65, 0x88, 0x80];
+        let mut out = vec![0xff; 4];
+
+        // The parameter sets precede the NAL units of a keyframe.
+        converter.convert(&buf, true, &mut out).unwrap();
+        assert_eq!(
+            out,
+            [
+                [0, 0, 0, 1, 0x67, 0x42].as_slice(),
+                &[0, 0, 0, 1, 0x68, 0xce],
+                &[0, 0, 0, 1, 0x06, 0x05],
+                &[0, 0, 0, 1, 0x65, 0x88, 0x80],
+            ]
+            .concat()
+        );
+
+        converter.convert(&buf, false, &mut out).unwrap();
+        assert_eq!(out, [0, 0, 0, 1, 0x06, 0x05, 0, 0, 0, 1, 0x65, 0x88, 0x80]);
+
+        // Truncated NAL unit.
+        assert!(converter.convert(&buf[..8], false, &mut out).is_err());
+
+        // The length size must be 1 to 4 bytes.
+        assert!(AnnexBConverter::new(0, &parameter_sets).is_err());
+    }
+
+    #[test]
+    fn verify_unescape_rbsp() {
+        assert_eq!(unescape_rbsp(&[0, 0, 3, 1, 0, 0, 3, 0, 0, 3]), [0, 0, 1, 0, 0, 0, 0]);
+        assert_eq!(unescape_rbsp(&[0, 3, 0, 0, 3]), [0, 3, 0, 0]);
+
+        // The payload of an Annex B NAL unit.
+        let buf = [0x00, 0x00, 0x01, 0x65, 0x88, 0x00, 0x00, 0x03, 0x01, 0x80];
+        let (_, payload) = NalUnitIter::new(&buf, NalUnitSyntax::Avc, NalUnitFraming::AnnexB)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(unescape_rbsp(payload), [0x88, 0x00, 0x00, 0x01, 0x80]);
+    }
+}
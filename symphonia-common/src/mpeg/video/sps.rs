@@ -0,0 +1,703 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use symphonia_core::codecs::CodecProfile;
+use symphonia_core::errors::{Result, decode_error};
+use symphonia_core::io::{BitReaderLtr, ReadBitsLtr};
+use symphonia_core::units::FrameRate;
+
+use super::HEVC_NAL_UNIT_SPS;
+use super::nal::unescape_rbsp;
+
+/// The H.264 sequence parameter set NAL unit type.
+const AVC_NAL_UNIT_SPS: u8 = 7;
+
+/// The H.264 sample aspect ratio indicator of an explicitly signalled aspect ratio.
+const ASPECT_RATIO_IDC_EXTENDED_SAR: u32 = 255;
+
+/// The maximum value of `log2_max_pic_order_cnt_lsb_minus4`.
+const MAX_LOG2_MAX_PIC_ORDER_CNT_LSB_MINUS4: u32 = 12;
+
+/// The maximum value of `abs_delta_rps_minus1`, and `delta_poc_s0_minus1` and
+/// `delta_poc_s1_minus1`, in a HEVC short-term reference picture set.
+const MAX_HEVC_DELTA_POC_MINUS1: u32 = (1 << 15) - 1;
+
+/// A H.264 sequence parameter set (ITU-T H.264, section 7.3.2.1.1).
+#[derive(Debug, Clone)]
+pub struct AVCSequenceParameterSet {
+    /// The profile (profile_idc).
+    pub profile: CodecProfile,
+    /// The level (level_idc).
+    pub level: u32,
+    /// The width in pixels after cropping.
+    pub width: u32,
+    /// The height in pixels after cropping.
+    pub height: u32,
+    /// The frame rate, if signalled by the VUI timing information.
+    pub frame_rate: Option<FrameRate>,
+}
+
+impl AVCSequenceParameterSet {
+    /// Read a sequence parameter set from a complete SPS NAL unit, including the NAL unit header.
+    pub fn read(nal_unit: &[u8]) -> Result<Self> {
+        match nal_unit.first() {
+            Some(header) if header & 0x1f == AVC_NAL_UNIT_SPS => (),
+            _ => return decode_error("common (avc): not a sequence parameter set"),
+        }
+
+        let rbsp = unescape_rbsp(&nal_unit[1..]);
+        let mut bs = BitReaderLtr::new(&rbsp);
+
+        let profile_idc = bs.read_bits_leq32(8)?;
+        let _constraint_set_flags = bs.read_bits_leq32(8)?;
+        let level_idc = bs.read_bits_leq32(8)?;
+        let _seq_parameter_set_id = read_ue(&mut bs)?;
+
+        let mut chroma_format_idc = 1;
+        let mut separate_colour_plane_flag = false;
+
+        if matches!(
+            profile_idc,
+            100 | 110 | 122 | 244 | 44 | 83 | 86 | 118 | 128 | 138 | 139 | 134 | 135
+        ) {
+            chroma_format_idc = read_ue(&mut bs)?;
+
+            if chroma_format_idc == 3 {
+                separate_colour_plane_flag = bs.read_bool()?;
+            }
+
+            let _bit_depth_luma_minus8 = read_ue(&mut bs)?;
+            let _bit_depth_chroma_minus8 = read_ue(&mut bs)?;
+            let _qpprime_y_zero_transform_bypass_flag = bs.read_bool()?;
+
+            // Scaling matrices.
+            if bs.read_bool()? {
+                let num_lists = if chroma_format_idc != 3 { 8 } else { 12 };
+
+                for i in 0..num_lists {
+                    if bs.read_bool()? {
+                        skip_avc_scaling_list(&mut bs, if i < 6 { 16 } else { 64 })?;
+                    }
+                }
+            }
+        }
+
+        let _log2_max_frame_num_minus4 = read_ue(&mut bs)?;
+
+        match read_ue(&mut bs)? {
+            0 => {
+                let _log2_max_pic_order_cnt_lsb_minus4 = read_ue(&mut bs)?;
+            }
+            1 => {
+                let _delta_pic_order_always_zero_flag = bs.read_bool()?;
+                let _offset_for_non_ref_pic = read_se(&mut bs)?;
+                let _offset_for_top_to_bottom_field = read_se(&mut bs)?;
+
+                for _ in 0..read_ue(&mut bs)? {
+                    let _offset_for_ref_frame = read_se(&mut bs)?;
+                }
+            }
+            _ => (),
+        }
+
+        let _max_num_ref_frames = read_ue(&mut bs)?;
+        let _gaps_in_frame_num_value_allowed_flag = bs.read_bool()?;
+
+        let pic_width_in_mbs = read_ue(&mut bs)?;
+        let pic_height_in_map_units = read_ue(&mut bs)?;
+        let frame_mbs_only_flag = bs.read_bool()?;
+
+        if !frame_mbs_only_flag {
+            let _mb_adaptive_frame_field_flag = bs.read_bool()?;
+        }
+
+        let _direct_8x8_inference_flag = bs.read_bool()?;
+
+        let crop = if bs.read_bool()? {
+            [read_ue(&mut bs)?, read_ue(&mut bs)?, read_ue(&mut bs)?, read_ue(&mut bs)?]
+        }
+        else {
+            [0; 4]
+        };
+
+        // The cropping units depend on the chroma sampling, and if the frame is field coded.
+        let chroma_array_type = if separate_colour_plane_flag { 0 } else { chroma_format_idc };
+
+        let (sub_width_c, sub_height_c) = chroma_subsampling(chroma_array_type);
+        let field_factor = if frame_mbs_only_flag { 1 } else { 2 };
+
+        let crop_unit_x = sub_width_c;
+        let crop_unit_y = sub_height_c * field_factor;
+
+        let coded_width = pic_width_in_mbs.checked_add(1).and_then(|mbs| mbs.checked_mul(16));
+        let coded_height = pic_height_in_map_units
+            .checked_add(1)
+            .and_then(|map_units| map_units.checked_mul(field_factor * 16));
+
+        let (Some(coded_width), Some(coded_height)) = (coded_width, coded_height)
+        else {
+            return decode_error("common (avc): invalid picture size");
+        };
+
+        let width = cropped_len(coded_width, crop_unit_x, crop[0], crop[1])?;
+        let height = cropped_len(coded_height, crop_unit_y, crop[2], crop[3])?;
+
+        // A frame spans two clock ticks.
+        let frame_rate = if bs.read_bool()? {
+            read_vui_timing(&mut bs, false)?.and_then(|(num_units_in_tick, time_scale)| {
+                make_frame_rate(time_scale, num_units_in_tick.checked_mul(2)?)
+            })
+        }
+        else {
+            None
+        };
+
+        Ok(AVCSequenceParameterSet {
+            profile: CodecProfile::new(profile_idc),
+            level: level_idc,
+            width,
+            height,
+            frame_rate,
+        })
+    }
+}
+
+/// A HEVC sequence parameter set (ITU-T H.265, section 7.3.2.2.1).
+#[derive(Debug, Clone)]
+pub struct HEVCSequenceParameterSet {
+    /// The general profile (general_profile_idc).
+    pub profile: CodecProfile,
+    /// The general level (general_level_idc).
+    pub level: u32,
+    /// The width in pixels after applying the conformance window.
+    pub width: u32,
+    /// The height in pixels after applying the conformance window.
+    pub height: u32,
+    /// The frame rate, if signalled by the VUI timing information.
+    pub frame_rate: Option<FrameRate>,
+}
+
+impl HEVCSequenceParameterSet {
+    /// Read a sequence parameter set from a complete SPS NAL unit, including the NAL unit header.
+    pub fn read(nal_unit: &[u8]) -> Result<Self> {
+        match nal_unit.first() {
+            Some(header) if nal_unit.len() >= 2 && (header >> 1) & 0x3f == HEVC_NAL_UNIT_SPS => (),
+            _ => return decode_error("common (hevc): not a sequence parameter set"),
+        }
+
+        let rbsp = unescape_rbsp(&nal_unit[2..]);
+        let mut bs = BitReaderLtr::new(&rbsp);
+
+        let _sps_video_parameter_set_id = bs.read_bits_leq32(4)?;
+        let max_sub_layers_minus1 = bs.read_bits_leq32(3)?;
+        let _sps_temporal_id_nesting_flag = bs.read_bool()?;
+
+        // The general profile, tier, and level.
+        let _general_profile_space = bs.read_bits_leq32(2)?;
+        let _general_tier_flag = bs.read_bool()?;
+        let general_profile_idc = bs.read_bits_leq32(5)?;
+        let _general_profile_compatibility_flags = bs.read_bits_leq32(32)?;
+        let _general_constraint_indicator_flags = bs.read_bits_leq64(48)?;
+        let general_level_idc = bs.read_bits_leq32(8)?;
+
+        // The sub-layer profiles, tiers, and levels.
+        let mut sub_layer_flags = Vec::new();
+
+        for _ in 0..max_sub_layers_minus1 {
+            sub_layer_flags.push((bs.read_bool()?, bs.read_bool()?));
+        }
+
+        if max_sub_layers_minus1 > 0 {
+            bs.ignore_bits(2 * (8 - max_sub_layers_minus1))?;
+        }
+
+        for (sub_layer_profile_present, sub_layer_level_present) in sub_layer_flags {
+            if sub_layer_profile_present {
+                bs.ignore_bits(88)?;
+            }
+            if sub_layer_level_present {
+                bs.ignore_bits(8)?;
+            }
+        }
+
+        let _sps_seq_parameter_set_id = read_ue(&mut bs)?;
+
+        let chroma_format_idc = read_ue(&mut bs)?;
+        let mut separate_colour_plane_flag = false;
+
+        if chroma_format_idc == 3 {
+            separate_colour_plane_flag = bs.read_bool()?;
+        }
+
+        let pic_width_in_luma_samples = read_ue(&mut bs)?;
+        let pic_height_in_luma_samples = read_ue(&mut bs)?;
+
+        let conf_win = if bs.read_bool()? {
+            [read_ue(&mut bs)?, read_ue(&mut bs)?, read_ue(&mut bs)?, read_ue(&mut bs)?]
+        }
+        else {
+            [0; 4]
+        };
+
+        let chroma_array_type = if separate_colour_plane_flag { 0 } else { chroma_format_idc };
+        let (sub_width_c, sub_height_c) = chroma_subsampling(chroma_array_type);
+
+        let width = cropped_len(pic_width_in_luma_samples, sub_width_c, conf_win[0], conf_win[1])?;
+        let height =
+            cropped_len(pic_height_in_luma_samples, sub_height_c, conf_win[2], conf_win[3])?;
+
+        let _bit_depth_luma_minus8 = read_ue(&mut bs)?;
+        let _bit_depth_chroma_minus8 = read_ue(&mut bs)?;
+        let log2_max_pic_order_cnt_lsb_minus4 = read_ue(&mut bs)?;
+
+        if log2_max_pic_order_cnt_lsb_minus4 > MAX_LOG2_MAX_PIC_ORDER_CNT_LSB_MINUS4 {
+            return decode_error("common (hevc): invalid maximum picture order count");
+        }
+
+        let log2_max_pic_order_cnt_lsb = log2_max_pic_order_cnt_lsb_minus4 + 4;
+
+        let sps_sub_layer_ordering_info_present_flag = bs.read_bool()?;
+        let first_sub_layer =
+            if sps_sub_layer_ordering_info_present_flag { 0 } else { max_sub_layers_minus1 };
+
+        for _ in first_sub_layer..=max_sub_layers_minus1 {
+            let _sps_max_dec_pic_buffering_minus1 = read_ue(&mut bs)?;
+            let _sps_max_num_reorder_pics = read_ue(&mut bs)?;
+            let _sps_max_latency_increase_plus1 = read_ue(&mut bs)?;
+        }
+
+        let _log2_min_luma_coding_block_size_minus3 = read_ue(&mut bs)?;
+        let _log2_diff_max_min_luma_coding_block_size = read_ue(&mut bs)?;
+        let _log2_min_luma_transform_block_size_minus2 = read_ue(&mut bs)?;
+        let _log2_diff_max_min_luma_transform_block_size = read_ue(&mut bs)?;
+        let _max_transform_hierarchy_depth_inter = read_ue(&mut bs)?;
+        let _max_transform_hierarchy_depth_intra = read_ue(&mut bs)?;
+
+        // Scaling lists.
+        if bs.read_bool()? && bs.read_bool()? {
+            skip_hevc_scaling_list_data(&mut bs)?;
+        }
+
+        let _amp_enabled_flag = bs.read_bool()?;
+        let _sample_adaptive_offset_enabled_flag = bs.read_bool()?;
+
+        // PCM.
+        if bs.read_bool()? {
+            let _pcm_sample_bit_depth_luma_minus1 = bs.read_bits_leq32(4)?;
+            let _pcm_sample_bit_depth_chroma_minus1 = bs.read_bits_leq32(4)?;
+            let _log2_min_pcm_luma_coding_block_size_minus3 = read_ue(&mut bs)?;
+            let _log2_diff_max_min_pcm_luma_coding_block_size = read_ue(&mut bs)?;
+            let _pcm_loop_filter_disabled_flag = bs.read_bool()?;
+        }
+
+        // Short-term reference picture sets.
+        let num_short_term_ref_pic_sets = read_ue(&mut bs)?;
+
+        if num_short_term_ref_pic_sets > 64 {
+            return decode_error("common (hevc): invalid number of short-term reference sets");
+        }
+
+        let mut st_ref_pic_sets = Vec::with_capacity(num_short_term_ref_pic_sets as usize);
+
+        for idx in 0..num_short_term_ref_pic_sets as usize {
+            let delta_pocs = read_hevc_st_ref_pic_set(&mut bs, idx, &st_ref_pic_sets)?;
+            st_ref_pic_sets.push(delta_pocs);
+        }
+
+        // Long-term reference pictures.
+        if bs.read_bool()? {
+            for _ in 0..read_ue(&mut bs)? {
+                let _lt_ref_pic_poc_lsb_sps = bs.read_bits_leq32(log2_max_pic_order_cnt_lsb)?;
+                let _used_by_curr_pic_lt_sps_flag = bs.read_bool()?;
+            }
+        }
+
+        let _sps_temporal_mvp_enabled_flag = bs.read_bool()?;
+        let _strong_intra_smoothing_enabled_flag = bs.read_bool()?;
+
+        let frame_rate = if bs.read_bool()? {
+            read_vui_timing(&mut bs, true)?.and_then(|(num_units_in_tick, time_scale)| {
+                make_frame_rate(time_scale, num_units_in_tick)
+            })
+        }
+        else {
+            None
+        };
+
+        Ok(HEVCSequenceParameterSet {
+            profile: CodecProfile::new(general_profile_idc),
+            level: general_level_idc,
+            width,
+            height,
+            frame_rate,
+        })
+    }
+}
+
+/// Read an unsigned Exp-Golomb coded value (ue(v)).
+fn read_ue(bs: &mut BitReaderLtr<'_>) -> Result<u32> {
+    let num_zeros = bs.read_unary_zeros()?;
+
+    if num_zeros > 31 {
+        return decode_error("common (mpeg): exp-golomb value is too large");
+    }
+
+    Ok(((1u64 << num_zeros) - 1 + bs.read_bits_leq64(num_zeros)?) as u32)
+}
+
+/// Read a signed Exp-Golomb coded value (se(v)).
+fn read_se(bs: &mut BitReaderLtr<'_>) -> Result<i32> {
+    let value = i64::from(read_ue(bs)?);
+
+    let value = if value & 1 == 1 { (value + 1) / 2 } else { -(value / 2) };
+
+    Ok(value as i32)
+}
+
+/// Get the horizontal and vertical chroma subsampling factors (SubWidthC and SubHeightC) for a
+/// chroma array type.
+fn chroma_subsampling(chroma_array_type: u32) -> (u32, u32) {
+    match chroma_array_type {
+        1 => (2, 2),
+        2 => (2, 1),
+        _ => (1, 1),
+    }
+}
+
+/// Get the length of a dimension after cropping the given number of units from each side.
+fn cropped_len(len: u32, unit: u32, start: u32, end: u32) -> Result<u32> {
+    let crop = start.checked_add(end).and_then(|crop| crop.checked_mul(unit));
+
+    match crop.and_then(|crop| len.checked_sub(crop)) {
+        Some(len) if len > 0 => Ok(len),
+        _ => decode_error("common (mpeg): invalid cropping"),
+    }
+}
+
+/// Make a reduced frame rate from a time scale and frame duration in ticks.
+fn make_frame_rate(time_scale: u32, frame_ticks: u32) -> Option<FrameRate> {
+    let gcd = gcd(time_scale, frame_ticks);
+
+    FrameRate::try_new(time_scale.checked_div(gcd)?, frame_ticks.checked_div(gcd)?)
+}
+
+fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Read the VUI parameters up-to and including the timing information, and return the number of
+/// units in a tick and the time scale, if present.
+///
+/// The H.264 and HEVC VUI parameters are identical up-to the timing information, except for a few
+/// additional HEVC fields.
+fn read_vui_timing(bs: &mut BitReaderLtr<'_>, is_hevc: bool) -> Result<Option<(u32, u32)>> {
+    // Aspect ratio information.
+    if bs.read_bool()? && bs.read_bits_leq32(8)? == ASPECT_RATIO_IDC_EXTENDED_SAR {
+        let _sar_width = bs.read_bits_leq32(16)?;
+        let _sar_height = bs.read_bits_leq32(16)?;
+    }
+
+    // Overscan information.
+    if bs.read_bool()? {
+        let _overscan_appropriate_flag = bs.read_bool()?;
+    }
+
+    // Video signal type.
+    if bs.read_bool()? {
+        let _video_format = bs.read_bits_leq32(3)?;
+        let _video_full_range_flag = bs.read_bool()?;
+
+        // Colour description.
+        if bs.read_bool()? {
+            let _colour_primaries = bs.read_bits_leq32(8)?;
+            let _transfer_characteristics = bs.read_bits_leq32(8)?;
+            let _matrix_coefficients = bs.read_bits_leq32(8)?;
+        }
+    }
+
+    // Chroma sample location.
+    if bs.read_bool()? {
+        let _chroma_sample_loc_type_top_field = read_ue(bs)?;
+        let _chroma_sample_loc_type_bottom_field = read_ue(bs)?;
+    }
+
+    if is_hevc {
+        let _neutral_chroma_indication_flag = bs.read_bool()?;
+        let _field_seq_flag = bs.read_bool()?;
+        let _frame_field_info_present_flag = bs.read_bool()?;
+
+        // Default display window.
+        if bs.read_bool()? {
+            for _ in 0..4 {
+                let _def_disp_win_offset = read_ue(bs)?;
+            }
+        }
+    }
+
+    // Timing information.
+    if !bs.read_bool()? {
+        return Ok(None);
+    }
+
+    let num_units_in_tick = bs.read_bits_leq32(32)?;
+    let time_scale = bs.read_bits_leq32(32)?;
+
+    Ok(Some((num_units_in_tick, time_scale)))
+}
+
+/// Skip a H.264 scaling list of the given size.
+fn skip_avc_scaling_list(bs: &mut BitReaderLtr<'_>, size: usize) -> Result<()> {
+    let mut last_scale = 8;
+    let mut next_scale = 8;
+
+    for _ in 0..size {
+        if next_scale != 0 {
+            let delta_scale = read_se(bs)?;
+
+            if !(-128..=127).contains(&delta_scale) {
+                return decode_error("common (avc): invalid scaling list delta");
+            }
+
+            next_scale = (last_scale + delta_scale + 256) % 256;
+        }
+
+        if next_scale != 0 {
+            last_scale = next_scale;
+        }
+    }
+
+    Ok(())
+}
+
+/// Skip the HEVC scaling list data.
+fn skip_hevc_scaling_list_data(bs: &mut BitReaderLtr<'_>) -> Result<()> {
+    for size_id in 0..4 {
+        let num_matrices = if size_id == 3 { 2 } else { 6 };
+
+        for _ in 0..num_matrices {
+            if !bs.read_bool()? {
+                let _scaling_list_pred_matrix_id_delta = read_ue(bs)?;
+                continue;
+            }
+
+            let coef_num = 64.min(1 << (4 + (size_id << 1)));
+
+            if size_id > 1 {
+                let _scaling_list_dc_coef_minus8 = read_se(bs)?;
+            }
+
+            for _ in 0..coef_num {
+                let _scaling_list_delta_coef = read_se(bs)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Read a HEVC short-term reference picture set, and return its delta POCs.
+fn read_hevc_st_ref_pic_set(
+    bs: &mut BitReaderLtr<'_>,
+    idx: usize,
+    st_ref_pic_sets: &[Vec<i32>],
+) -> Result<Vec<i32>> {
+    let inter_ref_pic_set_prediction_flag = idx != 0 && bs.read_bool()?;
+
+    let mut delta_pocs = Vec::new();
+
+    if inter_ref_pic_set_prediction_flag {
+        // In a sequence parameter set, the reference set is always the previous set.
+        let ref_delta_pocs = &st_ref_pic_sets[idx - 1];
+
+        let delta_rps_sign = bs.read_bool()?;
+        let abs_delta_rps = read_hevc_delta_poc(bs)?;
+        let delta_rps = if delta_rps_sign { -abs_delta_rps } else { abs_delta_rps };
+
+        // Each delta POC of the reference set, followed by the reference set itself, may be used
+        // to predict a delta POC of this set.
+        for j in 0..=ref_delta_pocs.len() {
+            let used_by_curr_pic_flag = bs.read_bool()?;
+            let use_delta_flag = used_by_curr_pic_flag || bs.read_bool()?;
+
+            let Some(delta_poc) =
+                delta_rps.checked_add(ref_delta_pocs.get(j).copied().unwrap_or(0))
+            else {
+                return decode_error("common (hevc): invalid short-term reference set");
+            };
+
+            if use_delta_flag && delta_poc != 0 {
+                delta_pocs.push(delta_poc);
+            }
+        }
+    }
+    else {
+        let num_negative_pics = read_ue(bs)?;
+        let num_positive_pics = read_ue(bs)?;
+
+        if num_negative_pics > 16 || num_positive_pics > 16 {
+            return decode_error("common (hevc): invalid short-term reference set");
+        }
+
+        // With at-most 16 deltas of at-most 2^15 each, the delta POCs cannot overflow.
+        let mut poc = 0;
+
+        for _ in 0..num_negative_pics {
+            poc -= read_hevc_delta_poc(bs)?;
+            let _used_by_curr_pic_s0_flag = bs.read_bool()?;
+            delta_pocs.push(poc);
+        }
+
+        poc = 0;
+
+        for _ in 0..num_positive_pics {
+            poc += read_hevc_delta_poc(bs)?;
+            let _used_by_curr_pic_s1_flag = bs.read_bool()?;
+            delta_pocs.push(poc);
+        }
+    }
+
+    if delta_pocs.len() > 32 {
+        return decode_error("common (hevc): invalid short-term reference set");
+    }
+
+    Ok(delta_pocs)
+}
+
+/// Read a HEVC short-term reference picture set delta coded as the delta minus one.
+fn read_hevc_delta_poc(bs: &mut BitReaderLtr<'_>) -> Result<i32> {
+    match read_ue(bs)? {
+        delta_minus1 if delta_minus1 <= MAX_HEVC_DELTA_POC_MINUS1 => Ok(delta_minus1 as i32 + 1),
+        _ => decode_error("common (hevc): invalid short-term reference set delta"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A High profile, level 4.0, 1920x1080 SPS with VUI timing information for 23.976 fps. The
+    /// coded height of 1088 is cropped by 8 lines.
+    const AVC_SPS_1080P: [u8; 26] = [
+        0x67, 0x64, 0x00, 0x28, 0xac, 0xd9, 0x40, 0x78, 0x02, 0x27, 0xe5, 0xc0, 0x5a, 0x80, 0x80,
+        0x80, 0xa0, 0x00, 0x00, 0x7d, 0x20, 0x00, 0x17, 0x70, 0x10, 0x80,
+    ];
+
+    /// A Main profile, level 4.0, 1920x1080 SPS with VUI timing information for 25 fps. The coded
+    /// height of 1088 is cropped by the conformance window. Contains emulation prevention bytes,
+    /// and both explicit and predicted short-term reference picture sets.
+    const HEVC_SPS_1080P: [u8; 44] = [
+        0x42, 0x01, 0x01, 0x01, 0x60, 0x00, 0x00, 0x03, 0x00, 0x90, 0x00, 0x00, 0x03, 0x00, 0x00,
+        0x03, 0x00, 0x78, 0xa0, 0x03, 0xc0, 0x80, 0x11, 0x07, 0xcb, 0x96, 0x57, 0x92, 0x4d, 0x9a,
+        0xf7, 0x70, 0x08, 0x00, 0x00, 0x03, 0x00, 0x08, 0x00, 0x00, 0x03, 0x00, 0xc8, 0x80,
+    ];
+
+    #[test]
+    fn verify_avc_sps() {
+        let sps = AVCSequenceParameterSet::read(&AVC_SPS_1080P).unwrap();
+
+        assert_eq!(sps.profile, CodecProfile::new(100));
+        assert_eq!(sps.level, 40);
+        assert_eq!((sps.width, sps.height), (1920, 1080));
+        assert_eq!(sps.frame_rate, FrameRate::try_new(24000, 1001));
+
+        // Not a SPS NAL unit.
+        assert!(AVCSequenceParameterSet::read(&[0x68, 0xee, 0x3c, 0x80]).is_err());
+        // Truncated.
+        assert!(AVCSequenceParameterSet::read(&AVC_SPS_1080P[..8]).is_err());
+    }
+
+    #[test]
+    fn verify_hevc_sps() {
+        let sps = HEVCSequenceParameterSet::read(&HEVC_SPS_1080P).unwrap();
+
+        assert_eq!(sps.profile, CodecProfile::new(1));
+        assert_eq!(sps.level, 120);
+        assert_eq!((sps.width, sps.height), (1920, 1080));
+        assert_eq!(sps.frame_rate, FrameRate::try_new(25, 1));
+
+        // Not a SPS NAL unit.
+        assert!(HEVCSequenceParameterSet::read(&[0x40, 0x01, 0x0c]).is_err());
+    }
+
+    #[test]
+    fn verify_exp_golomb() {
+        // 1, 010, 011, 00100, 00101, and 0001000.
+        let buf = [0b1010_0110, 0b0100_0010, 0b1000_1000];
+        let mut bs = BitReaderLtr::new(&buf);
+
+        assert_eq!(read_ue(&mut bs).unwrap(), 0);
+        assert_eq!(read_ue(&mut bs).unwrap(), 1);
+        assert_eq!(read_se(&mut bs).unwrap(), -1);
+        assert_eq!(read_ue(&mut bs).unwrap(), 3);
+        assert_eq!(read_se(&mut bs).unwrap(), -2);
+        assert_eq!(read_se(&mut bs).unwrap(), 4);
+    }
+
+    /// Mutate every byte of a valid SPS to every possible value, and truncate it at every length.
+    fn mutate_sps(sps: &[u8], read: impl Fn(&[u8])) {
+        let mut buf = sps.to_vec();
+
+        for i in 0..buf.len() {
+            for value in 0..=255 {
+                buf[i] = value;
+                read(&buf);
+            }
+            buf[i] = sps[i];
+        }
+
+        for len in 0..sps.len() {
+            read(&sps[..len]);
+        }
+    }
+
+    /// Generate SPS NAL units with the given NAL unit header followed by a pseudo-random payload.
+    fn random_sps(header: &[u8], read: impl Fn(&[u8])) {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+
+        for len in 1..64 {
+            for _ in 0..64 {
+                let mut buf = header.to_vec();
+
+                buf.extend((0..len).map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    state as u8
+                }));
+
+                read(&buf);
+            }
+        }
+    }
+
+    #[test]
+    fn verify_malformed_avc_sps() {
+        // A picture width of 2^28 macroblocks.
+        let sps = [0x67, 0x42, 0x00, 0x1e, 0xda, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x79];
+        assert!(AVCSequenceParameterSet::read(&sps).is_err());
+
+        mutate_sps(&AVC_SPS_1080P, |buf| {
+            let _ = AVCSequenceParameterSet::read(buf);
+        });
+        random_sps(&[0x67], |buf| {
+            let _ = AVCSequenceParameterSet::read(buf);
+        });
+    }
+
+    #[test]
+    fn verify_malformed_hevc_sps() {
+        mutate_sps(&HEVC_SPS_1080P, |buf| {
+            let _ = HEVCSequenceParameterSet::read(buf);
+        });
+        random_sps(&[0x42, 0x01], |buf| {
+            let _ = HEVCSequenceParameterSet::read(buf);
+        });
+    }
+}
@@ -13,4 +13,5 @@
 #![allow(clippy::manual_range_contains)]
 
 pub mod flac;
+pub mod opus;
 pub mod vorbis;
@@ -0,0 +1,10 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+/// The maximum number of audio frames in an Opus packet. A packet may contain up-to 120 ms of
+/// audio, and Opus is always decoded at 48 kHz.
+pub const OPUS_MAX_FRAMES_PER_PACKET: u64 = 5760;
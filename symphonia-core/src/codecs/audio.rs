@@ -454,6 +454,8 @@ pub mod well_known {
     pub const CODEC_ID_BINK_AUDIO: AudioCodecId = AudioCodecId(0x1019);
     /// Smacker Audio
     pub const CODEC_ID_SMACKER_AUDIO: AudioCodecId = AudioCodecId(0x1020);
+    /// MPEG-H 3D Audio (ISO/IEC 23008-3)
+    pub const CODEC_ID_MPEGH_3D_AUDIO: AudioCodecId = AudioCodecId(0x1021);
 
     // Compressed lossless audio codecs
     //---------------------------------
@@ -496,5 +498,37 @@ pub mod well_known {
         pub const CODEC_PROFILE_AAC_HE_V2: CodecProfile = CodecProfile(28);
         /// Extended HE-AAC (xHE-AAC) Profile (using Unified Speech and Audio Coding)
         pub const CODEC_PROFILE_AAC_USAC: CodecProfile = CodecProfile(41);
+
+        // E-AC-3 Profiles
+        //----------------
+
+        /// E-AC-3 with Joint Object Coding (JOC), used to carry Dolby Atmos
+        pub const CODEC_PROFILE_EAC3_JOC: CodecProfile = CodecProfile(1);
+
+        // DTS Profiles
+        //-------------
+
+        /// DTS core audio
+        pub const CODEC_PROFILE_DTS: CodecProfile = CodecProfile(0);
+        /// DTS-ES (Extended Surround)
+        pub const CODEC_PROFILE_DTS_ES: CodecProfile = CodecProfile(1);
+        /// DTS 96/24
+        pub const CODEC_PROFILE_DTS_96_24: CodecProfile = CodecProfile(2);
+        /// DTS-HD High Resolution Audio
+        pub const CODEC_PROFILE_DTS_HD_HRA: CodecProfile = CodecProfile(3);
+        /// DTS-HD Master Audio
+        pub const CODEC_PROFILE_DTS_HD_MA: CodecProfile = CodecProfile(4);
+        /// DTS-HD Master Audio with DTS:X
+        pub const CODEC_PROFILE_DTS_HD_MA_X: CodecProfile = CodecProfile(5);
+        /// DTS-HD Master Audio with IMAX Enhanced DTS:X
+        pub const CODEC_PROFILE_DTS_HD_MA_X_IMAX: CodecProfile = CodecProfile(6);
+        /// DTS Express
+        pub const CODEC_PROFILE_DTS_EXPRESS: CodecProfile = CodecProfile(7);
+
+        // TrueHD Profiles
+        //----------------
+
+        /// TrueHD with a 16-channel presentation, used to carry Dolby Atmos
+        pub const CODEC_PROFILE_TRUEHD_ATMOS: CodecProfile = CodecProfile(1);
     }
 }
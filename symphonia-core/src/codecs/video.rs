@@ -13,6 +13,7 @@ use std::fmt;
 use crate::codecs::CodecInfo;
 use crate::codecs::CodecProfile;
 use crate::common::FourCc;
+use crate::units::FrameRate;
 
 /// An `VideoCodecId` is a unique identifier used to identify a specific video codec.
 ///
@@ -78,6 +79,71 @@ pub struct VideoExtraData {
     pub data: Box<[u8]>,
 }
 
+/// Mastering display color volume metadata (SMPTE ST 2086).
+///
+/// Describes the color volume of the display used to master the video content.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct MasteringDisplay {
+    /// The CIE 1931 (x, y) chromaticity coordinates of the red, green, and blue display primaries,
+    /// in that order.
+    pub primaries: [(f32, f32); 3],
+    /// The CIE 1931 (x, y) chromaticity coordinates of the white point.
+    pub white_point: (f32, f32),
+    /// The maximum display luminance in candelas per square metre.
+    pub max_luminance: f32,
+    /// The minimum display luminance in candelas per square metre.
+    pub min_luminance: f32,
+}
+
+/// Content light level metadata (CTA-861.3).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContentLightLevel {
+    /// The maximum content light level (MaxCLL) in candelas per square metre.
+    pub max_cll: u16,
+    /// The maximum frame-average light level (MaxFALL) in candelas per square metre.
+    pub max_fall: u16,
+}
+
+/// The projection used to map spherical (360 degree) video onto the frame.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ProjectionType {
+    /// No projection. The video is a flat rectangle.
+    Rectangular,
+    /// Equirectangular projection.
+    Equirectangular,
+    /// Cubemap projection.
+    Cubemap,
+    /// Mesh projection.
+    Mesh,
+}
+
+/// Spherical (360 degree) video projection metadata.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Projection {
+    /// The projection type.
+    pub projection_type: ProjectionType,
+    /// Projection-specific data, if any. The format of the data depends on the container and the
+    /// projection type.
+    pub private: Option<Box<[u8]>>,
+    /// The yaw of the default viewing direction, in degrees.
+    pub yaw: f32,
+    /// The pitch of the default viewing direction, in degrees.
+    pub pitch: f32,
+    /// The roll of the default viewing direction, in degrees.
+    pub roll: f32,
+}
+
+/// The arrangement of the views of stereoscopic 3D video within the frame.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StereoMode {
+    /// Monoscopic video. The frame contains a single view.
+    Mono,
+    /// The left eye view is above the right eye view.
+    TopBottom,
+    /// The left eye view is to the left of the right eye view.
+    LeftRight,
+}
+
 /// Codec parameters for video codecs.
 #[derive(Clone, Debug, Default)]
 pub struct VideoCodecParameters {
@@ -88,11 +154,35 @@ pub struct VideoCodecParameters {
     /// The codec-defined level.
     pub level: Option<u32>,
     /// Video width.
+    ///
+    /// If the codec configuration signals the frame size, such as in a H.264 or HEVC sequence
+    /// parameter set, then this is the cropped width signalled by the codec, and takes precedence
+    /// over the width signalled by the container.
     pub width: Option<u16>,
     /// Video height.
+    ///
+    /// If the codec configuration signals the frame size, such as in a H.264 or HEVC sequence
+    /// parameter set, then this is the cropped height signalled by the codec, and takes precedence
+    /// over the height signalled by the container.
     pub height: Option<u16>,
+    /// The video width and height signalled by the container, if they differ from `width` and
+    /// `height`.
+    pub container_size: Option<(u16, u16)>,
+    /// The nominal frame rate, if known.
+    pub frame_rate: Option<FrameRate>,
     /// Extra data (defined by the codec).
     pub extra_data: Vec<VideoExtraData>,
+    /// The mastering display color volume, if known.
+    pub mastering_display: Option<MasteringDisplay>,
+    /// The content light level, if known.
+    pub content_light_level: Option<ContentLightLevel>,
+    /// The clockwise rotation, in degrees, that should be applied to decoded frames for display.
+    /// One of 0, 90, 180, or 270.
+    pub rotation_degrees: u16,
+    /// The spherical video projection, if known.
+    pub projection: Option<Projection>,
+    /// The stereoscopic 3D layout, if known.
+    pub stereo_mode: Option<StereoMode>,
 }
 
 impl VideoCodecParameters {
@@ -126,11 +216,79 @@ impl VideoCodecParameters {
         self
     }
 
+    /// Provide the video width and height signalled by the container.
+    pub fn with_container_size(&mut self, width: u16, height: u16) -> &mut Self {
+        self.container_size = Some((width, height));
+        self
+    }
+
+    /// Provide the video width and height signalled by the codec configuration, such as a sequence
+    /// parameter set.
+    ///
+    /// The codec size takes precedence over the size signalled by the container. If a different,
+    /// non-zero, size was already provided, it is retained as the container size. A size that
+    /// cannot be represented is ignored.
+    pub fn with_codec_size(&mut self, width: u32, height: u32) -> &mut Self {
+        let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(height))
+        else {
+            return self;
+        };
+
+        if let (Some(cur_width), Some(cur_height)) = (self.width, self.height) {
+            if cur_width != 0 && cur_height != 0 && (cur_width, cur_height) != (width, height) {
+                self.container_size = Some((cur_width, cur_height));
+            }
+        }
+
+        self.width = Some(width);
+        self.height = Some(height);
+        self
+    }
+
+    /// Provide the nominal frame rate.
+    pub fn with_frame_rate(&mut self, frame_rate: FrameRate) -> &mut Self {
+        self.frame_rate = Some(frame_rate);
+        self
+    }
+
     /// Adds codec's extra data.
     pub fn add_extra_data(&mut self, data: VideoExtraData) -> &mut Self {
         self.extra_data.push(data);
         self
     }
+
+    /// Provide the mastering display color volume.
+    pub fn with_mastering_display(&mut self, mastering_display: MasteringDisplay) -> &mut Self {
+        self.mastering_display = Some(mastering_display);
+        self
+    }
+
+    /// Provide the content light level.
+    pub fn with_content_light_level(
+        &mut self,
+        content_light_level: ContentLightLevel,
+    ) -> &mut Self {
+        self.content_light_level = Some(content_light_level);
+        self
+    }
+
+    /// Provide the clockwise display rotation in degrees.
+    pub fn with_rotation_degrees(&mut self, rotation_degrees: u16) -> &mut Self {
+        self.rotation_degrees = rotation_degrees;
+        self
+    }
+
+    /// Provide the spherical video projection.
+    pub fn with_projection(&mut self, projection: Projection) -> &mut Self {
+        self.projection = Some(projection);
+        self
+    }
+
+    /// Provide the stereoscopic 3D layout.
+    pub fn with_stereo_mode(&mut self, stereo_mode: StereoMode) -> &mut Self {
+        self.stereo_mode = Some(stereo_mode);
+        self
+    }
 }
 
 /// `VideoDecoderOptions` is a common set of options that all subtitle decoders use.
@@ -89,4 +89,9 @@ impl Limit {
             Limit::Maximum(max) => Some(*max),
         }
     }
+
+    /// Returns true if `value` exceeds the limit, or the default value if the limit is defaulted.
+    pub fn is_exceeded_by(&self, value: usize, default: usize) -> bool {
+        self.limit_or_default(default).is_some_and(|limit| value > limit)
+    }
 }
@@ -16,8 +16,11 @@ use std::result;
 #[non_exhaustive]
 #[derive(Debug)]
 pub enum SeekErrorKind {
-    /// The stream is not seekable at all.
+    /// The stream is not seekable at all because the underlying source does not support seeking.
     Unseekable,
+    /// The format, or the particular media, does not support seeking. For example, the media has
+    /// no tracks, lacks timing information, or lacks the index required to seek.
+    Unsupported,
     /// The stream can only be seeked forward.
     ForwardOnly,
     /// The timestamp to seek to is out of range.
@@ -30,6 +33,7 @@ impl SeekErrorKind {
     fn as_str(&self) -> &'static str {
         match *self {
             SeekErrorKind::Unseekable => "stream is not seekable",
+            SeekErrorKind::Unsupported => "format does not support seeking",
             SeekErrorKind::ForwardOnly => "stream can only be seeked forward",
             SeekErrorKind::OutOfRange => "requested seek timestamp is out-of-range for stream",
             SeekErrorKind::InvalidTrack => "invalid track id",
@@ -0,0 +1,247 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! The `backfill` module provides a `FormatReader` adapter that infers missing packet durations.
+
+use std::borrow::Cow;
+use std::collections::VecDeque;
+
+use crate::errors::Result;
+use crate::formats::{
+    Attachment, FormatCapabilities, FormatInfo, FormatReader, MediaInfo, SeekMode, SeekTo,
+    SeekedTo, Track, TrackGroup, TrackType,
+};
+use crate::io::MediaSourceStream;
+use crate::meta::{ChapterGroup, Metadata};
+use crate::packet::{Packet, PacketRef};
+use crate::units::Time;
+
+/// The maximum number of packets that will be buffered while searching for the next packet of a
+/// track.
+const MAX_LOOKAHEAD_PACKETS: usize = 256;
+
+/// `DurationBackfill` wraps a `FormatReader` and infers the duration of packets that have a
+/// duration of 0.
+///
+/// Some containers do not store the duration of a packet. For these packets, the duration is
+/// inferred to be the difference between the presentation timestamp of the packet and that of the
+/// next packet of the same track. To do so, packets are buffered until the next packet of the
+/// same track is read. Packets are always returned in the order they were read from the wrapped
+/// reader.
+///
+/// The duration of the last packet of a track, or of a packet followed by a packet with an earlier
+/// timestamp, cannot be inferred and remains 0.
+pub struct DurationBackfill<'r> {
+    inner: Box<dyn FormatReader + 'r>,
+    /// Packets read from the wrapped reader, but not yet returned.
+    packets: VecDeque<Packet>,
+    /// If `true`, the wrapped reader has no more packets.
+    is_eos: bool,
+}
+
+impl<'r> DurationBackfill<'r> {
+    /// Wrap the provided `FormatReader`.
+    pub fn new(inner: Box<dyn FormatReader + 'r>) -> Self {
+        DurationBackfill { inner, packets: Default::default(), is_eos: false }
+    }
+
+    /// Get a reference to the wrapped `FormatReader`.
+    pub fn inner(&self) -> &dyn FormatReader {
+        self.inner.as_ref()
+    }
+
+    /// Discard all buffered packets.
+    fn clear(&mut self) {
+        self.packets.clear();
+        self.is_eos = false;
+    }
+
+    /// Read the next packet from the wrapped reader into the buffer. Returns `false` if the wrapped
+    /// reader has no more packets.
+    fn read_ahead(&mut self) -> Result<bool> {
+        if !self.is_eos {
+            match self.inner.next_packet()? {
+                Some(packet) => self.packets.push_back(packet),
+                None => self.is_eos = true,
+            }
+        }
+        Ok(!self.is_eos)
+    }
+}
+
+impl FormatReader for DurationBackfill<'_> {
+    fn format_info(&self) -> &FormatInfo {
+        self.inner.format_info()
+    }
+
+    fn media_info(&self) -> &MediaInfo {
+        self.inner.media_info()
+    }
+
+    fn attachments(&self) -> &[Attachment] {
+        self.inner.attachments()
+    }
+
+    fn chapters(&self) -> Option<&ChapterGroup> {
+        self.inner.chapters()
+    }
+
+    fn metadata(&mut self) -> Metadata<'_> {
+        self.inner.metadata()
+    }
+
+    fn seek(&mut self, mode: SeekMode, to: SeekTo) -> Result<SeekedTo> {
+        self.clear();
+        self.inner.seek(mode, to)
+    }
+
+    fn seek_time(&mut self, time: Time) -> Result<Vec<SeekedTo>> {
+        self.clear();
+        self.inner.seek_time(time)
+    }
+
+    fn tracks(&self) -> &[Track] {
+        self.inner.tracks()
+    }
+
+    fn track_by_id(&self, id: u32) -> Option<&Track> {
+        self.inner.track_by_id(id)
+    }
+
+    fn duration(&self) -> Option<Time> {
+        self.inner.duration()
+    }
+
+    fn first_track(&self, track_type: TrackType) -> Option<&Track> {
+        self.inner.first_track(track_type)
+    }
+
+    fn first_track_known_codec(&self, track_type: TrackType) -> Option<&Track> {
+        self.inner.first_track_known_codec(track_type)
+    }
+
+    fn default_track(&self, track_type: TrackType) -> Option<&Track> {
+        self.inner.default_track(track_type)
+    }
+
+    fn track_groups(&self) -> Vec<TrackGroup> {
+        self.inner.track_groups()
+    }
+
+    fn set_track_filter(&mut self, track_ids: &[u32]) -> Result<()> {
+        self.inner.set_track_filter(track_ids)?;
+
+        // Drop buffered packets that no longer pass the filter.
+        if !track_ids.is_empty() {
+            self.packets.retain(|packet| track_ids.contains(&packet.track_id));
+        }
+
+        Ok(())
+    }
+
+    fn is_track_selected(&self, track_id: u32) -> bool {
+        self.inner.is_track_selected(track_id)
+    }
+
+    fn next_packet(&mut self) -> Result<Option<Packet>> {
+        if self.packets.is_empty() && !self.read_ahead()? {
+            return Ok(None);
+        }
+
+        // UNWRAP: There is atleast one buffered packet.
+        let packet = self.packets.front().unwrap();
+
+        if packet.dur.is_zero() {
+            let (track_id, pts) = (packet.track_id, packet.pts);
+
+            // Find the next packet of the same track, reading ahead if it has not been read yet.
+            let mut next = 1;
+
+            let next_pts = loop {
+                if let Some(packet) = self.packets.get(next) {
+                    if packet.track_id == track_id {
+                        break Some(packet.pts);
+                    }
+                    next += 1;
+                }
+                else if self.packets.len() > MAX_LOOKAHEAD_PACKETS || !self.read_ahead()? {
+                    break None;
+                }
+            };
+
+            if let Some(dur) = next_pts.and_then(|next_pts| pts.duration_to(next_pts)) {
+                // UNWRAP: There is atleast one buffered packet.
+                self.packets.front_mut().unwrap().dur = dur;
+            }
+        }
+
+        Ok(self.packets.pop_front())
+    }
+
+    fn next_packet_into<'b>(&mut self, buf: &'b mut Vec<u8>) -> Result<Option<PacketRef<'b>>> {
+        // Packets must be buffered to infer their durations, therefore they cannot be read directly
+        // into the caller's buffer.
+        let Some(packet) = self.next_packet()?
+        else {
+            return Ok(None);
+        };
+
+        buf.clear();
+        buf.extend_from_slice(&packet.data);
+
+        Ok(Some(PacketRef {
+            track_id: packet.track_id,
+            pts: packet.pts,
+            dts: packet.dts,
+            dur: packet.dur,
+            trim_start: packet.trim_start,
+            trim_end: packet.trim_end,
+            is_keyframe: packet.is_keyframe,
+            num_frames: packet.num_frames,
+            data: buf,
+            side_data: Cow::Owned(packet.side_data),
+        }))
+    }
+
+    fn source_position(&self) -> Option<u64> {
+        // The position of the wrapped reader is after any buffered packets.
+        match self.packets.is_empty() {
+            true => self.inner.source_position(),
+            false => None,
+        }
+    }
+
+    fn estimate_track_bitrate(&mut self, track_id: u32) -> Option<u32> {
+        let pos = self.inner.source_position();
+        let bitrate = self.inner.estimate_track_bitrate(track_id);
+
+        // The wrapped reader should be returned to its original position after estimating the
+        // bitrate. If it was not, the buffered packets are stale.
+        if self.inner.source_position() != pos {
+            self.clear();
+        }
+
+        bitrate
+    }
+
+    fn capabilities(&self) -> FormatCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.inner.reset()?;
+        self.clear();
+        Ok(())
+    }
+
+    fn into_inner<'s>(self: Box<Self>) -> MediaSourceStream<'s>
+    where
+        Self: 's,
+    {
+        self.inner.into_inner()
+    }
+}
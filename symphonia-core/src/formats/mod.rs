@@ -8,15 +8,16 @@
 //! The `format` module provides the traits and support structures necessary to implement media
 //! demuxers.
 
+use std::borrow::Cow;
 use std::fmt;
 
 use crate::codecs::{CodecParameters, audio, subtitle, video};
-use crate::common::FourCc;
-use crate::errors::Result;
+use crate::common::{FourCc, Limit};
+use crate::errors::{Error, Result, SeekErrorKind, unsupported_error};
 use crate::io::MediaSourceStream;
 use crate::meta::{ChapterGroup, Metadata, MetadataLog};
-use crate::packet::Packet;
-use crate::units::{Duration, Time, TimeBase, Timestamp};
+use crate::packet::{Packet, PacketRef};
+use crate::units::{Duration, Time, TimeBase, Timecode, Timestamp};
 
 use bitflags::bitflags;
 
@@ -24,15 +25,17 @@ pub mod prelude {
     //! The `formats` module prelude for format reader implementers.
 
     pub use crate::meta::{Chapter, ChapterGroup, ChapterGroupItem};
-    pub use crate::packet::{Packet, PacketBuilder};
+    pub use crate::packet::{Packet, PacketBuilder, PacketData, PacketRef};
     pub use crate::units::{Duration, TimeBase, Timestamp};
 
     pub use super::{
-        Attachment, FileAttachment, FormatId, FormatInfo, FormatOptions, FormatReader, MediaInfo,
-        SeekMode, SeekTo, SeekedTo, Track, VendorDataAttachment,
+        Attachment, FileAttachment, FormatCapabilities, FormatId, FormatInfo, FormatOptions,
+        FormatReader, MediaInfo, NalFormat, SeekMode, SeekTo, SeekedTo, Track, TrackGroup,
+        TrackGroupKind, VendorDataAttachment,
     };
 }
 
+pub mod backfill;
 pub mod probe;
 
 /// A `FormatId` is a unique identifier used to identify a specific container format.
@@ -94,13 +97,19 @@ pub enum SeekTo {
 }
 
 /// `SeekedTo` is the result of a seek.
+///
+/// Decoding must start from a keyframe. Therefore, if the packet containing the required timestamp
+/// is not a keyframe, such as in the middle of a group of pictures, a `FormatReader` will seek to
+/// the nearest keyframe before it instead. To reach the required timestamp exactly, all packets
+/// from the actual timestamp should be decoded, and any decoded output before the required
+/// timestamp discarded.
 #[derive(Copy, Clone, Debug)]
 pub struct SeekedTo {
     /// The track the seek was relative to.
     pub track_id: u32,
     /// The `TimeStamp` required for the requested seek.
     pub required_ts: Timestamp,
-    /// The `TimeStamp` that was seeked to.
+    /// The `TimeStamp` that was seeked to. This is the timestamp of the next packet of the track.
     pub actual_ts: Timestamp,
 }
 
@@ -113,6 +122,7 @@ pub enum SeekMode {
     /// accurate seek will be performed instead.
     Coarse,
     /// Accurate (aka sample-accurate) seek mode will be always seek to a position before the
+    /// requested position. For tracks with keyframes, this will be the keyframe at, or before, the
     /// requested position.
     Accurate,
 }
@@ -137,11 +147,50 @@ pub struct FormatOptions {
     /// a good compromise for casual playback of music, podcasts, movies, etc. However, for
     /// highly-interactive applications, this value should be decreased.
     pub seek_index_fill_period_ms: u16,
+    /// The maximum size in bytes of any single element (e.g., a metadata comment, or picture) a
+    /// `FormatReader` will allocate memory for when the size is stated by the media. Elements
+    /// exceeding this limit are rejected with a decode error, except for optional metadata which
+    /// may be skipped instead unless `strict` is set. Packet data is not subject to this limit.
+    ///
+    /// Default: `Limit::Default` (a reasonable limit chosen by the reader)
+    pub limit_element_bytes: Limit,
+    /// If `true`, malformed media that would otherwise be tolerated, with a warning, is rejected
+    /// with a decode error. Useful for validating media, but not recommended for playback.
+    ///
+    /// Default: `false`.
+    pub strict: bool,
+    /// If `true`, the duration of packets with a duration of 0 is inferred from the timestamp of
+    /// the next packet of the same track. This requires buffering packets.
+    ///
+    /// This option is applied by [`probe::Probe`], which wraps the `FormatReader` in a
+    /// [`backfill::DurationBackfill`]. A `FormatReader` instantiated directly must be wrapped
+    /// manually.
+    ///
+    /// Default: `false`.
+    pub backfill_packet_durations: bool,
+    /// The framing of the NAL units in the packets of H.264 and HEVC tracks.
+    ///
+    /// Default: `NalFormat::Native`.
+    pub nal_format: NalFormat,
     /// External, supplementary, data related to the media container read before the start of the
     /// container, or provided through some other side-channel.
     pub external_data: ExternalFormatData,
 }
 
+/// `NalFormat` selects the framing of the NAL units in the packets of H.264 and HEVC tracks.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum NalFormat {
+    /// The NAL units are framed as stored by the container. For ISO/MP4 and Matroska, each NAL
+    /// unit is prefixed by its length.
+    #[default]
+    Native,
+    /// Each NAL unit is prefixed by a start code (ITU-T H.264 and H.265, Annex B). The parameter
+    /// sets signalled by the container are inserted before the NAL units of each keyframe.
+    ///
+    /// Only supported by `FormatReader`s for containers that store length-prefixed NAL units.
+    AnnexB,
+}
+
 /// `ExternalFormatData` contains supplementary data related to the media container that was read
 /// before the start of the container, or provided through some other side-channel.
 #[derive(Clone, Debug, Default)]
@@ -160,6 +209,10 @@ impl Default for FormatOptions {
         FormatOptions {
             prebuild_seek_index: false,
             seek_index_fill_period_ms: 1000,
+            limit_element_bytes: Default::default(),
+            strict: false,
+            backfill_packet_durations: false,
+            nal_format: Default::default(),
             external_data: Default::default(),
         }
     }
@@ -189,6 +242,47 @@ impl FormatOptions {
         self.seek_index_fill_period_ms = period;
         self
     }
+
+    /// The maximum size in bytes of any single element (e.g., a metadata comment, or picture) a
+    /// `FormatReader` will allocate memory for when the size is stated by the media. Elements
+    /// exceeding this limit are rejected with a decode error, except for optional metadata which
+    /// may be skipped instead unless `strict` is set. Packet data is not subject to this limit.
+    ///
+    /// Default: `Limit::Default` (a reasonable limit chosen by the reader)
+    pub fn limit_element_bytes(mut self, limit: Limit) -> Self {
+        self.limit_element_bytes = limit;
+        self
+    }
+
+    /// If `true`, malformed media that would otherwise be tolerated, with a warning, is rejected
+    /// with a decode error. Useful for validating media, but not recommended for playback.
+    ///
+    /// Default: `false`.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// If `true`, the duration of packets with a duration of 0 is inferred from the timestamp of
+    /// the next packet of the same track. This requires buffering packets.
+    ///
+    /// This option is applied by [`probe::Probe`], which wraps the `FormatReader` in a
+    /// [`backfill::DurationBackfill`]. A `FormatReader` instantiated directly must be wrapped
+    /// manually.
+    ///
+    /// Default: `false`.
+    pub fn backfill_packet_durations(mut self, backfill: bool) -> Self {
+        self.backfill_packet_durations = backfill;
+        self
+    }
+
+    /// The framing of the NAL units in the packets of H.264 and HEVC tracks.
+    ///
+    /// Default: `NalFormat::Native`.
+    pub fn nal_format(mut self, nal_format: NalFormat) -> Self {
+        self.nal_format = nal_format;
+        self
+    }
 }
 
 bitflags! {
@@ -226,6 +320,10 @@ pub enum TrackType {
     Video,
     /// A subtitle track.
     Subtitle,
+    /// A timecode track.
+    Timecode,
+    /// A timed metadata, or other data, track.
+    Data,
 }
 
 /// A `Track` is an independently coded media bitstream. A media format may contain multiple tracks
@@ -237,6 +335,12 @@ pub struct Track {
     /// For most formats this is usually the zero-based index of the track, however, some more
     /// complex formats set this differently.
     pub id: u32,
+    /// The track type.
+    ///
+    /// The track type is set by the format reader if the container signals it, otherwise it is
+    /// derived from the codec parameters. Therefore, the track type may be known even if the codec
+    /// is not.
+    pub track_type: Option<TrackType>,
     /// The codec parameters for the track.
     ///
     /// If `None`, the format reader was unable to determine the codec parameters and the track will
@@ -273,6 +377,12 @@ pub struct Track {
     pub padding: Option<u32>,
     /// Flags indicating track attributes.
     pub flags: TrackFlags,
+    /// The alternate group of the track, if any.
+    ///
+    /// Tracks sharing an alternate group are mutually exclusive alternatives to each other, such
+    /// as audio tracks in different languages. Only one track of each alternate group should be
+    /// played at a time.
+    pub alternate_group: Option<u32>,
 }
 
 impl Track {
@@ -280,6 +390,7 @@ impl Track {
     pub fn new(id: u32) -> Self {
         Track {
             id,
+            track_type: None,
             codec_params: None,
             language: None,
             time_base: None,
@@ -289,6 +400,7 @@ impl Track {
             delay: None,
             padding: None,
             flags: TrackFlags::empty(),
+            alternate_group: None,
         }
     }
 
@@ -307,10 +419,25 @@ impl Track {
             };
         }
 
+        // Derive the track type from the codec parameters if one is not already set.
+        if self.track_type.is_none() {
+            self.track_type = Some(match &codec_params {
+                CodecParameters::Audio(_) => TrackType::Audio,
+                CodecParameters::Video(_) => TrackType::Video,
+                CodecParameters::Subtitle(_) => TrackType::Subtitle,
+            });
+        }
+
         self.codec_params = Some(codec_params);
         self
     }
 
+    /// Provide the track type.
+    pub fn with_track_type(&mut self, track_type: TrackType) -> &mut Self {
+        self.track_type = Some(track_type);
+        self
+    }
+
     /// Provide the track language.
     pub fn with_language(&mut self, language: &str) -> &mut Self {
         self.language = Some(language.to_string());
@@ -359,20 +486,47 @@ impl Track {
         self
     }
 
+    /// Provide the alternate group.
+    pub fn with_alternate_group(&mut self, alternate_group: u32) -> &mut Self {
+        self.alternate_group = Some(alternate_group);
+        self
+    }
+
     /// Get the track type.
     ///
-    /// Determining the track type requires knowing the codec parameters. If codec parameters is
-    /// `None`, then this function will also return `None`.
+    /// If the container does not signal the track type, and the codec parameters are `None`, then
+    /// this function will return `None`.
     pub fn track_type(&self) -> Option<TrackType> {
-        match self.codec_params {
-            Some(CodecParameters::Audio(_)) => Some(TrackType::Audio),
-            Some(CodecParameters::Video(_)) => Some(TrackType::Video),
-            Some(CodecParameters::Subtitle(_)) => Some(TrackType::Subtitle),
-            None => None,
-        }
+        self.track_type
     }
 }
 
+/// The relationship between the tracks of a `TrackGroup`.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TrackGroupKind {
+    /// The tracks are mutually exclusive alternatives to each other. Only one track of the group
+    /// should be played at a time.
+    Alternate,
+    /// The tracks are planes, such as the left and right eye views of stereoscopic video, that are
+    /// combined to create another track. The group ID is the ID of the combined track.
+    CombinePlanes,
+    /// The blocks of the tracks are joined to create another track. The group ID is the ID of the
+    /// joined track.
+    JoinBlocks,
+}
+
+/// A group of related tracks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrackGroup {
+    /// The relationship between the tracks of the group.
+    pub kind: TrackGroupKind,
+    /// The identifier of the group. Unique amongst groups of the same kind.
+    pub id: u32,
+    /// The IDs of the tracks in the group.
+    pub track_ids: Vec<u32>,
+}
+
 /// An attachment is additional data that is carried along with the container format.
 pub enum Attachment {
     /// A file.
@@ -402,6 +556,28 @@ pub struct VendorDataAttachment {
     pub data: Box<[u8]>,
 }
 
+bitflags! {
+    /// Flags indicating the capabilities of a format reader for the media being read.
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+    pub struct FormatCapabilities: u32 {
+        /// The reader can seek the media.
+        const SEEKABLE       = 1 << 0;
+        /// The total duration of the media is known.
+        const KNOWN_DURATION = 1 << 1;
+        /// The media is a live stream, or a stream of otherwise unknown length, that can only be
+        /// read sequentially.
+        const LIVE           = 1 << 2;
+        /// The media has chapters.
+        const CHAPTERS       = 1 << 3;
+        /// The media has attachments.
+        const ATTACHMENTS    = 1 << 4;
+        /// The media is laid out such that it can be played progressively without seeking to the
+        /// end of the source first. For example, an MP4 file with the movie atom before the media
+        /// data (faststart).
+        const STREAMABLE     = 1 << 5;
+    }
+}
+
 /// Information about a piece of media as a whole.
 #[non_exhaustive]
 #[derive(Copy, Clone, Debug, Default)]
@@ -442,6 +618,10 @@ pub struct MediaInfo {
     /// start timestamp is not provided, this should usually be equal to the start timestamp of the
     /// track that has the earliest start timestamp.
     pub start_ts: Timestamp,
+    /// The SMPTE timecode of the first frame, if available.
+    ///
+    /// This is commonly provided by professional media, and is distinct from the start timestamp.
+    pub start_timecode: Option<Timecode>,
 }
 
 impl MediaInfo {
@@ -452,7 +632,12 @@ impl MediaInfo {
     /// This function only populates the timebase, duration, and start timestamp. Other fields
     /// are defaulted and must be populated manually.
     pub fn from_track(track: &Track) -> Self {
-        MediaInfo { time_base: track.time_base, duration: track.duration, start_ts: track.start_ts }
+        MediaInfo {
+            time_base: track.time_base,
+            duration: track.duration,
+            start_ts: track.start_ts,
+            start_timecode: None,
+        }
     }
 
     /// For media that contains multiple tracks, populates and returns `MediaInfo` using the
@@ -529,6 +714,12 @@ impl MediaInfo {
         self.start_ts = start_ts;
         self
     }
+
+    /// Provide the timecode of the first frame.
+    pub fn with_start_timecode(&mut self, start_timecode: Timecode) -> &mut Self {
+        self.start_timecode = Some(start_timecode);
+        self
+    }
 }
 
 /// A `FormatReader` is a media container demuxer. It provides methods to read a media container
@@ -574,6 +765,16 @@ pub trait FormatReader: Send + Sync {
     }
 
     /// Gets the metadata revision log.
+    ///
+    /// Some media, such as chained Ogg streams, may update their metadata mid-stream. Whenever new
+    /// metadata is read by `next_packet`, a new revision is pushed to the end of the log. To
+    /// receive these updates, poll the log after reading packets: if [`Metadata::is_latest`]
+    /// returns `false`, then newer revisions are available and may be advanced to using
+    /// [`Metadata::pop`] or [`Metadata::skip_to_latest`].
+    ///
+    /// Internet radio (ICY) metadata is interleaved by the transport rather than the media, and is
+    /// therefore not part of this log. It is stripped from the stream, and delivered separately,
+    /// by the `IcySource` of `symphonia-metadata`.
     fn metadata(&mut self) -> Metadata<'_>;
 
     /// Seek, as precisely as possible depending on the mode, to the `Time` or track `TimeStamp`
@@ -590,9 +791,79 @@ pub trait FormatReader: Send + Sync {
     /// seek may sometimes be an accurate seek.
     fn seek(&mut self, mode: SeekMode, to: SeekTo) -> Result<SeekedTo>;
 
+    /// Seek all tracks to a common `Time` such that playback of all tracks remains in sync.
+    /// Returns the requested and actual `TimeStamp`s seeked to for each track, starting with the
+    /// primary track.
+    ///
+    /// The primary track is the first video track or, if there are no video tracks, the first
+    /// track. The primary track is accurately seeked to the keyframe at, or before, the requested
+    /// time. All other tracks are then positioned at the time of that keyframe. Any decoded output
+    /// before the required `TimeStamp` of a track should be discarded.
+    ///
+    /// The default implementation assumes that the packets of all tracks are interleaved, and
+    /// therefore, after seeking the primary track, all other tracks resume from the time of the
+    /// keyframe. A `FormatReader` that positions tracks independently should override this method.
+    fn seek_time(&mut self, time: Time) -> Result<Vec<SeekedTo>> {
+        let primary = self
+            .first_track(TrackType::Video)
+            .or_else(|| self.tracks().first())
+            .ok_or(Error::SeekError(SeekErrorKind::Unsupported))?;
+
+        let (primary_id, primary_tb) = (primary.id, primary.time_base);
+
+        let seeked =
+            self.seek(SeekMode::Accurate, SeekTo::Time { time, track_id: Some(primary_id) })?;
+
+        // The time of the keyframe the primary track was seeked to.
+        let actual_time = primary_tb.and_then(|tb| tb.calc_time(seeked.actual_ts)).unwrap_or(time);
+
+        let mut all_seeked = vec![seeked];
+
+        // Tracks without a timebase cannot be positioned.
+        all_seeked.extend(self.tracks().iter().filter(|track| track.id != primary_id).filter_map(
+            |track| {
+                let tb = track.time_base?;
+
+                Some(SeekedTo {
+                    track_id: track.id,
+                    required_ts: tb.calc_timestamp(time)?,
+                    actual_ts: tb.calc_timestamp(actual_time)?,
+                })
+            },
+        ));
+
+        Ok(all_seeked)
+    }
+
     /// Gets a list of tracks in the container.
     fn tracks(&self) -> &[Track];
 
+    /// Gets the track with the given track ID, if it exists.
+    ///
+    /// Track IDs are assigned by the container, and are not necessarily contiguous. Therefore, the
+    /// track ID of a track should not be used as an index into the list of tracks.
+    fn track_by_id(&self, id: u32) -> Option<&Track> {
+        self.tracks().iter().find(|track| track.id == id)
+    }
+
+    /// Get the overall duration of the media, if known.
+    ///
+    /// The overall duration is the longest of the duration of the media as a whole, and the
+    /// durations of all tracks. Durations without a timebase are ignored.
+    fn duration(&self) -> Option<Time> {
+        let calc_time = |tb: Option<TimeBase>, dur: Option<Duration>| {
+            tb?.calc_time(dur?.timestamp_from(Timestamp::ZERO)?)
+        };
+
+        let media_info = self.media_info();
+
+        self.tracks()
+            .iter()
+            .filter_map(|track| calc_time(track.time_base, track.duration))
+            .chain(calc_time(media_info.time_base, media_info.duration))
+            .max()
+    }
+
     /// Get the first track of a certain track type.
     fn first_track(&self, track_type: TrackType) -> Option<&Track> {
         // Find the first track matching the desired track type.
@@ -636,6 +907,90 @@ pub trait FormatReader: Send + Sync {
             .or_else(|| self.first_track_known_codec(track_type))
     }
 
+    /// Gets the groups of related tracks.
+    ///
+    /// # For Implementations
+    ///
+    /// The default implementation of this function groups tracks by their alternate group. Format
+    /// readers that support other kinds of track groups should override it.
+    fn track_groups(&self) -> Vec<TrackGroup> {
+        let mut groups: Vec<TrackGroup> = Vec::new();
+
+        for track in self.tracks() {
+            let Some(id) = track.alternate_group
+            else {
+                continue;
+            };
+
+            match groups.iter_mut().find(|group| group.id == id) {
+                Some(group) => group.track_ids.push(track.id),
+                None => groups.push(TrackGroup {
+                    kind: TrackGroupKind::Alternate,
+                    id,
+                    track_ids: vec![track.id],
+                }),
+            }
+        }
+
+        groups
+    }
+
+    /// Select the tracks for which packets will be returned by `next_packet`.
+    ///
+    /// Packets belonging to any other track are skipped by the reader, avoiding, where the
+    /// container allows it, the work of reading them. If `track_ids` is empty, the filter is
+    /// removed and packets for all tracks are returned. Unknown track IDs are ignored.
+    ///
+    /// # For Implementations
+    ///
+    /// The default implementation selects the tracks of the `TrackFilter` returned by
+    /// `track_filter_mut`. The reader must then discard the packets of unselected tracks after
+    /// reading them. If the reader has no `TrackFilter`, an unsupported error is returned. Readers
+    /// that can skip the packets of unselected tracks without reading them should override this
+    /// method, and `is_track_selected`, instead.
+    fn set_track_filter(&mut self, track_ids: &[u32]) -> Result<()> {
+        match self.track_filter_mut() {
+            Some(filter) => {
+                filter.select(track_ids);
+                Ok(())
+            }
+            None => unsupported_error("core (format): track filtering is not supported"),
+        }
+    }
+
+    /// Returns `true` if packets of the track are returned by `next_packet`, as selected by the
+    /// track filter.
+    ///
+    /// # For Implementations
+    ///
+    /// The default implementation checks the `TrackFilter` returned by `track_filter`. If the
+    /// reader has no `TrackFilter`, `true` is returned. Readers that override `set_track_filter`
+    /// should override this method.
+    fn is_track_selected(&self, track_id: u32) -> bool {
+        self.track_filter().is_none_or(|filter| filter.is_selected(track_id))
+    }
+
+    /// Get the `TrackFilter` used by the default implementation of `set_track_filter`.
+    ///
+    /// # For Implementations
+    ///
+    /// The default implementation returns `None`. Readers that discard the packets of unselected
+    /// tracks after reading them should keep a `TrackFilter`, and return it.
+    fn track_filter(&self) -> Option<&util::TrackFilter> {
+        None
+    }
+
+    /// Get a mutable reference to the `TrackFilter` used by the default implementation of
+    /// `set_track_filter`.
+    ///
+    /// # For Implementations
+    ///
+    /// The default implementation returns `None`. Readers that return a `TrackFilter` from
+    /// `track_filter` must return the same `TrackFilter` from this method.
+    fn track_filter_mut(&mut self) -> Option<&mut util::TrackFilter> {
+        None
+    }
+
     /// Reader the next packet from the container.
     ///
     /// If `Ok(None)` is returned, the media has ended and no more packets will be produced until
@@ -645,6 +1000,175 @@ pub trait FormatReader: Send + Sync {
     /// `Decoder`s re-created. All other errors are unrecoverable.
     fn next_packet(&mut self) -> Result<Option<Packet>>;
 
+    /// Read the next packet from the container into a caller-provided buffer.
+    ///
+    /// This method is identical to `next_packet`, except the packet data is written into `buf`,
+    /// replacing its previous contents, and a `PacketRef` referencing it is returned. By reusing
+    /// the same buffer for every call, a per-packet allocation can be avoided.
+    ///
+    /// # For Implementations
+    ///
+    /// The default implementation calls `next_packet` and copies the packet data into `buf`.
+    /// Readers that can read packet data directly into `buf` should override this method.
+    fn next_packet_into<'b>(&mut self, buf: &'b mut Vec<u8>) -> Result<Option<PacketRef<'b>>> {
+        let Some(packet) = self.next_packet()?
+        else {
+            return Ok(None);
+        };
+
+        buf.clear();
+        buf.extend_from_slice(&packet.data);
+
+        Ok(Some(PacketRef {
+            track_id: packet.track_id,
+            pts: packet.pts,
+            dts: packet.dts,
+            dur: packet.dur,
+            trim_start: packet.trim_start,
+            trim_end: packet.trim_end,
+            is_keyframe: packet.is_keyframe,
+            num_frames: packet.num_frames,
+            data: buf,
+            side_data: Cow::Owned(packet.side_data),
+        }))
+    }
+
+    /// Get the byte position of the reader in the underlying media source stream.
+    ///
+    /// This is the position of the next byte the reader will read from the source, and may be used,
+    /// for example, to build an external seek index. If the source is not seekable, the position
+    /// cannot be returned to, and `None` is returned.
+    ///
+    /// # For Implementations
+    ///
+    /// The default implementation returns `None`.
+    fn source_position(&self) -> Option<u64> {
+        None
+    }
+
+    /// Estimate the average bitrate, in bits per second, of a track.
+    ///
+    /// This is useful when the container does not state the bitrate of a track. The returned
+    /// bitrate is only an estimate, and may be inaccurate for variable bitrate media. If the
+    /// bitrate cannot be estimated, `None` is returned.
+    ///
+    /// Estimating the bitrate may require reading packets from the start of the media. In that
+    /// case, the reader is returned to its current position afterwards. If packets have already
+    /// been read, this requires reading them again, therefore, it is best to estimate the bitrate
+    /// before reading any packets.
+    ///
+    /// # For Implementations
+    ///
+    /// The default implementation resets the reader, and divides the total size of the track's
+    /// packets by their elapsed time, over up to the first 10 seconds of the track. The reader is
+    /// then reset again, and packets are read until the original source position is reached. If
+    /// the track is not selected by the track filter, the reader cannot be reset, or the source
+    /// position is unknown, `None` is returned. Readers that can estimate the bitrate without
+    /// reading packets, for example, from the size of the media data and the duration of a single
+    /// track, should override this method. In all cases, the position of the reader must not
+    /// change.
+    fn estimate_track_bitrate(&mut self, track_id: u32) -> Option<u32> {
+        /// The maximum elapsed time, in seconds, of the packets used for the estimate.
+        const WINDOW_SECS: f64 = 10.0;
+
+        let tb = self.track_by_id(track_id)?.time_base?;
+
+        // The packets of a track excluded by the track filter are never read.
+        if !self.is_track_selected(track_id) {
+            return None;
+        }
+
+        // The position to return to after estimating the bitrate.
+        let pos = self.source_position()?;
+
+        self.reset().ok()?;
+
+        // If the reader was not at the start of the media, packets must be read again to return to
+        // the original position.
+        let is_mid_stream = self.source_position() != Some(pos);
+
+        let mut num_bytes = 0;
+        let mut start_ts = None;
+        let mut end_ts = Timestamp::MIN;
+        let mut elapsed = 0.0;
+
+        while elapsed < WINDOW_SECS {
+            let Ok(Some(packet)) = self.next_packet()
+            else {
+                break;
+            };
+
+            if packet.track_id != track_id {
+                continue;
+            }
+
+            num_bytes += packet.data.len() as u64;
+
+            let start_ts = *start_ts.get_or_insert(packet.pts);
+            end_ts = end_ts.max(packet.pts.saturating_add(packet.dur));
+
+            elapsed = tb.calc_time_saturating(end_ts).as_secs_f64()
+                - tb.calc_time_saturating(start_ts).as_secs_f64();
+        }
+
+        self.reset().ok()?;
+
+        // While packets read at once, such as laced frames, remain to be returned, the position is
+        // unknown. Keep reading until it is known again, and reaches the original position.
+        if is_mid_stream {
+            while self.source_position().is_none_or(|cur| cur < pos) {
+                if !matches!(self.next_packet(), Ok(Some(_))) {
+                    break;
+                }
+            }
+        }
+
+        if elapsed <= 0.0 {
+            return None;
+        }
+
+        Some((num_bytes as f64 * 8.0 / elapsed).round() as u32)
+    }
+
+    /// Get the capabilities of the reader for the media being read.
+    ///
+    /// The capabilities allow a caller to determine up front whether, for example, seeking is
+    /// possible, instead of attempting a seek and handling the error.
+    ///
+    /// # For Implementations
+    ///
+    /// The default implementation assumes the reader can seek if, and only if, `source_position`
+    /// returns a position, and that media of unknown duration on an unseekable source is live.
+    /// Chapters and attachments are derived from `chapters` and `attachments`. The media is not
+    /// assumed to be streamable. Readers that can seek regardless of the media source, or that know
+    /// more about the media, should override this method.
+    fn capabilities(&self) -> FormatCapabilities {
+        let mut caps = FormatCapabilities::empty();
+
+        let seekable = self.source_position().is_some();
+        let known_duration = self.media_info().duration.is_some();
+
+        caps.set(FormatCapabilities::SEEKABLE, seekable);
+        caps.set(FormatCapabilities::KNOWN_DURATION, known_duration);
+        caps.set(FormatCapabilities::LIVE, !seekable && !known_duration);
+        caps.set(FormatCapabilities::CHAPTERS, self.chapters().is_some());
+        caps.set(FormatCapabilities::ATTACHMENTS, !self.attachments().is_empty());
+        caps
+    }
+
+    /// Reset the reader to the start of the media.
+    ///
+    /// After a successful reset, the next packet returned by the reader will be the first packet
+    /// of the media, as if the reader was just instantiated. Tracks, metadata, chapters, and the
+    /// track filter are retained. Resetting requires a seekable media source stream.
+    ///
+    /// # For Implementations
+    ///
+    /// The default implementation returns an unsupported error.
+    fn reset(&mut self) -> Result<()> {
+        unsupported_error("core (format): reset is not supported")
+    }
+
     /// Consumes the `FormatReader` and returns the underlying media source stream
     fn into_inner<'s>(self: Box<Self>) -> MediaSourceStream<'s>
     where
@@ -653,12 +1177,7 @@ pub trait FormatReader: Send + Sync {
 
 /// Returns true, if `track` is of the specific track type.
 fn matches_track_type(track: &Track, track_type: TrackType) -> bool {
-    match track.codec_params {
-        Some(CodecParameters::Audio(_)) if track_type == TrackType::Audio => true,
-        Some(CodecParameters::Video(_)) if track_type == TrackType::Video => true,
-        Some(CodecParameters::Subtitle(_)) if track_type == TrackType::Subtitle => true,
-        _ => false,
-    }
+    track.track_type == Some(track_type)
 }
 
 pub mod util {
@@ -794,6 +1313,26 @@ pub mod util {
         }
     }
 
+    /// A `TrackFilter` selects the tracks for which a `FormatReader` returns packets. It is used by
+    /// readers that discard the packets of unselected tracks after reading them.
+    #[derive(Clone, Debug, Default)]
+    pub struct TrackFilter {
+        /// The IDs of the selected tracks. If empty, all tracks are selected.
+        track_ids: Vec<u32>,
+    }
+
+    impl TrackFilter {
+        /// Select the tracks with the given IDs. If `track_ids` is empty, all tracks are selected.
+        pub fn select(&mut self, track_ids: &[u32]) {
+            self.track_ids = track_ids.to_vec();
+        }
+
+        /// Returns `true` if the track is selected.
+        pub fn is_selected(&self, track_id: u32) -> bool {
+            self.track_ids.is_empty() || self.track_ids.contains(&track_id)
+        }
+    }
+
     #[cfg(test)]
     mod tests {
         use crate::units::Timestamp;
@@ -908,8 +1447,14 @@ pub mod well_known {
     pub const FORMAT_ID_WAVPACK: FormatId = FormatId(0x10a);
     /// ISO Base Media File Format
     pub const FORMAT_ID_ISOMP4: FormatId = FormatId(0x10b);
-    /// Matroska/WebM
+    /// Matroska
     pub const FORMAT_ID_MKV: FormatId = FormatId(0x10c);
     /// Flash Video
     pub const FORMAT_ID_FLV: FormatId = FormatId(0x10d);
+    /// SubRip Subtitles
+    pub const FORMAT_ID_SRT: FormatId = FormatId(0x10e);
+    /// Web Video Text Tracks
+    pub const FORMAT_ID_WEBVTT: FormatId = FormatId(0x10f);
+    /// WebM
+    pub const FORMAT_ID_WEBM: FormatId = FormatId(0x110);
 }
@@ -12,9 +12,12 @@ use std::io::{Seek, SeekFrom};
 
 use crate::common::Tier;
 use crate::errors::{Error, Result, unsupported_error};
-use crate::formats::{FormatInfo, FormatOptions, FormatReader};
+use crate::formats::backfill::DurationBackfill;
+use crate::formats::{Attachment, FormatInfo, FormatOptions, FormatReader, MediaInfo, Track};
 use crate::io::{MediaSource, MediaSourceStream, ReadBytes, ScopedStream, SeekBuffered};
-use crate::meta::{MetadataInfo, MetadataOptions, MetadataReader, MetadataSideData};
+use crate::meta::{
+    ChapterGroup, Metadata, MetadataInfo, MetadataOptions, MetadataReader, MetadataSideData,
+};
 
 use log::{debug, error, trace, warn};
 
@@ -296,6 +299,18 @@ pub struct ProbeOptions {
     ///
     /// The default is 16 kB, the maximum is 64 kB.
     pub max_score_depth: u16,
+    /// The maximum total number of bytes, from the initial position of the media source, that may
+    /// be consumed while searching for a container format. Unlike `max_probe_depth`, this limit
+    /// includes the bytes of any metadata read before the container format.
+    ///
+    /// The limit is checked before each scan for the next element. It does not bound the reading of
+    /// the metadata itself: metadata found within the limit is read in full, and trailing metadata
+    /// read from the end of a seekable source is not counted. The memory used by metadata is
+    /// bounded by the limits of each metadata reader instead.
+    ///
+    /// If the limit is reached before a container format is found, probing fails. The default is
+    /// no limit.
+    pub max_probe_len: Option<u64>,
 }
 
 impl Default for ProbeOptions {
@@ -303,8 +318,67 @@ impl Default for ProbeOptions {
         Self {
             max_probe_depth: 1 * 1024 * 1024, // 1 MB
             max_score_depth: 16 * 1024,       // 16 kB
+            max_probe_len: None,
+        }
+    }
+}
+
+/// A container format found by [`Probe::probe_metadata_only`].
+///
+/// A `ProbedFormat` provides access to the format, media, track, and metadata information read
+/// from the headers of the container, but not the packets. Use [`ProbedFormat::into_reader`] to
+/// upgrade it to a `FormatReader` and start reading packets.
+pub struct ProbedFormat<'s> {
+    reader: Box<dyn FormatReader + 's>,
+    backfill: bool,
+}
+
+impl<'s> ProbedFormat<'s> {
+    /// Get basic information about the container format.
+    pub fn format_info(&self) -> &FormatInfo {
+        self.reader.format_info()
+    }
+
+    /// Get information about the media as a whole.
+    pub fn media_info(&self) -> &MediaInfo {
+        self.reader.media_info()
+    }
+
+    /// Get a list of all tracks in the container.
+    pub fn tracks(&self) -> &[Track] {
+        self.reader.tracks()
+    }
+
+    /// Get a list of all attachments.
+    pub fn attachments(&self) -> &[Attachment] {
+        self.reader.attachments()
+    }
+
+    /// Get media chapters, if available.
+    pub fn chapters(&self) -> Option<&ChapterGroup> {
+        self.reader.chapters()
+    }
+
+    /// Gets the metadata revision log.
+    pub fn metadata(&mut self) -> Metadata<'_> {
+        self.reader.metadata()
+    }
+
+    /// Upgrade to a `FormatReader` positioned at the first packet of the media.
+    ///
+    /// If backfilling packet durations was requested in the `FormatOptions` used to probe the
+    /// format, the returned reader will backfill packet durations.
+    pub fn into_reader(self) -> Box<dyn FormatReader + 's> {
+        match self.backfill {
+            true => Box::new(DurationBackfill::new(self.reader)),
+            false => self.reader,
         }
     }
+
+    /// Consumes the `ProbedFormat` and returns the underlying media source stream.
+    pub fn into_inner(self) -> MediaSourceStream<'s> {
+        self.reader.into_inner()
+    }
 }
 
 /// `Probe` scans a `MediaSourceStream` for metadata and container formats, and provides an
@@ -427,12 +501,28 @@ impl Probe {
     /// during the search will be queued and attached to the `FormatReader` instance once a
     /// container format is found.
     pub fn probe<'s>(
+        &self,
+        hint: &Hint,
+        mss: MediaSourceStream<'s>,
+        fmt_opts: FormatOptions,
+        meta_opts: MetadataOptions,
+    ) -> Result<Box<dyn FormatReader + 's>> {
+        Ok(self.probe_metadata_only(hint, mss, fmt_opts, meta_opts)?.into_reader())
+    }
+
+    /// Searches the provided `MediaSourceStream` for a container format like [`Probe::probe`], but
+    /// returns a [`ProbedFormat`] that only exposes the format, track, and metadata information
+    /// read from the container's headers.
+    ///
+    /// No packets are read. If the media should be decoded after inspecting it, the probed format
+    /// may be upgraded to a full `FormatReader` without probing or reading the headers again.
+    pub fn probe_metadata_only<'s>(
         &self,
         hint: &Hint,
         mut mss: MediaSourceStream<'s>,
         mut fmt_opts: FormatOptions,
         meta_opts: MetadataOptions,
-    ) -> Result<Box<dyn FormatReader + 's>> {
+    ) -> Result<ProbedFormat<'s>> {
         // Probe for trailing metadata only if the media source stream is seekable, and the length
         // is known.
         if mss.is_seekable() {
@@ -448,13 +538,34 @@ impl Probe {
             }
         }
 
+        let init_pos = mss.pos();
+
         // Loop over all elements in the stream until a container format is found.
         loop {
-            match self.next(&mut mss, hint)? {
-                // If a container format is found, return an instance to it's reader.
+            // The scan depth is limited by the number of bytes left to probe.
+            let max_depth = match self.opts.max_probe_len {
+                Some(max_len) => {
+                    let len = mss.pos().saturating_sub(init_pos);
+
+                    if len >= max_len {
+                        error!("reached probe length limit of {max_len} bytes");
+                        return unsupported_error("core (probe): no suitable format reader found");
+                    }
+
+                    u64::from(self.opts.max_probe_depth).min(max_len - len)
+                }
+                None => u64::from(self.opts.max_probe_depth),
+            };
+
+            match self.next(&mut mss, hint, max_depth)? {
+                // If a container format is found, instantiate it's reader. Wrapping the reader to
+                // backfill packet durations, if requested, is deferred until it is upgraded.
                 ProbeMatch::Format { factory, .. } => {
-                    // Instantiate the format reader.
-                    return factory(mss, fmt_opts);
+                    let backfill = fmt_opts.backfill_packet_durations;
+
+                    let reader = factory(mss, fmt_opts)?;
+
+                    return Ok(ProbedFormat { reader, backfill });
                 }
                 // If metadata was found, instantiate the metadata reader, read the metadata, and
                 // push it onto the metadata log.
@@ -543,9 +654,14 @@ impl Probe {
         Ok(mss)
     }
 
-    /// Scans the provided `MediaSourceStream` from the current position for the best next metadata
-    /// or format reader. If a match is found, returns it.
-    fn next(&self, mss: &mut MediaSourceStream<'_>, _hint: &Hint) -> Result<ProbeMatch> {
+    /// Scans the provided `MediaSourceStream`, upto `max_depth` bytes from the current position, for
+    /// the best next metadata or format reader. If a match is found, returns it.
+    fn next(
+        &self,
+        mss: &mut MediaSourceStream<'_>,
+        _hint: &Hint,
+        max_depth: u64,
+    ) -> Result<ProbeMatch> {
         let mut win = 0u16;
 
         let init_pos = mss.pos();
@@ -557,15 +673,12 @@ impl Probe {
 
             count += 1;
 
-            if count > self.opts.max_probe_depth {
+            if count > max_depth {
                 break;
             }
 
             if count % 4096 == 0 {
-                debug!(
-                    "searching for format marker... {}+{} / {} bytes",
-                    init_pos, count, self.opts.max_probe_depth
-                );
+                debug!("searching for format marker... {init_pos}+{count} / {max_depth} bytes");
             }
 
             // Use the bloom filter to check if the the 2-byte window may be a prefix of a
@@ -586,12 +699,12 @@ impl Probe {
             }
         }
 
-        if count < self.opts.max_probe_depth {
+        if count < max_depth {
             error!("probe reached EOF at {count} bytes");
         }
         else {
             // Could not find any marker within the probe limit.
-            error!("reached probe limit of {} bytes", self.opts.max_probe_depth);
+            error!("reached probe limit of {max_depth} bytes");
         }
 
         unsupported_error("core (probe): no suitable format reader found")
@@ -796,3 +909,74 @@ macro_rules! support_metadata {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use super::{Hint, Probe, ProbeOptions};
+    use crate::errors::Error;
+    use crate::io::{MediaSource, MediaSourceStream};
+
+    /// An unseekable source of zeros that counts the number of bytes read from it.
+    struct ZeroSource {
+        len: u64,
+        read: Arc<AtomicU64>,
+    }
+
+    impl io::Read for ZeroSource {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let read = self.read.load(Ordering::Relaxed);
+            let len = (buf.len() as u64).min(self.len - read) as usize;
+            buf[..len].fill(0);
+            self.read.store(read + len as u64, Ordering::Relaxed);
+            Ok(len)
+        }
+    }
+
+    impl io::Seek for ZeroSource {
+        fn seek(&mut self, _: io::SeekFrom) -> io::Result<u64> {
+            Err(io::Error::other("source does not support seeking"))
+        }
+    }
+
+    impl MediaSource for ZeroSource {
+        fn is_seekable(&self) -> bool {
+            false
+        }
+
+        fn byte_len(&self) -> Option<u64> {
+            Some(self.len)
+        }
+    }
+
+    /// Probe 1 GB of non-media data, and return the number of bytes read from the source.
+    fn probe_non_media(opts: &ProbeOptions) -> u64 {
+        let read = Arc::new(AtomicU64::new(0));
+        let source = ZeroSource { len: 1 << 30, read: read.clone() };
+
+        let mss = MediaSourceStream::new(Box::new(source), Default::default());
+
+        let result = Probe::new_with_options(opts).probe(
+            &Hint::new(),
+            mss,
+            Default::default(),
+            Default::default(),
+        );
+        assert!(matches!(result, Err(Error::Unsupported(_))));
+
+        read.load(Ordering::Relaxed)
+    }
+
+    #[test]
+    fn verify_probe_limits() {
+        // The scan is limited by the maximum probe depth. Some extra data may be buffered by the
+        // media source stream.
+        assert!(probe_non_media(&Default::default()) <= 2 * 1024 * 1024);
+
+        let opts = ProbeOptions { max_probe_len: Some(16 * 1024), ..Default::default() };
+        assert!(probe_non_media(&opts) <= 128 * 1024);
+    }
+}
@@ -140,6 +140,79 @@ impl<R: io::Read> io::Seek for ReadOnlySource<R> {
     }
 }
 
+/// `OffsetSource` wraps a seekable [`MediaSource`] such that the media starts at a byte offset
+/// within the source.
+///
+/// All positions are relative to the start offset. Therefore, a media embedded within a larger
+/// file (e.g., a concatenated archive) may be probed and read as if it were a standalone file.
+pub struct OffsetSource<S: MediaSource> {
+    inner: S,
+    start_offset: u64,
+}
+
+impl<S: MediaSource> OffsetSource<S> {
+    /// Instantiates a new `OffsetSource<S>` by taking ownership of the provided source, and
+    /// seeking it to the start offset.
+    pub fn new(mut inner: S, start_offset: u64) -> io::Result<Self> {
+        inner.seek(io::SeekFrom::Start(start_offset))?;
+        Ok(OffsetSource { inner, start_offset })
+    }
+
+    /// Gets the start offset of the media within the underlying source.
+    pub fn start_offset(&self) -> u64 {
+        self.start_offset
+    }
+
+    /// Gets a reference to the underlying source.
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Unwraps this `OffsetSource<S>`, returning the underlying source.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: MediaSource> MediaSource for OffsetSource<S> {
+    fn is_seekable(&self) -> bool {
+        self.inner.is_seekable()
+    }
+
+    fn byte_len(&self) -> Option<u64> {
+        self.inner.byte_len().map(|len| len.saturating_sub(self.start_offset))
+    }
+}
+
+impl<S: MediaSource> io::Read for OffsetSource<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<S: MediaSource> io::Seek for OffsetSource<S> {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        let pos = match pos {
+            io::SeekFrom::Start(pos) => io::SeekFrom::Start(self.start_offset.saturating_add(pos)),
+            _ => pos,
+        };
+
+        let inner_pos = self.inner.seek(pos)?;
+
+        // Relative seeks may land before the start offset.
+        if inner_pos < self.start_offset {
+            self.inner.seek(io::SeekFrom::Start(self.start_offset))?;
+
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "seek to a position before the start offset",
+            ));
+        }
+
+        Ok(inner_pos - self.start_offset)
+    }
+}
+
 /// `ReadBytes` provides methods to read bytes and interpret them as little- or big-endian
 /// unsigned integers or floating-point values of standard widths.
 pub trait ReadBytes {
@@ -57,7 +57,7 @@ use std::num::NonZeroU8;
 use std::sync::Arc;
 
 use crate::common::{FourCc, Limit};
-use crate::errors::Result;
+use crate::errors::{Error, Result};
 use crate::io::MediaSourceStream;
 use crate::units::Time;
 
@@ -114,6 +114,12 @@ pub struct MetadataOptions {
     ///
     /// Default: `Limit::Default` (a reasonable limit chosen by the reader)
     pub limit_visual_bytes: Limit,
+
+    /// If `true`, malformed metadata that would otherwise be skipped, with a warning, is rejected
+    /// with a decode error.
+    ///
+    /// Default: `false`.
+    pub strict: bool,
 }
 
 impl MetadataOptions {
@@ -134,6 +140,15 @@ impl MetadataOptions {
         self.limit_visual_bytes = limit;
         self
     }
+
+    /// If `true`, malformed metadata that would otherwise be skipped, with a warning, is rejected
+    /// with a decode error.
+    ///
+    /// Default: `false`.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
 }
 
 /// `StandardVisualKey` is an enumeration providing standardized keys for common visual dispositions.
@@ -700,13 +715,63 @@ pub enum ChapterGroupItem {
     Chapter(Chapter),
 }
 
-/// A container of metadata tags and pictures.
+/// Lyrics, or other text, synchronized to the media.
+#[derive(Clone, Debug, Default)]
+pub struct SyncedLyrics {
+    /// The language of the lyrics as an ISO 639-2 code, if known.
+    pub language: Option<String>,
+    /// A description of the lyrics, if provided.
+    pub description: Option<String>,
+    /// Each line of the lyrics, and the time it is presented at, in presentation order.
+    pub lines: Vec<(Time, String)>,
+}
+
+/// A geographic location, such as where the media was recorded.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GeoLocation {
+    /// The latitude in decimal degrees. Positive values are north of the equator.
+    pub latitude: f64,
+    /// The longitude in decimal degrees. Positive values are east of the prime meridian.
+    pub longitude: f64,
+    /// The altitude in metres, if known.
+    pub altitude: Option<f64>,
+}
+
+/// A container of metadata tags, pictures, synchronized lyrics, and location.
 #[derive(Clone, Debug, Default)]
 pub struct MetadataContainer {
     /// Key-value pairs of metadata.
     pub tags: Vec<Tag>,
     /// Attached pictures.
     pub visuals: Vec<Visual>,
+    /// Synchronized lyrics.
+    pub synced_lyrics: Vec<SyncedLyrics>,
+    /// The geographic location of the media, if known.
+    pub location: Option<GeoLocation>,
+}
+
+impl MetadataContainer {
+    /// Gets the visual best suited for use as a cover or thumbnail.
+    ///
+    /// Front covers are preferred over all other visuals. Amongst the preferred visuals, the one
+    /// with the largest dimensions is selected. If no dimensions are known, the first preferred
+    /// visual is selected.
+    pub fn best_cover(&self) -> Option<&Visual> {
+        let is_front_cover = |visual: &Visual| visual.usage == Some(StandardVisualKey::FrontCover);
+
+        let has_front_cover = self.visuals.iter().any(is_front_cover);
+
+        let area = |visual: &Visual| {
+            visual.dimensions.map_or(0, |size| u64::from(size.width) * u64::from(size.height))
+        };
+
+        // Find the largest preferred visual. If there are many of the same size, select the first.
+        self.visuals
+            .iter()
+            .filter(|visual| !has_front_cover || is_front_cover(visual))
+            .rev()
+            .max_by_key(|visual| area(visual))
+    }
 }
 
 /// Container for metadata associated with a specific track. A [`MetadataContainer`] wrapper
@@ -761,6 +826,18 @@ impl MetadataBuilder {
         self
     }
 
+    /// Add media-level `SyncedLyrics` to the metadata.
+    pub fn add_synced_lyrics(&mut self, lyrics: SyncedLyrics) -> &mut Self {
+        self.revision.media.synced_lyrics.push(lyrics);
+        self
+    }
+
+    /// Set the media-level `GeoLocation` of the metadata.
+    pub fn set_location(&mut self, location: GeoLocation) -> &mut Self {
+        self.revision.media.location = Some(location);
+        self
+    }
+
     /// Add track-specific metadata.
     pub fn add_track(&mut self, per_track: PerTrackMetadata) -> &mut Self {
         self.revision.per_track.push(per_track);
@@ -805,13 +882,44 @@ impl PerTrackMetadataBuilder {
     }
 }
 
+/// An error encountered while reading metadata.
+///
+/// Malformed metadata does not prevent the media from being read. Instead, the error is recorded
+/// in the [`MetadataLog`], and any metadata read before the error was encountered is kept.
+#[derive(Clone, Debug)]
+pub struct MetadataError {
+    /// Information about the metadata format being read.
+    pub info: MetadataInfo,
+    /// A description of the error.
+    pub message: String,
+}
+
+impl MetadataError {
+    /// Create a new `MetadataError` for the given metadata format from an error.
+    pub fn new(info: MetadataInfo, err: &Error) -> Self {
+        MetadataError { info, message: err.to_string() }
+    }
+}
+
+impl fmt::Display for MetadataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} metadata: {}", self.info.short_name, self.message)
+    }
+}
+
 /// A reference to the metadata inside of a [`MetadataLog`].
 #[derive(Debug)]
 pub struct Metadata<'a> {
     revisions: &'a mut VecDeque<MetadataRevision>,
+    errors: &'a [MetadataError],
 }
 
 impl Metadata<'_> {
+    /// Gets all errors encountered while reading metadata.
+    pub fn errors(&self) -> &[MetadataError] {
+        self.errors
+    }
+
     /// Returns `true` if the current metadata revision is the newest, `false` otherwise.
     pub fn is_latest(&self) -> bool {
         self.revisions.len() <= 1
@@ -822,6 +930,12 @@ impl Metadata<'_> {
         self.revisions.front()
     }
 
+    /// Gets the media-level visual of the current revision best suited for use as a cover or
+    /// thumbnail. See [`MetadataContainer::best_cover`] for how the visual is selected.
+    pub fn best_cover(&self) -> Option<&Visual> {
+        self.current().and_then(|revision| revision.media.best_cover())
+    }
+
     /// Skips to, and gets an immutable reference to the latest, and therefore newest, revision of
     /// the metadata.
     pub fn skip_to_latest(&mut self) -> Option<&MetadataRevision> {
@@ -846,12 +960,18 @@ impl Metadata<'_> {
 #[derive(Clone, Debug, Default)]
 pub struct MetadataLog {
     revisions: VecDeque<MetadataRevision>,
+    errors: Vec<MetadataError>,
 }
 
 impl MetadataLog {
     /// Returns a reference to the metadata revisions inside the log.
     pub fn metadata(&mut self) -> Metadata<'_> {
-        Metadata { revisions: &mut self.revisions }
+        Metadata { revisions: &mut self.revisions, errors: &self.errors }
+    }
+
+    /// Record an error encountered while reading metadata.
+    pub fn push_error(&mut self, err: MetadataError) {
+        self.errors.push(err);
     }
 
     /// Push a new metadata revision to the end of the log.
@@ -862,6 +982,7 @@ impl MetadataLog {
     /// Moves all metadata revisions from another metadata log to the end of this log.
     pub fn append(&mut self, other: &mut MetadataLog) {
         self.revisions.append(&mut other.revisions);
+        self.errors.append(&mut other.errors);
     }
 
     /// Push a metadata revision to the front of the log.
@@ -875,6 +996,8 @@ impl MetadataLog {
         while let Some(revision) = other.revisions.pop_back() {
             self.revisions.push_front(revision)
         }
+
+        self.errors.splice(0..0, other.errors.drain(..));
     }
 }
 
@@ -945,4 +1068,84 @@ pub mod well_known {
     pub const METADATA_ID_MATROSKA: MetadataId = MetadataId(0x403);
     /// ISOMP4 tags
     pub const METADATA_ID_ISOMP4: MetadataId = MetadataId(0x404);
+    /// FLV script data
+    pub const METADATA_ID_FLV: MetadataId = MetadataId(0x405);
+    /// Core Audio Format information chunk
+    pub const METADATA_ID_CAF: MetadataId = MetadataId(0x406);
+
+    // Stream metadata
+
+    /// ICY (SHOUTcast) stream metadata
+    pub const METADATA_ID_ICY: MetadataId = MetadataId(0x500);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MetadataContainer, Size, StandardVisualKey, Visual};
+
+    fn visual(id: u8, usage: Option<StandardVisualKey>, dimensions: Option<(u32, u32)>) -> Visual {
+        Visual {
+            media_type: None,
+            dimensions: dimensions.map(|(width, height)| Size { width, height }),
+            color_mode: None,
+            usage,
+            tags: Vec::new(),
+            data: Box::new([id]),
+        }
+    }
+
+    fn best_cover(visuals: Vec<Visual>) -> Option<u8> {
+        let container = MetadataContainer { visuals, ..Default::default() };
+        container.best_cover().map(|visual| visual.data[0])
+    }
+
+    #[test]
+    fn verify_best_cover() {
+        use StandardVisualKey::*;
+
+        assert_eq!(best_cover(vec![]), None);
+
+        // The front cover is preferred, even if smaller than other visuals.
+        assert_eq!(
+            best_cover(vec![
+                visual(0, Some(BackCover), Some((1000, 1000))),
+                visual(1, Some(FrontCover), Some((500, 500))),
+                visual(2, Some(BandArtistLogo), None),
+            ]),
+            Some(1)
+        );
+
+        // The largest front cover is preferred.
+        assert_eq!(
+            best_cover(vec![
+                visual(0, Some(FrontCover), Some((300, 300))),
+                visual(1, Some(FrontCover), None),
+                visual(2, Some(FrontCover), Some((600, 600))),
+            ]),
+            Some(2)
+        );
+
+        // Without a front cover, the largest visual is preferred.
+        assert_eq!(
+            best_cover(vec![
+                visual(0, Some(BackCover), Some((300, 300))),
+                visual(1, None, Some((640, 480))),
+                visual(2, Some(Leaflet), Some((600, 400))),
+            ]),
+            Some(1)
+        );
+
+        // Otherwise, the first visual is selected.
+        assert_eq!(
+            best_cover(vec![visual(0, Some(BackCover), None), visual(1, None, None)]),
+            Some(0)
+        );
+        assert_eq!(
+            best_cover(vec![
+                visual(0, Some(FrontCover), Some((500, 500))),
+                visual(1, Some(FrontCover), Some((500, 500))),
+            ]),
+            Some(0)
+        );
+    }
 }
@@ -7,8 +7,168 @@
 
 //! The `packet` module defines the packet structure.
 
+use std::borrow::Cow;
+use std::num::NonZero;
+use std::ops::{Deref, Range};
+use std::sync::Arc;
+
 use crate::io::BufReader;
-use crate::units::{Duration, Timestamp};
+use crate::units::{Duration, FrameRate, TimeBase, Timestamp};
+
+/// The data buffer of a [`Packet`].
+///
+/// The data buffer may either be owned exclusively by the packet, or be a range of a larger buffer
+/// shared with other packets. A shared buffer allows a format reader to produce many packets from
+/// a single buffer without copying. For example, when several frames are laced into a single
+/// container block, or when the entire media is already in memory.
+///
+/// In either case, `PacketData` dereferences to the byte slice of the packet data.
+#[derive(Clone)]
+pub enum PacketData {
+    /// A data buffer owned by the packet.
+    Owned(Box<[u8]>),
+    /// A range of a shared data buffer.
+    ///
+    /// The range must be within the bounds of the shared buffer, otherwise dereferencing will
+    /// panic.
+    Shared(Arc<[u8]>, Range<usize>),
+}
+
+impl PacketData {
+    /// Create packet data from a range of a shared buffer.
+    ///
+    /// Returns `None` if the range is not within the bounds of the buffer.
+    pub fn shared(buf: Arc<[u8]>, range: Range<usize>) -> Option<Self> {
+        if range.start > range.end || range.end > buf.len() {
+            return None;
+        }
+
+        Some(PacketData::Shared(buf, range))
+    }
+
+    /// Returns `true` if the data is a range of a shared buffer.
+    pub fn is_shared(&self) -> bool {
+        matches!(self, PacketData::Shared(..))
+    }
+
+    /// Converts the packet data into an owned boxed slice. Copies the data only if it is shared.
+    pub fn into_boxed_slice(self) -> Box<[u8]> {
+        match self {
+            PacketData::Owned(buf) => buf,
+            PacketData::Shared(buf, range) => buf[range].into(),
+        }
+    }
+}
+
+impl Deref for PacketData {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            PacketData::Owned(buf) => buf,
+            PacketData::Shared(buf, range) => &buf[range.clone()],
+        }
+    }
+}
+
+impl AsRef<[u8]> for PacketData {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl Default for PacketData {
+    fn default() -> Self {
+        PacketData::Owned(Default::default())
+    }
+}
+
+impl From<Box<[u8]>> for PacketData {
+    fn from(buf: Box<[u8]>) -> Self {
+        PacketData::Owned(buf)
+    }
+}
+
+impl From<Vec<u8>> for PacketData {
+    fn from(buf: Vec<u8>) -> Self {
+        PacketData::Owned(buf.into_boxed_slice())
+    }
+}
+
+impl From<&[u8]> for PacketData {
+    fn from(buf: &[u8]) -> Self {
+        PacketData::Owned(buf.into())
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for PacketData {
+    fn from(buf: [u8; N]) -> Self {
+        PacketData::Owned(Box::new(buf))
+    }
+}
+
+impl From<Arc<[u8]>> for PacketData {
+    fn from(buf: Arc<[u8]>) -> Self {
+        let range = 0..buf.len();
+        PacketData::Shared(buf, range)
+    }
+}
+
+/// Format-specific data attached to a [`Packet`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SideData {
+    /// Additional data for the packet, as stored in a Matroska `BlockAdditions` element.
+    ///
+    /// The meaning of the data is given by the ID, and the block addition mappings of the track.
+    BlockAddition {
+        /// The block addition ID.
+        id: u64,
+        /// The block addition data.
+        data: Box<[u8]>,
+    },
+}
+
+/// Calculate the duration and trim start of a packet after trimming all decoded frames preceding
+/// `ts`. Only valid frames may be trimmed, therefore the trim end is unaffected.
+///
+/// The trim start is a count of audio frames at `rate`, while the timestamps and the duration are
+/// in `time_base` units.
+fn trim_start_to(
+    pts: Timestamp,
+    dur: Duration,
+    trim_start: Duration,
+    ts: Timestamp,
+    time_base: TimeBase,
+    rate: FrameRate,
+) -> (Duration, Duration) {
+    let to_frames = |dur: Duration| {
+        Timestamp::try_from(dur.get())
+            .ok()
+            .and_then(|dur| time_base.ts_to_frames(dur, rate))
+            .and_then(|frames| u64::try_from(frames).ok())
+            .map_or(Duration::new(u64::MAX), Duration::new)
+    };
+
+    let to_dur = |frames: Duration| {
+        i64::try_from(frames.get())
+            .ok()
+            .and_then(|frames| time_base.frames_to_ts(frames, rate))
+            .and_then(|dur| u64::try_from(dur.get()).ok())
+            .map_or(Duration::new(u64::MAX), Duration::new)
+    };
+
+    let max_trim_start = trim_start.saturating_add(to_frames(dur));
+    let new_trim_start =
+        to_frames(pts.duration_to(ts).unwrap_or(Duration::ZERO)).min(max_trim_start);
+
+    if new_trim_start > trim_start {
+        (dur.saturating_sub(to_dur(new_trim_start.saturating_sub(trim_start))), new_trim_start)
+    }
+    else {
+        (dur, trim_start)
+    }
+}
 
 /// A `Packet` contains a discrete amount of encoded data for a single codec bitstream. The exact
 /// amount of data is bounded, but not defined, and is dependant on the container and/or the
@@ -38,6 +198,12 @@ use crate::units::{Duration, Timestamp};
 /// and/or padding frames. The latter are generally discarded by the decoder. The duration of all
 /// *decoded* frames is also called the block duration.
 ///
+/// # Keyframes
+///
+/// A keyframe packet can be decoded independently of all preceding packets, and is therefore a
+/// suitable point to start decoding from. If the container does not signal keyframes, then every
+/// packet is assumed to be a keyframe.
+///
 /// # For Implementers
 ///
 /// When synthesizing PTS, negative PTS should be used for encoder delay frames. However, this is
@@ -70,13 +236,23 @@ pub struct Packet {
     /// The duration of *decoded* frames that should be trimmed from the end of the decoded
     /// buffer to remove encoder padding.
     pub trim_end: Duration,
+    /// If `true`, the packet is a keyframe (random access point).
+    pub is_keyframe: bool,
+    /// The number of *decoded* audio frames in the packet, if known.
+    ///
+    /// Unlike the block duration, which is in `TimeBase` units, this is always a count of audio
+    /// frames, including any delay or padding frames. It is `None` for packets of non-audio tracks,
+    /// or if the format reader does not know the exact number of frames.
+    pub num_frames: Option<u64>,
     /// The packet data buffer.
-    pub data: Box<[u8]>,
+    pub data: PacketData,
+    /// Format-specific data attached to the packet.
+    pub side_data: Vec<SideData>,
 }
 
 impl Packet {
     /// Create a new untrimmed `Packet`.
-    pub fn new(track_id: u32, pts: Timestamp, dur: Duration, data: impl Into<Box<[u8]>>) -> Self {
+    pub fn new(track_id: u32, pts: Timestamp, dur: Duration, data: impl Into<PacketData>) -> Self {
         Packet {
             track_id,
             pts,
@@ -84,7 +260,10 @@ impl Packet {
             dur,
             trim_start: Duration::ZERO,
             trim_end: Duration::ZERO,
+            is_keyframe: true,
+            num_frames: None,
             data: data.into(),
+            side_data: Vec::new(),
         }
     }
 
@@ -97,6 +276,22 @@ impl Packet {
         self.dur.saturating_add(self.trim_start).saturating_add(self.trim_end)
     }
 
+    /// Trim all *decoded* frames preceding the timestamp `ts` from the start of the packet.
+    ///
+    /// The trim start is only ever increased. After a seek, a format reader may use this to trim
+    /// the frames preceding the required timestamp such that the decoded audio starts exactly at
+    /// the seeked position.
+    ///
+    /// Decoders trim a count of audio frames, while the timestamps of the packet are in
+    /// `time_base` units. Therefore, the trimmed duration is converted to audio frames using the
+    /// `sample_rate` of the track. The block duration is only unchanged if the time base is the
+    /// reciprocal of the sample rate.
+    pub fn trim_start_to(&mut self, ts: Timestamp, time_base: TimeBase, sample_rate: NonZero<u32>) {
+        let rate = FrameRate::new(sample_rate, NonZero::<u32>::MIN);
+        (self.dur, self.trim_start) =
+            trim_start_to(self.pts, self.dur, self.trim_start, ts, time_base, rate);
+    }
+
     /// Get a `BufReader` to read the packet data buffer sequentially.
     #[inline]
     pub fn as_buf_reader(&self) -> BufReader<'_> {
@@ -113,7 +308,10 @@ impl Packet {
             dur: self.dur,
             trim_start: self.trim_start,
             trim_end: self.trim_end,
+            is_keyframe: self.is_keyframe,
+            num_frames: self.num_frames,
             data: &self.data,
+            side_data: Cow::Borrowed(&self.side_data),
         }
     }
 }
@@ -127,8 +325,11 @@ impl std::fmt::Debug for Packet {
             .field("dur", &self.dur)
             .field("trim_start", &self.trim_start)
             .field("trim_end", &self.trim_end)
+            .field("is_keyframe", &self.is_keyframe)
+            .field("num_frames", &self.num_frames)
             // Omit the data buffer contents.
             .field("data", &format_args!("<{} bytes>", self.data.len()))
+            .field("side_data", &self.side_data)
             .finish()
     }
 }
@@ -141,7 +342,7 @@ impl std::fmt::Debug for Packet {
 /// the data directly without forcing a heap allocation and deep copy.
 ///
 /// See [`Packet`] for more details on the various timing and implementation details.
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 #[non_exhaustive]
 pub struct PacketRef<'a> {
     /// The track ID of the track this packet belongs to.
@@ -166,8 +367,21 @@ pub struct PacketRef<'a> {
     /// The duration of *decoded* frames that should be trimmed from the end of the decoded
     /// buffer to remove encoder padding.
     pub trim_end: Duration,
+    /// If `true`, the packet is a keyframe (random access point).
+    pub is_keyframe: bool,
+    /// The number of *decoded* audio frames in the packet, if known.
+    ///
+    /// Unlike the block duration, which is in `TimeBase` units, this is always a count of audio
+    /// frames, including any delay or padding frames. It is `None` for packets of non-audio tracks,
+    /// or if the format reader does not know the exact number of frames.
+    pub num_frames: Option<u64>,
     /// The packet data buffer.
     pub data: &'a [u8],
+    /// Format-specific data attached to the packet.
+    ///
+    /// The side data is borrowed when the `PacketRef` is made from a `Packet`, and is owned when it
+    /// is returned by [`FormatReader::next_packet_into`](crate::formats::FormatReader::next_packet_into).
+    pub side_data: Cow<'a, [SideData]>,
 }
 
 impl<'a> PacketRef<'a> {
@@ -183,7 +397,10 @@ impl<'a> PacketRef<'a> {
             dur,
             trim_start: Duration::ZERO,
             trim_end: Duration::ZERO,
+            is_keyframe: true,
+            num_frames: None,
             data,
+            side_data: Cow::Borrowed(&[]),
         }
     }
 
@@ -193,6 +410,15 @@ impl<'a> PacketRef<'a> {
         self.dur.saturating_add(self.trim_start).saturating_add(self.trim_end)
     }
 
+    /// Trim all *decoded* frames preceding the timestamp `ts` from the start of the packet.
+    ///
+    /// See [`Packet::trim_start_to`] for more details.
+    pub fn trim_start_to(&mut self, ts: Timestamp, time_base: TimeBase, sample_rate: NonZero<u32>) {
+        let rate = FrameRate::new(sample_rate, NonZero::<u32>::MIN);
+        (self.dur, self.trim_start) =
+            trim_start_to(self.pts, self.dur, self.trim_start, ts, time_base, rate);
+    }
+
     /// Get a `BufReader` to read the packet data buffer sequentially.
     #[inline]
     pub fn as_buf_reader(&self) -> BufReader<'_> {
@@ -209,8 +435,11 @@ impl std::fmt::Debug for PacketRef<'_> {
             .field("dur", &self.dur)
             .field("trim_start", &self.trim_start)
             .field("trim_end", &self.trim_end)
+            .field("is_keyframe", &self.is_keyframe)
+            .field("num_frames", &self.num_frames)
             // Omit the data buffer contents.
             .field("data", &format_args!("<{} bytes>", self.data.len()))
+            .field("side_data", &self.side_data)
             .finish()
     }
 }
@@ -222,7 +451,9 @@ impl<'a> From<&'a Packet> for PacketRef<'a> {
 }
 
 mod builder {
-    use crate::packet::{Packet, PacketRef};
+    use std::borrow::Cow;
+
+    use crate::packet::{Packet, PacketData, PacketRef};
     use crate::units::{Duration, Timestamp};
 
     pub struct HasTrackId(u32);
@@ -234,7 +465,7 @@ mod builder {
     pub struct HasDur(Duration);
     pub struct NoDur;
 
-    pub struct HasBuf(Box<[u8]>);
+    pub struct HasBuf(PacketData);
     pub struct HasBufRef<'a>(&'a [u8]);
     pub struct NoBuf;
 
@@ -252,6 +483,8 @@ mod builder {
         dts: Option<Timestamp>,
         trim_start: Duration,
         trim_end: Duration,
+        is_keyframe: bool,
+        num_frames: Option<u64>,
     }
 
     impl Default for PacketBuilder<NoTrackId, NoPts, NoDur, NoBuf> {
@@ -271,6 +504,8 @@ mod builder {
                 dts: None,
                 trim_start: Duration::ZERO,
                 trim_end: Duration::ZERO,
+                is_keyframe: true,
+                num_frames: None,
             }
         }
     }
@@ -285,7 +520,10 @@ mod builder {
                 dur: self.dur.0,
                 trim_start: self.trim_start,
                 trim_end: self.trim_end,
+                is_keyframe: self.is_keyframe,
+                num_frames: self.num_frames,
                 data: self.buf.0,
+                side_data: Vec::new(),
             }
         }
     }
@@ -300,7 +538,10 @@ mod builder {
                 dur: self.dur.0,
                 trim_start: self.trim_start,
                 trim_end: self.trim_end,
+                is_keyframe: self.is_keyframe,
+                num_frames: self.num_frames,
                 data: self.buf.0,
+                side_data: Cow::Borrowed(&[]),
             }
         }
     }
@@ -320,7 +561,7 @@ mod builder {
             block_dur: Duration,
             end_pts: Option<Timestamp>,
         ) -> PacketBuilder<T, HasPts, HasDur, B> {
-            let Self { track_id, pts, buf, dts, .. } = self;
+            let Self { track_id, pts, buf, dts, is_keyframe, num_frames, .. } = self;
 
             // All frames with a negative PTS must be trimmed first. This duration may exceed the
             // number of decoded frames.
@@ -337,24 +578,47 @@ mod builder {
                 }
             }
 
-            let dur = block_dur.saturating_sub(self.trim_start).saturating_sub(self.trim_end);
+            let dur = block_dur.saturating_sub(trim_start).saturating_sub(trim_end);
 
-            PacketBuilder { track_id, pts, dur: HasDur(dur), buf, dts, trim_start, trim_end }
+            PacketBuilder {
+                track_id,
+                pts,
+                dur: HasDur(dur),
+                buf,
+                dts,
+                trim_start,
+                trim_end,
+                is_keyframe,
+                num_frames,
+            }
         }
     }
 
     impl<T, P, B> PacketBuilder<T, P, NoDur, B> {
         /// Provide the packet's duration including delay and padding frames.
         pub fn dur(self, dur: Duration) -> PacketBuilder<T, P, HasDur, B> {
-            let Self { track_id, pts, buf, dts, trim_start, trim_end, .. } = self;
-            PacketBuilder { track_id, pts, dur: HasDur(dur), buf, dts, trim_start, trim_end }
+            let Self {
+                track_id, pts, buf, dts, trim_start, trim_end, is_keyframe, num_frames, ..
+            } = self;
+            PacketBuilder {
+                track_id,
+                pts,
+                dur: HasDur(dur),
+                buf,
+                dts,
+                trim_start,
+                trim_end,
+                is_keyframe,
+                num_frames,
+            }
         }
     }
 
     impl<T, P, D, B> PacketBuilder<T, P, D, B> {
         /// Provide the track ID.
         pub fn track_id(self, track_id: u32) -> PacketBuilder<HasTrackId, P, D, B> {
-            let Self { pts, dur, buf, dts, trim_start, trim_end, .. } = self;
+            let Self { pts, dur, buf, dts, trim_start, trim_end, is_keyframe, num_frames, .. } =
+                self;
             PacketBuilder {
                 track_id: HasTrackId(track_id),
                 pts,
@@ -363,29 +627,67 @@ mod builder {
                 dts,
                 trim_start,
                 trim_end,
+                is_keyframe,
+                num_frames,
             }
         }
 
         /// Provide the presentation timestamp (PTS).
         pub fn pts(self, pts: Timestamp) -> PacketBuilder<T, HasPts, D, B> {
-            let Self { track_id, dur, buf, dts, trim_start, trim_end, .. } = self;
-            PacketBuilder { track_id, pts: HasPts(pts), dur, buf, dts, trim_start, trim_end }
+            let Self {
+                track_id, dur, buf, dts, trim_start, trim_end, is_keyframe, num_frames, ..
+            } = self;
+            PacketBuilder {
+                track_id,
+                pts: HasPts(pts),
+                dur,
+                buf,
+                dts,
+                trim_start,
+                trim_end,
+                is_keyframe,
+                num_frames,
+            }
         }
 
         /// Provide the packet's data buffer.
         ///
         /// When holding an owned data buffer, an owning `Packet` is built.
-        pub fn data(self, buf: impl Into<Box<[u8]>>) -> PacketBuilder<T, P, D, HasBuf> {
-            let Self { track_id, pts, dur, dts, trim_start, trim_end, .. } = self;
-            PacketBuilder { track_id, pts, dur, buf: HasBuf(buf.into()), dts, trim_start, trim_end }
+        pub fn data(self, buf: impl Into<PacketData>) -> PacketBuilder<T, P, D, HasBuf> {
+            let Self {
+                track_id, pts, dur, dts, trim_start, trim_end, is_keyframe, num_frames, ..
+            } = self;
+            PacketBuilder {
+                track_id,
+                pts,
+                dur,
+                buf: HasBuf(buf.into()),
+                dts,
+                trim_start,
+                trim_end,
+                is_keyframe,
+                num_frames,
+            }
         }
 
         /// Provide the packet's data buffer as a non-owning reference.
         ///
         /// When holding a non-owning data buffer reference, a non-owning `PacketRef` is built.
         pub fn data_by_ref<'a>(self, buf: &'a [u8]) -> PacketBuilder<T, P, D, HasBufRef<'a>> {
-            let Self { track_id, pts, dur, dts, trim_start, trim_end, .. } = self;
-            PacketBuilder { track_id, pts, dur, buf: HasBufRef(buf), dts, trim_start, trim_end }
+            let Self {
+                track_id, pts, dur, dts, trim_start, trim_end, is_keyframe, num_frames, ..
+            } = self;
+            PacketBuilder {
+                track_id,
+                pts,
+                dur,
+                buf: HasBufRef(buf),
+                dts,
+                trim_start,
+                trim_end,
+                is_keyframe,
+                num_frames,
+            }
         }
 
         /// Provide the decode timestamp (DTS).
@@ -405,6 +707,18 @@ mod builder {
             self.trim_end = trim_end;
             self
         }
+
+        /// Provide if the packet is a keyframe. By default, packets are keyframes.
+        pub fn keyframe(mut self, is_keyframe: bool) -> Self {
+            self.is_keyframe = is_keyframe;
+            self
+        }
+
+        /// Provide the number of decoded audio frames in the packet.
+        pub fn num_frames(mut self, num_frames: u64) -> Self {
+            self.num_frames = Some(num_frames);
+            self
+        }
     }
 }
 
@@ -412,8 +726,12 @@ pub use builder::PacketBuilder;
 
 #[cfg(test)]
 mod tests {
-    use super::PacketBuilder;
-    use crate::units::{Duration, Timestamp};
+    use std::sync::Arc;
+
+    use std::num::NonZero;
+
+    use super::{PacketBuilder, PacketData};
+    use crate::units::{Duration, TimeBase, Timestamp};
 
     #[test]
     fn verify_packet_ref_creation() {
@@ -435,6 +753,7 @@ mod tests {
         assert_eq!(pkt_ref.dur, Duration::new(50));
         assert_eq!(pkt_ref.trim_start, Duration::new(10));
         assert_eq!(pkt_ref.trim_end, Duration::new(5));
+        assert!(pkt_ref.is_keyframe);
         assert_eq!(&pkt_ref.data, &[1, 2, 3, 4]);
 
         // block_dur = dur + trim_start + trim_end = 50 + 10 + 5 = 65
@@ -453,6 +772,8 @@ mod tests {
             .dts(Timestamp::new(190))
             .trim_start(Duration::new(20))
             .trim_end(Duration::new(10))
+            .keyframe(false)
+            .num_frames(130)
             .build();
 
         let pkt_ref = pkt.as_packet_ref();
@@ -463,6 +784,123 @@ mod tests {
         assert_eq!(pkt_ref.dur, Duration::new(100));
         assert_eq!(pkt_ref.trim_start, Duration::new(20));
         assert_eq!(pkt_ref.trim_end, Duration::new(10));
+        assert!(!pkt_ref.is_keyframe);
+        assert_eq!(pkt_ref.num_frames, Some(130));
         assert_eq!(&pkt_ref.data, &[5, 6, 7, 8]);
     }
+
+    #[test]
+    fn verify_trimmed_dur() {
+        // Encoder delay spanning the entire packet.
+        let pkt = PacketBuilder::new()
+            .track_id(0)
+            .pts(Timestamp::new(-2000))
+            .trimmed_dur(Duration::new(1152), None)
+            .data(vec![])
+            .build();
+
+        assert_eq!(pkt.trim_start, Duration::new(1152));
+        assert_eq!(pkt.trim_end, Duration::ZERO);
+        assert_eq!(pkt.dur, Duration::ZERO);
+
+        // Encoder delay ending within the packet.
+        let pkt = PacketBuilder::new()
+            .track_id(0)
+            .pts(Timestamp::new(-1105))
+            .trimmed_dur(Duration::new(1152), Some(Timestamp::new(4055)))
+            .data(vec![])
+            .build();
+
+        assert_eq!(pkt.trim_start, Duration::new(1105));
+        assert_eq!(pkt.trim_end, Duration::ZERO);
+        assert_eq!(pkt.dur, Duration::new(47));
+        assert_eq!(pkt.block_dur(), Duration::new(1152));
+
+        // Padding at the end of the stream.
+        let pkt = PacketBuilder::new()
+            .track_id(0)
+            .pts(Timestamp::new(3503))
+            .trimmed_dur(Duration::new(1152), Some(Timestamp::new(4055)))
+            .data(vec![])
+            .build();
+
+        assert_eq!(pkt.trim_start, Duration::ZERO);
+        assert_eq!(pkt.trim_end, Duration::new(600));
+        assert_eq!(pkt.dur, Duration::new(552));
+        assert_eq!(pkt.block_dur(), Duration::new(1152));
+    }
+
+    #[test]
+    fn verify_shared_packet_data() {
+        let buf: Arc<[u8]> = Arc::from([1u8, 2, 3, 4, 5, 6]);
+
+        let first = PacketData::shared(buf.clone(), 0..2).unwrap();
+        let second = PacketData::shared(buf.clone(), 2..6).unwrap();
+
+        assert!(first.is_shared());
+        assert_eq!(&*first, &[1, 2]);
+        assert_eq!(&*second, &[3, 4, 5, 6]);
+        assert_eq!(&*second.into_boxed_slice(), &[3, 4, 5, 6]);
+
+        // Out-of-bounds ranges are rejected.
+        assert!(PacketData::shared(buf.clone(), 4..7).is_none());
+
+        let pkt = PacketBuilder::new()
+            .track_id(3)
+            .pts(Timestamp::new(0))
+            .dur(Duration::new(1))
+            .data(first)
+            .build();
+
+        assert_eq!(pkt.as_packet_ref().data, &[1, 2]);
+    }
+
+    #[test]
+    fn verify_trim_start_to() {
+        let rate = NonZero::new(1000).unwrap();
+        let tb = TimeBase::from_recip(rate);
+
+        let mut pkt = PacketBuilder::new()
+            .track_id(0)
+            .pts(Timestamp::new(-100))
+            .trimmed_dur(Duration::new(1152), None)
+            .data(vec![])
+            .build();
+
+        // Frames already trimmed are not trimmed again.
+        pkt.trim_start_to(Timestamp::new(-50), tb, rate);
+        assert_eq!((pkt.trim_start, pkt.dur), (Duration::new(100), Duration::new(1052)));
+
+        // Trim to a timestamp within the packet.
+        pkt.trim_start_to(Timestamp::new(400), tb, rate);
+        assert_eq!((pkt.trim_start, pkt.dur), (Duration::new(500), Duration::new(652)));
+        assert_eq!(pkt.block_dur(), Duration::new(1152));
+
+        // Trim to a timestamp after the packet.
+        let mut pkt_ref = pkt.as_packet_ref();
+        pkt_ref.trim_start_to(Timestamp::new(2000), tb, rate);
+        assert_eq!((pkt_ref.trim_start, pkt_ref.dur), (Duration::new(1152), Duration::ZERO));
+    }
+
+    #[test]
+    fn verify_trim_start_to_frames() {
+        // A 1 ms time base, and a 48 kHz sample rate.
+        let rate = NonZero::new(48000).unwrap();
+        let tb = TimeBase::try_new(1, 1000).unwrap();
+
+        let mut pkt = PacketBuilder::new()
+            .track_id(0)
+            .pts(Timestamp::new(100))
+            .dur(Duration::new(20))
+            .data(vec![])
+            .build();
+
+        // Trimming 5 ms trims 240 frames.
+        pkt.trim_start_to(Timestamp::new(105), tb, rate);
+        assert_eq!((pkt.trim_start, pkt.dur), (Duration::new(240), Duration::new(15)));
+
+        // Trim to a timestamp after the packet.
+        pkt.trim_start_to(Timestamp::new(200), tb, rate);
+        assert_eq!((pkt.trim_start, pkt.dur), (Duration::new(960), Duration::ZERO));
+    }
 }
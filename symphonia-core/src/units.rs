@@ -1029,6 +1029,53 @@ impl TimeBase {
         }
     }
 
+    /// Calculate the number of whole frames, at the provided frame rate, that have elapsed at the
+    /// provided `Timestamp` using `self` as the conversion factor.
+    ///
+    /// The result is rounded to the nearest frame such that it round-trips with
+    /// [`TimeBase::frames_to_ts`]. Returns `None` if an overflow occurs.
+    pub fn ts_to_frames(&self, ts: Timestamp, frame_rate: FrameRate) -> Option<i64> {
+        // frames = ts * (numer / denom) * (fps_numer / fps_denom)
+        let n = i128::from(ts.get())
+            * i128::from(self.numer.get())
+            * i128::from(frame_rate.numer.get());
+        let d = i128::from(self.denom.get()) * i128::from(frame_rate.denom.get());
+
+        div_round_i128(n, d).try_into().ok()
+    }
+
+    /// Calculate the `Timestamp` of the provided frame number, at the provided frame rate, using
+    /// `self` as the conversion factor.
+    ///
+    /// The result is rounded to the nearest tick such that it round-trips with
+    /// [`TimeBase::ts_to_frames`]. Returns `None` if an overflow occurs.
+    pub fn frames_to_ts(&self, frames: i64, frame_rate: FrameRate) -> Option<Timestamp> {
+        // ts = frames * (fps_denom / fps_numer) * (denom / numer)
+        let n =
+            i128::from(frames) * i128::from(frame_rate.denom.get()) * i128::from(self.denom.get());
+        let d = i128::from(frame_rate.numer.get()) * i128::from(self.numer.get());
+
+        div_round_i128(n, d).try_into().ok().map(Timestamp)
+    }
+
+    /// Calculate the number of frames, at the provided frame rate, spanned by the provided
+    /// `Duration` using `self` as the conversion factor.
+    ///
+    /// The result is rounded to the nearest frame. Since a duration rounded to the nearest tick is
+    /// only within half a frame of the exact duration if a tick is no longer than a frame, `None` is
+    /// returned if a tick is longer than a frame, or if an overflow occurs.
+    pub fn dur_to_frames(&self, dur: Duration, frame_rate: FrameRate) -> Option<u64> {
+        // A tick is no longer than a frame if numer / denom <= fps_denom / fps_numer.
+        let tick = u64::from(self.numer.get()) * u64::from(frame_rate.numer.get());
+        let frame = u64::from(self.denom.get()) * u64::from(frame_rate.denom.get());
+
+        if tick > frame {
+            return None;
+        }
+
+        div_round_i128(i128::from(dur.get()) * i128::from(tick), i128::from(frame)).try_into().ok()
+    }
+
     /// Scale the timebase by a positive, non-zero, floating-point scale factor.
     ///
     /// Returns `None` if the scale factor is <= 0.0, or if the timebase would become invalid
@@ -1081,6 +1128,13 @@ impl TimeBase {
     }
 }
 
+/// Divide `n` by the positive divisor `d`, rounding to the nearest integer with ties rounding
+/// towards positive infinity.
+#[inline]
+fn div_round_i128(n: i128, d: i128) -> i128 {
+    (n + d / 2).div_euclid(d)
+}
+
 impl From<TimeBase> for f64 {
     fn from(timebase: TimeBase) -> Self {
         f64::from(timebase.numer.get()) / f64::from(timebase.denom.get())
@@ -1093,11 +1147,127 @@ impl fmt::Display for TimeBase {
     }
 }
 
+/// A `FrameRate` is a rational number of frames per second.
+///
+/// Non-integer frame rates, such as the NTSC-derived rates, are exactly representable. For
+/// example, 29.97 fps is `30000/1001`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FrameRate {
+    /// The numerator.
+    pub numer: NonZero<u32>,
+    /// The denominator.
+    pub denom: NonZero<u32>,
+}
+
+impl FrameRate {
+    /// Create a new frame rate.
+    pub const fn new(numer: NonZero<u32>, denom: NonZero<u32>) -> Self {
+        FrameRate { numer, denom }
+    }
+
+    /// Try to create a new frame rate. Returns `None` if either the numerator or denominator are 0.
+    pub fn try_new(numer: u32, denom: u32) -> Option<Self> {
+        let numer = NonZero::new(numer)?;
+        let denom = NonZero::new(denom)?;
+        Some(FrameRate { numer, denom })
+    }
+
+    /// Get the duration of a single frame as a `TimeBase`.
+    pub const fn frame_period(&self) -> TimeBase {
+        TimeBase { numer: self.denom, denom: self.numer }
+    }
+}
+
+impl From<FrameRate> for f64 {
+    fn from(frame_rate: FrameRate) -> Self {
+        f64::from(frame_rate.numer.get()) / f64::from(frame_rate.denom.get())
+    }
+}
+
+impl fmt::Display for FrameRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.numer, self.denom)
+    }
+}
+
+/// A `Timecode` is a SMPTE timecode of hours, minutes, seconds, and frames.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timecode {
+    /// The hours.
+    pub hours: u8,
+    /// The minutes.
+    pub minutes: u8,
+    /// The seconds.
+    pub seconds: u8,
+    /// The frames.
+    pub frames: u8,
+    /// If true, the timecode uses drop-frame counting.
+    pub drop_frame: bool,
+}
+
+impl Timecode {
+    /// Create a timecode from a count of frames since 00:00:00:00, and the nominal (integer) number
+    /// of frames per second.
+    ///
+    /// If `drop_frame` is true, the frame count is converted using drop-frame counting, where frame
+    /// numbers are skipped at the start of each minute, except every tenth minute. The hours wrap
+    /// around after 24 hours.
+    ///
+    /// Returns `None` if the number of frames per second is 0.
+    pub fn from_frame_count(count: u64, frames_per_sec: u8, drop_frame: bool) -> Option<Self> {
+        let fps = u64::from(frames_per_sec);
+
+        if fps == 0 {
+            return None;
+        }
+
+        let mut count = count;
+
+        if drop_frame {
+            // The number of frame numbers skipped per minute. For example, 2 for 29.97 fps.
+            let dropped = fps / 15;
+
+            let frames_per_min = 60 * fps - dropped;
+            let frames_per_10_min = 10 * frames_per_min + dropped;
+
+            let tens = count / frames_per_10_min;
+            let rem = count % frames_per_10_min;
+
+            count += 9 * dropped * tens;
+
+            if rem > dropped {
+                count += dropped * ((rem - dropped) / frames_per_min);
+            }
+        }
+
+        Some(Timecode {
+            hours: ((count / (3600 * fps)) % 24) as u8,
+            minutes: ((count / (60 * fps)) % 60) as u8,
+            seconds: ((count / fps) % 60) as u8,
+            frames: (count % fps) as u8,
+            drop_frame,
+        })
+    }
+}
+
+impl fmt::Display for Timecode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // By convention, the frames of a drop-frame timecode are separated by a semicolon.
+        let sep = if self.drop_frame { ';' } else { ':' };
+
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{}{:02}",
+            self.hours, self.minutes, self.seconds, sep, self.frames
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::i64;
 
-    use super::{Time, TimeBase, Timestamp};
+    use super::{Duration, FrameRate, Time, TimeBase, Timecode, Timestamp};
 
     #[test]
     fn verify_time() {
@@ -1431,4 +1601,96 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn verify_frames_roundtrip() {
+        let rates = [
+            FrameRate::try_new(24_000, 1001).unwrap(), // 23.976 fps
+            FrameRate::try_new(25, 1).unwrap(),
+            FrameRate::try_new(30_000, 1001).unwrap(), // 29.97 fps
+            FrameRate::try_new(60_000, 1001).unwrap(), // 59.94 fps
+        ];
+
+        let bases = [
+            TimeBase::try_new(1, 1_000).unwrap(),
+            TimeBase::try_new(1, 90_000).unwrap(),
+            TimeBase::try_new(1, 48_000).unwrap(),
+            TimeBase::try_new(1, 30_000).unwrap(),
+            TimeBase::try_new(1, 1_000_000_000).unwrap(),
+        ];
+
+        // NOTE: The round-trip is only stable if the frame period is at least 1 tick.
+        for tb in bases {
+            for rate in rates {
+                for frames in -10_000..=10_000 {
+                    let ts = tb.frames_to_ts(frames, rate).unwrap();
+                    assert_eq!(tb.ts_to_frames(ts, rate).unwrap(), frames);
+                }
+            }
+        }
+
+        let tb = TimeBase::try_new(1, 90_000).unwrap();
+        let ntsc = FrameRate::try_new(30_000, 1001).unwrap();
+
+        // 1 frame at 29.97 fps is 3003 ticks at 90 kHz.
+        assert_eq!(tb.frames_to_ts(1, ntsc).unwrap(), Timestamp::from(3003));
+        assert_eq!(tb.ts_to_frames(Timestamp::from(3003 * 1000), ntsc).unwrap(), 1000);
+        // A timestamp within a frame rounds to the nearest frame.
+        assert_eq!(tb.ts_to_frames(Timestamp::from(3003 + 1000), ntsc).unwrap(), 1);
+        assert_eq!(tb.ts_to_frames(Timestamp::from(3003 + 2000), ntsc).unwrap(), 2);
+
+        // 1 hour of 25 fps video is 90000 frames.
+        let time = Time::from_hhmmss(1, 0, 0, 0).unwrap();
+        let ts = tb.calc_timestamp(time).unwrap();
+        assert_eq!(tb.ts_to_frames(ts, FrameRate::try_new(25, 1).unwrap()).unwrap(), 90_000);
+
+        // Overflow.
+        let tb = TimeBase::try_new(u32::MAX, 1).unwrap();
+        let rate = FrameRate::try_new(u32::MAX, 1).unwrap();
+        assert!(tb.ts_to_frames(Timestamp::MAX, rate).is_none());
+        let tb = TimeBase::try_new(1, u32::MAX).unwrap();
+        let rate = FrameRate::try_new(1, u32::MAX).unwrap();
+        assert!(tb.frames_to_ts(i64::MAX, rate).is_none());
+    }
+
+    #[test]
+    fn verify_dur_to_frames() {
+        let rate = FrameRate::try_new(44_100, 1).unwrap();
+
+        // 1024 frames at 44.1 kHz is 2089.8 ticks at 90 kHz, and rounds back to 1024 frames.
+        let tb = TimeBase::try_new(1, 90_000).unwrap();
+        assert_eq!(tb.dur_to_frames(Duration::new(2090), rate), Some(1024));
+        assert_eq!(tb.dur_to_frames(Duration::new(2089), rate), Some(1024));
+
+        // A time base equal to the sample rate.
+        let tb = TimeBase::try_new(1, 44_100).unwrap();
+        assert_eq!(tb.dur_to_frames(Duration::new(1152), rate), Some(1152));
+
+        // A 1 ms tick is longer than a frame.
+        let tb = TimeBase::try_new(1, 1_000).unwrap();
+        assert_eq!(tb.dur_to_frames(Duration::new(23), rate), None);
+    }
+
+    #[test]
+    fn verify_timecode() {
+        let tc = |count, fps, drop_frame| {
+            Timecode::from_frame_count(count, fps, drop_frame).unwrap().to_string()
+        };
+
+        assert_eq!(tc(0, 25, false), "00:00:00:00");
+        assert_eq!(tc(90_000, 25, false), "01:00:00:00");
+        assert_eq!(tc(90_000 + 25 * 61 + 7, 25, false), "01:01:01:07");
+        // Hours wrap around after 24 hours.
+        assert_eq!(tc(24 * 90_000 + 1, 25, false), "00:00:00:01");
+
+        // Drop-frame timecode skips frames 0 and 1 at the start of each minute, except every tenth
+        // minute.
+        assert_eq!(tc(1799, 30, true), "00:00:59;29");
+        assert_eq!(tc(1800, 30, true), "00:01:00;02");
+        assert_eq!(tc(17_982, 30, true), "00:10:00;00");
+        assert_eq!(tc(107_892, 30, true), "01:00:00;00");
+        assert_eq!(tc(3600, 60, true), "00:01:00;04");
+
+        assert!(Timecode::from_frame_count(0, 0, false).is_none());
+    }
 }
@@ -65,6 +65,7 @@ pub enum Chunk {
     ChannelLayout(ChannelLayout),
     PacketTable(PacketTable),
     MagicCookie(Box<[u8]>),
+    Information(Information),
     Free,
 }
 
@@ -101,6 +102,7 @@ impl Chunk {
                     return invalid_chunk_size_error("Magic Cookie", chunk_size);
                 }
             }
+            b"info" => Chunk::Information(Information::read(reader, chunk_size)?),
             b"free" => {
                 if chunk_size < 0 {
                     return invalid_chunk_size_error("Free", chunk_size);
@@ -212,6 +214,8 @@ impl AudioDescription {
             AppleLossless => CODEC_ID_ALAC,
             Flac => CODEC_ID_FLAC,
             Opus => CODEC_ID_OPUS,
+            G722 => CODEC_ID_ADPCM_G722,
+            G726 => CODEC_ID_ADPCM_G726,
             unsupported => {
                 error!("unsupported codec ({unsupported:?})");
                 return unsupported_error("caf: unsupported codec");
@@ -221,6 +225,35 @@ impl AudioDescription {
         Ok(result)
     }
 
+    pub fn bits_per_coded_sample(&self) -> Result<u32> {
+        use AudioDescriptionFormatId::*;
+
+        match &self.format_id {
+            // G.722 always codes a pair of samples into one byte.
+            G722 => Ok(4),
+            // G.726 codes each sample with 2 to 5 bits. If the bits per channel field is not valid,
+            // derive the code size from the packet size instead.
+            G726 => {
+                let bits = match self.bits_per_channel {
+                    bits @ 2..=5 => bits,
+                    _ if self.frames_per_packet > 0 => {
+                        (u64::from(self.bytes_per_packet) * 8
+                            / (u64::from(self.frames_per_packet)
+                                * u64::from(self.channels_per_frame)))
+                            as u32
+                    }
+                    _ => 0,
+                };
+
+                match bits {
+                    2..=5 => Ok(bits),
+                    _ => decode_error("caf: invalid bits per sample for G.726"),
+                }
+            }
+            _ => Ok((self.bytes_per_packet * 8) / self.channels_per_frame),
+        }
+    }
+
     pub fn is_variable_packet_format(&self) -> bool {
         self.bytes_per_packet == 0 || self.frames_per_packet == 0
     }
@@ -270,6 +303,8 @@ pub enum AudioDescriptionFormatId {
     AppleLossless,
     Flac,
     Opus,
+    G722,
+    G726,
 }
 
 impl AudioDescriptionFormatId {
@@ -304,6 +339,9 @@ impl AudioDescriptionFormatId {
             // Additional formats from CoreAudioBaseTypes.h
             b"flac" => Flac,
             b"opus" => Opus,
+            // Microsoft formats are identified by "ms" followed by the WAVE format tag.
+            b"ms\x00\x45" | b"ms\x00\x64" => G726,
+            b"ms\x00\x65" | b"ms\x02\x8f" => G722,
             other => {
                 error!("unsupported format id ({other:?})");
                 return unsupported_error("caf: unsupported format id");
@@ -509,7 +547,9 @@ impl PacketTable {
                     current_frame = current_frame
                         .checked_add(frames)
                         .ok_or(Error::Unsupported("track too long"))?;
-                    packet_offset += size; // TODO: This could overflow...
+                    packet_offset = packet_offset
+                        .checked_add(size)
+                        .ok_or(Error::DecodeError("caf: packet offset overflow"))?;
                 }
             }
             // Variable bytes per packet, constant number of frames
@@ -526,7 +566,9 @@ impl PacketTable {
                     current_frame = current_frame
                         .checked_add(frames)
                         .ok_or(Error::Unsupported("track too long"))?;
-                    packet_offset += size;
+                    packet_offset = packet_offset
+                        .checked_add(size)
+                        .ok_or(Error::DecodeError("caf: packet offset overflow"))?;
                 }
             }
             // Constant bytes per packet, variable number of frames
@@ -543,7 +585,9 @@ impl PacketTable {
                     current_frame = current_frame
                         .checked_add(frames)
                         .ok_or(Error::Unsupported("track too long"))?;
-                    packet_offset += size;
+                    packet_offset = packet_offset
+                        .checked_add(size)
+                        .ok_or(Error::DecodeError("caf: packet offset overflow"))?;
                 }
             }
             // Constant bit rate format
@@ -577,6 +621,69 @@ impl fmt::Debug for PacketTable {
     }
 }
 
+#[derive(Debug)]
+pub struct Information {
+    pub entries: Vec<(String, String)>,
+}
+
+impl Information {
+    pub fn read(reader: &mut MediaSourceStream<'_>, chunk_size: i64) -> Result<Self> {
+        let Some(mut data_len) = chunk_size.checked_sub(4).and_then(|len| u64::try_from(len).ok())
+        else {
+            return invalid_chunk_size_error("Information", chunk_size);
+        };
+
+        let num_entries = reader.read_be_u32()?;
+
+        // Each entry is a pair of null-terminated UTF-8 strings. The chunk size is untrusted, so
+        // the strings are read one at a time instead of reading the whole chunk upfront.
+        let mut entries = Vec::new();
+
+        for _ in 0..num_entries {
+            let key = read_info_string(reader, &mut data_len)?;
+            let value = read_info_string(reader, &mut data_len)?;
+
+            match (key, value) {
+                (Some(key), Some(value)) => entries.push((key, value)),
+                _ => {
+                    warn!("information chunk is missing entries");
+                    break;
+                }
+            }
+        }
+
+        // Skip any data following the entries.
+        reader.ignore_bytes(data_len)?;
+
+        Ok(Self { entries })
+    }
+}
+
+/// Read a null-terminated UTF-8 string from an information chunk with `data_len` bytes remaining.
+/// Returns `None` if the chunk has no data remaining.
+fn read_info_string(
+    reader: &mut MediaSourceStream<'_>,
+    data_len: &mut u64,
+) -> Result<Option<String>> {
+    if *data_len == 0 {
+        return Ok(None);
+    }
+
+    let mut buf = Vec::new();
+
+    // The last string in the chunk may not be terminated.
+    while *data_len > 0 {
+        *data_len -= 1;
+
+        match reader.read_byte()? {
+            0 => break,
+            byte => buf.push(byte),
+        }
+    }
+
+    Ok(Some(String::from_utf8_lossy(&buf).into_owned()))
+}
+
 #[derive(Debug)]
 pub struct CafPacket {
     // The packet's offset in bytes from the start of the data
@@ -629,6 +736,33 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn information() {
+        let read = |chunk_size: i64, data: &[u8]| {
+            let cursor = Cursor::new(Vec::from(data));
+            let mut source = MediaSourceStream::new(Box::new(cursor), Default::default());
+            Information::read(&mut source, chunk_size).map(|info| info.entries)
+        };
+
+        let data = b"\0\0\0\x02artist\0Someone\0title\0Something";
+        assert_eq!(
+            read(data.len() as i64, data).unwrap(),
+            [
+                ("artist".to_string(), "Someone".to_string()),
+                ("title".to_string(), "Something".to_string())
+            ]
+        );
+
+        // Fewer entries than signalled.
+        assert_eq!(
+            read(data.len() as i64, &[&[0, 0, 0, 3], &data[4..]].concat()).unwrap().len(),
+            2
+        );
+
+        // A chunk size far exceeding the data is an error, not an allocation of the chunk size.
+        assert!(read(i64::MAX, data).is_err());
+    }
+
     #[test]
     fn variable_length_integers() -> Result<()> {
         variable_length_integer_test(&[0x01], 1)?;
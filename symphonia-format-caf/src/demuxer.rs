@@ -15,27 +15,36 @@ use symphonia_core::{
     audio::{Channels, Position},
     codecs::{
         CodecParameters,
-        audio::{well_known::CODEC_ID_AAC, *},
+        audio::{
+            well_known::{CODEC_ID_AAC, CODEC_ID_ALAC},
+            *,
+        },
     },
     errors::{Error, Result, SeekErrorKind, decode_error, seek_error, unsupported_error},
     formats::{
         prelude::*,
         probe::{ProbeFormatData, ProbeableFormat, Score, Scoreable},
+        util::TrackFilter,
         well_known::FORMAT_ID_CAF,
     },
     io::*,
-    meta::{Metadata, MetadataLog},
+    meta::{Metadata, MetadataBuilder, MetadataInfo, MetadataLog, well_known::METADATA_ID_CAF},
     support_format,
     units::{TimeBase, Timestamp},
 };
 
+use symphonia_common::apple::audio::alac;
 use symphonia_common::mpeg::formats::*;
+use symphonia_metadata::embedded::caf::parse_caf_info_string;
 
 const MAX_FRAMES_PER_PACKET: u64 = 1152;
 
 const CAF_FORMAT_INFO: FormatInfo =
     FormatInfo { format: FORMAT_ID_CAF, short_name: "caf", long_name: "Core Audio Format" };
 
+const CAF_METADATA_INFO: MetadataInfo =
+    MetadataInfo { metadata: METADATA_ID_CAF, short_name: "caf", long_name: "Core Audio Format" };
+
 /// Core Audio Format (CAF) format reader.
 ///
 /// `CafReader` implements a demuxer for Core Audio Format containers.
@@ -45,6 +54,7 @@ pub struct CafReader<'s> {
     tracks: Vec<Track>,
     chapters: Option<ChapterGroup>,
     metadata: MetadataLog,
+    track_filter: TrackFilter,
     data_start_pos: u64,
     data_len: Option<u64>,
     packet_info: PacketInfo,
@@ -92,73 +102,12 @@ impl FormatReader for CafReader<'_> {
     }
 
     fn next_packet(&mut self) -> Result<Option<Packet>> {
-        match &mut self.packet_info {
-            PacketInfo::FixedAudioPacket { bytes_per_packet, frames_per_packet, start_pts } => {
-                let pos = self.reader.pos();
-                let data_pos = pos - self.data_start_pos;
-
-                let bytes_per_packet = u64::from(bytes_per_packet.get());
-                let frames_per_packet = u64::from(frames_per_packet.get());
-
-                // frames_per_packet == 1 means uncompressed data which we want to chunk into
-                // MAX_FRAMES_PER_PACKET
-                let max_bytes_to_read = if frames_per_packet == 1 {
-                    bytes_per_packet * MAX_FRAMES_PER_PACKET
-                }
-                else {
-                    bytes_per_packet
-                };
-
-                let bytes_remaining = if let Some(data_len) = self.data_len {
-                    data_len.saturating_sub(data_pos)
-                }
-                else {
-                    max_bytes_to_read
-                };
-
-                if bytes_remaining == 0 {
-                    return Ok(None);
-                }
-
-                let bytes_to_read = max_bytes_to_read.min(bytes_remaining);
-
-                // Calculate the packet duration.
-                let Some(dur) = (bytes_to_read / bytes_per_packet)
-                    .checked_mul(frames_per_packet)
-                    .map(Duration::new)
-                else {
-                    warn!("packet duration exceeds maximum representable duration");
-                    return Ok(None);
-                };
-
-                // Calculate the packet PTS by offsetting the duration read so far from the start
-                // PTS.
-                let Some(pts) = (data_pos / bytes_per_packet)
-                    .checked_mul(frames_per_packet)
-                    .and_then(|offset| start_pts.checked_add(Duration::from(offset)))
-                else {
-                    warn!("media exceeds maximum representable duration");
-                    return Ok(None);
-                };
-
-                let buf = self.reader.read_boxed_slice(bytes_to_read as usize)?;
-
-                Ok(Some(Packet::new(0, pts, dur, buf)))
-            }
-            PacketInfo::VariableAudioPacket { packets, current_packet_index } => {
-                if let Some(packet) = packets.get(*current_packet_index) {
-                    *current_packet_index += 1;
-                    let buffer = self.reader.read_boxed_slice(packet.size as usize)?;
-                    Ok(Some(Packet::new(0, packet.start_frame, packet.frames, buffer)))
-                }
-                else if *current_packet_index == packets.len() {
-                    Ok(None)
-                }
-                else {
-                    decode_error("caf: invalid packet index")
-                }
+        // The packets of unselected tracks are discarded after reading them.
+        loop {
+            match self.read_packet()? {
+                Some(packet) if !self.track_filter.is_selected(packet.track_id) => (),
+                packet => return Ok(packet),
             }
-            PacketInfo::Unknown => decode_error("caf: missing packet info"),
         }
     }
 
@@ -174,12 +123,20 @@ impl FormatReader for CafReader<'_> {
         &self.tracks
     }
 
+    fn track_filter(&self) -> Option<&TrackFilter> {
+        Some(&self.track_filter)
+    }
+
+    fn track_filter_mut(&mut self) -> Option<&mut TrackFilter> {
+        Some(&mut self.track_filter)
+    }
+
     fn seek(&mut self, _mode: SeekMode, to: SeekTo) -> Result<SeekedTo> {
         let required_ts = match to {
             SeekTo::Timestamp { ts, .. } => ts,
             SeekTo::Time { time, .. } => {
                 // The timebase is required to calculate the timestamp.
-                let tb = self.time_base().ok_or(Error::SeekError(SeekErrorKind::Unseekable))?;
+                let tb = self.time_base().ok_or(Error::SeekError(SeekErrorKind::Unsupported))?;
                 // If the timestamp overflows, the seek if out-of-range.
                 tb.calc_timestamp(time).ok_or(Error::SeekError(SeekErrorKind::OutOfRange))?
             }
@@ -307,6 +264,10 @@ impl FormatReader for CafReader<'_> {
         }
     }
 
+    fn source_position(&self) -> Option<u64> {
+        self.reader.is_seekable().then(|| self.reader.pos())
+    }
+
     fn into_inner<'s>(self: Box<Self>) -> MediaSourceStream<'s>
     where
         Self: 's,
@@ -316,6 +277,84 @@ impl FormatReader for CafReader<'_> {
 }
 
 impl<'s> CafReader<'s> {
+    /// Read the next packet, regardless of the track filter.
+    fn read_packet(&mut self) -> Result<Option<Packet>> {
+        match &mut self.packet_info {
+            PacketInfo::FixedAudioPacket { bytes_per_packet, frames_per_packet, start_pts } => {
+                let pos = self.reader.pos();
+                let data_pos = pos - self.data_start_pos;
+
+                let bytes_per_packet = u64::from(bytes_per_packet.get());
+                let frames_per_packet = u64::from(frames_per_packet.get());
+
+                // frames_per_packet == 1 means uncompressed data which we want to chunk into
+                // MAX_FRAMES_PER_PACKET
+                let max_bytes_to_read = if frames_per_packet == 1 {
+                    bytes_per_packet * MAX_FRAMES_PER_PACKET
+                }
+                else {
+                    bytes_per_packet
+                };
+
+                let bytes_remaining = if let Some(data_len) = self.data_len {
+                    data_len.saturating_sub(data_pos)
+                }
+                else {
+                    max_bytes_to_read
+                };
+
+                if bytes_remaining == 0 {
+                    return Ok(None);
+                }
+
+                let bytes_to_read = max_bytes_to_read.min(bytes_remaining);
+
+                // Calculate the packet duration.
+                let Some(dur) = (bytes_to_read / bytes_per_packet)
+                    .checked_mul(frames_per_packet)
+                    .map(Duration::new)
+                else {
+                    warn!("packet duration exceeds maximum representable duration");
+                    return Ok(None);
+                };
+
+                // Calculate the packet PTS by offsetting the duration read so far from the start
+                // PTS.
+                let Some(pts) = (data_pos / bytes_per_packet)
+                    .checked_mul(frames_per_packet)
+                    .and_then(|offset| start_pts.checked_add(Duration::from(offset)))
+                else {
+                    warn!("media exceeds maximum representable duration");
+                    return Ok(None);
+                };
+
+                let buf = self.reader.read_boxed_slice(bytes_to_read as usize)?;
+
+                let mut packet = Packet::new(0, pts, dur, buf);
+                packet.num_frames = Some(dur.get());
+
+                Ok(Some(packet))
+            }
+            PacketInfo::VariableAudioPacket { packets, current_packet_index } => {
+                if let Some(packet) = packets.get(*current_packet_index) {
+                    *current_packet_index += 1;
+                    let buffer = self.reader.read_boxed_slice(packet.size as usize)?;
+                    let mut packet = Packet::new(0, packet.start_frame, packet.frames, buffer);
+                    packet.num_frames = Some(packet.dur.get());
+
+                    Ok(Some(packet))
+                }
+                else if *current_packet_index == packets.len() {
+                    Ok(None)
+                }
+                else {
+                    decode_error("caf: invalid packet index")
+                }
+            }
+            PacketInfo::Unknown => decode_error("caf: missing packet info"),
+        }
+    }
+
     pub fn try_new(mss: MediaSourceStream<'s>, opts: FormatOptions) -> Result<Self> {
         let mut reader = Self {
             reader: mss,
@@ -323,6 +362,7 @@ impl<'s> CafReader<'s> {
             tracks: vec![],
             chapters: opts.external_data.chapters,
             metadata: opts.external_data.metadata.unwrap_or_default(),
+            track_filter: Default::default(),
             data_start_pos: 0,
             data_len: None,
             packet_info: PacketInfo::Unknown,
@@ -386,7 +426,7 @@ impl<'s> CafReader<'s> {
             .for_codec(desc.codec_id()?)
             .with_sample_rate(desc.sample_rate as u32)
             .with_bits_per_sample(desc.bits_per_channel)
-            .with_bits_per_coded_sample((desc.bytes_per_packet * 8) / desc.channels_per_frame);
+            .with_bits_per_coded_sample(desc.bits_per_coded_sample()?);
 
         // TODO: Bits per sample and bits per coded sample are wrong for compressed.
 
@@ -425,7 +465,8 @@ impl<'s> CafReader<'s> {
                 .with_frames_per_block(MAX_FRAMES_PER_PACKET);
         }
         else {
-            // TODO: Handle variable frames per packet (frames per packet == 0).
+            // If the number of frames per packet is variable (0), the maximum is set from the
+            // packet table.
             codec_params
                 .with_max_frames_per_packet(u64::from(desc.frames_per_packet))
                 .with_frames_per_block(u64::from(desc.frames_per_packet));
@@ -508,6 +549,21 @@ impl<'s> CafReader<'s> {
                             *start_pts = Timestamp::from(-i64::from(table.priming_frames));
                         }
                         PacketInfo::VariableAudioPacket { packets, .. } => {
+                            // If the number of frames per packet is variable, the maximum number
+                            // of frames per packet can only be known from the packet table.
+                            if audio_desc.as_ref().is_some_and(|desc| desc.frames_per_packet == 0) {
+                                let max_frames = table
+                                    .packets
+                                    .iter()
+                                    .map(|packet| packet.frames.get())
+                                    .max()
+                                    .unwrap_or(0);
+
+                                codec_params
+                                    .with_max_frames_per_packet(max_frames)
+                                    .with_frames_per_block(max_frames);
+                            }
+
                             num_frames = Some(table.valid_frames as u64);
                             *packets = table.packets;
                         }
@@ -535,12 +591,36 @@ impl<'s> CafReader<'s> {
                                 }
                             }
                         }
+                        CODEC_ID_ALAC => {
+                            // For ALAC, the magic cookie is the ALAC specific configuration. The
+                            // audio description does not state the bit depth, and the frame length
+                            // is the maximum number of frames per packet.
+                            if let Ok(cookie) = alac::MagicCookie::read(&data) {
+                                let frame_length = u64::from(cookie.frame_length);
+
+                                codec_params
+                                    .with_bits_per_sample(u32::from(cookie.bit_depth))
+                                    .with_max_frames_per_packet(frame_length)
+                                    .with_frames_per_block(frame_length);
+                            }
+
+                            codec_params.with_extra_data(data);
+                        }
                         _ => {
                             // For all other formats attach the entire magic cookie.
                             codec_params.with_extra_data(data);
                         }
                     }
                 }
+                Some(Information(info)) => {
+                    let mut builder = MetadataBuilder::new(CAF_METADATA_INFO);
+
+                    for (key, value) in &info.entries {
+                        parse_caf_info_string(key, value, &mut builder);
+                    }
+
+                    self.metadata.push(builder.build());
+                }
                 Some(Free) | None => {}
             }
 
@@ -572,3 +652,224 @@ impl<'s> CafReader<'s> {
         Ok(track)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::sync::Arc;
+
+    use symphonia_core::codecs::audio::well_known::{CODEC_ID_ADPCM_G722, CODEC_ID_ADPCM_G726};
+    use symphonia_core::meta::StandardTag;
+
+    use super::*;
+
+    /// Write a chunk with the given type and content.
+    fn chunk(chunk_type: &[u8; 4], content: &[u8]) -> Vec<u8> {
+        [chunk_type.as_slice(), &(content.len() as i64).to_be_bytes(), content].concat()
+    }
+
+    /// Write a stereo AAC CAF file with the given frames per packet, additional chunks, and audio
+    /// data.
+    fn make_aac_caf(frames_per_packet: u32, chunks: &[Vec<u8>], data: &[u8]) -> Vec<u8> {
+        make_caf(b"aac ", 2, 0, frames_per_packet, chunks, data)
+    }
+
+    /// Write a stereo CAF file with the given format ID, format flags, bytes and frames per packet,
+    /// additional chunks, and audio data.
+    fn make_caf(
+        format_id: &[u8; 4],
+        format_flags: u32,
+        bytes_per_packet: u32,
+        frames_per_packet: u32,
+        chunks: &[Vec<u8>],
+        data: &[u8],
+    ) -> Vec<u8> {
+        // Sample rate, format ID, format flags, bytes per packet, frames per packet, channels per
+        // frame, and bits per channel.
+        let desc = [
+            44100f64.to_be_bytes().as_slice(),
+            format_id,
+            &format_flags.to_be_bytes(),
+            &bytes_per_packet.to_be_bytes(),
+            &frames_per_packet.to_be_bytes(),
+            &2u32.to_be_bytes(),
+            &0u32.to_be_bytes(),
+        ]
+        .concat();
+
+        // The audio data is preceded by the edit count.
+        let data = [0u32.to_be_bytes().as_slice(), data].concat();
+
+        [
+            b"caff".as_slice(),
+            &1u16.to_be_bytes(),
+            &0u16.to_be_bytes(),
+            &chunk(b"desc", &desc),
+            &chunks.concat(),
+            &chunk(b"data", &data),
+        ]
+        .concat()
+    }
+
+    /// Write a packet table with the given number of packets, frame counts, and entries. The
+    /// entries must be less than 128 to be written as single byte variable-length integers.
+    fn make_pakt(
+        packets: i64,
+        valid: i64,
+        priming: i32,
+        remainder: i32,
+        entries: &[u8],
+    ) -> Vec<u8> {
+        [
+            &packets.to_be_bytes(),
+            valid.to_be_bytes().as_slice(),
+            &priming.to_be_bytes(),
+            &remainder.to_be_bytes(),
+            entries,
+        ]
+        .concat()
+    }
+
+    fn read_packets(file: Vec<u8>) -> (Track, Vec<Packet>) {
+        let mss = MediaSourceStream::new(Box::new(Cursor::new(file)), Default::default());
+        let mut reader = CafReader::try_new(mss, Default::default()).unwrap();
+
+        let track = reader.tracks()[0].clone();
+
+        let mut packets = Vec::new();
+        while let Some(packet) = reader.next_packet().unwrap() {
+            packets.push(packet);
+        }
+
+        (track, packets)
+    }
+
+    #[test]
+    fn read_variable_packet_sizes() {
+        // 3 packets of 3, 1, and 2 bytes, with 32 frames each. The first 10 frames are priming
+        // frames, and the last 6 frames are remainder frames.
+        let pakt = make_pakt(3, 80, 10, 6, &[3, 1, 2]);
+        let data = [1, 1, 1, 2, 3, 3];
+
+        let (track, packets) = read_packets(make_aac_caf(32, &[chunk(b"pakt", &pakt)], &data));
+
+        assert_eq!(track.num_frames, Some(80));
+        assert_eq!(track.delay, Some(10));
+        assert_eq!(track.padding, Some(6));
+
+        let packets: Vec<_> = packets
+            .iter()
+            .map(|packet| (packet.pts.get(), packet.dur.get(), &*packet.data))
+            .collect();
+
+        assert_eq!(packets, [(-10, 32, [1, 1, 1].as_slice()), (22, 32, &[2]), (54, 32, &[3, 3])]);
+    }
+
+    #[test]
+    fn read_variable_packet_frames() {
+        // 2 packets of 2 and 1 bytes, with 20 and 12 frames.
+        let pakt = make_pakt(2, 32, 0, 0, &[2, 20, 1, 12]);
+        let data = [1, 1, 2];
+
+        let (track, packets) = read_packets(make_aac_caf(0, &[chunk(b"pakt", &pakt)], &data));
+
+        assert_eq!(track.num_frames, Some(32));
+
+        let CodecParameters::Audio(params) = track.codec_params.as_ref().unwrap()
+        else {
+            panic!("expected audio codec parameters");
+        };
+        assert_eq!(params.max_frames_per_packet, Some(20));
+
+        let packets: Vec<_> = packets
+            .iter()
+            .map(|packet| (packet.pts.get(), packet.dur.get(), &*packet.data))
+            .collect();
+
+        assert_eq!(packets, [(0, 20, [1, 1].as_slice()), (20, 12, &[2])]);
+    }
+
+    #[test]
+    fn read_information() {
+        // The number of entries, followed by null-terminated key and value strings.
+        let mut info = 3u32.to_be_bytes().to_vec();
+
+        for string in ["title", "Song", "tempo", "120", "custom", "Value"] {
+            info.extend_from_slice(string.as_bytes());
+            info.push(0);
+        }
+
+        let pakt = make_pakt(1, 32, 0, 0, &[1]);
+        let file = make_aac_caf(32, &[chunk(b"info", &info), chunk(b"pakt", &pakt)], &[1]);
+
+        let mss = MediaSourceStream::new(Box::new(Cursor::new(file)), Default::default());
+        let mut reader = CafReader::try_new(mss, Default::default()).unwrap();
+
+        let metadata = reader.metadata();
+        let tags = &metadata.current().unwrap().media.tags;
+
+        assert_eq!(tags.len(), 3);
+        assert_eq!(tags[0].std, Some(StandardTag::TrackTitle(Arc::new("Song".to_string()))));
+        assert_eq!(tags[1].std, Some(StandardTag::Bpm(120)));
+        assert_eq!(tags[2].raw.key, "custom");
+        assert_eq!(tags[2].std, None);
+    }
+
+    #[test]
+    fn read_alac_magic_cookie() {
+        // Frame length, compatible version, bit depth, the rice parameters, number of channels,
+        // maximum run, maximum frame size, average bit rate, and sample rate.
+        let cookie = [
+            4096u32.to_be_bytes().as_slice(),
+            &[0, 24, 40, 10, 14, 2],
+            &255u16.to_be_bytes(),
+            &[0; 8],
+            &44100u32.to_be_bytes(),
+        ]
+        .concat();
+
+        let pakt = make_pakt(1, 4096, 0, 0, &[1]);
+        let file =
+            make_caf(b"alac", 0, 0, 4096, &[chunk(b"kuki", &cookie), chunk(b"pakt", &pakt)], &[1]);
+
+        let (track, packets) = read_packets(file);
+
+        let CodecParameters::Audio(params) = track.codec_params.as_ref().unwrap()
+        else {
+            panic!("expected audio codec parameters");
+        };
+
+        assert_eq!(params.codec, CODEC_ID_ALAC);
+        assert_eq!(params.bits_per_sample, Some(24));
+        assert_eq!(params.max_frames_per_packet, Some(4096));
+        assert_eq!(params.frames_per_block, Some(4096));
+        assert_eq!(params.extra_data.as_deref(), Some(cookie.as_slice()));
+        assert_eq!(packets.len(), 1);
+    }
+
+    #[test]
+    fn read_itu_adpcm() {
+        // G.726 with 3 bits per sample, coding 8 stereo frames into 6 byte packets.
+        let (track, packets) = read_packets(make_caf(b"ms\x00\x45", 0, 6, 8, &[], &[0; 12]));
+
+        let CodecParameters::Audio(params) = track.codec_params.as_ref().unwrap()
+        else {
+            panic!("expected audio codec parameters");
+        };
+
+        assert_eq!(params.codec, CODEC_ID_ADPCM_G726);
+        assert_eq!(params.bits_per_coded_sample, Some(3));
+        assert_eq!(packets.len(), 2);
+
+        // G.722 codes 2 samples per byte.
+        let (track, _) = read_packets(make_caf(b"ms\x02\x8f", 0, 1, 1, &[], &[0; 4]));
+
+        let CodecParameters::Audio(params) = track.codec_params.as_ref().unwrap()
+        else {
+            panic!("expected audio codec parameters");
+        };
+
+        assert_eq!(params.codec, CODEC_ID_ADPCM_G722);
+        assert_eq!(params.bits_per_coded_sample, Some(4));
+    }
+}
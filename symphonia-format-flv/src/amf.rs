@@ -0,0 +1,207 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A minimal Action Message Format 0 (AMF0) reader for FLV script data.
+
+use symphonia_core::errors::{Result, decode_error};
+use symphonia_core::io::{BufReader, FiniteStream, ReadBytes};
+
+/// The maximum nesting depth of objects and arrays.
+const MAX_DEPTH: usize = 16;
+
+const AMF0_NUMBER: u8 = 0x00;
+const AMF0_BOOLEAN: u8 = 0x01;
+const AMF0_STRING: u8 = 0x02;
+const AMF0_OBJECT: u8 = 0x03;
+const AMF0_MOVIECLIP: u8 = 0x04;
+const AMF0_NULL: u8 = 0x05;
+const AMF0_UNDEFINED: u8 = 0x06;
+const AMF0_REFERENCE: u8 = 0x07;
+const AMF0_ECMA_ARRAY: u8 = 0x08;
+const AMF0_OBJECT_END: u8 = 0x09;
+const AMF0_STRICT_ARRAY: u8 = 0x0a;
+const AMF0_DATE: u8 = 0x0b;
+const AMF0_LONG_STRING: u8 = 0x0c;
+const AMF0_UNSUPPORTED: u8 = 0x0d;
+const AMF0_XML_DOCUMENT: u8 = 0x0f;
+const AMF0_TYPED_OBJECT: u8 = 0x10;
+
+/// An AMF0 value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AmfValue {
+    Number(f64),
+    Boolean(bool),
+    String(String),
+    /// An anonymous, typed, or ECMA array object of key-value pairs.
+    Object(Vec<(String, AmfValue)>),
+    StrictArray(Vec<AmfValue>),
+    /// A date in milliseconds since the Unix epoch.
+    Date(f64),
+    Null,
+    Undefined,
+}
+
+impl AmfValue {
+    /// Get the value of a property if the value is an object.
+    pub fn get(&self, key: &str) -> Option<&AmfValue> {
+        match self {
+            AmfValue::Object(props) => props.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a number.
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            AmfValue::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// Read a script data tag. Returns the name and the value of the script data.
+pub fn read_script_data(buf: &[u8]) -> Result<(String, AmfValue)> {
+    let mut reader = BufReader::new(buf);
+
+    let name = match read_value(&mut reader, 0)? {
+        AmfValue::String(name) => name,
+        _ => return decode_error("flv (amf): script data name is not a string"),
+    };
+
+    let value = read_value(&mut reader, 0)?;
+
+    Ok((name, value))
+}
+
+fn read_value(reader: &mut BufReader<'_>, depth: usize) -> Result<AmfValue> {
+    if depth > MAX_DEPTH {
+        return decode_error("flv (amf): maximum nesting depth exceeded");
+    }
+
+    let value = match reader.read_u8()? {
+        AMF0_NUMBER => AmfValue::Number(reader.read_be_f64()?),
+        AMF0_BOOLEAN => AmfValue::Boolean(reader.read_u8()? != 0),
+        AMF0_STRING => AmfValue::String(read_string(reader)?),
+        AMF0_OBJECT => AmfValue::Object(read_properties(reader, depth)?),
+        AMF0_NULL => AmfValue::Null,
+        AMF0_UNDEFINED | AMF0_MOVIECLIP | AMF0_UNSUPPORTED => AmfValue::Undefined,
+        AMF0_REFERENCE => {
+            // References to previously read complex values are not resolved.
+            let _index = reader.read_be_u16()?;
+            AmfValue::Undefined
+        }
+        AMF0_ECMA_ARRAY => {
+            // The associative count is only a hint. The array is terminated like an object.
+            let _count = reader.read_be_u32()?;
+            AmfValue::Object(read_properties(reader, depth)?)
+        }
+        AMF0_STRICT_ARRAY => {
+            let count = reader.read_be_u32()?;
+
+            // Each value is atleast one byte long.
+            if u64::from(count) > reader.bytes_available() {
+                return decode_error("flv (amf): strict array count exceeds the data length");
+            }
+
+            let mut values = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                values.push(read_value(reader, depth + 1)?);
+            }
+            AmfValue::StrictArray(values)
+        }
+        AMF0_DATE => {
+            let date = reader.read_be_f64()?;
+            let _time_zone = reader.read_be_u16()?;
+            AmfValue::Date(date)
+        }
+        AMF0_LONG_STRING | AMF0_XML_DOCUMENT => {
+            let len = reader.read_be_u32()? as usize;
+            AmfValue::String(String::from_utf8_lossy(reader.read_buf_bytes_ref(len)?).into())
+        }
+        AMF0_TYPED_OBJECT => {
+            let _class_name = read_string(reader)?;
+            AmfValue::Object(read_properties(reader, depth)?)
+        }
+        _ => return decode_error("flv (amf): unknown value type"),
+    };
+
+    Ok(value)
+}
+
+fn read_string(reader: &mut BufReader<'_>) -> Result<String> {
+    let len = usize::from(reader.read_be_u16()?);
+    Ok(String::from_utf8_lossy(reader.read_buf_bytes_ref(len)?).into())
+}
+
+fn read_properties(reader: &mut BufReader<'_>, depth: usize) -> Result<Vec<(String, AmfValue)>> {
+    let mut props = Vec::new();
+
+    loop {
+        let key = read_string(reader)?;
+
+        // An empty key followed by the object end marker terminates the object. Some muxers omit
+        // the object end marker at the end of the script data.
+        if key.is_empty() {
+            if reader.bytes_available() == 0 || reader.read_u8()? == AMF0_OBJECT_END {
+                break;
+            }
+            return decode_error("flv (amf): expected object end marker");
+        }
+
+        let value = read_value(reader, depth + 1)?;
+        props.push((key, value));
+    }
+
+    Ok(props)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(value: &str) -> Vec<u8> {
+        let mut buf = (value.len() as u16).to_be_bytes().to_vec();
+        buf.extend_from_slice(value.as_bytes());
+        buf
+    }
+
+    #[test]
+    fn verify_read_script_data() {
+        let mut buf = vec![AMF0_STRING];
+        buf.extend(string("onMetaData"));
+        buf.push(AMF0_ECMA_ARRAY);
+        buf.extend(3u32.to_be_bytes());
+        buf.extend(string("duration"));
+        buf.push(AMF0_NUMBER);
+        buf.extend(12.5f64.to_be_bytes());
+        buf.extend(string("stereo"));
+        buf.extend([AMF0_BOOLEAN, 1]);
+        buf.extend(string("keyframes"));
+        buf.push(AMF0_OBJECT);
+        buf.extend(string("times"));
+        buf.push(AMF0_STRICT_ARRAY);
+        buf.extend(1u32.to_be_bytes());
+        buf.push(AMF0_NUMBER);
+        buf.extend(0f64.to_be_bytes());
+        buf.extend(string(""));
+        buf.push(AMF0_OBJECT_END);
+        buf.extend(string(""));
+        buf.push(AMF0_OBJECT_END);
+
+        let (name, value) = read_script_data(&buf).unwrap();
+        assert_eq!(name, "onMetaData");
+        assert_eq!(value.get("duration").and_then(|v| v.as_number()), Some(12.5));
+        assert_eq!(value.get("stereo"), Some(&AmfValue::Boolean(true)));
+        assert_eq!(
+            value.get("keyframes").and_then(|v| v.get("times")),
+            Some(&AmfValue::StrictArray(vec![AmfValue::Number(0.0)]))
+        );
+
+        // Truncated script data.
+        assert!(read_script_data(&buf[..20]).is_err());
+    }
+}
@@ -0,0 +1,651 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::collections::VecDeque;
+use std::io::{Seek, SeekFrom};
+use std::sync::Arc;
+
+use symphonia_common::mpeg::audio::{AudioSpecificConfig, get_audio_codec_profile};
+use symphonia_common::mpeg::video::{
+    AVCDecoderConfigurationRecord, HEVCDecoderConfigurationRecord,
+};
+use symphonia_core::audio::{Channels, Position};
+use symphonia_core::codecs::CodecParameters;
+use symphonia_core::codecs::audio::AudioCodecParameters;
+use symphonia_core::codecs::audio::well_known::CODEC_ID_AAC;
+use symphonia_core::codecs::video::well_known::extra_data::{
+    VIDEO_EXTRA_DATA_ID_AVC_DECODER_CONFIG, VIDEO_EXTRA_DATA_ID_HEVC_DECODER_CONFIG,
+};
+use symphonia_core::codecs::video::well_known::{CODEC_ID_H264, CODEC_ID_HEVC};
+use symphonia_core::codecs::video::{VideoCodecParameters, VideoExtraData};
+use symphonia_core::errors::{Error, Result, SeekErrorKind};
+use symphonia_core::errors::{decode_error, seek_error, unsupported_error};
+use symphonia_core::formats::prelude::*;
+use symphonia_core::formats::probe::{ProbeFormatData, ProbeableFormat, Score, Scoreable};
+use symphonia_core::formats::well_known::FORMAT_ID_FLV;
+use symphonia_core::io::*;
+use symphonia_core::meta::well_known::METADATA_ID_FLV;
+use symphonia_core::meta::{
+    Metadata, MetadataBuilder, MetadataInfo, MetadataLog, MetadataRevision, StandardTag, Tag,
+};
+use symphonia_core::support_format;
+
+use log::{debug, info, warn};
+
+use crate::amf::{AmfValue, read_script_data};
+use crate::tags::*;
+
+/// The FLV file signature.
+const FLV_SIGNATURE: [u8; 3] = *b"FLV";
+/// The only FLV version.
+const FLV_VERSION: u8 = 1;
+
+/// The header flag indicating audio tags are present.
+const FLV_FLAG_AUDIO: u8 = 0x04;
+/// The header flag indicating video tags are present.
+const FLV_FLAG_VIDEO: u8 = 0x01;
+
+/// The maximum number of tags read while probing for streams.
+const MAX_PROBE_TAGS: usize = 32;
+
+const FLV_FORMAT_INFO: FormatInfo =
+    FormatInfo { format: FORMAT_ID_FLV, short_name: "flv", long_name: "Flash Video" };
+
+const FLV_METADATA_INFO: MetadataInfo =
+    MetadataInfo { metadata: METADATA_ID_FLV, short_name: "flv", long_name: "FLV Script Data" };
+
+/// An FLV tag.
+struct FlvTag {
+    header: TagHeader,
+    data: Box<[u8]>,
+}
+
+/// Flash Video (FLV) format reader.
+///
+/// `FlvReader` implements a demuxer for the FLV container format. Tag timestamps are in
+/// milliseconds, therefore all tracks have a 1/1000 time base.
+pub struct FlvReader<'s> {
+    reader: MediaSourceStream<'s>,
+    media_info: MediaInfo,
+    tracks: Vec<Track>,
+    chapters: Option<ChapterGroup>,
+    metadata: MetadataLog,
+    /// The index of the audio track.
+    audio_track: Option<usize>,
+    /// The index of the video track.
+    video_track: Option<usize>,
+    /// The selected state of each track.
+    selected: Vec<bool>,
+    /// The position of the first tag.
+    first_tag_pos: u64,
+    /// Packets read while probing for streams.
+    buffered: VecDeque<Packet>,
+}
+
+impl<'s> FlvReader<'s> {
+    pub fn try_new(mut mss: MediaSourceStream<'s>, opts: FormatOptions) -> Result<Self> {
+        let start_pos = mss.pos();
+
+        let mut signature = [0; 3];
+        mss.read_buf_exact(&mut signature)?;
+
+        if signature != FLV_SIGNATURE {
+            return unsupported_error("flv: missing flv signature");
+        }
+
+        let version = mss.read_u8()?;
+
+        if version != FLV_VERSION {
+            return unsupported_error("flv: unsupported version");
+        }
+
+        let flags = mss.read_u8()?;
+
+        // The length of the header. Tags start immediately after it.
+        let data_offset = mss.read_be_u32()?;
+
+        if data_offset < FLV_HEADER_LEN {
+            return decode_error("flv: invalid header length");
+        }
+
+        mss.ignore_bytes(u64::from(data_offset - FLV_HEADER_LEN))?;
+
+        let mut reader = FlvReader {
+            reader: mss,
+            media_info: Default::default(),
+            tracks: Vec::new(),
+            chapters: opts.external_data.chapters,
+            metadata: opts.external_data.metadata.unwrap_or_default(),
+            audio_track: None,
+            video_track: None,
+            selected: Vec::new(),
+            first_tag_pos: start_pos + u64::from(data_offset),
+            buffered: VecDeque::new(),
+        };
+
+        reader.probe_streams(flags)?;
+
+        if reader.tracks.is_empty() {
+            return decode_error("flv: no audio or video tags");
+        }
+
+        reader.media_info = MediaInfo::from_tracks(&reader.tracks);
+
+        Ok(reader)
+    }
+
+    /// Read tags until a track was created for each stream signalled by the header flags, and
+    /// apply the `onMetaData` script data to the tracks.
+    fn probe_streams(&mut self, flags: u8) -> Result<()> {
+        // If the header flags are not set, probe until the tag limit is reached.
+        let has_audio = flags & FLV_FLAG_AUDIO != 0;
+        let has_video = flags & FLV_FLAG_VIDEO != 0;
+
+        let mut script_data = None;
+
+        for _ in 0..MAX_PROBE_TAGS {
+            let Some(tag) = self.read_tag()?
+            else {
+                break;
+            };
+
+            match tag.header.tag_type {
+                TAG_TYPE_SCRIPT_DATA if script_data.is_none() => {
+                    match read_script_data(&tag.data) {
+                        Ok((name, value)) if name == "onMetaData" => script_data = Some(value),
+                        Ok(_) => (),
+                        Err(err) => warn!("failed to read script data: {err}"),
+                    }
+                }
+                TAG_TYPE_AUDIO if self.audio_track.is_none() && !tag.data.is_empty() => {
+                    self.audio_track = Some(self.add_audio_track(&tag.data)?);
+                }
+                TAG_TYPE_VIDEO if self.video_track.is_none() && !tag.data.is_empty() => {
+                    self.video_track = Some(self.add_video_track(&tag.data)?);
+                }
+                _ => (),
+            }
+
+            if let Some(packet) = self.make_packet(tag)? {
+                self.buffered.push_back(packet);
+            }
+
+            let found_audio = self.audio_track.is_some() || !has_audio;
+            let found_video = self.video_track.is_some() || !has_video;
+
+            if found_audio && found_video && !self.tracks.is_empty() {
+                break;
+            }
+        }
+
+        if let Some(script_data) = script_data {
+            self.apply_script_data(&script_data);
+        }
+
+        self.selected = vec![true; self.tracks.len()];
+
+        Ok(())
+    }
+
+    fn add_audio_track(&mut self, data: &[u8]) -> Result<usize> {
+        let header = AudioTagHeader::read(data)?;
+
+        let mut codec_params = AudioCodecParameters::new();
+
+        match header.codec_id() {
+            Some(codec) => {
+                codec_params.for_codec(codec);
+            }
+            None => info!("unsupported sound format: {}", header.sound_format),
+        }
+
+        codec_params.with_sample_rate(header.sample_rate);
+
+        if header.is_pcm() {
+            codec_params
+                .with_bits_per_coded_sample(header.bits_per_sample)
+                .with_bits_per_sample(header.bits_per_sample);
+        }
+
+        let mut channels = Position::from_wave_channel_count(u32::from(header.num_channels))
+            .map(Channels::Positioned);
+
+        // The sample rate and channels of AAC are only signalled by the AudioSpecificConfig in the
+        // sequence header.
+        if codec_params.codec == CODEC_ID_AAC && header.is_sequence_header() {
+            let asc = &data[header.len..];
+
+            if let Ok(config) = AudioSpecificConfig::read(asc) {
+                if let Some(profile) = get_audio_codec_profile(&config) {
+                    codec_params.with_profile(profile);
+                }
+
+                // The AAC decoder does not apply SBR or PS, therefore report the sample rate,
+                // channels, and frames per packet of the core. The profile still identifies HE-AAC.
+                codec_params.with_sample_rate(config.sample_rate);
+
+                if let Some(frames) = config.frames_per_packet() {
+                    codec_params.with_max_frames_per_packet(frames);
+                }
+                channels = config.channels.or(channels);
+            }
+
+            codec_params.with_extra_data(Box::from(asc));
+        }
+
+        if let Some(channels) = channels {
+            codec_params.with_channels(channels);
+        }
+
+        Ok(self.add_track(CodecParameters::Audio(codec_params)))
+    }
+
+    fn add_video_track(&mut self, data: &[u8]) -> Result<usize> {
+        let header = VideoTagHeader::read(data)?;
+
+        let mut codec_params = VideoCodecParameters::default();
+
+        match header.codec_id() {
+            Some(codec) => {
+                codec_params.for_codec(codec);
+            }
+            None => info!("unsupported video codec: {}", header.codec),
+        }
+
+        // The decoder configuration record of AVC and HEVC is in the sequence header.
+        if header.is_sequence_header() {
+            let config = &data[header.len..];
+
+            let profile_and_level = match codec_params.codec {
+                CODEC_ID_H264 => AVCDecoderConfigurationRecord::read(config)
+                    .map(|cfg| (cfg.profile, cfg.level))
+                    .ok(),
+                CODEC_ID_HEVC => HEVCDecoderConfigurationRecord::read(config)
+                    .map(|cfg| (cfg.profile, cfg.level))
+                    .ok(),
+                _ => None,
+            };
+
+            if let Some((profile, level)) = profile_and_level {
+                codec_params.with_profile(profile).with_level(level);
+            }
+
+            let id = match codec_params.codec {
+                CODEC_ID_HEVC => VIDEO_EXTRA_DATA_ID_HEVC_DECODER_CONFIG,
+                _ => VIDEO_EXTRA_DATA_ID_AVC_DECODER_CONFIG,
+            };
+
+            codec_params.add_extra_data(VideoExtraData { id, data: Box::from(config) });
+        }
+
+        Ok(self.add_track(CodecParameters::Video(codec_params)))
+    }
+
+    fn add_track(&mut self, codec_params: CodecParameters) -> usize {
+        let id = self.tracks.len();
+
+        let mut track = Track::new(id as u32);
+
+        // The time base must be set before the codec parameters, otherwise it is derived from the
+        // sample rate.
+        track.with_time_base(TimeBase::try_new(1, 1000).unwrap()).with_codec_params(codec_params);
+
+        self.tracks.push(track);
+        id
+    }
+
+    /// Apply the duration, and video dimensions, from the `onMetaData` script data, and record
+    /// all scalar properties as metadata.
+    fn apply_script_data(&mut self, script_data: &AmfValue) {
+        let number = |key| script_data.get(key).and_then(AmfValue::as_number);
+
+        // The duration is in seconds.
+        if let Some(duration) = number("duration").filter(|dur| dur.is_finite() && *dur > 0.0) {
+            let duration = Duration::from((duration * 1000.0).round() as u64);
+
+            for track in self.tracks.iter_mut() {
+                track.with_duration(duration);
+            }
+        }
+
+        if let Some(track) = self.video_track.map(|index| &mut self.tracks[index]) {
+            if let Some(CodecParameters::Video(params)) = &mut track.codec_params {
+                if let Some(width) = number("width").and_then(to_u16) {
+                    params.with_width(width);
+                }
+                if let Some(height) = number("height").and_then(to_u16) {
+                    params.with_height(height);
+                }
+            }
+        }
+
+        self.metadata.push(make_metadata(script_data));
+    }
+
+    /// Read the next tag. Returns `None` if the end of the stream was reached.
+    fn read_tag(&mut self) -> Result<Option<FlvTag>> {
+        loop {
+            let Some(header) = read_tag_header(&mut self.reader)?
+            else {
+                return Ok(None);
+            };
+
+            // Encrypted tags cannot be read.
+            if header.filtered {
+                self.reader.ignore_bytes(u64::from(header.data_size))?;
+                continue;
+            }
+
+            let data = self.reader.read_boxed_slice_exact(header.data_size as usize)?;
+
+            return Ok(Some(FlvTag { header, data }));
+        }
+    }
+
+    /// Make a packet from an audio or video tag. Returns `None` if the tag is not a packet of a
+    /// track.
+    fn make_packet(&self, tag: FlvTag) -> Result<Option<Packet>> {
+        let FlvTag { header, data } = tag;
+
+        if data.is_empty() {
+            return Ok(None);
+        }
+
+        let (track, pts, offset, is_keyframe) = match header.tag_type {
+            TAG_TYPE_AUDIO => {
+                let audio = AudioTagHeader::read(&data)?;
+
+                // Sequence headers following the first are ignored.
+                if audio.is_sequence_header() {
+                    return Ok(None);
+                }
+
+                // Audio tags do not signal keyframes.
+                (self.audio_track, header.timestamp, audio.len, true)
+            }
+            TAG_TYPE_VIDEO => {
+                let video = VideoTagHeader::read(&data)?;
+
+                if video.is_sequence_header() || video.is_frameless() {
+                    return Ok(None);
+                }
+
+                let pts = header.timestamp + i64::from(video.composition_time);
+
+                (self.video_track, pts, video.len, video.is_keyframe())
+            }
+            _ => return Ok(None),
+        };
+
+        let Some(track) = track
+        else {
+            return Ok(None);
+        };
+
+        if !self.selected.get(track).copied().unwrap_or(true) || offset > data.len() {
+            return Ok(None);
+        }
+
+        // FLV does not store packet durations.
+        let mut packet =
+            Packet::new(track as u32, Timestamp::new(pts), Duration::ZERO, &data[offset..]);
+        packet.dts = Timestamp::new(header.timestamp);
+        packet.is_keyframe = is_keyframe;
+
+        Ok(Some(packet))
+    }
+
+    /// Returns true if the tag data is a point that can be seeked to on the given track.
+    fn is_sync_tag(&self, track: usize, tag_type: u8, data: &[u8]) -> bool {
+        if Some(track) == self.video_track && tag_type == TAG_TYPE_VIDEO {
+            VideoTagHeader::read(data)
+                .map(|video| {
+                    video.is_keyframe() && !video.is_sequence_header() && !video.is_frameless()
+                })
+                .unwrap_or(false)
+        }
+        else if Some(track) == self.audio_track && tag_type == TAG_TYPE_AUDIO {
+            AudioTagHeader::read(data).map(|audio| !audio.is_sequence_header()).unwrap_or(false)
+        }
+        else {
+            false
+        }
+    }
+}
+
+/// Read a tag header, preceded by the size of the previous tag. Returns `None` if the end of the
+/// stream was reached.
+fn read_tag_header(reader: &mut MediaSourceStream<'_>) -> Result<Option<TagHeader>> {
+    let mut buf = [0; TAG_HEADER_LEN as usize];
+
+    // The stream ends with the size of the last tag, so a partial tag header is also the end of
+    // the stream.
+    match reader.read_buf_exact(&mut buf) {
+        Ok(_) => (),
+        Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err.into()),
+    }
+
+    Ok(Some(TagHeader::read(&buf)?))
+}
+
+/// Make a metadata revision from the scalar properties of the `onMetaData` script data.
+fn make_metadata(script_data: &AmfValue) -> MetadataRevision {
+    let mut builder = MetadataBuilder::new(FLV_METADATA_INFO);
+
+    if let AmfValue::Object(props) = script_data {
+        for (key, value) in props {
+            let tag = match value {
+                AmfValue::Number(value) => Tag::new_from_parts(key.as_str(), *value, None),
+                AmfValue::Boolean(value) => Tag::new_from_parts(key.as_str(), *value, None),
+                AmfValue::String(value) => {
+                    let std = match key.as_str() {
+                        "encoder" => Some(StandardTag::Encoder(Arc::new(value.clone()))),
+                        _ => None,
+                    };
+                    Tag::new_from_parts(key.as_str(), value.as_str(), std)
+                }
+                _ => continue,
+            };
+
+            builder.add_tag(tag);
+        }
+    }
+
+    builder.build()
+}
+
+fn to_u16(value: f64) -> Option<u16> {
+    match value {
+        value if value >= 1.0 && value <= f64::from(u16::MAX) => Some(value as u16),
+        _ => None,
+    }
+}
+
+impl Scoreable for FlvReader<'_> {
+    fn score(mut src: ScopedStream<&mut MediaSourceStream<'_>>) -> Result<Score> {
+        // Perform simple scoring by testing that the signature and version are valid.
+        let mut signature = [0; 3];
+        src.read_buf_exact(&mut signature)?;
+        let version = src.read_u8()?;
+
+        if signature != FLV_SIGNATURE || version != FLV_VERSION {
+            return Ok(Score::Unsupported);
+        }
+
+        Ok(Score::Supported(255))
+    }
+}
+
+impl ProbeableFormat<'_> for FlvReader<'_> {
+    fn try_probe_new(
+        mss: MediaSourceStream<'_>,
+        opts: FormatOptions,
+    ) -> Result<Box<dyn FormatReader + '_>> {
+        Ok(Box::new(FlvReader::try_new(mss, opts)?))
+    }
+
+    fn probe_data() -> &'static [ProbeFormatData] {
+        &[support_format!(FLV_FORMAT_INFO, &["flv"], &["video/x-flv"], &[b"FLV"])]
+    }
+}
+
+impl FormatReader for FlvReader<'_> {
+    fn format_info(&self) -> &FormatInfo {
+        &FLV_FORMAT_INFO
+    }
+
+    fn media_info(&self) -> &MediaInfo {
+        &self.media_info
+    }
+
+    fn next_packet(&mut self) -> Result<Option<Packet>> {
+        while let Some(packet) = self.buffered.pop_front() {
+            if self.selected[packet.track_id as usize] {
+                return Ok(Some(packet));
+            }
+        }
+
+        while let Some(tag) = self.read_tag()? {
+            if let Some(packet) = self.make_packet(tag)? {
+                return Ok(Some(packet));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn metadata(&mut self) -> Metadata<'_> {
+        self.metadata.metadata()
+    }
+
+    fn chapters(&self) -> Option<&ChapterGroup> {
+        self.chapters.as_ref()
+    }
+
+    fn tracks(&self) -> &[Track] {
+        &self.tracks
+    }
+
+    fn set_track_filter(&mut self, track_ids: &[u32]) -> Result<()> {
+        for (id, selected) in self.selected.iter_mut().enumerate() {
+            *selected = track_ids.is_empty() || track_ids.contains(&(id as u32));
+        }
+        Ok(())
+    }
+
+    fn is_track_selected(&self, track_id: u32) -> bool {
+        self.selected.get(track_id as usize).copied().unwrap_or(false)
+    }
+
+    fn seek(&mut self, _mode: SeekMode, to: SeekTo) -> Result<SeekedTo> {
+        // Prefer seeking on the video track.
+        let default_track = self.video_track.unwrap_or(0);
+
+        // Track IDs are the track indicies.
+        let (track, required_ts) = match to {
+            SeekTo::Time { time, track_id } => {
+                let track = track_id.map(|id| id as usize).unwrap_or(default_track);
+
+                let tb = self
+                    .tracks
+                    .get(track)
+                    .and_then(|track| track.time_base)
+                    .ok_or(Error::SeekError(SeekErrorKind::InvalidTrack))?;
+
+                // If the timestamp overflows, the seek if out-of-range.
+                let ts =
+                    tb.calc_timestamp(time).ok_or(Error::SeekError(SeekErrorKind::OutOfRange))?;
+
+                (track, ts)
+            }
+            SeekTo::Timestamp { ts, track_id } => {
+                if track_id as usize >= self.tracks.len() {
+                    return seek_error(SeekErrorKind::InvalidTrack);
+                }
+                (track_id as usize, ts)
+            }
+        };
+
+        // FLV has no index, so seeking requires scanning the tags from the start of the stream.
+        if !self.reader.is_seekable() {
+            return seek_error(SeekErrorKind::Unseekable);
+        }
+
+        debug!("seeking track_id={track} to ts={required_ts}");
+
+        self.reader.seek(SeekFrom::Start(self.first_tag_pos))?;
+
+        // The position and timestamp of the last sync tag at or before the required timestamp. If
+        // there is none, the first sync tag.
+        let mut seek_point = None;
+
+        loop {
+            let pos = self.reader.pos();
+
+            let Some(header) = read_tag_header(&mut self.reader)?
+            else {
+                break;
+            };
+
+            // Only the audio or video tag header is required to identify a sync tag.
+            let len = header.data_size.min(5);
+            let data = self.reader.read_boxed_slice_exact(len as usize)?;
+            self.reader.ignore_bytes(u64::from(header.data_size - len))?;
+
+            if header.filtered || !self.is_sync_tag(track, header.tag_type, &data) {
+                continue;
+            }
+
+            let mut ts = header.timestamp;
+
+            if header.tag_type == TAG_TYPE_VIDEO {
+                let video = VideoTagHeader::read(&data)?;
+                ts += i64::from(video.composition_time);
+            }
+
+            if ts > required_ts.get() && seek_point.is_some() {
+                break;
+            }
+
+            seek_point = Some((pos, ts));
+
+            if ts > required_ts.get() {
+                break;
+            }
+        }
+
+        let Some((seek_pos, ts)) = seek_point
+        else {
+            return seek_error(SeekErrorKind::OutOfRange);
+        };
+
+        self.reader.seek(SeekFrom::Start(seek_pos))?;
+        self.buffered.clear();
+
+        let actual_ts = Timestamp::new(ts);
+
+        debug!(
+            "seeked to packet_ts={} (delta={})",
+            actual_ts,
+            actual_ts.saturating_delta(required_ts)
+        );
+
+        Ok(SeekedTo { track_id: track as u32, actual_ts, required_ts })
+    }
+
+    fn source_position(&self) -> Option<u64> {
+        self.reader.is_seekable().then(|| self.reader.pos())
+    }
+
+    fn into_inner<'s>(self: Box<Self>) -> MediaSourceStream<'s>
+    where
+        Self: 's,
+    {
+        self.reader
+    }
+}
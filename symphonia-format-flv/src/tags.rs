@@ -0,0 +1,284 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use symphonia_core::codecs::audio::AudioCodecId;
+use symphonia_core::codecs::audio::well_known::*;
+use symphonia_core::codecs::video::VideoCodecId;
+use symphonia_core::codecs::video::well_known::*;
+use symphonia_core::errors::{Result, decode_error};
+use symphonia_core::io::{BufReader, ReadBytes};
+
+pub const TAG_TYPE_AUDIO: u8 = 8;
+pub const TAG_TYPE_VIDEO: u8 = 9;
+pub const TAG_TYPE_SCRIPT_DATA: u8 = 18;
+
+const SOUND_FORMAT_PCM: u8 = 0;
+const SOUND_FORMAT_MP3: u8 = 2;
+const SOUND_FORMAT_PCM_LE: u8 = 3;
+const SOUND_FORMAT_ALAW: u8 = 7;
+const SOUND_FORMAT_MULAW: u8 = 8;
+const SOUND_FORMAT_AAC: u8 = 10;
+const SOUND_FORMAT_SPEEX: u8 = 11;
+const SOUND_FORMAT_MP3_8KHZ: u8 = 14;
+
+const VIDEO_CODEC_SORENSON_H263: u8 = 2;
+const VIDEO_CODEC_VP6: u8 = 4;
+const VIDEO_CODEC_VP6_ALPHA: u8 = 5;
+const VIDEO_CODEC_AVC: u8 = 7;
+const VIDEO_CODEC_HEVC: u8 = 12;
+
+const FRAME_TYPE_KEYFRAME: u8 = 1;
+const FRAME_TYPE_COMMAND: u8 = 5;
+
+/// The AAC or AVC packet type of a sequence header.
+const PACKET_TYPE_SEQUENCE_HEADER: u8 = 0;
+/// The AVC packet type of an end of sequence marker.
+const PACKET_TYPE_END_OF_SEQUENCE: u8 = 2;
+
+/// The length of the FLV file header.
+pub const FLV_HEADER_LEN: u32 = 9;
+/// The length of a tag header, including the preceding previous tag size field.
+pub const TAG_HEADER_LEN: u64 = 15;
+
+/// The header of an FLV tag.
+pub struct TagHeader {
+    pub tag_type: u8,
+    /// If true, the tag data is encrypted.
+    pub filtered: bool,
+    pub data_size: u32,
+    /// The decode timestamp in milliseconds.
+    pub timestamp: i64,
+}
+
+impl TagHeader {
+    /// Read a tag header, preceded by the size of the previous tag, from the provided buffer.
+    pub fn read(buf: &[u8; TAG_HEADER_LEN as usize]) -> Result<TagHeader> {
+        let mut reader = BufReader::new(buf);
+
+        let _prev_tag_size = reader.read_be_u32()?;
+        let flags = reader.read_u8()?;
+        let data_size = reader.read_be_u24()?;
+
+        // The timestamp is a signed 32-bit value with the upper 8 bits stored after the lower 24.
+        let timestamp_lo = reader.read_be_u24()?;
+        let timestamp_hi = reader.read_u8()?;
+        let timestamp = ((u32::from(timestamp_hi) << 24) | timestamp_lo) as i32;
+
+        let _stream_id = reader.read_be_u24()?;
+
+        Ok(TagHeader {
+            tag_type: flags & 0x1f,
+            filtered: flags & 0x20 != 0,
+            data_size,
+            timestamp: i64::from(timestamp),
+        })
+    }
+}
+
+/// The header of an audio tag's data.
+pub struct AudioTagHeader {
+    pub sound_format: u8,
+    pub sample_rate: u32,
+    pub bits_per_sample: u32,
+    pub num_channels: u16,
+    /// For AAC, the AAC packet type.
+    pub packet_type: Option<u8>,
+    /// The length of the header.
+    pub len: usize,
+}
+
+impl AudioTagHeader {
+    pub fn read(buf: &[u8]) -> Result<AudioTagHeader> {
+        let mut reader = BufReader::new(buf);
+
+        let flags = reader.read_u8()?;
+
+        let sound_format = flags >> 4;
+
+        let sample_rate = match sound_format {
+            // Nellymoser and MP3 variants with a fixed sample rate.
+            4 => 16000,
+            5 | SOUND_FORMAT_MP3_8KHZ => 8000,
+            SOUND_FORMAT_SPEEX => 16000,
+            _ => [5512, 11025, 22050, 44100][usize::from((flags >> 2) & 0x3)],
+        };
+
+        let bits_per_sample = if flags & 0x2 != 0 { 16 } else { 8 };
+        let num_channels = if flags & 0x1 != 0 { 2 } else { 1 };
+
+        let packet_type = match sound_format {
+            SOUND_FORMAT_AAC => Some(reader.read_u8()?),
+            _ => None,
+        };
+
+        Ok(AudioTagHeader {
+            sound_format,
+            sample_rate,
+            bits_per_sample,
+            num_channels,
+            packet_type,
+            len: reader.pos() as usize,
+        })
+    }
+
+    /// Returns true if the tag contains a sequence header instead of audio data.
+    pub fn is_sequence_header(&self) -> bool {
+        self.packet_type == Some(PACKET_TYPE_SEQUENCE_HEADER)
+    }
+
+    /// Get the codec ID for the sound format, if it is supported.
+    pub fn codec_id(&self) -> Option<AudioCodecId> {
+        let codec_id = match self.sound_format {
+            // Platform-endian PCM is little-endian in practice.
+            SOUND_FORMAT_PCM | SOUND_FORMAT_PCM_LE => match self.bits_per_sample {
+                8 => CODEC_ID_PCM_U8,
+                _ => CODEC_ID_PCM_S16LE,
+            },
+            SOUND_FORMAT_MP3 | SOUND_FORMAT_MP3_8KHZ => CODEC_ID_MP3,
+            SOUND_FORMAT_ALAW => CODEC_ID_PCM_ALAW,
+            SOUND_FORMAT_MULAW => CODEC_ID_PCM_MULAW,
+            SOUND_FORMAT_AAC => CODEC_ID_AAC,
+            SOUND_FORMAT_SPEEX => CODEC_ID_SPEEX,
+            _ => return None,
+        };
+
+        Some(codec_id)
+    }
+
+    /// Returns true if the codec is PCM.
+    pub fn is_pcm(&self) -> bool {
+        matches!(self.sound_format, SOUND_FORMAT_PCM | SOUND_FORMAT_PCM_LE)
+    }
+}
+
+/// The header of a video tag's data.
+pub struct VideoTagHeader {
+    pub frame_type: u8,
+    pub codec: u8,
+    /// For AVC and HEVC, the packet type.
+    pub packet_type: Option<u8>,
+    /// The composition time offset in milliseconds.
+    pub composition_time: i32,
+    /// The length of the header.
+    pub len: usize,
+}
+
+impl VideoTagHeader {
+    pub fn read(buf: &[u8]) -> Result<VideoTagHeader> {
+        let mut reader = BufReader::new(buf);
+
+        let flags = reader.read_u8()?;
+
+        let frame_type = flags >> 4;
+        let codec = flags & 0xf;
+
+        let mut packet_type = None;
+        let mut composition_time = 0;
+
+        match codec {
+            VIDEO_CODEC_AVC | VIDEO_CODEC_HEVC => {
+                packet_type = Some(reader.read_u8()?);
+                composition_time = reader.read_be_i24()?;
+            }
+            // VP6 frames are preceded by the horizontal and vertical adjustment.
+            VIDEO_CODEC_VP6 => reader.ignore_bytes(1)?,
+            // VP6 frames with alpha are additionally preceded by the offset to the alpha data.
+            VIDEO_CODEC_VP6_ALPHA => reader.ignore_bytes(4)?,
+            _ => (),
+        }
+
+        if frame_type == 0 {
+            return decode_error("flv: invalid video frame type");
+        }
+
+        Ok(VideoTagHeader {
+            frame_type,
+            codec,
+            packet_type,
+            composition_time,
+            len: reader.pos() as usize,
+        })
+    }
+
+    pub fn is_keyframe(&self) -> bool {
+        self.frame_type == FRAME_TYPE_KEYFRAME
+    }
+
+    /// Returns true if the tag contains a sequence header instead of a video frame.
+    pub fn is_sequence_header(&self) -> bool {
+        self.packet_type == Some(PACKET_TYPE_SEQUENCE_HEADER)
+    }
+
+    /// Returns true if the tag does not contain a video frame.
+    pub fn is_frameless(&self) -> bool {
+        self.frame_type == FRAME_TYPE_COMMAND
+            || self.packet_type == Some(PACKET_TYPE_END_OF_SEQUENCE)
+    }
+
+    /// Get the codec ID for the video codec, if it is supported.
+    pub fn codec_id(&self) -> Option<VideoCodecId> {
+        let codec_id = match self.codec {
+            VIDEO_CODEC_SORENSON_H263 => CODEC_ID_FLV,
+            VIDEO_CODEC_VP6 | VIDEO_CODEC_VP6_ALPHA => CODEC_ID_VP6,
+            VIDEO_CODEC_AVC => CODEC_ID_H264,
+            VIDEO_CODEC_HEVC => CODEC_ID_HEVC,
+            _ => return None,
+        };
+
+        Some(codec_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_read_tag_header() {
+        let buf = [0, 0, 0, 11, 0x29, 0, 0, 5, 0x12, 0x34, 0x56, 0xff, 0, 0, 0];
+        let header = TagHeader::read(&buf).unwrap();
+        assert_eq!(header.tag_type, TAG_TYPE_VIDEO);
+        assert!(header.filtered);
+        assert_eq!(header.data_size, 5);
+        // The extended timestamp is the sign bit.
+        assert_eq!(header.timestamp, i64::from(0xff123456u32 as i32));
+    }
+
+    #[test]
+    fn verify_read_audio_tag_header() {
+        // AAC, 44.1 kHz, 16-bit, stereo, raw data.
+        let header = AudioTagHeader::read(&[0xaf, 0x01]).unwrap();
+        assert_eq!(header.codec_id(), Some(CODEC_ID_AAC));
+        assert_eq!((header.sample_rate, header.num_channels), (44100, 2));
+        assert!(!header.is_sequence_header());
+        assert_eq!(header.len, 2);
+
+        // 8-bit mono PCM, 11 kHz.
+        let header = AudioTagHeader::read(&[0x34]).unwrap();
+        assert_eq!(header.codec_id(), Some(CODEC_ID_PCM_U8));
+        assert_eq!((header.sample_rate, header.num_channels), (11025, 1));
+        assert_eq!(header.len, 1);
+    }
+
+    #[test]
+    fn verify_read_video_tag_header() {
+        // AVC keyframe sequence header.
+        let header = VideoTagHeader::read(&[0x17, 0x00, 0, 0, 0]).unwrap();
+        assert_eq!(header.codec_id(), Some(CODEC_ID_H264));
+        assert!(header.is_keyframe() && header.is_sequence_header());
+
+        // AVC inter frame with a negative composition time.
+        let header = VideoTagHeader::read(&[0x27, 0x01, 0xff, 0xff, 0xd8]).unwrap();
+        assert!(!header.is_keyframe());
+        assert_eq!((header.composition_time, header.len), (-40, 5));
+
+        // VP6 inter frame.
+        let header = VideoTagHeader::read(&[0x24, 0x00]).unwrap();
+        assert_eq!(header.codec_id(), Some(CODEC_ID_VP6));
+        assert_eq!(header.len, 2);
+    }
+}
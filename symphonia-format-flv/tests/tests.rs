@@ -0,0 +1,251 @@
+use symphonia_core::codecs::CodecParameters;
+use symphonia_core::codecs::audio::well_known::CODEC_ID_AAC;
+use symphonia_core::codecs::video::well_known::CODEC_ID_H264;
+use symphonia_core::codecs::video::well_known::extra_data::VIDEO_EXTRA_DATA_ID_AVC_DECODER_CONFIG;
+use symphonia_core::errors::{Error, SeekErrorKind};
+use symphonia_core::formats::well_known::FORMAT_ID_FLV;
+use symphonia_core::formats::{FormatCapabilities, FormatReader, SeekMode, SeekTo};
+use symphonia_core::io::{MediaSourceStream, ReadOnlySource};
+use symphonia_core::meta::{RawValue, StandardTag};
+use symphonia_format_flv::FlvReader;
+
+const TAG_TYPE_AUDIO: u8 = 8;
+const TAG_TYPE_VIDEO: u8 = 9;
+const TAG_TYPE_SCRIPT_DATA: u8 = 18;
+
+/// An AVCDecoderConfigurationRecord for the High profile at level 3.1, without parameter sets.
+const AVC_CONFIG: [u8; 7] = [1, 0x64, 0, 0x1f, 0xff, 0xe0, 0];
+/// An AudioSpecificConfig for AAC-LC at 44.1 kHz in stereo.
+const AAC_CONFIG: [u8; 2] = [0x12, 0x10];
+
+/// Write an FLV tag, followed by the previous tag size field.
+fn tag(tag_type: u8, timestamp: u32, data: &[u8]) -> Vec<u8> {
+    let mut buf = vec![tag_type];
+    buf.extend_from_slice(&(data.len() as u32).to_be_bytes()[1..]);
+    buf.extend_from_slice(&timestamp.to_be_bytes()[1..]);
+    buf.push((timestamp >> 24) as u8);
+    buf.extend_from_slice(&[0; 3]);
+    buf.extend_from_slice(data);
+    buf.extend_from_slice(&(data.len() as u32 + 11).to_be_bytes());
+    buf
+}
+
+/// Write an AVC video tag with the given frame type, packet type, and composition time.
+fn avc_tag(timestamp: u32, keyframe: bool, packet_type: u8, cts: u32, data: &[u8]) -> Vec<u8> {
+    let flags = if keyframe { 0x17 } else { 0x27 };
+    let header = [&[flags, packet_type], &cts.to_be_bytes()[1..]].concat();
+    tag(TAG_TYPE_VIDEO, timestamp, &[header.as_slice(), data].concat())
+}
+
+/// Write an AAC audio tag with the given packet type.
+fn aac_tag(timestamp: u32, packet_type: u8, data: &[u8]) -> Vec<u8> {
+    tag(TAG_TYPE_AUDIO, timestamp, &[&[0xaf, packet_type], data].concat())
+}
+
+fn amf_string(value: &str) -> Vec<u8> {
+    [&(value.len() as u16).to_be_bytes(), value.as_bytes()].concat()
+}
+
+/// Write the `onMetaData` script data tag.
+fn on_metadata_tag() -> Vec<u8> {
+    let mut buf = vec![0x02];
+    buf.extend(amf_string("onMetaData"));
+    // ECMA array with 4 properties.
+    buf.push(0x08);
+    buf.extend(4u32.to_be_bytes());
+    for (key, value) in [("duration", 0.1f64), ("width", 64.0), ("height", 48.0)] {
+        buf.extend(amf_string(key));
+        buf.push(0x00);
+        buf.extend(value.to_be_bytes());
+    }
+    buf.extend(amf_string("encoder"));
+    buf.push(0x02);
+    buf.extend(amf_string("Lavf61.7.100"));
+    buf.extend(amf_string(""));
+    buf.push(0x09);
+    tag(TAG_TYPE_SCRIPT_DATA, 0, &buf)
+}
+
+/// Build an FLV file with H.264 video and AAC audio.
+///
+/// The video track has keyframes at 40 ms (after reordering), and 80 ms.
+fn make_flv() -> Vec<u8> {
+    // Signature, version, audio and video flags, header length, and the first previous tag size.
+    let mut buf = vec![b'F', b'L', b'V', 1, 0x05, 0, 0, 0, 9, 0, 0, 0, 0];
+
+    buf.extend(on_metadata_tag());
+    buf.extend(avc_tag(0, true, 0, 0, &AVC_CONFIG));
+    buf.extend(aac_tag(0, 0, &AAC_CONFIG));
+    buf.extend(avc_tag(0, true, 1, 40, &[1; 4]));
+    buf.extend(aac_tag(0, 1, &[2; 3]));
+    buf.extend(avc_tag(40, false, 1, 0, &[3; 2]));
+    buf.extend(aac_tag(23, 1, &[4; 3]));
+    buf.extend(avc_tag(80, true, 1, 0, &[5; 5]));
+    buf.extend(aac_tag(46, 1, &[6; 3]));
+    buf
+}
+
+fn open_flv(data: Vec<u8>) -> FlvReader<'static> {
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    FlvReader::try_new(mss, Default::default()).unwrap()
+}
+
+/// Read the track ID, PTS, DTS, and length of all packets.
+fn read_packets(reader: &mut FlvReader<'_>) -> Vec<(u32, i64, i64, usize)> {
+    let mut packets = Vec::new();
+    while let Some(packet) = reader.next_packet().unwrap() {
+        packets.push((packet.track_id, packet.pts.get(), packet.dts.get(), packet.data.len()));
+    }
+    packets
+}
+
+#[test]
+fn read_flv_tracks() {
+    let reader = open_flv(make_flv());
+
+    assert_eq!(reader.format_info().format, FORMAT_ID_FLV);
+
+    let tracks = reader.tracks();
+    assert_eq!(tracks.len(), 2);
+
+    // Tracks are created in order of their first tag.
+    match &tracks[0].codec_params {
+        Some(CodecParameters::Video(params)) => {
+            assert_eq!(params.codec, CODEC_ID_H264);
+            assert_eq!(params.level, Some(0x1f));
+            assert_eq!((params.width, params.height), (Some(64), Some(48)));
+            assert_eq!(params.extra_data.len(), 1);
+            assert_eq!(params.extra_data[0].id, VIDEO_EXTRA_DATA_ID_AVC_DECODER_CONFIG);
+            assert_eq!(&params.extra_data[0].data[..], &AVC_CONFIG);
+        }
+        _ => panic!("expected video codec parameters"),
+    }
+
+    match &tracks[1].codec_params {
+        Some(CodecParameters::Audio(params)) => {
+            assert_eq!(params.codec, CODEC_ID_AAC);
+            assert_eq!(params.sample_rate, Some(44100));
+            assert_eq!(params.channels.as_ref().map(|channels| channels.count()), Some(2));
+            assert_eq!(params.extra_data.as_deref(), Some(AAC_CONFIG.as_slice()));
+        }
+        _ => panic!("expected audio codec parameters"),
+    }
+
+    for track in tracks {
+        let tb = track.time_base.unwrap();
+        assert_eq!((tb.numer.get(), tb.denom.get()), (1, 1000));
+        assert_eq!(track.duration.map(|dur| dur.get()), Some(100));
+    }
+}
+
+#[test]
+fn read_flv_metadata() {
+    let mut reader = open_flv(make_flv());
+
+    let metadata = reader.metadata();
+    let rev = metadata.current().unwrap();
+
+    let tag = rev.media.tags.iter().find(|tag| tag.raw.key == "duration").unwrap();
+    assert!(matches!(tag.raw.value, RawValue::Float(value) if value == 0.1));
+
+    let tag = rev.media.tags.iter().find(|tag| tag.raw.key == "encoder").unwrap();
+    assert!(matches!(&tag.std, Some(StandardTag::Encoder(value)) if **value == "Lavf61.7.100"));
+}
+
+#[test]
+fn read_flv_packets() {
+    let len = make_flv().len() as u64;
+    let mut reader = open_flv(make_flv());
+
+    // Sequence headers are not returned as packets. Video packets are presented after the
+    // composition time offset.
+    assert_eq!(
+        read_packets(&mut reader),
+        vec![
+            (0, 40, 0, 4),
+            (1, 0, 0, 3),
+            (0, 40, 40, 2),
+            (1, 23, 23, 3),
+            (0, 80, 80, 5),
+            (1, 46, 46, 3)
+        ]
+    );
+
+    // The whole source was read.
+    assert_eq!(reader.source_position(), Some(len));
+}
+
+#[test]
+fn read_flv_keyframes() {
+    let mut reader = open_flv(make_flv());
+
+    let mut keyframes = Vec::new();
+    while let Some(packet) = reader.next_packet().unwrap() {
+        keyframes.push((packet.track_id, packet.is_keyframe));
+    }
+
+    // The video frame type signals keyframes. Audio tags do not signal keyframes.
+    assert_eq!(keyframes, vec![(0, true), (1, true), (0, false), (1, true), (0, true), (1, true)]);
+}
+
+#[test]
+fn read_flv_packets_with_track_filter() {
+    let mut reader = open_flv(make_flv());
+
+    reader.set_track_filter(&[1]).unwrap();
+
+    assert_eq!(read_packets(&mut reader), vec![(1, 0, 0, 3), (1, 23, 23, 3), (1, 46, 46, 3)]);
+}
+
+#[test]
+fn seek_flv() {
+    let mut reader = open_flv(make_flv());
+
+    assert_eq!(
+        reader.capabilities(),
+        FormatCapabilities::SEEKABLE | FormatCapabilities::KNOWN_DURATION
+    );
+
+    // Seeking lands on the video keyframe at or preceding the required timestamp.
+    let seeked =
+        reader.seek(SeekMode::Accurate, SeekTo::Timestamp { ts: 90.into(), track_id: 0 }).unwrap();
+    assert_eq!((seeked.track_id, seeked.actual_ts.get()), (0, 80));
+
+    assert_eq!(read_packets(&mut reader), vec![(0, 80, 80, 5), (1, 46, 46, 3)]);
+
+    let seeked =
+        reader.seek(SeekMode::Accurate, SeekTo::Timestamp { ts: 60.into(), track_id: 0 }).unwrap();
+    assert_eq!(seeked.actual_ts.get(), 40);
+    assert_eq!(read_packets(&mut reader).len(), 6);
+
+    // If there is no keyframe preceding the required timestamp, the first keyframe is used.
+    let seeked =
+        reader.seek(SeekMode::Accurate, SeekTo::Timestamp { ts: 0.into(), track_id: 0 }).unwrap();
+    assert_eq!(seeked.actual_ts.get(), 40);
+
+    // Every audio tag is a sync point.
+    let seeked =
+        reader.seek(SeekMode::Accurate, SeekTo::Timestamp { ts: 30.into(), track_id: 1 }).unwrap();
+    assert_eq!((seeked.track_id, seeked.actual_ts.get()), (1, 23));
+    assert_eq!(read_packets(&mut reader).len(), 3);
+}
+
+#[test]
+fn read_flv_unseekable() {
+    let source = ReadOnlySource::new(std::io::Cursor::new(make_flv()));
+    let mss = MediaSourceStream::new(Box::new(source), Default::default());
+    let mut reader = FlvReader::try_new(mss, Default::default()).unwrap();
+
+    // The position of an unseekable source is not reported.
+    assert_eq!(reader.source_position(), None);
+
+    // The duration is still known from the metadata.
+    assert_eq!(reader.capabilities(), FormatCapabilities::KNOWN_DURATION);
+
+    assert!(matches!(
+        reader.seek(SeekMode::Accurate, SeekTo::Timestamp { ts: 0.into(), track_id: 0 }),
+        Err(Error::SeekError(SeekErrorKind::Unseekable))
+    ));
+
+    assert_eq!(read_packets(&mut reader).len(), 6);
+}
@@ -5,7 +5,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use symphonia_common::mpeg::video::AVCDecoderConfigurationRecord;
+use symphonia_common::mpeg::video::{AVCDecoderConfigurationRecord, AVCSequenceParameterSet};
 use symphonia_core::codecs::CodecProfile;
 use symphonia_core::codecs::video::VideoExtraData;
 use symphonia_core::codecs::video::well_known::CODEC_ID_H264;
@@ -20,6 +20,8 @@ pub struct AvcCAtom {
     extra_data: VideoExtraData,
     profile: CodecProfile,
     level: u32,
+    /// The first sequence parameter set.
+    sps: Option<AVCSequenceParameterSet>,
 }
 
 impl Atom for AvcCAtom {
@@ -46,7 +48,12 @@ impl Atom for AvcCAtom {
 
         let avc_config = AVCDecoderConfigurationRecord::read(&extra_data.data)?;
 
-        Ok(Self { extra_data, profile: avc_config.profile, level: avc_config.level })
+        Ok(Self {
+            extra_data,
+            profile: avc_config.profile,
+            level: avc_config.level,
+            sps: avc_config.sps,
+        })
     }
 }
 
@@ -56,5 +63,9 @@ impl AvcCAtom {
         entry.profile = Some(self.profile);
         entry.level = Some(self.level);
         entry.extra_data.push(self.extra_data);
+
+        if let Some(sps) = self.sps {
+            entry.apply_sps(sps.width, sps.height, sps.frame_rate);
+        }
     }
 }
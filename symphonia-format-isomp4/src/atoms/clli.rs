@@ -0,0 +1,47 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use symphonia_core::codecs::video::ContentLightLevel;
+
+use crate::atoms::stsd::VisualSampleEntry;
+use crate::atoms::{Atom, AtomHeader, AtomIterator, ReadAtom, Result, decode_error};
+
+/// The size of the content light level atom payload.
+const CLLI_SIZE: u64 = 4;
+/// The size of the content light level full atom payload, including the version and flags.
+const COLL_SIZE: u64 = 8;
+
+/// Content light level atom (clli and CoLL).
+#[derive(Debug)]
+pub struct ClliAtom {
+    content_light_level: ContentLightLevel,
+}
+
+impl Atom for ClliAtom {
+    fn read<R: ReadAtom>(it: &mut AtomIterator<R>, header: &AtomHeader) -> Result<Self> {
+        // The CoLL atom, used by VP9 and older muxers, is a full atom version of the clli atom.
+        match header.data_size() {
+            Some(CLLI_SIZE) => (),
+            Some(COLL_SIZE) => {
+                let (_, _) = it.read_extended_header()?;
+            }
+            Some(_) => return decode_error("isomp4 (clli): invalid atom size"),
+            None => return decode_error("isomp4 (clli): expected atom size to be known"),
+        }
+
+        let content_light_level =
+            ContentLightLevel { max_cll: it.read_u16()?, max_fall: it.read_u16()? };
+
+        Ok(Self { content_light_level })
+    }
+}
+
+impl ClliAtom {
+    pub fn fill_video_sample_entry(self, entry: &mut VisualSampleEntry) {
+        entry.content_light_level = Some(self.content_light_level);
+    }
+}
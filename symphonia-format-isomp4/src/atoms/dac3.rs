@@ -5,7 +5,9 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use symphonia_core::audio::{Channels, Position};
 use symphonia_core::codecs::audio::well_known::CODEC_ID_AC3;
+use symphonia_core::io::{BitReaderLtr, ReadBitsLtr};
 
 use crate::atoms::stsd::AudioSampleEntry;
 use crate::atoms::{Atom, AtomHeader, AtomIterator, ReadAtom, Result, decode_error};
@@ -14,11 +16,14 @@ use crate::atoms::{Atom, AtomHeader, AtomIterator, ReadAtom, Result, decode_erro
 pub struct Dac3Atom {
     /// AC3SpecificBox
     extra_data: Box<[u8]>,
+    /// The sample rate, if signalled.
+    sample_rate: Option<u32>,
+    /// The channels of the main program.
+    channels: Position,
 }
 
 impl Atom for Dac3Atom {
     fn read<R: ReadAtom>(it: &mut AtomIterator<R>, header: &AtomHeader) -> Result<Self> {
-        // TODO: Validate.
         const MAX_DAC3_ATOM_SIZE: u64 = 4 * 1024;
 
         // AC3SpecificBox should have length
@@ -30,13 +35,76 @@ impl Atom for Dac3Atom {
 
         let extra_data = it.read_boxed_slice_exact(len)?;
 
-        Ok(Dac3Atom { extra_data })
+        // ETSI TS 102 366, Annex F.4.
+        let mut bs = BitReaderLtr::new(&extra_data);
+
+        let fscod = bs.read_bits_leq32(2)?;
+        let _bsid = bs.read_bits_leq32(5)?;
+        let _bsmod = bs.read_bits_leq32(3)?;
+        let acmod = bs.read_bits_leq32(3)?;
+        let lfeon = bs.read_bool()?;
+
+        Ok(Dac3Atom {
+            extra_data,
+            sample_rate: ac3_sample_rate(fscod),
+            channels: ac3_channels(acmod, lfeon),
+        })
     }
 }
 
 impl Dac3Atom {
     pub fn fill_audio_sample_entry(self, entry: &mut AudioSampleEntry) {
         entry.codec_id = CODEC_ID_AC3;
+
+        if let Some(sample_rate) = self.sample_rate {
+            entry.sample_rate = f64::from(sample_rate);
+        }
+
+        entry.channels = Some(Channels::Positioned(self.channels));
         entry.extra_data = Some(self.extra_data);
     }
 }
+
+/// Gets the sample rate signalled by an AC-3 or E-AC-3 sample rate code (fscod).
+pub(crate) fn ac3_sample_rate(fscod: u32) -> Option<u32> {
+    match fscod {
+        0 => Some(48000),
+        1 => Some(44100),
+        2 => Some(32000),
+        // A reduced sample rate is signalled in the bitstream only.
+        _ => None,
+    }
+}
+
+/// Gets the channels signalled by an AC-3 or E-AC-3 audio coding mode (acmod) and LFE flag.
+pub(crate) fn ac3_channels(acmod: u32, lfeon: bool) -> Position {
+    let channels = match acmod {
+        // Dual mono (1+1).
+        0 => Position::FRONT_LEFT | Position::FRONT_RIGHT,
+        1 => Position::FRONT_CENTER,
+        2 => Position::FRONT_LEFT | Position::FRONT_RIGHT,
+        3 => Position::FRONT_LEFT | Position::FRONT_CENTER | Position::FRONT_RIGHT,
+        4 => Position::FRONT_LEFT | Position::FRONT_RIGHT | Position::REAR_CENTER,
+        5 => {
+            Position::FRONT_LEFT
+                | Position::FRONT_CENTER
+                | Position::FRONT_RIGHT
+                | Position::REAR_CENTER
+        }
+        6 => {
+            Position::FRONT_LEFT
+                | Position::FRONT_RIGHT
+                | Position::SIDE_LEFT
+                | Position::SIDE_RIGHT
+        }
+        _ => {
+            Position::FRONT_LEFT
+                | Position::FRONT_CENTER
+                | Position::FRONT_RIGHT
+                | Position::SIDE_LEFT
+                | Position::SIDE_RIGHT
+        }
+    };
+
+    if lfeon { channels | Position::LFE1 } else { channels }
+}
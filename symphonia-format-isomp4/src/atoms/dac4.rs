@@ -0,0 +1,64 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use log::debug;
+use symphonia_core::codecs::audio::well_known::CODEC_ID_AC4;
+use symphonia_core::io::{BitReaderLtr, ReadBitsLtr};
+
+use crate::atoms::stsd::AudioSampleEntry;
+use crate::atoms::{Atom, AtomHeader, AtomIterator, ReadAtom, Result, decode_error};
+
+#[derive(Debug)]
+pub struct Dac4Atom {
+    /// AC4SpecificBox
+    extra_data: Box<[u8]>,
+    /// The sample rate signalled by the AC-4 decoder specific information.
+    sample_rate: u32,
+}
+
+impl Atom for Dac4Atom {
+    fn read<R: ReadAtom>(it: &mut AtomIterator<R>, header: &AtomHeader) -> Result<Self> {
+        const MAX_DAC4_ATOM_SIZE: u64 = 4 * 1024;
+
+        let len = match header.data_size() {
+            Some(len) if len <= MAX_DAC4_ATOM_SIZE => len as usize,
+            Some(_) => return decode_error("isomp4 (dac4): atom size is greater than 4 kb"),
+            None => return decode_error("isomp4 (dac4): expected atom size to be known"),
+        };
+
+        let extra_data = it.read_boxed_slice_exact(len)?;
+
+        // Parse the start of the ac4_dsi_v1 structure (ETSI TS 103 190-2, Annex E.6).
+        let mut bs = BitReaderLtr::new(&extra_data);
+
+        let ac4_dsi_version = bs.read_bits_leq32(3)?;
+
+        if ac4_dsi_version != 1 {
+            return decode_error("isomp4 (dac4): unsupported ac4 dsi version");
+        }
+
+        let bitstream_version = bs.read_bits_leq32(7)?;
+        let sample_rate = if bs.read_bool()? { 48000 } else { 44100 };
+        let frame_rate_index = bs.read_bits_leq32(4)?;
+        let num_presentations = bs.read_bits_leq32(9)?;
+
+        debug!(
+            "ac-4: bitstream_version={bitstream_version}, sample_rate={sample_rate}, \
+            frame_rate_index={frame_rate_index}, n_presentations={num_presentations}"
+        );
+
+        Ok(Dac4Atom { extra_data, sample_rate })
+    }
+}
+
+impl Dac4Atom {
+    pub fn fill_audio_sample_entry(self, entry: &mut AudioSampleEntry) {
+        entry.codec_id = CODEC_ID_AC4;
+        entry.sample_rate = f64::from(self.sample_rate);
+        entry.extra_data = Some(self.extra_data);
+    }
+}
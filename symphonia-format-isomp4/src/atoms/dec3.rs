@@ -5,8 +5,12 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use symphonia_core::audio::{Channels, Position};
 use symphonia_core::codecs::audio::well_known::CODEC_ID_EAC3;
+use symphonia_core::codecs::audio::well_known::profiles::CODEC_PROFILE_EAC3_JOC;
+use symphonia_core::io::{BitReaderLtr, FiniteBitStream, ReadBitsLtr};
 
+use crate::atoms::dac3::{ac3_channels, ac3_sample_rate};
 use crate::atoms::stsd::AudioSampleEntry;
 use crate::atoms::{Atom, AtomHeader, AtomIterator, ReadAtom, Result, decode_error};
 
@@ -14,11 +18,16 @@ use crate::atoms::{Atom, AtomHeader, AtomIterator, ReadAtom, Result, decode_erro
 pub struct Dec3Atom {
     /// EAC3SpecificBox
     extra_data: Box<[u8]>,
+    /// The sample rate of the main program, if signalled.
+    sample_rate: Option<u32>,
+    /// The channels of the main program, including those of its dependent substreams.
+    channels: Position,
+    /// The main program carries Joint Object Coding (Dolby Atmos) content.
+    is_joc: bool,
 }
 
 impl Atom for Dec3Atom {
     fn read<R: ReadAtom>(it: &mut AtomIterator<R>, header: &AtomHeader) -> Result<Self> {
-        // TODO: Validate.
         const MAX_DEC3_ATOM_SIZE: u64 = 4 * 1024;
 
         // EAC3SpecificBox should have length
@@ -30,13 +39,92 @@ impl Atom for Dec3Atom {
 
         let extra_data = it.read_boxed_slice_exact(len)?;
 
-        Ok(Dec3Atom { extra_data })
+        // ETSI TS 102 366, Annex F.6.
+        let mut bs = BitReaderLtr::new(&extra_data);
+
+        let _data_rate = bs.read_bits_leq32(13)?;
+        let num_ind_sub = bs.read_bits_leq32(3)? + 1;
+
+        let mut sample_rate = None;
+        let mut channels = Position::empty();
+
+        for i in 0..num_ind_sub {
+            let fscod = bs.read_bits_leq32(2)?;
+            let _bsid = bs.read_bits_leq32(5)?;
+            bs.ignore_bits(1)?;
+            let _asvc = bs.read_bool()?;
+            let _bsmod = bs.read_bits_leq32(3)?;
+            let acmod = bs.read_bits_leq32(3)?;
+            let lfeon = bs.read_bool()?;
+            bs.ignore_bits(3)?;
+            let num_dep_sub = bs.read_bits_leq32(4)?;
+
+            let chan_loc = if num_dep_sub > 0 {
+                bs.read_bits_leq32(9)?
+            }
+            else {
+                bs.ignore_bits(1)?;
+                0
+            };
+
+            // Only the first independent substream carries the main program.
+            if i == 0 {
+                sample_rate = ac3_sample_rate(fscod);
+                channels = ac3_channels(acmod, lfeon) | dependent_channels(chan_loc);
+            }
+        }
+
+        // The Dolby Atmos extension is optional and follows the independent substreams.
+        let is_joc = if bs.bits_left() >= 16 {
+            bs.ignore_bits(7)?;
+            let flag_ec3_extension_type_a = bs.read_bool()?;
+            let _complexity_index_type_a = bs.read_bits_leq32(8)?;
+            flag_ec3_extension_type_a
+        }
+        else {
+            false
+        };
+
+        Ok(Dec3Atom { extra_data, sample_rate, channels, is_joc })
     }
 }
 
 impl Dec3Atom {
     pub fn fill_audio_sample_entry(self, entry: &mut AudioSampleEntry) {
         entry.codec_id = CODEC_ID_EAC3;
+
+        if let Some(sample_rate) = self.sample_rate {
+            entry.sample_rate = f64::from(sample_rate);
+        }
+
+        if self.is_joc {
+            entry.profile = Some(CODEC_PROFILE_EAC3_JOC);
+        }
+
+        entry.channels = Some(Channels::Positioned(self.channels));
         entry.extra_data = Some(self.extra_data);
     }
 }
+
+/// Gets the additional channels carried by the dependent substreams of an independent substream
+/// from their channel locations (chan_loc).
+fn dependent_channels(chan_loc: u32) -> Position {
+    const CHAN_LOC_POSITIONS: [Position; 9] = [
+        Position::FRONT_LEFT_CENTER.union(Position::FRONT_RIGHT_CENTER),
+        Position::REAR_LEFT.union(Position::REAR_RIGHT),
+        Position::REAR_CENTER,
+        Position::TOP_CENTER,
+        Position::SIDE_LEFT.union(Position::SIDE_RIGHT),
+        Position::FRONT_LEFT_WIDE.union(Position::FRONT_RIGHT_WIDE),
+        Position::TOP_FRONT_LEFT.union(Position::TOP_FRONT_RIGHT),
+        Position::TOP_FRONT_CENTER,
+        Position::LFE2,
+    ];
+
+    // The first channel location is the most-significant bit.
+    CHAN_LOC_POSITIONS
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| chan_loc & (0x100 >> i) != 0)
+        .fold(Position::empty(), |acc, (_, &pos)| acc | pos)
+}
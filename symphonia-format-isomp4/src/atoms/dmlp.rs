@@ -0,0 +1,56 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use symphonia_common::dolby::audio::truehd::FormatInfo;
+use symphonia_core::codecs::audio::well_known::CODEC_ID_TRUEHD;
+
+use crate::atoms::stsd::AudioSampleEntry;
+use crate::atoms::{Atom, AtomHeader, AtomIterator, ReadAtom, Result, decode_error};
+
+#[derive(Debug)]
+pub struct DmlpAtom {
+    /// MLPSpecificBox
+    extra_data: Box<[u8]>,
+    /// The stream format information.
+    format_info: FormatInfo,
+}
+
+impl Atom for DmlpAtom {
+    fn read<R: ReadAtom>(it: &mut AtomIterator<R>, header: &AtomHeader) -> Result<Self> {
+        const MAX_DMLP_ATOM_SIZE: u64 = 4 * 1024;
+
+        // The format information, peak data rate, and reserved fields.
+        const MIN_DMLP_ATOM_SIZE: u64 = 10;
+
+        let len = match header.data_size() {
+            Some(len) if len > MAX_DMLP_ATOM_SIZE => {
+                return decode_error("isomp4 (dmlp): atom size is greater than 4 kb");
+            }
+            Some(len) if len < MIN_DMLP_ATOM_SIZE => {
+                return decode_error("isomp4 (dmlp): atom is too short");
+            }
+            Some(len) => len as usize,
+            None => return decode_error("isomp4 (dmlp): expected atom size to be known"),
+        };
+
+        let extra_data = it.read_boxed_slice_exact(len)?;
+
+        let format_info =
+            FormatInfo::from_u32(u32::from_be_bytes(extra_data[..4].try_into().unwrap()))?;
+
+        Ok(DmlpAtom { extra_data, format_info })
+    }
+}
+
+impl DmlpAtom {
+    pub fn fill_audio_sample_entry(self, entry: &mut AudioSampleEntry) {
+        entry.codec_id = CODEC_ID_TRUEHD;
+        entry.sample_rate = f64::from(self.format_info.sample_rate);
+        entry.channels = Some(self.format_info.channels);
+        entry.extra_data = Some(self.extra_data);
+    }
+}
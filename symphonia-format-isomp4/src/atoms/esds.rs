@@ -62,10 +62,13 @@ impl EsdsAtom {
     /// If the elementary stream descriptor describes an audio stream, populate the provided
     /// audio sample entry.
     pub fn fill_audio_sample_entry(self, entry: &mut AudioSampleEntry) -> Result<()> {
-        match codec_id_from_object_type_indication(self.es_desc.dec_config.object_type_indication) {
+        let obj_type = self.es_desc.dec_config.object_type_indication;
+
+        match codec_id_from_object_type_indication(obj_type) {
             Some(CodecId::Audio(id)) => {
                 // Object type indication identified an audio codec.
                 entry.codec_id = id;
+                entry.profile = profile_from_object_type_indication(obj_type);
             }
             Some(_) => {
                 // Object type indication identified a non-audio codec. This is unexpected.
@@ -77,7 +80,13 @@ impl EsdsAtom {
         if let Some(ds_config) = self.es_desc.dec_config.dec_specific_info {
             // Try to read the audio specific configuration and populate the audio sample entry.
             if let Ok(asc) = AudioSpecificConfig::read(&ds_config.extra_data) {
-                entry.profile = get_audio_codec_profile(&asc);
+                entry.profile = get_audio_codec_profile(&asc).or(entry.profile);
+
+                // The audio specific configuration supersedes the sample entry. The AAC decoder
+                // does not apply SBR or PS, therefore report the sample rate, channels, and frames
+                // per packet of the core. The profile still identifies HE-AAC.
+                entry.sample_rate = f64::from(asc.sample_rate);
+                entry.frames_per_packet = asc.frames_per_packet();
                 entry.channels = asc.channels;
             }
 
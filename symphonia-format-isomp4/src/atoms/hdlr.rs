@@ -22,8 +22,12 @@ pub enum HandlerType {
     Subtitle,
     /// Metadata handler.
     Metadata,
+    /// QuickTime metadata handler. Metadata keys are stored in a separate keys atom.
+    QuickTimeMetadata,
     /// Text handler.
     Text,
+    /// Timecode handler.
+    Timecode,
     /// Unknown handler type.
     Other([u8; 4]),
 }
@@ -52,8 +56,10 @@ impl Atom for HdlrAtom {
             b"vide" => HandlerType::Video,
             b"soun" => HandlerType::Sound,
             b"meta" => HandlerType::Metadata,
+            b"mdta" => HandlerType::QuickTimeMetadata,
             b"subt" => HandlerType::Subtitle,
             b"text" => HandlerType::Text,
+            b"tmcd" => HandlerType::Timecode,
             hdlr => {
                 warn!("unknown handler type '{}'", std::str::from_utf8(hdlr).unwrap_or("????"));
                 HandlerType::Other(*hdlr)
@@ -5,7 +5,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use symphonia_common::mpeg::video::HEVCDecoderConfigurationRecord;
+use symphonia_common::mpeg::video::{HEVCDecoderConfigurationRecord, HEVCSequenceParameterSet};
 use symphonia_core::codecs::CodecProfile;
 use symphonia_core::codecs::video::VideoExtraData;
 use symphonia_core::codecs::video::well_known::CODEC_ID_HEVC;
@@ -21,6 +21,8 @@ pub struct HvcCAtom {
     extra_data: VideoExtraData,
     profile: CodecProfile,
     level: u32,
+    /// The first sequence parameter set.
+    sps: Option<HEVCSequenceParameterSet>,
 }
 
 impl Atom for HvcCAtom {
@@ -42,7 +44,12 @@ impl Atom for HvcCAtom {
 
         let hevc_config = HEVCDecoderConfigurationRecord::read(&extra_data.data)?;
 
-        Ok(Self { extra_data, profile: hevc_config.profile, level: hevc_config.level })
+        Ok(Self {
+            extra_data,
+            profile: hevc_config.profile,
+            level: hevc_config.level,
+            sps: hevc_config.sps,
+        })
     }
 }
 
@@ -52,5 +59,9 @@ impl HvcCAtom {
         entry.profile = Some(self.profile);
         entry.level = Some(self.level);
         entry.extra_data.push(self.extra_data);
+
+        if let Some(sps) = self.sps {
+            entry.apply_sps(sps.width, sps.height, sps.frame_rate);
+        }
     }
 }
@@ -17,7 +17,7 @@ use symphonia_core::meta::{
 use symphonia_core::meta::{RawValue, Visual};
 use symphonia_core::util::{bits, text};
 use symphonia_metadata::utils::images::try_get_image_info;
-use symphonia_metadata::utils::{id3v1, itunes};
+use symphonia_metadata::utils::{id3v1, iso6709, itunes};
 
 use crate::atoms::{Atom, AtomHeader, AtomIterator, AtomType, ReadAtom, Result, decode_error};
 
@@ -937,6 +937,96 @@ impl Atom for IlstAtom {
     }
 }
 
+/// QuickTime metadata item list atom.
+///
+/// Unlike the iTunes-style metadata item list, the atom type of each item is the 1-based index of
+/// the item's key in the keys atom.
+#[allow(dead_code)]
+pub struct MdtaIlstAtom {
+    /// Metadata items and the index of their key.
+    pub items: Vec<(u32, MetaTagAtom)>,
+}
+
+impl MdtaIlstAtom {
+    /// Build a metadata revision from the metadata items using the provided keys.
+    pub fn into_metadata(self, keys: &[String]) -> MetadataRevision {
+        let mut mb = MetadataBuilder::new(ISOMP4_METADATA_INFO);
+
+        for (index, tag) in self.items {
+            // Key indices are 1-based.
+            let Some(key) = index.checked_sub(1).and_then(|i| keys.get(i as usize))
+            else {
+                debug!("metadata item references unknown key {index}");
+                continue;
+            };
+
+            let map = get_mdta_std_tag_map(key);
+
+            for value_atom in tag.values.iter() {
+                // Parse the value atom data into a raw value of any type, if possible.
+                if let Some(raw_value) = parse_tag_value(value_atom.data_type, &value_atom.data) {
+                    // Parse the location into structured coordinates.
+                    if key == "com.apple.quicktime.location.ISO6709" {
+                        if let RawValue::String(value) = &raw_value {
+                            match iso6709::parse_iso6709(value) {
+                                Some(location) => {
+                                    mb.set_location(location);
+                                }
+                                None => warn!("invalid iso 6709 location '{value}'"),
+                            }
+                        }
+                    }
+
+                    let std_tag = map(&raw_value);
+                    mb.add_tag(Tag::new_from_parts(key.as_str(), raw_value, std_tag));
+                }
+                else {
+                    warn!("unsupported data type {:?} for '{}' tag", value_atom.data_type, key);
+                }
+            }
+        }
+
+        mb.build()
+    }
+}
+
+impl Atom for MdtaIlstAtom {
+    fn read<R: ReadAtom>(it: &mut AtomIterator<R>, _header: &AtomHeader) -> Result<Self> {
+        let mut items = Vec::new();
+
+        while let Some(header) = it.next_header()? {
+            // The atom type is the big-endian key index.
+            let index = match header.atom_type {
+                AtomType::Other(index) => u32::from_be_bytes(index),
+                _ => continue,
+            };
+
+            items.push((index, it.read_atom::<MetaTagAtom>()?));
+        }
+
+        Ok(MdtaIlstAtom { items })
+    }
+}
+
+/// Get the standard tag mapping for a QuickTime metadata key.
+fn get_mdta_std_tag_map(key: &str) -> fn(&RawValue) -> Option<StandardTag> {
+    match key {
+        "com.apple.quicktime.artist" => map_std_str!(StandardTag::Artist),
+        "com.apple.quicktime.author" => map_std_str!(StandardTag::Author),
+        "com.apple.quicktime.comment" => map_std_str!(StandardTag::Comment),
+        "com.apple.quicktime.copyright" => map_std_str!(StandardTag::Copyright),
+        "com.apple.quicktime.creationdate" => map_std_str!(StandardTag::RecordingDate),
+        "com.apple.quicktime.description" => map_std_str!(StandardTag::Description),
+        "com.apple.quicktime.genre" => map_std_str!(StandardTag::Genre),
+        "com.apple.quicktime.keywords" => map_std_str!(StandardTag::PodcastKeywords),
+        "com.apple.quicktime.location.name" => map_std_str!(StandardTag::RecordingLocation),
+        "com.apple.quicktime.software" => map_std_str!(StandardTag::Encoder),
+        "com.apple.quicktime.title" => map_std_str!(StandardTag::TrackTitle),
+        // Make, model, and ISO 6709 location have no standard tag.
+        _ => |_| None,
+    }
+}
+
 /// Get a raw tag key for a given metadata atom type.
 fn get_raw_tag_key(atom_type: AtomType) -> &'static str {
     match atom_type {
@@ -0,0 +1,47 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::atoms::limits::*;
+use crate::atoms::{Atom, AtomHeader, AtomIterator, ReadAtom, Result, decode_error};
+
+/// Metadata item keys atom.
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct KeysAtom {
+    /// Metadata item keys. The metadata item list refers to a key by its 1-based index.
+    pub keys: Vec<String>,
+}
+
+impl Atom for KeysAtom {
+    fn read<R: ReadAtom>(it: &mut AtomIterator<R>, _header: &AtomHeader) -> Result<Self> {
+        let (_, _) = it.read_extended_header()?;
+
+        let entry_count = it.read_u32()?;
+
+        // Limit the maximum initial capacity to prevent malicious files from using all the
+        // available memory.
+        let mut keys = Vec::with_capacity(MAX_TABLE_INITIAL_CAPACITY.min(entry_count as usize));
+
+        for _ in 0..entry_count {
+            // The key size includes the size and namespace fields.
+            let key_size = it.read_u32()?;
+
+            if key_size < 8 {
+                return decode_error("isomp4 (keys): invalid key size");
+            }
+
+            // The key namespace. In practice, this is always 'mdta' (reverse DNS).
+            let _namespace = it.read_quad_bytes()?;
+
+            // Do a lossy conversion because metadata should not prevent the demuxer from working.
+            let buf = it.read_boxed_slice_exact(key_size as usize - 8)?;
+            keys.push(String::from_utf8_lossy(&buf).to_string());
+        }
+
+        Ok(KeysAtom { keys })
+    }
+}
@@ -0,0 +1,86 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use symphonia_core::codecs::video::MasteringDisplay;
+
+use crate::atoms::stsd::VisualSampleEntry;
+use crate::atoms::{Atom, AtomHeader, AtomIterator, ReadAtom, Result, decode_error};
+
+/// The size of the mastering display colour volume atom payload.
+const MDCV_SIZE: u64 = 24;
+/// The size of the SMPTE 2086 mastering display metadata atom payload, including the version and
+/// flags.
+const SMDM_SIZE: u64 = 28;
+
+/// Mastering display colour volume atom (mdcv and SmDm).
+#[derive(Debug)]
+pub struct MdcvAtom {
+    mastering_display: MasteringDisplay,
+}
+
+impl Atom for MdcvAtom {
+    fn read<R: ReadAtom>(it: &mut AtomIterator<R>, header: &AtomHeader) -> Result<Self> {
+        // The mdcv atom (ISO/IEC 23001-8) stores the values as they are in the HEVC mastering
+        // display colour volume SEI message. The SmDm atom, used by VP9 and older muxers, is a
+        // full atom storing them as fixed-point values. Both variants map to the same atom type,
+        // but may be differentiated by their size.
+        let mastering_display = match header.data_size() {
+            Some(MDCV_SIZE) => {
+                // The primaries are in green, blue, and red order, in increments of 0.00002.
+                let mut primaries = [(0.0, 0.0); 3];
+
+                for i in [1, 2, 0] {
+                    primaries[i] = read_chromaticity(it, 50_000.0)?;
+                }
+
+                MasteringDisplay {
+                    primaries,
+                    white_point: read_chromaticity(it, 50_000.0)?,
+                    // The luminance values are in increments of 0.0001 candelas per square metre.
+                    max_luminance: it.read_u32()? as f32 / 10_000.0,
+                    min_luminance: it.read_u32()? as f32 / 10_000.0,
+                }
+            }
+            Some(SMDM_SIZE) => {
+                let (_, _) = it.read_extended_header()?;
+
+                // The primaries are in red, green, and blue order, as 0.16 fixed-point values.
+                let mut primaries = [(0.0, 0.0); 3];
+
+                for primary in primaries.iter_mut() {
+                    *primary = read_chromaticity(it, 65_536.0)?;
+                }
+
+                MasteringDisplay {
+                    primaries,
+                    white_point: read_chromaticity(it, 65_536.0)?,
+                    // The maximum and minimum luminance are 24.8 and 18.14 fixed-point values,
+                    // respectively.
+                    max_luminance: it.read_u32()? as f32 / 256.0,
+                    min_luminance: it.read_u32()? as f32 / 16_384.0,
+                }
+            }
+            Some(_) => return decode_error("isomp4 (mdcv): invalid atom size"),
+            None => return decode_error("isomp4 (mdcv): expected atom size to be known"),
+        };
+
+        Ok(Self { mastering_display })
+    }
+}
+
+impl MdcvAtom {
+    pub fn fill_video_sample_entry(self, entry: &mut VisualSampleEntry) {
+        entry.mastering_display = Some(self.mastering_display);
+    }
+}
+
+/// Read a pair of (x, y) chromaticity coordinates, scaled by the provided divisor.
+fn read_chromaticity<R: ReadAtom>(it: &mut AtomIterator<R>, divisor: f32) -> Result<(f32, f32)> {
+    let x = f32::from(it.read_u16()?) / divisor;
+    let y = f32::from(it.read_u16()?) / divisor;
+    Ok((x, y))
+}
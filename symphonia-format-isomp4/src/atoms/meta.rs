@@ -9,7 +9,11 @@ use std::fmt::Debug;
 
 use symphonia_core::meta::MetadataRevision;
 
-use crate::atoms::{Atom, AtomHeader, AtomIterator, AtomType, IlstAtom, ReadAtom, Result};
+use crate::atoms::ilst::MdtaIlstAtom;
+use crate::atoms::{
+    Atom, AtomHeader, AtomIterator, AtomType, HandlerType, HdlrAtom, IlstAtom, KeysAtom, ReadAtom,
+    Result,
+};
 
 /// User data atom.
 #[allow(dead_code)]
@@ -32,14 +36,34 @@ impl MetaAtom {
 }
 
 impl Atom for MetaAtom {
-    #[allow(clippy::single_match)]
     fn read<R: ReadAtom>(it: &mut AtomIterator<R>, _header: &AtomHeader) -> Result<Self> {
-        let (_, _) = it.read_extended_header()?;
+        // The ISO/MP4 BMFF meta atom is a full atom, but the QuickTime meta atom is not. A QuickTime
+        // meta atom starts with the handler atom.
+        let mut peek = [0; 8];
+        it.peek_buf_exact(&mut peek)?;
+
+        if &peek[4..8] != b"hdlr" {
+            let (_, _) = it.read_extended_header()?;
+        }
 
         let mut metadata = None;
+        let mut is_mdta = false;
+        let mut keys = None;
+        let mut mdta_ilst = None;
 
         while let Some(header) = it.next_header()? {
             match header.atom_type {
+                AtomType::Handler => {
+                    let hdlr = it.read_atom::<HdlrAtom>()?;
+                    is_mdta = hdlr.handler_type == HandlerType::QuickTimeMetadata;
+                }
+                AtomType::MetaKeys => {
+                    keys = Some(it.read_atom::<KeysAtom>()?);
+                }
+                AtomType::MetaList if is_mdta => {
+                    // The items of a QuickTime metadata item list reference keys in the keys atom.
+                    mdta_ilst = Some(it.read_atom::<MdtaIlstAtom>()?);
+                }
                 AtomType::MetaList => {
                     metadata = Some(it.read_atom::<IlstAtom>()?.metadata);
                 }
@@ -48,6 +72,11 @@ impl Atom for MetaAtom {
             }
         }
 
+        if let Some(ilst) = mdta_ilst {
+            let keys = keys.map(|keys| keys.keys).unwrap_or_default();
+            metadata = Some(ilst.into_metadata(&keys));
+        }
+
         Ok(MetaAtom { metadata })
     }
 }
@@ -0,0 +1,62 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use log::debug;
+use symphonia_core::codecs::audio::well_known::CODEC_ID_MPEGH_3D_AUDIO;
+
+use crate::atoms::stsd::AudioSampleEntry;
+use crate::atoms::{Atom, AtomHeader, AtomIterator, ReadAtom, Result, decode_error};
+
+#[derive(Debug)]
+pub struct MhaCAtom {
+    /// MHADecoderConfigurationRecord
+    extra_data: Box<[u8]>,
+}
+
+impl Atom for MhaCAtom {
+    fn read<R: ReadAtom>(it: &mut AtomIterator<R>, header: &AtomHeader) -> Result<Self> {
+        const MAX_MHAC_ATOM_SIZE: u64 = 64 * 1024;
+
+        // The configuration version, profile and level, reference channel layout, and the length
+        // of the configuration.
+        const MIN_MHAC_ATOM_SIZE: u64 = 5;
+
+        let len = match header.data_size() {
+            Some(len) if len > MAX_MHAC_ATOM_SIZE => {
+                return decode_error("isomp4 (mhaC): atom size is greater than 64 kb");
+            }
+            Some(len) if len < MIN_MHAC_ATOM_SIZE => {
+                return decode_error("isomp4 (mhaC): atom is too short");
+            }
+            Some(len) => len as usize,
+            None => return decode_error("isomp4 (mhaC): expected atom size to be known"),
+        };
+
+        let extra_data = it.read_boxed_slice_exact(len)?;
+
+        // The configuration (mpegh3daConfig) must be contained within the atom.
+        let config_len = usize::from(u16::from_be_bytes([extra_data[3], extra_data[4]]));
+
+        if 5 + config_len > len {
+            return decode_error("isomp4 (mhaC): configuration exceeds atom size");
+        }
+
+        debug!(
+            "mpeg-h: profile_level={}, reference_channel_layout={}",
+            extra_data[1], extra_data[2]
+        );
+
+        Ok(MhaCAtom { extra_data })
+    }
+}
+
+impl MhaCAtom {
+    pub fn fill_audio_sample_entry(self, entry: &mut AudioSampleEntry) {
+        entry.codec_id = CODEC_ID_MPEGH_3D_AUDIO;
+        entry.extra_data = Some(self.extra_data);
+    }
+}
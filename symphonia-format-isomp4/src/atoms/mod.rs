@@ -21,10 +21,13 @@ pub mod limits {
 
 pub(crate) mod alac;
 pub(crate) mod avcc;
+pub(crate) mod clli;
 pub(crate) mod co64;
 pub(crate) mod ctts;
 pub(crate) mod dac3;
+pub(crate) mod dac4;
 pub(crate) mod dec3;
+pub(crate) mod dmlp;
 pub(crate) mod dovi;
 pub(crate) mod edts;
 pub(crate) mod elst;
@@ -34,11 +37,14 @@ pub(crate) mod ftyp;
 pub(crate) mod hdlr;
 pub(crate) mod hvcc;
 pub(crate) mod ilst;
+pub(crate) mod keys;
+pub(crate) mod mdcv;
 pub(crate) mod mdhd;
 pub(crate) mod mdia;
 pub(crate) mod mehd;
 pub(crate) mod meta;
 pub(crate) mod mfhd;
+pub(crate) mod mhac;
 pub(crate) mod minf;
 pub(crate) mod moof;
 pub(crate) mod moov;
@@ -47,6 +53,7 @@ pub(crate) mod mvhd;
 pub(crate) mod opus;
 pub(crate) mod sidx;
 pub(crate) mod smhd;
+pub(crate) mod st3d;
 pub(crate) mod stbl;
 pub(crate) mod stco;
 pub(crate) mod stsc;
@@ -54,6 +61,7 @@ pub(crate) mod stsd;
 pub(crate) mod stss;
 pub(crate) mod stsz;
 pub(crate) mod stts;
+pub(crate) mod sv3d;
 pub(crate) mod tfhd;
 pub(crate) mod tkhd;
 pub(crate) mod traf;
@@ -68,24 +76,30 @@ use crate::atoms::limits::MAX_ITERATION_DEPTH;
 pub use self::meta::MetaAtom;
 pub use alac::AlacAtom;
 pub use avcc::AvcCAtom;
+pub use clli::ClliAtom;
 pub use co64::Co64Atom;
 #[allow(unused_imports)]
 pub use ctts::CttsAtom;
 pub use dac3::Dac3Atom;
+pub use dac4::Dac4Atom;
 pub use dec3::Dec3Atom;
+pub use dmlp::DmlpAtom;
 pub use dovi::DoviAtom;
 pub use edts::EdtsAtom;
 pub use elst::ElstAtom;
 pub use esds::EsdsAtom;
 pub use flac::FlacAtom;
 pub use ftyp::FtypAtom;
-pub use hdlr::HdlrAtom;
+pub use hdlr::{HandlerType, HdlrAtom};
 pub use hvcc::HvcCAtom;
 pub use ilst::IlstAtom;
+pub use keys::KeysAtom;
+pub use mdcv::MdcvAtom;
 pub use mdhd::MdhdAtom;
 pub use mdia::MdiaAtom;
 pub use mehd::MehdAtom;
 pub use mfhd::MfhdAtom;
+pub use mhac::MhaCAtom;
 pub use minf::MinfAtom;
 pub use moof::MoofAtom;
 pub use moov::MoovAtom;
@@ -94,6 +108,7 @@ pub use mvhd::MvhdAtom;
 pub use opus::OpusAtom;
 pub use sidx::SidxAtom;
 pub use smhd::SmhdAtom;
+pub use st3d::St3dAtom;
 pub use stbl::StblAtom;
 pub use stco::StcoAtom;
 pub use stsc::StscAtom;
@@ -102,6 +117,7 @@ pub use stsd::StsdAtom;
 pub use stss::StssAtom;
 pub use stsz::StszAtom;
 pub use stts::SttsAtom;
+pub use sv3d::Sv3dAtom;
 pub use tfhd::TfhdAtom;
 pub use tkhd::TkhdAtom;
 pub use traf::TrafAtom;
@@ -115,21 +131,26 @@ pub use wave::WaveAtom;
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum AtomType {
     Ac3Config,
+    Ac4Config,
     AdvisoryTag,
     AlbumArtistTag,
     AlbumTag,
     ArrangerTag,
     ArtistTag,
     AudioSampleEntryAc3,
+    AudioSampleEntryAc4,
     AudioSampleEntryAlac,
     AudioSampleEntryALaw,
+    AudioSampleEntryDts,
     AudioSampleEntryEc3,
     AudioSampleEntryF32,
     AudioSampleEntryF64,
     AudioSampleEntryFlac,
     AudioSampleEntryLpcm,
     AudioSampleEntryMp3,
+    AudioSampleEntryMlp,
     AudioSampleEntryMp4a,
+    AudioSampleEntryMpegH,
     AudioSampleEntryMuLaw,
     AudioSampleEntryOpus,
     AudioSampleEntryQtWave,
@@ -149,8 +170,10 @@ pub enum AtomType {
     ComposerTag,
     CompositionTimeToSample,
     ConductorTag,
+    ContentLightLevel,
     CopyrightTag,
     CoverTag,
+    CubemapProjection,
     CustomGenreTag,
     DateTag,
     DescriptionTag,
@@ -161,6 +184,7 @@ pub enum AtomType {
     EditList,
     EncodedByTag,
     EncoderTag,
+    EquirectangularProjection,
     Esds,
     FileCreatorUrlTag,
     FileType,
@@ -187,12 +211,15 @@ pub enum AtomType {
     LabelUrlTag,
     LongDescriptionTag,
     LyricsTag,
+    MasteringDisplayColourVolume,
     Media,
     MediaData,
     MediaHeader,
     MediaInfo,
     MediaTypeTag,
+    MeshProjection,
     Meta,
+    MetaKeys,
     MetaList,
     MetaTagData,
     MetaTagMeaning,
@@ -205,7 +232,9 @@ pub enum AtomType {
     MovieExtendsHeader,
     MovieFragment,
     MovieFragmentHeader,
+    MlpConfig,
     MovieHeader,
+    MpegHConfig,
     NarratorTag,
     OpusDsConfig,
     OriginalArtistTag,
@@ -215,6 +244,8 @@ pub enum AtomType {
     PodcastKeywordsTag,
     PodcastTag,
     ProducerTag,
+    Projection,
+    ProjectionHeader,
     PublisherTag,
     PurchaseDateTag,
     RatingTag,
@@ -234,6 +265,8 @@ pub enum AtomType {
     SortNameTag,
     SortShowNameTag,
     SoundMediaHeader,
+    SphericalVideo,
+    Stereoscopic3d,
     SubtitleSampleEntryText,
     SubtitleSampleEntryTimedText,
     SubtitleSampleEntryXml,
@@ -241,6 +274,7 @@ pub enum AtomType {
     TempoTag,
     TextConfig,
     TimeToSample,
+    TimecodeSampleEntry,
     Track,
     TrackArtistUrl,
     TrackExtends,
@@ -267,6 +301,7 @@ pub enum AtomType {
     VisualSampleEntryMp4v,
     VisualSampleEntryVp8,
     VisualSampleEntryVp9,
+    Wide,
     WorkTag,
     WriterTag,
     XidTag,
@@ -278,18 +313,25 @@ impl From<[u8; 4]> for AtomType {
         match &val {
             b".mp3" => AtomType::AudioSampleEntryMp3,
             b"ac-3" => AtomType::AudioSampleEntryAc3,
+            b"ac-4" => AtomType::AudioSampleEntryAc4,
             b"alac" => AtomType::AudioSampleEntryAlac,
             b"alaw" => AtomType::AudioSampleEntryALaw,
             b"av01" => AtomType::VisualSampleEntryAv1,
             b"avc1" => AtomType::VisualSampleEntryAvc1,
             b"avcC" => AtomType::AvcConfiguration,
             b"btrt" => AtomType::BitRate,
+            b"cbmp" => AtomType::CubemapProjection,
             b"ec-3" => AtomType::AudioSampleEntryEc3,
             b"clap" => AtomType::CleanAperture,
+            b"clli" => AtomType::ContentLightLevel,
             b"co64" => AtomType::ChunkOffset64,
+            b"CoLL" => AtomType::ContentLightLevel,
             b"ctts" => AtomType::CompositionTimeToSample,
             b"dac3" => AtomType::Ac3Config,
+            b"dac4" => AtomType::Ac4Config,
             b"dec3" => AtomType::Eac3Config,
+            b"dmlp" => AtomType::MlpConfig,
+            b"dtsc" | b"dtse" | b"dtsh" | b"dtsl" => AtomType::AudioSampleEntryDts,
             b"data" => AtomType::MetaTagData,
             b"dfLa" => AtomType::FlacDsConfig,
             b"dOps" => AtomType::OpusDsConfig,
@@ -299,6 +341,7 @@ impl From<[u8; 4]> for AtomType {
             b"dvvC" => AtomType::DolbyVisionConfiguration,
             b"edts" => AtomType::Edit,
             b"elst" => AtomType::EditList,
+            b"equi" => AtomType::EquirectangularProjection,
             b"esds" => AtomType::Esds,
             b"fl32" => AtomType::AudioSampleEntryF32,
             b"fl64" => AtomType::AudioSampleEntryF64,
@@ -310,32 +353,42 @@ impl From<[u8; 4]> for AtomType {
             b"hvc1" => AtomType::VisualSampleEntryHvc1,
             b"hvcC" => AtomType::HevcConfiguration,
             b"ilst" => AtomType::MetaList,
+            b"keys" => AtomType::MetaKeys,
             b"in24" => AtomType::AudioSampleEntryS24,
             b"in32" => AtomType::AudioSampleEntryS32,
             b"lpcm" => AtomType::AudioSampleEntryLpcm,
             b"mdat" => AtomType::MediaData,
+            b"mdcv" => AtomType::MasteringDisplayColourVolume,
             b"mdhd" => AtomType::MediaHeader,
             b"mdia" => AtomType::Media,
             b"mean" => AtomType::MetaTagMeaning,
             b"mehd" => AtomType::MovieExtendsHeader,
             b"meta" => AtomType::Meta,
             b"mfhd" => AtomType::MovieFragmentHeader,
+            b"mha1" | b"mha2" | b"mhm1" | b"mhm2" => AtomType::AudioSampleEntryMpegH,
+            b"mhaC" => AtomType::MpegHConfig,
+            b"mlpa" => AtomType::AudioSampleEntryMlp,
             b"minf" => AtomType::MediaInfo,
             b"moof" => AtomType::MovieFragment,
             b"moov" => AtomType::Movie,
             b"mp4a" => AtomType::AudioSampleEntryMp4a,
             b"mp4v" => AtomType::VisualSampleEntryMp4v,
+            b"mshp" => AtomType::MeshProjection,
             b"mvex" => AtomType::MovieExtends,
             b"mvhd" => AtomType::MovieHeader,
             b"name" => AtomType::MetaTagName,
             b"Opus" => AtomType::AudioSampleEntryOpus,
             b"pasp" => AtomType::PixelAspectRatio,
+            b"prhd" => AtomType::ProjectionHeader,
+            b"proj" => AtomType::Projection,
             b"raw " => AtomType::AudioSampleEntryU8,
             b"sbtt" => AtomType::SubtitleSampleEntryText,
             b"sidx" => AtomType::SegmentIndex,
             b"skip" => AtomType::Skip,
+            b"SmDm" => AtomType::MasteringDisplayColourVolume,
             b"smhd" => AtomType::SoundMediaHeader,
             b"sowt" => AtomType::AudioSampleEntryS16Le,
+            b"st3d" => AtomType::Stereoscopic3d,
             b"stbl" => AtomType::SampleTable,
             b"stco" => AtomType::ChunkOffset,
             b"stpp" => AtomType::SubtitleSampleEntryXml,
@@ -344,8 +397,10 @@ impl From<[u8; 4]> for AtomType {
             b"stss" => AtomType::SyncSample,
             b"stsz" => AtomType::SampleSize,
             b"stts" => AtomType::TimeToSample,
+            b"sv3d" => AtomType::SphericalVideo,
             b"tfhd" => AtomType::TrackFragmentHeader,
             b"tkhd" => AtomType::TrackHeader,
+            b"tmcd" => AtomType::TimecodeSampleEntry,
             b"traf" => AtomType::TrackFragment,
             b"trak" => AtomType::Track,
             b"trex" => AtomType::TrackExtends,
@@ -359,6 +414,7 @@ impl From<[u8; 4]> for AtomType {
             b"vp08" => AtomType::VisualSampleEntryVp8,
             b"vp09" => AtomType::VisualSampleEntryVp9,
             b"wave" => AtomType::AudioSampleEntryQtWave,
+            b"wide" => AtomType::Wide,
             // Metadata Boxes
             b"----" => AtomType::FreeFormTag,
             b"aART" => AtomType::AlbumArtistTag,
@@ -465,6 +521,8 @@ pub enum AtomError {
     UnexpectedUnknownSizeAtom,
     /// The size of the atom is unknown,
     UnknownAtomSize,
+    /// The size of the data to be read from the atom exceeds the maximum that may be allocated.
+    AtomDataTooLarge,
     /// Other Symphonia errors encountered during atom parsing.
     Other(symphonia_core::errors::Error),
 }
@@ -558,6 +616,15 @@ impl AtomHeader {
         Ok(AtomHeader { atom_type, atom_pos, atom_len, header_len })
     }
 
+    /// If the atom size is unknown, set the size such that the atom ends at the given position.
+    fn resolve_unknown_size(&mut self, end: u64) {
+        if self.atom_len.is_none() {
+            // The atom must be atleast the size of the header.
+            let len = end.saturating_sub(self.atom_pos).max(u64::from(self.header_len));
+            self.atom_len = NonZeroU64::new(len);
+        }
+    }
+
     /// Get the atom type.
     pub fn atom_type(&self) -> AtomType {
         self.atom_type
@@ -606,13 +673,15 @@ pub struct AtomIterator<R: ReadAtom> {
     pending: Option<AtomHeader>,
     /// The length of the container, if known.
     len: Option<u64>,
+    /// The maximum size of the data read from an atom into a new buffer, if limited.
+    max_data_size: Option<usize>,
 }
 
 impl<R: ReadAtom> AtomIterator<R> {
     /// Instantiate a new atom iterator.
-    pub(crate) fn new(reader: R, len: Option<u64>) -> Self {
+    pub(crate) fn new(reader: R, len: Option<u64>, max_data_size: Option<usize>) -> Self {
         let stack = Vec::with_capacity(MAX_ITERATION_DEPTH);
-        AtomIterator { reader, stack, pending: None, len }
+        AtomIterator { reader, stack, pending: None, len, max_data_size }
     }
 
     /// Consume the iterator and return the inner reader.
@@ -620,6 +689,16 @@ impl<R: ReadAtom> AtomIterator<R> {
         self.reader
     }
 
+    /// Get an immutable reference to the inner reader.
+    pub(crate) fn inner(&self) -> &R {
+        &self.reader
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub(crate) fn inner_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
     /// Get an immutable reference to the pending atom.
     pub(crate) fn pending(&self) -> Option<&AtomHeader> {
         self.pending.as_ref()
@@ -633,6 +712,12 @@ impl<R: ReadAtom> AtomIterator<R> {
     ///
     /// Discards any unread data from the previous atom.
     pub(crate) fn next_header(&mut self) -> Result<Option<&AtomHeader>> {
+        // Unknown atoms, such as vendor-specific atoms, are never read. Log them before they are
+        // skipped.
+        if let Some(AtomType::Other(atom_type)) = self.pending.map(|atom| atom.atom_type()) {
+            log::debug!("skipping unknown atom: {:?}", String::from_utf8_lossy(&atom_type));
+        }
+
         // If there is a pending atom, or it wasn't fully consumed, skip over it now.
         let _ = self.skip_atom();
 
@@ -657,7 +742,15 @@ impl<R: ReadAtom> AtomIterator<R> {
             }
         }
 
-        let atom = AtomHeader::read(&mut self.reader)?;
+        let mut atom = AtomHeader::read(&mut self.reader)?;
+
+        // A top-level atom with an unknown size extends to the end of the file. If the length of
+        // the file is known, then the size of the atom is too.
+        if self.stack.is_empty() {
+            if let Some(len) = self.len {
+                atom.resolve_unknown_size(len);
+            }
+        }
 
         // let indent = 2 * self.stack.len();
         // log::trace!(
@@ -772,6 +865,25 @@ impl<R: ReadAtom> AtomIterator<R> {
         Ok(())
     }
 
+    /// Repositions the iterator and inner reader to the start of the container.
+    pub(crate) fn rewind(&mut self) -> Result<()>
+    where
+        R: MediaSource,
+    {
+        self.seek_top_level(0)
+    }
+
+    /// Repositions the iterator and inner reader to the start of the top-level atom at `pos`.
+    pub(crate) fn seek_top_level(&mut self, pos: u64) -> Result<()>
+    where
+        R: MediaSource,
+    {
+        self.seek_reader(pos)?;
+        self.stack.clear();
+        self.pending = None;
+        Ok(())
+    }
+
     /// If an atom is pending to be read, repositions the iterator and inner reader to the end of
     /// the pending atom.
     ///
@@ -864,6 +976,33 @@ impl<R: ReadAtom> AtomIterator<R> {
         Ok(data)
     }
 
+    /// Reads exactly the number of bytes requested, at the specified position, into the provided
+    /// buffer. The buffer is resized to the number of bytes requested.
+    ///
+    /// After this operation, it is only safe to make more raw read calls. To continue iteration,
+    /// the iterator must be re-synchronized by calling `seek_atom_start` or `seek_atom_end`.
+    pub(crate) fn read_raw_exact_into(
+        &mut self,
+        pos: u64,
+        len: usize,
+        buf: &mut Vec<u8>,
+    ) -> Result<()>
+    where
+        R: MediaSource,
+    {
+        // Must currently have a pending atom to allow resynchronizing to it.
+        let _ = self.pending.as_ref().ok_or(AtomError::NoPendingAtom)?;
+
+        // Seek to the desired position. Doesn't seek if already in position.
+        self.seek_reader(pos)?;
+
+        // Do the read.
+        buf.resize(len, 0);
+        ReadBytes::read_buf_exact(&mut self.reader, buf)?;
+
+        Ok(())
+    }
+
     //
     // Reading primitives
     //
@@ -906,10 +1045,26 @@ impl<R: ReadAtom> AtomIterator<R> {
         Ok(self.reader.read_buf_exact(buf)?)
     }
 
+    /// Reads exactly the number of bytes required to fill the provided buffer, but does not advance
+    /// the reader, or returns an error.
+    pub(crate) fn peek_buf_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        let pos = self.reader.pos();
+        self.read_buf_exact(buf)?;
+
+        if self.reader.seek_buffered(pos) != pos {
+            return Err(AtomError::SeekOutOfRange);
+        }
+
+        Ok(())
+    }
+
     /// Reads exactly the number of bytes requested, and returns a boxed slice of the data or an
     /// error.
     #[inline]
     pub(crate) fn read_boxed_slice_exact(&mut self, len: usize) -> Result<Box<[u8]>> {
+        if self.max_data_size.is_some_and(|max| len > max) {
+            return Err(AtomError::AtomDataTooLarge);
+        }
         self.ensure_parent_atom_data(len as u64)?;
         Ok(self.reader.read_boxed_slice_exact(len)?)
     }
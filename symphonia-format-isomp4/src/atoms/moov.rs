@@ -8,8 +8,8 @@
 use symphonia_core::meta::MetadataRevision;
 
 use crate::atoms::{
-    Atom, AtomHeader, AtomIterator, AtomType, MvexAtom, MvhdAtom, ReadAtom, Result, TrakAtom,
-    UdtaAtom, decode_error,
+    Atom, AtomHeader, AtomIterator, AtomType, MetaAtom, MvexAtom, MvhdAtom, ReadAtom, Result,
+    TrakAtom, UdtaAtom, decode_error,
 };
 
 use log::warn;
@@ -26,12 +26,27 @@ pub struct MoovAtom {
     pub mvex: Option<MvexAtom>,
     /// User data (usually metadata).
     pub udta: Option<UdtaAtom>,
+    /// Metadata atom. QuickTime metadata is usually stored here instead of in the user data atom.
+    pub meta: Option<MetaAtom>,
 }
 
 impl MoovAtom {
     /// If metadata was read, consumes the metadata and returns it.
     pub fn take_metadata(&mut self) -> Option<MetadataRevision> {
-        self.udta.as_mut().and_then(|udta| udta.take_metadata())
+        let udta = self.udta.as_mut().and_then(|udta| udta.take_metadata());
+        let meta = self.meta.as_mut().and_then(|meta| meta.take_metadata());
+
+        match (udta, meta) {
+            (Some(mut udta), Some(meta)) => {
+                // Merge both revisions.
+                udta.media.tags.extend(meta.media.tags);
+                udta.media.visuals.extend(meta.media.visuals);
+                udta.media.synced_lyrics.extend(meta.media.synced_lyrics);
+                udta.media.location = udta.media.location.or(meta.media.location);
+                Some(udta)
+            }
+            (udta, meta) => udta.or(meta),
+        }
     }
 
     /// Is the movie segmented.
@@ -46,6 +61,7 @@ impl Atom for MoovAtom {
         let mut traks = Vec::new();
         let mut mvex = None;
         let mut udta = None;
+        let mut meta = None;
 
         while let Some(header) = it.next_header()? {
             match header.atom_type {
@@ -62,6 +78,9 @@ impl Atom for MoovAtom {
                 AtomType::UserData => {
                     udta = Some(it.read_atom::<UdtaAtom>()?);
                 }
+                AtomType::Meta => {
+                    meta = Some(it.read_atom::<MetaAtom>()?);
+                }
                 _ => (),
             }
         }
@@ -82,6 +101,6 @@ impl Atom for MoovAtom {
             }
         }
 
-        Ok(MoovAtom { mvhd: mvhd.unwrap(), traks, mvex, udta })
+        Ok(MoovAtom { mvhd: mvhd.unwrap(), traks, mvex, udta, meta })
     }
 }
@@ -5,6 +5,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use symphonia_common::xiph::audio::opus::OPUS_MAX_FRAMES_PER_PACKET;
 use symphonia_core::codecs::audio::well_known::CODEC_ID_OPUS;
 use symphonia_core::errors::Error;
 
@@ -68,6 +69,7 @@ impl Atom for OpusAtom {
 impl OpusAtom {
     pub fn fill_audio_sample_entry(self, entry: &mut AudioSampleEntry) {
         entry.codec_id = CODEC_ID_OPUS;
+        entry.frames_per_packet = Some(OPUS_MAX_FRAMES_PER_PACKET);
         entry.extra_data = Some(self.extra_data);
     }
 }
@@ -55,11 +55,15 @@ impl Atom for SidxAtom {
             .ok_or(Error::DecodeError("isomp4 (sidx): expected atom size to be known"))?;
 
         let (earliest_pts, first_offset) = match version {
-            0 => (u64::from(it.read_u32()?), anchor + u64::from(it.read_u32()?)),
-            1 => (it.read_u64()?, anchor + it.read_u64()?),
+            0 => (u64::from(it.read_u32()?), u64::from(it.read_u32()?)),
+            1 => (it.read_u64()?, it.read_u64()?),
             _ => return decode_error("isomp4 (sidx): invalid version"),
         };
 
+        let first_offset = anchor
+            .checked_add(first_offset)
+            .ok_or(Error::DecodeError("isomp4 (sidx): first offset overflow"))?;
+
         let _reserved = it.read_u16()?;
         let reference_count = it.read_u16()?;
 
@@ -0,0 +1,44 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use symphonia_core::codecs::video::StereoMode;
+
+use crate::atoms::stsd::VisualSampleEntry;
+use crate::atoms::{Atom, AtomHeader, AtomIterator, ReadAtom, Result};
+
+use log::debug;
+
+/// Stereoscopic 3D video atom.
+#[derive(Debug)]
+pub struct St3dAtom {
+    /// The stereo mode, if known.
+    stereo_mode: Option<StereoMode>,
+}
+
+impl Atom for St3dAtom {
+    fn read<R: ReadAtom>(it: &mut AtomIterator<R>, _header: &AtomHeader) -> Result<Self> {
+        let (_, _) = it.read_extended_header()?;
+
+        let stereo_mode = match it.read_u8()? {
+            0 => Some(StereoMode::Mono),
+            1 => Some(StereoMode::TopBottom),
+            2 => Some(StereoMode::LeftRight),
+            mode => {
+                debug!("unknown stereo mode {mode}");
+                None
+            }
+        };
+
+        Ok(Self { stereo_mode })
+    }
+}
+
+impl St3dAtom {
+    pub fn fill_video_sample_entry(self, entry: &mut VisualSampleEntry) {
+        entry.stereo_mode = self.stereo_mode;
+    }
+}
@@ -7,7 +7,7 @@
 
 use crate::atoms::{
     Atom, AtomHeader, AtomIterator, AtomType, Co64Atom, ReadAtom, Result, StcoAtom, StscAtom,
-    StsdAtom, StszAtom, SttsAtom, decode_error,
+    StsdAtom, StssAtom, StszAtom, SttsAtom, decode_error,
 };
 
 use log::{debug, warn};
@@ -20,6 +20,8 @@ pub struct StblAtom {
     pub stts: SttsAtom,
     pub stsc: StscAtom,
     pub stsz: StszAtom,
+    /// If not present, all samples are sync samples.
+    pub stss: Option<StssAtom>,
     pub stco: Option<StcoAtom>,
     pub co64: Option<Co64Atom>,
 }
@@ -30,6 +32,7 @@ impl Atom for StblAtom {
         let mut stts = None;
         let mut stsc = None;
         let mut stsz = None;
+        let mut stss = None;
         let mut stco = None;
         let mut co64 = None;
 
@@ -46,8 +49,7 @@ impl Atom for StblAtom {
                     debug!("ignoring ctts atom.");
                 }
                 AtomType::SyncSample => {
-                    // Sync sample atom is only required for video.
-                    debug!("ignoring stss atom.");
+                    stss = Some(it.read_atom::<StssAtom>()?);
                 }
                 AtomType::SampleToChunk => {
                     stsc = Some(it.read_atom::<StscAtom>()?);
@@ -91,6 +93,7 @@ impl Atom for StblAtom {
             stts: stts.unwrap(),
             stsc: stsc.unwrap(),
             stsz: stsz.unwrap(),
+            stss,
             stco,
             co64,
         })
@@ -9,7 +9,9 @@ use core::str;
 
 use log::debug;
 use symphonia_core::audio::{Channels, Position};
-use symphonia_core::codecs::audio::well_known::CODEC_ID_MP3;
+use symphonia_core::codecs::audio::well_known::{
+    CODEC_ID_DCA, CODEC_ID_MP3, CODEC_ID_MPEGH_3D_AUDIO,
+};
 use symphonia_core::codecs::audio::well_known::{CODEC_ID_PCM_F32BE, CODEC_ID_PCM_F32LE};
 use symphonia_core::codecs::audio::well_known::{CODEC_ID_PCM_F64BE, CODEC_ID_PCM_F64LE};
 use symphonia_core::codecs::audio::well_known::{CODEC_ID_PCM_S8, CODEC_ID_PCM_U8};
@@ -24,13 +26,17 @@ use symphonia_core::codecs::audio::{
 };
 use symphonia_core::codecs::subtitle::SubtitleCodecParameters;
 use symphonia_core::codecs::subtitle::well_known::CODEC_ID_MOV_TEXT;
-use symphonia_core::codecs::video::{VideoCodecId, VideoCodecParameters, VideoExtraData};
+use symphonia_core::codecs::video::{
+    ContentLightLevel, MasteringDisplay, Projection, StereoMode, VideoCodecId,
+    VideoCodecParameters, VideoExtraData,
+};
 use symphonia_core::codecs::{CodecParameters, CodecProfile};
+use symphonia_core::units::{FrameRate, Timecode};
 
 use crate::atoms::{
-    AlacAtom, Atom, AtomHeader, AtomIterator, AtomType, AvcCAtom, Dac3Atom, Dec3Atom, DoviAtom,
-    EsdsAtom, FlacAtom, HvcCAtom, OpusAtom, ReadAtom, Result, WaveAtom, decode_error,
-    unsupported_error,
+    AlacAtom, Atom, AtomHeader, AtomIterator, AtomType, AvcCAtom, ClliAtom, Dac3Atom, Dac4Atom,
+    Dec3Atom, DmlpAtom, DoviAtom, EsdsAtom, FlacAtom, HvcCAtom, MdcvAtom, MhaCAtom, OpusAtom,
+    ReadAtom, Result, St3dAtom, Sv3dAtom, WaveAtom, decode_error, unsupported_error,
 };
 use crate::fp::FpU16;
 
@@ -66,10 +72,14 @@ impl Atom for StsdAtom {
             AtomType::AudioSampleEntryMp4a
             | AtomType::AudioSampleEntryAlac
             | AtomType::AudioSampleEntryAc3
+            | AtomType::AudioSampleEntryAc4
+            | AtomType::AudioSampleEntryDts
             | AtomType::AudioSampleEntryEc3
             | AtomType::AudioSampleEntryFlac
             | AtomType::AudioSampleEntryOpus
+            | AtomType::AudioSampleEntryMlp
             | AtomType::AudioSampleEntryMp3
+            | AtomType::AudioSampleEntryMpegH
             | AtomType::AudioSampleEntryLpcm
             | AtomType::AudioSampleEntryQtWave
             | AtomType::AudioSampleEntryALaw
@@ -102,6 +112,10 @@ impl Atom for StsdAtom {
                 let entry = it.read_atom::<SubtitleSampleEntry>()?;
                 SampleEntry::Subtitle(entry)
             }
+            AtomType::TimecodeSampleEntry => {
+                let entry = it.read_atom::<TimecodeSampleEntry>()?;
+                SampleEntry::Timecode(entry)
+            }
             _ => {
                 // Potentially subtitles, metadata, hints, etc.
                 SampleEntry::Other
@@ -125,6 +139,14 @@ impl StsdAtom {
             _ => None,
         }
     }
+
+    /// Get the timecode sample entry, if the track is a timecode track.
+    pub fn timecode_sample_entry(&self) -> Option<&TimecodeSampleEntry> {
+        match &self.sample_entry {
+            SampleEntry::Timecode(entry) => Some(entry),
+            _ => None,
+        }
+    }
 }
 
 /// Polymorphic sample entry atom.
@@ -133,6 +155,7 @@ pub enum SampleEntry {
     Audio(AudioSampleEntry),
     Visual(VisualSampleEntry),
     Subtitle(SubtitleSampleEntry),
+    Timecode(TimecodeSampleEntry),
     // Metadata,
     Other,
 }
@@ -308,6 +331,10 @@ impl Atom for AudioSampleEntry {
                     let atom = it.read_atom::<Dac3Atom>()?;
                     atom.fill_audio_sample_entry(&mut entry);
                 }
+                AtomType::Ac4Config => {
+                    let atom = it.read_atom::<Dac4Atom>()?;
+                    atom.fill_audio_sample_entry(&mut entry);
+                }
                 AtomType::AudioSampleEntryAlac => {
                     let atom = it.read_atom::<AlacAtom>()?;
                     atom.fill_audio_sample_entry(&mut entry);
@@ -324,6 +351,14 @@ impl Atom for AudioSampleEntry {
                     let atom = it.read_atom::<OpusAtom>()?;
                     atom.fill_audio_sample_entry(&mut entry);
                 }
+                AtomType::MlpConfig => {
+                    let atom = it.read_atom::<DmlpAtom>()?;
+                    atom.fill_audio_sample_entry(&mut entry);
+                }
+                AtomType::MpegHConfig => {
+                    let atom = it.read_atom::<MhaCAtom>()?;
+                    atom.fill_audio_sample_entry(&mut entry);
+                }
                 AtomType::AudioSampleEntryQtWave => {
                     // The QuickTime WAVE (aka. siDecompressionParam) atom may contain many
                     // different types of sub-atoms to store decoder parameters.
@@ -341,6 +376,17 @@ impl Atom for AudioSampleEntry {
             entry.codec_id = CODEC_ID_MP3;
         }
 
+        // The DTS specific atom is not required to identify the codec. The extensions present are
+        // determined from the media data.
+        if header.atom_type == AtomType::AudioSampleEntryDts {
+            entry.codec_id = CODEC_ID_DCA;
+        }
+
+        // The configuration of MPEG-H 3D Audio may be carried in-band instead of in a mhaC atom.
+        if header.atom_type == AtomType::AudioSampleEntryMpegH {
+            entry.codec_id = CODEC_ID_MPEGH_3D_AUDIO;
+        }
+
         Ok(entry)
     }
 }
@@ -473,18 +519,47 @@ pub struct VisualSampleEntry {
     pub profile: Option<CodecProfile>,
     pub level: Option<u32>,
     pub extra_data: Vec<VideoExtraData>,
+    pub mastering_display: Option<MasteringDisplay>,
+    pub content_light_level: Option<ContentLightLevel>,
+    pub projection: Option<Projection>,
+    pub stereo_mode: Option<StereoMode>,
+    pub frame_rate: Option<FrameRate>,
+    /// The size signalled by the sequence parameter set, if any.
+    pub codec_size: Option<(u32, u32)>,
 }
 
 impl VisualSampleEntry {
+    /// Augment the sample entry using the size and frame rate signalled by a sequence parameter
+    /// set.
+    ///
+    /// The size signalled by the sequence parameter set is canonical. If the sample entry signals a
+    /// different size, it is retained as the container size.
+    pub(crate) fn apply_sps(&mut self, width: u32, height: u32, frame_rate: Option<FrameRate>) {
+        self.codec_size = Some((width, height));
+
+        if frame_rate.is_some() {
+            self.frame_rate = frame_rate;
+        }
+    }
+
     pub(crate) fn make_codec_params(&self) -> VideoCodecParameters {
         let mut codec_params = VideoCodecParameters {
             width: Some(self.width),
             height: Some(self.height),
             codec: self.codec_id,
             extra_data: self.extra_data.clone(),
+            mastering_display: self.mastering_display,
+            content_light_level: self.content_light_level,
+            projection: self.projection.clone(),
+            stereo_mode: self.stereo_mode,
+            frame_rate: self.frame_rate,
             ..Default::default()
         };
 
+        if let Some((width, height)) = self.codec_size {
+            codec_params.with_codec_size(width, height);
+        }
+
         if let Some(profile) = self.profile {
             codec_params.with_profile(profile);
         }
@@ -563,6 +638,22 @@ impl Atom for VisualSampleEntry {
                     let atom = it.read_atom::<DoviAtom>()?;
                     atom.fill_video_sample_entry(&mut entry);
                 }
+                AtomType::MasteringDisplayColourVolume => {
+                    let atom = it.read_atom::<MdcvAtom>()?;
+                    atom.fill_video_sample_entry(&mut entry);
+                }
+                AtomType::ContentLightLevel => {
+                    let atom = it.read_atom::<ClliAtom>()?;
+                    atom.fill_video_sample_entry(&mut entry);
+                }
+                AtomType::Stereoscopic3d => {
+                    let atom = it.read_atom::<St3dAtom>()?;
+                    atom.fill_video_sample_entry(&mut entry);
+                }
+                AtomType::SphericalVideo => {
+                    let atom = it.read_atom::<Sv3dAtom>()?;
+                    atom.fill_video_sample_entry(&mut entry);
+                }
                 _ => {
                     debug!("unknown visual sample entry sub-atom: {:?}.", entry_header.atom_type());
                 }
@@ -662,6 +753,66 @@ impl Atom for SubtitleSampleEntry {
     }
 }
 
+/// Timecode sample entry.
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct TimecodeSampleEntry {
+    /// Timecode flags.
+    pub flags: u32,
+    /// The number of time units per second.
+    pub timescale: u32,
+    /// The duration of a frame in time units.
+    pub frame_duration: u32,
+    /// The nominal (integer) number of frames per second.
+    pub num_frames: u8,
+}
+
+impl TimecodeSampleEntry {
+    /// The timecode uses drop-frame counting.
+    const FLAG_DROP_FRAME: u32 = 0x1;
+    /// The timecode samples are a tick counter instead of a frame counter.
+    const FLAG_COUNTER: u32 = 0x8;
+
+    /// Convert the frame count of a timecode sample into a timecode.
+    pub fn make_timecode(&self, count: u32) -> Option<Timecode> {
+        // Tick counters are not frame counts.
+        if self.flags & TimecodeSampleEntry::FLAG_COUNTER != 0 {
+            return None;
+        }
+
+        let drop_frame = self.flags & TimecodeSampleEntry::FLAG_DROP_FRAME != 0;
+
+        Timecode::from_frame_count(u64::from(count), self.num_frames, drop_frame)
+    }
+}
+
+impl Atom for TimecodeSampleEntry {
+    fn read<R: ReadAtom>(it: &mut AtomIterator<R>, _header: &AtomHeader) -> Result<Self> {
+        // SampleEntry portion
+
+        // Reserved. All 0.
+        it.ignore_bytes(6)?;
+
+        // Sample entry data reference.
+        let _ = it.read_u16()?;
+
+        // TimecodeSampleEntry portion
+
+        // Reserved.
+        it.ignore_bytes(4)?;
+
+        let flags = it.read_u32()?;
+        let timescale = it.read_u32()?;
+        let frame_duration = it.read_u32()?;
+        let num_frames = it.read_u8()?;
+
+        // Reserved.
+        it.ignore_bytes(1)?;
+
+        Ok(TimecodeSampleEntry { flags, timescale, frame_duration, num_frames })
+    }
+}
+
 /// Bitrate atom.
 #[allow(dead_code)]
 #[derive(Debug)]
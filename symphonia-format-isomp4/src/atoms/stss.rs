@@ -5,14 +5,52 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use crate::atoms::limits::*;
 use crate::atoms::{Atom, AtomHeader, AtomIterator, ReadAtom, Result};
 
-#[allow(dead_code)]
+/// Sync sample atom.
 #[derive(Debug)]
-pub struct StssAtom {}
+pub struct StssAtom {
+    /// The sample numbers of all sync samples in ascending order. Sample numbers start at 1.
+    pub sync_samples: Vec<u32>,
+}
+
+impl StssAtom {
+    /// Returns `true` if the sample indicated by the zero-based sample number, `sample_num`, is a
+    /// sync sample.
+    pub fn is_sync_sample(&self, sample_num: u32) -> bool {
+        self.sync_samples.binary_search(&(sample_num + 1)).is_ok()
+    }
+
+    /// Gets the zero-based sample number of the nearest sync sample at, or before, the sample
+    /// indicated by the zero-based sample number, `sample_num`.
+    pub fn sync_sample_at_or_before(&self, sample_num: u32) -> Option<u32> {
+        let idx = self.sync_samples.partition_point(|&sync| sync <= sample_num + 1);
+
+        idx.checked_sub(1).map(|idx| self.sync_samples[idx].saturating_sub(1))
+    }
+}
 
 impl Atom for StssAtom {
-    fn read<R: ReadAtom>(_reader: &mut AtomIterator<R>, _header: &AtomHeader) -> Result<Self> {
-        todo!()
+    fn read<R: ReadAtom>(it: &mut AtomIterator<R>, _header: &AtomHeader) -> Result<Self> {
+        let (_, _) = it.read_extended_header()?;
+
+        let entry_count = it.read_u32()?;
+
+        // Limit the maximum initial capacity to prevent malicious files from using all the
+        // available memory.
+        let mut sync_samples =
+            Vec::with_capacity(MAX_TABLE_INITIAL_CAPACITY.min(entry_count as usize));
+
+        for _ in 0..entry_count {
+            sync_samples.push(it.read_u32()?);
+        }
+
+        // The table should be in ascending order, but sort it if it is not.
+        if !sync_samples.is_sorted() {
+            sync_samples.sort_unstable();
+        }
+
+        Ok(StssAtom { sync_samples })
     }
 }
@@ -5,6 +5,8 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use std::ops::Range;
+
 use crate::atoms::limits::*;
 use crate::atoms::{Atom, AtomHeader, AtomIterator, ReadAtom, Result};
 
@@ -22,6 +24,53 @@ pub struct StszAtom {
     pub sample_count: u32,
     /// A vector of `sample_count` sample sizes, or a constant size for all samples.
     pub sample_sizes: SampleSize,
+    /// If built, the cumulative size of the samples preceeding each sample.
+    index: Option<Vec<u64>>,
+}
+
+impl StszAtom {
+    /// Build an index of the byte offset of each sample relative to the first sample. Once built,
+    /// the offset of a sample within its chunk can be found in O(1).
+    pub fn build_index(&mut self) {
+        if let SampleSize::Variable(entries) = &self.sample_sizes {
+            let mut index = Vec::with_capacity(entries.len() + 1);
+
+            let mut offset = 0u64;
+            index.push(offset);
+
+            for &size in entries {
+                offset += u64::from(size);
+                index.push(offset);
+            }
+
+            self.index = Some(index);
+        }
+    }
+
+    /// Get the total size in bytes of the samples in the range `samples`. Note, `samples` is
+    /// indexed relative to the `StszAtom`. Complexity of this function is O(N) for variable size
+    /// samples, or O(1) if the index was built.
+    pub fn size_of_samples(&self, samples: Range<u32>) -> Option<u64> {
+        if samples.start > samples.end {
+            return None;
+        }
+
+        match &self.sample_sizes {
+            SampleSize::Constant(size) => {
+                Some(u64::from(samples.end - samples.start) * u64::from(*size))
+            }
+            SampleSize::Variable(entries) => {
+                if let Some(index) = &self.index {
+                    let start = index.get(samples.start as usize)?;
+                    let end = index.get(samples.end as usize)?;
+                    return Some(end - start);
+                }
+
+                let sizes = entries.get(samples.start as usize..samples.end as usize)?;
+                Some(sizes.iter().map(|&size| u64::from(size)).sum())
+            }
+        }
+    }
 }
 
 impl Atom for StszAtom {
@@ -47,6 +96,51 @@ impl Atom for StszAtom {
             SampleSize::Constant(sample_size)
         };
 
-        Ok(StszAtom { sample_count, sample_sizes })
+        Ok(StszAtom { sample_count, sample_sizes, index: None })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_stsz(sample_sizes: SampleSize) -> StszAtom {
+        let sample_count = match &sample_sizes {
+            SampleSize::Variable(sizes) => sizes.len() as u32,
+            SampleSize::Constant(_) => 4,
+        };
+
+        StszAtom { sample_count, sample_sizes, index: None }
+    }
+
+    #[test]
+    fn verify_indexed_size_of_samples_matches_linear_scan() {
+        let sizes = vec![10, 0, 25, 7, 0, 0, 300, 1];
+
+        let linear = make_stsz(SampleSize::Variable(sizes.clone()));
+        let mut indexed = make_stsz(SampleSize::Variable(sizes));
+        indexed.build_index();
+
+        for start in 0..linear.sample_count + 2 {
+            for end in 0..linear.sample_count + 2 {
+                assert_eq!(
+                    indexed.size_of_samples(start..end),
+                    linear.size_of_samples(start..end),
+                    "size of samples {start}..{end}"
+                );
+            }
+        }
+
+        assert_eq!(indexed.size_of_samples(2..6), Some(32));
+        assert_eq!(indexed.size_of_samples(3..3), Some(0));
+        assert_eq!(indexed.size_of_samples(0..9), None);
+    }
+
+    #[test]
+    fn verify_constant_size_of_samples() {
+        let stsz = make_stsz(SampleSize::Constant(6));
+
+        assert_eq!(stsz.size_of_samples(1..3), Some(12));
+        assert_eq!(stsz.size_of_samples(0..4), Some(24));
     }
 }
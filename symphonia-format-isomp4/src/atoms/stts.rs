@@ -14,18 +14,63 @@ pub struct SampleDurationEntry {
     pub sample_delta: u32,
 }
 
+/// The first sample number and timestamp of a `SampleDurationEntry`.
+#[derive(Debug)]
+struct SampleDurationIndexEntry {
+    first_sample: u32,
+    first_ts: u64,
+}
+
 /// Time-to-sample atom.
 #[allow(dead_code)]
 #[derive(Debug)]
 pub struct SttsAtom {
     pub entries: Vec<SampleDurationEntry>,
     pub total_duration: u64,
+    /// If built, the cumulative sample count and duration preceeding each entry.
+    index: Option<Vec<SampleDurationIndexEntry>>,
 }
 
 impl SttsAtom {
+    /// Build an index of the first sample number and timestamp of each entry. Once built, the
+    /// complexity of timing and sample lookups is reduced to O(log N).
+    pub fn build_index(&mut self) {
+        let mut index = Vec::with_capacity(self.entries.len());
+
+        let mut first_sample = 0u32;
+        let mut first_ts = 0u64;
+
+        for entry in &self.entries {
+            index.push(SampleDurationIndexEntry { first_sample, first_ts });
+
+            first_sample = first_sample.saturating_add(entry.sample_count);
+            first_ts += u64::from(entry.sample_count) * u64::from(entry.sample_delta);
+        }
+
+        self.index = Some(index);
+    }
+
     /// Get the timestamp and duration for the sample indicated by `sample_num`. Note, `sample_num`
-    /// is indexed relative to the `SttsAtom`. Complexity of this function in O(N).
+    /// is indexed relative to the `SttsAtom`. Complexity of this function in O(N), or O(log N) if
+    /// the index was built.
     pub fn find_timing_for_sample(&self, sample_num: u32) -> Option<(u64, u32)> {
+        if let Some(index) = &self.index {
+            // Find the last entry starting at, or before, the sample.
+            let i = index.partition_point(|item| item.first_sample <= sample_num).checked_sub(1)?;
+
+            let entry = &self.entries[i];
+            let entry_sample_offset = sample_num - index[i].first_sample;
+
+            if entry_sample_offset >= entry.sample_count {
+                return None;
+            }
+
+            let ts =
+                index[i].first_ts + u64::from(entry.sample_delta) * u64::from(entry_sample_offset);
+
+            return Some((ts, entry.sample_delta));
+        }
+
         let mut ts = 0;
         let mut next_entry_first_sample = 0;
 
@@ -51,8 +96,25 @@ impl SttsAtom {
     }
 
     /// Get the sample that contains the timestamp indicated by `ts`. Note, the returned `sample_num`
-    /// is indexed relative to the `SttsAtom`. Complexity of this function in O(N).
+    /// is indexed relative to the `SttsAtom`. Complexity of this function in O(N), or O(log N) if
+    /// the index was built.
     pub fn find_sample_for_timestamp(&self, ts: u64) -> Option<u32> {
+        if let Some(index) = &self.index {
+            // Find the last entry starting at, or before, the timestamp.
+            let i = index.partition_point(|item| item.first_ts <= ts).checked_sub(1)?;
+
+            let entry = &self.entries[i];
+            let delta = u64::from(entry.sample_delta) * u64::from(entry.sample_count);
+
+            if index[i].first_ts + delta <= ts {
+                return None;
+            }
+
+            let entry_sample_offset = (ts - index[i].first_ts) / u64::from(entry.sample_delta);
+
+            return Some(index[i].first_sample + entry_sample_offset as u32);
+        }
+
         let mut ts_accum = 0;
         let mut sample_num = 0;
 
@@ -98,6 +160,60 @@ impl Atom for SttsAtom {
             entries.push(SampleDurationEntry { sample_count, sample_delta });
         }
 
-        Ok(SttsAtom { entries, total_duration })
+        Ok(SttsAtom { entries, total_duration, index: None })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_stts(entries: &[(u32, u32)]) -> SttsAtom {
+        let entries: Vec<_> = entries
+            .iter()
+            .map(|&(sample_count, sample_delta)| SampleDurationEntry { sample_count, sample_delta })
+            .collect();
+
+        let total_duration =
+            entries.iter().map(|e| u64::from(e.sample_count) * u64::from(e.sample_delta)).sum();
+
+        SttsAtom { entries, total_duration, index: None }
+    }
+
+    #[test]
+    fn verify_indexed_lookup_matches_linear_scan() {
+        let tables: [&[(u32, u32)]; 5] = [
+            &[],
+            &[(10, 1024)],
+            &[(3, 1024), (1, 512), (4, 1024)],
+            // Entries with a sample count of 0.
+            &[(0, 100), (3, 1024), (0, 512), (2, 256), (0, 64)],
+            // Entries with a sample delta of 0.
+            &[(2, 0), (3, 1024), (2, 0), (2, 256), (1, 0)],
+        ];
+
+        for table in tables {
+            let linear = make_stts(table);
+            let mut indexed = make_stts(table);
+            indexed.build_index();
+
+            let num_samples: u32 = table.iter().map(|&(count, _)| count).sum();
+
+            for sample_num in 0..num_samples + 2 {
+                assert_eq!(
+                    indexed.find_timing_for_sample(sample_num),
+                    linear.find_timing_for_sample(sample_num),
+                    "timing for sample {sample_num} of {table:?}"
+                );
+            }
+
+            for ts in 0..linear.total_duration + 2 {
+                assert_eq!(
+                    indexed.find_sample_for_timestamp(ts),
+                    linear.find_sample_for_timestamp(ts),
+                    "sample for timestamp {ts} of {table:?}"
+                );
+            }
+        }
     }
 }
@@ -0,0 +1,134 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use symphonia_core::codecs::video::{Projection, ProjectionType};
+use symphonia_core::errors::Error;
+
+use crate::atoms::stsd::VisualSampleEntry;
+use crate::atoms::{Atom, AtomHeader, AtomIterator, AtomType, ReadAtom, Result};
+
+use log::debug;
+
+/// Spherical video atom.
+#[derive(Debug)]
+pub struct Sv3dAtom {
+    /// The projection, if known.
+    projection: Option<Projection>,
+}
+
+impl Atom for Sv3dAtom {
+    #[allow(clippy::single_match)]
+    fn read<R: ReadAtom>(it: &mut AtomIterator<R>, _header: &AtomHeader) -> Result<Self> {
+        let mut projection = None;
+
+        while let Some(header) = it.next_header()? {
+            match header.atom_type {
+                AtomType::Projection => {
+                    projection = it.read_atom::<ProjAtom>()?.projection;
+                }
+                // The spherical video header atom only contains the name of the tool that wrote
+                // the metadata.
+                _ => (),
+            }
+        }
+
+        Ok(Self { projection })
+    }
+}
+
+impl Sv3dAtom {
+    pub fn fill_video_sample_entry(self, entry: &mut VisualSampleEntry) {
+        entry.projection = self.projection;
+    }
+}
+
+/// Projection atom.
+#[derive(Debug)]
+struct ProjAtom {
+    projection: Option<Projection>,
+}
+
+impl Atom for ProjAtom {
+    fn read<R: ReadAtom>(it: &mut AtomIterator<R>, _header: &AtomHeader) -> Result<Self> {
+        let mut pose = None;
+        let mut projection = None;
+
+        while let Some(header) = it.next_header()? {
+            let projection_type = match header.atom_type {
+                AtomType::ProjectionHeader => {
+                    pose = Some(it.read_atom::<PrhdAtom>()?);
+                    continue;
+                }
+                AtomType::EquirectangularProjection => ProjectionType::Equirectangular,
+                AtomType::CubemapProjection => ProjectionType::Cubemap,
+                AtomType::MeshProjection => ProjectionType::Mesh,
+                other => {
+                    debug!("unknown projection sub-atom: {other:?}");
+                    continue;
+                }
+            };
+
+            // The projection-specific data is the payload of the atom, including the version and
+            // flags. This is the same as the projection private data of Matroska.
+            let private = it.read_atom::<ProjectionDataAtom>()?.data;
+
+            projection = Some(Projection {
+                projection_type,
+                private: Some(private),
+                yaw: 0.0,
+                pitch: 0.0,
+                roll: 0.0,
+            });
+        }
+
+        // The projection header is mandatory, but assume the default pose if it is missing.
+        if let (Some(projection), Some(pose)) = (projection.as_mut(), pose) {
+            projection.yaw = pose.yaw;
+            projection.pitch = pose.pitch;
+            projection.roll = pose.roll;
+        }
+
+        Ok(Self { projection })
+    }
+}
+
+/// Projection header atom.
+#[derive(Debug)]
+struct PrhdAtom {
+    yaw: f32,
+    pitch: f32,
+    roll: f32,
+}
+
+impl Atom for PrhdAtom {
+    fn read<R: ReadAtom>(it: &mut AtomIterator<R>, _header: &AtomHeader) -> Result<Self> {
+        let (_, _) = it.read_extended_header()?;
+
+        // The pose angles are in degrees, as 16.16 fixed-point values.
+        Ok(Self {
+            yaw: it.read_i32()? as f32 / 65_536.0,
+            pitch: it.read_i32()? as f32 / 65_536.0,
+            roll: it.read_i32()? as f32 / 65_536.0,
+        })
+    }
+}
+
+/// Projection-specific data atom (equi, cbmp, and mshp).
+#[derive(Debug)]
+struct ProjectionDataAtom {
+    data: Box<[u8]>,
+}
+
+impl Atom for ProjectionDataAtom {
+    fn read<R: ReadAtom>(it: &mut AtomIterator<R>, _header: &AtomHeader) -> Result<Self> {
+        let size = it
+            .data_left()?
+            .ok_or(Error::DecodeError("isomp4 (proj): expected atom size to be known"))?;
+
+        Ok(Self { data: it.read_boxed_slice_exact(size as usize)? })
+    }
+}
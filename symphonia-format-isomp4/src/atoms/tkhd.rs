@@ -31,6 +31,26 @@ pub struct TkhdAtom {
     pub alternate_group: u16,
     /// Preferred volume for track playback.
     pub volume: FpU8,
+    /// The transformation matrix for video. The first two columns of each row are 16.16 fixed
+    /// point values, and the last column is a 2.30 fixed point value.
+    pub matrix: [i32; 9],
+}
+
+impl TkhdAtom {
+    /// Get the clockwise rotation, in degrees, described by the transformation matrix. Returns
+    /// `None` if the matrix is not a pure rotation by a multiple of 90 degrees.
+    pub fn rotation_degrees(&self) -> Option<u16> {
+        // Only the signs of the rotation and scaling elements are relevant.
+        let [a, b, _, c, d, _, _, _, _] = self.matrix.map(i32::signum);
+
+        match (a, b, c, d) {
+            (1, 0, 0, 1) => Some(0),
+            (0, 1, -1, 0) => Some(90),
+            (-1, 0, 0, -1) => Some(180),
+            (0, -1, 1, 0) => Some(270),
+            _ => None,
+        }
+    }
 }
 
 impl Atom for TkhdAtom {
@@ -46,6 +66,7 @@ impl Atom for TkhdAtom {
             layer: 0,
             alternate_group: 0,
             volume: Default::default(),
+            matrix: [0x10000, 0, 0, 0, 0x10000, 0, 0, 0, 0x40000000],
         };
 
         // Version 0 uses 32-bit time values, verion 1 used 64-bit values.
@@ -74,7 +95,16 @@ impl Atom for TkhdAtom {
         tkhd.alternate_group = it.read_u16()?;
         tkhd.volume = FpU8::parse_raw(it.read_u16()?);
 
-        // The remainder of the header is only useful for video tracks.
+        // The remainder of the header is only useful for video tracks, and some muxers truncate it
+        // for other tracks. If the transformation matrix is missing, keep the identity matrix.
+        if it.data_left()?.is_none_or(|len| len >= 2 + 36) {
+            // Reserved
+            let _ = it.read_u16()?;
+
+            for value in tkhd.matrix.iter_mut() {
+                *value = it.read_i32()?;
+            }
+        }
 
         Ok(tkhd)
     }
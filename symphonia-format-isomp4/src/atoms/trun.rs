@@ -69,7 +69,6 @@ impl TrunAtom {
     }
 
     /// Indicates if sample flags are provided.
-    #[allow(dead_code)]
     pub fn are_sample_flags_present(&self) -> bool {
         self.flags & TrunAtom::SAMPLE_FLAGS_PRESENT != 0
     }
@@ -166,6 +165,22 @@ impl TrunAtom {
         }
     }
 
+    /// Get the flags of a sample. The desired sample is specified by the trun-relative sample
+    /// number, `sample_num_rel`.
+    pub fn sample_flags(&self, sample_num_rel: u32, default_flags: u32) -> u32 {
+        debug_assert!(sample_num_rel < self.sample_count);
+
+        if self.are_sample_flags_present() {
+            self.sample_flags[sample_num_rel as usize]
+        }
+        else {
+            match self.first_sample_flags {
+                Some(flags) if sample_num_rel == 0 => flags,
+                _ => default_flags,
+            }
+        }
+    }
+
     /// Get the byte offset and size of a sample. The desired sample is specified by the
     /// trun-relative sample number, `sample_num_rel`.
     pub fn sample_offset(&self, sample_num_rel: u32, default_size: u32) -> (u64, u32) {
@@ -5,30 +5,42 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use symphonia_common::dolby::audio::truehd::MajorSync;
+use symphonia_common::dts::audio::dca;
+use symphonia_common::mpeg::video::make_annex_b_converter;
+use symphonia_common::mpeg::video::nal::AnnexBConverter;
 use symphonia_core::codecs::CodecParameters;
+use symphonia_core::codecs::audio::well_known::profiles::CODEC_PROFILE_TRUEHD_ATMOS;
+use symphonia_core::codecs::audio::well_known::{CODEC_ID_DCA, CODEC_ID_TRUEHD};
 use symphonia_core::support_format;
 
 use symphonia_core::errors::{
     Error, Result, SeekErrorKind, decode_error, seek_error, unsupported_error,
 };
+use symphonia_core::formats::TrackType;
 use symphonia_core::formats::prelude::*;
 use symphonia_core::formats::probe::{ProbeFormatData, ProbeableFormat, Score, Scoreable};
 use symphonia_core::formats::well_known::FORMAT_ID_ISOMP4;
 use symphonia_core::io::*;
 use symphonia_core::meta::{Metadata, MetadataLog};
-use symphonia_core::units::Time;
+use symphonia_core::units::{FrameRate, Time, Timecode};
 
 use std::collections::HashMap;
 use std::io::{Seek, SeekFrom};
 use std::num::NonZero;
 use std::sync::Arc;
 
-use crate::atoms::{AtomError, AtomIterator, AtomType, ReadAtom};
-use crate::atoms::{FtypAtom, MetaAtom, MoofAtom, MoovAtom, SidxAtom, TrakAtom};
+use crate::atoms::sidx::ReferenceType;
+use crate::atoms::stsz::SampleSize;
+use crate::atoms::{AtomError, AtomIterator, AtomType, HandlerType, ReadAtom};
+use crate::atoms::{FtypAtom, MetaAtom, MoofAtom, MoovAtom, SidxAtom, StblAtom, TrakAtom};
 use crate::stream::*;
 
 use log::{debug, info, trace, warn};
 
+/// The maximum size of the data read from an atom, if the element size limit is defaulted.
+const DEFAULT_LIMIT_ELEMENT_BYTES: usize = 64 * 1024 * 1024;
+
 const ISOMP4_FORMAT_INFO: FormatInfo = FormatInfo {
     format: FORMAT_ID_ISOMP4,
     short_name: "isomp4",
@@ -46,17 +58,51 @@ pub struct TrackState {
     next_sample: u32,
     /// The current sample byte position relative to the start of the track.
     next_sample_pos: u64,
+    /// If `false`, the samples of the track are skipped instead of being returned as packets.
+    selected: bool,
+    /// The sample rate of an audio track, if known.
+    sample_rate: Option<NonZero<u32>>,
+    /// After a seek of an audio track, the required timestamp. Frames preceding it are trimmed
+    /// from the packets of the track.
+    seek_ts: Option<Timestamp>,
+    /// If the NAL units of the track's packets are to be converted to Annex B, the converter.
+    annex_b: Option<AnnexBConverter>,
 }
 
 impl TrackState {
     pub fn make(track_num: usize, trak: &TrakAtom, timespan: &TimeSpan) -> (Self, Track) {
         let mut track = Track::new(trak.tkhd.id);
 
+        // Set the track type using the handler type, since the codec may not be supported.
+        let track_type = match trak.mdia.hdlr.handler_type {
+            HandlerType::Sound => Some(TrackType::Audio),
+            HandlerType::Video => Some(TrackType::Video),
+            HandlerType::Subtitle | HandlerType::Text => Some(TrackType::Subtitle),
+            HandlerType::Timecode => Some(TrackType::Timecode),
+            HandlerType::Metadata | HandlerType::QuickTimeMetadata => Some(TrackType::Data),
+            _ => None,
+        };
+
+        if let Some(track_type) = track_type {
+            track.with_track_type(track_type);
+        }
+
         // Create the codec parameters using the sample description atom.
-        if let Some(codec_params) = trak.mdia.minf.stbl.stsd.make_codec_params() {
+        if let Some(mut codec_params) = trak.mdia.minf.stbl.stsd.make_codec_params() {
+            // The display rotation of video is described by the track header.
+            if let CodecParameters::Video(video) = &mut codec_params {
+                if let Some(rotation) = trak.tkhd.rotation_degrees() {
+                    video.with_rotation_degrees(rotation);
+                }
+            }
             track.with_codec_params(codec_params);
         }
 
+        // An alternate group of 0 indicates the track is not in an alternate group.
+        if trak.tkhd.alternate_group != 0 {
+            track.with_alternate_group(u32::from(trak.tkhd.alternate_group));
+        }
+
         // Populate timing information.
         track
             .with_time_base(TimeBase::from_recip(timespan.timescale))
@@ -74,12 +120,21 @@ impl TrackState {
             }
         }
 
+        let sample_rate = match &track.codec_params {
+            Some(CodecParameters::Audio(audio)) => audio.sample_rate.and_then(NonZero::new),
+            _ => None,
+        };
+
         let state = Self {
             track_num,
             track_id: trak.tkhd.id,
             cur_seg: 0,
             next_sample: 0,
             next_sample_pos: 0,
+            selected: true,
+            sample_rate,
+            seek_ts: None,
+            annex_b: None,
         };
 
         (state, track)
@@ -99,6 +154,8 @@ struct NextSampleInfo {
     time: Time,
     /// The duration of the next sample.
     dur: Duration,
+    /// If `true`, the next sample is a keyframe.
+    is_keyframe: bool,
     /// The segment containing the next sample.
     seg_idx: usize,
 }
@@ -131,6 +188,107 @@ impl TimeSpan {
     }
 }
 
+/// A reference to a subsegment, or to another segment index atom, from a segment index atom.
+#[derive(Copy, Clone, Debug)]
+struct SegmentIndexEntry {
+    /// The ID of the track the segment index atom was written for.
+    track_id: u32,
+    /// The timescale of `ts`.
+    timescale: NonZero<u32>,
+    /// The timestamp of the start of the referenced media, relative to the start of the first
+    /// subsegment of the track.
+    ts: u64,
+    /// The position of the referenced atom.
+    pos: u64,
+    /// If `true`, the entry references another segment index atom instead of a movie fragment.
+    is_sidx: bool,
+}
+
+impl SegmentIndexEntry {
+    fn time(&self) -> Option<Time> {
+        let ts = Timestamp::try_from(self.ts).ok()?;
+        TimeBase::from_recip(self.timescale).calc_time(ts)
+    }
+}
+
+/// Convert a timestamp from one timescale to another.
+fn rescale_ts(ts: u64, from: NonZero<u32>, to: NonZero<u32>) -> u64 {
+    if from == to {
+        return ts;
+    }
+    let ts = u128::from(ts) * u128::from(to.get()) / u128::from(from.get());
+    u64::try_from(ts).unwrap_or(u64::MAX)
+}
+
+/// An index of the subsegments of a fragmented stream built from segment index (sidx) atoms.
+#[derive(Debug, Default)]
+struct SegmentIndex {
+    entries: Vec<SegmentIndexEntry>,
+    /// The positions of all segment index atoms added to the index.
+    added: Vec<u64>,
+}
+
+impl SegmentIndex {
+    /// Returns `true` if the segment index atom at `pos` is referenced by another segment index
+    /// atom in the index. This is the case for hierarchical and daisy-chained segment indices.
+    fn is_referenced(&self, pos: u64) -> bool {
+        self.entries.iter().any(|entry| entry.is_sidx && entry.pos == pos)
+    }
+
+    /// Add the references of the segment index atom read from `pos` to the index.
+    fn push(&mut self, pos: u64, sidx: &SidxAtom) -> Result<()> {
+        if self.added.contains(&pos) {
+            return Ok(());
+        }
+
+        self.added.push(pos);
+
+        // A referenced segment index atom starts where the reference to it starts. Otherwise, it
+        // starts at its earliest presentation time.
+        let parent = self.entries.iter().find(|entry| entry.is_sidx && entry.pos == pos);
+
+        let mut ts = match parent {
+            Some(parent) => rescale_ts(parent.ts, parent.timescale, sidx.timescale),
+            None => sidx.earliest_pts,
+        };
+
+        let mut pos = sidx.first_offset;
+
+        for reference in &sidx.references {
+            self.entries.push(SegmentIndexEntry {
+                track_id: sidx.reference_id,
+                timescale: sidx.timescale,
+                ts,
+                pos,
+                is_sidx: matches!(reference.reference_type, ReferenceType::Segment),
+            });
+
+            ts = ts.saturating_add(u64::from(reference.subsegment_duration));
+            pos = pos
+                .checked_add(u64::from(reference.reference_size))
+                .ok_or(Error::DecodeError("isomp4 (sidx): reference offset overflow"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Find the entry with the latest start time at, or before, `time`. The entries of the track
+    /// identified by `track_id` are preferred, if there are any.
+    fn find(&self, track_id: u32, time: Time) -> Option<(SegmentIndexEntry, Time)> {
+        let has_track = self.entries.iter().any(|entry| entry.track_id == track_id);
+
+        self.entries
+            .iter()
+            .filter(|entry| !has_track || entry.track_id == track_id)
+            .filter_map(|entry| entry.time().map(|entry_time| (*entry, entry_time)))
+            .filter(|(_, entry_time)| *entry_time <= time)
+            // Prefer a movie fragment over a segment index atom starting at the same time.
+            .max_by(|(a, a_time), (b, b_time)| {
+                a_time.cmp(b_time).then((!a.is_sidx).cmp(&!b.is_sidx))
+            })
+    }
+}
+
 /// ISO Base Media File Format (MP4, M4A, MOV, etc.) demultiplexer.
 ///
 /// `IsoMp4Reader` implements a demuxer for the ISO Base Media File Format.
@@ -145,6 +303,13 @@ pub struct IsoMp4Reader<'s> {
     track_states: Vec<TrackState>,
     /// Optional, movie extends atom used for fragmented streams.
     moov: Arc<MoovAtom>,
+    /// Index of the subsegments of a fragmented stream.
+    sidx: SegmentIndex,
+    /// If the next segment to be read does not follow the last segment, such as after a seek, the
+    /// segment index entry for the next segment.
+    next_seg: Option<SegmentIndexEntry>,
+    /// Whether the movie atom precedes the media data (faststart).
+    is_faststart: bool,
 }
 
 impl<'s> IsoMp4Reader<'s> {
@@ -156,6 +321,8 @@ impl<'s> IsoMp4Reader<'s> {
 
         let mut ftyp = None;
         let mut moov = None;
+        // Whether the movie atom was read before the first media data or movie fragment atom.
+        let mut is_faststart = None;
 
         // Get the total length of the stream, if possible.
         let total_len = if is_seekable {
@@ -171,11 +338,15 @@ impl<'s> IsoMp4Reader<'s> {
 
         let mut metadata = opts.external_data.metadata.unwrap_or_default();
 
+        // Sample data is read into separate buffers and is not subject to the element size limit.
+        let max_data_size = opts.limit_element_bytes.limit_or_default(DEFAULT_LIMIT_ELEMENT_BYTES);
+
         // Parse all atoms if the stream is seekable, otherwise parse all atoms up-to the mdat atom.
-        let mut it = AtomIterator::new(mss, total_len);
+        let mut it = AtomIterator::new(mss, total_len, max_data_size);
         // Maps each track id to its cumulative duration (TimeSpan) as parsed from the segment
         // index.
         let mut sidx_timespans: HashMap<u32, TimeSpan> = HashMap::new();
+        let mut sidx_index = SegmentIndex::default();
 
         while let Some(header) = it.next_header()? {
             // Top-level atoms.
@@ -187,8 +358,19 @@ impl<'s> IsoMp4Reader<'s> {
                     moov = Some(it.read_atom::<MoovAtom>()?);
                 }
                 AtomType::SegmentIndex => {
+                    let pos = header.pos();
                     let sidx = it.read_atom::<SidxAtom>()?;
 
+                    // A segment index atom referenced by another covers a span of time already
+                    // accounted for by the referencing atom.
+                    let is_referenced = sidx_index.is_referenced(pos);
+
+                    sidx_index.push(pos, &sidx)?;
+
+                    if is_referenced {
+                        continue;
+                    }
+
                     // Calculate the total duration, per track, from the segment index atoms.
                     let sidx_timespan = sidx_timespans
                         .entry(sidx.reference_id)
@@ -208,6 +390,8 @@ impl<'s> IsoMp4Reader<'s> {
                         .ok_or(Error::DecodeError("isomp4: sidx total duration overflow"))?
                 }
                 AtomType::MediaData | AtomType::MovieFragment => {
+                    is_faststart.get_or_insert(moov.is_some());
+
                     // The mdat atom contains the codec bitstream data. For fragmented streams, a
                     // moof + mdat pair is required. If the ftyp and moov atoms have been read, then
                     // the top-level atom scan can exit here and begin playback immediately as an
@@ -232,8 +416,10 @@ impl<'s> IsoMp4Reader<'s> {
                         metadata.push(rev);
                     }
                 }
+                // Padding atoms.
                 AtomType::Free => (),
                 AtomType::Skip => (),
+                AtomType::Wide => (),
                 _ => {
                     info!("skipping top-level atom: {:?}.", header.atom_type());
                 }
@@ -256,7 +442,7 @@ impl<'s> IsoMp4Reader<'s> {
             let mut mss = it.into_inner();
             mss.seek(SeekFrom::Start(0))?;
 
-            it = AtomIterator::new(mss, total_len);
+            it = AtomIterator::new(mss, total_len, max_data_size);
 
             while let Some(header) = it.next_header()? {
                 if let AtomType::MovieFragment | AtomType::MediaData = header.atom_type() {
@@ -288,6 +474,17 @@ impl<'s> IsoMp4Reader<'s> {
             metadata.push(rev);
         }
 
+        // If requested, index the time-to-sample and sample size tables of all tracks upfront to
+        // speed up seeking.
+        if opts.prebuild_seek_index {
+            debug!("building time-to-sample and sample offset indices for all tracks");
+
+            for trak in moov.traks.iter_mut() {
+                trak.mdia.minf.stbl.stts.build_index();
+                trak.mdia.minf.stbl.stsz.build_index();
+            }
+        }
+
         // Create a track and track state for each Track (trak) atom.
         let mut tracks = Vec::with_capacity(moov.traks.len());
         let mut track_states = Vec::with_capacity(moov.traks.len());
@@ -315,18 +512,58 @@ impl<'s> IsoMp4Reader<'s> {
                 TimeSpan::new(trak.mdia.mdhd.timescale, duration)
             };
 
-            let (track_state, track) = TrackState::make(t, trak, &timespan);
+            let (mut track_state, track) = TrackState::make(t, trak, &timespan);
+
+            if opts.nal_format == NalFormat::AnnexB {
+                if let Some(CodecParameters::Video(codec_params)) = &track.codec_params {
+                    // Fallback to the native framing if the decoder configuration is malformed.
+                    match make_annex_b_converter(codec_params) {
+                        Ok(converter) => track_state.annex_b = converter,
+                        Err(err) => warn!("track {}: cannot convert to annex b: {}", track.id, err),
+                    }
+                }
+            }
 
             tracks.push(track);
             track_states.push(track_state);
         }
 
-        // The number of tracks specified in the moov atom must match the number in the mvex atom.
+        // Each track specified in the moov atom must have a track extends atom in the mvex atom.
         if let Some(mvex) = &moov.mvex {
             if mvex.trexs.len() != moov.traks.len() {
                 return decode_error("isomp4: mvex and moov track number mismatch");
             }
+
+            for trak in &moov.traks {
+                if !mvex.trexs.iter().any(|trex| trex.track_id == trak.tkhd.id) {
+                    return decode_error("isomp4: missing trex atom for track");
+                }
+            }
+        }
+
+        // Read the start timecode, and the first access unit of TrueHD and DTS tracks. Both are
+        // stored in the media data, so this is only possible if the source is seekable.
+        let start_timecode = if is_seekable && !moov.is_fragmented() {
+            let mss = it.inner_mut();
+            let pos = mss.pos();
+
+            let start_timecode = read_start_timecode(mss, &moov).unwrap_or_else(|err| {
+                warn!("failed to read start timecode: {err}");
+                None
+            });
+
+            for (track, trak) in tracks.iter_mut().zip(&moov.traks) {
+                if let Err(err) = read_first_access_unit(mss, trak, track) {
+                    warn!("failed to read first access unit: {err}");
+                }
+            }
+
+            mss.seek(SeekFrom::Start(pos))?;
+            start_timecode
         }
+        else {
+            None
+        };
 
         // The moov atom will be shared among all segments and the demuxer using an Arc.
         let moov = Arc::new(moov);
@@ -338,7 +575,23 @@ impl<'s> IsoMp4Reader<'s> {
         media_info.with_time_base(TimeBase::from_recip(moov.mvhd.timescale));
         media_info.with_duration(Duration::new(moov.mvhd.duration));
 
-        Ok(IsoMp4Reader { iter: it, media_info, tracks, metadata, track_states, segs, moov })
+        if let Some(start_timecode) = start_timecode {
+            media_info.with_start_timecode(start_timecode);
+        }
+
+        Ok(IsoMp4Reader {
+            iter: it,
+            media_info,
+            tracks,
+            metadata,
+            track_states,
+            segs,
+            moov,
+            sidx: sidx_index,
+            next_seg: None,
+            // Without any media data, there is nothing to seek past.
+            is_faststart: is_faststart.unwrap_or(true),
+        })
     }
 
     /// Idempotently gets information regarding the next sample of the media stream. This function
@@ -385,6 +638,7 @@ impl<'s> IsoMp4Reader<'s> {
                                 ts,
                                 time: sample_time,
                                 dur: Duration::from(timing.dur),
+                                is_keyframe: timing.is_sync,
                                 seg_idx: seg_idx_delta + state.cur_seg,
                             });
                         }
@@ -432,6 +686,83 @@ impl<'s> IsoMp4Reader<'s> {
         Ok(Some(SampleDataInfo { pos, len: sample_data_desc.size }))
     }
 
+    /// Get the number of audio frames in the next sample, if the track is an audio track with a
+    /// known sample rate.
+    fn num_frames(&self, info: &NextSampleInfo) -> Option<u64> {
+        let sample_rate = self.track_states[info.track_num].sample_rate?;
+        let time_base = self.tracks[info.track_num].time_base?;
+
+        time_base.dur_to_frames(info.dur, FrameRate::new(sample_rate, NonZero::<u32>::MIN))
+    }
+
+    /// Get the timestamp that frames of the next sample must be trimmed to after a seek, if any.
+    /// The time base and sample rate of the track are returned with it.
+    fn take_seek_ts(
+        &mut self,
+        info: &NextSampleInfo,
+    ) -> Option<(Timestamp, TimeBase, NonZero<u32>)> {
+        let track = &mut self.track_states[info.track_num];
+        let seek_ts = track.seek_ts?;
+
+        // Samples following the sample containing the required timestamp are not trimmed.
+        if info.ts.checked_add(info.dur).is_none_or(|end| end >= seek_ts) {
+            track.seek_ts = None;
+        }
+
+        Some((seek_ts, self.tracks[info.track_num].time_base?, track.sample_rate?))
+    }
+
+    /// Get the timing and data location of the next sample, and advance the reader past it.
+    fn next_sample(&mut self) -> Result<Option<(NextSampleInfo, SampleDataInfo)>> {
+        loop {
+            let Some((info, sample_info)) = self.next_sample_any_track()?
+            else {
+                return Ok(None);
+            };
+
+            // Skip the samples of unselected tracks without reading them.
+            if self.track_states[info.track_num].selected {
+                return Ok(Some((info, sample_info)));
+            }
+        }
+    }
+
+    /// Get the timing and data location of the next sample of any track, and advance the reader
+    /// past it.
+    fn next_sample_any_track(&mut self) -> Result<Option<(NextSampleInfo, SampleDataInfo)>> {
+        // Get the index of the track with the next-nearest (minimum) timestamp.
+        let next_sample_info = loop {
+            // Using the current set of segments, try to get the next sample info.
+            if let Some(info) = self.next_sample_info()? {
+                break info;
+            }
+            else {
+                // The inner reader of the atom iterator has been used/seeked around to read
+                // packets, so resync the reader and iterator by seeking to the end of the current
+                // pending atom. Under regular circumstances, no actual expensive seek operation is
+                // performed since the reader should be at the end of the last iterated atom if we
+                // are trying to read another.
+                match self.iter.seek_atom_end() {
+                    Ok(_) | Err(AtomError::NoPendingAtom) => (),
+                    Err(_) => return decode_error("sync lost"),
+                };
+
+                // No more segments. If the stream is unseekable, it may be the case that there are
+                // more segments coming. If the stream is seekable it might be fragmented and no
+                // segments are found in the moov atom. Iterate atoms until a new segment is found
+                // or the end-of-stream is reached
+                if !self.try_read_more_segments()? {
+                    return Ok(None);
+                }
+            }
+        };
+
+        // Get the position and length information of the next sample.
+        let sample_info = self.consume_next_sample(&next_sample_info)?.unwrap();
+
+        Ok(Some((next_sample_info, sample_info)))
+    }
+
     fn try_read_more_segments(&mut self) -> Result<bool> {
         // If all tracks ended in the last segment, then do not try to read anymore segments.
         //
@@ -462,6 +793,13 @@ impl<'s> IsoMp4Reader<'s> {
                 AtomType::MediaData => {
                     return Ok(true);
                 }
+                AtomType::SegmentIndex => {
+                    // Segment index atoms may be interleaved with the movie fragments.
+                    let pos = header.pos();
+                    let sidx = self.iter.read_atom::<SidxAtom>()?;
+
+                    self.sidx.push(pos, &sidx)?;
+                }
                 AtomType::MovieFragment => {
                     let moof = self.iter.read_atom::<MoofAtom>()?;
 
@@ -470,8 +808,33 @@ impl<'s> IsoMp4Reader<'s> {
                         // Get the last segment.
                         let last_seg = self.segs.last().unwrap();
 
-                        // Create a new segment for the moof atom.
-                        let seg = MoofSegment::new(moof, self.moov.clone(), last_seg.as_ref());
+                        // Create a new segment for the moof atom. If the segment does not follow
+                        // the last segment, then the timestamps of its first samples are
+                        // calculated from its start time.
+                        let seg = match self.next_seg.take() {
+                            Some(entry) => {
+                                let first_ts = self
+                                    .moov
+                                    .traks
+                                    .iter()
+                                    .map(|trak| {
+                                        rescale_ts(
+                                            entry.ts,
+                                            entry.timescale,
+                                            trak.mdia.mdhd.timescale,
+                                        )
+                                    })
+                                    .collect::<Vec<_>>();
+
+                                MoofSegment::new_at(
+                                    moof,
+                                    self.moov.clone(),
+                                    last_seg.as_ref(),
+                                    &first_ts,
+                                )
+                            }
+                            None => MoofSegment::new(moof, self.moov.clone(), last_seg.as_ref()),
+                        };
 
                         // Segments should have a monotonic sequence number.
                         if seg.sequence_num() <= last_seg.sequence_num() {
@@ -495,6 +858,71 @@ impl<'s> IsoMp4Reader<'s> {
         Ok(false)
     }
 
+    /// If the segment index references a movie fragment containing `time` for the track
+    /// `track_num`, and the fragment has not been read, then reposition the reader to the fragment
+    /// instead of reading all the fragments preceding it.
+    fn seek_segment_index(&mut self, track_num: usize, time: Time) -> Result<()> {
+        if !self.iter.inner().is_seekable() {
+            return Ok(());
+        }
+
+        let track_id = self.tracks[track_num].id;
+
+        let Some((mut entry, entry_time)) = self.sidx.find(track_id, time)
+        else {
+            return Ok(());
+        };
+
+        // If the referenced media was read, then the segments already read will be searched.
+        if self.segs.len() > 1 {
+            let tb = self.tracks[track_num].time_base.unwrap();
+
+            let calc_time = |ts: u64| Timestamp::try_from(ts).ok().and_then(|ts| tb.calc_time(ts));
+
+            let start = calc_time(self.segs[1].track_ts_range(track_num).start);
+            let end = calc_time(self.segs.last().unwrap().track_ts_range(track_num).end);
+
+            if let (Some(start), Some(end)) = (start, end) {
+                if (start..=end).contains(&entry_time) {
+                    return Ok(());
+                }
+            }
+        }
+
+        // If the entry references another segment index atom, read it to find a movie fragment
+        // closer to the desired time.
+        while entry.is_sidx && !self.sidx.added.contains(&entry.pos) {
+            self.iter.seek_top_level(entry.pos)?;
+
+            match self.iter.next_header()? {
+                Some(header) if header.atom_type() == AtomType::SegmentIndex => (),
+                _ => break,
+            }
+
+            let sidx = self.iter.read_atom::<SidxAtom>()?;
+            self.sidx.push(entry.pos, &sidx)?;
+
+            match self.sidx.find(track_id, time) {
+                Some((found, _)) => entry = found,
+                None => break,
+            }
+        }
+
+        debug!("seeking to fragment at pos={} using the segment index", entry.pos);
+
+        self.iter.seek_top_level(entry.pos)?;
+
+        // Drop all fragment segments. The first segment is always the moov segment.
+        self.segs.truncate(1);
+        self.next_seg = Some(entry);
+
+        for state in self.track_states.iter_mut() {
+            state.cur_seg = 0;
+        }
+
+        Ok(())
+    }
+
     fn seek_track_by_time(&mut self, track_num: usize, time: Time) -> Result<SeekedTo> {
         // Convert time to timestamp for the track.
         if let Some(track) = self.tracks.get(track_num) {
@@ -503,7 +931,7 @@ impl<'s> IsoMp4Reader<'s> {
             self.seek_track_by_ts(track_num, ts)
         }
         else {
-            seek_error(SeekErrorKind::Unseekable)
+            seek_error(SeekErrorKind::InvalidTrack)
         }
     }
 
@@ -542,8 +970,12 @@ impl<'s> IsoMp4Reader<'s> {
 
         let seg = &self.segs[seek_loc.seg_idx];
 
+        // Decoding must start from a sync sample. Therefore, seek to the nearest sync sample at, or
+        // before, the sample containing the desired timestamp.
+        let sample_num = seg.sync_sample(track_num, seek_loc.sample_num)?;
+
         // Get the sample timing.
-        let timing = seg.sample_timing(track_num, seek_loc.sample_num)?.unwrap();
+        let timing = seg.sample_timing(track_num, sample_num)?.unwrap();
 
         // Try to convert the sample timing to a timestamp.
         let actual_ts = match Timestamp::try_from(timing.ts) {
@@ -552,15 +984,19 @@ impl<'s> IsoMp4Reader<'s> {
         };
 
         // Get the sample information.
-        let data_desc = seg.sample_data(track_num, seek_loc.sample_num, true)?;
+        let data_desc = seg.sample_data(track_num, sample_num, true)?;
 
         // Update the track's next sample information to point to the seeked sample.
         let track = &mut self.track_states[track_num];
 
         track.cur_seg = seek_loc.seg_idx;
-        track.next_sample = seek_loc.sample_num;
+        track.next_sample = sample_num;
         track.next_sample_pos = data_desc.base_pos + data_desc.offset.unwrap();
 
+        // Decoding of an audio track should start exactly at the required timestamp, therefore the
+        // frames preceding it will be trimmed. Trimming requires the sample rate of the track.
+        track.seek_ts = track.sample_rate.map(|_| ts);
+
         debug!(
             "seeked track_num={} (track_id={}) to packet_ts={} (delta={})",
             track_num,
@@ -573,6 +1009,110 @@ impl<'s> IsoMp4Reader<'s> {
     }
 }
 
+/// Read the start timecode from the first sample of the first timecode track.
+fn read_start_timecode(
+    mss: &mut MediaSourceStream<'_>,
+    moov: &MoovAtom,
+) -> Result<Option<Timecode>> {
+    for trak in &moov.traks {
+        let stbl = &trak.mdia.minf.stbl;
+
+        let Some(entry) = stbl.stsd.timecode_sample_entry()
+        else {
+            continue;
+        };
+
+        let Some(offset) = first_sample_offset(stbl)
+        else {
+            continue;
+        };
+
+        // The sample is the frame count of the first frame.
+        mss.seek(SeekFrom::Start(offset))?;
+
+        return Ok(entry.make_timecode(mss.read_be_u32()?));
+    }
+
+    Ok(None)
+}
+
+/// If the track is a TrueHD or DTS track, refine its codec parameters using the first access unit.
+/// The sample entry cannot signal the presence of Dolby Atmos, or all the DTS extensions.
+fn read_first_access_unit(
+    mss: &mut MediaSourceStream<'_>,
+    trak: &TrakAtom,
+    track: &mut Track,
+) -> Result<()> {
+    // The maximum number of bytes of a DTS frame to read.
+    const MAX_DTS_FRAME_SIZE: u32 = 64 * 1024;
+
+    let Some(CodecParameters::Audio(params)) = &mut track.codec_params
+    else {
+        return Ok(());
+    };
+
+    let stbl = &trak.mdia.minf.stbl;
+
+    let len = match params.codec {
+        // The access unit header and the major sync, up-to and including the substream
+        // information.
+        CODEC_ID_TRUEHD => 22,
+        CODEC_ID_DCA => match &stbl.stsz.sample_sizes {
+            SampleSize::Constant(size) => *size,
+            SampleSize::Variable(sizes) => sizes.first().copied().unwrap_or(0),
+        }
+        .min(MAX_DTS_FRAME_SIZE),
+        _ => return Ok(()),
+    };
+
+    let Some(offset) = first_sample_offset(stbl)
+    else {
+        return Ok(());
+    };
+
+    mss.seek(SeekFrom::Start(offset))?;
+    let buf = mss.read_boxed_slice_exact(len as usize)?;
+
+    if params.codec == CODEC_ID_TRUEHD {
+        let major_sync = MajorSync::read(&buf)?;
+
+        params
+            .with_sample_rate(major_sync.format_info.sample_rate)
+            .with_channels(major_sync.format_info.channels);
+
+        if major_sync.has_16ch_presentation {
+            params.with_profile(CODEC_PROFILE_TRUEHD_ATMOS);
+        }
+    }
+    else {
+        let frame_info = dca::FrameInfo::read(&buf)?;
+
+        if let Some(sample_rate) = frame_info.sample_rate {
+            params.with_sample_rate(sample_rate);
+        }
+
+        if let Some(channels) = frame_info.channels {
+            params.with_channels(channels);
+        }
+
+        params.with_profile(frame_info.profile.into());
+    }
+
+    Ok(())
+}
+
+/// Get the position of the first sample of a track, if the track has any samples.
+fn first_sample_offset(stbl: &StblAtom) -> Option<u64> {
+    // The first sample is at the start of the first chunk.
+    let offset = match (&stbl.stco, &stbl.co64) {
+        (Some(stco), _) => stco.chunk_offsets.first().map(|&offset| u64::from(offset)),
+        (_, Some(co64)) => co64.chunk_offsets.first().copied(),
+        _ => None,
+    };
+
+    offset.filter(|_| stbl.stsz.sample_count > 0)
+}
+
 impl Scoreable for IsoMp4Reader<'_> {
     fn score(_src: ScopedStream<&mut MediaSourceStream<'_>>) -> Result<Score> {
         Ok(Score::Supported(255))
@@ -607,45 +1147,72 @@ impl FormatReader for IsoMp4Reader<'_> {
     }
 
     fn next_packet(&mut self) -> Result<Option<Packet>> {
-        // Get the index of the track with the next-nearest (minimum) timestamp.
-        let next_sample_info = loop {
-            // Using the current set of segments, try to get the next sample info.
-            if let Some(info) = self.next_sample_info()? {
-                break info;
-            }
-            else {
-                // The inner reader of the atom iterator has been used/seeked around to read
-                // packets, so resync the reader and iterator by seeking to the end of the current
-                // pending atom. Under regular circumstances, no actual expensive seek operation is
-                // performed since the reader should be at the end of the last iterated atom if we
-                // are trying to read another.
-                match self.iter.seek_atom_end() {
-                    Ok(_) | Err(AtomError::NoPendingAtom) => (),
-                    Err(_) => return decode_error("sync lost"),
-                };
+        let Some((info, sample_info)) = self.next_sample()?
+        else {
+            return Ok(None);
+        };
 
-                // No more segments. If the stream is unseekable, it may be the case that there are
-                // more segments coming. If the stream is seekable it might be fragmented and no
-                // segments are found in the moov atom. Iterate atoms until a new segment is found
-                // or the end-of-stream is reached
-                if !self.try_read_more_segments()? {
-                    return Ok(None);
-                }
-            }
+        let mut data =
+            self.iter.read_raw_boxed_slice_exact(sample_info.pos, sample_info.len as usize)?;
+
+        if let Some(converter) = &self.track_states[info.track_num].annex_b {
+            let mut buf = Vec::new();
+            converter.convert(&data, info.is_keyframe, &mut buf)?;
+            data = buf.into_boxed_slice();
+        }
+
+        let mut packet = Packet::new(info.track_id, info.ts, info.dur, data);
+        packet.is_keyframe = info.is_keyframe;
+        packet.num_frames = self.num_frames(&info);
+
+        if let Some((seek_ts, time_base, sample_rate)) = self.take_seek_ts(&info) {
+            packet.trim_start_to(seek_ts, time_base, sample_rate);
+        }
+
+        Ok(Some(packet))
+    }
+
+    fn set_track_filter(&mut self, track_ids: &[u32]) -> Result<()> {
+        for state in self.track_states.iter_mut() {
+            state.selected = track_ids.is_empty() || track_ids.contains(&state.track_id);
+        }
+        Ok(())
+    }
+
+    fn is_track_selected(&self, track_id: u32) -> bool {
+        self.track_states.iter().any(|state| state.track_id == track_id && state.selected)
+    }
+
+    fn next_packet_into<'b>(&mut self, buf: &'b mut Vec<u8>) -> Result<Option<PacketRef<'b>>> {
+        let Some((info, sample_info)) = self.next_sample()?
+        else {
+            return Ok(None);
         };
 
-        // Get the position and length information of the next sample.
-        let sample_info = self.consume_next_sample(&next_sample_info)?.unwrap();
+        match &self.track_states[info.track_num].annex_b {
+            Some(converter) => {
+                let data = self
+                    .iter
+                    .read_raw_boxed_slice_exact(sample_info.pos, sample_info.len as usize)?;
+                converter.convert(&data, info.is_keyframe, buf)?;
+            }
+            None => {
+                self.iter.read_raw_exact_into(sample_info.pos, sample_info.len as usize, buf)?
+            }
+        }
 
-        let data =
-            self.iter.read_raw_boxed_slice_exact(sample_info.pos, sample_info.len as usize)?;
+        let seek_ts = self.take_seek_ts(&info);
+        let num_frames = self.num_frames(&info);
+
+        let mut packet = PacketRef::new(info.track_id, info.ts, info.dur, buf);
+        packet.is_keyframe = info.is_keyframe;
+        packet.num_frames = num_frames;
+
+        if let Some((seek_ts, time_base, sample_rate)) = seek_ts {
+            packet.trim_start_to(seek_ts, time_base, sample_rate);
+        }
 
-        Ok(Some(Packet::new(
-            next_sample_info.track_id,
-            next_sample_info.ts,
-            next_sample_info.dur,
-            data,
-        )))
+        Ok(Some(packet))
     }
 
     fn metadata(&mut self) -> Metadata<'_> {
@@ -658,7 +1225,7 @@ impl FormatReader for IsoMp4Reader<'_> {
 
     fn seek(&mut self, _mode: SeekMode, to: SeekTo) -> Result<SeekedTo> {
         if self.tracks.is_empty() {
-            return seek_error(SeekErrorKind::Unseekable);
+            return seek_error(SeekErrorKind::Unsupported);
         }
 
         match to {
@@ -674,7 +1241,9 @@ impl FormatReader for IsoMp4Reader<'_> {
                         .time_base
                         .unwrap()
                         .calc_time(ts)
-                        .ok_or(Error::SeekError(SeekErrorKind::Unseekable))?;
+                        .ok_or(Error::SeekError(SeekErrorKind::OutOfRange))?;
+
+                    self.seek_segment_index(track_num, time)?;
 
                     // Seek all tracks excluding the primary track to the desired time.
                     for t in 0..self.track_states.len() {
@@ -702,6 +1271,8 @@ impl FormatReader for IsoMp4Reader<'_> {
                     None => 0,
                 };
 
+                self.seek_segment_index(track_num, time)?;
+
                 // Seek all tracks excluding the selected track and discard the result.
                 for t in 0..self.track_states.len() {
                     if t != track_num {
@@ -715,6 +1286,102 @@ impl FormatReader for IsoMp4Reader<'_> {
         }
     }
 
+    fn seek_time(&mut self, time: Time) -> Result<Vec<SeekedTo>> {
+        if self.tracks.is_empty() {
+            return seek_error(SeekErrorKind::Unsupported);
+        }
+
+        // The primary track is the first video track, or the first track if there are none.
+        let track_num = self
+            .tracks
+            .iter()
+            .position(|track| track.track_type() == Some(TrackType::Video))
+            .unwrap_or(0);
+
+        self.seek_segment_index(track_num, time)?;
+
+        // Seek the primary track first. It may be seeked to a sync sample before the desired time.
+        let seeked = self.seek_track_by_time(track_num, time)?;
+
+        let actual_time = self.tracks[track_num]
+            .time_base
+            .unwrap()
+            .calc_time(seeked.actual_ts)
+            .ok_or(Error::SeekError(SeekErrorKind::OutOfRange))?;
+
+        let mut all_seeked = vec![seeked];
+
+        // Seek all other tracks to the time of the sample the primary track was seeked to, but
+        // report the timestamp of the desired time as the required timestamp.
+        for t in 0..self.track_states.len() {
+            if t != track_num {
+                let mut seeked = self.seek_track_by_time(t, actual_time)?;
+
+                seeked.required_ts = self.tracks[t]
+                    .time_base
+                    .unwrap()
+                    .calc_timestamp(time)
+                    .ok_or(Error::SeekError(SeekErrorKind::OutOfRange))?;
+
+                all_seeked.push(seeked);
+            }
+        }
+
+        Ok(all_seeked)
+    }
+
+    fn source_position(&self) -> Option<u64> {
+        let reader = self.iter.inner();
+        reader.is_seekable().then(|| reader.pos())
+    }
+
+    fn capabilities(&self) -> FormatCapabilities {
+        let seekable = self.iter.inner().is_seekable();
+        let known_duration = self.media_info.duration.is_some();
+
+        let mut caps = FormatCapabilities::empty();
+        caps.set(FormatCapabilities::SEEKABLE, seekable);
+        caps.set(FormatCapabilities::KNOWN_DURATION, known_duration);
+        caps.set(FormatCapabilities::LIVE, !seekable && !known_duration);
+        caps.set(FormatCapabilities::STREAMABLE, self.is_faststart);
+        caps
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        if !self.iter.inner().is_seekable() {
+            return unsupported_error("isomp4: reset requires a seekable source");
+        }
+
+        // The moov atom is immutable, therefore only the top-level atoms preceding the first moof
+        // or mdat atom need to be scanned again.
+        self.iter.rewind()?;
+
+        while let Some(atom_type) = self.iter.next_header()?.map(|header| header.atom_type()) {
+            match atom_type {
+                AtomType::MediaData => break,
+                AtomType::MovieFragment => {
+                    // Fragments are streamed, so seek the iterator to the start of the moof atom.
+                    self.iter.seek_atom_start()?;
+                    break;
+                }
+                _ => (),
+            }
+        }
+
+        // Drop all fragment segments. The first segment is always the moov segment.
+        self.segs.truncate(1);
+        self.next_seg = None;
+
+        for state in self.track_states.iter_mut() {
+            state.cur_seg = 0;
+            state.next_sample = 0;
+            state.next_sample_pos = 0;
+            state.seek_ts = None;
+        }
+
+        Ok(())
+    }
+
     fn into_inner<'s>(self: Box<Self>) -> MediaSourceStream<'s>
     where
         Self: 's,
@@ -741,6 +1408,7 @@ impl From<AtomError> for Error {
             AtomError::UnexpectedUnknownSizeAtom => "isomp4: unknown size atom has sized parent",
             AtomError::UnexpectedReadOperation => "isomp4: unexpected read operation",
             AtomError::UnknownAtomSize => "isomp4: unknown atom size",
+            AtomError::AtomDataTooLarge => "isomp4: atom data size exceeds the limit",
             AtomError::Other(err) => return err,
         };
         Error::DecodeError(msg)
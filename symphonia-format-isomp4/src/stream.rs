@@ -6,11 +6,14 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 use symphonia_core::errors::{Error, Result, decode_error};
 
-use crate::atoms::{Co64Atom, MoofAtom, MoovAtom, StcoAtom, TrafAtom, stsz::SampleSize};
+use crate::atoms::{Co64Atom, MoofAtom, MoovAtom, StcoAtom, TrafAtom, TrexAtom, stsz::SampleSize};
 
 use std::ops::Range;
 use std::sync::Arc;
 
+/// The sample flag indicating that a sample is not a sync sample.
+const SAMPLE_IS_NON_SYNC_SAMPLE: u32 = 0x1_0000;
+
 /// Sample data information.
 pub struct SampleDataDesc {
     /// The starting byte position within the media data of the group of samples that contains the
@@ -28,6 +31,8 @@ pub struct SampleTiming {
     pub ts: u64,
     /// The duration of the sample.
     pub dur: u32,
+    /// If `true`, the sample is a sync sample (keyframe).
+    pub is_sync: bool,
 }
 
 pub trait StreamSegment: Send + Sync {
@@ -51,6 +56,11 @@ pub trait StreamSegment: Send + Sync {
     // `track_num`.
     fn ts_sample(&self, track_num: usize, ts: u64) -> Result<Option<u32>>;
 
+    /// Get the sample number of the nearest sync sample at, or before, the sample indicated by
+    /// `sample_num` for track `track_num`. If there is no such sync sample in this segment, then
+    /// the first sample of the track in this segment is returned.
+    fn sync_sample(&self, track_num: usize, sample_num: u32) -> Result<u32>;
+
     /// Get the byte position of the group of samples containing the sample indicated by
     /// `sample_num` for track `track_num`, and it's size.
     ///
@@ -78,6 +88,8 @@ struct SequenceInfo {
     /// If present in the moof segment, this is the index of the track fragment atom for the track
     /// this sequence information is associated with.
     traf_idx: Option<usize>,
+    /// The index of the track extends atom for the track.
+    trex_idx: usize,
 }
 
 pub struct MoofSegment {
@@ -91,13 +103,23 @@ impl MoofSegment {
     pub fn new(moof: MoofAtom, moov: Arc<MoovAtom>, prev: &dyn StreamSegment) -> MoofSegment {
         let mvex = moov.mvex.as_ref().expect("mvex atom present");
 
-        let mut seq = Vec::with_capacity(mvex.trexs.len());
+        let mut seq = Vec::with_capacity(moov.traks.len());
 
         // Calculate the sequence information for each track, even if not present in the fragment.
-        for (track_num, trex) in mvex.trexs.iter().enumerate() {
+        // The track extends atoms may be in any order, so they are matched to tracks by track ID.
+        for (track_num, trak) in moov.traks.iter().enumerate() {
+            let trex_idx = mvex
+                .trexs
+                .iter()
+                .position(|trex| trex.track_id == trak.tkhd.id)
+                .expect("trex atom present");
+
+            let trex = &mvex.trexs[trex_idx];
+
             let mut info = SequenceInfo {
                 first_sample: prev.track_sample_range(track_num).end,
                 first_ts: prev.track_ts_range(track_num).end,
+                trex_idx,
                 ..Default::default()
             };
 
@@ -125,11 +147,34 @@ impl MoofSegment {
         MoofSegment { moof, moov, seq }
     }
 
+    /// Instantiate a new segment from a `MoofAtom` that does not immediately follow the previous
+    /// segment, such as after a seek. The timestamp of the first sample of each track is provided
+    /// by `first_ts`, indexed by track number.
+    pub fn new_at(
+        moof: MoofAtom,
+        moov: Arc<MoovAtom>,
+        prev: &dyn StreamSegment,
+        first_ts: &[u64],
+    ) -> MoofSegment {
+        let mut seg = MoofSegment::new(moof, moov, prev);
+
+        for (info, &ts) in seg.seq.iter_mut().zip(first_ts) {
+            info.first_ts = ts;
+        }
+
+        seg
+    }
+
     /// Try to get the Track Fragment atom associated with the track identified by `track_num`.
     fn try_get_traf(&self, track_num: usize) -> Option<&TrafAtom> {
         debug_assert!(track_num < self.seq.len());
         self.seq[track_num].traf_idx.map(|idx| &self.moof.trafs[idx])
     }
+
+    /// Get the Track Extends atom associated with the track identified by `track_num`.
+    fn trex(&self, track_num: usize) -> &TrexAtom {
+        &self.moov.mvex.as_ref().unwrap().trexs[self.seq[track_num].trex_idx]
+    }
 }
 
 impl StreamSegment for MoofSegment {
@@ -152,17 +197,23 @@ impl StreamSegment for MoofSegment {
         let mut sample_num_rel = sample_num - self.seq[track_num].first_sample;
         let mut trun_ts_offset = self.seq[track_num].first_ts;
 
-        let default_dur = traf
-            .tfhd
-            .default_sample_duration
-            .unwrap_or(self.moov.mvex.as_ref().unwrap().trexs[track_num].default_sample_duration);
+        let trex = self.trex(track_num);
+
+        let default_dur = traf.tfhd.default_sample_duration.unwrap_or(trex.default_sample_duration);
+        let default_flags = traf.tfhd.default_sample_flags.unwrap_or(trex.default_sample_flags);
 
         for trun in traf.truns.iter() {
             // If the sample is contained within the this track run, get the timing of of the
             // sample.
             if sample_num_rel < trun.sample_count {
                 let (ts, dur) = trun.sample_timing(sample_num_rel, default_dur);
-                return Ok(Some(SampleTiming { ts: trun_ts_offset + ts, dur }));
+                let flags = trun.sample_flags(sample_num_rel, default_flags);
+
+                return Ok(Some(SampleTiming {
+                    ts: trun_ts_offset + ts,
+                    dur,
+                    is_sync: flags & SAMPLE_IS_NON_SYNC_SAMPLE == 0,
+                }));
             }
 
             let trun_dur = trun.total_duration(default_dur);
@@ -184,10 +235,16 @@ impl StreamSegment for MoofSegment {
         let mut sample_num = self.seq[track_num].first_sample;
         let mut ts_accum = self.seq[track_num].first_ts;
 
+        // If the segment was read after a seek, the preceding segments may not have been read. In
+        // that case, the first sample of the segment is the nearest to the desired timestamp.
+        if ts < ts_accum {
+            return Ok(Some(sample_num));
+        }
+
         let default_dur = traf
             .tfhd
             .default_sample_duration
-            .unwrap_or(self.moov.mvex.as_ref().unwrap().trexs[track_num].default_sample_duration);
+            .unwrap_or(self.trex(track_num).default_sample_duration);
 
         for trun in traf.truns.iter() {
             // Get the total duration of this track run.
@@ -207,6 +264,40 @@ impl StreamSegment for MoofSegment {
         Ok(None)
     }
 
+    fn sync_sample(&self, track_num: usize, sample_num: u32) -> Result<u32> {
+        // Get the track fragment associated with track_num.
+        let traf = match self.try_get_traf(track_num) {
+            Some(traf) => traf,
+            None => return Ok(sample_num),
+        };
+
+        let first_sample = self.seq[track_num].first_sample;
+        let sample_num_rel = sample_num - first_sample;
+
+        let trex = self.trex(track_num);
+        let default_flags = traf.tfhd.default_sample_flags.unwrap_or(trex.default_sample_flags);
+
+        let mut sync_sample_rel = 0;
+        let mut trun_first_rel = 0;
+
+        // Find the last sync sample in all track runs up-to and including the sample.
+        'runs: for trun in traf.truns.iter() {
+            for i in 0..trun.sample_count {
+                if trun_first_rel + i > sample_num_rel {
+                    break 'runs;
+                }
+
+                if trun.sample_flags(i, default_flags) & SAMPLE_IS_NON_SYNC_SAMPLE == 0 {
+                    sync_sample_rel = trun_first_rel + i;
+                }
+            }
+
+            trun_first_rel += trun.sample_count;
+        }
+
+        Ok(first_sample + sync_sample_rel)
+    }
+
     fn sample_data(
         &self,
         track_num: usize,
@@ -226,10 +317,8 @@ impl StreamSegment for MoofSegment {
         let mut sample_num_rel = sample_num - self.seq[track_num].first_sample;
         let mut trun_offset = traf_base_pos;
 
-        let default_size = traf
-            .tfhd
-            .default_sample_size
-            .unwrap_or(self.moov.mvex.as_ref().unwrap().trexs[track_num].default_sample_size);
+        let default_size =
+            traf.tfhd.default_sample_size.unwrap_or(self.trex(track_num).default_sample_size);
 
         for trun in traf.truns.iter() {
             // If a data offset is present for this track fragment run, then calculate the new base
@@ -355,10 +444,16 @@ impl StreamSegment for MoovSegment {
 
         let trak = &self.moov.traks[track_num];
 
-        // Find the sample timing. Note, complexity of O(N).
+        // Find the sample timing. Note, complexity of O(N), or O(log N) if indexed.
         let timing = trak.mdia.minf.stbl.stts.find_timing_for_sample(sample_num);
 
-        if let Some((ts, dur)) = timing { Ok(Some(SampleTiming { ts, dur })) } else { Ok(None) }
+        // If there is no sync sample table, then all samples are sync samples.
+        let is_sync = match &trak.mdia.minf.stbl.stss {
+            Some(stss) => stss.is_sync_sample(sample_num),
+            None => true,
+        };
+
+        Ok(timing.map(|(ts, dur)| SampleTiming { ts, dur, is_sync }))
     }
 
     fn ts_sample(&self, track_num: usize, ts: u64) -> Result<Option<u32>> {
@@ -367,10 +462,25 @@ impl StreamSegment for MoovSegment {
 
         let trak = &self.moov.traks[track_num];
 
-        // Find the sample timestamp. Note, complexity of O(N).
+        // Find the sample timestamp. Note, complexity of O(N), or O(log N) if indexed.
         Ok(trak.mdia.minf.stbl.stts.find_sample_for_timestamp(ts))
     }
 
+    fn sync_sample(&self, track_num: usize, sample_num: u32) -> Result<u32> {
+        // Get the trak atom associated with track_num.
+        debug_assert!(track_num < self.moov.traks.len());
+
+        let trak = &self.moov.traks[track_num];
+
+        // If there is no sync sample table, then all samples are sync samples.
+        let sync_sample = match &trak.mdia.minf.stbl.stss {
+            Some(stss) => stss.sync_sample_at_or_before(sample_num).unwrap_or(0),
+            None => sample_num,
+        };
+
+        Ok(sync_sample)
+    }
+
     fn sample_data(
         &self,
         track_num: usize,
@@ -411,24 +521,14 @@ impl StreamSegment for MoovSegment {
             // Index of the sample relative to the chunk containing the sample.
             let sample_in_chunk = sample_in_group - (chunk_in_group * group.samples_per_chunk);
 
-            // Calculat the byte offset of the sample relative to the chunk containing it.
-            let offset = match stsz.sample_sizes {
-                SampleSize::Constant(size) => {
-                    // Constant size samples can be calculated directly.
-                    u64::from(sample_in_chunk) * u64::from(size)
-                }
-                SampleSize::Variable(ref entries) => {
-                    // For variable size samples, sum the sizes of all the samples preceeding the
-                    // desired sample in the chunk.
-                    let chunk_first_sample = (sample_num - sample_in_chunk) as usize;
-
-                    if let Some(samples) = entries.get(chunk_first_sample..sample_num as usize) {
-                        samples.iter().map(|&size| u64::from(size)).sum()
-                    }
-                    else {
-                        return decode_error("isomp4: missing one or more stsz entries");
-                    }
-                }
+            // Calculate the byte offset of the sample relative to the chunk containing it by summing
+            // the sizes of all the samples preceeding the sample in the chunk. Note, complexity of
+            // O(N) for variable size samples, or O(1) if indexed.
+            let chunk_first_sample = sample_num - sample_in_chunk;
+
+            let Some(offset) = stsz.size_of_samples(chunk_first_sample..sample_num)
+            else {
+                return decode_error("isomp4: missing one or more stsz entries");
             };
 
             Some(offset)
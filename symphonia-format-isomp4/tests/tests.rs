@@ -0,0 +1,1722 @@
+use symphonia_core::audio::{Channels, layouts};
+use symphonia_core::codecs::CodecParameters;
+use symphonia_core::codecs::audio::AudioCodecParameters;
+use symphonia_core::codecs::audio::well_known::profiles::{
+    CODEC_PROFILE_AAC_HE, CODEC_PROFILE_AAC_HE_V2, CODEC_PROFILE_AAC_LC, CODEC_PROFILE_DTS,
+    CODEC_PROFILE_DTS_HD_MA, CODEC_PROFILE_DTS_HD_MA_X, CODEC_PROFILE_EAC3_JOC,
+    CODEC_PROFILE_TRUEHD_ATMOS,
+};
+use symphonia_core::codecs::audio::well_known::{
+    CODEC_ID_AAC, CODEC_ID_AC3, CODEC_ID_AC4, CODEC_ID_ALAC, CODEC_ID_DCA, CODEC_ID_EAC3,
+    CODEC_ID_MPEGH_3D_AUDIO, CODEC_ID_TRUEHD,
+};
+use symphonia_core::codecs::video::{
+    ContentLightLevel, ProjectionType, StereoMode, VideoCodecParameters,
+};
+use symphonia_core::common::Limit;
+use symphonia_core::formats::backfill::DurationBackfill;
+use symphonia_core::formats::probe::{Hint, Probe};
+use symphonia_core::formats::well_known::FORMAT_ID_ISOMP4;
+use symphonia_core::formats::{
+    FormatCapabilities, FormatOptions, FormatReader, NalFormat, SeekMode, SeekTo, TrackGroup,
+    TrackGroupKind, TrackType,
+};
+use symphonia_core::io::{MediaSource, MediaSourceStream, OffsetSource};
+use symphonia_core::meta::{
+    GeoLocation, MetadataRevision, RawValue, StandardTag, StandardVisualKey, Tag,
+};
+use symphonia_core::units::{Duration, FrameRate, Time, Timestamp};
+use symphonia_format_isomp4::IsoMp4Reader;
+
+/// The well-known UTF-8 data type of a metadata value.
+const DATA_TYPE_UTF8: u32 = 1;
+/// The well-known PNG data type of a metadata value.
+const DATA_TYPE_PNG: u32 = 14;
+
+/// Write an atom with the given type and payload.
+fn atom(atom_type: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+    let mut buf = (8 + payload.len() as u32).to_be_bytes().to_vec();
+    buf.extend_from_slice(atom_type);
+    buf.extend_from_slice(payload);
+    buf
+}
+
+/// Write a full atom with the given type, version, flags, and payload.
+fn full_atom(atom_type: &[u8; 4], version: u8, flags: u32, payload: &[u8]) -> Vec<u8> {
+    let header = (u32::from(version) << 24) | flags;
+    atom(atom_type, &[&header.to_be_bytes(), payload].concat())
+}
+
+/// Write a metadata value (data) atom.
+fn data(data_type: u32, value: &[u8]) -> Vec<u8> {
+    // The data type is followed by a 4-byte locale indicator.
+    full_atom(b"data", 0, data_type, &[&[0; 4], value].concat())
+}
+
+/// Write a metadata tag atom with a single UTF-8 string value.
+fn string_tag(atom_type: &[u8; 4], value: &str) -> Vec<u8> {
+    atom(atom_type, &data(DATA_TYPE_UTF8, value.as_bytes()))
+}
+
+/// Write a freeform (`----`) metadata tag atom.
+fn freeform_tag(mean: &str, name: &str, value: &str) -> Vec<u8> {
+    let children = [
+        full_atom(b"mean", 0, 0, mean.as_bytes()),
+        full_atom(b"name", 0, 0, name.as_bytes()),
+        data(DATA_TYPE_UTF8, value.as_bytes()),
+    ];
+    atom(b"----", &children.concat())
+}
+
+/// A 2x1 8-bit truecolor PNG signature and IHDR chunk.
+fn png() -> Vec<u8> {
+    let mut buf = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+    buf.extend_from_slice(&13u32.to_be_bytes());
+    buf.extend_from_slice(b"IHDR");
+    buf.extend_from_slice(&2u32.to_be_bytes());
+    buf.extend_from_slice(&1u32.to_be_bytes());
+    // Bit depth, color type, compression, filter, and interlace methods, and the CRC.
+    buf.extend_from_slice(&[8, 2, 0, 0, 0, 0, 0, 0, 0]);
+    buf
+}
+
+/// Build an audio-less M4A file with iTunes-style metadata and cover art.
+fn make_m4a() -> Vec<u8> {
+    let ftyp = atom(b"ftyp", &[b"M4A ".as_slice(), &[0; 4], b"M4A ", b"isom"].concat());
+
+    // Creation and modification times, timescale, and duration, followed by the preferred rate
+    // and volume, and the remaining fields.
+    let mvhd =
+        full_atom(b"mvhd", 0, 0, &[[0; 8].as_slice(), &1000u32.to_be_bytes(), &[0; 84]].concat());
+
+    let ilst = atom(
+        b"ilst",
+        &[
+            string_tag(b"\xa9nam", "Title"),
+            string_tag(b"\xa9ART", "Artist"),
+            atom(b"trkn", &data(0, &[0, 0, 0, 3, 0, 12, 0, 0])),
+            atom(b"covr", &data(DATA_TYPE_PNG, &png())),
+            freeform_tag("com.apple.iTunes", "ISRC", "USABC0000001"),
+            freeform_tag("org.example", "Custom", "Value"),
+        ]
+        .concat(),
+    );
+
+    let hdlr = full_atom(b"hdlr", 0, 0, &[&[0; 4], b"mdir".as_slice(), &[0; 13]].concat());
+    let meta = full_atom(b"meta", 0, 0, &[hdlr, ilst].concat());
+    let moov = atom(b"moov", &[mvhd, atom(b"udta", &meta)].concat());
+
+    [ftyp, moov, atom(b"mdat", &[])].concat()
+}
+
+/// Build an audio-less MP4 file with QuickTime-style (keys and mdta) metadata, like that written
+/// by phone cameras.
+fn make_mdta_mp4() -> Vec<u8> {
+    let ftyp = atom(b"ftyp", &[b"qt  ".as_slice(), &[0; 4], b"qt  "].concat());
+
+    let mvhd =
+        full_atom(b"mvhd", 0, 0, &[[0; 8].as_slice(), &1000u32.to_be_bytes(), &[0; 84]].concat());
+
+    let keys = [
+        "com.apple.quicktime.location.ISO6709",
+        "com.apple.quicktime.make",
+        "com.apple.quicktime.model",
+        "com.apple.quicktime.creationdate",
+    ];
+
+    // Each key is prefixed by its size, including the size and namespace fields, and namespace.
+    let mut entries = (keys.len() as u32).to_be_bytes().to_vec();
+    for key in keys {
+        entries.extend_from_slice(&(8 + key.len() as u32).to_be_bytes());
+        entries.extend_from_slice(b"mdta");
+        entries.extend_from_slice(key.as_bytes());
+    }
+
+    // Items are identified by the 1-based index of their key. The make is not present.
+    let ilst = atom(
+        b"ilst",
+        &[
+            string_tag(&1u32.to_be_bytes(), "+37.3349-122.0090+030.000/"),
+            string_tag(&3u32.to_be_bytes(), "iPhone 15 Pro"),
+            string_tag(&4u32.to_be_bytes(), "2024-05-01T12:34:56+0200"),
+        ]
+        .concat(),
+    );
+
+    // The QuickTime meta atom is not a full atom.
+    let hdlr = full_atom(b"hdlr", 0, 0, &[&[0; 4], b"mdta".as_slice(), &[0; 13]].concat());
+    let meta = atom(b"meta", &[hdlr, full_atom(b"keys", 0, 0, &entries), ilst].concat());
+    let moov = atom(b"moov", &[mvhd, meta].concat());
+
+    [ftyp, moov, atom(b"mdat", &[])].concat()
+}
+
+/// Write the sample tables of a track without samples.
+fn no_samples() -> [Vec<u8>; 4] {
+    [
+        full_atom(b"stts", 0, 0, &[0; 4]),
+        full_atom(b"stsc", 0, 0, &[0; 4]),
+        full_atom(b"stsz", 0, 0, &[0; 8]),
+        full_atom(b"stco", 0, 0, &[0; 4]),
+    ]
+}
+
+/// Write the sample tables of a track with a single sample of the given size and offset.
+fn one_sample(size: u32, offset: u32) -> [Vec<u8>; 4] {
+    let be =
+        |values: &[u32]| values.iter().flat_map(|value| value.to_be_bytes()).collect::<Vec<_>>();
+
+    [
+        full_atom(b"stts", 0, 0, &be(&[1, 1, 1])),
+        full_atom(b"stsc", 0, 0, &be(&[1, 1, 1, 1])),
+        full_atom(b"stsz", 0, 0, &be(&[size, 1])),
+        full_atom(b"stco", 0, 0, &be(&[1, offset])),
+    ]
+}
+
+/// The identity transformation matrix.
+const IDENTITY_MATRIX: [i32; 9] = [0x10000, 0, 0, 0, 0x10000, 0, 0, 0, 0x40000000];
+
+/// Write a track atom with the given track ID, handler type, sample entry, and sample tables.
+fn trak(
+    track_id: u32,
+    handler_type: &[u8; 4],
+    sample_entry: Vec<u8>,
+    sample_tables: [Vec<u8>; 4],
+) -> Vec<u8> {
+    trak_with_tkhd(track_id, handler_type, sample_entry, sample_tables, IDENTITY_MATRIX, 0)
+}
+
+/// Write a track atom with the given track ID, handler type, sample entry, sample tables,
+/// transformation matrix, and alternate group.
+fn trak_with_tkhd(
+    track_id: u32,
+    handler_type: &[u8; 4],
+    sample_entry: Vec<u8>,
+    sample_tables: [Vec<u8>; 4],
+    matrix: [i32; 9],
+    alternate_group: u16,
+) -> Vec<u8> {
+    let matrix = matrix.iter().flat_map(|value| value.to_be_bytes()).collect::<Vec<_>>();
+
+    // Creation and modification times, track ID, reserved, duration, reserved, layer, alternate
+    // group, volume, and reserved, followed by the matrix, width, and height.
+    let tkhd = full_atom(
+        b"tkhd",
+        0,
+        0,
+        &[
+            [0; 8].as_slice(),
+            &track_id.to_be_bytes(),
+            &[0; 18],
+            &alternate_group.to_be_bytes(),
+            &[0; 4],
+            &matrix,
+            &[0; 8],
+        ]
+        .concat(),
+    );
+
+    // Creation and modification times, timescale, duration, language, and quality.
+    let mdhd =
+        full_atom(b"mdhd", 0, 0, &[[0; 8].as_slice(), &90000u32.to_be_bytes(), &[0; 8]].concat());
+
+    let hdlr = full_atom(b"hdlr", 0, 0, &[&[0; 4], handler_type.as_slice(), &[0; 13]].concat());
+
+    let stsd = full_atom(b"stsd", 0, 0, &[&1u32.to_be_bytes(), sample_entry.as_slice()].concat());
+
+    let stbl = atom(b"stbl", &[stsd, sample_tables.concat()].concat());
+
+    let mdia = atom(b"mdia", &[mdhd, hdlr, atom(b"minf", &stbl)].concat());
+    atom(b"trak", &[tkhd, mdia].concat())
+}
+
+/// Build an MP4 file with the given tracks and media data. The media data is at the end of the
+/// file.
+fn make_mp4(traks: &[Vec<u8>], mdat: &[u8]) -> Vec<u8> {
+    let ftyp = atom(b"ftyp", &[b"isom".as_slice(), &[0; 4], b"isom"].concat());
+
+    let mvhd =
+        full_atom(b"mvhd", 0, 0, &[[0; 8].as_slice(), &1000u32.to_be_bytes(), &[0; 84]].concat());
+
+    let moov = atom(b"moov", &[mvhd, traks.concat()].concat());
+
+    [ftyp, moov, atom(b"mdat", mdat)].concat()
+}
+
+/// Build an MP4 file with the given media data at the end of the file. The tracks are built by
+/// `make_traks` given the offset of the media data in the file.
+fn make_mp4_with_samples(make_traks: impl Fn(u32) -> Vec<Vec<u8>>, mdat: &[u8]) -> Vec<u8> {
+    let offset = make_mp4(&make_traks(0), &[]).len() as u32;
+    make_mp4(&make_traks(offset), mdat)
+}
+
+/// Open an MP4 file with the default options.
+fn open_mp4(data: Vec<u8>) -> IsoMp4Reader<'static> {
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    IsoMp4Reader::try_new(mss, Default::default()).unwrap()
+}
+
+/// Build an MP4 file with `make_mp4_with_samples`, and open it with the default options.
+fn open_mp4_with_samples(
+    make_traks: impl Fn(u32) -> Vec<Vec<u8>>,
+    mdat: &[u8],
+) -> IsoMp4Reader<'static> {
+    open_mp4(make_mp4_with_samples(make_traks, mdat))
+}
+
+/// Write a 3840x2160 VP9 visual sample entry. The provided atoms are appended to the sample entry.
+fn uhd_sample_entry(sample_entry_atoms: &[Vec<u8>]) -> Vec<u8> {
+    // The sample entry, visual sample entry fields with a 3840x2160 size, and the child atoms.
+    let mut vp09 = vec![0; 24];
+    vp09.extend_from_slice(&3840u16.to_be_bytes());
+    vp09.extend_from_slice(&2160u16.to_be_bytes());
+    vp09.extend_from_slice(&[0; 50]);
+    vp09.extend(sample_entry_atoms.concat());
+
+    atom(b"vp09", &vp09)
+}
+
+/// Build a video-only UHD MP4 file without samples. The provided atoms are appended to the visual
+/// sample entry.
+fn make_uhd_mp4(sample_entry_atoms: &[Vec<u8>]) -> Vec<u8> {
+    make_mp4(&[trak(1, b"vide", uhd_sample_entry(sample_entry_atoms), no_samples())], &[])
+}
+
+fn read_video_params(data: Vec<u8>) -> VideoCodecParameters {
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let reader = IsoMp4Reader::try_new(mss, Default::default()).unwrap();
+
+    match &reader.tracks()[0].codec_params {
+        Some(CodecParameters::Video(params)) => params.clone(),
+        _ => panic!("expected video codec parameters"),
+    }
+}
+
+/// Write a version 0 audio sample entry with the given codec, number of channels, and sample rate.
+/// The provided atoms are appended to the sample entry.
+fn audio_sample_entry(
+    codec: &[u8; 4],
+    num_channels: u16,
+    sample_rate: u32,
+    sample_entry_atoms: &[Vec<u8>],
+) -> Vec<u8> {
+    // Reserved, data reference index, version, revision, vendor, number of channels, sample size,
+    // compression ID, packet size, and sample rate, followed by the child atoms.
+    atom(
+        codec,
+        &[
+            [0, 0, 0, 0, 0, 0, 0, 1].as_slice(),
+            &[0; 8],
+            &num_channels.to_be_bytes(),
+            &[0, 16, 0, 0, 0, 0],
+            &(sample_rate << 16).to_be_bytes(),
+            &sample_entry_atoms.concat(),
+        ]
+        .concat(),
+    )
+}
+
+/// Write an esds atom for an AAC elementary stream with the given object type indication and
+/// AudioSpecificConfig. If the AudioSpecificConfig is empty, the decoder specific info is omitted.
+fn aac_esds(obj_type: u8, asc: &[u8]) -> Vec<u8> {
+    let dec_specific_info = match asc.len() {
+        0 => vec![],
+        len => [[0x05, len as u8].as_slice(), asc].concat(),
+    };
+
+    // Object type indication, stream type (audio), buffer size, and maximum and average bitrates,
+    // followed by the decoder specific info.
+    let dec_config = [[obj_type, 0x15].as_slice(), &[0; 11], &dec_specific_info].concat();
+
+    // ES ID, flags, the decoder config descriptor, and a predefined MP4 SL config descriptor.
+    let es_desc =
+        [[0, 1, 0].as_slice(), &[0x04, dec_config.len() as u8], &dec_config, &[0x06, 1, 0x02]]
+            .concat();
+
+    full_atom(b"esds", 0, 0, &[[0x03, es_desc.len() as u8].as_slice(), &es_desc].concat())
+}
+
+fn read_audio_params(data: Vec<u8>) -> AudioCodecParameters {
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let reader = IsoMp4Reader::try_new(mss, Default::default()).unwrap();
+
+    match &reader.tracks()[0].codec_params {
+        Some(CodecParameters::Audio(params)) => params.clone(),
+        _ => panic!("expected audio codec parameters"),
+    }
+}
+
+fn find_tag<'a>(rev: &'a MetadataRevision, key: &str) -> &'a Tag {
+    rev.media.tags.iter().find(|tag| tag.raw.key == key).unwrap()
+}
+
+#[test]
+fn read_ilst_metadata() {
+    let mss =
+        MediaSourceStream::new(Box::new(std::io::Cursor::new(make_m4a())), Default::default());
+    let mut reader = IsoMp4Reader::try_new(mss, Default::default()).unwrap();
+
+    assert_eq!(reader.format_info().format, FORMAT_ID_ISOMP4);
+
+    let metadata = reader.metadata();
+    let rev = metadata.current().unwrap();
+
+    assert!(matches!(
+        &find_tag(rev, "\u{a9}nam").std,
+        Some(StandardTag::TrackTitle(value)) if **value == "Title"
+    ));
+    assert!(matches!(
+        &find_tag(rev, "\u{a9}ART").std,
+        Some(StandardTag::Artist(value)) if **value == "Artist"
+    ));
+
+    // The track number and total are split into separate tags.
+    let track_tags = rev.media.tags.iter().filter(|tag| tag.raw.key == "trkn");
+    let std_tags = track_tags.filter_map(|tag| tag.std.clone()).collect::<Vec<_>>();
+    assert!(matches!(std_tags[..], [StandardTag::TrackNumber(3), StandardTag::TrackTotal(12)]));
+
+    // Freeform tags are keyed by their mean and name. Well-known iTunes freeform tags are mapped
+    // to standard tags.
+    assert!(matches!(
+        &find_tag(rev, "com.apple.iTunes:ISRC").std,
+        Some(StandardTag::IdentIsrc(value)) if **value == "USABC0000001"
+    ));
+
+    let tag = find_tag(rev, "org.example:Custom");
+    assert!(tag.std.is_none());
+    assert!(matches!(&tag.raw.value, RawValue::String(value) if **value == "Value"));
+
+    // The cover art is a visual.
+    assert_eq!(rev.media.visuals.len(), 1);
+
+    let visual = &rev.media.visuals[0];
+    assert_eq!(visual.media_type.as_deref(), Some("image/png"));
+    assert_eq!(visual.dimensions.map(|size| (size.width, size.height)), Some((2, 1)));
+    assert_eq!(visual.usage, Some(StandardVisualKey::FrontCover));
+    assert_eq!(&visual.data[..], &png()[..]);
+}
+
+#[test]
+fn limit_element_bytes() {
+    let open = |limit| {
+        let data = std::io::Cursor::new(make_m4a());
+        let mss = MediaSourceStream::new(Box::new(data), Default::default());
+        IsoMp4Reader::try_new(mss, FormatOptions::default().limit_element_bytes(limit))
+    };
+
+    // The cover art is rejected if it exceeds the limit.
+    assert!(open(Limit::Maximum(16)).is_err());
+    assert!(open(Limit::Maximum(64)).is_ok());
+    assert!(open(Limit::None).is_ok());
+}
+
+#[test]
+fn read_mdta_metadata() {
+    let mss =
+        MediaSourceStream::new(Box::new(std::io::Cursor::new(make_mdta_mp4())), Default::default());
+    let mut reader = IsoMp4Reader::try_new(mss, Default::default()).unwrap();
+
+    let metadata = reader.metadata();
+    let rev = metadata.current().unwrap();
+
+    // Tags are keyed by the full key name.
+    let tag = find_tag(rev, "com.apple.quicktime.location.ISO6709");
+    assert!(tag.std.is_none());
+    assert!(matches!(
+        &tag.raw.value,
+        RawValue::String(value) if **value == "+37.3349-122.0090+030.000/"
+    ));
+
+    let tag = find_tag(rev, "com.apple.quicktime.model");
+    assert!(matches!(&tag.raw.value, RawValue::String(value) if **value == "iPhone 15 Pro"));
+
+    assert!(matches!(
+        &find_tag(rev, "com.apple.quicktime.creationdate").std,
+        Some(StandardTag::RecordingDate(value)) if **value == "2024-05-01T12:34:56+0200"
+    ));
+
+    assert_eq!(rev.media.tags.len(), 3);
+
+    // The location is also parsed into coordinates.
+    assert_eq!(
+        rev.media.location,
+        Some(GeoLocation { latitude: 37.3349, longitude: -122.009, altitude: Some(30.0) })
+    );
+}
+
+#[test]
+fn probe_at_offset() {
+    // The M4A file is embedded after some leading junk.
+    let junk = vec![0xaa; 1000];
+    let data = [junk.as_slice(), &make_m4a()].concat();
+
+    let source = OffsetSource::new(std::io::Cursor::new(data), junk.len() as u64).unwrap();
+    let mss = MediaSourceStream::new(Box::new(source), Default::default());
+
+    // The start offset is position 0 of the media source stream.
+    assert_eq!(mss.byte_len(), Some(make_m4a().len() as u64));
+
+    let mut probe = Probe::new();
+    probe.register_format::<IsoMp4Reader<'_>>();
+
+    let mut reader =
+        probe.probe(&Hint::new(), mss, Default::default(), Default::default()).unwrap();
+
+    assert_eq!(reader.format_info().format, FORMAT_ID_ISOMP4);
+
+    let metadata = reader.metadata();
+    let rev = metadata.current().unwrap();
+    assert!(matches!(
+        &find_tag(rev, "\u{a9}nam").std,
+        Some(StandardTag::TrackTitle(value)) if **value == "Title"
+    ));
+}
+
+#[test]
+fn read_unknown_codec_track_type() {
+    // Replace the VP9 sample entry with an unknown sample entry.
+    let mut data = make_uhd_mp4(&[]);
+    let pos = data.windows(4).position(|window| window == b"vp09").unwrap();
+    data[pos..pos + 4].copy_from_slice(b"zzzz");
+
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let reader = IsoMp4Reader::try_new(mss, Default::default()).unwrap();
+
+    // The track type is known from the handler, even though the codec is not.
+    let track = &reader.tracks()[0];
+    assert!(track.codec_params.is_none());
+    assert_eq!(track.track_type(), Some(TrackType::Video));
+
+    assert_eq!(reader.first_track(TrackType::Video).map(|track| track.id), Some(1));
+    assert!(reader.default_track(TrackType::Video).is_none());
+}
+
+#[test]
+fn read_alternate_groups() {
+    let audio = |track_id: u32, alternate_group: u16| {
+        let sample_entry = audio_sample_entry(b"sowt", 2, 44100, &[]);
+        trak_with_tkhd(
+            track_id,
+            b"soun",
+            sample_entry,
+            no_samples(),
+            IDENTITY_MATRIX,
+            alternate_group,
+        )
+    };
+
+    // Two audio tracks in the same alternate group, and a video track not in any group.
+    let data = make_mp4(
+        &[trak(1, b"vide", uhd_sample_entry(&[]), no_samples()), audio(2, 1), audio(3, 1)],
+        &[],
+    );
+
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let reader = IsoMp4Reader::try_new(mss, Default::default()).unwrap();
+
+    let groups: Vec<_> = reader.tracks().iter().map(|track| track.alternate_group).collect();
+    assert_eq!(groups, vec![None, Some(1), Some(1)]);
+
+    assert_eq!(
+        reader.track_groups(),
+        vec![TrackGroup { kind: TrackGroupKind::Alternate, id: 1, track_ids: vec![2, 3] }]
+    );
+}
+
+#[test]
+fn read_timecode_and_data_tracks() {
+    let data = make_mp4(
+        &[
+            trak(1, b"vide", atom(b"zzzz", &[0; 8]), no_samples()),
+            trak(2, b"tmcd", atom(b"tmcd", &[0; 26]), no_samples()),
+            trak(3, b"meta", atom(b"mebx", &[0; 8]), no_samples()),
+        ],
+        &[],
+    );
+
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let reader = IsoMp4Reader::try_new(mss, Default::default()).unwrap();
+
+    // Timecode and timed metadata tracks are represented as tracks.
+    let track_types = reader.tracks().iter().map(|track| track.track_type()).collect::<Vec<_>>();
+    assert_eq!(
+        track_types,
+        vec![Some(TrackType::Video), Some(TrackType::Timecode), Some(TrackType::Data)]
+    );
+}
+
+#[test]
+fn read_alac_magic_cookie() {
+    // Frame length, compatible version, bit depth, the rice parameters, number of channels,
+    // maximum run, maximum frame size, average bit rate, and sample rate.
+    let cookie = [
+        4096u32.to_be_bytes().as_slice(),
+        &[0, 24, 40, 10, 14, 2],
+        &255u16.to_be_bytes(),
+        &[0; 8],
+        &96000u32.to_be_bytes(),
+    ]
+    .concat();
+
+    let sample_entry = audio_sample_entry(b"alac", 2, 44100, &[full_atom(b"alac", 0, 0, &cookie)]);
+    let params = read_audio_params(make_mp4(&[trak(1, b"soun", sample_entry, no_samples())], &[]));
+
+    // The magic cookie supersedes the sample entry.
+    assert_eq!(params.codec, CODEC_ID_ALAC);
+    assert_eq!(params.sample_rate, Some(96000));
+    assert_eq!(params.bits_per_sample, Some(24));
+    assert_eq!(params.max_frames_per_packet, Some(4096));
+    assert_eq!(params.channels.as_ref().map(Channels::count), Some(2));
+    assert_eq!(params.extra_data.as_deref(), Some(cookie.as_slice()));
+}
+
+#[test]
+fn read_he_aac_config() {
+    // HE-AAC with explicit SBR signalling: a 24 kHz stereo AAC-LC core with a 48 kHz SBR layer. The
+    // sample entry states the core sample rate.
+    let esds = aac_esds(0x40, &[0x2b, 0x11, 0x88, 0x00]);
+    let sample_entry = audio_sample_entry(b"mp4a", 2, 24000, &[esds]);
+    let params = read_audio_params(make_mp4(&[trak(1, b"soun", sample_entry, no_samples())], &[]));
+
+    // The AAC decoder only decodes the core, therefore the sample rate, channels, and frames per
+    // packet are those of the core.
+    assert_eq!(params.codec, CODEC_ID_AAC);
+    assert_eq!(params.profile, Some(CODEC_PROFILE_AAC_HE));
+    assert_eq!(params.sample_rate, Some(24000));
+    assert_eq!(params.channels.as_ref().map(Channels::count), Some(2));
+    assert_eq!(params.max_frames_per_packet, Some(1024));
+
+    // HE-AAC v2 with backward-compatible SBR and PS signalling: a 24 kHz mono AAC-LC core. The
+    // sample entry states the SBR sample rate.
+    let esds = aac_esds(0x40, &[0x13, 0x08, 0x56, 0xe5, 0x9d, 0x48, 0x80]);
+    let sample_entry = audio_sample_entry(b"mp4a", 2, 48000, &[esds]);
+    let params = read_audio_params(make_mp4(&[trak(1, b"soun", sample_entry, no_samples())], &[]));
+
+    assert_eq!(params.profile, Some(CODEC_PROFILE_AAC_HE_V2));
+    assert_eq!(params.sample_rate, Some(24000));
+    assert_eq!(params.channels.as_ref().map(Channels::count), Some(1));
+    assert_eq!(params.max_frames_per_packet, Some(1024));
+}
+
+#[test]
+fn read_aac_profile() {
+    let read_profile = |esds: Vec<u8>| {
+        let sample_entry = audio_sample_entry(b"mp4a", 2, 44100, &[esds]);
+        read_audio_params(make_mp4(&[trak(1, b"soun", sample_entry, no_samples())], &[])).profile
+    };
+
+    // AAC-LC, 44.1 kHz, stereo, signalled by the AudioSpecificConfig.
+    assert_eq!(read_profile(aac_esds(0x40, &[0x12, 0x10])), Some(CODEC_PROFILE_AAC_LC));
+    // MPEG-2 AAC-LC, signalled only by the object type indication.
+    assert_eq!(read_profile(aac_esds(0x67, &[])), Some(CODEC_PROFILE_AAC_LC));
+    // MPEG-4 audio without an AudioSpecificConfig.
+    assert_eq!(read_profile(aac_esds(0x40, &[])), None);
+}
+
+#[test]
+fn read_ac3_config() {
+    // 44.1 kHz, bsid 8, complete main, stereo, no LFE, 192 kbps.
+    let sample_entry = audio_sample_entry(b"ac-3", 6, 48000, &[atom(b"dac3", &[0x50, 0x11, 0x40])]);
+    let params = read_audio_params(make_mp4(&[trak(1, b"soun", sample_entry, no_samples())], &[]));
+
+    assert_eq!(params.codec, CODEC_ID_AC3);
+    assert_eq!(params.sample_rate, Some(44100));
+    assert_eq!(params.channels, Some(layouts::CHANNEL_LAYOUT_STEREO));
+}
+
+#[test]
+fn read_eac3_joc_config() {
+    // 640 kbps, one independent substream: 48 kHz, bsid 16, 3/2 with LFE, no dependent substreams,
+    // followed by the Dolby Atmos extension with a complexity index of 16.
+    let dec3 = [0x14, 0x00, 0x20, 0x0f, 0x00, 0x01, 0x10];
+
+    let sample_entry = audio_sample_entry(b"ec-3", 2, 48000, &[atom(b"dec3", &dec3)]);
+    let params = read_audio_params(make_mp4(&[trak(1, b"soun", sample_entry, no_samples())], &[]));
+
+    assert_eq!(params.codec, CODEC_ID_EAC3);
+    assert_eq!(params.profile, Some(CODEC_PROFILE_EAC3_JOC));
+    assert_eq!(params.sample_rate, Some(48000));
+    assert_eq!(params.channels, Some(layouts::CHANNEL_LAYOUT_5P1_SIDE));
+    assert_eq!(params.extra_data.as_deref(), Some(dec3.as_slice()));
+
+    // The same program without the extension, and with a dependent substream carrying the rear
+    // surround channels.
+    let dec3 = [0x14, 0x00, 0x20, 0x0f, 0x02, 0x80];
+
+    let sample_entry = audio_sample_entry(b"ec-3", 2, 48000, &[atom(b"dec3", &dec3)]);
+    let params = read_audio_params(make_mp4(&[trak(1, b"soun", sample_entry, no_samples())], &[]));
+
+    assert_eq!(params.profile, None);
+    assert_eq!(params.channels, Some(layouts::CHANNEL_LAYOUT_7P1));
+}
+
+/// Build an MP4 file with a 48 kHz 7.1 TrueHD track. The first access unit of the track may signal
+/// a 16-channel presentation.
+fn make_truehd_mp4(has_16ch_presentation: bool) -> Vec<u8> {
+    // 48 kHz, 5.1 for the 6-channel presentation, and 7.1 for the 8-channel presentation.
+    let format_info = 0x0007_804fu32.to_be_bytes();
+
+    // The format information, peak data rate, and reserved fields.
+    let dmlp = [format_info.as_slice(), &[0; 6]].concat();
+
+    // The access unit header, followed by the major sync up-to the substream information. Four
+    // substreams are present.
+    let access_unit = [
+        [0xf0, 0x20, 0x00, 0x00, 0xf8, 0x72, 0x6f, 0xba].as_slice(),
+        &format_info,
+        &[0xb7, 0x52, 0x00, 0x00, 0x00, 0x00, 0x80, 0x00, 0x40],
+        &[if has_16ch_presentation { 0x80 } else { 0x00 }],
+        &[0; 42],
+    ]
+    .concat();
+
+    let traks = |offset| {
+        let sample_entry = audio_sample_entry(b"mlpa", 2, 0, &[atom(b"dmlp", &dmlp)]);
+        vec![trak(1, b"soun", sample_entry, one_sample(access_unit.len() as u32, offset))]
+    };
+
+    make_mp4_with_samples(traks, &access_unit)
+}
+
+#[test]
+fn read_truehd_atmos() {
+    let params = read_audio_params(make_truehd_mp4(true));
+
+    assert_eq!(params.codec, CODEC_ID_TRUEHD);
+    assert_eq!(params.profile, Some(CODEC_PROFILE_TRUEHD_ATMOS));
+    assert_eq!(params.sample_rate, Some(48000));
+    assert_eq!(params.channels, Some(layouts::CHANNEL_LAYOUT_7P1));
+
+    let params = read_audio_params(make_truehd_mp4(false));
+
+    assert_eq!(params.codec, CODEC_ID_TRUEHD);
+    assert_eq!(params.profile, None);
+    assert_eq!(params.channels, Some(layouts::CHANNEL_LAYOUT_7P1));
+}
+
+/// Build an MP4 file with a DTS track. The first frame consists of a 64 byte 48 kHz 5.1 core frame,
+/// followed by the given extension substream, if any.
+fn make_dts_mp4(codec: &[u8; 4], substream: &[u8]) -> Vec<u8> {
+    // The core frame header: a normal frame of 16 blocks, 5 front channels at 48 kHz, and LFE.
+    let mut frame = vec![0x7f, 0xfe, 0x80, 0x01, 0xfc, 0x3c, 0x03, 0xf2, 0x75, 0xe0, 0x02];
+    frame.resize(64, 0);
+    frame.extend_from_slice(substream);
+
+    let traks = |offset| {
+        let sample_entry = audio_sample_entry(codec, 6, 48000, &[]);
+        vec![trak(1, b"soun", sample_entry, one_sample(frame.len() as u32, offset))]
+    };
+
+    make_mp4_with_samples(traks, &frame)
+}
+
+/// Write an extension substream with a single 48 kHz 7.1 audio asset coded with the lossless (XLL)
+/// extension. If DTS:X is present, the lossless extension contains the DTS:X sync word.
+fn dts_xll_substream(has_x: bool) -> Vec<u8> {
+    // The 23 byte extension substream header, with static fields, and the descriptor of a single
+    // 32 byte asset. The asset is 24-bit, 48 kHz, has 8 channels with the C, L/R, Ls/Rs, LFE1,
+    // and Lsr/Rsr loudspeaker mask (0x004f), and uses the lossless coding mode.
+    let mut substream = vec![
+        0x64, 0x58, 0x20, 0x25, 0x00, 0x02, 0xc0, 0x06, 0xd0, 0x00, 0x80, 0x00, 0x07, 0xc0, 0x00,
+        0x5f, 0x01, 0xe7, 0x00, 0x4f, 0x02, 0x00, 0x3e,
+    ];
+
+    // The lossless extension.
+    substream.extend_from_slice(&[0x41, 0xa2, 0x95, 0x47]);
+    substream.resize(39, 0);
+
+    if has_x {
+        substream.extend_from_slice(&[0x02, 0x00, 0x08, 0x50]);
+    }
+
+    substream.resize(55, 0);
+    substream
+}
+
+#[test]
+fn read_dts_profile() {
+    // DTS-HD Master Audio. The channels of the lossless asset take precedence over the 5.1 core.
+    let params = read_audio_params(make_dts_mp4(b"dtsl", &dts_xll_substream(false)));
+
+    assert_eq!(params.codec, CODEC_ID_DCA);
+    assert_eq!(params.profile, Some(CODEC_PROFILE_DTS_HD_MA));
+    assert_eq!(params.sample_rate, Some(48000));
+    assert_eq!(params.channels, Some(layouts::CHANNEL_LAYOUT_7P1));
+
+    // DTS-HD Master Audio with DTS:X.
+    let params = read_audio_params(make_dts_mp4(b"dtsl", &dts_xll_substream(true)));
+
+    assert_eq!(params.profile, Some(CODEC_PROFILE_DTS_HD_MA_X));
+
+    // Core audio only.
+    let params = read_audio_params(make_dts_mp4(b"dtsc", &[]));
+
+    assert_eq!(params.codec, CODEC_ID_DCA);
+    assert_eq!(params.profile, Some(CODEC_PROFILE_DTS));
+    assert_eq!(params.channels.as_ref().map(Channels::count), Some(6));
+}
+
+#[test]
+fn read_ac4_config() {
+    // AC-4 decoder specific information version 1, bitstream version 2, 48 kHz, frame rate index 2,
+    // and a single presentation (truncated).
+    let dsi = [0x20, 0xa4, 0x01, 0x00, 0x00, 0x00, 0x00];
+
+    // The sample entry states 44.1 kHz, but the dac4 atom takes precedence.
+    let sample_entry = audio_sample_entry(b"ac-4", 2, 44100, &[atom(b"dac4", &dsi)]);
+    let params = read_audio_params(make_mp4(&[trak(1, b"soun", sample_entry, no_samples())], &[]));
+
+    assert_eq!(params.codec, CODEC_ID_AC4);
+    assert_eq!(params.sample_rate, Some(48000));
+    assert_eq!(params.extra_data.as_deref(), Some(dsi.as_slice()));
+}
+
+#[test]
+fn read_mpegh_config() {
+    // Configuration version 1, profile and level, reference channel layout, and a 2-byte
+    // configuration.
+    let config = [0x01, 0x0d, 0x06, 0x00, 0x02, 0xaa, 0xbb];
+
+    let sample_entry = audio_sample_entry(b"mha1", 6, 48000, &[atom(b"mhaC", &config)]);
+    let params = read_audio_params(make_mp4(&[trak(1, b"soun", sample_entry, no_samples())], &[]));
+
+    assert_eq!(params.codec, CODEC_ID_MPEGH_3D_AUDIO);
+    assert_eq!(params.sample_rate, Some(48000));
+    assert_eq!(params.extra_data.as_deref(), Some(config.as_slice()));
+
+    // The configuration of a mhm1 sample entry is carried in-band.
+    let sample_entry = audio_sample_entry(b"mhm1", 6, 48000, &[]);
+    let params = read_audio_params(make_mp4(&[trak(1, b"soun", sample_entry, no_samples())], &[]));
+
+    assert_eq!(params.codec, CODEC_ID_MPEGH_3D_AUDIO);
+    assert_eq!(params.extra_data, None);
+}
+
+/// Write the sample tables of a track with the given number of 4-byte samples, each with the given
+/// duration, in a single chunk at the given offset. If provided, only the given sample numbers,
+/// starting at 1, are sync samples.
+fn sample_tables(num_samples: u32, dur: u32, offset: u32, sync: Option<&[u32]>) -> [Vec<u8>; 4] {
+    let be =
+        |values: &[u32]| values.iter().flat_map(|value| value.to_be_bytes()).collect::<Vec<_>>();
+
+    let mut stco = full_atom(b"stco", 0, 0, &be(&[1, offset]));
+
+    if let Some(sync) = sync {
+        stco.extend(full_atom(b"stss", 0, 0, &[be(&[sync.len() as u32]), be(sync)].concat()));
+    }
+
+    [
+        full_atom(b"stts", 0, 0, &be(&[1, num_samples, dur])),
+        full_atom(b"stsc", 0, 0, &be(&[1, 1, num_samples, 1])),
+        full_atom(b"stsz", 0, 0, &be(&[4, num_samples])),
+        stco,
+    ]
+}
+
+/// Build a video-only MP4 file with four 4-byte samples, each with a duration of 1. Only the first
+/// and third samples are sync samples. The bytes of each sample are set to the sample number.
+fn make_sync_samples_mp4() -> Vec<u8> {
+    let mdat = [[0; 4], [1; 4], [2; 4], [3; 4]].concat();
+
+    let traks = |offset| {
+        let sample_tables = sample_tables(4, 1, offset, Some(&[1, 3]));
+        vec![trak(1, b"vide", uhd_sample_entry(&[]), sample_tables)]
+    };
+
+    make_mp4_with_samples(traks, &mdat)
+}
+
+#[test]
+fn skip_unknown_atoms() {
+    let mdat = [[0; 4], [1; 4]].concat();
+
+    // Vendor-specific atoms in the movie, track, and sample table atoms.
+    let traks = |offset| {
+        let mut sample_tables = sample_tables(2, 1, offset, None);
+        sample_tables[0].extend(atom(b"xstb", &[1, 2, 3]));
+
+        let mut trak = trak(1, b"vide", uhd_sample_entry(&[]), sample_tables);
+        trak.extend(full_atom(b"xtrk", 0, 0, &[4, 5, 6]));
+
+        vec![atom(b"xmov", &[]), trak]
+    };
+
+    let mut reader = open_mp4_with_samples(traks, &mdat);
+
+    let mut packets = Vec::new();
+    while let Some(packet) = reader.next_packet().unwrap() {
+        packets.push(packet.data.to_vec());
+    }
+    assert_eq!(packets, vec![vec![0; 4], vec![1; 4]]);
+}
+
+#[test]
+fn skip_padding_atoms() {
+    let mdat = [[0; 4], [1; 4]].concat();
+
+    // Padding atoms between the movie and media data atoms, including a large free atom, like that
+    // reserved by streaming-optimized files to grow the movie atom in-place.
+    let padding =
+        [atom(b"wide", &[]), atom(b"free", &[0; 65536]), atom(b"skip", &[0; 16])].concat();
+
+    // The media data follows the padding.
+    let traks = |offset| {
+        let sample_tables = sample_tables(2, 1, offset + padding.len() as u32, None);
+        vec![trak(1, b"vide", uhd_sample_entry(&[]), sample_tables)]
+    };
+
+    let mp4 = make_mp4_with_samples(traks, &mdat);
+    let (head, mdat) = mp4.split_at(mp4.len() - mdat.len() - 8);
+
+    let mut reader = open_mp4([head, &padding, mdat].concat());
+
+    let mut packets = Vec::new();
+    while let Some(packet) = reader.next_packet().unwrap() {
+        packets.push(packet.data.to_vec());
+    }
+    assert_eq!(packets, vec![vec![0; 4], vec![1; 4]]);
+}
+
+#[test]
+fn read_extended_and_unknown_atom_sizes() {
+    let mdat = [[0; 4], [1; 4]].concat();
+
+    // The file type atom uses a 64-bit size, and the movie atom, at the end of the file, has a size
+    // of 0 to indicate that it extends to the end of the file.
+    let ftyp_payload = [b"isom".as_slice(), &[0; 4], b"isom"].concat();
+    let ftyp = [
+        1u32.to_be_bytes().as_slice(),
+        b"ftyp",
+        &(16 + ftyp_payload.len() as u64).to_be_bytes(),
+        ftyp_payload.as_slice(),
+    ]
+    .concat();
+
+    let mdat = atom(b"mdat", &mdat);
+
+    let mvhd =
+        full_atom(b"mvhd", 0, 0, &[[0; 8].as_slice(), &1000u32.to_be_bytes(), &[0; 84]].concat());
+    let offset = (ftyp.len() + 8) as u32;
+    let trak = trak(1, b"vide", uhd_sample_entry(&[]), sample_tables(2, 1, offset, None));
+    let moov = [0u32.to_be_bytes().as_slice(), b"moov", &mvhd, &trak].concat();
+
+    let mut reader = open_mp4([ftyp, mdat, moov].concat());
+
+    let mut packets = Vec::new();
+    while let Some(packet) = reader.next_packet().unwrap() {
+        packets.push(packet.data.to_vec());
+    }
+    assert_eq!(packets, vec![vec![0; 4], vec![1; 4]]);
+}
+
+#[test]
+fn read_sync_samples() {
+    let mss = MediaSourceStream::new(
+        Box::new(std::io::Cursor::new(make_sync_samples_mp4())),
+        Default::default(),
+    );
+    let mut reader = IsoMp4Reader::try_new(mss, Default::default()).unwrap();
+
+    let mut keyframes = Vec::new();
+    while let Some(packet) = reader.next_packet().unwrap() {
+        keyframes.push(packet.is_keyframe);
+    }
+    assert_eq!(keyframes, vec![true, false, true, false]);
+}
+
+#[test]
+fn report_faststart() {
+    let open = |data: Vec<u8>| {
+        let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+        IsoMp4Reader::try_new(mss, Default::default()).unwrap()
+    };
+
+    // The movie atom precedes the media data.
+    let faststart = make_sync_samples_mp4();
+    assert!(open(faststart.clone()).capabilities().contains(FormatCapabilities::STREAMABLE));
+
+    // Move the movie atom to the end of the file. The sample offsets are not important.
+    let ftyp_len = 20;
+    let mdat_len = 24;
+    let (head, mdat) = faststart.split_at(faststart.len() - mdat_len);
+    let (ftyp, moov) = head.split_at(ftyp_len);
+
+    let reader = open([ftyp, mdat, moov].concat());
+    assert!(!reader.capabilities().contains(FormatCapabilities::STREAMABLE));
+    assert!(reader.capabilities().contains(FormatCapabilities::SEEKABLE));
+}
+
+#[test]
+fn estimate_bitrate_mid_stream() {
+    // Four 4-byte samples, each with a duration of 0.1 s, for a bitrate of 320 bps.
+    let mdat = [[0; 4], [1; 4], [2; 4], [3; 4]].concat();
+
+    let traks = |offset| {
+        let sample_tables = sample_tables(4, 9000, offset, None);
+        vec![trak(1, b"vide", uhd_sample_entry(&[]), sample_tables)]
+    };
+
+    let faststart = make_mp4_with_samples(traks, &mdat);
+
+    // Move the movie atom to the end of the file, after the 20 byte ftyp atom and 24 byte mdat
+    // atom.
+    let data = make_mp4(&traks(28), &mdat);
+    let (head, mdat) = data.split_at(data.len() - 24);
+    let (ftyp, moov) = head.split_at(20);
+
+    for data in [faststart, [ftyp, mdat, moov].concat()] {
+        let mut reader = open_mp4(data);
+
+        assert_eq!(reader.estimate_track_bitrate(1), Some(320));
+        assert_eq!(reader.next_packet().unwrap().unwrap().data[0], 0);
+        assert_eq!(reader.next_packet().unwrap().unwrap().data[0], 1);
+
+        // The reader is returned to its position before estimating the bitrate.
+        assert_eq!(reader.estimate_track_bitrate(1), Some(320));
+        assert_eq!(reader.next_packet().unwrap().unwrap().data[0], 2);
+        assert_eq!(reader.next_packet().unwrap().unwrap().data[0], 3);
+        assert!(reader.next_packet().unwrap().is_none());
+    }
+}
+
+#[test]
+fn seek_to_sync_sample() {
+    let mss = MediaSourceStream::new(
+        Box::new(std::io::Cursor::new(make_sync_samples_mp4())),
+        Default::default(),
+    );
+    let mut reader = IsoMp4Reader::try_new(mss, Default::default()).unwrap();
+
+    let mut seek = |ts: i64| {
+        let seeked = reader
+            .seek(SeekMode::Accurate, SeekTo::Timestamp { ts: Timestamp::new(ts), track_id: 1 })
+            .unwrap();
+        assert_eq!(seeked.required_ts, Timestamp::new(ts));
+
+        let packet = reader.next_packet().unwrap().unwrap();
+        assert_eq!(packet.pts, seeked.actual_ts);
+        assert!(packet.is_keyframe);
+        (seeked.actual_ts.get(), packet.data[0])
+    };
+
+    // Seeking to a sync sample lands on that sample, while seeking to a sample in the middle of a
+    // group of pictures lands on the preceding sync sample.
+    assert_eq!(seek(2), (2, 2));
+    assert_eq!(seek(3), (2, 2));
+    assert_eq!(seek(1), (0, 0));
+    assert_eq!(seek(0), (0, 0));
+}
+
+/// Build an MP4 file with a video track with four 0.1 s samples, of which only the first and third
+/// are sync samples, and an audio track with eight 0.05 s samples. The bytes of each sample are set
+/// to the track ID.
+fn make_av_mp4() -> Vec<u8> {
+    let mdat = [[1; 16].as_slice(), &[2; 32]].concat();
+
+    let traks = |offset| {
+        let video = sample_tables(4, 9000, offset, Some(&[1, 3]));
+        let audio = sample_tables(8, 4500, offset + 16, None);
+        let audio_entry = audio_sample_entry(b"sowt", 2, 44100, &[]);
+
+        vec![trak(1, b"vide", uhd_sample_entry(&[]), video), trak(2, b"soun", audio_entry, audio)]
+    };
+
+    make_mp4_with_samples(traks, &mdat)
+}
+
+#[test]
+fn seek_time_aligns_tracks() {
+    let data = make_av_mp4();
+
+    let mss =
+        MediaSourceStream::new(Box::new(std::io::Cursor::new(data.clone())), Default::default());
+    let mut reader = IsoMp4Reader::try_new(mss, Default::default()).unwrap();
+
+    // Seek to 0.35 s. The video track is seeked to the sync sample at 0.2 s, and the audio track
+    // is seeked to the same time.
+    let seeked = reader.seek_time(Time::try_new(0, 350_000_000).unwrap()).unwrap();
+
+    let seeked: Vec<_> = seeked
+        .iter()
+        .map(|seeked| (seeked.track_id, seeked.required_ts.get(), seeked.actual_ts.get()))
+        .collect();
+    assert_eq!(seeked, vec![(1, 31500, 18000), (2, 31500, 18000)]);
+
+    // The first packet of each track is at the time seeked to.
+    let mut first_pts = Vec::new();
+    while let Some(packet) = reader.next_packet().unwrap() {
+        if !first_pts.iter().any(|&(track_id, _)| track_id == packet.track_id) {
+            first_pts.push((packet.track_id, packet.pts.get()));
+        }
+    }
+    first_pts.sort();
+    assert_eq!(first_pts, vec![(1, 18000), (2, 18000)]);
+
+    // The seek is forwarded when packet durations are backfilled.
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let mut reader =
+        DurationBackfill::new(Box::new(IsoMp4Reader::try_new(mss, Default::default()).unwrap()));
+
+    let seeked = reader.seek_time(Time::try_new(0, 350_000_000).unwrap()).unwrap();
+    assert_eq!(seeked.len(), 2);
+    assert_eq!(reader.next_packet().unwrap().unwrap().pts.get(), 18000);
+}
+
+#[test]
+fn read_duration() {
+    // A video track with a duration of 0.4 s, and an audio track with a duration of 0.5 s.
+    let traks = |offset| {
+        let video = sample_tables(4, 9000, offset, None);
+        let audio = sample_tables(10, 4500, offset + 16, None);
+        let audio_entry = audio_sample_entry(b"sowt", 2, 44100, &[]);
+
+        vec![trak(1, b"vide", uhd_sample_entry(&[]), video), trak(2, b"soun", audio_entry, audio)]
+    };
+
+    let reader = open_mp4_with_samples(traks, &[0; 56]);
+
+    // The overall duration is the duration of the longest track.
+    let longest = reader
+        .tracks()
+        .iter()
+        .filter_map(|track| {
+            track.time_base?.calc_time(track.duration?.timestamp_from(Timestamp::ZERO)?)
+        })
+        .max();
+
+    assert_eq!(reader.duration(), Some(Time::try_new(0, 500_000_000).unwrap()));
+    assert_eq!(reader.duration(), longest);
+}
+
+#[test]
+fn seek_trims_audio() {
+    // An audio track with four samples, each with a duration of 1024.
+    let traks = |offset| {
+        let audio = sample_tables(4, 1024, offset, None);
+        let audio_entry = audio_sample_entry(b"sowt", 2, 44100, &[]);
+
+        vec![trak(1, b"soun", audio_entry, audio)]
+    };
+
+    let mut reader = open_mp4_with_samples(traks, &[0; 16]);
+
+    // Seek to a timestamp in the middle of the second sample.
+    let seeked = reader
+        .seek(SeekMode::Accurate, SeekTo::Timestamp { ts: Timestamp::new(1500), track_id: 1 })
+        .unwrap();
+    assert_eq!(seeked.actual_ts, Timestamp::new(1024));
+
+    // The frames of the first packet preceding the required timestamp are trimmed. The timescale
+    // is 90 kHz, therefore the 476 ticks are trimmed as 233 frames at 44.1 kHz.
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!(packet.pts, Timestamp::new(1024));
+    assert_eq!(packet.trim_start, Duration::new(233));
+    assert_eq!(packet.dur, Duration::new(548));
+
+    // The following packets are not trimmed.
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!(packet.pts, Timestamp::new(2048));
+    assert_eq!(packet.trim_start, Duration::ZERO);
+    assert_eq!(packet.dur, Duration::new(1024));
+}
+
+#[test]
+fn read_aac_num_frames() {
+    // An AAC-LC 44.1 kHz stereo track with three samples. Each sample of 1024 frames is 2090 ticks
+    // at the 90 kHz timescale.
+    let traks = |offset| {
+        let audio = sample_tables(3, 2090, offset, None);
+        let audio_entry = audio_sample_entry(b"mp4a", 2, 44100, &[aac_esds(0x40, &[0x12, 0x10])]);
+
+        vec![trak(1, b"soun", audio_entry, audio)]
+    };
+
+    let mut reader = open_mp4_with_samples(traks, &[0; 12]);
+
+    // The number of frames is derived from the sample duration and the sample rate.
+    let mut num_frames = Vec::new();
+    while let Some(packet) = reader.next_packet().unwrap() {
+        num_frames.push(packet.num_frames);
+    }
+
+    assert_eq!(num_frames, vec![Some(1024); 3]);
+}
+
+/// Read the track ID and timestamp of up to `count` packets.
+fn read_packets(reader: &mut IsoMp4Reader<'_>, count: usize) -> Vec<(u32, i64)> {
+    let mut packets = Vec::new();
+    while packets.len() < count {
+        let Some(packet) = reader.next_packet().unwrap()
+        else {
+            break;
+        };
+        assert!(packet.data.iter().all(|&byte| u32::from(byte) == packet.track_id));
+        packets.push((packet.track_id, packet.pts.get()));
+    }
+    packets
+}
+
+#[test]
+fn read_with_track_filter() {
+    let mss =
+        MediaSourceStream::new(Box::new(std::io::Cursor::new(make_av_mp4())), Default::default());
+    let mut reader = IsoMp4Reader::try_new(mss, Default::default()).unwrap();
+
+    // Packets of unselected tracks are skipped.
+    reader.set_track_filter(&[2]).unwrap();
+    assert!(!reader.is_track_selected(1));
+    assert!(reader.is_track_selected(2));
+    assert_eq!(read_packets(&mut reader, 3), vec![(2, 0), (2, 4500), (2, 9000)]);
+
+    // Clearing the filter selects all tracks again. The skipped video packets are not returned.
+    reader.set_track_filter(&[]).unwrap();
+    assert!(reader.is_track_selected(1));
+    assert_eq!(
+        read_packets(&mut reader, usize::MAX),
+        vec![(2, 13500), (1, 18000), (2, 18000), (2, 22500), (1, 27000), (2, 27000), (2, 31500)]
+    );
+}
+
+#[test]
+fn reset_to_first_packet() {
+    let mss =
+        MediaSourceStream::new(Box::new(std::io::Cursor::new(make_av_mp4())), Default::default());
+    let mut reader = IsoMp4Reader::try_new(mss, Default::default()).unwrap();
+
+    let packets = read_packets(&mut reader, usize::MAX);
+    assert_eq!(packets.len(), 12);
+
+    // After resetting, the media is read again from the first packet.
+    reader.reset().unwrap();
+    assert_eq!(read_packets(&mut reader, usize::MAX), packets);
+}
+
+/// Write a movie fragment (a moof and mdat atom pair) for track 1 with the given sequence number.
+/// Each sample is 4 bytes long, and has a duration of 1000.
+fn fragment(sequence_num: u32, samples: &[[u8; 4]]) -> Vec<u8> {
+    let moof = |data_offset: u32| {
+        // Track ID, default sample duration, and default sample size, with the
+        // default-base-is-moof flag set.
+        let tfhd = full_atom(
+            b"tfhd",
+            0,
+            0x02_0018,
+            &[1u32.to_be_bytes(), 1000u32.to_be_bytes(), 4u32.to_be_bytes()].concat(),
+        );
+        // Sample count and data offset.
+        let trun = full_atom(
+            b"trun",
+            0,
+            0x1,
+            &[(samples.len() as u32).to_be_bytes(), data_offset.to_be_bytes()].concat(),
+        );
+
+        let mfhd = full_atom(b"mfhd", 0, 0, &sequence_num.to_be_bytes());
+        atom(b"moof", &[mfhd, atom(b"traf", &[tfhd, trun].concat())].concat())
+    };
+
+    // The samples follow the moof atom and the mdat atom header.
+    let moof_len = moof(0).len() as u32;
+
+    [moof(moof_len + 8), atom(b"mdat", &samples.concat())].concat()
+}
+
+/// Write a segment index atom for track 1 that references media of the given sizes and durations.
+/// If `is_sidx` is `true`, the reference is to another segment index atom.
+fn sidx(references: &[(usize, u32, bool)]) -> Vec<u8> {
+    sidx_at(0, references)
+}
+
+/// Write a segment index atom for track 1 that starts at the given earliest presentation time, and
+/// references media of the given sizes and durations.
+fn sidx_at(earliest_pts: u32, references: &[(usize, u32, bool)]) -> Vec<u8> {
+    // Reference ID, timescale, earliest presentation time, first offset, reserved, and the
+    // reference count.
+    let mut payload =
+        [1u32.to_be_bytes(), 90000u32.to_be_bytes(), earliest_pts.to_be_bytes(), [0; 4]].concat();
+    payload.extend_from_slice(&[0, 0]);
+    payload.extend_from_slice(&(references.len() as u16).to_be_bytes());
+
+    for &(size, duration, is_sidx) in references {
+        let reference = (u32::from(is_sidx) << 31) | size as u32;
+        payload.extend(reference.to_be_bytes());
+        payload.extend(duration.to_be_bytes());
+        payload.extend(0x9000_0000u32.to_be_bytes());
+    }
+
+    full_atom(b"sidx", 0, 0, &payload)
+}
+
+#[test]
+fn seek_fragmented_with_sidx() {
+    let ftyp = atom(b"ftyp", &[b"iso6".as_slice(), &[0; 4], b"iso6", b"dash"].concat());
+
+    let mvhd =
+        full_atom(b"mvhd", 0, 0, &[[0; 8].as_slice(), &1000u32.to_be_bytes(), &[0; 84]].concat());
+    let trex = full_atom(
+        b"trex",
+        0,
+        0,
+        &[1u32.to_be_bytes(), 1u32.to_be_bytes(), [0; 4], [0; 4], [0; 4]].concat(),
+    );
+    let trak = trak(1, b"vide", uhd_sample_entry(&[]), no_samples());
+    let moov = atom(b"moov", &[mvhd, trak, atom(b"mvex", &trex)].concat());
+
+    // The second fragment is invalid, and would fail to be read if the seek did not skip it.
+    let frags = [
+        fragment(1, &[[0, 0, 0, 0], [0, 0, 0, 1]]),
+        [atom(b"moof", &[]), atom(b"mdat", &[0; 8])].concat(),
+        fragment(3, &[[2, 2, 2, 0], [2, 2, 2, 1]]),
+    ];
+
+    // A single segment index referencing all fragments.
+    let flat = sidx(&[
+        (frags[0].len(), 2000, false),
+        (frags[1].len(), 2000, false),
+        (frags[2].len(), 2000, false),
+    ]);
+
+    // A daisy-chain of segment indices. The first references the first fragment, and the second
+    // segment index. The second references the remaining fragments.
+    let chained = {
+        let second = sidx(&[(frags[1].len(), 2000, false), (frags[2].len(), 2000, false)]);
+        let first = sidx(&[
+            (frags[0].len(), 2000, false),
+            (second.len() + frags[1].len() + frags[2].len(), 4000, true),
+        ]);
+        [first, frags[0].clone(), second].concat()
+    };
+
+    for data in [
+        [ftyp.clone(), moov.clone(), flat, frags.concat()].concat(),
+        [ftyp.clone(), moov.clone(), chained, frags[1..].concat()].concat(),
+    ] {
+        let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+        let mut reader = IsoMp4Reader::try_new(mss, Default::default()).unwrap();
+
+        // Seek to the second sample of the third fragment.
+        let seeked = reader
+            .seek(SeekMode::Accurate, SeekTo::Timestamp { ts: Timestamp::new(5000), track_id: 1 })
+            .unwrap();
+        assert_eq!(seeked.actual_ts, Timestamp::new(5000));
+
+        let packet = reader.next_packet().unwrap().unwrap();
+        assert_eq!(packet.pts, Timestamp::new(5000));
+        assert_eq!(&*packet.data, &[2, 2, 2, 1]);
+
+        // Seek backwards to the second sample of the first fragment.
+        let seeked = reader
+            .seek(SeekMode::Accurate, SeekTo::Timestamp { ts: Timestamp::new(1000), track_id: 1 })
+            .unwrap();
+        assert_eq!(seeked.actual_ts, Timestamp::new(1000));
+
+        let packet = reader.next_packet().unwrap().unwrap();
+        assert_eq!(packet.pts, Timestamp::new(1000));
+        assert_eq!(&*packet.data, &[0, 0, 0, 1]);
+    }
+}
+
+#[test]
+fn seek_fragmented_with_sidx_earliest_pts() {
+    let ftyp = atom(b"ftyp", &[b"iso6".as_slice(), &[0; 4], b"iso6", b"dash"].concat());
+
+    let mvhd =
+        full_atom(b"mvhd", 0, 0, &[[0; 8].as_slice(), &1000u32.to_be_bytes(), &[0; 84]].concat());
+    let trex = full_atom(
+        b"trex",
+        0,
+        0,
+        &[1u32.to_be_bytes(), 1u32.to_be_bytes(), [0; 4], [0; 4], [0; 4]].concat(),
+    );
+    let trak = trak(1, b"vide", uhd_sample_entry(&[]), no_samples());
+    let moov = atom(b"moov", &[mvhd, trak, atom(b"mvex", &trex)].concat());
+
+    let frags =
+        [fragment(1, &[[0, 0, 0, 0], [0, 0, 0, 1]]), fragment(2, &[[1, 1, 1, 0], [1, 1, 1, 1]])];
+
+    // The segment index starts at 2000 instead of 0.
+    let sidx = sidx_at(2000, &[(frags[0].len(), 2000, false), (frags[1].len(), 2000, false)]);
+
+    let data = [ftyp, moov, sidx, frags.concat()].concat();
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let mut reader = IsoMp4Reader::try_new(mss, Default::default()).unwrap();
+
+    // Seek to the second sample of the second fragment.
+    let seeked = reader
+        .seek(SeekMode::Accurate, SeekTo::Timestamp { ts: Timestamp::new(5000), track_id: 1 })
+        .unwrap();
+    assert_eq!(seeked.actual_ts, Timestamp::new(5000));
+
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!(packet.pts, Timestamp::new(5000));
+    assert_eq!(&*packet.data, &[1, 1, 1, 1]);
+}
+
+#[test]
+fn reject_sidx_offset_overflow() {
+    let ftyp = atom(b"ftyp", &[b"iso6".as_slice(), &[0; 4], b"iso6", b"dash"].concat());
+
+    let mvhd =
+        full_atom(b"mvhd", 0, 0, &[[0; 8].as_slice(), &1000u32.to_be_bytes(), &[0; 84]].concat());
+    let trex = full_atom(
+        b"trex",
+        0,
+        0,
+        &[1u32.to_be_bytes(), 1u32.to_be_bytes(), [0; 4], [0; 4], [0; 4]].concat(),
+    );
+    let trak = trak(1, b"vide", uhd_sample_entry(&[]), no_samples());
+    let moov = atom(b"moov", &[mvhd, trak, atom(b"mvex", &trex)].concat());
+
+    // A version 1 segment index with two references, where the second reference starts past the
+    // largest possible position.
+    let sidx = |first_offset: u64| {
+        let mut payload = [1u32.to_be_bytes(), 90000u32.to_be_bytes()].concat();
+        payload.extend(0u64.to_be_bytes());
+        payload.extend(first_offset.to_be_bytes());
+        payload.extend([0, 0, 0, 2]);
+
+        for _ in 0..2 {
+            payload.extend(4u32.to_be_bytes());
+            payload.extend(1000u32.to_be_bytes());
+            payload.extend(0x9000_0000u32.to_be_bytes());
+        }
+
+        full_atom(b"sidx", 1, 0, &payload)
+    };
+
+    let anchor = (ftyp.len() + moov.len() + sidx(0).len()) as u64;
+
+    let data = [ftyp, moov, sidx(u64::MAX - anchor - 1)].concat();
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    assert!(IsoMp4Reader::try_new(mss, Default::default()).is_err());
+}
+
+#[test]
+fn read_fragmented_trex_order() {
+    let ftyp = atom(b"ftyp", &[b"iso6".as_slice(), &[0; 4], b"iso6"].concat());
+
+    let mvhd =
+        full_atom(b"mvhd", 0, 0, &[[0; 8].as_slice(), &1000u32.to_be_bytes(), &[0; 84]].concat());
+
+    // Track ID, sample description index, and the default sample duration, size, and flags.
+    let trex = |track_id: u32, dur: u32, size: u32| {
+        full_atom(
+            b"trex",
+            0,
+            0,
+            &[
+                track_id.to_be_bytes(),
+                1u32.to_be_bytes(),
+                dur.to_be_bytes(),
+                size.to_be_bytes(),
+                [0; 4],
+            ]
+            .concat(),
+        )
+    };
+
+    // The track extends atoms are in the opposite order of the tracks.
+    let traks = [
+        trak(1, b"vide", uhd_sample_entry(&[]), no_samples()),
+        trak(2, b"vide", uhd_sample_entry(&[]), no_samples()),
+    ];
+    let mvex = atom(b"mvex", &[trex(2, 500, 2), trex(1, 1000, 4)].concat());
+    let moov = atom(b"moov", &[mvhd, traks.concat(), mvex].concat());
+
+    // A fragment for track 1 that uses the defaults from the track extends atom.
+    let moof = |data_offset: u32| {
+        let tfhd = full_atom(b"tfhd", 0, 0x02_0000, &1u32.to_be_bytes());
+        let trun =
+            full_atom(b"trun", 0, 0x1, &[2u32.to_be_bytes(), data_offset.to_be_bytes()].concat());
+        let mfhd = full_atom(b"mfhd", 0, 0, &1u32.to_be_bytes());
+        atom(b"moof", &[mfhd, atom(b"traf", &[tfhd, trun].concat())].concat())
+    };
+    let moof_len = moof(0).len() as u32;
+    let frag = [moof(moof_len + 8), atom(b"mdat", &[0, 0, 0, 0, 0, 0, 0, 1])].concat();
+
+    let data = [ftyp, moov, frag].concat();
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let mut reader = IsoMp4Reader::try_new(mss, Default::default()).unwrap();
+
+    let mut packets = Vec::new();
+    while let Some(packet) = reader.next_packet().unwrap() {
+        packets.push((packet.track_id, packet.pts.get(), packet.data.to_vec()));
+    }
+
+    assert_eq!(packets, vec![(1, 0, vec![0, 0, 0, 0]), (1, 1000, vec![0, 0, 0, 1])]);
+}
+
+#[test]
+fn reset_fragmented_to_first_packet() {
+    let ftyp = atom(b"ftyp", &[b"iso6".as_slice(), &[0; 4], b"iso6"].concat());
+
+    let mvhd =
+        full_atom(b"mvhd", 0, 0, &[[0; 8].as_slice(), &1000u32.to_be_bytes(), &[0; 84]].concat());
+    let trex = full_atom(
+        b"trex",
+        0,
+        0,
+        &[1u32.to_be_bytes(), 1u32.to_be_bytes(), [0; 4], [0; 4], [0; 4]].concat(),
+    );
+    let trak = trak(1, b"vide", uhd_sample_entry(&[]), no_samples());
+    let moov = atom(b"moov", &[mvhd, trak, atom(b"mvex", &trex)].concat());
+
+    let frags = [fragment(1, &[[0, 0, 0, 0], [0, 0, 0, 1]]), fragment(2, &[[1, 1, 1, 0]])];
+
+    let data = [ftyp, moov, frags.concat()].concat();
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let mut reader = IsoMp4Reader::try_new(mss, Default::default()).unwrap();
+
+    let read_all = |reader: &mut IsoMp4Reader<'_>| {
+        let mut packets = Vec::new();
+        while let Some(packet) = reader.next_packet().unwrap() {
+            packets.push((packet.pts.get(), packet.data.to_vec()));
+        }
+        packets
+    };
+
+    let packets = read_all(&mut reader);
+    assert_eq!(
+        packets,
+        vec![(0, vec![0, 0, 0, 0]), (1000, vec![0, 0, 0, 1]), (2000, vec![1, 1, 1, 0])]
+    );
+
+    // After resetting, the fragments are read again from the first packet.
+    reader.reset().unwrap();
+    assert_eq!(read_all(&mut reader), packets);
+}
+
+/// Build an MP4 file with a timecode track with the given flags, nominal frame rate, and frame
+/// count of the first frame.
+fn make_timecode_mp4(flags: u32, num_frames: u8, count: u32) -> Vec<u8> {
+    // The sample entry, reserved, flags, timescale, frame duration, number of frames, and
+    // reserved.
+    let mut tmcd = vec![0; 12];
+    tmcd.extend_from_slice(&flags.to_be_bytes());
+    tmcd.extend_from_slice(&(u32::from(num_frames) * 1000).to_be_bytes());
+    tmcd.extend_from_slice(&1000u32.to_be_bytes());
+    tmcd.extend_from_slice(&[num_frames, 0]);
+
+    let traks = |offset| vec![trak(1, b"tmcd", atom(b"tmcd", &tmcd), one_sample(4, offset))];
+
+    make_mp4_with_samples(traks, &count.to_be_bytes())
+}
+
+fn read_start_timecode(data: Vec<u8>) -> Option<String> {
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let reader = IsoMp4Reader::try_new(mss, Default::default()).unwrap();
+
+    reader.media_info().start_timecode.map(|timecode| timecode.to_string())
+}
+
+#[test]
+fn read_timecode() {
+    // 01:00:00:00 at 25 fps.
+    assert_eq!(read_start_timecode(make_timecode_mp4(0, 25, 90_000)), Some("01:00:00:00".into()));
+
+    // 01:00:00;00 at 29.97 fps, with drop-frame counting.
+    assert_eq!(read_start_timecode(make_timecode_mp4(1, 30, 107_892)), Some("01:00:00;00".into()));
+
+    // A tick counter is not a timecode.
+    assert_eq!(read_start_timecode(make_timecode_mp4(8, 25, 90_000)), None);
+}
+
+#[test]
+fn read_hdr_metadata() {
+    // BT.2020 primaries (green, blue, red), D65 white point, and a 1000 to 0.0001 cd/m^2 mastering
+    // display luminance, in mdcv units.
+    let mut mdcv = Vec::new();
+    for value in [8500u16, 39850, 6550, 2300, 35400, 14600, 15635, 16450] {
+        mdcv.extend_from_slice(&value.to_be_bytes());
+    }
+    mdcv.extend_from_slice(&10_000_000u32.to_be_bytes());
+    mdcv.extend_from_slice(&1u32.to_be_bytes());
+
+    let clli = [1000u16.to_be_bytes(), 400u16.to_be_bytes()].concat();
+
+    let params = read_video_params(make_uhd_mp4(&[atom(b"mdcv", &mdcv), atom(b"clli", &clli)]));
+
+    assert_eq!((params.width, params.height), (Some(3840), Some(2160)));
+
+    let mastering_display = params.mastering_display.unwrap();
+    assert_eq!(mastering_display.primaries, [(0.708, 0.292), (0.17, 0.797), (0.131, 0.046)]);
+    assert_eq!(mastering_display.white_point, (0.3127, 0.329));
+    assert_eq!(mastering_display.max_luminance, 1000.0);
+    assert_eq!(mastering_display.min_luminance, 0.0001);
+
+    assert_eq!(
+        params.content_light_level,
+        Some(ContentLightLevel { max_cll: 1000, max_fall: 400 })
+    );
+}
+
+#[test]
+fn read_hdr_metadata_full_atoms() {
+    // Red, green, and blue primaries, and the white point, as 0.16 fixed-point values, followed by
+    // the maximum (24.8) and minimum (18.14) luminance.
+    let mut smdm = Vec::new();
+    for value in [0x8000u16, 0x4000, 0x2000, 0xc000, 0x1000, 0x0800, 0x5000, 0x5400] {
+        smdm.extend_from_slice(&value.to_be_bytes());
+    }
+    smdm.extend_from_slice(&(1000u32 << 8).to_be_bytes());
+    smdm.extend_from_slice(&(1u32 << 13).to_be_bytes());
+
+    let coll = [600u16.to_be_bytes(), 200u16.to_be_bytes()].concat();
+
+    let params = read_video_params(make_uhd_mp4(&[
+        full_atom(b"SmDm", 0, 0, &smdm),
+        full_atom(b"CoLL", 0, 0, &coll),
+    ]));
+
+    let mastering_display = params.mastering_display.unwrap();
+    assert_eq!(mastering_display.primaries, [(0.5, 0.25), (0.125, 0.75), (0.0625, 0.03125)]);
+    assert_eq!(mastering_display.white_point, (0.3125, 0.328125));
+    assert_eq!(mastering_display.max_luminance, 1000.0);
+    assert_eq!(mastering_display.min_luminance, 0.5);
+
+    assert_eq!(params.content_light_level, Some(ContentLightLevel { max_cll: 600, max_fall: 200 }));
+}
+
+#[test]
+fn read_rotation() {
+    let read_rotation = |matrix: [i32; 9]| {
+        let trak = trak_with_tkhd(1, b"vide", uhd_sample_entry(&[]), no_samples(), matrix, 0);
+        read_video_params(make_mp4(&[trak], &[])).rotation_degrees
+    };
+
+    // Video recorded by a phone held upright is rotated 90 degrees.
+    assert_eq!(read_rotation([0, 0x10000, 0, -0x10000, 0, 0, 0x870000, 0, 0x40000000]), 90);
+    assert_eq!(read_rotation([-0x10000, 0, 0, 0, -0x10000, 0, 0, 0, 0x40000000]), 180);
+    assert_eq!(read_rotation([0, -0x10000, 0, 0x10000, 0, 0, 0, 0, 0x40000000]), 270);
+    assert_eq!(read_rotation(IDENTITY_MATRIX), 0);
+
+    // A mirroring matrix is not a rotation.
+    assert_eq!(read_rotation([-0x10000, 0, 0, 0, 0x10000, 0, 0, 0, 0x40000000]), 0);
+}
+
+#[test]
+fn read_truncated_tkhd() {
+    let trak = trak_with_tkhd(1, b"vide", uhd_sample_entry(&[]), no_samples(), IDENTITY_MATRIX, 0);
+
+    // Truncate the track header after the volume, dropping the reserved field, the matrix, width,
+    // and height. The track header is the first atom in the track atom.
+    let tkhd_len = 46u32;
+    let tkhd = [&tkhd_len.to_be_bytes(), &trak[12..8 + tkhd_len as usize]].concat();
+    let trak = atom(b"trak", &[tkhd.as_slice(), &trak[8 + 92..]].concat());
+
+    assert_eq!(read_video_params(make_mp4(&[trak], &[])).rotation_degrees, 0);
+}
+
+#[test]
+fn read_spherical_video_metadata() {
+    let fixed = |value: i32| (value << 16).to_be_bytes();
+
+    // Google spherical video (v2) metadata for top-bottom stereoscopic, equirectangular video.
+    let st3d = full_atom(b"st3d", 0, 0, &[1]);
+
+    let prhd = full_atom(b"prhd", 0, 0, &[fixed(90), fixed(-30), fixed(0)].concat());
+    let equi = full_atom(b"equi", 0, 0, &[0; 16]);
+    let sv3d = atom(
+        b"sv3d",
+        &[
+            full_atom(b"svhd", 0, 0, b"Spatial Media Metadata Injector\0"),
+            atom(b"proj", &[prhd, equi].concat()),
+        ]
+        .concat(),
+    );
+
+    let params = read_video_params(make_uhd_mp4(&[st3d, sv3d]));
+
+    assert_eq!(params.stereo_mode, Some(StereoMode::TopBottom));
+
+    let projection = params.projection.unwrap();
+    assert_eq!(projection.projection_type, ProjectionType::Equirectangular);
+    // The projection-specific data includes the version and flags.
+    assert_eq!(projection.private.as_deref(), Some([0; 20].as_slice()));
+    assert_eq!((projection.yaw, projection.pitch, projection.roll), (90.0, -30.0, 0.0));
+}
+
+/// Write a H.264 visual sample entry with a size of 1920x1088, and a decoder configuration record
+/// with 4 byte NAL unit length prefixes and the given parameter sets.
+fn avc_sample_entry(sps: &[u8], pps: &[u8]) -> Vec<u8> {
+    // Version, profile, profile compatibility, level, length size, and the parameter sets.
+    let avcc = atom(
+        b"avcC",
+        &[
+            [1, 0x64, 0x00, 0x28, 0xff, 0xe1].as_slice(),
+            &(sps.len() as u16).to_be_bytes(),
+            sps,
+            &[1],
+            &(pps.len() as u16).to_be_bytes(),
+            pps,
+        ]
+        .concat(),
+    );
+
+    let mut avc1 = vec![0; 24];
+    avc1.extend_from_slice(&1920u16.to_be_bytes());
+    avc1.extend_from_slice(&1088u16.to_be_bytes());
+    avc1.extend_from_slice(&[0; 50]);
+    avc1.extend(avcc);
+
+    atom(b"avc1", &avc1)
+}
+
+/// A High profile, level 4.0, 1920x1080 sequence parameter set with VUI timing information for
+/// 23.976 fps. The coded height of 1088 is cropped to 1080.
+const AVC_SPS_1080P: [u8; 26] = [
+    0x67, 0x64, 0x00, 0x28, 0xac, 0xd9, 0x40, 0x78, 0x02, 0x27, 0xe5, 0xc0, 0x5a, 0x80, 0x80, 0x80,
+    0xa0, 0x00, 0x00, 0x7d, 0x20, 0x00, 0x17, 0x70, 0x10, 0x80,
+];
+
+/// A picture parameter set.
+const AVC_PPS: [u8; 6] = [0x68, 0xeb, 0xe3, 0xcb, 0x22, 0xc0];
+
+#[test]
+fn read_avc_sps_size_and_frame_rate() {
+    // The sample entry signals the uncropped 1920x1088 size.
+    let sample_entry = avc_sample_entry(&AVC_SPS_1080P, &AVC_PPS);
+
+    let params = read_video_params(make_mp4(&[trak(1, b"vide", sample_entry, no_samples())], &[]));
+
+    // The cropped size signalled by the sequence parameter set is reported.
+    assert_eq!((params.width, params.height), (Some(1920), Some(1080)));
+    assert_eq!(params.container_size, Some((1920, 1088)));
+    assert_eq!(params.frame_rate, FrameRate::try_new(24000, 1001));
+    assert_eq!(params.level, Some(40));
+}
+
+#[test]
+fn read_avc_annex_b() {
+    // A SEI and an IDR slice NAL unit, each with a 4 byte length prefix.
+    let sample = [0, 0, 0, 3, 0x06, 0x05, 0x01, 0, 0, 0, 2, 0x65, 0x88];
+
+    let traks = |offset| {
+        let sample_entry = avc_sample_entry(&AVC_SPS_1080P, &AVC_PPS);
+        vec![trak(1, b"vide", sample_entry, one_sample(sample.len() as u32, offset))]
+    };
+
+    let data = make_mp4_with_samples(traks, &sample);
+
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let opts = FormatOptions::default().nal_format(NalFormat::AnnexB);
+    let mut reader = IsoMp4Reader::try_new(mss, opts).unwrap();
+
+    // The parameter sets precede the NAL units of the keyframe, and each NAL unit is prefixed by a
+    // start code.
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert!(packet.is_keyframe);
+    assert_eq!(
+        &*packet.data,
+        [
+            [0, 0, 0, 1].as_slice(),
+            &AVC_SPS_1080P,
+            &[0, 0, 0, 1],
+            &AVC_PPS,
+            &[0, 0, 0, 1, 0x06, 0x05, 0x01],
+            &[0, 0, 0, 1, 0x65, 0x88],
+        ]
+        .concat()
+    );
+}
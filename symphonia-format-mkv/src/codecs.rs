@@ -5,31 +5,42 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use std::num::NonZeroU64;
+
 use extra_data::{
     VIDEO_EXTRA_DATA_ID_AV1_DECODER_CONFIG, VIDEO_EXTRA_DATA_ID_AVC_DECODER_CONFIG,
     VIDEO_EXTRA_DATA_ID_HEVC_DECODER_CONFIG, VIDEO_EXTRA_DATA_ID_VP9_DECODER_CONFIG,
 };
 use log::warn;
 
+use symphonia_common::dolby::audio::truehd::MajorSync;
+use symphonia_common::dts::audio::dca;
+use symphonia_common::microsoft::audio::WaveFormatEx;
+use symphonia_common::microsoft::video::{BitmapInfoHeader, vc1};
+use symphonia_common::mpeg::audio::AudioSpecificConfig;
 use symphonia_common::mpeg::video::{
     AVCDecoderConfigurationRecord, HEVCDecoderConfigurationRecord,
 };
 use symphonia_common::xiph::audio::flac::{MetadataBlockHeader, MetadataBlockType, StreamInfo};
+use symphonia_common::xiph::audio::opus::OPUS_MAX_FRAMES_PER_PACKET;
 use symphonia_core::audio::Channels;
 use symphonia_core::audio::sample::SampleFormat;
 use symphonia_core::codecs::audio::AudioCodecParameters;
+use symphonia_core::codecs::audio::well_known::profiles::CODEC_PROFILE_TRUEHD_ATMOS;
 use symphonia_core::codecs::audio::well_known::{CODEC_ID_FLAC, CODEC_ID_VORBIS};
 use symphonia_core::codecs::audio::{AudioCodecId, well_known::*};
 use symphonia_core::codecs::subtitle::{SubtitleCodecId, SubtitleCodecParameters, well_known::*};
 use symphonia_core::codecs::video::{
-    VIDEO_EXTRA_DATA_ID_NULL, VideoCodecId, VideoCodecParameters, VideoExtraData, well_known::*,
+    Projection, ProjectionType, VIDEO_EXTRA_DATA_ID_NULL, VideoCodecId, VideoCodecParameters,
+    VideoExtraData, well_known::*,
 };
 use symphonia_core::codecs::{CodecId, CodecParameters, CodecProfile};
 use symphonia_core::errors::{Error, Result, decode_error};
 use symphonia_core::io::{BufReader, ReadBytes};
+use symphonia_core::units::FrameRate;
 
 use crate::lacing::read_xiph_sizes;
-use crate::segment::TrackElement;
+use crate::segment::{ProjectionElement, TrackElement};
 
 pub(crate) fn make_track_codec_params(track: TrackElement) -> Result<Option<CodecParameters>> {
     // Get the codec ID for the track.
@@ -58,6 +69,18 @@ fn make_audio_codec_params(
         }
     };
 
+    // Audio tracks using the ACM compatibility codec ID embed a WAVEFORMATEX structure in the
+    // codec private data.
+    let wave_format = match (track.codec_id.as_str(), &track.codec_private) {
+        ("A_MS/ACM", Some(codec_private)) => Some(WaveFormatEx::read(codec_private)?),
+        _ => None,
+    };
+
+    // Prefer the bit depth from the audio element, but fallback to the WAVEFORMATEX.
+    let bit_depth = audio.bit_depth.or_else(|| {
+        wave_format.as_ref().and_then(|wfx| NonZeroU64::new(u64::from(wfx.bits_per_sample)))
+    });
+
     let mut codec_params = AudioCodecParameters::new();
 
     codec_params.for_codec(id);
@@ -69,7 +92,22 @@ fn make_audio_codec_params(
     codec_params.with_sample_rate(audio.sampling_frequency.round() as u32);
     codec_params.with_channels(Channels::Discrete(audio.channels.get() as u16));
 
-    let format = audio.bit_depth.and_then(|bits| match bits.get() {
+    // The maximum number of frames per packet is known for some codecs.
+    let max_frames_per_packet = match id {
+        CODEC_ID_AAC => track
+            .codec_private
+            .as_deref()
+            .and_then(|buf| AudioSpecificConfig::read(buf).ok())
+            .and_then(|asc| asc.frames_per_packet()),
+        CODEC_ID_OPUS => Some(OPUS_MAX_FRAMES_PER_PACKET),
+        _ => None,
+    };
+
+    if let Some(frames) = max_frames_per_packet {
+        codec_params.with_max_frames_per_packet(frames);
+    }
+
+    let format = bit_depth.and_then(|bits| match bits.get() {
         8 => Some(SampleFormat::S8),
         16 => Some(SampleFormat::S16),
         24 => Some(SampleFormat::S24),
@@ -81,11 +119,17 @@ fn make_audio_codec_params(
         codec_params.with_sample_format(format);
     }
 
-    if let Some(bits) = audio.bit_depth {
+    if let Some(bits) = bit_depth {
         codec_params.with_bits_per_sample(bits.get() as u32);
     }
 
-    if let Some(codec_private) = track.codec_private {
+    if let Some(wfx) = wave_format {
+        // The extra data follows the WAVEFORMATEX structure.
+        if !wfx.extra_data.is_empty() {
+            codec_params.with_extra_data(wfx.extra_data);
+        }
+    }
+    else if let Some(codec_private) = track.codec_private {
         let extra_data = match id {
             CODEC_ID_VORBIS => vorbis_extra_data_from_codec_private(&codec_private)?,
             CODEC_ID_FLAC => flac_extra_data_from_codec_private(&codec_private)?,
@@ -98,6 +142,52 @@ fn make_audio_codec_params(
     Ok(Some(CodecParameters::Audio(codec_params)))
 }
 
+/// Get the number of audio frames in each frame of an audio track, if the codec has a constant
+/// number of audio frames per frame.
+pub(crate) fn constant_frames_per_packet(params: &AudioCodecParameters) -> Option<u64> {
+    match params.codec {
+        // The number of frames per packet is read from the audio specific configuration.
+        CODEC_ID_AAC => params.max_frames_per_packet,
+        _ => None,
+    }
+}
+
+/// Returns `true` if the codec parameters of an audio track are only complete once the first frame
+/// of the track is read.
+pub(crate) fn is_first_frame_required(params: &AudioCodecParameters) -> bool {
+    matches!(params.codec, CODEC_ID_DCA | CODEC_ID_TRUEHD)
+}
+
+/// Complete the codec parameters of an audio track from the first frame of the track.
+pub(crate) fn apply_first_frame(params: &mut AudioCodecParameters, frame: &[u8]) -> Result<()> {
+    if params.codec == CODEC_ID_TRUEHD {
+        let major_sync = MajorSync::read(frame)?;
+
+        params
+            .with_sample_rate(major_sync.format_info.sample_rate)
+            .with_channels(major_sync.format_info.channels);
+
+        if major_sync.has_16ch_presentation {
+            params.with_profile(CODEC_PROFILE_TRUEHD_ATMOS);
+        }
+    }
+    else if params.codec == CODEC_ID_DCA {
+        let frame_info = dca::FrameInfo::read(frame)?;
+
+        if let Some(sample_rate) = frame_info.sample_rate {
+            params.with_sample_rate(sample_rate);
+        }
+
+        if let Some(channels) = frame_info.channels {
+            params.with_channels(channels);
+        }
+
+        params.with_profile(frame_info.profile.into());
+    }
+
+    Ok(())
+}
+
 fn make_video_codec_params(
     id: VideoCodecId,
     profile: Option<CodecProfile>,
@@ -113,16 +203,15 @@ fn make_video_codec_params(
         }
     };
 
+    let width = u16::try_from(video.pixel_width.get())
+        .map_err(|_| Error::Unsupported("mkv: video width too large"))?;
+    let height = u16::try_from(video.pixel_height.get())
+        .map_err(|_| Error::Unsupported("mkv: video height too large"))?;
+
     let mut codec_params = VideoCodecParameters {
         codec: id,
-        width: Some(
-            u16::try_from(video.pixel_width.get())
-                .map_err(|_| Error::Unsupported("mkv: video width too large"))?,
-        ),
-        height: Some(
-            u16::try_from(video.pixel_height.get())
-                .map_err(|_| Error::Unsupported("mkv: video height too large"))?,
-        ),
+        width: Some(width),
+        height: Some(height),
         ..Default::default()
     };
 
@@ -134,15 +223,78 @@ fn make_video_codec_params(
         codec_params.with_level(level);
     }
 
+    if let Some(projection) = video.projection.and_then(make_projection) {
+        codec_params.with_projection(projection);
+    }
+
     if let Some(codec_private) = track.codec_private {
-        let extra_data_id = match id {
-            CODEC_ID_H264 => VIDEO_EXTRA_DATA_ID_AVC_DECODER_CONFIG,
-            CODEC_ID_HEVC => VIDEO_EXTRA_DATA_ID_HEVC_DECODER_CONFIG,
-            CODEC_ID_VP9 => VIDEO_EXTRA_DATA_ID_VP9_DECODER_CONFIG,
-            CODEC_ID_AV1 => VIDEO_EXTRA_DATA_ID_AV1_DECODER_CONFIG,
-            _ => VIDEO_EXTRA_DATA_ID_NULL,
-        };
-        codec_params.add_extra_data(VideoExtraData { id: extra_data_id, data: codec_private });
+        if track.codec_id == "V_MS/VFW/FOURCC" {
+            // Video tracks using the VfW compatibility codec ID embed a BITMAPINFOHEADER structure
+            // in the codec private data. The extra data follows the structure, and is in the same
+            // format as for AVI.
+            let extra_data = BitmapInfoHeader::read(&codec_private)?.extra_data;
+
+            // VC-1 Advanced profile extra data contains the sequence header.
+            if id == CODEC_ID_VC1 {
+                if let Ok(seq) = vc1::SequenceHeader::find(&extra_data) {
+                    codec_params
+                        .with_profile(seq.profile)
+                        .with_level(seq.level)
+                        .with_codec_size(seq.width, seq.height);
+
+                    if let Some(frame_rate) = seq.frame_rate {
+                        codec_params.with_frame_rate(frame_rate);
+                    }
+                }
+            }
+
+            // VC-1 Simple and Main profile extra data is the sequence layer data.
+            if id == CODEC_ID_WMV3 {
+                if let Ok(seq) = vc1::SequenceLayerData::read(&extra_data) {
+                    codec_params.with_profile(seq.profile);
+                }
+            }
+
+            if !extra_data.is_empty() {
+                codec_params.add_extra_data(VideoExtraData {
+                    id: VIDEO_EXTRA_DATA_ID_NULL,
+                    data: extra_data,
+                });
+            }
+        }
+        else {
+            // The codec private data of H.264 and HEVC tracks is a decoder configuration record, as
+            // defined in ISO/IEC 14496-15.
+            match read_decoder_config(id, &codec_private) {
+                Ok(Some(config)) => {
+                    codec_params.with_profile(config.profile).with_level(config.level);
+
+                    // The length prefix of a NAL unit may only be 1, 2, or 4 bytes.
+                    if config.nal_length_size == 3 {
+                        warn!("invalid nal unit length size of 3 bytes");
+                    }
+
+                    if let Some((sps_width, sps_height, frame_rate)) = config.sps {
+                        codec_params.with_codec_size(sps_width, sps_height);
+
+                        if let Some(frame_rate) = frame_rate {
+                            codec_params.with_frame_rate(frame_rate);
+                        }
+                    }
+                }
+                Ok(None) => (),
+                Err(err) => warn!("invalid decoder configuration record: {}", err),
+            }
+
+            let extra_data_id = match id {
+                CODEC_ID_H264 => VIDEO_EXTRA_DATA_ID_AVC_DECODER_CONFIG,
+                CODEC_ID_HEVC => VIDEO_EXTRA_DATA_ID_HEVC_DECODER_CONFIG,
+                CODEC_ID_VP9 => VIDEO_EXTRA_DATA_ID_VP9_DECODER_CONFIG,
+                CODEC_ID_AV1 => VIDEO_EXTRA_DATA_ID_AV1_DECODER_CONFIG,
+                _ => VIDEO_EXTRA_DATA_ID_NULL,
+            };
+            codec_params.add_extra_data(VideoExtraData { id: extra_data_id, data: codec_private });
+        }
     }
 
     for block in track.block_addition_mappings {
@@ -154,6 +306,67 @@ fn make_video_codec_params(
     Ok(Some(CodecParameters::Video(codec_params)))
 }
 
+/// The decoder configuration of a H.264 or HEVC track.
+struct DecoderConfig {
+    profile: CodecProfile,
+    level: u32,
+    /// The size, in bytes, of the length prefix of each NAL unit in a frame.
+    nal_length_size: usize,
+    /// The width, height, and frame rate signalled by the sequence parameter set, if present.
+    sps: Option<(u32, u32, Option<FrameRate>)>,
+}
+
+/// Read the decoder configuration record of a H.264 or HEVC track. Returns `None` for all other
+/// codecs.
+fn read_decoder_config(id: VideoCodecId, codec_private: &[u8]) -> Result<Option<DecoderConfig>> {
+    let config = match id {
+        CODEC_ID_H264 => {
+            let record = AVCDecoderConfigurationRecord::read(codec_private)?;
+
+            DecoderConfig {
+                profile: record.profile,
+                level: record.level,
+                nal_length_size: record.nal_length_size,
+                sps: record.sps.map(|sps| (sps.width, sps.height, sps.frame_rate)),
+            }
+        }
+        CODEC_ID_HEVC => {
+            let record = HEVCDecoderConfigurationRecord::read(codec_private)?;
+
+            DecoderConfig {
+                profile: record.profile,
+                level: record.level,
+                nal_length_size: record.nal_length_size,
+                sps: record.sps.map(|sps| (sps.width, sps.height, sps.frame_rate)),
+            }
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(config))
+}
+
+fn make_projection(projection: ProjectionElement) -> Option<Projection> {
+    let projection_type = match projection.projection_type {
+        0 => ProjectionType::Rectangular,
+        1 => ProjectionType::Equirectangular,
+        2 => ProjectionType::Cubemap,
+        3 => ProjectionType::Mesh,
+        other => {
+            warn!("unknown video projection type {other}");
+            return None;
+        }
+    };
+
+    Some(Projection {
+        projection_type,
+        private: projection.private,
+        yaw: projection.pose_yaw as f32,
+        pitch: projection.pose_pitch as f32,
+        roll: projection.pose_roll as f32,
+    })
+}
+
 fn make_subtitle_codec_params(
     id: SubtitleCodecId,
     track: TrackElement,
@@ -295,7 +508,12 @@ fn get_codec_id(track: &TrackElement) -> Option<CodecId> {
             64 => CodecId::Audio(CODEC_ID_PCM_F64LE),
             _ => return None,
         },
-        // A_MS/ACM
+        "A_MS/ACM" => {
+            // The audio codec is identified by the format tag of the WAVEFORMATEX structure in the
+            // codec private data.
+            let codec_private = track.codec_private.as_ref()?;
+            CodecId::Audio(WaveFormatEx::read(codec_private).ok()?.codec_id()?)
+        }
         // A_QUICKTIME
         // A_QUICKTIME/QDMC
         // A_QUICKTIME/QDM2
@@ -318,7 +536,12 @@ fn get_codec_id(track: &TrackElement) -> Option<CodecId> {
         "V_AV1" => CodecId::Video(CODEC_ID_AV1),
         "V_AVS2" => CodecId::Video(CODEC_ID_AVS2),
         "V_AVS3" => CodecId::Video(CODEC_ID_AVS3),
-        // V_MS/VFW/FOURCC WVC1 (VC-1)
+        "V_MS/VFW/FOURCC" => {
+            // The video codec is identified by the compression FourCC of the BITMAPINFOHEADER
+            // structure in the codec private data.
+            let codec_private = track.codec_private.as_ref()?;
+            CodecId::Video(BitmapInfoHeader::read(codec_private).ok()?.codec_id()?)
+        }
         // V_UNCOMPRESSED
         // V_QUICKTIME
         // V_PRORES
@@ -367,24 +590,6 @@ fn get_codec_profile_and_level(track: &TrackElement) -> (Option<CodecProfile>, O
         // Video Codecs
         "V_MPEG4/ISO/SP" => (Some(CODEC_PROFILE_MPEG4_SIMPLE), None),
         "V_MPEG4/ISO/ASP" => (Some(CODEC_PROFILE_MPEG4_ADVANCED_SIMPLE), None),
-        "V_MPEG4/ISO/AVC" | "V_MPEG4/ISO/AP" => {
-            // Parse AVCDecoderConfigurationRecord extra data to acquire the profile and level.
-            track
-                .codec_private
-                .as_ref()
-                .and_then(|buf| AVCDecoderConfigurationRecord::read(buf).ok())
-                .map(|cfg| (Some(cfg.profile), Some(cfg.level)))
-                .unwrap_or_else(|| (None, None))
-        }
-        "V_MPEGH/ISO/HEVC" => {
-            // Parse HevcDecoderConfigurationRecord extra data to acquire the profile and level.
-            track
-                .codec_private
-                .as_ref()
-                .and_then(|buf| HEVCDecoderConfigurationRecord::read(buf).ok())
-                .map(|cfg| (Some(cfg.profile), Some(cfg.level)))
-                .unwrap_or_else(|| (None, None))
-        }
 
         // Other Codecs
         _ => (None, None),
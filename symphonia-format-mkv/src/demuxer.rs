@@ -5,34 +5,59 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use std::borrow::Cow;
 use std::collections::{HashMap, VecDeque};
 use std::convert::TryFrom;
 use std::num::NonZero;
-
-use symphonia_core::errors::{Error, Result, SeekErrorKind, seek_error, unsupported_error};
+use std::sync::Arc;
+
+use symphonia_common::mpeg::video::make_annex_b_converter;
+use symphonia_common::mpeg::video::nal::AnnexBConverter;
+use symphonia_core::codecs::CodecParameters;
+use symphonia_core::codecs::audio::well_known::{CODEC_ID_OPUS, CODEC_ID_VORBIS};
+use symphonia_core::codecs::subtitle::well_known::CODEC_ID_WEBVTT;
+use symphonia_core::codecs::video::well_known::{CODEC_ID_AV1, CODEC_ID_VP8, CODEC_ID_VP9};
+use symphonia_core::errors::{
+    Error, Result, SeekErrorKind, decode_error, seek_error, unsupported_error,
+};
 use symphonia_core::formats::prelude::*;
 use symphonia_core::formats::probe::{ProbeFormatData, ProbeableFormat, Score, Scoreable};
-use symphonia_core::formats::well_known::FORMAT_ID_MKV;
+use symphonia_core::formats::well_known::{FORMAT_ID_MKV, FORMAT_ID_WEBM};
 use symphonia_core::io::*;
 use symphonia_core::meta::{Metadata, MetadataLog};
+use symphonia_core::packet::SideData;
 use symphonia_core::support_format;
-use symphonia_core::units::TimeBase;
+use symphonia_core::units::{FrameRate, TimeBase};
 
 use log::{info, warn};
 
-use crate::codecs::make_track_codec_params;
-use crate::ebml::{EbmlElementInfo, EbmlError, EbmlIterator, EbmlSchema, ReadEbml};
+use crate::codecs::{
+    apply_first_frame, constant_frames_per_packet, is_first_frame_required, make_track_codec_params,
+};
+use crate::ebml::{
+    EbmlElementInfo, EbmlError, EbmlIterator, EbmlIteratorState, EbmlSchema, ReadEbml,
+};
 use crate::lacing::{Frame, extract_frames};
 use crate::schema::{MkvElement, MkvSchema};
 use crate::segment::{
     AttachmentsElement, BlockGroupElement, ChaptersElement, CuesElement, EbmlHeaderElement,
     InfoElement, MatroskaTicks, NonZeroMatroskaTicks, SeekHeadElement, SegmentTicks,
-    SignedTrackTicks, TagsElement, TargetTagsMap, TracksElement,
+    SignedTrackTicks, TagsElement, TargetTagsMap, TrackElement, TracksElement,
 };
 
+/// The maximum data size of a string or binary element, if the element size limit is defaulted.
+const DEFAULT_LIMIT_ELEMENT_BYTES: usize = 64 * 1024 * 1024;
+
+/// The maximum number of frames read while searching for the first frame of each track that
+/// requires it.
+const MAX_FIRST_FRAME_SEARCH: usize = 1024;
+
 const MKV_FORMAT_INFO: FormatInfo =
     FormatInfo { format: FORMAT_ID_MKV, short_name: "matroska", long_name: "Matroska / WebM" };
 
+const WEBM_FORMAT_INFO: FormatInfo =
+    FormatInfo { format: FORMAT_ID_WEBM, short_name: "webm", long_name: "WebM" };
+
 pub struct TrackState {
     /// The Matroska track number (Symphonia's track ID).
     track_num: u32,
@@ -44,14 +69,32 @@ pub struct TrackState {
     pub(crate) track_time_base: TimeBase,
     /// The track's timestamp scale.
     pub(crate) track_timestamp_scale: f64,
+    /// If `false`, the frames of the track are skipped instead of being returned as packets.
+    selected: bool,
+    /// The sample rate of an audio track, if known.
+    sample_rate: Option<NonZero<u32>>,
+    /// The number of audio frames in each frame of an audio track, if the codec has a constant
+    /// number of audio frames per frame.
+    frames_per_packet: Option<u64>,
+    /// After a seek of an audio track, the required timestamp. Frames preceding it are trimmed
+    /// from the packets of the track.
+    seek_ts: Option<Timestamp>,
+    /// If the NAL units of the track's frames are to be converted to Annex B, the converter.
+    annex_b: Option<AnnexBConverter>,
 }
 
 /// Matroska (MKV) and WebM demultiplexer.
 ///
 /// `MkvReader` implements a demuxer for the Matroska and WebM formats.
+///
+/// Seeking lands on the keyframe preceding the requested position. Without cues, the clusters are
+/// scanned for the keyframe. If the source is not seekable, the reader can only scan forward, and a
+/// seek may then land on a frame that is not a keyframe, or past the requested position.
 pub struct MkvReader<'s> {
     /// Iterator over EBML element headers
     iter: EbmlIterator<MediaSourceStream<'s>, MkvSchema>,
+    /// The format information. Differs for the Matroska and WebM document types.
+    format_info: &'static FormatInfo,
     media_info: MediaInfo,
     tracks: Vec<Track>,
     track_states: HashMap<u32, TrackState>,
@@ -59,8 +102,19 @@ pub struct MkvReader<'s> {
     chapters: Option<ChapterGroup>,
     metadata: MetadataLog,
     cues: Option<CuesElement>,
+    /// The groups of tracks related by a track operation.
+    track_groups: Vec<TrackGroup>,
     current_cluster: Option<ClusterState>,
+    /// The data of the last block read. All buffered frames reference this block.
+    block: Vec<u8>,
+    /// A shared copy of the last block read. Only made once a packet sharing the block is read.
+    shared_block: Option<Arc<[u8]>>,
+    /// The side data of the last block read. Attached to all frames extracted from the block.
+    block_side_data: Vec<SideData>,
     frames: VecDeque<Frame>,
+    /// The iterator and cluster states at the start of the first cluster. Only saved if the
+    /// source is seekable.
+    start_state: Option<(EbmlIteratorState<MkvSchema>, Option<ClusterState>)>,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -81,14 +135,19 @@ impl<'s> MkvReader<'s> {
             _ => (),
         }
 
-        let mut it = EbmlIterator::new(mss, MkvSchema, total_len);
+        // Packet data is read into shared buffers and is not subject to the element size limit.
+        let max_data_size = opts.limit_element_bytes.limit_or_default(DEFAULT_LIMIT_ELEMENT_BYTES);
+
+        let mut it = EbmlIterator::new(mss, MkvSchema, total_len, max_data_size);
 
         // Read the EBML header.
         let ebml = it.next_element::<EbmlHeaderElement>()?;
 
-        if !matches!(ebml.doc_type.as_str(), "matroska" | "webm") {
-            return unsupported_error("mkv: not a matroska / webm file");
-        }
+        let format_info = match ebml.doc_type.as_str() {
+            "matroska" => &MKV_FORMAT_INFO,
+            "webm" => &WEBM_FORMAT_INFO,
+            _ => return unsupported_error("mkv: not a matroska / webm file"),
+        };
 
         // Read the root element, Segment.
         let segment_pos = match it.next_header()? {
@@ -164,8 +223,10 @@ impl<'s> MkvReader<'s> {
 
         if is_seekable {
             // All elements preceeding the element iterator's current position have already been
-            // read and do not need to be revisited.
-            seek_positions.retain(|sp| sp.1 >= it.pos());
+            // read and do not need to be revisited. Seek positions are relative to the start of
+            // the Segment's data.
+            let scanned_pos = it.pos() - segment_pos;
+            seek_positions.retain(|sp| sp.1 >= scanned_pos);
             // Make sure we don't jump backwards unnecessarily.
             seek_positions.sort_by_key(|sp| sp.1);
 
@@ -233,6 +294,9 @@ impl<'s> MkvReader<'s> {
         // Descend into the cluster.
         it.push_element()?;
 
+        // Save the state at the start of the first cluster to allow resetting the reader.
+        let start_state = is_seekable.then(|| (it.save_state(), current_cluster));
+
         let info = info.ok_or(Error::DecodeError("mkv: missing Info element"))?;
 
         // Create a hashmap of all per-target tags (edition, chapter, & attachment tags).
@@ -268,26 +332,31 @@ impl<'s> MkvReader<'s> {
             .unwrap_or_default();
 
         // Should TimeBase use a u64/u64 rational?
-        // Reduce the timebase to reduce the chance of overflows later.
-        let time_base = TimeBase::new(
+        let segment_time_base = TimeBase::new(
             info.timestamp_scale
                 .try_into()
                 .map_err(|_| Error::Unsupported("mkv: timestamp scale too large (report this)"))?,
             NonZero::new(1_000_000_000).unwrap(),
-        )
-        .reduce();
+        );
+
+        // Reduce the timebase to reduce the chance of overflows later.
+        let time_base = segment_time_base.reduce();
+
+        let track_groups = make_track_groups(&segment_tracks.tracks);
 
         let mut tracks = Vec::new();
         let mut track_states = HashMap::new();
 
         for track in segment_tracks.tracks {
-            // The track's timebase is scaled by the track timestamp scale.
-            let track_time_base = time_base
+            // The track's timebase is scaled by the track timestamp scale. Scale the unreduced
+            // Segment timebase to avoid rounding a fractional scale into the reduced numerator.
+            let track_time_base = segment_time_base
                 .scale(track.track_timestamp_scale)
-                .ok_or(Error::DecodeError("mkv: track timebase is invalid"))?;
+                .ok_or(Error::DecodeError("mkv: track timebase is invalid"))?
+                .reduce();
 
             // Create the track state.
-            let state = TrackState {
+            let mut state = TrackState {
                 // TODO: This should be 64-bit, but track IDs are 32-bit.
                 track_num: u32::try_from(track.number.get())
                     .map_err(|_| Error::Unsupported("mkv: track number too large (report this)"))?,
@@ -295,6 +364,11 @@ impl<'s> MkvReader<'s> {
                 codec_delay: track.codec_delay,
                 track_time_base,
                 track_timestamp_scale: track.track_timestamp_scale,
+                selected: true,
+                sample_rate: None,
+                frames_per_packet: None,
+                seek_ts: None,
+                annex_b: None,
             };
 
             // Create the track.
@@ -311,10 +385,40 @@ impl<'s> MkvReader<'s> {
 
             tr.with_flags(track.flags);
 
+            if let Some(track_type) = track.track_type {
+                tr.with_track_type(track_type);
+            }
+
             if let Some(codec_params) = make_track_codec_params(track)? {
+                if let CodecParameters::Audio(params) = &codec_params {
+                    state.sample_rate = params.sample_rate.and_then(NonZero::new);
+                    state.frames_per_packet = constant_frames_per_packet(params);
+                }
                 tr.with_codec_params(codec_params);
             }
 
+            if opts.nal_format == NalFormat::AnnexB {
+                if let Some(CodecParameters::Video(codec_params)) = &tr.codec_params {
+                    // Fallback to the native framing if the decoder configuration is malformed.
+                    match make_annex_b_converter(codec_params) {
+                        Ok(converter) => state.annex_b = converter,
+                        Err(err) => warn!("track {}: cannot convert to annex b: {}", tr.id, err),
+                    }
+                }
+            }
+
+            // WebM only permits a subset of the codecs Matroska does. An unknown codec may or may
+            // not be permitted, so it is not rejected.
+            if opts.strict && format_info.format == FORMAT_ID_WEBM {
+                match &tr.codec_params {
+                    Some(_) if !is_webm_codec(&tr) => {
+                        return decode_error("mkv: codec not permitted in webm");
+                    }
+                    None => warn!("track {}: unknown codec in webm", tr.id),
+                    _ => (),
+                }
+            }
+
             tracks.push(tr);
             track_states.insert(state.track_num, state);
         }
@@ -328,8 +432,9 @@ impl<'s> MkvReader<'s> {
             media_info.with_duration(Duration::new(duration.get().round() as u64));
         }
 
-        Ok(Self {
+        let mut reader = Self {
             iter: it,
+            format_info,
             media_info,
             tracks,
             track_states,
@@ -337,38 +442,253 @@ impl<'s> MkvReader<'s> {
             chapters,
             metadata,
             cues,
+            track_groups,
             current_cluster,
+            block: Vec::new(),
+            shared_block: None,
+            block_side_data: Vec::new(),
             frames: VecDeque::new(),
-        })
+            start_state,
+        };
+
+        // Complete the codec parameters of the tracks that require the first frame. Since the
+        // reader must be reset afterwards, this is only possible if the source is seekable.
+        if reader.start_state.is_some() {
+            reader.read_first_frames()?;
+        }
+
+        Ok(reader)
+    }
+
+    /// Read the first frame of each track that requires it to complete its codec parameters, and
+    /// then reset the reader.
+    fn read_first_frames(&mut self) -> Result<()> {
+        let mut pending = self
+            .tracks
+            .iter()
+            .filter(|track| match &track.codec_params {
+                Some(CodecParameters::Audio(params)) => is_first_frame_required(params),
+                _ => false,
+            })
+            .map(|track| track.id)
+            .collect::<Vec<_>>();
+
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let mut num_frames = 0;
+
+        while !pending.is_empty() && num_frames < MAX_FIRST_FRAME_SEARCH {
+            let Some(frame) = self.frames.pop_front()
+            else {
+                match self.next_element() {
+                    Ok(true) => continue,
+                    Ok(false) => break,
+                    Err(err) => {
+                        warn!("failed to read first frames: {err}");
+                        break;
+                    }
+                }
+            };
+
+            num_frames += 1;
+
+            let Some(idx) = pending.iter().position(|&id| id == frame.track_num)
+            else {
+                continue;
+            };
+
+            pending.swap_remove(idx);
+
+            let track = self.tracks.iter_mut().find(|track| track.id == frame.track_num);
+
+            if let Some(Some(CodecParameters::Audio(params))) =
+                track.map(|track| &mut track.codec_params)
+            {
+                if let Err(err) = apply_first_frame(params, &self.block[frame.data]) {
+                    warn!("track {}: failed to read first frame: {}", frame.track_num, err);
+                }
+
+                if let Some(state) = self.track_states.get_mut(&frame.track_num) {
+                    state.sample_rate = params.sample_rate.and_then(NonZero::new);
+                }
+            }
+        }
+
+        self.reset()
+    }
+
+    /// Get a shared copy of the last block read. The block is copied at most once.
+    fn shared_block(&mut self) -> Arc<[u8]> {
+        self.shared_block.get_or_insert_with(|| Arc::from(self.block.as_slice())).clone()
     }
 
+    /// Pop the next buffered frame belonging to a selected track. Frames of unselected tracks are
+    /// discarded.
+    fn next_selected_frame(&mut self) -> Option<Frame> {
+        while let Some(frame) = self.frames.pop_front() {
+            if self.track_states.get(&frame.track_num).is_some_and(|track| track.selected) {
+                return Some(frame);
+            }
+        }
+        None
+    }
+
+    /// Scan forward to the frame of the track containing the given timestamp, and return to the
+    /// last keyframe of the track at or before that frame. If the scan started past the timestamp,
+    /// past that keyframe, or at the end of the media, it is repeated once from the first cluster.
+    /// Restarting the scan is not possible if the source is not seekable.
     fn seek_track_by_ts_forward(&mut self, track_id: u32, ts: Timestamp) -> Result<SeekedTo> {
+        /// The reader state when a keyframe of the track was the next buffered frame.
+        struct KeyframeState {
+            pts: Timestamp,
+            iter_state: EbmlIteratorState<MkvSchema>,
+            cluster_state: Option<ClusterState>,
+            block: Arc<[u8]>,
+            block_side_data: Vec<SideData>,
+            frames: VecDeque<Frame>,
+        }
+
+        // Returning to an earlier keyframe is only possible if the source is seekable.
+        let is_seekable = self.iter.inner().is_seekable();
+
+        let mut last_keyframe: Option<KeyframeState> = None;
+
+        // Whether a frame of the track preceding the timestamp was scanned, and whether the scan
+        // was restarted from the first cluster.
+        let mut is_track_scanned = false;
+        let mut is_rewound = false;
+
         let actual_ts = 'out: loop {
             // Skip frames from the buffer until the given timestamp
             while let Some(frame) = self.frames.front() {
-                let next_frame_pts = frame
-                    .pts
-                    .into_ts()
+                let pts = frame.pts.into_ts();
+                let is_keyframe = frame.is_keyframe;
+
+                let next_frame_pts = pts
                     .checked_add(frame.dur.into_dur())
                     .ok_or(Error::SeekError(SeekErrorKind::OutOfRange))?;
 
-                if next_frame_pts >= ts && frame.track_num == track_id {
-                    break 'out frame.pts.into_ts();
-                }
-                else {
-                    self.frames.pop_front();
+                if frame.track_num == track_id {
+                    if next_frame_pts >= ts {
+                        // The scan started past the timestamp, or past the last keyframe preceding
+                        // it. Restart the scan from the first cluster, if possible.
+                        let is_past = (!is_track_scanned && pts > ts)
+                            || (!is_keyframe && last_keyframe.is_none());
+
+                        if is_past && !is_rewound && self.rewind()? {
+                            is_rewound = true;
+                            continue 'out;
+                        }
+
+                        if is_keyframe {
+                            break 'out pts;
+                        }
+
+                        // Decoding must start from a keyframe. If the frame containing the given
+                        // timestamp is not a keyframe, return to the last keyframe of the track.
+                        if let Some(keyframe) = last_keyframe.take() {
+                            self.iter.restore_state(keyframe.iter_state)?;
+                            self.current_cluster = keyframe.cluster_state;
+                            self.block.clear();
+                            self.block.extend_from_slice(&keyframe.block);
+                            self.shared_block = Some(keyframe.block);
+                            self.block_side_data = keyframe.block_side_data;
+                            self.frames = keyframe.frames;
+                            break 'out keyframe.pts;
+                        }
+
+                        break 'out pts;
+                    }
+
+                    if is_keyframe && is_seekable {
+                        last_keyframe = Some(KeyframeState {
+                            pts,
+                            iter_state: self.iter.save_state(),
+                            cluster_state: self.current_cluster,
+                            block: self.shared_block(),
+                            block_side_data: self.block_side_data.clone(),
+                            frames: self.frames.clone(),
+                        });
+                    }
+
+                    is_track_scanned = true;
                 }
+
+                self.frames.pop_front();
             }
 
             if !self.next_element()? {
-                // There are no more elements.
+                // There are no more elements. If the scan started at the end of the media, restart
+                // it from the first cluster, if possible.
+                if !is_track_scanned && !is_rewound && self.rewind()? {
+                    is_rewound = true;
+                    continue;
+                }
                 return Err(Error::SeekError(SeekErrorKind::OutOfRange));
             }
         };
 
+        // Decoding of an audio track should start exactly at the required timestamp, therefore the
+        // frames preceding it will be trimmed. Trimming requires the sample rate of the track.
+        if let Some(state) = self.track_states.get_mut(&track_id) {
+            state.seek_ts = state.sample_rate.map(|_| ts);
+        }
+
         Ok(SeekedTo { track_id, required_ts: ts, actual_ts })
     }
 
+    /// Return to the start of the first cluster, and discard all buffered frames. Returns `false`
+    /// if the source is not seekable.
+    fn rewind(&mut self) -> Result<bool> {
+        let Some((iter_state, cluster_state)) = self.start_state.clone()
+        else {
+            return Ok(false);
+        };
+
+        // The headers preceding the first cluster are immutable, therefore only the iterator and
+        // cluster states need to be restored.
+        self.iter.restore_state(iter_state)?;
+        self.current_cluster = cluster_state;
+        self.frames.clear();
+        self.block.clear();
+        self.shared_block = None;
+        self.block_side_data.clear();
+
+        Ok(true)
+    }
+
+    /// Get the number of audio frames in a frame of an audio track, if known. Unless the codec has a
+    /// constant number of audio frames per frame, it is derived from the duration of the frame.
+    fn num_frames(&self, frame: &Frame) -> Option<u64> {
+        let state = self.track_states.get(&frame.track_num)?;
+
+        state.frames_per_packet.or_else(|| {
+            let rate = FrameRate::new(state.sample_rate?, NonZero::<u32>::MIN);
+            state.track_time_base.dur_to_frames(frame.dur.into_dur(), rate)
+        })
+    }
+
+    /// Get the timestamp that the packet of a frame must be trimmed to after a seek, if any. The
+    /// time base and sample rate of the track are returned with it.
+    fn take_seek_ts(&mut self, frame: &Frame) -> Option<(Timestamp, TimeBase, NonZero<u32>)> {
+        let state = self.track_states.get_mut(&frame.track_num)?;
+        let seek_ts = state.seek_ts?;
+
+        // Frames following the frame containing the required timestamp are not trimmed.
+        if frame.pts.into_ts().checked_add(frame.dur.into_dur()).is_none_or(|end| end >= seek_ts) {
+            state.seek_ts = None;
+        }
+
+        Some((seek_ts, state.track_time_base, state.sample_rate?))
+    }
+
+    /// Get the Annex B converter for the track of a frame, if the frame is to be converted.
+    fn annex_b_converter(&self, frame: &Frame) -> Option<&AnnexBConverter> {
+        self.track_states.get(&frame.track_num)?.annex_b.as_ref()
+    }
+
     fn seek_track_by_ts_atomic(
         &mut self,
         id: u32,
@@ -434,7 +754,7 @@ impl<'s> MkvReader<'s> {
                 Some(header) if header.element_type() == MkvElement::Cluster => header,
                 // The seeked element is not a cluster or there were no more elements at the cue
                 // position. The cue point was malformed.
-                _ => return seek_error(SeekErrorKind::Unseekable),
+                _ => return decode_error("mkv: cue point does not reference a cluster"),
             };
 
             // Convert the cue point's timestamp (Matroska ticks) into Segment ticks for the cluster
@@ -516,20 +836,42 @@ impl<'s> MkvReader<'s> {
                             return Ok(true);
                         };
 
-                        // Get block data and duration.
-                        let (data, duration) = match block_type {
-                            MkvElement::SimpleBlock => (self.iter.read_binary()?, None),
+                        // A new block may only be read once all frames referencing the previous
+                        // block were consumed.
+                        debug_assert!(self.frames.is_empty());
+
+                        // Get block data, duration, and keyframe flag. A simple block carries its own
+                        // keyframe flag, while a block group is a keyframe if it does not reference
+                        // any other block.
+                        let (duration, is_keyframe) = match block_type {
+                            MkvElement::SimpleBlock => {
+                                self.iter.read_binary_to_vec(&mut self.block)?;
+                                self.block_side_data.clear();
+                                (None, None)
+                            }
                             MkvElement::BlockGroup => {
                                 let group = self.iter.read_master_element::<BlockGroupElement>()?;
-                                (group.data, group.duration)
+                                self.block = group.data.into_vec();
+                                self.block_side_data = group
+                                    .additions
+                                    .into_iter()
+                                    .map(|more| SideData::BlockAddition {
+                                        id: more.id,
+                                        data: more.data,
+                                    })
+                                    .collect();
+                                (group.duration, Some(group.reference_block.is_none()))
                             }
                             _ => unreachable!(),
                         };
 
+                        self.shared_block = None;
+
                         // Extract frames.
                         if !extract_frames(
-                            &data,
+                            &self.block,
                             duration,
+                            is_keyframe,
                             cluster_ts,
                             &self.track_states,
                             &mut self.frames,
@@ -550,6 +892,64 @@ impl<'s> MkvReader<'s> {
     }
 }
 
+/// Make the track groups described by the track operations of all tracks.
+fn make_track_groups(tracks: &[TrackElement]) -> Vec<TrackGroup> {
+    // Track operations reference tracks by their UID.
+    let track_num_for_uid = |uid: u64| {
+        tracks
+            .iter()
+            .find(|track| track.uid.get() == uid)
+            .and_then(|track| u32::try_from(track.number.get()).ok())
+    };
+
+    let mut groups = Vec::new();
+
+    for track in tracks {
+        let Some(operation) = &track.operation
+        else {
+            continue;
+        };
+
+        let Ok(id) = u32::try_from(track.number.get())
+        else {
+            continue;
+        };
+
+        let planes: Vec<u32> = operation
+            .combine_planes
+            .iter()
+            .filter_map(|plane| track_num_for_uid(plane.uid))
+            .collect();
+
+        if !planes.is_empty() {
+            groups.push(TrackGroup { kind: TrackGroupKind::CombinePlanes, id, track_ids: planes });
+        }
+
+        let joined: Vec<u32> =
+            operation.join_uids.iter().filter_map(|&uid| track_num_for_uid(uid)).collect();
+
+        if !joined.is_empty() {
+            groups.push(TrackGroup { kind: TrackGroupKind::JoinBlocks, id, track_ids: joined });
+        }
+    }
+
+    groups
+}
+
+/// Returns `true` if the codec of the track is known, and permitted in WebM.
+fn is_webm_codec(track: &Track) -> bool {
+    match &track.codec_params {
+        Some(CodecParameters::Audio(params)) => {
+            matches!(params.codec, CODEC_ID_VORBIS | CODEC_ID_OPUS)
+        }
+        Some(CodecParameters::Video(params)) => {
+            matches!(params.codec, CODEC_ID_VP8 | CODEC_ID_VP9 | CODEC_ID_AV1)
+        }
+        Some(CodecParameters::Subtitle(params)) => params.codec == CODEC_ID_WEBVTT,
+        _ => false,
+    }
+}
+
 impl ProbeableFormat<'_> for MkvReader<'_> {
     fn try_probe_new(
         mss: MediaSourceStream<'_>,
@@ -573,7 +973,7 @@ impl ProbeableFormat<'_> for MkvReader<'_> {
 
 impl FormatReader for MkvReader<'_> {
     fn format_info(&self) -> &FormatInfo {
-        &MKV_FORMAT_INFO
+        self.format_info
     }
 
     fn media_info(&self) -> &MediaInfo {
@@ -594,7 +994,7 @@ impl FormatReader for MkvReader<'_> {
 
     fn seek(&mut self, _mode: SeekMode, to: SeekTo) -> Result<SeekedTo> {
         if self.tracks.is_empty() {
-            return seek_error(SeekErrorKind::Unseekable);
+            return seek_error(SeekErrorKind::Unsupported);
         }
 
         match to {
@@ -633,15 +1033,47 @@ impl FormatReader for MkvReader<'_> {
         &self.tracks
     }
 
+    fn track_groups(&self) -> Vec<TrackGroup> {
+        self.track_groups.clone()
+    }
+
+    fn set_track_filter(&mut self, track_ids: &[u32]) -> Result<()> {
+        for state in self.track_states.values_mut() {
+            state.selected = track_ids.is_empty() || track_ids.contains(&state.track_num);
+        }
+        Ok(())
+    }
+
+    fn is_track_selected(&self, track_id: u32) -> bool {
+        self.track_states.get(&track_id).is_some_and(|state| state.selected)
+    }
+
     fn next_packet(&mut self) -> Result<Option<Packet>> {
         loop {
-            if let Some(frame) = self.frames.pop_front() {
-                return Ok(Some(Packet::new(
-                    frame.track_num,
-                    frame.pts.into_ts(),
-                    frame.dur.into_dur(),
-                    frame.data,
-                )));
+            if let Some(frame) = self.next_selected_frame() {
+                let seek_ts = self.take_seek_ts(&frame);
+                let num_frames = self.num_frames(&frame);
+
+                let data = match self.annex_b_converter(&frame) {
+                    Some(converter) => {
+                        let mut buf = Vec::new();
+                        converter.convert(&self.block[frame.data], frame.is_keyframe, &mut buf)?;
+                        PacketData::from(buf)
+                    }
+                    None => PacketData::Shared(self.shared_block(), frame.data),
+                };
+
+                let mut packet =
+                    Packet::new(frame.track_num, frame.pts.into_ts(), frame.dur.into_dur(), data);
+                packet.side_data = self.block_side_data.clone();
+                packet.is_keyframe = frame.is_keyframe;
+                packet.num_frames = num_frames;
+
+                if let Some((seek_ts, time_base, sample_rate)) = seek_ts {
+                    packet.trim_start_to(seek_ts, time_base, sample_rate);
+                }
+
+                return Ok(Some(packet));
             }
 
             if !self.next_element()? {
@@ -651,6 +1083,78 @@ impl FormatReader for MkvReader<'_> {
         }
     }
 
+    fn next_packet_into<'b>(&mut self, buf: &'b mut Vec<u8>) -> Result<Option<PacketRef<'b>>> {
+        loop {
+            if let Some(frame) = self.next_selected_frame() {
+                let seek_ts = self.take_seek_ts(&frame);
+                let num_frames = self.num_frames(&frame);
+
+                match self.annex_b_converter(&frame) {
+                    Some(converter) => {
+                        converter.convert(&self.block[frame.data], frame.is_keyframe, buf)?
+                    }
+                    None => {
+                        buf.clear();
+                        buf.extend_from_slice(&self.block[frame.data]);
+                    }
+                }
+
+                let mut packet =
+                    PacketRef::new(frame.track_num, frame.pts.into_ts(), frame.dur.into_dur(), buf);
+                packet.is_keyframe = frame.is_keyframe;
+                packet.num_frames = num_frames;
+                packet.side_data = Cow::Owned(self.block_side_data.clone());
+
+                if let Some((seek_ts, time_base, sample_rate)) = seek_ts {
+                    packet.trim_start_to(seek_ts, time_base, sample_rate);
+                }
+
+                return Ok(Some(packet));
+            }
+
+            if !self.next_element()? {
+                // Reached the end of stream.
+                return Ok(None);
+            }
+        }
+    }
+
+    fn source_position(&self) -> Option<u64> {
+        // All frames of a laced block are read at once. Until the remaining frames are returned,
+        // the position after the block is not the position of the next packet.
+        if !self.frames.is_empty() {
+            return None;
+        }
+
+        let reader = self.iter.inner();
+        reader.is_seekable().then(|| reader.pos())
+    }
+
+    fn capabilities(&self) -> FormatCapabilities {
+        let seekable = self.iter.inner().is_seekable();
+        let known_duration = self.media_info.duration.is_some();
+
+        let mut caps = FormatCapabilities::empty();
+        caps.set(FormatCapabilities::SEEKABLE, seekable);
+        caps.set(FormatCapabilities::KNOWN_DURATION, known_duration);
+        caps.set(FormatCapabilities::LIVE, !seekable && !known_duration);
+        caps.set(FormatCapabilities::CHAPTERS, self.chapters().is_some());
+        caps.set(FormatCapabilities::ATTACHMENTS, !self.attachments().is_empty());
+        caps
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        if !self.rewind()? {
+            return unsupported_error("mkv: reset requires a seekable source");
+        }
+
+        for state in self.track_states.values_mut() {
+            state.seek_ts = None;
+        }
+
+        Ok(())
+    }
+
     fn into_inner<'s>(self: Box<Self>) -> MediaSourceStream<'s>
     where
         Self: 's,
@@ -694,6 +1198,7 @@ impl From<EbmlError> for Error {
             EbmlError::SeekOutOfRange => "mkv (ebml): the seek was out of range",
             EbmlError::BufferTooSmall => "mkv (ebml): the buffer is too small",
             EbmlError::MaximumDepthReached => "mkv (ebml): maximum ebml document depth reached",
+            EbmlError::ElementTooLarge => "mkv (ebml): the element data size exceeds the limit",
             EbmlError::ElementError(err) => err,
         };
         Error::DecodeError(msg)
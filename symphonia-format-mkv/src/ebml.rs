@@ -48,6 +48,8 @@ pub enum EbmlError {
     BufferTooSmall,
     /// Maximum depth reached.
     MaximumDepthReached,
+    /// The element's data size exceeds the maximum size that may be allocated.
+    ElementTooLarge,
     /// A user-defined error for element decoding/parsing errors.
     ElementError(&'static str),
 }
@@ -274,6 +276,12 @@ pub(crate) struct EbmlIteratorState<S: EbmlSchema> {
     pos: u64,
 }
 
+impl<S: EbmlSchema> Clone for EbmlIteratorState<S> {
+    fn clone(&self) -> Self {
+        Self { stack: self.stack.clone(), current: self.current, pos: self.pos }
+    }
+}
+
 /// An EBML document iterator supporting hierarchical traversal.
 pub(crate) struct EbmlIterator<R: ReadEbml, S: EbmlSchema> {
     /// The inner reader.
@@ -286,13 +294,20 @@ pub(crate) struct EbmlIterator<R: ReadEbml, S: EbmlSchema> {
     current: Option<EbmlElementHeader<S>>,
     /// The length of the container, if known.
     len: Option<u64>,
+    /// The maximum data size of a string or binary element read into a new buffer, if limited.
+    max_data_size: Option<usize>,
 }
 
 impl<R: ReadEbml, S: EbmlSchema> EbmlIterator<R, S> {
-    pub(crate) fn new(reader: R, schema: S, len: Option<u64>) -> Self {
+    pub(crate) fn new(
+        reader: R,
+        schema: S,
+        len: Option<u64>,
+        max_data_size: Option<usize>,
+    ) -> Self {
         // Pre-allocate the iteration stack.
         let stack = Vec::with_capacity(S::MAX_DEPTH);
-        Self { reader, schema, stack, current: None, len }
+        Self { reader, schema, stack, current: None, len, max_data_size }
     }
 
     /// Get a reference to the schema being used by the EBML reader.
@@ -305,6 +320,11 @@ impl<R: ReadEbml, S: EbmlSchema> EbmlIterator<R, S> {
         self.reader
     }
 
+    /// Get an immutable reference to the inner reader.
+    pub(crate) fn inner(&self) -> &R {
+        &self.reader
+    }
+
     /// Get the position of the inner reader.
     pub(crate) fn pos(&self) -> u64 {
         self.reader.pos()
@@ -460,6 +480,11 @@ impl<R: ReadEbml, S: EbmlSchema> EbmlIterator<R, S> {
         // Consume the current element if it has a known size, and skip past any remaining unread
         // data.
         if let Some(elem) = self.current.take() {
+            // Unknown elements, such as vendor-specific elements, are never read.
+            if elem.element_info.is_none() {
+                log::debug!("skipping unknown element: {:#x}", elem.id);
+            }
+
             match elem.end() {
                 Some(end) => {
                     // Element had a known size.
@@ -575,6 +600,13 @@ impl<R: ReadEbml, S: EbmlSchema> EbmlIterator<R, S> {
                     return Ok(None);
                 }
             }
+            else if header.element_info.is_none() {
+                // An unknown element with an unknown size cannot be skipped. Since the parent has a
+                // known size, the remainder of the parent may be discarded instead.
+                log::debug!("unknown element {:#x} with an unknown size, ignoring", header.id);
+                self.reader.ignore_bytes(parent_end - pos)?;
+                return Ok(None);
+            }
         }
 
         // Element is valid for the current parent and depth of the iterator. Return it.
@@ -788,7 +820,7 @@ impl<R: ReadEbml, S: EbmlSchema> EbmlIterator<R, S> {
             Some(info) => match info.data_type() {
                 EbmlDataType::Master => Err(EbmlError::ExpectedNonMasterElement),
                 EbmlDataType::String => {
-                    let size = element.data_size.ok_or(EbmlError::UnknownElementDataSize)? as usize;
+                    let size = self.alloc_data_size(element)?;
                     match size {
                         0 => Ok(None),
                         _ => {
@@ -829,7 +861,7 @@ impl<R: ReadEbml, S: EbmlSchema> EbmlIterator<R, S> {
             Some(info) => match info.data_type() {
                 EbmlDataType::Master => Err(EbmlError::ExpectedNonMasterElement),
                 EbmlDataType::Binary => {
-                    let size = element.data_size.ok_or(EbmlError::UnknownElementDataSize)? as usize;
+                    let size = self.alloc_data_size(element)?;
                     let data = self.reader.read_boxed_slice_exact(size)?;
                     self.discard_current();
                     Ok(data)
@@ -840,6 +872,27 @@ impl<R: ReadEbml, S: EbmlSchema> EbmlIterator<R, S> {
         }
     }
 
+    /// Read the binary data carried by a binary element into a provided vector, replacing its
+    /// previous contents.
+    pub(crate) fn read_binary_to_vec(&mut self, buf: &mut Vec<u8>) -> Result<()> {
+        let element = self.current_or_err()?;
+
+        match element.element_info {
+            Some(info) => match info.data_type() {
+                EbmlDataType::Master => Err(EbmlError::ExpectedNonMasterElement),
+                EbmlDataType::Binary => {
+                    let size = self.alloc_data_size(element)?;
+                    buf.resize(size, 0);
+                    self.reader.read_buf_exact(buf)?;
+                    self.discard_current();
+                    Ok(())
+                }
+                _ => Err(EbmlError::UnexpectedElementDataType),
+            },
+            _ => Err(EbmlError::UnknownElement),
+        }
+    }
+
     /// Read the binary data carried by a binary element into a provided byte slice.
     ///
     /// It is an error if the buffer is too small.
@@ -870,6 +923,17 @@ impl<R: ReadEbml, S: EbmlSchema> EbmlIterator<R, S> {
         self.current.as_ref().ok_or(EbmlError::NoElement)
     }
 
+    /// Get the data size of an element to be read into a newly allocated buffer. It is an error if
+    /// the data size exceeds the maximum.
+    fn alloc_data_size(&self, element: &EbmlElementHeader<S>) -> Result<usize> {
+        let size = element.data_size.ok_or(EbmlError::UnknownElementDataSize)?;
+
+        match self.max_data_size {
+            Some(max) if size > max as u64 => Err(EbmlError::ElementTooLarge),
+            _ => Ok(size as usize),
+        }
+    }
+
     /// Discard the current element.
     ///
     /// NOTE: It is a logic error to call this before fully consuming the element. Will panic in
@@ -6,6 +6,7 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use std::collections::{HashMap, VecDeque};
+use std::ops::Range;
 
 use symphonia_core::errors::{Error, Result, decode_error};
 use symphonia_core::io::{BufReader, ReadBytes};
@@ -65,6 +66,7 @@ pub(crate) fn read_xiph_sizes<R: ReadBytes>(mut reader: R, num_frames: usize) ->
     Ok(sizes)
 }
 
+#[derive(Clone)]
 pub(crate) struct Frame {
     /// The Matroska track number (Symphonia's track ID).
     pub(crate) track_num: u32,
@@ -72,8 +74,17 @@ pub(crate) struct Frame {
     pub(crate) pts: SignedTrackTicks,
     /// The frame's duration.
     pub(crate) dur: TrackTicks,
-    /// Frame data.
-    pub(crate) data: Box<[u8]>,
+    /// The range of the frame data within the block the frame was extracted from.
+    pub(crate) data: Range<usize>,
+    /// If true, the frame is a keyframe.
+    pub(crate) is_keyframe: bool,
+}
+
+/// Skip over the data of a frame, and return its range within the block.
+fn read_frame_range(reader: &mut BufReader<'_>, len: usize) -> Result<Range<usize>> {
+    let start = reader.pos() as usize;
+    reader.ignore_bytes(len as u64)?;
+    Ok(start..start + len)
 }
 
 /// Calculate the PTS of a block. This is the PTS of the first frame in the block.
@@ -129,9 +140,14 @@ impl FrameDurationIter {
     }
 }
 
+/// Extract the frames of a block.
+///
+/// If `is_keyframe` is `None`, the keyframe flag of the block itself is used. This flag is only
+/// present in a `SimpleBlock`.
 pub(crate) fn extract_frames(
     block: &[u8],
     block_duration: Option<TrackTicks>,
+    is_keyframe: Option<bool>,
     cluster_ts: SegmentTicks,
     tracks: &HashMap<u32, TrackState>,
     frames: &mut VecDeque<Frame>,
@@ -141,6 +157,7 @@ pub(crate) fn extract_frames(
     let block_rel_ts = SignedTrackTicks::from((reader.read_be_u16()? as i16) as i64);
     let flags = reader.read_byte()?;
     let lacing = parse_flags(flags)?;
+    let is_keyframe = is_keyframe.unwrap_or(flags & 0x80 != 0);
 
     // Get the track associated with the block. It's an error if the track doesn't exist.
     let track =
@@ -153,9 +170,10 @@ pub(crate) fn extract_frames(
 
     match lacing {
         Lacing::None => {
-            let data = reader.read_boxed_slice_exact(block.len() - reader.pos() as usize)?;
+            let size = block.len() - reader.pos() as usize;
+            let data = read_frame_range(&mut reader, size)?;
             let dur = FrameDurationIter::new(block_duration, track, 1).next();
-            frames.push_back(Frame { track_num, pts, data, dur });
+            frames.push_back(Frame { track_num, pts, data, dur, is_keyframe });
         }
         Lacing::Xiph | Lacing::Ebml => {
             // Read number of stored sizes which is actually `number of frames` - 1
@@ -179,10 +197,10 @@ pub(crate) fn extract_frames(
             let mut dur_it = FrameDurationIter::new(block_duration, track, num_frames as u64 + 1);
 
             for frame_size in sizes {
-                let data = reader.read_boxed_slice_exact(frame_size as usize)?;
+                let data = read_frame_range(&mut reader, frame_size as usize)?;
                 let dur = dur_it.next();
 
-                frames.push_back(Frame { track_num, pts, data, dur });
+                frames.push_back(Frame { track_num, pts, data, dur, is_keyframe });
 
                 // If PTS overflows, end the stream.
                 pts = match pts.checked_add_unsigned(dur) {
@@ -193,8 +211,8 @@ pub(crate) fn extract_frames(
 
             // Size of last frame is not provided so we read to the end of the block.
             let size = block.len() - reader.pos() as usize;
-            let data = reader.read_boxed_slice_exact(size)?;
-            frames.push_back(Frame { track_num, pts, data, dur: dur_it.next() });
+            let data = read_frame_range(&mut reader, size)?;
+            frames.push_back(Frame { track_num, pts, data, dur: dur_it.next(), is_keyframe });
         }
         Lacing::FixedSize => {
             let num_frames = reader.read_byte()? as usize + 1;
@@ -207,10 +225,10 @@ pub(crate) fn extract_frames(
 
             let frame_size = total_size / num_frames;
             for _ in 0..num_frames {
-                let data = reader.read_boxed_slice_exact(frame_size)?;
+                let data = read_frame_range(&mut reader, frame_size)?;
                 let dur = dur_it.next();
 
-                frames.push_back(Frame { track_num, pts, data, dur });
+                frames.push_back(Frame { track_num, pts, data, dur, is_keyframe });
 
                 // If PTS overflows, end the stream.
                 pts = match pts.checked_add_unsigned(dur) {
@@ -14,7 +14,7 @@ use symphonia_core::codecs::video::VideoExtraData;
 use symphonia_core::codecs::video::well_known::extra_data::{
     VIDEO_EXTRA_DATA_ID_DOLBY_VISION_CONFIG, VIDEO_EXTRA_DATA_ID_DOLBY_VISION_EL_HEVC,
 };
-use symphonia_core::formats::{Attachment, FileAttachment, TrackFlags};
+use symphonia_core::formats::{Attachment, FileAttachment, TrackFlags, TrackType};
 use symphonia_core::meta::well_known::METADATA_ID_MATROSKA;
 use symphonia_core::meta::{
     Chapter, ChapterGroup, ChapterGroupItem, MetadataBuilder, MetadataInfo, MetadataRevision,
@@ -204,7 +204,8 @@ impl SegmentTicks {
         if track_timestamp_scale == 1.0 {
             return TrackTicks(self.0);
         }
-        TrackTicks((self.0 as f64 * track_timestamp_scale).round() as u64)
+        // A Track tick is `track_timestamp_scale` Segment ticks long.
+        TrackTicks((self.0 as f64 / track_timestamp_scale).round() as u64)
     }
 }
 
@@ -354,6 +355,7 @@ impl std::fmt::Display for SignedTrackTicks {
 pub(crate) struct TrackElement {
     pub(crate) number: NonZeroU64,
     pub(crate) uid: NonZeroU64,
+    pub(crate) track_type: Option<TrackType>,
     pub(crate) lang: String,
     pub(crate) lang_bcp47: Option<String>,
     pub(crate) codec_id: String,
@@ -367,6 +369,7 @@ pub(crate) struct TrackElement {
     pub(crate) track_timestamp_scale: f64,
     pub(crate) seek_pre_roll: MatroskaTicks,
     pub(crate) flags: TrackFlags,
+    pub(crate) operation: Option<TrackOperationElement>,
 }
 
 impl EbmlElement<MkvSchema> for TrackElement {
@@ -375,6 +378,7 @@ impl EbmlElement<MkvSchema> for TrackElement {
     fn read<R: ReadEbml>(it: &mut MkvEbmlIterator<R>, hdr: &MkvEbmlElementHeader) -> Result<Self> {
         let mut number = None;
         let mut uid = None;
+        let mut track_type = None;
         let mut lang = None;
         let mut lang_bcp47 = None;
         let mut audio = None;
@@ -388,6 +392,7 @@ impl EbmlElement<MkvSchema> for TrackElement {
         let mut track_timestamp_scale = None;
         let mut seek_pre_roll = None;
         let mut flags = Default::default();
+        let mut operation = None;
 
         while let Some(child) = it.next_header()? {
             match child.element_type() {
@@ -405,6 +410,17 @@ impl EbmlElement<MkvSchema> for TrackElement {
 
                     uid = Some(val);
                 }
+                MkvElement::TrackType => {
+                    // Mandatory element. No schema-defined default.
+                    track_type = match it.read_u64_no_default()? {
+                        1 => Some(TrackType::Video),
+                        2 => Some(TrackType::Audio),
+                        0x11 => Some(TrackType::Subtitle),
+                        0x21 => Some(TrackType::Data),
+                        // Complex, logo, buttons, and control tracks.
+                        _ => None,
+                    };
+                }
                 MkvElement::Language => {
                     // Mandatory element. Schema-defined default is "eng".
                     lang = it.read_string()?;
@@ -505,6 +521,10 @@ impl EbmlElement<MkvSchema> for TrackElement {
                         flags |= TrackFlags::COMMENTARY;
                     }
                 }
+                MkvElement::TrackOperation => {
+                    // Non-mandatory element.
+                    operation = Some(it.read_master_element()?);
+                }
                 other => {
                     // Unexpected child element.
                     log::debug!("ignored {:?} child {:?}", hdr.element_type(), other);
@@ -521,6 +541,7 @@ impl EbmlElement<MkvSchema> for TrackElement {
         Ok(Self {
             number: number.ok_or(EbmlError::ElementError("mkv: missing track number"))?,
             uid: uid.ok_or(EbmlError::ElementError("mkv: missing track uid"))?,
+            track_type,
             lang,
             lang_bcp47,
             codec_id: codec_id.ok_or(EbmlError::ElementError("mkv: missing codec id"))?,
@@ -534,10 +555,145 @@ impl EbmlElement<MkvSchema> for TrackElement {
             track_timestamp_scale,
             seek_pre_roll,
             flags,
+            operation,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct TrackOperationElement {
+    /// The planes combined to create this track.
+    pub(crate) combine_planes: Vec<TrackPlaneElement>,
+    /// The UIDs of the tracks whose blocks are joined to create this track.
+    pub(crate) join_uids: Vec<u64>,
+}
+
+impl EbmlElement<MkvSchema> for TrackOperationElement {
+    const TYPE: MkvElement = MkvElement::TrackOperation;
+
+    fn read<R: ReadEbml>(it: &mut MkvEbmlIterator<R>, hdr: &MkvEbmlElementHeader) -> Result<Self> {
+        let mut combine_planes = Vec::new();
+        let mut join_uids = Vec::new();
+
+        while let Some(child) = it.next_header()? {
+            match child.element_type() {
+                MkvElement::TrackCombinePlanes => {
+                    // Non-mandatory element.
+                    let elem = it.read_master_element::<TrackCombinePlanesElement>()?;
+                    combine_planes.extend(elem.planes);
+                }
+                MkvElement::TrackJoinBlocks => {
+                    // Non-mandatory element.
+                    let elem = it.read_master_element::<TrackJoinBlocksElement>()?;
+                    join_uids.extend(elem.uids);
+                }
+                other => {
+                    // Unexpected child element.
+                    log::debug!("ignored {:?} child {:?}", hdr.element_type(), other);
+                }
+            }
+        }
+
+        Ok(Self { combine_planes, join_uids })
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct TrackCombinePlanesElement {
+    pub(crate) planes: Vec<TrackPlaneElement>,
+}
+
+impl EbmlElement<MkvSchema> for TrackCombinePlanesElement {
+    const TYPE: MkvElement = MkvElement::TrackCombinePlanes;
+
+    fn read<R: ReadEbml>(it: &mut MkvEbmlIterator<R>, hdr: &MkvEbmlElementHeader) -> Result<Self> {
+        let mut planes = Vec::new();
+
+        while let Some(child) = it.next_header()? {
+            match child.element_type() {
+                MkvElement::TrackPlane => {
+                    // Mandatory element.
+                    planes.push(it.read_master_element()?);
+                }
+                other => {
+                    // Unexpected child element.
+                    log::debug!("ignored {:?} child {:?}", hdr.element_type(), other);
+                }
+            }
+        }
+
+        Ok(Self { planes })
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug)]
+pub(crate) struct TrackPlaneElement {
+    pub(crate) uid: u64,
+    /// The kind of plane: 0 for the left eye, 1 for the right eye, and 2 for the background.
+    pub(crate) plane_type: u64,
+}
+
+impl EbmlElement<MkvSchema> for TrackPlaneElement {
+    const TYPE: MkvElement = MkvElement::TrackPlane;
+
+    fn read<R: ReadEbml>(it: &mut MkvEbmlIterator<R>, hdr: &MkvEbmlElementHeader) -> Result<Self> {
+        let mut uid = None;
+        let mut plane_type = None;
+
+        while let Some(child) = it.next_header()? {
+            match child.element_type() {
+                MkvElement::TrackPlaneUid => {
+                    // Mandatory element. No schema-defined default.
+                    uid = Some(it.read_u64_no_default()?);
+                }
+                MkvElement::TrackPlaneType => {
+                    // Mandatory element. No schema-defined default.
+                    plane_type = Some(it.read_u64_no_default()?);
+                }
+                other => {
+                    // Unexpected child element.
+                    log::debug!("ignored {:?} child {:?}", hdr.element_type(), other);
+                }
+            }
+        }
+
+        Ok(Self {
+            uid: uid.ok_or(EbmlError::ElementError("mkv: missing track plane uid"))?,
+            plane_type: plane_type
+                .ok_or(EbmlError::ElementError("mkv: missing track plane type"))?,
         })
     }
 }
 
+#[derive(Debug)]
+pub(crate) struct TrackJoinBlocksElement {
+    pub(crate) uids: Vec<u64>,
+}
+
+impl EbmlElement<MkvSchema> for TrackJoinBlocksElement {
+    const TYPE: MkvElement = MkvElement::TrackJoinBlocks;
+
+    fn read<R: ReadEbml>(it: &mut MkvEbmlIterator<R>, hdr: &MkvEbmlElementHeader) -> Result<Self> {
+        let mut uids = Vec::new();
+
+        while let Some(child) = it.next_header()? {
+            match child.element_type() {
+                MkvElement::TrackJoinUid => {
+                    // Mandatory element. No schema-defined default.
+                    uids.push(it.read_u64_no_default()?);
+                }
+                other => {
+                    // Unexpected child element.
+                    log::debug!("ignored {:?} child {:?}", hdr.element_type(), other);
+                }
+            }
+        }
+
+        Ok(Self { uids })
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub(crate) struct AudioElement {
@@ -626,6 +782,7 @@ impl EbmlElement<MkvSchema> for AudioElement {
 pub(crate) struct VideoElement {
     pub(crate) pixel_width: NonZeroU64,
     pub(crate) pixel_height: NonZeroU64,
+    pub(crate) projection: Option<ProjectionElement>,
 }
 
 impl EbmlElement<MkvSchema> for VideoElement {
@@ -634,6 +791,7 @@ impl EbmlElement<MkvSchema> for VideoElement {
     fn read<R: ReadEbml>(it: &mut MkvEbmlIterator<R>, hdr: &MkvEbmlElementHeader) -> Result<Self> {
         let mut pixel_width = None;
         let mut pixel_height = None;
+        let mut projection = None;
 
         while let Some(child) = it.next_header()? {
             match child.element_type() {
@@ -651,6 +809,10 @@ impl EbmlElement<MkvSchema> for VideoElement {
 
                     pixel_height = Some(val);
                 }
+                MkvElement::Projection => {
+                    // Non-mandatory element.
+                    projection = Some(it.read_master_element()?);
+                }
                 other => {
                     // Unexpected child element.
                     log::debug!("ignored {:?} child {:?}", hdr.element_type(), other);
@@ -662,6 +824,65 @@ impl EbmlElement<MkvSchema> for VideoElement {
             pixel_width: pixel_width.ok_or(EbmlError::ElementError("mkv: missing video width"))?,
             pixel_height: pixel_height
                 .ok_or(EbmlError::ElementError("mkv: missing video height"))?,
+            projection,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ProjectionElement {
+    pub(crate) projection_type: u64,
+    pub(crate) private: Option<Box<[u8]>>,
+    pub(crate) pose_yaw: f64,
+    pub(crate) pose_pitch: f64,
+    pub(crate) pose_roll: f64,
+}
+
+impl EbmlElement<MkvSchema> for ProjectionElement {
+    const TYPE: MkvElement = MkvElement::Projection;
+
+    fn read<R: ReadEbml>(it: &mut MkvEbmlIterator<R>, hdr: &MkvEbmlElementHeader) -> Result<Self> {
+        let mut projection_type = None;
+        let mut private = None;
+        let mut pose_yaw = None;
+        let mut pose_pitch = None;
+        let mut pose_roll = None;
+
+        while let Some(child) = it.next_header()? {
+            match child.element_type() {
+                MkvElement::ProjectionType => {
+                    // Mandatory element. Schema-defined default is 0 (rectangular).
+                    projection_type = it.read_u64()?;
+                }
+                MkvElement::ProjectionPrivate => {
+                    // Non-mandatory element. No schema-defined default.
+                    private = Some(it.read_binary()?);
+                }
+                MkvElement::ProjectionPoseYaw => {
+                    // Mandatory element. Schema-defined default is 0.0.
+                    pose_yaw = it.read_f64()?;
+                }
+                MkvElement::ProjectionPosePitch => {
+                    // Mandatory element. Schema-defined default is 0.0.
+                    pose_pitch = it.read_f64()?;
+                }
+                MkvElement::ProjectionPoseRoll => {
+                    // Mandatory element. Schema-defined default is 0.0.
+                    pose_roll = it.read_f64()?;
+                }
+                other => {
+                    // Unexpected child element.
+                    log::debug!("ignored {:?} child {:?}", hdr.element_type(), other);
+                }
+            }
+        }
+
+        Ok(Self {
+            projection_type: projection_type.unwrap_or(0),
+            private,
+            pose_yaw: pose_yaw.unwrap_or(0.0),
+            pose_pitch: pose_pitch.unwrap_or(0.0),
+            pose_roll: pose_roll.unwrap_or(0.0),
         })
     }
 }
@@ -1166,6 +1387,7 @@ pub(crate) struct BlockGroupElement {
     pub(crate) duration: Option<TrackTicks>,
     pub(crate) reference_block: Option<i64>,
     pub(crate) discard_padding: Option<SignedMatroskaTicks>,
+    pub(crate) additions: Vec<BlockMoreElement>,
 }
 
 impl EbmlElement<MkvSchema> for BlockGroupElement {
@@ -1176,9 +1398,15 @@ impl EbmlElement<MkvSchema> for BlockGroupElement {
         let mut block_duration = None;
         let mut reference_block = None;
         let mut discard_padding = None;
+        let mut additions = Vec::new();
 
         while let Some(child) = it.next_header()? {
             match child.element_type() {
+                MkvElement::BlockAdditions => {
+                    // Non-mandatory element.
+                    let elem = it.read_master_element::<BlockAdditionsElement>()?;
+                    additions.extend(elem.block_mores);
+                }
                 MkvElement::DiscardPadding => {
                     // Non-mandatory element. No schema-defined default.
                     discard_padding = Some(SignedMatroskaTicks::from(it.read_i64_no_default()?));
@@ -1207,6 +1435,72 @@ impl EbmlElement<MkvSchema> for BlockGroupElement {
             duration: block_duration,
             reference_block,
             discard_padding,
+            additions,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct BlockAdditionsElement {
+    pub(crate) block_mores: Vec<BlockMoreElement>,
+}
+
+impl EbmlElement<MkvSchema> for BlockAdditionsElement {
+    const TYPE: MkvElement = MkvElement::BlockAdditions;
+
+    fn read<R: ReadEbml>(it: &mut MkvEbmlIterator<R>, hdr: &MkvEbmlElementHeader) -> Result<Self> {
+        let mut block_mores = Vec::new();
+
+        while let Some(child) = it.next_header()? {
+            match child.element_type() {
+                MkvElement::BlockMore => {
+                    // Mandatory element.
+                    block_mores.push(it.read_master_element()?);
+                }
+                other => {
+                    // Unexpected child element.
+                    log::debug!("ignored {:?} child {:?}", hdr.element_type(), other);
+                }
+            }
+        }
+
+        Ok(Self { block_mores })
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct BlockMoreElement {
+    pub(crate) id: u64,
+    pub(crate) data: Box<[u8]>,
+}
+
+impl EbmlElement<MkvSchema> for BlockMoreElement {
+    const TYPE: MkvElement = MkvElement::BlockMore;
+
+    fn read<R: ReadEbml>(it: &mut MkvEbmlIterator<R>, hdr: &MkvEbmlElementHeader) -> Result<Self> {
+        let mut id = None;
+        let mut data = None;
+
+        while let Some(child) = it.next_header()? {
+            match child.element_type() {
+                MkvElement::BlockAddId => {
+                    // Mandatory element. Schema-defined default is 1.
+                    id = it.read_u64()?;
+                }
+                MkvElement::BlockAdditional => {
+                    // Mandatory element. No schema-defined default.
+                    data = Some(it.read_binary()?);
+                }
+                other => {
+                    // Unexpected child element.
+                    log::debug!("ignored {:?} child {:?}", hdr.element_type(), other);
+                }
+            }
+        }
+
+        Ok(Self {
+            id: id.unwrap_or(1),
+            data: data.ok_or(EbmlError::ElementError("mkv: missing block additional data"))?,
         })
     }
 }
@@ -0,0 +1,1488 @@
+use std::io::{Seek, SeekFrom};
+
+use symphonia_common::mpeg::video::nal::{NalUnitFraming, NalUnitIter, NalUnitSyntax};
+use symphonia_core::audio::{Channels, layouts};
+use symphonia_core::codecs::audio::well_known::profiles::{
+    CODEC_PROFILE_DTS_HD_MA, CODEC_PROFILE_TRUEHD_ATMOS,
+};
+use symphonia_core::codecs::audio::well_known::{CODEC_ID_DCA, CODEC_ID_MP3, CODEC_ID_TRUEHD};
+use symphonia_core::codecs::video::ProjectionType;
+use symphonia_core::codecs::video::well_known::extra_data::VIDEO_EXTRA_DATA_ID_AVC_DECODER_CONFIG;
+use symphonia_core::codecs::video::well_known::{CODEC_ID_H264, CODEC_ID_MPEG4};
+use symphonia_core::codecs::{CodecParameters, CodecProfile};
+use symphonia_core::common::Limit;
+use symphonia_core::formats::backfill::DurationBackfill;
+use symphonia_core::formats::probe::{Hint, Probe};
+use symphonia_core::formats::well_known::{FORMAT_ID_MKV, FORMAT_ID_WEBM};
+use symphonia_core::formats::{
+    FormatCapabilities, FormatOptions, FormatReader, NalFormat, SeekMode, SeekTo, TrackGroup,
+    TrackGroupKind, TrackType,
+};
+use symphonia_core::io::{MediaSourceStream, ReadOnlySource};
+use symphonia_core::meta::{Chapter, ChapterGroup, ChapterGroupItem, RawValue, StandardTag};
+use symphonia_core::packet::SideData;
+use symphonia_core::units::{FrameRate, Time, Timestamp};
+use symphonia_format_mkv::MkvReader;
+use symphonia_format_mkv::sub_fields::*;
+
+/// Write an EBML element with the given ID and payload. The element size is always encoded using
+/// 8 bytes.
+fn element(id: u32, payload: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    // Element ID, with its leading zero bytes stripped.
+    let id = id.to_be_bytes();
+    let skip = id.iter().take_while(|&&b| b == 0).count();
+    buf.extend_from_slice(&id[skip..]);
+
+    // 8-byte element size.
+    buf.push(0x01);
+    buf.extend_from_slice(&(payload.len() as u64).to_be_bytes()[1..]);
+
+    buf.extend_from_slice(payload);
+    buf
+}
+
+fn uint(id: u32, value: u64) -> Vec<u8> {
+    element(id, &value.to_be_bytes())
+}
+
+fn float(id: u32, value: f64) -> Vec<u8> {
+    element(id, &value.to_be_bytes())
+}
+
+fn string(id: u32, value: &str) -> Vec<u8> {
+    element(id, value.as_bytes())
+}
+
+fn master(id: u32, children: &[Vec<u8>]) -> Vec<u8> {
+    element(id, &children.concat())
+}
+
+fn make_ebml_header() -> Vec<u8> {
+    master(0x1a45dfa3, &[string(0x4282, "matroska")])
+}
+
+fn make_info() -> Vec<u8> {
+    master(0x1549a966, &[uint(0x2ad7b1, 1_000_000), string(0x4d80, "test"), string(0x5741, "test")])
+}
+
+/// Build a Tracks element with a single 16-bit stereo PCM track. The provided elements are
+/// appended to the track entry.
+fn make_tracks(track: &[Vec<u8>]) -> Vec<u8> {
+    let mut track_entry = vec![
+        uint(0xd7, 1),
+        uint(0x73c5, 1),
+        uint(0x83, 2),
+        string(0x86, "A_PCM/INT/LIT"),
+        master(0xe1, &[float(0xb5, 48000.0), uint(0x9f, 2), uint(0x6264, 16)]),
+    ];
+    track_entry.extend_from_slice(track);
+
+    master(0x1654ae6b, &[master(0xae, &track_entry)])
+}
+
+/// Build a minimal Matroska file with a single 16-bit stereo PCM track. The provided track
+/// elements are appended to the track entry, and the provided segment elements are appended to the
+/// segment after the Info and Tracks elements.
+fn make_mkv(track: &[Vec<u8>], segment: &[Vec<u8>]) -> Vec<u8> {
+    let mut children = vec![make_info(), make_tracks(track)];
+    children.extend_from_slice(segment);
+
+    [make_ebml_header(), master(0x18538067, &children)].concat()
+}
+
+fn open_mkv(data: Vec<u8>) -> MkvReader<'static> {
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    MkvReader::try_new(mss, Default::default()).unwrap()
+}
+
+/// Build a Cluster element containing a SimpleBlock for track 1 at each of the given relative
+/// timestamps.
+fn cluster(timestamp: u64, block_rel_ts: &[i16]) -> Vec<u8> {
+    let mut children = vec![uint(0xe7, timestamp)];
+
+    for rel_ts in block_rel_ts {
+        // Track number 1, relative timestamp, keyframe flag, and 4 bytes of frame data.
+        let mut block = vec![0x81];
+        block.extend_from_slice(&rel_ts.to_be_bytes());
+        block.extend_from_slice(&[0x80, 0, 0, 0, 0]);
+        children.push(element(0xa3, &block));
+    }
+
+    master(0x1f43b675, &children)
+}
+
+/// Read the timestamp and duration of all packets.
+fn read_packet_timing(reader: &mut MkvReader<'_>) -> Vec<(i64, u64)> {
+    let mut timing = Vec::new();
+    while let Some(packet) = reader.next_packet().unwrap() {
+        timing.push((packet.pts.get(), packet.dur.get()));
+    }
+    timing
+}
+
+/// Build a SeekHead element with a Seek entry for each element ID and Segment-relative position.
+fn seek_head(seeks: &[(u32, u64)]) -> Vec<u8> {
+    let seeks: Vec<_> = seeks
+        .iter()
+        .map(|&(id, pos)| {
+            let id = id.to_be_bytes();
+            let skip = id.iter().take_while(|&&b| b == 0).count();
+            master(0x4dbb, &[element(0x53ab, &id[skip..]), uint(0x53ac, pos)])
+        })
+        .collect();
+
+    master(0x114d9b74, &seeks)
+}
+
+/// Build a Chapters element with a single edition containing a single chapter.
+fn chapters(title: &str) -> Vec<u8> {
+    let atom = chapter_atom(1, 0, 1_000_000_000, &[chapter_display(title, "eng", None, None)]);
+    master(0x1043a770, &[master(0x45b9, &[uint(0x45bc, 1), atom])])
+}
+
+/// Build a Matroska file where a Chapters element follows the first Cluster, and is only
+/// discoverable through the SeekHead.
+fn make_mkv_with_trailing_chapters() -> Vec<u8> {
+    let info = make_info();
+    let tracks = make_tracks(&[]);
+    let cluster = cluster(0, &[0]);
+
+    // The size of the SeekHead does not depend on the seek position.
+    let head_len = seek_head(&[(0x1043a770, 0)]).len();
+    let chapters_pos = (head_len + info.len() + tracks.len() + cluster.len()) as u64;
+
+    let segment =
+        [seek_head(&[(0x1043a770, chapters_pos)]), info, tracks, cluster, chapters("End")];
+
+    [make_ebml_header(), master(0x18538067, &segment)].concat()
+}
+
+/// Build a ChapterAtom element.
+fn chapter_atom(uid: u64, start: u64, end: u64, children: &[Vec<u8>]) -> Vec<u8> {
+    let mut atom = vec![uint(0x73c4, uid), uint(0x91, start), uint(0x92, end)];
+    atom.extend_from_slice(children);
+    master(0xb6, &atom)
+}
+
+/// Build a ChapterDisplay element.
+fn chapter_display(
+    name: &str,
+    lang: &str,
+    lang_bcp47: Option<&str>,
+    country: Option<&str>,
+) -> Vec<u8> {
+    let mut display = vec![string(0x85, name), string(0x437c, lang)];
+    if let Some(lang_bcp47) = lang_bcp47 {
+        display.push(string(0x437d, lang_bcp47));
+    }
+    if let Some(country) = country {
+        display.push(string(0x437e, country));
+    }
+    master(0x80, &display)
+}
+
+fn as_chapter(item: &ChapterGroupItem) -> &Chapter {
+    match item {
+        ChapterGroupItem::Chapter(chapter) => chapter,
+        _ => panic!("expected a chapter"),
+    }
+}
+
+fn as_group(item: &ChapterGroupItem) -> &ChapterGroup {
+    match item {
+        ChapterGroupItem::Group(group) => group,
+        _ => panic!("expected a chapter group"),
+    }
+}
+
+/// Get the chapter titles, and their sub-fields, of a chapter.
+fn chapter_titles(chapter: &Chapter) -> Vec<(String, Vec<(String, String)>)> {
+    chapter
+        .tags
+        .iter()
+        .filter_map(|tag| match &tag.std {
+            Some(StandardTag::ChapterTitle(title)) => {
+                let sub_fields = tag
+                    .raw
+                    .sub_fields
+                    .iter()
+                    .flatten()
+                    .map(|sub_field| match &sub_field.value {
+                        RawValue::String(value) => (sub_field.field.clone(), value.to_string()),
+                        _ => panic!("expected a string sub-field"),
+                    })
+                    .collect();
+
+                Some((title.to_string(), sub_fields))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn sub_field(field: &str, value: &str) -> (String, String) {
+    (field.to_string(), value.to_string())
+}
+
+#[test]
+fn read_nested_chapters() {
+    const SEC: u64 = 1_000_000_000;
+
+    // Second-level chapters.
+    let part_1a =
+        chapter_atom(4, 10 * SEC, 20 * SEC, &[chapter_display("Part 1a", "eng", None, None)]);
+    let part_1b =
+        chapter_atom(5, 20 * SEC, 30 * SEC, &[chapter_display("Part 1b", "eng", None, None)]);
+
+    // First-level chapters.
+    let intro = chapter_atom(
+        2,
+        0,
+        10 * SEC,
+        &[
+            chapter_display("Intro", "eng", None, Some("us")),
+            chapter_display("Einleitung", "ger", Some("de-AT"), Some("at")),
+        ],
+    );
+    let part_1 = chapter_atom(
+        3,
+        10 * SEC,
+        30 * SEC,
+        &[chapter_display("Part 1", "eng", None, None), part_1a, part_1b],
+    );
+
+    // A hidden chapter must not be exposed.
+    let hidden = master(
+        0xb6,
+        &[
+            uint(0x73c4, 6),
+            uint(0x91, 30 * SEC),
+            uint(0x98, 1),
+            chapter_display("Hidden", "eng", None, None),
+        ],
+    );
+
+    let edition = master(0x45b9, &[uint(0x45bc, 1), uint(0x45db, 1), intro, part_1, hidden]);
+
+    let reader = open_mkv(make_mkv(&[], &[master(0x1043a770, &[edition])]));
+
+    let chapters = reader.chapters().expect("expected chapters");
+    assert_eq!(chapters.items.len(), 2);
+
+    // A chapter without nested chapters is a plain chapter item.
+    let intro = as_chapter(&chapters.items[0]);
+    assert_eq!(intro.start_time, Time::from_nanos_u64(0));
+    assert_eq!(intro.end_time, Some(Time::from_nanos_u64(10 * SEC)));
+    assert_eq!(
+        chapter_titles(intro),
+        vec![
+            (
+                "Intro".to_string(),
+                vec![
+                    sub_field(CHAPTER_TITLE_LANGUAGE, "eng"),
+                    sub_field(CHAPTER_TITLE_COUNTRY, "us")
+                ]
+            ),
+            // The BCP47 language takes precedence over the ISO 639-2 language and country.
+            ("Einleitung".to_string(), vec![sub_field(CHAPTER_TITLE_LANGUAGE_BCP47, "de-AT")]),
+        ]
+    );
+
+    // A chapter with nested chapters is a group containing the chapter followed by a group of the
+    // nested chapters.
+    let part_1_group = as_group(&chapters.items[1]);
+    assert_eq!(part_1_group.items.len(), 2);
+
+    let part_1 = as_chapter(&part_1_group.items[0]);
+    assert_eq!(part_1.start_time, Time::from_nanos_u64(10 * SEC));
+    assert_eq!(part_1.end_time, Some(Time::from_nanos_u64(30 * SEC)));
+    assert_eq!(
+        chapter_titles(part_1),
+        vec![("Part 1".to_string(), vec![sub_field(CHAPTER_TITLE_LANGUAGE, "eng")])]
+    );
+
+    let nested = as_group(&part_1_group.items[1]);
+    assert_eq!(nested.items.len(), 2);
+
+    let part_1a = as_chapter(&nested.items[0]);
+    assert_eq!(part_1a.start_time, Time::from_nanos_u64(10 * SEC));
+    assert_eq!(chapter_titles(part_1a)[0].0, "Part 1a");
+
+    let part_1b = as_chapter(&nested.items[1]);
+    assert_eq!(part_1b.start_time, Time::from_nanos_u64(20 * SEC));
+    assert_eq!(part_1b.end_time, Some(Time::from_nanos_u64(30 * SEC)));
+    assert_eq!(chapter_titles(part_1b)[0].0, "Part 1b");
+}
+
+#[test]
+fn read_default_edition_chapters() {
+    // The first non-hidden default edition is selected over earlier non-default editions.
+    let first = master(
+        0x45b9,
+        &[
+            uint(0x45bc, 1),
+            chapter_atom(1, 0, 1000, &[chapter_display("First", "eng", None, None)]),
+        ],
+    );
+    let default = master(
+        0x45b9,
+        &[
+            uint(0x45bc, 2),
+            uint(0x45db, 1),
+            chapter_atom(2, 0, 1000, &[chapter_display("Default", "eng", None, None)]),
+        ],
+    );
+
+    let reader = open_mkv(make_mkv(&[], &[master(0x1043a770, &[first, default])]));
+
+    let chapters = reader.chapters().expect("expected chapters");
+    assert_eq!(chapters.items.len(), 1);
+    assert_eq!(chapter_titles(as_chapter(&chapters.items[0]))[0].0, "Default");
+}
+
+#[test]
+fn read_track_timestamp_scale() {
+    // The Segment timebase is 1 ms. The track timestamp scale is 2.0, so the track timebase is
+    // 2 ms, and each frame is 20 ms (10 track ticks) long.
+    let track = [float(0x23314f, 2.0), uint(0x23e383, 20_000_000)];
+
+    // The cluster timestamp is 1 s (1000 Segment ticks, or 500 track ticks). Block timestamps are
+    // relative to the cluster in track ticks.
+    let mut reader = open_mkv(make_mkv(&track, &[cluster(1000, &[0, 10, 20])]));
+
+    let tb = reader.tracks()[0].time_base.unwrap();
+    assert_eq!((tb.numer.get(), tb.denom.get()), (1, 500));
+
+    assert_eq!(read_packet_timing(&mut reader), vec![(500, 10), (510, 10), (520, 10)]);
+}
+
+#[test]
+fn read_fractional_track_timestamp_scale() {
+    // The Segment timebase is 1 ms. The track timestamp scale is 1.5, so the track timebase is
+    // 1.5 ms, and each frame is 15 ms (10 track ticks) long.
+    let track = [float(0x23314f, 1.5), uint(0x23e383, 15_000_000)];
+
+    // The cluster timestamp is 3 s (3000 Segment ticks, or 2000 track ticks).
+    let mut reader = open_mkv(make_mkv(&track, &[cluster(3000, &[0, 10])]));
+
+    let tb = reader.tracks()[0].time_base.unwrap();
+    assert_eq!((tb.numer.get(), tb.denom.get()), (3, 2000));
+
+    assert_eq!(read_packet_timing(&mut reader), vec![(2000, 10), (2010, 10)]);
+}
+
+#[test]
+fn read_without_default_duration() {
+    // Without a default duration, or a block duration, frames have an unknown (0) duration.
+    let mut reader = open_mkv(make_mkv(&[], &[cluster(1000, &[0, 20])]));
+
+    assert_eq!(read_packet_timing(&mut reader), vec![(1000, 0), (1020, 0)]);
+}
+
+/// Build a block for track 1 with the given relative timestamp and flags, and 4 bytes of frame
+/// data.
+fn block(rel_ts: i16, flags: u8) -> Vec<u8> {
+    let mut block = vec![0x81];
+    block.extend_from_slice(&rel_ts.to_be_bytes());
+    block.extend_from_slice(&[flags, 0, 0, 0, 0]);
+    block
+}
+
+#[test]
+fn read_keyframes() {
+    let cluster = master(
+        0x1f43b675,
+        &[
+            uint(0xe7, 0),
+            // A simple block with, and without, the keyframe flag.
+            element(0xa3, &block(0, 0x80)),
+            element(0xa3, &block(10, 0x00)),
+            // A block group referencing an earlier block, and one without any references.
+            master(0xa0, &[element(0xa1, &block(20, 0x00)), uint(0xfb, (-10i64) as u64)]),
+            master(0xa0, &[element(0xa1, &block(30, 0x00))]),
+        ],
+    );
+
+    let mut reader = open_mkv(make_mkv(&[], &[cluster]));
+
+    let mut keyframes = Vec::new();
+    while let Some(packet) = reader.next_packet().unwrap() {
+        keyframes.push(packet.is_keyframe);
+    }
+    assert_eq!(keyframes, vec![true, false, false, true]);
+}
+
+#[test]
+fn seek_to_keyframe() {
+    // Each frame is 10 ms long. Only the first and third frames are keyframes.
+    let track = [uint(0x23e383, 10_000_000)];
+    let cluster = master(
+        0x1f43b675,
+        &[
+            uint(0xe7, 0),
+            element(0xa3, &block(0, 0x80)),
+            element(0xa3, &block(10, 0x00)),
+            element(0xa3, &block(20, 0x80)),
+            element(0xa3, &block(30, 0x00)),
+        ],
+    );
+
+    let mut reader = open_mkv(make_mkv(&track, &[cluster]));
+
+    let mut seek = |ts: i64| {
+        let seeked = reader
+            .seek(SeekMode::Accurate, SeekTo::Timestamp { ts: Timestamp::new(ts), track_id: 1 })
+            .unwrap();
+        assert_eq!(seeked.required_ts, Timestamp::new(ts));
+        seeked.actual_ts.get()
+    };
+
+    // Seeking to the middle of a group of pictures lands on the preceding keyframe, while seeking
+    // to a keyframe lands on that keyframe.
+    assert_eq!(seek(15), 0);
+    assert_eq!(seek(25), 20);
+    assert_eq!(seek(35), 20);
+
+    // Reading resumes from the keyframe.
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!(packet.pts, Timestamp::new(20));
+    assert!(packet.is_keyframe);
+
+    // The next frame follows the keyframe. Seeking to it, or to an earlier frame, rescans the
+    // clusters for the preceding keyframe.
+    assert_eq!(reader.next_packet().unwrap().unwrap().pts, Timestamp::new(30));
+    assert!(reader.next_packet().unwrap().is_none());
+
+    let mut seek = |ts: i64| {
+        let seeked = reader
+            .seek(SeekMode::Accurate, SeekTo::Timestamp { ts: Timestamp::new(ts), track_id: 1 })
+            .unwrap();
+        seeked.actual_ts.get()
+    };
+
+    assert_eq!(seek(35), 20);
+    assert_eq!(seek(5), 0);
+}
+
+#[test]
+fn seek_unseekable_forward() {
+    // Each frame is 10 ms long. Only the first and third frames are keyframes.
+    let track = [uint(0x23e383, 10_000_000)];
+    let cluster = master(
+        0x1f43b675,
+        &[
+            uint(0xe7, 0),
+            element(0xa3, &block(0, 0x80)),
+            element(0xa3, &block(10, 0x00)),
+            element(0xa3, &block(20, 0x80)),
+            element(0xa3, &block(30, 0x00)),
+        ],
+    );
+
+    let source = ReadOnlySource::new(std::io::Cursor::new(make_mkv(&track, &[cluster])));
+    let mss = MediaSourceStream::new(Box::new(source), Default::default());
+    let mut reader = MkvReader::try_new(mss, Default::default()).unwrap();
+
+    // The reader cannot return to the keyframe preceding the frame containing the timestamp, and
+    // lands on that frame instead.
+    let seeked = reader
+        .seek(SeekMode::Accurate, SeekTo::Timestamp { ts: Timestamp::new(35), track_id: 1 })
+        .unwrap();
+    assert_eq!(seeked.actual_ts, Timestamp::new(30));
+
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!(packet.pts, Timestamp::new(30));
+    assert!(!packet.is_keyframe);
+}
+
+#[test]
+fn seek_trims_audio_frames() {
+    // Each frame is 10 ms long. The time base is 1 ms, while the sample rate is 48 kHz.
+    let track = [uint(0x23e383, 10_000_000)];
+    let mut reader = open_mkv(make_mkv(&track, &[cluster(0, &[0, 10, 20])]));
+
+    let seeked = reader
+        .seek(SeekMode::Accurate, SeekTo::Timestamp { ts: Timestamp::new(15), track_id: 1 })
+        .unwrap();
+    assert_eq!(seeked.actual_ts, Timestamp::new(10));
+
+    // The 5 ms preceding the seek position are trimmed as 240 audio frames.
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!(packet.pts, Timestamp::new(10));
+    assert_eq!((packet.trim_start.get(), packet.dur.get()), (240, 5));
+
+    // The following packets are not trimmed.
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!((packet.trim_start.get(), packet.dur.get()), (0, 10));
+}
+
+#[test]
+fn skip_unknown_elements() {
+    // A vendor-specific element, and a vendor-specific element with an unknown size.
+    let vendor = element(0x5fff, &[1, 2, 3]);
+    let vendor_unknown_size = [0x5f, 0xfe, 0xff, 4, 5, 6].to_vec();
+
+    let clusters = [
+        master(
+            0x1f43b675,
+            &[
+                uint(0xe7, 0),
+                element(0xa3, &block(0, 0x80)),
+                vendor.clone(),
+                element(0xa3, &block(10, 0x80)),
+                vendor_unknown_size,
+            ],
+        ),
+        master(0x1f43b675, &[uint(0xe7, 20), vendor.clone(), element(0xa3, &block(0, 0x80))]),
+    ];
+
+    let segment = [vendor.clone(), clusters.concat()];
+    let mut reader = open_mkv(make_mkv(&[vendor], &segment));
+
+    // The unknown elements are skipped, and an unknown element with an unknown size ends its
+    // parent.
+    assert_eq!(read_packet_timing(&mut reader), vec![(0, 0), (10, 0), (20, 0)]);
+}
+
+#[test]
+fn read_unknown_codec_track_type() {
+    // Override the track type and codec ID with a video track of an unknown codec.
+    let reader = open_mkv(make_mkv(&[uint(0x83, 1), string(0x86, "V_UNKNOWN")], &[]));
+
+    // The track type is known from the track entry, even though the codec is not.
+    let track = &reader.tracks()[0];
+    assert!(track.codec_params.is_none());
+    assert_eq!(track.track_type(), Some(TrackType::Video));
+    assert!(reader.first_track(TrackType::Audio).is_none());
+}
+
+#[test]
+fn read_track_operations() {
+    let video_track = |number: u64, children: &[Vec<u8>]| {
+        let mut entry = vec![uint(0xd7, number), uint(0x73c5, number), uint(0x83, 1)];
+        entry.push(string(0x86, "V_UNKNOWN"));
+        entry.extend_from_slice(children);
+        master(0xae, &entry)
+    };
+
+    let plane =
+        |uid: u64, plane_type: u64| master(0xe4, &[uint(0xe5, uid), uint(0xe6, plane_type)]);
+
+    // Tracks 1 and 2 are the left and right eye planes of track 3. The blocks of tracks 1 and 2
+    // are also joined to create track 4.
+    let combine = master(0xe2, &[master(0xe3, &[plane(1, 0), plane(2, 1)])]);
+    let join = master(0xe2, &[master(0xe9, &[uint(0xed, 1), uint(0xed, 2)])]);
+
+    let tracks = master(
+        0x1654ae6b,
+        &[
+            video_track(1, &[]),
+            video_track(2, &[]),
+            video_track(3, &[combine]),
+            video_track(4, &[join]),
+        ],
+    );
+
+    let data = [make_ebml_header(), master(0x18538067, &[make_info(), tracks])].concat();
+    let reader = open_mkv(data.clone());
+
+    let track_groups = vec![
+        TrackGroup { kind: TrackGroupKind::CombinePlanes, id: 3, track_ids: vec![1, 2] },
+        TrackGroup { kind: TrackGroupKind::JoinBlocks, id: 4, track_ids: vec![1, 2] },
+    ];
+    assert_eq!(reader.track_groups(), track_groups);
+
+    // The track groups are forwarded when packet durations are backfilled.
+    let reader = DurationBackfill::new(Box::new(open_mkv(data)));
+    assert_eq!(reader.track_groups(), track_groups);
+}
+
+#[test]
+fn read_seek_head_chapters() {
+    let mut reader = open_mkv(make_mkv_with_trailing_chapters());
+
+    // The chapters following the first cluster are located using the SeekHead.
+    let chapters = reader.chapters().expect("expected chapters");
+    assert_eq!(chapter_titles(as_chapter(&chapters.items[0]))[0].0, "End");
+
+    // Reading resumes from the first cluster.
+    assert_eq!(read_packet_timing(&mut reader), vec![(0, 0)]);
+}
+
+#[test]
+fn read_seek_head_unseekable() {
+    let data = make_mkv_with_trailing_chapters();
+
+    let source = ReadOnlySource::new(std::io::Cursor::new(data));
+    let mss = MediaSourceStream::new(Box::new(source), Default::default());
+    let mut reader = MkvReader::try_new(mss, Default::default()).unwrap();
+
+    // The SeekHead cannot be followed if the source is unseekable.
+    assert!(reader.chapters().is_none());
+
+    let packet = reader.next_packet().unwrap().expect("expected a packet");
+    assert_eq!(packet.pts.get(), 0);
+}
+
+/// Build a WAVEFORMATEX structure for MPEG-1 Layer 3 audio.
+fn make_mp3_wave_format_ex() -> Vec<u8> {
+    let mut buf = Vec::new();
+    buf.extend_from_slice(&0x0055u16.to_le_bytes());
+    buf.extend_from_slice(&2u16.to_le_bytes());
+    buf.extend_from_slice(&44100u32.to_le_bytes());
+    buf.extend_from_slice(&16000u32.to_le_bytes());
+    buf.extend_from_slice(&1u16.to_le_bytes());
+    buf.extend_from_slice(&0u16.to_le_bytes());
+    // MPEGLAYER3WAVEFORMAT extra data.
+    buf.extend_from_slice(&12u16.to_le_bytes());
+    buf.extend_from_slice(&[1, 0, 2, 0, 0, 0, 0x9c, 0x01, 1, 0, 0x71, 0x05]);
+    buf
+}
+
+/// Build a BITMAPINFOHEADER structure for XviD video, followed by extra data.
+fn make_xvid_bitmap_info_header(extra: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    buf.extend_from_slice(&40u32.to_le_bytes());
+    buf.extend_from_slice(&320i32.to_le_bytes());
+    buf.extend_from_slice(&240i32.to_le_bytes());
+    buf.extend_from_slice(&1u16.to_le_bytes());
+    buf.extend_from_slice(&24u16.to_le_bytes());
+    buf.extend_from_slice(b"XVID");
+    buf.extend_from_slice(&[0; 20]);
+    buf.extend_from_slice(extra);
+    buf
+}
+
+#[test]
+fn read_microsoft_compatibility_codecs() {
+    // Tracks remuxed from an AVI file.
+    let audio = master(
+        0xae,
+        &[
+            uint(0xd7, 1),
+            uint(0x73c5, 1),
+            uint(0x83, 2),
+            string(0x86, "A_MS/ACM"),
+            element(0x63a2, &make_mp3_wave_format_ex()),
+            master(0xe1, &[float(0xb5, 44100.0), uint(0x9f, 2)]),
+        ],
+    );
+    let video = master(
+        0xae,
+        &[
+            uint(0xd7, 2),
+            uint(0x73c5, 2),
+            uint(0x83, 1),
+            string(0x86, "V_MS/VFW/FOURCC"),
+            element(0x63a2, &make_xvid_bitmap_info_header(&[0, 0, 1, 0xb0, 0xf5])),
+            master(0xe0, &[uint(0xb0, 320), uint(0xba, 240)]),
+        ],
+    );
+
+    let segment = [make_info(), master(0x1654ae6b, &[audio, video])];
+    let reader = open_mkv([make_ebml_header(), master(0x18538067, &segment)].concat());
+
+    let tracks = reader.tracks();
+    assert_eq!(tracks.len(), 2);
+
+    match &tracks[0].codec_params {
+        Some(CodecParameters::Audio(params)) => {
+            assert_eq!(params.codec, CODEC_ID_MP3);
+            assert_eq!(params.sample_rate, Some(44100));
+            // The extra data follows the WAVEFORMATEX structure.
+            assert_eq!(params.extra_data.as_ref().map(|data| data.len()), Some(12));
+        }
+        _ => panic!("expected audio codec parameters"),
+    }
+
+    match &tracks[1].codec_params {
+        Some(CodecParameters::Video(params)) => {
+            assert_eq!(params.codec, CODEC_ID_MPEG4);
+            assert_eq!((params.width, params.height), (Some(320), Some(240)));
+            // The extra data follows the BITMAPINFOHEADER structure.
+            assert_eq!(params.extra_data.len(), 1);
+            assert_eq!(&*params.extra_data[0].data, &[0, 0, 1, 0xb0, 0xf5]);
+        }
+        _ => panic!("expected video codec parameters"),
+    }
+}
+
+/// Build an AVCDecoderConfigurationRecord for High profile, level 4.0, 1920x1080 video at 23.976
+/// fps with 4 byte NAL unit length prefixes.
+fn make_avc_decoder_config() -> Vec<u8> {
+    let sps = [
+        0x67, 0x64, 0x00, 0x28, 0xac, 0xd9, 0x40, 0x78, 0x02, 0x27, 0xe5, 0xc0, 0x5a, 0x80, 0x80,
+        0x80, 0xa0, 0x00, 0x00, 0x7d, 0x20, 0x00, 0x17, 0x70, 0x10, 0x80,
+    ];
+    let pps = [0x68, 0xeb, 0xe3, 0xcb, 0x22, 0xc0];
+
+    [
+        [1, 0x64, 0x00, 0x28, 0xff, 0xe1].as_slice(),
+        &(sps.len() as u16).to_be_bytes(),
+        &sps,
+        &[1],
+        &(pps.len() as u16).to_be_bytes(),
+        &pps,
+    ]
+    .concat()
+}
+
+/// Build a Matroska file with a single 1920x1080 H.264 video track, and a cluster containing the
+/// given frame as a keyframe.
+/// Build a Matroska file with a single 48 kHz 6 channel audio track of the given codec, with one
+/// block containing the given frame.
+fn make_audio_mkv(codec_id: &str, frame: &[u8]) -> Vec<u8> {
+    let audio = master(
+        0xae,
+        &[
+            uint(0xd7, 1),
+            uint(0x73c5, 1),
+            uint(0x83, 2),
+            string(0x86, codec_id),
+            master(0xe1, &[float(0xb5, 48000.0), uint(0x9f, 6)]),
+        ],
+    );
+
+    let block = [[0x81, 0, 0, 0x80].as_slice(), frame].concat();
+    let cluster = master(0x1f43b675, &[uint(0xe7, 0), element(0xa3, &block)]);
+
+    let segment = [make_info(), master(0x1654ae6b, &[audio]), cluster];
+    [make_ebml_header(), master(0x18538067, &segment)].concat()
+}
+
+/// Build a DTS frame consisting of a 64 byte 48 kHz 5.1 core frame, followed by an extension
+/// substream with a single 48 kHz 7.1 audio asset coded with the lossless (XLL) extension.
+fn make_dts_xll_frame() -> Vec<u8> {
+    // The core frame header: a normal frame of 16 blocks, 5 front channels at 48 kHz, and LFE.
+    let mut frame = vec![0x7f, 0xfe, 0x80, 0x01, 0xfc, 0x3c, 0x03, 0xf2, 0x75, 0xe0, 0x02];
+    frame.resize(64, 0);
+
+    // The 23 byte extension substream header, with static fields, and the descriptor of a single
+    // 32 byte asset. The asset is 24-bit, 48 kHz, has 8 channels with the C, L/R, Ls/Rs, LFE1,
+    // and Lsr/Rsr loudspeaker mask (0x004f), and uses the lossless coding mode.
+    frame.extend_from_slice(&[
+        0x64, 0x58, 0x20, 0x25, 0x00, 0x02, 0xc0, 0x06, 0xd0, 0x00, 0x80, 0x00, 0x07, 0xc0, 0x00,
+        0x5f, 0x01, 0xe7, 0x00, 0x4f, 0x02, 0x00, 0x3e,
+    ]);
+
+    // The lossless extension.
+    frame.extend_from_slice(&[0x41, 0xa2, 0x95, 0x47]);
+    frame.resize(64 + 55, 0);
+    frame
+}
+
+#[test]
+fn read_dts_first_frame() {
+    let frame = make_dts_xll_frame();
+    let mut reader = open_mkv(make_audio_mkv("A_DTS", &frame));
+
+    // The profile and channels of the lossless extension are read from the first frame.
+    match &reader.tracks()[0].codec_params {
+        Some(CodecParameters::Audio(params)) => {
+            assert_eq!(params.codec, CODEC_ID_DCA);
+            assert_eq!(params.profile, Some(CODEC_PROFILE_DTS_HD_MA));
+            assert_eq!(params.sample_rate, Some(48000));
+            assert_eq!(params.channels, Some(layouts::CHANNEL_LAYOUT_7P1));
+        }
+        _ => panic!("expected audio codec parameters"),
+    }
+
+    // Reading starts from the first frame.
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!(&*packet.data, frame.as_slice());
+
+    // An unseekable source cannot be rewound after reading the first frame.
+    let source = ReadOnlySource::new(std::io::Cursor::new(make_audio_mkv("A_DTS", &frame)));
+    let mss = MediaSourceStream::new(Box::new(source), Default::default());
+    let reader = MkvReader::try_new(mss, Default::default()).unwrap();
+
+    match &reader.tracks()[0].codec_params {
+        Some(CodecParameters::Audio(params)) => {
+            assert_eq!(params.profile, None);
+            assert_eq!(params.channels, Some(Channels::Discrete(6)));
+        }
+        _ => panic!("expected audio codec parameters"),
+    }
+}
+
+#[test]
+fn read_aac_num_frames() {
+    // An AAC-LC 48 kHz stereo track. Each frame of 1024 audio frames is 21.3 ms long, which cannot
+    // be represented exactly by the 1 ms time base.
+    let audio = master(
+        0xae,
+        &[
+            uint(0xd7, 1),
+            uint(0x73c5, 1),
+            uint(0x83, 2),
+            string(0x86, "A_AAC"),
+            element(0x63a2, &[0x11, 0x90]),
+            master(0xe1, &[float(0xb5, 48000.0), uint(0x9f, 2)]),
+        ],
+    );
+
+    let segment = [make_info(), master(0x1654ae6b, &[audio]), cluster(0, &[0, 21, 43])];
+    let mut reader = open_mkv([make_ebml_header(), master(0x18538067, &segment)].concat());
+
+    // The number of frames per packet is known from the audio specific configuration.
+    let mut num_frames = Vec::new();
+    while let Some(packet) = reader.next_packet().unwrap() {
+        num_frames.push(packet.num_frames);
+    }
+
+    assert_eq!(num_frames, vec![Some(1024); 3]);
+
+    // The number of frames of a PCM track cannot be derived from the duration of its frames, since
+    // a tick of the time base is longer than an audio frame.
+    let mut reader = open_mkv(make_mkv(&[], &[cluster(0, &[0, 10])]));
+    assert_eq!(reader.next_packet().unwrap().unwrap().num_frames, None);
+}
+
+/// Build the first access unit of a 48 kHz 7.1 TrueHD stream, up-to the substream information. The
+/// major sync may signal a 16-channel presentation.
+fn make_truehd_access_unit(has_16ch_presentation: bool) -> Vec<u8> {
+    // 48 kHz, 5.1 for the 6-channel presentation, and 7.1 for the 8-channel presentation.
+    let format_info = 0x0007_804fu32.to_be_bytes();
+
+    // The access unit header, followed by the major sync up-to the substream information. Four
+    // substreams are present.
+    [
+        [0xf0, 0x20, 0x00, 0x00, 0xf8, 0x72, 0x6f, 0xba].as_slice(),
+        &format_info,
+        &[0xb7, 0x52, 0x00, 0x00, 0x00, 0x00, 0x80, 0x00, 0x40],
+        &[if has_16ch_presentation { 0x80 } else { 0x00 }],
+        &[0; 42],
+    ]
+    .concat()
+}
+
+#[test]
+fn read_truehd_first_frame() {
+    let mut reader = open_mkv(make_audio_mkv("A_TRUEHD", &make_truehd_access_unit(true)));
+
+    match &reader.tracks()[0].codec_params {
+        Some(CodecParameters::Audio(params)) => {
+            assert_eq!(params.codec, CODEC_ID_TRUEHD);
+            assert_eq!(params.profile, Some(CODEC_PROFILE_TRUEHD_ATMOS));
+            assert_eq!(params.sample_rate, Some(48000));
+            assert_eq!(params.channels, Some(layouts::CHANNEL_LAYOUT_7P1));
+        }
+        _ => panic!("expected audio codec parameters"),
+    }
+
+    // Reading starts from the first frame.
+    assert!(reader.next_packet().unwrap().is_some());
+
+    let reader = open_mkv(make_audio_mkv("A_TRUEHD", &make_truehd_access_unit(false)));
+
+    match &reader.tracks()[0].codec_params {
+        Some(CodecParameters::Audio(params)) => {
+            assert_eq!(params.profile, None);
+            assert_eq!(params.channels, Some(layouts::CHANNEL_LAYOUT_7P1));
+        }
+        _ => panic!("expected audio codec parameters"),
+    }
+}
+
+fn make_h264_mkv(frame: &[u8]) -> Vec<u8> {
+    let video = master(
+        0xae,
+        &[
+            uint(0xd7, 1),
+            uint(0x73c5, 1),
+            uint(0x83, 1),
+            string(0x86, "V_MPEG4/ISO/AVC"),
+            element(0x63a2, &make_avc_decoder_config()),
+            master(0xe0, &[uint(0xb0, 1920), uint(0xba, 1080)]),
+        ],
+    );
+
+    let block = [[0x81, 0, 0, 0x80].as_slice(), frame].concat();
+    let cluster = master(0x1f43b675, &[uint(0xe7, 0), element(0xa3, &block)]);
+
+    let segment = [make_info(), master(0x1654ae6b, &[video]), cluster];
+    [make_ebml_header(), master(0x18538067, &segment)].concat()
+}
+
+#[test]
+fn read_h264_codec_private() {
+    // A SEI and an IDR slice NAL unit, each with a 4 byte length prefix.
+    let frame = [0, 0, 0, 3, 0x06, 0x05, 0x01, 0, 0, 0, 2, 0x65, 0x88];
+
+    let mut reader = open_mkv(make_h264_mkv(&frame));
+
+    match &reader.tracks()[0].codec_params {
+        Some(CodecParameters::Video(params)) => {
+            assert_eq!(params.codec, CODEC_ID_H264);
+            assert_eq!(params.profile, Some(CodecProfile::new(100)));
+            assert_eq!(params.level, Some(40));
+            assert_eq!((params.width, params.height), (Some(1920), Some(1080)));
+            assert_eq!(params.frame_rate, FrameRate::try_new(24000, 1001));
+            // The codec private data is the decoder configuration record.
+            assert_eq!(params.extra_data.len(), 1);
+            assert_eq!(params.extra_data[0].id, VIDEO_EXTRA_DATA_ID_AVC_DECODER_CONFIG);
+            assert_eq!(&*params.extra_data[0].data, make_avc_decoder_config().as_slice());
+        }
+        _ => panic!("expected video codec parameters"),
+    }
+
+    // The frame is returned as-is, with length prefixed NAL units.
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!(&*packet.data, frame.as_slice());
+
+    let nal_unit_types =
+        NalUnitIter::new(&packet.data, NalUnitSyntax::Avc, NalUnitFraming::LengthPrefixed(4))
+            .unwrap()
+            .map(|nal_unit| nal_unit.unwrap().0)
+            .collect::<Vec<_>>();
+
+    assert_eq!(nal_unit_types, vec![6, 5]);
+}
+
+#[test]
+fn read_h264_annex_b() {
+    // A SEI and an IDR slice NAL unit, each with a 4 byte length prefix.
+    let frame = [0, 0, 0, 3, 0x06, 0x05, 0x01, 0, 0, 0, 2, 0x65, 0x88];
+
+    let mss = MediaSourceStream::new(
+        Box::new(std::io::Cursor::new(make_h264_mkv(&frame))),
+        Default::default(),
+    );
+    let opts = FormatOptions::default().nal_format(NalFormat::AnnexB);
+    let mut reader = MkvReader::try_new(mss, opts).unwrap();
+
+    // The parameter sets of the decoder configuration record precede the NAL units of the
+    // keyframe, and each NAL unit is prefixed by a start code.
+    let config = make_avc_decoder_config();
+    let expected = [
+        [0, 0, 0, 1].as_slice(),
+        &config[8..34],
+        &[0, 0, 0, 1],
+        &config[37..],
+        &[0, 0, 0, 1, 0x06, 0x05, 0x01],
+        &[0, 0, 0, 1, 0x65, 0x88],
+    ]
+    .concat();
+
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert!(packet.data.starts_with(&[0, 0, 0, 1, 0x67]));
+    assert_eq!(&*packet.data, expected.as_slice());
+
+    // The frame is converted when reading into a caller-provided buffer.
+    reader.reset().unwrap();
+
+    let mut buf = Vec::new();
+    let packet = reader.next_packet_into(&mut buf).unwrap().unwrap();
+    assert_eq!(packet.data, expected.as_slice());
+}
+
+#[test]
+fn read_h264_annex_b_invalid_config() {
+    let frame = [0, 0, 0, 3, 0x06, 0x05, 0x01, 0, 0, 0, 2, 0x65, 0x88];
+
+    // Change the version of the decoder configuration record to an unknown version.
+    let mut data = make_h264_mkv(&frame);
+    let pos = data.windows(6).position(|bytes| bytes == [1, 0x64, 0x00, 0x28, 0xff, 0xe1]).unwrap();
+    data[pos] = 2;
+
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let opts = FormatOptions::default().nal_format(NalFormat::AnnexB);
+    let mut reader = MkvReader::try_new(mss, opts).unwrap();
+
+    // The frame cannot be converted, so it is returned as-is.
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!(&*packet.data, frame.as_slice());
+}
+
+#[test]
+fn read_non_contiguous_track_numbers() {
+    let track = |number: u64, uid: u64| {
+        master(
+            0xae,
+            &[
+                uint(0xd7, number),
+                uint(0x73c5, uid),
+                uint(0x83, 2),
+                string(0x86, "A_PCM/INT/LIT"),
+                master(0xe1, &[float(0xb5, 48000.0), uint(0x9f, 2), uint(0x6264, 16)]),
+            ],
+        )
+    };
+
+    // A frame of track 5, followed by a frame of track 2.
+    let cluster = master(
+        0x1f43b675,
+        &[
+            uint(0xe7, 0),
+            element(0xa3, &[0x85, 0, 0, 0x80, 5, 5, 5, 5]),
+            element(0xa3, &[0x82, 0, 0, 0x80, 2, 2, 2, 2]),
+        ],
+    );
+
+    let segment = [make_info(), master(0x1654ae6b, &[track(2, 1), track(5, 2)]), cluster];
+    let mut reader = open_mkv([make_ebml_header(), master(0x18538067, &segment)].concat());
+
+    let ids = reader.tracks().iter().map(|track| track.id).collect::<Vec<_>>();
+    assert_eq!(ids, vec![2, 5]);
+
+    // The track of every packet can be found by its ID.
+    let mut packet_ids = Vec::new();
+
+    while let Some(packet) = reader.next_packet().unwrap() {
+        let track = reader.track_by_id(packet.track_id).unwrap();
+        assert_eq!(track.id, packet.track_id);
+        assert_eq!(packet.data[0], packet.track_id as u8);
+        packet_ids.push(packet.track_id);
+    }
+
+    assert_eq!(packet_ids, vec![5, 2]);
+
+    assert!(reader.track_by_id(1).is_none());
+    assert!(reader.track_by_id(3).is_none());
+}
+
+#[test]
+fn read_video_projection() {
+    // An equirectangular 360 degree VP9 video track.
+    let projection = master(
+        0x7670,
+        &[
+            uint(0x7671, 1),
+            element(0x7672, &[0; 20]),
+            float(0x7673, 90.0),
+            float(0x7674, -15.0),
+            float(0x7675, 0.5),
+        ],
+    );
+    let video = master(
+        0xae,
+        &[
+            uint(0xd7, 1),
+            uint(0x73c5, 1),
+            uint(0x83, 1),
+            string(0x86, "V_VP9"),
+            master(0xe0, &[uint(0xb0, 3840), uint(0xba, 1920), projection]),
+        ],
+    );
+
+    let segment = [make_info(), master(0x1654ae6b, &[video])];
+    let reader = open_mkv([make_ebml_header(), master(0x18538067, &segment)].concat());
+
+    let projection = match &reader.tracks()[0].codec_params {
+        Some(CodecParameters::Video(params)) => params.projection.clone().unwrap(),
+        _ => panic!("expected video codec parameters"),
+    };
+
+    assert_eq!(projection.projection_type, ProjectionType::Equirectangular);
+    assert_eq!(projection.private.as_deref(), Some([0; 20].as_slice()));
+    assert_eq!((projection.yaw, projection.pitch, projection.roll), (90.0, -15.0, 0.5));
+}
+
+#[test]
+fn into_inner_and_reprobe() {
+    let mut probe = Probe::new();
+    probe.register_format::<MkvReader<'_>>();
+
+    let data = make_mkv(&[], &[cluster(0, &[0, 10])]);
+
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let mut reader =
+        probe.probe(&Hint::new(), mss, Default::default(), Default::default()).unwrap();
+
+    let packet = reader.next_packet().unwrap().unwrap();
+
+    // The returned media source stream is positioned after the first packet. Rewind it, and probe
+    // it again.
+    let mut mss = reader.into_inner();
+    mss.seek(SeekFrom::Start(0)).unwrap();
+
+    let mut reader =
+        probe.probe(&Hint::new(), mss, Default::default(), Default::default()).unwrap();
+
+    assert_eq!(reader.format_info().format, FORMAT_ID_MKV);
+    assert_eq!(reader.tracks().len(), 1);
+
+    let reprobed = reader.next_packet().unwrap().unwrap();
+    assert_eq!(reprobed.pts, packet.pts);
+    assert_eq!(&reprobed.data[..], &packet.data[..]);
+}
+
+#[test]
+fn probe_metadata_only_and_upgrade() {
+    // Subtitle blocks without a block duration have an unknown (0) duration.
+    let track = [uint(0x83, 0x11), string(0x86, "S_TEXT/UTF8")];
+    let data = make_mkv(&track, &[cluster(0, &[0, 1500])]);
+
+    let mut probe = Probe::new();
+    probe.register_format::<MkvReader<'_>>();
+
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let opts = FormatOptions::default().backfill_packet_durations(true);
+    let probed = probe.probe_metadata_only(&Hint::new(), mss, opts, Default::default()).unwrap();
+
+    assert_eq!(probed.format_info().format, FORMAT_ID_MKV);
+    assert_eq!(probed.tracks().len(), 1);
+    assert_eq!(probed.tracks()[0].track_type(), Some(TrackType::Subtitle));
+
+    // The upgraded reader starts at the first packet, and backfills packet durations.
+    let mut reader = probed.into_reader();
+    assert_eq!(reader.tracks().len(), 1);
+
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!((packet.pts.get(), packet.dur.get()), (0, 1500));
+}
+
+#[test]
+fn read_with_track_filter() {
+    let track = |number: u64| {
+        master(
+            0xae,
+            &[
+                uint(0xd7, number),
+                uint(0x73c5, number),
+                uint(0x83, 2),
+                string(0x86, "A_PCM/INT/LIT"),
+                master(0xe1, &[float(0xb5, 48000.0), uint(0x9f, 2), uint(0x6264, 16)]),
+            ],
+        )
+    };
+
+    // Alternating frames of tracks 1 and 2.
+    let mut children = vec![uint(0xe7, 0)];
+    for number in [1, 2, 1, 2, 1, 2] {
+        children.push(element(0xa3, &[0x80 | number, 0, 0, 0x80, number, number, number, number]));
+    }
+
+    let segment =
+        [make_info(), master(0x1654ae6b, &[track(1), track(2)]), master(0x1f43b675, &children)];
+    let mut reader = open_mkv([make_ebml_header(), master(0x18538067, &segment)].concat());
+
+    let read_track_ids = |reader: &mut MkvReader<'_>, count: usize| {
+        let mut ids = Vec::new();
+        while ids.len() < count {
+            let Some(packet) = reader.next_packet().unwrap()
+            else {
+                break;
+            };
+            assert_eq!(packet.data[0], packet.track_id as u8);
+            ids.push(packet.track_id);
+        }
+        ids
+    };
+
+    // Packets of unselected tracks are skipped.
+    reader.set_track_filter(&[1]).unwrap();
+    assert!(reader.is_track_selected(1));
+    assert!(!reader.is_track_selected(2));
+    assert_eq!(read_track_ids(&mut reader, 2), vec![1, 1]);
+
+    // Clearing the filter selects all tracks again.
+    reader.set_track_filter(&[]).unwrap();
+    assert!(reader.is_track_selected(2));
+    assert_eq!(read_track_ids(&mut reader, usize::MAX), vec![2, 1, 2]);
+
+    // The filter applies from the start of the media after a reset.
+    reader.reset().unwrap();
+    reader.set_track_filter(&[2]).unwrap();
+    assert_eq!(read_track_ids(&mut reader, usize::MAX), vec![2, 2, 2]);
+}
+
+#[test]
+fn reset_to_first_packet() {
+    let mut reader = open_mkv(make_mkv(&[], &[cluster(0, &[0, 10]), cluster(20, &[0])]));
+
+    let first = reader.next_packet().unwrap().unwrap();
+    assert_eq!(read_packet_timing(&mut reader).len(), 2);
+
+    // After resetting, the media is read again from the first packet.
+    reader.reset().unwrap();
+
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!((packet.track_id, packet.pts, packet.dur), (first.track_id, first.pts, first.dur));
+    assert_eq!(&packet.data[..], &first.data[..]);
+
+    assert_eq!(read_packet_timing(&mut reader).len(), 2);
+}
+
+#[test]
+fn reset_unseekable() {
+    let source = ReadOnlySource::new(std::io::Cursor::new(make_mkv(&[], &[cluster(0, &[0])])));
+    let mss = MediaSourceStream::new(Box::new(source), Default::default());
+    let mut reader = MkvReader::try_new(mss, Default::default()).unwrap();
+
+    assert!(reader.reset().is_err());
+}
+
+#[test]
+fn estimate_bitrate_from_packets() {
+    // Each frame is 10 ms long, and has 4 bytes of data, for a constant bitrate of 3.2 kbps.
+    let track = [uint(0x23e383, 10_000_000)];
+    let mut reader = open_mkv(make_mkv(&track, &[cluster(0, &[0, 10, 20]), cluster(30, &[0])]));
+
+    let track_id = reader.tracks()[0].id;
+    assert_eq!(reader.estimate_track_bitrate(track_id), Some(3200));
+
+    // The reader is reset after estimating the bitrate.
+    assert_eq!(read_packet_timing(&mut reader), vec![(0, 10), (10, 10), (20, 10), (30, 10)]);
+}
+
+#[test]
+fn estimate_bitrate_mid_stream() {
+    let track = [uint(0x23e383, 10_000_000)];
+    let mut reader = open_mkv(make_mkv(&track, &[cluster(0, &[0, 10, 20]), cluster(30, &[0])]));
+
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!(packet.pts.get(), 0);
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!(packet.pts.get(), 10);
+
+    let track_id = reader.tracks()[0].id;
+    assert_eq!(reader.estimate_track_bitrate(track_id), Some(3200));
+
+    // The reader is returned to its position before estimating the bitrate.
+    assert_eq!(read_packet_timing(&mut reader), vec![(20, 10), (30, 10)]);
+}
+
+#[test]
+fn estimate_bitrate_filtered_track() {
+    let track = [uint(0x23e383, 10_000_000)];
+    let mut reader = open_mkv(make_mkv(&track, &[cluster(0, &[0, 10, 20]), cluster(30, &[0])]));
+
+    // The bitrate of a track excluded by the track filter cannot be estimated.
+    let track_id = reader.tracks()[0].id;
+    reader.set_track_filter(&[track_id + 1]).unwrap();
+    assert!(!reader.is_track_selected(track_id));
+    assert_eq!(reader.estimate_track_bitrate(track_id), None);
+
+    reader.set_track_filter(&[]).unwrap();
+    assert!(reader.is_track_selected(track_id));
+    assert_eq!(read_packet_timing(&mut reader).len(), 4);
+}
+
+#[test]
+fn estimate_bitrate_within_laced_block() {
+    // A block with 3 fixed-size laced frames, followed by a block with a single frame.
+    let laced = element(0xa3, &[0x81, 0, 0, 0x84, 0x02, 1, 2, 3, 4, 5, 6]);
+    let cluster = master(0x1f43b675, &[uint(0xe7, 0), laced, element(0xa3, &block(30, 0x80))]);
+    let mut reader = open_mkv(make_mkv(&[], &[cluster]));
+
+    let track_id = reader.tracks()[0].id;
+
+    // While frames of a laced block remain, the position of the next packet is unknown.
+    reader.next_packet().unwrap().unwrap();
+    assert_eq!(reader.source_position(), None);
+    assert_eq!(reader.estimate_track_bitrate(track_id), None);
+    assert!(reader.capabilities().contains(FormatCapabilities::SEEKABLE));
+
+    reader.next_packet().unwrap().unwrap();
+    reader.next_packet().unwrap().unwrap();
+    assert!(reader.source_position().is_some());
+
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!(&packet.data[..], &[0, 0, 0, 0]);
+    assert!(reader.next_packet().unwrap().is_none());
+}
+
+#[test]
+fn estimate_bitrate_after_laced_block() {
+    // A block with 3 fixed-size laced frames, followed by two blocks with a single frame. Each
+    // frame is 10 ms long.
+    let track = [uint(0x23e383, 10_000_000)];
+    let laced = element(0xa3, &[0x81, 0, 0, 0x84, 0x02, 1, 2, 3, 4, 5, 6]);
+    let cluster = master(
+        0x1f43b675,
+        &[uint(0xe7, 0), laced, element(0xa3, &block(30, 0x80)), element(0xa3, &block(40, 0x80))],
+    );
+    let mut reader = open_mkv(make_mkv(&track, &[cluster]));
+
+    for _ in 0..4 {
+        reader.next_packet().unwrap().unwrap();
+    }
+
+    let track_id = reader.tracks()[0].id;
+    assert!(reader.estimate_track_bitrate(track_id).is_some());
+
+    // The reader is returned past the laced block to its position before estimating the bitrate.
+    assert_eq!(read_packet_timing(&mut reader), vec![(40, 10)]);
+}
+
+#[test]
+fn estimate_bitrate_unseekable() {
+    let source = ReadOnlySource::new(std::io::Cursor::new(make_mkv(&[], &[cluster(0, &[0])])));
+    let mss = MediaSourceStream::new(Box::new(source), Default::default());
+    let mut reader = MkvReader::try_new(mss, Default::default()).unwrap();
+
+    // Estimating the bitrate requires resetting the reader.
+    let track_id = reader.tracks()[0].id;
+    assert_eq!(reader.estimate_track_bitrate(track_id), None);
+}
+
+#[test]
+fn backfill_subtitle_packet_durations() {
+    // Subtitle blocks without a block duration have an unknown (0) duration.
+    let track = [uint(0x83, 0x11), string(0x86, "S_TEXT/UTF8")];
+    let data = make_mkv(&track, &[cluster(0, &[0, 1500]), cluster(4000, &[0])]);
+
+    assert_eq!(read_packet_timing(&mut open_mkv(data.clone())), vec![(0, 0), (1500, 0), (4000, 0)]);
+
+    // When requested, the duration is inferred from the next packet. The duration of the last
+    // packet cannot be inferred.
+    let mut probe = Probe::new();
+    probe.register_format::<MkvReader<'_>>();
+
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let opts = FormatOptions::default().backfill_packet_durations(true);
+    let mut reader = probe.probe(&Hint::new(), mss, opts, Default::default()).unwrap();
+
+    let mut timing = Vec::new();
+    while let Some(packet) = reader.next_packet().unwrap() {
+        timing.push((packet.pts.get(), packet.dur.get()));
+    }
+
+    assert_eq!(timing, vec![(0, 1500), (1500, 2500), (4000, 0)]);
+
+    // Buffered packets are discarded on reset.
+    reader.reset().unwrap();
+    assert_eq!(reader.next_packet().unwrap().map(|packet| packet.dur.get()), Some(1500));
+}
+
+#[test]
+fn backfill_estimate_bitrate_keeps_packets() {
+    let track = [uint(0x83, 0x11), string(0x86, "S_TEXT/UTF8")];
+    let data = make_mkv(&track, &[cluster(0, &[0, 1500]), cluster(4000, &[0])]);
+
+    let mut reader = DurationBackfill::new(Box::new(open_mkv(data)));
+
+    // Reading the first packet reads the second packet ahead to infer the duration.
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!((packet.pts.get(), packet.dur.get()), (0, 1500));
+
+    // The wrapped reader is returned to its position after estimating the bitrate, so the
+    // buffered packet is kept.
+    let track_id = reader.tracks()[0].id;
+    assert!(reader.estimate_track_bitrate(track_id).is_some());
+
+    let mut buf = Vec::new();
+    let mut timing = Vec::new();
+    while let Some(packet) = reader.next_packet_into(&mut buf).unwrap() {
+        timing.push((packet.pts.get(), packet.dur.get()));
+    }
+
+    assert_eq!(timing, vec![(1500, 2500), (4000, 0)]);
+}
+
+#[test]
+fn read_block_additions() {
+    // A BlockGroup with two block additions, followed by a SimpleBlock without any.
+    let block_more = |id: u64, data: &[u8]| master(0xa6, &[uint(0xee, id), element(0xa5, data)]);
+
+    let block_group = master(
+        0xa0,
+        &[
+            element(0xa1, &[0x81, 0, 0, 0x00, 1, 2, 3, 4]),
+            master(0x75a1, &[block_more(1, b"alpha"), block_more(4, b"cue")]),
+        ],
+    );
+    let cluster = master(
+        0x1f43b675,
+        &[uint(0xe7, 0), block_group, element(0xa3, &[0x81, 0, 10, 0x80, 5, 6, 7, 8])],
+    );
+
+    let data = make_mkv(&[], &[cluster]);
+    let mut reader = open_mkv(data.clone());
+
+    let side_data = [
+        SideData::BlockAddition { id: 1, data: b"alpha".as_slice().into() },
+        SideData::BlockAddition { id: 4, data: b"cue".as_slice().into() },
+    ];
+
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!(&*packet.data, &[1, 2, 3, 4]);
+    assert_eq!(packet.side_data, side_data);
+    assert_eq!(*packet.as_packet_ref().side_data, side_data);
+
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!(&*packet.data, &[5, 6, 7, 8]);
+    assert!(packet.side_data.is_empty());
+
+    // The side data is also returned when reading into a buffer, including when packet durations
+    // are backfilled.
+    let readers: [Box<dyn FormatReader>; 2] = [
+        Box::new(open_mkv(data.clone())),
+        Box::new(DurationBackfill::new(Box::new(open_mkv(data)))),
+    ];
+
+    for mut reader in readers {
+        let mut buf = Vec::new();
+
+        let packet = reader.next_packet_into(&mut buf).unwrap().unwrap();
+        assert_eq!(packet.data, &[1, 2, 3, 4]);
+        assert_eq!(*packet.side_data, side_data);
+
+        let packet = reader.next_packet_into(&mut buf).unwrap().unwrap();
+        assert_eq!(packet.data, &[5, 6, 7, 8]);
+        assert!(packet.side_data.is_empty());
+    }
+}
+
+#[test]
+fn read_webm() {
+    let vp9 = master(
+        0xae,
+        &[
+            uint(0xd7, 2),
+            uint(0x73c5, 2),
+            uint(0x83, 1),
+            string(0x86, "V_VP9"),
+            master(0xe0, &[uint(0xb0, 1280), uint(0xba, 720)]),
+        ],
+    );
+    let webm = |tracks: Vec<u8>| {
+        let segment = [make_info(), tracks];
+        [master(0x1a45dfa3, &[string(0x4282, "webm")]), master(0x18538067, &segment)].concat()
+    };
+    let try_open = |data: Vec<u8>, strict: bool| {
+        let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+        MkvReader::try_new(mss, FormatOptions::default().strict(strict))
+    };
+
+    // The WebM document type is identified separately from Matroska.
+    let reader = try_open(webm(master(0x1654ae6b, &[vp9])), true).unwrap();
+    assert_eq!(reader.format_info().format, FORMAT_ID_WEBM);
+    assert_eq!(reader.format_info().short_name, "webm");
+
+    let reader = open_mkv(make_mkv(&[], &[]));
+    assert_eq!(reader.format_info().format, FORMAT_ID_MKV);
+    assert_eq!(reader.format_info().short_name, "matroska");
+
+    // PCM is not permitted in WebM, but is only rejected in strict mode.
+    let tracks = make_tracks(&[]);
+    assert!(try_open(webm(tracks.clone()), false).is_ok());
+    assert!(try_open(webm(tracks), true).is_err());
+
+    // An unknown codec is not rejected in strict mode.
+    let unknown =
+        master(0xae, &[uint(0xd7, 1), uint(0x73c5, 1), uint(0x83, 1), string(0x86, "V_UNKNOWN")]);
+    let reader = try_open(webm(master(0x1654ae6b, &[unknown])), true).unwrap();
+    assert!(reader.tracks()[0].codec_params.is_none());
+}
+
+#[test]
+fn limit_element_bytes() {
+    let data = make_mkv(&[element(0x63a2, &[0; 32])], &[]);
+
+    let open = |limit| {
+        let mss = MediaSourceStream::new(
+            Box::new(std::io::Cursor::new(data.clone())),
+            Default::default(),
+        );
+        MkvReader::try_new(mss, FormatOptions::default().limit_element_bytes(limit))
+    };
+
+    // The codec private data is rejected if it exceeds the limit.
+    assert!(open(Limit::Maximum(16)).is_err());
+    assert!(open(Limit::Maximum(32)).is_ok());
+    assert!(open(Limit::None).is_ok());
+}
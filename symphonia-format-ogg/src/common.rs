@@ -5,10 +5,13 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use symphonia_core::meta::{MetadataRevision, MetadataSideData};
+use symphonia_core::meta::{MetadataError, MetadataRevision, MetadataSideData};
 
 /// Map packet side data variants.
 pub enum SideData {
     /// Metadata.
     Metadata { rev: MetadataRevision, side_data: Vec<MetadataSideData> },
+    /// Metadata that could not be read completely, and the metadata read before the error, if
+    /// any.
+    MetadataError { rev: Option<MetadataRevision>, err: Box<MetadataError> },
 }
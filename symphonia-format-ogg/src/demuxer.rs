@@ -8,10 +8,12 @@
 use std::collections::BTreeMap;
 use std::io::{Seek, SeekFrom};
 
+use symphonia_core::common::Limit;
 use symphonia_core::errors::{Error, Result, SeekErrorKind};
-use symphonia_core::errors::{reset_error, seek_error, unsupported_error};
+use symphonia_core::errors::{decode_error, reset_error, seek_error, unsupported_error};
 use symphonia_core::formats::prelude::*;
 use symphonia_core::formats::probe::{ProbeFormatData, ProbeableFormat, Score, Scoreable};
+use symphonia_core::formats::util::TrackFilter;
 use symphonia_core::formats::well_known::FORMAT_ID_OGG;
 use symphonia_core::io::*;
 use symphonia_core::meta::{Metadata, MetadataLog, MetadataSideData};
@@ -37,6 +39,7 @@ pub struct OggReader<'s> {
     tracks: Vec<Track>,
     chapters: Option<ChapterGroup>,
     metadata: MetadataLog,
+    track_filter: TrackFilter,
     /// The page reader.
     pages: PageReader,
     /// `LogicalStream` for each serial.
@@ -45,6 +48,10 @@ pub struct OggReader<'s> {
     phys_byte_range_start: u64,
     /// The position of the first byte of the next physical stream, if available.
     phys_byte_range_end: Option<u64>,
+    /// The maximum size in bytes of a metadata element.
+    limit_element_bytes: Limit,
+    /// If `true`, malformed metadata is an error.
+    strict: bool,
 }
 
 impl<'s> OggReader<'s> {
@@ -64,10 +71,13 @@ impl<'s> OggReader<'s> {
             tracks: Default::default(),
             chapters: opts.external_data.chapters,
             metadata: opts.external_data.metadata.unwrap_or_default(),
+            track_filter: Default::default(),
             streams: Default::default(),
             pages,
             phys_byte_range_start: 0,
             phys_byte_range_end: None,
+            limit_element_bytes: opts.limit_element_bytes,
+            strict: opts.strict,
         };
 
         ogg.start_new_physical_stream()?;
@@ -95,13 +105,41 @@ impl<'s> OggReader<'s> {
             return reset_error();
         }
 
-        if let Some(stream) = self.streams.get_mut(&page.header.serial) {
-            // TODO: Process side data.
-            let _side_data = stream.read_page(&page)?;
-        }
-        else {
-            // If there is no associated logical stream with this page, then this is a
-            // completely random page within the physical stream. Discard it.
+        let side_data = match self.streams.get_mut(&page.header.serial) {
+            Some(stream) => stream.read_page(&page)?,
+            // If there is no associated logical stream with this page, then this is a completely
+            // random page within the physical stream. Discard it.
+            None => Vec::new(),
+        };
+
+        // Metadata may be updated mid-stream.
+        self.consume_side_data(side_data)
+    }
+
+    /// Consume side data returned by a logical stream.
+    fn consume_side_data(&mut self, side_data: Vec<SideData>) -> Result<()> {
+        for data in side_data {
+            match data {
+                SideData::Metadata { rev, side_data } => {
+                    self.metadata.push(rev);
+
+                    // Process side data.
+                    for data in side_data {
+                        if let MetadataSideData::Chapters(chapters) = data {
+                            self.chapters = Some(chapters);
+                        }
+                    }
+                }
+                SideData::MetadataError { .. } if self.strict => {
+                    return decode_error("ogg: malformed metadata");
+                }
+                SideData::MetadataError { rev, err } => {
+                    if let Some(rev) = rev {
+                        self.metadata.push(rev);
+                    }
+                    self.metadata.push_error(*err);
+                }
+            }
         }
 
         Ok(())
@@ -326,7 +364,9 @@ impl<'s> OggReader<'s> {
             // There should only be a single packet, the identification packet, in the first page.
             if let Some(pkt) = self.pages.first_packet() {
                 // If a stream mapper has been detected, create a logical stream with it.
-                if let Some(mapper) = mappings::detect(header.serial, pkt)? {
+                if let Some(mapper) =
+                    mappings::detect(header.serial, pkt, self.limit_element_bytes, self.strict)?
+                {
                     info!(
                         "selected {} mapper for stream with serial={:#x}",
                         mapper.name(),
@@ -352,21 +392,7 @@ impl<'s> OggReader<'s> {
             if let Some(stream) = streams.get_mut(&page.header.serial) {
                 let side_data = stream.read_page_init(&page, true)?;
 
-                // Consume each piece of side data.
-                for data in side_data {
-                    match data {
-                        SideData::Metadata { rev, side_data } => {
-                            self.metadata.push(rev);
-
-                            // Process side data.
-                            for data in side_data {
-                                if let MetadataSideData::Chapters(chapters) = data {
-                                    self.chapters = Some(chapters);
-                                }
-                            }
-                        }
-                    }
-                }
+                self.consume_side_data(side_data)?;
 
                 if stream.has_packets() {
                     break;
@@ -468,7 +494,13 @@ impl FormatReader for OggReader<'_> {
     }
 
     fn next_packet(&mut self) -> Result<Option<Packet>> {
-        self.next_logical_packet()
+        // The packets of unselected tracks are discarded after reading them.
+        loop {
+            match self.next_logical_packet()? {
+                Some(packet) if !self.track_filter.is_selected(packet.track_id) => (),
+                packet => return Ok(packet),
+            }
+        }
     }
 
     fn metadata(&mut self) -> Metadata<'_> {
@@ -483,6 +515,14 @@ impl FormatReader for OggReader<'_> {
         &self.tracks
     }
 
+    fn track_filter(&self) -> Option<&TrackFilter> {
+        Some(&self.track_filter)
+    }
+
+    fn track_filter_mut(&mut self) -> Option<&mut TrackFilter> {
+        Some(&mut self.track_filter)
+    }
+
     fn seek(&mut self, _mode: SeekMode, to: SeekTo) -> Result<SeekedTo> {
         // Get the timestamp of the desired audio frame.
         let (required_ts, serial) = match to {
@@ -502,7 +542,7 @@ impl FormatReader for OggReader<'_> {
                         let max_ts = track
                             .start_ts
                             .checked_add(Duration::from(num_frames))
-                            .ok_or(Error::SeekError(SeekErrorKind::Unseekable))?;
+                            .ok_or(Error::SeekError(SeekErrorKind::OutOfRange))?;
 
                         if ts > max_ts {
                             return seek_error(SeekErrorKind::OutOfRange);
@@ -526,7 +566,7 @@ impl FormatReader for OggReader<'_> {
                 }
                 else {
                     // No tracks.
-                    return seek_error(SeekErrorKind::Unseekable);
+                    return seek_error(SeekErrorKind::Unsupported);
                 };
 
                 // Convert the time to a timestamp.
@@ -534,7 +574,7 @@ impl FormatReader for OggReader<'_> {
                     let track = stream.track();
 
                     // The timebase is required to calculate the timestamp.
-                    let tb = track.time_base.ok_or(Error::SeekError(SeekErrorKind::Unseekable))?;
+                    let tb = track.time_base.ok_or(Error::SeekError(SeekErrorKind::Unsupported))?;
 
                     // If the timestamp overflows, the seek if out-of-range.
                     let ts = tb
@@ -551,7 +591,7 @@ impl FormatReader for OggReader<'_> {
                         let max_ts = track
                             .start_ts
                             .checked_add(Duration::from(num_frames))
-                            .ok_or(Error::SeekError(SeekErrorKind::Unseekable))?;
+                            .ok_or(Error::SeekError(SeekErrorKind::OutOfRange))?;
 
                         if ts > max_ts {
                             return seek_error(SeekErrorKind::OutOfRange);
@@ -575,6 +615,10 @@ impl FormatReader for OggReader<'_> {
         self.do_seek(serial, required_ts)
     }
 
+    fn source_position(&self) -> Option<u64> {
+        self.reader.is_seekable().then(|| self.reader.pos())
+    }
+
     fn into_inner<'s>(self: Box<Self>) -> MediaSourceStream<'s>
     where
         Self: 's,
@@ -179,12 +179,15 @@ impl LogicalStream {
                     total_pkt_dur = total_pkt_dur.saturating_add(dur);
                     total_pkt_discard = total_pkt_discard.saturating_add(discard);
 
+                    // The time base of a mapped track is the reciprocal of its sample rate, so the
+                    // decoded duration is also the number of decoded audio frames.
                     let packet = PacketBuilder::new()
                         .track_id(page.header.serial)
                         .pts(Timestamp::ZERO)
                         .dur(dur)
                         .data(data)
                         .trim_start(discard)
+                        .num_frames(dur.get())
                         .build();
 
                     self.packets.push_back(packet);
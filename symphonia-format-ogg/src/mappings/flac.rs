@@ -14,10 +14,11 @@ use symphonia_core::checksum::Crc8Ccitt;
 use symphonia_core::codecs::CodecParameters;
 use symphonia_core::codecs::audio::well_known::CODEC_ID_FLAC;
 use symphonia_core::codecs::audio::{AudioCodecParameters, VerificationCheck};
+use symphonia_core::common::Limit;
 use symphonia_core::errors::{Result, decode_error};
 use symphonia_core::formats::Track;
 use symphonia_core::io::{BufReader, MonitorStream, ReadBytes};
-use symphonia_core::meta::MetadataBuilder;
+use symphonia_core::meta::{MetadataBuilder, MetadataError};
 use symphonia_core::units::Duration;
 use symphonia_metadata::embedded::flac::{
     FLAC_METADATA_INFO, read_flac_comment_block, read_flac_picture_block,
@@ -40,7 +41,12 @@ const OGG_FLAC_PACKET_TYPE: u8 = 0x7f;
 /// The native FLAC signature.
 const FLAC_SIGNATURE: &[u8] = b"fLaC";
 
-pub fn detect(serial: u32, buf: &[u8]) -> Result<Option<Box<dyn Mapper>>> {
+pub fn detect(
+    serial: u32,
+    buf: &[u8],
+    limit: Limit,
+    strict: bool,
+) -> Result<Option<Box<dyn Mapper>>> {
     // The packet shall be exactly the expected length.
     if buf.len() != OGG_FLAC_HEADER_PACKET_SIZE {
         return Ok(None);
@@ -119,7 +125,7 @@ pub fn detect(serial: u32, buf: &[u8]) -> Result<Option<Box<dyn Mapper>>> {
     track.with_codec_params(CodecParameters::Audio(codec_params));
 
     // Instantiate the FLAC mapper.
-    let mapper = Box::new(FlacMapper { track });
+    let mapper = Box::new(FlacMapper { track, limit, strict });
 
     Ok(Some(mapper))
 }
@@ -273,6 +279,10 @@ impl PacketParser for FlacPacketParser {
 
 struct FlacMapper {
     track: Track,
+    /// The maximum size in bytes of a metadata element.
+    limit: Limit,
+    /// If `true`, malformed metadata is not tolerated.
+    strict: bool,
 }
 
 impl Mapper for FlacMapper {
@@ -320,27 +330,40 @@ impl Mapper for FlacMapper {
             // Packet types in the range 0x01 thru 0x7f, and 0x81 thru 0xfe are metadata blocks.
             let header = MetadataBlockHeader::read(&mut reader)?;
 
-            match header.block_type {
-                MetadataBlockType::VorbisComment => {
-                    let mut builder = MetadataBuilder::new(FLAC_METADATA_INFO);
-
-                    read_flac_comment_block(&mut reader, &mut builder)?;
-
-                    let rev = builder.build();
+            let mut builder = MetadataBuilder::new(FLAC_METADATA_INFO);
 
-                    Ok(MapResult::SideData { data: SideData::Metadata { rev, side_data: vec![] } })
+            let result = match header.block_type {
+                MetadataBlockType::VorbisComment => {
+                    read_flac_comment_block(&mut reader, &mut builder, self.limit, self.strict)
                 }
                 MetadataBlockType::Picture => {
-                    let mut builder = MetadataBuilder::new(FLAC_METADATA_INFO);
-
-                    builder.add_visual(read_flac_picture_block(&mut reader)?);
-
-                    let rev = builder.build();
+                    read_flac_picture_block(&mut reader, self.limit).map(|visual| {
+                        builder.add_visual(visual);
+                    })
+                }
+                _ => return Ok(MapResult::Unknown),
+            };
 
-                    Ok(MapResult::SideData { data: SideData::Metadata { rev, side_data: vec![] } })
+            // A malformed metadata block does not prevent the stream from being read. The tags of a
+            // comment block read before the error are kept.
+            let data = match result {
+                Ok(_) => SideData::Metadata { rev: builder.build(), side_data: vec![] },
+                Err(err) => {
+                    warn!("ogg (flac): malformed metadata block ({err}), skipping");
+
+                    let rev = match header.block_type {
+                        MetadataBlockType::VorbisComment => Some(builder.build()),
+                        _ => None,
+                    };
+
+                    SideData::MetadataError {
+                        rev,
+                        err: Box::new(MetadataError::new(FLAC_METADATA_INFO, &err)),
+                    }
                 }
-                _ => Ok(MapResult::Unknown),
-            }
+            };
+
+            Ok(MapResult::SideData { data })
         }
     }
 }
@@ -7,6 +7,7 @@
 
 use super::common::SideData;
 
+use symphonia_core::common::Limit;
 use symphonia_core::errors::Result;
 use symphonia_core::formats::Track;
 use symphonia_core::units::{Duration, Timestamp};
@@ -16,10 +17,18 @@ mod opus;
 mod vorbis;
 
 /// Detect a `Mapper` for a logical stream given the identification packet of the stream.
-pub fn detect(serial: u32, buf: &[u8]) -> Result<Option<Box<dyn Mapper>>> {
-    let mapper = flac::detect(serial, buf)?
-        .or(vorbis::detect(serial, buf)?)
-        .or(opus::detect(serial, buf)?)
+///
+/// Metadata elements read by the `Mapper` larger than `limit` bytes are rejected. If `strict` is
+/// `true`, malformed metadata that would otherwise be tolerated is an error.
+pub fn detect(
+    serial: u32,
+    buf: &[u8],
+    limit: Limit,
+    strict: bool,
+) -> Result<Option<Box<dyn Mapper>>> {
+    let mapper = flac::detect(serial, buf, limit, strict)?
+        .or(vorbis::detect(serial, buf, limit, strict)?)
+        .or(opus::detect(serial, buf, limit, strict)?)
         .or_else(make_null_mapper);
 
     Ok(mapper)
@@ -9,14 +9,16 @@ use crate::common::SideData;
 
 use super::{MapResult, Mapper, PacketParser};
 
+use symphonia_common::xiph::audio::opus::OPUS_MAX_FRAMES_PER_PACKET;
 use symphonia_core::audio::{Channels, Position};
 use symphonia_core::codecs::CodecParameters;
 use symphonia_core::codecs::audio::AudioCodecParameters;
 use symphonia_core::codecs::audio::well_known::CODEC_ID_OPUS;
+use symphonia_core::common::Limit;
 use symphonia_core::errors::Result;
 use symphonia_core::formats::Track;
 use symphonia_core::io::{BufReader, ReadBytes};
-use symphonia_core::meta::MetadataBuilder;
+use symphonia_core::meta::{MetadataBuilder, MetadataError};
 
 use symphonia_core::units::Duration;
 use symphonia_metadata::embedded::vorbis::{self, VORBIS_COMMENT_METADATA_INFO};
@@ -35,7 +37,12 @@ const OGG_OPUS_COMMENT_SIGNATURE: &[u8] = b"OpusTags";
 /// The maximum support Opus OGG mapping version.
 const OGG_OPUS_MAPPING_VERSION_MAX: u8 = 0x0f;
 
-pub fn detect(serial: u32, buf: &[u8]) -> Result<Option<Box<dyn Mapper>>> {
+pub fn detect(
+    serial: u32,
+    buf: &[u8],
+    limit: Limit,
+    strict: bool,
+) -> Result<Option<Box<dyn Mapper>>> {
     // The identification packet for Opus must be a minimum size.
     if buf.len() < OGG_OPUS_MIN_IDENTIFICATION_PACKET_SIZE {
         return Ok(None);
@@ -140,6 +147,7 @@ pub fn detect(serial: u32, buf: &[u8]) -> Result<Option<Box<dyn Mapper>>> {
         .for_codec(CODEC_ID_OPUS)
         .with_sample_rate(48_000)
         .with_channels(Channels::Positioned(positions))
+        .with_max_frames_per_packet(OPUS_MAX_FRAMES_PER_PACKET)
         .with_extra_data(Box::from(buf));
 
     // Create the track.
@@ -148,7 +156,7 @@ pub fn detect(serial: u32, buf: &[u8]) -> Result<Option<Box<dyn Mapper>>> {
     track.with_codec_params(CodecParameters::Audio(codec_params)).with_delay(u32::from(pre_skip));
 
     // Instantiate the Opus mapper.
-    let mapper = Box::new(OpusMapper { track, need_comment: true });
+    let mapper = Box::new(OpusMapper { track, need_comment: true, limit, strict });
 
     Ok(Some(mapper))
 }
@@ -219,6 +227,10 @@ impl PacketParser for OpusPacketParser {
 struct OpusMapper {
     track: Track,
     need_comment: bool,
+    /// The maximum size in bytes of a metadata element.
+    limit: Limit,
+    /// If `true`, malformed metadata is not tolerated.
+    strict: bool,
 }
 
 impl Mapper for OpusMapper {
@@ -259,13 +271,28 @@ impl Mapper for OpusMapper {
                 let mut builder = MetadataBuilder::new(VORBIS_COMMENT_METADATA_INFO);
                 let mut side_data = Default::default();
 
-                vorbis::read_vorbis_comment(&mut reader, &mut builder, &mut side_data)?;
-
-                let rev = builder.build();
-
+                // All packets following the comment header are audio packets, even if the comment
+                // header is malformed.
                 self.need_comment = false;
 
-                Ok(MapResult::SideData { data: SideData::Metadata { rev, side_data } })
+                let data = match vorbis::read_vorbis_comment(
+                    &mut reader,
+                    &mut builder,
+                    &mut side_data,
+                    self.limit,
+                    self.strict,
+                ) {
+                    Ok(_) => SideData::Metadata { rev: builder.build(), side_data },
+                    Err(err) => {
+                        warn!("ogg (opus): malformed comment header ({err})");
+                        SideData::MetadataError {
+                            rev: Some(builder.build()),
+                            err: Box::new(MetadataError::new(VORBIS_COMMENT_METADATA_INFO, &err)),
+                        }
+                    }
+                };
+
+                Ok(MapResult::SideData { data })
             }
             else {
                 warn!("ogg (opus): invalid packet type");
@@ -12,10 +12,11 @@ use symphonia_common::xiph::audio::vorbis::*;
 use symphonia_core::codecs::CodecParameters;
 use symphonia_core::codecs::audio::AudioCodecParameters;
 use symphonia_core::codecs::audio::well_known::CODEC_ID_VORBIS;
+use symphonia_core::common::Limit;
 use symphonia_core::errors::{Result, decode_error, unsupported_error};
 use symphonia_core::formats::Track;
 use symphonia_core::io::{BitReaderRtl, BufReader, ReadBitsRtl, ReadBytes};
-use symphonia_core::meta::MetadataBuilder;
+use symphonia_core::meta::{MetadataBuilder, MetadataError};
 use symphonia_core::units::Duration;
 use symphonia_metadata::embedded::vorbis::*;
 
@@ -107,7 +108,12 @@ impl PacketParser for VorbisPacketParser {
     }
 }
 
-pub fn detect(serial: u32, buf: &[u8]) -> Result<Option<Box<dyn Mapper>>> {
+pub fn detect(
+    serial: u32,
+    buf: &[u8],
+    limit: Limit,
+    strict: bool,
+) -> Result<Option<Box<dyn Mapper>>> {
     // The identification header packet must be the correct size.
     if buf.len() != VORBIS_IDENTIFICATION_HEADER_SIZE {
         return Ok(None);
@@ -137,7 +143,14 @@ pub fn detect(serial: u32, buf: &[u8]) -> Result<Option<Box<dyn Mapper>>> {
     track.with_codec_params(CodecParameters::Audio(codec_params));
 
     // Instantiate the Vorbis mapper.
-    let mapper = Box::new(VorbisMapper { track, ident, parser: None, has_setup_header: false });
+    let mapper = Box::new(VorbisMapper {
+        track,
+        ident,
+        parser: None,
+        has_setup_header: false,
+        limit,
+        strict,
+    });
 
     Ok(Some(mapper))
 }
@@ -147,6 +160,10 @@ struct VorbisMapper {
     ident: IdentHeader,
     parser: Option<VorbisPacketParser>,
     has_setup_header: bool,
+    /// The maximum size in bytes of a metadata element.
+    limit: Limit,
+    /// If `true`, malformed metadata is not tolerated.
+    strict: bool,
 }
 
 impl Mapper for VorbisMapper {
@@ -224,11 +241,28 @@ impl Mapper for VorbisMapper {
                     let mut builder = MetadataBuilder::new(VORBIS_COMMENT_METADATA_INFO);
                     let mut side_data = Default::default();
 
-                    read_vorbis_comment(&mut reader, &mut builder, &mut side_data)?;
-
-                    let rev = builder.build();
+                    // A malformed comment header does not prevent the stream from being read.
+                    let data = match read_vorbis_comment(
+                        &mut reader,
+                        &mut builder,
+                        &mut side_data,
+                        self.limit,
+                        self.strict,
+                    ) {
+                        Ok(_) => SideData::Metadata { rev: builder.build(), side_data },
+                        Err(err) => {
+                            warn!("ogg (vorbis): malformed comment header ({err})");
+                            SideData::MetadataError {
+                                rev: Some(builder.build()),
+                                err: Box::new(MetadataError::new(
+                                    VORBIS_COMMENT_METADATA_INFO,
+                                    &err,
+                                )),
+                            }
+                        }
+                    };
 
-                    Ok(MapResult::SideData { data: SideData::Metadata { rev, side_data } })
+                    Ok(MapResult::SideData { data })
                 }
                 VORBIS_PACKET_TYPE_SETUP => {
                     // Safety: Audio codec parameters are always available if mapper is
@@ -0,0 +1,177 @@
+use symphonia_core::checksum::Crc32;
+use symphonia_core::errors::Error;
+use symphonia_core::formats::{FormatOptions, FormatReader};
+use symphonia_core::io::{MediaSourceStream, Monitor};
+use symphonia_core::meta::{MetadataRevision, RawValue};
+use symphonia_format_ogg::OggReader;
+
+/// The page header flag marking the first page of a logical stream.
+const FIRST_PAGE: u8 = 0x2;
+/// The page header flag marking the last page of a logical stream.
+const LAST_PAGE: u8 = 0x4;
+
+/// Write an Ogg page with the given header flags, absolute granule position, serial, and sequence
+/// number. Each packet must be shorter than 255 bytes.
+fn page(flags: u8, absgp: u64, serial: u32, sequence: u32, packets: &[Vec<u8>]) -> Vec<u8> {
+    let mut buf = b"OggS".to_vec();
+    buf.extend_from_slice(&[0, flags]);
+    buf.extend_from_slice(&absgp.to_le_bytes());
+    buf.extend_from_slice(&serial.to_le_bytes());
+    buf.extend_from_slice(&sequence.to_le_bytes());
+    // The CRC is calculated with the CRC field set to 0.
+    buf.extend_from_slice(&[0; 4]);
+
+    // The segment table.
+    buf.push(packets.len() as u8);
+    buf.extend(packets.iter().map(|packet| packet.len() as u8));
+
+    for packet in packets {
+        buf.extend_from_slice(packet);
+    }
+
+    let mut crc32 = Crc32::new(0);
+    crc32.process_buf_bytes(&buf);
+    buf[22..26].copy_from_slice(&crc32.crc().to_le_bytes());
+    buf
+}
+
+/// Write a mono Opus logical stream, with the given serial and title, containing a single audio
+/// packet.
+fn opus_stream(serial: u32, title: &str) -> Vec<u8> {
+    opus_stream_with_num_comments(serial, title, 1)
+}
+
+/// Write a mono Opus logical stream like `opus_stream`, but with the given number of comments
+/// signalled by the comment header. Only the title comment is present.
+fn opus_stream_with_num_comments(serial: u32, title: &str, num_comments: u32) -> Vec<u8> {
+    opus_pages(serial, title, num_comments).concat()
+}
+
+/// Write the identification header, comment header, and audio pages of an Opus logical stream
+/// like `opus_stream_with_num_comments`.
+fn opus_pages(serial: u32, title: &str, num_comments: u32) -> [Vec<u8>; 3] {
+    // Signature, version, number of channels, pre-skip, input sample rate, output gain, and
+    // channel mapping family.
+    let head =
+        [b"OpusHead".as_slice(), &[1, 1, 0, 0], &48000u32.to_le_bytes(), &[0, 0, 0]].concat();
+
+    // Signature, vendor string, and the user comments.
+    let comment = format!("TITLE={title}");
+    let tags = [
+        b"OpusTags".as_slice(),
+        &4u32.to_le_bytes(),
+        b"test",
+        &num_comments.to_le_bytes(),
+        &(comment.len() as u32).to_le_bytes(),
+        comment.as_bytes(),
+    ]
+    .concat();
+
+    // A single 20 ms SILK frame.
+    let audio = vec![0x08, 0, 0, 0];
+
+    [
+        page(FIRST_PAGE, 0, serial, 0, &[head]),
+        page(0, 0, serial, 1, &[tags]),
+        page(LAST_PAGE, 960, serial, 2, &[audio]),
+    ]
+}
+
+fn title(rev: &MetadataRevision) -> &str {
+    let tag = rev.media.tags.iter().find(|tag| tag.raw.key == "TITLE").unwrap();
+
+    match &tag.raw.value {
+        RawValue::String(value) => value,
+        _ => panic!("expected a string"),
+    }
+}
+
+#[test]
+fn read_chained_stream_metadata() {
+    // Two chained physical streams, each with a different title.
+    let data = [opus_stream(1, "First"), opus_stream(2, "Second")].concat();
+
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let mut reader = OggReader::try_new(mss, Default::default()).unwrap();
+
+    // Before reading any packets, only the metadata of the first stream is available.
+    assert_eq!(title(reader.metadata().current().unwrap()), "First");
+    assert!(reader.metadata().is_latest());
+
+    let mut num_packets = 0;
+
+    loop {
+        match reader.next_packet() {
+            Ok(Some(_)) => num_packets += 1,
+            Ok(None) => break,
+            // A new physical stream was started.
+            Err(Error::ResetRequired) => continue,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    assert_eq!(num_packets, 2);
+
+    // The metadata of the second stream was pushed as a new revision.
+    let mut metadata = reader.metadata();
+    assert!(!metadata.is_latest());
+    assert_eq!(title(&metadata.pop().unwrap()), "First");
+    assert_eq!(title(metadata.current().unwrap()), "Second");
+    assert!(metadata.is_latest());
+}
+
+#[test]
+fn read_num_frames() {
+    let data = opus_stream(1, "First");
+
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let mut reader = OggReader::try_new(mss, Default::default()).unwrap();
+
+    // A 20 ms Opus packet is 960 frames at 48 kHz.
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!(packet.num_frames, Some(960));
+}
+
+#[test]
+fn read_with_track_filter() {
+    // Two multiplexed logical streams.
+    let first = opus_pages(1, "First", 1);
+    let second = opus_pages(2, "Second", 1);
+    let data = first.into_iter().zip(second).flat_map(|(a, b)| [a, b]).collect::<Vec<_>>().concat();
+
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let mut reader = OggReader::try_new(mss, Default::default()).unwrap();
+
+    reader.set_track_filter(&[2]).unwrap();
+    assert!(!reader.is_track_selected(1));
+    assert!(reader.is_track_selected(2));
+
+    // The packets of the unselected stream are discarded.
+    let mut track_ids = Vec::new();
+    while let Some(packet) = reader.next_packet().unwrap() {
+        track_ids.push(packet.track_id);
+    }
+    assert_eq!(track_ids, vec![2]);
+}
+
+#[test]
+fn read_truncated_comment_header() {
+    // The comment header signals a second comment that is not present.
+    let data = opus_stream_with_num_comments(1, "First", 2);
+
+    let mss =
+        MediaSourceStream::new(Box::new(std::io::Cursor::new(data.clone())), Default::default());
+    let mut reader = OggReader::try_new(mss, Default::default()).unwrap();
+
+    // The tags read before the error are kept, and the error is recorded.
+    let metadata = reader.metadata();
+    assert_eq!(title(metadata.current().unwrap()), "First");
+    assert_eq!(metadata.errors().len(), 1);
+
+    // The stream is still read.
+    assert!(reader.next_packet().unwrap().is_some());
+
+    // In strict mode, the malformed comment header is an error.
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    assert!(OggReader::try_new(mss, FormatOptions::default().strict(true)).is_err());
+}
@@ -17,6 +17,7 @@ use symphonia_core::codecs::audio::well_known::{
     CODEC_ID_PCM_S16BE, CODEC_ID_PCM_S16LE, CODEC_ID_PCM_S24BE, CODEC_ID_PCM_S32BE,
     CODEC_ID_PCM_S32LE, CODEC_ID_PCM_U8,
 };
+use symphonia_core::common::Limit;
 use symphonia_core::errors::{Result, decode_error, unsupported_error};
 use symphonia_core::io::ReadBytes;
 use symphonia_core::meta::{MetadataRevision, StandardTag, Tag};
@@ -189,7 +190,7 @@ impl CommonChunk {
             FormatData::Extensible(_) => {
                 unsupported_error("aiff: packet info not implemented for format Extensible")
             }
-            FormatData::Adpcm(_) => {
+            FormatData::Adpcm(_) | FormatData::ItuAdpcm(_) => {
                 unsupported_error("aiff: packet info not implemented for format Adpcm")
             }
         }
@@ -242,7 +243,7 @@ impl fmt::Display for CommonChunk {
             FormatData::Extensible(_) => {
                 writeln!(f, "\tformat_data: Extensible DISPLAY UNSUPPORTED {{")?;
             }
-            FormatData::Adpcm(_) => {
+            FormatData::Adpcm(_) | FormatData::ItuAdpcm(_) => {
                 writeln!(f, "\tformat_data: Adpcm DISPLAY UNSUPPORTED {{")?;
             }
         };
@@ -454,14 +455,36 @@ pub struct Id3Chunk {
     pub metadata: MetadataRevision,
 }
 
-impl ParseChunk for Id3Chunk {
-    fn parse<B: ReadBytes>(reader: &mut B, _tag: [u8; 4], _len: u32) -> Result<Self> {
+impl Id3Chunk {
+    /// Read the ID3 chunk and discard any unread data in the chunk. If `strict` is `true`,
+    /// malformed ID3v2 frames are returned as an error. Frames larger than `limit` bytes are
+    /// skipped, or rejected in strict mode.
+    pub fn read<B: ReadBytes>(
+        reader: &mut B,
+        len: u32,
+        strict: bool,
+        limit: Limit,
+    ) -> Result<Self> {
+        let pos = reader.pos();
+
         let mut side_data = Vec::new();
-        let metadata = riff::read_riff_id3_chunk(reader, &mut side_data)?;
+        let metadata = riff::read_riff_id3_chunk(reader, &mut side_data, strict, limit)?;
+
+        let bytes_read = reader.pos() - pos;
+        if bytes_read < u64::from(len) {
+            reader.ignore_bytes(u64::from(len) - bytes_read)?;
+        }
+
         Ok(Id3Chunk { metadata })
     }
 }
 
+impl ParseChunk for Id3Chunk {
+    fn parse<B: ReadBytes>(reader: &mut B, _tag: [u8; 4], len: u32) -> Result<Self> {
+        Self::read(reader, len, false, Limit::Default)
+    }
+}
+
 pub enum RiffAiffChunks {
     Common(ChunkParser<CommonChunk>),
     Sound(ChunkParser<SoundChunk>),
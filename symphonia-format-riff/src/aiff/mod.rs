@@ -15,6 +15,7 @@ use symphonia_core::errors::{Error, decode_error, seek_error, unsupported_error}
 use symphonia_core::errors::{Result, SeekErrorKind};
 use symphonia_core::formats::prelude::*;
 use symphonia_core::formats::probe::{ProbeFormatData, ProbeableFormat, Score, Scoreable};
+use symphonia_core::formats::util::TrackFilter;
 use symphonia_core::formats::well_known::FORMAT_ID_AIFF;
 use symphonia_core::io::*;
 use symphonia_core::meta::well_known::METADATA_ID_AIFF;
@@ -26,7 +27,8 @@ use symphonia_core::support_format;
 use log::debug;
 
 use crate::common::{
-    ByteOrder, ChunksReader, PacketInfo, append_data_params, append_format_params, next_packet,
+    ByteOrder, ChunksReader, PacketInfo, append_data_params, append_format_params,
+    estimate_bitrate, next_packet,
 };
 mod chunks;
 use chunks::*;
@@ -60,6 +62,7 @@ pub struct AiffReader<'s> {
     attachments: Vec<Attachment>,
     chapters: Option<ChapterGroup>,
     metadata: MetadataLog,
+    track_filter: TrackFilter,
     packet_info: PacketInfo,
     data_start_pos: u64,
     data_end_pos: Option<u64>,
@@ -163,7 +166,7 @@ impl<'s> AiffReader<'s> {
                 }
                 RiffAiffChunks::AppSpecific(chunk) => {
                     // Add application-specific data.
-                    let appl = chunk.parse_and_skip_unread(&mut mss)?;
+                    let appl = chunk.parse_limited(&mut mss, opts.limit_element_bytes)?;
 
                     attachments.push(Attachment::VendorData(VendorDataAttachment {
                         ident: appl.application,
@@ -172,10 +175,17 @@ impl<'s> AiffReader<'s> {
                 }
                 RiffAiffChunks::Text(chunk) => {
                     // Add tag.
-                    let text = chunk.parse_and_skip_unread(&mut mss)?;
+                    let text = chunk.parse_limited(&mut mss, opts.limit_element_bytes)?;
                     builder.add_tag(text.tag);
                 }
-                RiffAiffChunks::Id3(chunk) => id3 = Some(chunk.parse_and_skip_unread(&mut mss)?),
+                RiffAiffChunks::Id3(chunk) => {
+                    id3 = Some(Id3Chunk::read(
+                        &mut mss,
+                        chunk.len,
+                        opts.strict,
+                        opts.limit_element_bytes,
+                    )?);
+                }
             }
         }
 
@@ -233,6 +243,7 @@ impl<'s> AiffReader<'s> {
             attachments,
             chapters: chapters.or(opts.external_data.chapters),
             metadata,
+            track_filter: Default::default(),
             packet_info,
             data_start_pos: ssnd.data_start_pos,
             data_end_pos: ssnd.len.map(|data_len| ssnd.data_start_pos + u64::from(data_len)),
@@ -362,13 +373,19 @@ impl FormatReader for AiffReader<'_> {
     }
 
     fn next_packet(&mut self) -> Result<Option<Packet>> {
-        next_packet(
-            &mut self.reader,
-            &self.packet_info,
-            &self.tracks,
-            self.data_start_pos,
-            self.data_end_pos.unwrap_or(u64::MAX),
-        )
+        // The packets of unselected tracks are discarded after reading them.
+        loop {
+            match next_packet(
+                &mut self.reader,
+                &self.packet_info,
+                &self.tracks,
+                self.data_start_pos,
+                self.data_end_pos.unwrap_or(u64::MAX),
+            )? {
+                Some(packet) if !self.track_filter.is_selected(packet.track_id) => (),
+                packet => return Ok(packet),
+            }
+        }
     }
 
     fn metadata(&mut self) -> Metadata<'_> {
@@ -387,9 +404,28 @@ impl FormatReader for AiffReader<'_> {
         &self.tracks
     }
 
+    fn track_filter(&self) -> Option<&TrackFilter> {
+        Some(&self.track_filter)
+    }
+
+    fn track_filter_mut(&mut self) -> Option<&mut TrackFilter> {
+        Some(&mut self.track_filter)
+    }
+
+    fn estimate_track_bitrate(&mut self, track_id: u32) -> Option<u32> {
+        // The audio data spans to the end of the data chunk, or the end of the source if the
+        // length of the data chunk is unknown.
+        let data_end_pos = self.data_end_pos.or_else(|| self.reader.byte_len())?;
+        let data_len = data_end_pos.checked_sub(self.data_start_pos)?;
+
+        let track = self.tracks.iter().find(|track| track.id == track_id)?;
+
+        estimate_bitrate(track, data_len)
+    }
+
     fn seek(&mut self, _mode: SeekMode, to: SeekTo) -> Result<SeekedTo> {
         if self.tracks.is_empty() {
-            return seek_error(SeekErrorKind::Unseekable);
+            return seek_error(SeekErrorKind::Unsupported);
         }
 
         let track = &self.tracks[0];
@@ -400,7 +436,7 @@ impl FormatReader for AiffReader<'_> {
             // Time value given, calculate frame timestamp using the timebase.
             SeekTo::Time { time, .. } => {
                 // The timebase is required to calculate the timestamp.
-                let tb = track.time_base.ok_or(Error::SeekError(SeekErrorKind::Unseekable))?;
+                let tb = track.time_base.ok_or(Error::SeekError(SeekErrorKind::Unsupported))?;
 
                 // If the timestamp overflows, the seek if out-of-range.
                 tb.calc_timestamp(time).ok_or(Error::SeekError(SeekErrorKind::OutOfRange))?
@@ -460,6 +496,10 @@ impl FormatReader for AiffReader<'_> {
         Ok(SeekedTo { track_id: 0, actual_ts, required_ts })
     }
 
+    fn source_position(&self) -> Option<u64> {
+        self.reader.is_seekable().then(|| self.reader.pos())
+    }
+
     fn into_inner<'s>(self: Box<Self>) -> MediaSourceStream<'s>
     where
         Self: 's,
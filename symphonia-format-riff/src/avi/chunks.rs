@@ -0,0 +1,290 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::fmt;
+
+use symphonia_core::errors::{Result, decode_error};
+use symphonia_core::io::ReadBytes;
+
+use crate::common::{ByteOrder, ChunkParser, ChunksReader, NullChunks, ParseChunk, ParseChunkTag};
+
+/// The index entry flag indicating the chunk is a keyframe.
+const AVIIF_KEYFRAME: u32 = 0x10;
+
+pub struct AviMainHeaderChunk {
+    /// The number of microseconds between frames.
+    pub micro_sec_per_frame: u32,
+    /// The total number of frames in the first RIFF chunk.
+    pub total_frames: u32,
+    /// The number of streams in the file.
+    pub streams: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ParseChunk for AviMainHeaderChunk {
+    fn parse<B: ReadBytes>(reader: &mut B, _tag: [u8; 4], len: u32) -> Result<Self> {
+        if len < 40 {
+            return decode_error("avi: malformed avih chunk");
+        }
+
+        let micro_sec_per_frame = reader.read_u32()?;
+        let _max_bytes_per_sec = reader.read_u32()?;
+        let _padding_granularity = reader.read_u32()?;
+        let _flags = reader.read_u32()?;
+        let total_frames = reader.read_u32()?;
+        let _initial_frames = reader.read_u32()?;
+        let streams = reader.read_u32()?;
+        let _suggested_buffer_size = reader.read_u32()?;
+        let width = reader.read_u32()?;
+        let height = reader.read_u32()?;
+
+        Ok(AviMainHeaderChunk { micro_sec_per_frame, total_frames, streams, width, height })
+    }
+}
+
+impl fmt::Display for AviMainHeaderChunk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "AviMainHeaderChunk {{")?;
+        writeln!(f, "\tmicro_sec_per_frame: {},", self.micro_sec_per_frame)?;
+        writeln!(f, "\ttotal_frames: {},", self.total_frames)?;
+        writeln!(f, "\tstreams: {},", self.streams)?;
+        writeln!(f, "\twidth: {},", self.width)?;
+        writeln!(f, "\theight: {},", self.height)?;
+        writeln!(f, "}}")
+    }
+}
+
+pub struct AviStreamHeaderChunk {
+    /// The type of data in the stream (e.g., `vids`, or `auds`).
+    pub stream_type: [u8; 4],
+    /// The preferred handler (codec) for the stream.
+    pub handler: [u8; 4],
+    /// The time scale. Dividing the rate by the scale gives the number of samples per second.
+    pub scale: u32,
+    /// The rate.
+    pub rate: u32,
+    /// The starting time of the stream in units of the stream's time base.
+    pub start: u32,
+    /// The length of the stream in units of the stream's time base.
+    pub length: u32,
+    /// The suggested buffer size for reading the stream.
+    pub suggested_buffer_size: u32,
+    /// The size of a single sample, or 0 if the samples vary in size. If non-zero, a data chunk
+    /// may contain multiple samples.
+    pub sample_size: u32,
+}
+
+impl ParseChunk for AviStreamHeaderChunk {
+    fn parse<B: ReadBytes>(reader: &mut B, _tag: [u8; 4], len: u32) -> Result<Self> {
+        // The destination rectangle at the end of the stream header is optional.
+        if len < 48 {
+            return decode_error("avi: malformed strh chunk");
+        }
+
+        let stream_type = reader.read_quad_bytes()?;
+        let handler = reader.read_quad_bytes()?;
+        let _flags = reader.read_u32()?;
+        let _priority = reader.read_u16()?;
+        let _language = reader.read_u16()?;
+        let _initial_frames = reader.read_u32()?;
+        let scale = reader.read_u32()?;
+        let rate = reader.read_u32()?;
+        let start = reader.read_u32()?;
+        let length = reader.read_u32()?;
+        let suggested_buffer_size = reader.read_u32()?;
+        let _quality = reader.read_u32()?;
+        let sample_size = reader.read_u32()?;
+
+        Ok(AviStreamHeaderChunk {
+            stream_type,
+            handler,
+            scale,
+            rate,
+            start,
+            length,
+            suggested_buffer_size,
+            sample_size,
+        })
+    }
+}
+
+impl fmt::Display for AviStreamHeaderChunk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "AviStreamHeaderChunk {{")?;
+        writeln!(f, "\tstream_type: {},", String::from_utf8_lossy(&self.stream_type))?;
+        writeln!(f, "\thandler: {},", String::from_utf8_lossy(&self.handler))?;
+        writeln!(f, "\tscale: {},", self.scale)?;
+        writeln!(f, "\trate: {},", self.rate)?;
+        writeln!(f, "\tstart: {},", self.start)?;
+        writeln!(f, "\tlength: {},", self.length)?;
+        writeln!(f, "\tsuggested_buffer_size: {},", self.suggested_buffer_size)?;
+        writeln!(f, "\tsample_size: {},", self.sample_size)?;
+        writeln!(f, "}}")
+    }
+}
+
+/// The stream format chunk. The contents depend on the stream type: a `BITMAPINFOHEADER` for video
+/// streams, and a `WAVEFORMATEX` for audio streams.
+pub struct AviStreamFormatChunk {
+    pub buf: Box<[u8]>,
+}
+
+impl ParseChunk for AviStreamFormatChunk {
+    fn parse<B: ReadBytes>(reader: &mut B, _tag: [u8; 4], len: u32) -> Result<Self> {
+        let buf = reader.read_boxed_slice_exact(len as usize)?;
+        Ok(AviStreamFormatChunk { buf })
+    }
+}
+
+pub struct AviIndexEntry {
+    /// The chunk ID of the indexed chunk.
+    pub ckid: [u8; 4],
+    /// The index entry flags.
+    pub flags: u32,
+    /// The offset of the indexed chunk's header. Usually relative to the `movi` list's form type,
+    /// but some muxers write absolute file offsets.
+    pub offset: u32,
+    /// The length of the indexed chunk's data.
+    pub len: u32,
+}
+
+impl AviIndexEntry {
+    pub fn is_keyframe(&self) -> bool {
+        self.flags & AVIIF_KEYFRAME != 0
+    }
+}
+
+pub struct AviIndexChunk {
+    pub entries: Vec<AviIndexEntry>,
+}
+
+impl ParseChunk for AviIndexChunk {
+    fn parse<B: ReadBytes>(reader: &mut B, _tag: [u8; 4], len: u32) -> Result<Self> {
+        // Each index entry is 16 bytes long. Any trailing data is ignored.
+        let num_entries = len / 16;
+
+        let mut entries = Vec::with_capacity(num_entries as usize);
+
+        for _ in 0..num_entries {
+            entries.push(AviIndexEntry {
+                ckid: reader.read_quad_bytes()?,
+                flags: reader.read_u32()?,
+                offset: reader.read_u32()?,
+                len: reader.read_u32()?,
+            });
+        }
+
+        Ok(AviIndexChunk { entries })
+    }
+}
+
+pub struct ListChunk {
+    pub form: [u8; 4],
+    pub len: u32,
+}
+
+impl ListChunk {
+    pub fn skip<B: ReadBytes>(&self, reader: &mut B) -> Result<()> {
+        ChunksReader::<NullChunks>::new(Some(self.len), ByteOrder::LittleEndian).finish(reader)
+    }
+}
+
+impl ParseChunk for ListChunk {
+    fn parse<B: ReadBytes>(reader: &mut B, _tag: [u8; 4], len: u32) -> Result<Self> {
+        // A List chunk must contain atleast the list/form identifier. However, an empty list
+        // (len == 4) is permissible.
+        if len < 4 {
+            return decode_error("avi: malformed list chunk");
+        }
+
+        Ok(ListChunk { form: reader.read_quad_bytes()?, len: len - 4 })
+    }
+}
+
+impl fmt::Display for ListChunk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "ListChunk {{")?;
+        writeln!(f, "\tform: {},", String::from_utf8_lossy(&self.form))?;
+        writeln!(f, "\tlen: {},", self.len)?;
+        writeln!(f, "}}")
+    }
+}
+
+macro_rules! parser {
+    ($class:expr, $result:ty, $tag:expr, $len:expr) => {
+        Some($class(ChunkParser::<$result>::new($tag, $len)))
+    };
+}
+
+/// Chunks found in the top-level `RIFF AVI ` chunk.
+pub enum RiffAviChunks {
+    List(ChunkParser<ListChunk>),
+    Index(ChunkParser<AviIndexChunk>),
+}
+
+impl ParseChunkTag for RiffAviChunks {
+    fn parse_tag(tag: [u8; 4], len: u32) -> Option<Self> {
+        match &tag {
+            b"LIST" => parser!(RiffAviChunks::List, ListChunk, tag, len),
+            b"idx1" => parser!(RiffAviChunks::Index, AviIndexChunk, tag, len),
+            _ => None,
+        }
+    }
+}
+
+/// Chunks found in the `hdrl` list.
+pub enum AviHeaderListChunks {
+    MainHeader(ChunkParser<AviMainHeaderChunk>),
+    List(ChunkParser<ListChunk>),
+}
+
+impl ParseChunkTag for AviHeaderListChunks {
+    fn parse_tag(tag: [u8; 4], len: u32) -> Option<Self> {
+        match &tag {
+            b"avih" => parser!(AviHeaderListChunks::MainHeader, AviMainHeaderChunk, tag, len),
+            b"LIST" => parser!(AviHeaderListChunks::List, ListChunk, tag, len),
+            _ => None,
+        }
+    }
+}
+
+/// Chunks found in a `strl` list.
+pub enum AviStreamListChunks {
+    StreamHeader(ChunkParser<AviStreamHeaderChunk>),
+    StreamFormat(ChunkParser<AviStreamFormatChunk>),
+}
+
+impl ParseChunkTag for AviStreamListChunks {
+    fn parse_tag(tag: [u8; 4], len: u32) -> Option<Self> {
+        match &tag {
+            b"strh" => parser!(AviStreamListChunks::StreamHeader, AviStreamHeaderChunk, tag, len),
+            b"strf" => parser!(AviStreamListChunks::StreamFormat, AviStreamFormatChunk, tag, len),
+            _ => None,
+        }
+    }
+}
+
+/// Get the stream index from a `movi` data chunk ID. Data chunk IDs are composed of a two digit
+/// stream index followed by a two character type code (e.g., `00dc`, or `01wb`).
+pub fn stream_index_from_ckid(ckid: &[u8; 4]) -> Option<u32> {
+    match ckid[..2] {
+        [hi @ b'0'..=b'9', lo @ b'0'..=b'9'] => {
+            Some(u32::from(hi - b'0') * 10 + u32::from(lo - b'0'))
+        }
+        _ => None,
+    }
+}
+
+#[test]
+fn test_stream_index_from_ckid() {
+    assert_eq!(stream_index_from_ckid(b"00dc"), Some(0));
+    assert_eq!(stream_index_from_ckid(b"01wb"), Some(1));
+    assert_eq!(stream_index_from_ckid(b"12pc"), Some(12));
+    assert_eq!(stream_index_from_ckid(b"ix00"), None);
+    assert_eq!(stream_index_from_ckid(b"JUNK"), None);
+}
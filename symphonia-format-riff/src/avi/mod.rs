@@ -0,0 +1,825 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::io::{Seek, SeekFrom};
+
+use symphonia_common::microsoft::audio::WaveFormatEx;
+use symphonia_common::microsoft::video::{BitmapInfoHeader, vc1};
+use symphonia_core::audio::{Channels, Position};
+use symphonia_core::codecs::CodecParameters;
+use symphonia_core::codecs::audio::AudioCodecParameters;
+use symphonia_core::codecs::audio::well_known::{
+    CODEC_ID_ADPCM_IMA_WAV, CODEC_ID_ADPCM_MS, CODEC_ID_PCM_S16LE, CODEC_ID_PCM_S24LE,
+    CODEC_ID_PCM_S32LE, CODEC_ID_PCM_U8,
+};
+use symphonia_core::codecs::video::well_known::{CODEC_ID_VC1, CODEC_ID_WMV3};
+use symphonia_core::codecs::video::{
+    VIDEO_EXTRA_DATA_ID_NULL, VideoCodecParameters, VideoExtraData,
+};
+use symphonia_core::common::Limit;
+use symphonia_core::errors::{Error, Result, SeekErrorKind};
+use symphonia_core::errors::{decode_error, seek_error, unsupported_error};
+use symphonia_core::formats::prelude::*;
+use symphonia_core::formats::probe::{ProbeFormatData, ProbeableFormat, Score, Scoreable};
+use symphonia_core::formats::well_known::FORMAT_ID_AVI;
+use symphonia_core::io::*;
+use symphonia_core::meta::{Metadata, MetadataLog};
+use symphonia_core::support_format;
+
+use log::{debug, error, info, warn};
+
+use crate::common::{ByteOrder, ChunksReader, FormatAdpcm};
+mod chunks;
+use chunks::*;
+
+/// AVI is a RIFF stream, with a "RIFF" ASCII stream marker.
+const AVI_STREAM_MARKER: [u8; 4] = *b"RIFF";
+/// The RIFF form of the first RIFF chunk.
+const AVI_RIFF_FORM: [u8; 4] = *b"AVI ";
+/// The RIFF form of OpenDML extended RIFF chunks following the first RIFF chunk.
+const AVI_EXTENDED_RIFF_FORM: [u8; 4] = *b"AVIX";
+
+const AVI_FORMAT_INFO: FormatInfo =
+    FormatInfo { format: FORMAT_ID_AVI, short_name: "avi", long_name: "Audio Video Interleave" };
+
+/// An entry in a stream's seek index.
+struct IndexPoint {
+    /// The absolute position of the data chunk's header.
+    pos: u64,
+    /// The timestamp of the first frame in the data chunk.
+    ts: i64,
+    is_keyframe: bool,
+}
+
+struct StreamState {
+    /// The size of a sample, or 0 if each data chunk contains a single sample.
+    sample_size: u32,
+    /// The number of frames per sample.
+    frames_per_sample: u64,
+    /// The timestamp of the next data chunk.
+    next_ts: i64,
+    /// If true, packets are returned for the stream.
+    selected: bool,
+    /// The seek index built from the `idx1` chunk.
+    index: Vec<IndexPoint>,
+    /// The timestamp following the last indexed data chunk.
+    index_end_ts: i64,
+}
+
+impl StreamState {
+    /// Get the duration of a data chunk of the given length.
+    fn chunk_dur(&self, len: u32) -> u64 {
+        match len.checked_div(self.sample_size) {
+            Some(num_samples) => u64::from(num_samples) * self.frames_per_sample,
+            // Each data chunk is one sample, even if empty.
+            None => self.frames_per_sample,
+        }
+    }
+}
+
+/// The contents of a `strl` list.
+struct StreamList {
+    header: AviStreamHeaderChunk,
+    /// The raw stream format.
+    format: Option<Box<[u8]>>,
+}
+
+/// The bounds of a `movi` list.
+#[derive(Copy, Clone)]
+struct MoviList {
+    /// The end of the `movi` list, if known.
+    end: Option<u64>,
+    /// The end of the parent RIFF chunk, if known.
+    riff_end: Option<u64>,
+}
+
+/// Audio Video Interleave (AVI) format reader.
+///
+/// `AviReader` implements a demuxer for the AVI container format, including OpenDML extended
+/// files.
+pub struct AviReader<'s> {
+    reader: MediaSourceStream<'s>,
+    media_info: MediaInfo,
+    tracks: Vec<Track>,
+    streams: Vec<StreamState>,
+    chapters: Option<ChapterGroup>,
+    metadata: MetadataLog,
+    /// The index of the stream used for seeking if no track is specified.
+    default_stream: usize,
+    /// The first `movi` list.
+    first_movi: MoviList,
+    /// The `movi` list currently being read.
+    movi: MoviList,
+}
+
+impl<'s> AviReader<'s> {
+    pub fn try_new(mut mss: MediaSourceStream<'s>, opts: FormatOptions) -> Result<Self> {
+        let riff_pos = mss.pos();
+
+        // The top-level chunk has the RIFF chunk ID. This is also the file marker.
+        let marker = mss.read_quad_bytes()?;
+
+        if marker != AVI_STREAM_MARKER {
+            return unsupported_error("avi: missing avi riff stream marker");
+        }
+
+        // The length of the top-level RIFF chunk. Must be atleast 4 bytes.
+        let riff_len = mss.read_u32()?;
+
+        if riff_len < 4 {
+            return decode_error("avi: invalid riff length");
+        }
+
+        // The form type. Only the AVI form is supported.
+        let riff_form = mss.read_quad_bytes()?;
+
+        if riff_form != AVI_RIFF_FORM {
+            error!("riff form is not avi ({})", String::from_utf8_lossy(&riff_form));
+
+            return unsupported_error("avi: riff form is not avi");
+        }
+
+        // Streamed files may not have a valid RIFF chunk length.
+        let riff_data_len = if riff_len < u32::MAX { Some(riff_len - 4) } else { None };
+        let riff_end = riff_data_len.map(|len| riff_pos + 12 + u64::from(len));
+
+        let mut riff_chunks =
+            ChunksReader::<RiffAviChunks>::new(riff_data_len, ByteOrder::LittleEndian);
+
+        let mut stream_lists = Vec::new();
+        let mut movi = None;
+        let mut index = None;
+
+        while let Some(chunk) = riff_chunks.next(&mut mss)? {
+            match chunk {
+                RiffAviChunks::List(lst) => {
+                    let list = lst.parse(&mut mss)?;
+
+                    match &list.form {
+                        b"hdrl" => {
+                            stream_lists =
+                                read_header_list(&mut mss, list.len, opts.limit_element_bytes)?
+                        }
+                        b"movi" => {
+                            let movi_pos = mss.pos();
+
+                            // A length of 0 may be written by muxers that were interrupted, or are
+                            // streaming.
+                            let end = Some(list.len)
+                                .filter(|&len| len > 0)
+                                .map(|len| movi_pos + u64::from(len));
+
+                            movi = Some((movi_pos, MoviList { end, riff_end }));
+
+                            // The legacy index follows the movi list. It can only be read if the
+                            // source is seekable and the movi list length is known.
+                            if !mss.is_seekable() || end.is_none() {
+                                break;
+                            }
+
+                            list.skip(&mut mss)?;
+                        }
+                        _ => list.skip(&mut mss)?,
+                    }
+                }
+                RiffAviChunks::Index(idx1) => {
+                    index = Some(idx1.parse_limited(&mut mss, opts.limit_element_bytes)?);
+                }
+            }
+        }
+
+        let Some((movi_pos, first_movi)) = movi
+        else {
+            return unsupported_error("avi: missing movi list");
+        };
+
+        if stream_lists.is_empty() {
+            return decode_error("avi: missing stream headers");
+        }
+
+        let mut tracks = Vec::with_capacity(stream_lists.len());
+        let mut streams = Vec::with_capacity(stream_lists.len());
+
+        for (id, list) in stream_lists.into_iter().enumerate() {
+            debug!("stream {id}: {}", list.header);
+
+            let (track, stream) = make_track(id as u32, &list.header, list.format.as_deref())?;
+
+            tracks.push(track);
+            streams.push(stream);
+        }
+
+        // Build the seek index from the legacy index, if present.
+        if let Some(index) = index {
+            build_seek_index(&mut streams, &index.entries, movi_pos);
+        }
+
+        // Prefer seeking on the first video stream.
+        let default_stream = tracks
+            .iter()
+            .position(|track| matches!(track.codec_params, Some(CodecParameters::Video(_))))
+            .unwrap_or(0);
+
+        // Return to the start of the movi list data.
+        if mss.pos() != movi_pos {
+            mss.seek(SeekFrom::Start(movi_pos))?;
+        }
+
+        Ok(AviReader {
+            reader: mss,
+            media_info: MediaInfo::from_tracks(&tracks),
+            tracks,
+            streams,
+            chapters: opts.external_data.chapters,
+            metadata: opts.external_data.metadata.unwrap_or_default(),
+            default_stream,
+            first_movi,
+            movi: first_movi,
+        })
+    }
+
+    /// Find the next `movi` list in an OpenDML extended RIFF chunk. Returns `false` if there are
+    /// no more `movi` lists.
+    fn next_movi_list(&mut self) -> Result<bool> {
+        // Skip to the end of the current RIFF chunk.
+        let Some(riff_end) = self.movi.riff_end
+        else {
+            return Ok(false);
+        };
+
+        // Align to the next 2-byte boundary.
+        let riff_end = riff_end + (riff_end & 0x1);
+
+        let pos = self.reader.pos();
+
+        if pos < riff_end {
+            self.reader.ignore_bytes(riff_end - pos)?;
+        }
+
+        let riff_pos = self.reader.pos();
+
+        let header = match read_chunk_header(&mut self.reader)? {
+            Some(header) => header,
+            None => return Ok(false),
+        };
+
+        if header.0 != AVI_STREAM_MARKER || header.1 < 4 {
+            return Ok(false);
+        }
+
+        if self.reader.read_quad_bytes()? != AVI_EXTENDED_RIFF_FORM {
+            return Ok(false);
+        }
+
+        let riff_end = Some(riff_pos + 8 + u64::from(header.1));
+
+        let mut riff_chunks =
+            ChunksReader::<RiffAviChunks>::new(Some(header.1 - 4), ByteOrder::LittleEndian);
+
+        while let Some(chunk) = riff_chunks.next(&mut self.reader)? {
+            match chunk {
+                RiffAviChunks::List(lst) => {
+                    let list = lst.parse(&mut self.reader)?;
+
+                    if &list.form == b"movi" {
+                        let end = Some(self.reader.pos() + u64::from(list.len));
+
+                        self.movi = MoviList { end, riff_end };
+                        return Ok(true);
+                    }
+
+                    list.skip(&mut self.reader)?;
+                }
+                RiffAviChunks::Index(idx1) => {
+                    // A legacy index is not expected in an extended RIFF chunk.
+                    let _ = idx1.parse(&mut self.reader)?;
+                }
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+/// Read the stream lists from the `hdrl` list.
+fn read_header_list(
+    mss: &mut MediaSourceStream<'_>,
+    len: u32,
+    limit: Limit,
+) -> Result<Vec<StreamList>> {
+    let mut streams = Vec::new();
+
+    let mut chunks = ChunksReader::<AviHeaderListChunks>::new(Some(len), ByteOrder::LittleEndian);
+
+    while let Some(chunk) = chunks.next(mss)? {
+        match chunk {
+            AviHeaderListChunks::MainHeader(avih) => {
+                let header = avih.parse_and_skip_unread(mss)?;
+                debug!("{header}");
+            }
+            AviHeaderListChunks::List(lst) => {
+                let list = lst.parse(mss)?;
+
+                match &list.form {
+                    b"strl" => {
+                        if let Some(stream) = read_stream_list(mss, list.len, limit)? {
+                            streams.push(stream);
+                        }
+                    }
+                    _ => list.skip(mss)?,
+                }
+            }
+        }
+    }
+
+    chunks.finish(mss)?;
+
+    Ok(streams)
+}
+
+/// Read the stream header and format from a `strl` list.
+fn read_stream_list(
+    mss: &mut MediaSourceStream<'_>,
+    len: u32,
+    limit: Limit,
+) -> Result<Option<StreamList>> {
+    let mut header = None;
+    let mut format = None;
+
+    let mut chunks = ChunksReader::<AviStreamListChunks>::new(Some(len), ByteOrder::LittleEndian);
+
+    while let Some(chunk) = chunks.next(mss)? {
+        match chunk {
+            AviStreamListChunks::StreamHeader(strh) => {
+                header = Some(strh.parse_and_skip_unread(mss)?);
+            }
+            AviStreamListChunks::StreamFormat(strf) => {
+                format = Some(strf.parse_limited(mss, limit)?.buf);
+            }
+        }
+    }
+
+    chunks.finish(mss)?;
+
+    // A stream list without a stream header is ignored.
+    if header.is_none() {
+        warn!("stream list is missing a stream header");
+    }
+
+    Ok(header.map(|header| StreamList { header, format }))
+}
+
+/// Create the track, and its associated state, for a stream.
+fn make_track(
+    id: u32,
+    header: &AviStreamHeaderChunk,
+    format: Option<&[u8]>,
+) -> Result<(Track, StreamState)> {
+    let mut track = Track::new(id);
+
+    let mut stream = StreamState {
+        sample_size: header.sample_size,
+        frames_per_sample: 1,
+        next_ts: 0,
+        selected: true,
+        index: Vec::new(),
+        index_end_ts: 0,
+    };
+
+    // The stream's time base is the reciprocal of the stream's sample rate, rate / scale.
+    let mut time_base = TimeBase::try_new(header.scale, header.rate).map(|tb| tb.reduce());
+
+    match (&header.stream_type, format) {
+        (b"auds", Some(format)) => {
+            let wfx = WaveFormatEx::read(format)?;
+
+            let mut codec_params = AudioCodecParameters::new();
+
+            if let Some(codec) = wfx.codec_id() {
+                codec_params.for_codec(codec);
+            }
+            else {
+                info!("unsupported audio format tag: {:#06x}", wfx.format_tag);
+            }
+
+            codec_params.with_sample_rate(wfx.sample_rate);
+
+            let positions = match wfx.channel_mask {
+                Some(mask) if mask.count_ones() == u32::from(wfx.num_channels) => {
+                    Position::from_wave_channel_mask(mask)
+                }
+                _ => Position::from_wave_channel_count(u32::from(wfx.num_channels)),
+            };
+
+            let channels = match positions {
+                Some(positions) => Channels::Positioned(positions),
+                None => Channels::Discrete(wfx.num_channels),
+            };
+
+            match codec_params.codec {
+                CODEC_ID_PCM_U8 | CODEC_ID_PCM_S16LE | CODEC_ID_PCM_S24LE | CODEC_ID_PCM_S32LE => {
+                    codec_params
+                        .with_bits_per_coded_sample(u32::from(wfx.bits_per_sample))
+                        .with_bits_per_sample(u32::from(wfx.bits_per_sample));
+                }
+                codec @ (CODEC_ID_ADPCM_MS | CODEC_ID_ADPCM_IMA_WAV) => {
+                    let adpcm = FormatAdpcm {
+                        block_align: wfx.block_align,
+                        bits_per_sample: wfx.bits_per_sample,
+                        channels: channels.clone(),
+                        codec,
+                    };
+
+                    let info = adpcm.make_packet_info()?;
+                    let frames_per_block = info.frames_per_block.get();
+
+                    codec_params.with_frames_per_block(frames_per_block);
+
+                    if header.suggested_buffer_size > 0 {
+                        let max_blocks = u64::from(header.suggested_buffer_size)
+                            .div_ceil(u64::from(wfx.block_align));
+
+                        codec_params.with_max_frames_per_packet(max_blocks * frames_per_block);
+                    }
+
+                    // ADPCM decoders require packet durations in audio frames. If the stream is
+                    // sampled in whole blocks, timestamp the stream in audio frames instead.
+                    if header.sample_size == u32::from(wfx.block_align) {
+                        stream.frames_per_sample = frames_per_block;
+                        time_base = TimeBase::try_from_recip(wfx.sample_rate);
+                    }
+                }
+                _ => (),
+            }
+
+            codec_params.with_channels(channels);
+
+            if !wfx.extra_data.is_empty() {
+                codec_params.with_extra_data(wfx.extra_data);
+            }
+
+            track.with_codec_params(CodecParameters::Audio(codec_params));
+        }
+        (b"vids", Some(format)) => {
+            let bih = BitmapInfoHeader::read(format)?;
+
+            let mut codec_params = VideoCodecParameters::default();
+
+            match bih.codec_id() {
+                Some(codec) => {
+                    codec_params.for_codec(codec);
+                }
+                None => info!("unsupported video compression: {:?}", bih.compression),
+            }
+
+            // A negative height indicates a top-down bitmap.
+            if let Ok(width) = u16::try_from(bih.width) {
+                codec_params.with_width(width);
+            }
+            if let Ok(height) = u16::try_from(bih.height.unsigned_abs()) {
+                codec_params.with_height(height);
+            }
+
+            // VC-1 Advanced profile extra data contains the sequence header.
+            if codec_params.codec == CODEC_ID_VC1 {
+                if let Ok(seq) = vc1::SequenceHeader::find(&bih.extra_data) {
+                    codec_params
+                        .with_profile(seq.profile)
+                        .with_level(seq.level)
+                        .with_codec_size(seq.width, seq.height);
+
+                    if let Some(frame_rate) = seq.frame_rate {
+                        codec_params.with_frame_rate(frame_rate);
+                    }
+                }
+            }
+
+            // VC-1 Simple and Main profile extra data is the sequence layer data.
+            if codec_params.codec == CODEC_ID_WMV3 {
+                if let Ok(seq) = vc1::SequenceLayerData::read(&bih.extra_data) {
+                    codec_params.with_profile(seq.profile);
+                }
+            }
+
+            if !bih.extra_data.is_empty() {
+                codec_params.add_extra_data(VideoExtraData {
+                    id: VIDEO_EXTRA_DATA_ID_NULL,
+                    data: bih.extra_data,
+                });
+            }
+
+            track.with_codec_params(CodecParameters::Video(codec_params));
+            track.with_num_frames(u64::from(header.length));
+        }
+        _ => {
+            info!("unsupported stream type: {}", String::from_utf8_lossy(&header.stream_type));
+        }
+    }
+
+    let start = i64::from(header.start) * stream.frames_per_sample as i64;
+    let length = u64::from(header.length) * stream.frames_per_sample;
+
+    stream.next_ts = start;
+
+    if let Some(time_base) = time_base {
+        track.with_time_base(time_base);
+    }
+
+    track.with_start_ts(Timestamp::new(start)).with_duration(Duration::from(length));
+
+    Ok((track, stream))
+}
+
+/// Build the seek index of each stream from the entries of the legacy index.
+fn build_seek_index(streams: &mut [StreamState], entries: &[AviIndexEntry], movi_pos: u64) {
+    // Index entry offsets should be relative to the movi list's form type, but some muxers write
+    // absolute file offsets.
+    let movi_form_pos = movi_pos - 4;
+
+    let base = match entries.first() {
+        Some(entry) if u64::from(entry.offset) >= movi_form_pos => 0,
+        _ => movi_form_pos,
+    };
+
+    for entry in entries {
+        // Palette changes are not indexed.
+        if &entry.ckid[2..] == b"pc" {
+            continue;
+        }
+
+        let Some(stream) =
+            stream_index_from_ckid(&entry.ckid).and_then(|index| streams.get_mut(index as usize))
+        else {
+            continue;
+        };
+
+        if stream.index.is_empty() {
+            stream.index_end_ts = stream.next_ts;
+        }
+
+        // Every audio chunk can be seeked to.
+        let is_keyframe = entry.is_keyframe() || stream.sample_size > 0;
+
+        stream.index.push(IndexPoint {
+            pos: base + u64::from(entry.offset),
+            ts: stream.index_end_ts,
+            is_keyframe,
+        });
+
+        stream.index_end_ts += stream.chunk_dur(entry.len) as i64;
+    }
+}
+
+/// Read a chunk header. Returns `None` if the end of the stream was reached.
+fn read_chunk_header(reader: &mut MediaSourceStream<'_>) -> Result<Option<([u8; 4], u32)>> {
+    let tag = match reader.read_quad_bytes() {
+        Ok(tag) => tag,
+        Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(Some((tag, reader.read_u32()?)))
+}
+
+impl Scoreable for AviReader<'_> {
+    fn score(mut src: ScopedStream<&mut MediaSourceStream<'_>>) -> Result<Score> {
+        // Perform simple scoring by testing that the RIFF stream marker and RIFF form are both
+        // valid for AVI.
+        let riff_marker = src.read_quad_bytes()?;
+        src.ignore_bytes(4)?;
+        let riff_form = src.read_quad_bytes()?;
+
+        if riff_marker != AVI_STREAM_MARKER || riff_form != AVI_RIFF_FORM {
+            return Ok(Score::Unsupported);
+        }
+
+        Ok(Score::Supported(255))
+    }
+}
+
+impl ProbeableFormat<'_> for AviReader<'_> {
+    fn try_probe_new(
+        mss: MediaSourceStream<'_>,
+        opts: FormatOptions,
+    ) -> Result<Box<dyn FormatReader + '_>> {
+        Ok(Box::new(AviReader::try_new(mss, opts)?))
+    }
+
+    fn probe_data() -> &'static [ProbeFormatData] {
+        &[
+            // AVI RIFF form
+            support_format!(
+                AVI_FORMAT_INFO,
+                &["avi"],
+                &["video/x-msvideo", "video/avi", "video/msvideo"],
+                &[b"RIFF"]
+            ),
+        ]
+    }
+}
+
+impl FormatReader for AviReader<'_> {
+    fn format_info(&self) -> &FormatInfo {
+        &AVI_FORMAT_INFO
+    }
+
+    fn media_info(&self) -> &MediaInfo {
+        &self.media_info
+    }
+
+    fn next_packet(&mut self) -> Result<Option<Packet>> {
+        loop {
+            // Move to the next movi list at the end of the current one.
+            if let Some(end) = self.movi.end {
+                if self.reader.pos() + 8 > end {
+                    if !self.next_movi_list()? {
+                        return Ok(None);
+                    }
+                    continue;
+                }
+            }
+
+            let chunk_pos = self.reader.pos();
+
+            let Some((tag, len)) = read_chunk_header(&mut self.reader)?
+            else {
+                return Ok(None);
+            };
+
+            // Chunks are padded to a 2-byte boundary.
+            let padded_len = u64::from(len) + u64::from(len & 0x1);
+
+            if tag == *b"LIST" {
+                if len < 4 {
+                    return decode_error("avi: malformed list chunk");
+                }
+
+                // Data chunks may be grouped in rec lists. Descend into them, and skip all other
+                // lists.
+                if self.reader.read_quad_bytes()? != *b"rec " {
+                    self.reader.ignore_bytes(padded_len - 4)?;
+                }
+                continue;
+            }
+
+            // Only data chunks have a stream index. Palette changes are not supported.
+            let id = match stream_index_from_ckid(&tag) {
+                Some(id) if (id as usize) < self.streams.len() && &tag[2..] != b"pc" => id,
+                _ => {
+                    self.reader.ignore_bytes(padded_len)?;
+                    continue;
+                }
+            };
+
+            let stream = &mut self.streams[id as usize];
+
+            let pts = stream.next_ts;
+            let dur = stream.chunk_dur(len);
+
+            stream.next_ts += dur as i64;
+
+            // Empty data chunks, such as dropped video frames, only advance the timestamp.
+            if !stream.selected || len == 0 {
+                self.reader.ignore_bytes(padded_len)?;
+                continue;
+            }
+
+            // The legacy index signals which data chunks are keyframes. Data chunks that are not
+            // indexed are assumed to be keyframes.
+            let is_keyframe = stream
+                .index
+                .binary_search_by_key(&chunk_pos, |point| point.pos)
+                .ok()
+                .is_none_or(|i| stream.index[i].is_keyframe);
+
+            let buf = self.reader.read_boxed_slice_exact(len as usize)?;
+
+            if len & 0x1 == 1 {
+                self.reader.read_u8()?;
+            }
+
+            let mut packet = Packet::new(id, Timestamp::new(pts), Duration::from(dur), buf);
+            packet.is_keyframe = is_keyframe;
+
+            return Ok(Some(packet));
+        }
+    }
+
+    fn metadata(&mut self) -> Metadata<'_> {
+        self.metadata.metadata()
+    }
+
+    fn chapters(&self) -> Option<&ChapterGroup> {
+        self.chapters.as_ref()
+    }
+
+    fn tracks(&self) -> &[Track] {
+        &self.tracks
+    }
+
+    fn set_track_filter(&mut self, track_ids: &[u32]) -> Result<()> {
+        for (id, stream) in self.streams.iter_mut().enumerate() {
+            stream.selected = track_ids.is_empty() || track_ids.contains(&(id as u32));
+        }
+        Ok(())
+    }
+
+    fn is_track_selected(&self, track_id: u32) -> bool {
+        self.streams.get(track_id as usize).is_some_and(|stream| stream.selected)
+    }
+
+    fn seek(&mut self, _mode: SeekMode, to: SeekTo) -> Result<SeekedTo> {
+        if self.tracks.is_empty() {
+            return seek_error(SeekErrorKind::Unsupported);
+        }
+
+        // Track IDs are the stream indicies.
+        let (id, required_ts) = match to {
+            SeekTo::Time { time, track_id } => {
+                let id = track_id.map(|id| id as usize).unwrap_or(self.default_stream);
+
+                let track =
+                    self.tracks.get(id).ok_or(Error::SeekError(SeekErrorKind::InvalidTrack))?;
+
+                // The timebase is required to calculate the timestamp.
+                let tb = track.time_base.ok_or(Error::SeekError(SeekErrorKind::Unsupported))?;
+
+                // If the timestamp overflows, the seek if out-of-range.
+                let ts =
+                    tb.calc_timestamp(time).ok_or(Error::SeekError(SeekErrorKind::OutOfRange))?;
+
+                (id, ts)
+            }
+            SeekTo::Timestamp { ts, track_id } => {
+                if track_id as usize >= self.tracks.len() {
+                    return seek_error(SeekErrorKind::InvalidTrack);
+                }
+                (track_id as usize, ts)
+            }
+        };
+
+        // Seeking requires a seekable source, and the legacy index. The index is not read from an
+        // unseekable source, so check the source first.
+        if !self.reader.is_seekable() {
+            return seek_error(SeekErrorKind::Unseekable);
+        }
+
+        let index = &self.streams[id].index;
+
+        if index.is_empty() {
+            return seek_error(SeekErrorKind::Unsupported);
+        }
+
+        debug!("seeking track_id={id} to ts={required_ts}");
+
+        // Find the last keyframe at or before the required timestamp. If there is none, use the
+        // first keyframe.
+        let mut keyframes = index.iter().filter(|point| point.is_keyframe);
+
+        let first = keyframes.next().ok_or(Error::SeekError(SeekErrorKind::Unsupported))?;
+        let point =
+            keyframes.take_while(|point| point.ts <= required_ts.get()).last().unwrap_or(first);
+
+        let seek_pos = point.pos;
+        let actual_ts = Timestamp::new(point.ts);
+
+        // Resume the timestamps of all streams from their first data chunk following the seek
+        // position.
+        for stream in self.streams.iter_mut() {
+            if let Some(index_point) = stream.index.iter().find(|point| point.pos >= seek_pos) {
+                stream.next_ts = index_point.ts;
+            }
+            else if !stream.index.is_empty() {
+                stream.next_ts = stream.index_end_ts;
+            }
+        }
+
+        // The legacy index only covers the first movi list.
+        self.movi = self.first_movi;
+
+        self.reader.seek(SeekFrom::Start(seek_pos))?;
+
+        debug!(
+            "seeked to packet_ts={} (delta={})",
+            actual_ts,
+            actual_ts.saturating_delta(required_ts)
+        );
+
+        Ok(SeekedTo { track_id: id as u32, actual_ts, required_ts })
+    }
+
+    fn source_position(&self) -> Option<u64> {
+        self.reader.is_seekable().then(|| self.reader.pos())
+    }
+
+    fn into_inner<'s>(self: Box<Self>) -> MediaSourceStream<'s>
+    where
+        Self: 's,
+    {
+        self.reader
+    }
+}
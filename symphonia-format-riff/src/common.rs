@@ -16,6 +16,7 @@ use symphonia_core::codecs::audio::well_known::{
     CODEC_ID_PCM_F64BE, CODEC_ID_PCM_F64LE,
 };
 use symphonia_core::codecs::audio::{AudioCodecId, AudioCodecParameters};
+use symphonia_core::common::Limit;
 use symphonia_core::errors::{Result, decode_error};
 use symphonia_core::formats::prelude::*;
 use symphonia_core::io::{MediaSourceStream, ReadBytes};
@@ -29,6 +30,9 @@ pub enum ByteOrder {
 
 /// The maximum number of frames that will be in a packet.
 /// Since there are no real packets in AIFF, this is arbitrary, used same value as MP3.
+/// The default maximum size of a chunk that is read into memory.
+const DEFAULT_LIMIT_ELEMENT_BYTES: usize = 64 * 1024 * 1024;
+
 const MAX_FRAMES_PER_PACKET: NonZero<u64> = NonZero::new(1152).unwrap();
 
 /// `ParseChunkTag` implements `parse_tag` to map between the 4-byte chunk identifier and the
@@ -187,11 +191,21 @@ impl<P: ParseChunk> ChunkParser<P> {
     pub fn parse_and_skip_unread<B: ReadBytes>(&self, reader: &mut B) -> Result<P> {
         P::parse_and_skip_unread(reader, self.tag, self.len)
     }
+
+    /// Parse a chunk that is read into memory, and discard any unread data in the chunk. Returns
+    /// an error if the chunk is larger than `limit` bytes.
+    pub fn parse_limited<B: ReadBytes>(&self, reader: &mut B, limit: Limit) -> Result<P> {
+        if limit.is_exceeded_by(self.len as usize, DEFAULT_LIMIT_ELEMENT_BYTES) {
+            return decode_error("riff: chunk exceeds the size limit");
+        }
+        self.parse_and_skip_unread(reader)
+    }
 }
 
 pub enum FormatData {
     Pcm(FormatPcm),
     Adpcm(FormatAdpcm),
+    ItuAdpcm(FormatItuAdpcm),
     IeeeFloat(FormatIeeeFloat),
     Extensible(FormatExtensible),
     ALaw(FormatALaw),
@@ -256,6 +270,22 @@ impl FormatAdpcm {
     }
 }
 
+pub struct FormatItuAdpcm {
+    /// The number of bits per coded sample.
+    pub bits_per_sample: u16,
+    /// Channel bitmask.
+    pub channels: Channels,
+    /// Codec ID.
+    pub codec: AudioCodecId,
+}
+
+impl FormatItuAdpcm {
+    pub fn make_packet_info(&self) -> Result<PacketInfo> {
+        let num_channels = self.channels.count() as u32;
+        PacketInfo::with_packed_frames(u32::from(self.bits_per_sample) * num_channels)
+    }
+}
+
 pub struct FormatIeeeFloat {
     /// Channel bitmask.
     pub channels: Channels,
@@ -348,23 +378,41 @@ impl PacketInfo {
             None => return decode_error("riff: block size is 0"),
         };
 
-        // Atleast one block must be present per packet.
-        let max_blocks_per_packet = match NonZero::new(
-            frames_per_block.max(MAX_FRAMES_PER_PACKET).get() / frames_per_block.get(),
-        ) {
-            Some(value) => value,
-            _ => return decode_error("riff: max blocks per packet is 0"),
-        };
-
-        // Pre-compute maximum frames per packet.
-        let max_frames_per_packet = match max_blocks_per_packet.checked_mul(frames_per_block) {
-            Some(value) => value,
-            _ => return decode_error("riff: max frames per packet overflow"),
-        };
+        // Blocks are coded independently of each other, therefore each packet contains exactly one
+        // block.
+        let max_blocks_per_packet = NonZero::new(1).unwrap();
+        let max_frames_per_packet = frames_per_block;
 
         Ok(Self { block_size, frames_per_block, max_blocks_per_packet, max_frames_per_packet })
     }
 
+    /// Packetization for codecs that tightly pack frames of `bits_per_frame` bits without any
+    /// block structure. A block is the smallest number of frames that fill a whole number of bytes.
+    pub fn with_packed_frames(bits_per_frame: u32) -> Result<Self> {
+        if bits_per_frame == 0 {
+            return decode_error("riff: bits per frame is 0");
+        }
+
+        // A byte is 2^3 bits, therefore a block contains at most 8 frames.
+        let frames_per_block = 8 >> bits_per_frame.trailing_zeros().min(3);
+        let block_size = u64::from(bits_per_frame) * frames_per_block / 8;
+
+        // UNWRAP: Both values are non-zero.
+        let block_size = NonZero::new(block_size).unwrap();
+        let frames_per_block = NonZero::new(frames_per_block).unwrap();
+
+        // The maximum number of frames per packet is a multiple of 8.
+        let max_blocks_per_packet =
+            NonZero::new(MAX_FRAMES_PER_PACKET.get() / frames_per_block.get()).unwrap();
+
+        Ok(Self {
+            block_size,
+            frames_per_block,
+            max_blocks_per_packet,
+            max_frames_per_packet: MAX_FRAMES_PER_PACKET,
+        })
+    }
+
     pub fn without_blocks(frame_len: u32) -> Result<Self> {
         // Block size must be non-zero.
         let block_size = match NonZero::new(u64::from(frame_len)) {
@@ -426,7 +474,10 @@ pub fn next_packet(
     // Copy the frames.
     let packet_buf = reader.read_boxed_slice(pkt_len as usize)?;
 
-    Ok(Some(Packet::new(0, pts, dur, packet_buf)))
+    let mut packet = Packet::new(0, pts, dur, packet_buf);
+    packet.num_frames = Some(dur.get());
+
+    Ok(Some(packet))
 }
 
 /// TODO: format here refers to format chunk in Wave terminology, but the data being handled here is
@@ -449,6 +500,12 @@ pub fn append_format_params(
         FormatData::Adpcm(adpcm) => {
             codec_params.for_codec(adpcm.codec).with_channels(adpcm.channels);
         }
+        FormatData::ItuAdpcm(adpcm) => {
+            codec_params
+                .for_codec(adpcm.codec)
+                .with_bits_per_coded_sample(u32::from(adpcm.bits_per_sample))
+                .with_channels(adpcm.channels);
+        }
         FormatData::IeeeFloat(ieee) => {
             codec_params.for_codec(ieee.codec).with_channels(ieee.channels);
         }
@@ -468,6 +525,18 @@ pub fn append_format_params(
     }
 }
 
+/// Estimate the bitrate of a track from the length of its audio data and its duration.
+pub fn estimate_bitrate(track: &Track, data_len: u64) -> Option<u32> {
+    let duration = Timestamp::try_from(track.duration?.get()).ok()?;
+    let secs = track.time_base?.calc_time(duration)?.as_secs_f64();
+
+    if secs <= 0.0 {
+        return None;
+    }
+
+    Some((data_len as f64 * 8.0 / secs).round() as u32)
+}
+
 /// TODO: format here refers to format chunk in Wave terminology, but the data being handled here is
 /// generic - find a better name, or combine with append_data_params append_format_params
 pub fn append_data_params(track: &mut Track, data_len: u64, packet_info: &PacketInfo) {
@@ -18,10 +18,14 @@ mod common;
 
 #[cfg(feature = "aiff")]
 mod aiff;
+#[cfg(feature = "avi")]
+mod avi;
 #[cfg(feature = "wav")]
 mod wave;
 
 #[cfg(feature = "aiff")]
 pub use aiff::AiffReader;
+#[cfg(feature = "avi")]
+pub use avi::AviReader;
 #[cfg(feature = "wav")]
 pub use wave::WavReader;
@@ -11,10 +11,11 @@ use symphonia_core::audio::AmbisonicBFormat;
 use symphonia_core::audio::{ChannelLabel, Channels, Position};
 use symphonia_core::codecs::audio::AudioCodecId;
 use symphonia_core::codecs::audio::well_known::{
-    CODEC_ID_ADPCM_IMA_WAV, CODEC_ID_ADPCM_MS, CODEC_ID_PCM_ALAW, CODEC_ID_PCM_F32LE,
-    CODEC_ID_PCM_F64LE, CODEC_ID_PCM_MULAW, CODEC_ID_PCM_S16LE, CODEC_ID_PCM_S24LE,
-    CODEC_ID_PCM_S32LE, CODEC_ID_PCM_U8,
+    CODEC_ID_ADPCM_G722, CODEC_ID_ADPCM_G726, CODEC_ID_ADPCM_IMA_WAV, CODEC_ID_ADPCM_MS,
+    CODEC_ID_PCM_ALAW, CODEC_ID_PCM_F32LE, CODEC_ID_PCM_F64LE, CODEC_ID_PCM_MULAW,
+    CODEC_ID_PCM_S16LE, CODEC_ID_PCM_S24LE, CODEC_ID_PCM_S32LE, CODEC_ID_PCM_U8,
 };
+use symphonia_core::common::Limit;
 use symphonia_core::errors::{Error, Result, decode_error, unsupported_error};
 use symphonia_core::formats::Track;
 use symphonia_core::io::{MediaSourceStream, ReadBytes};
@@ -24,7 +25,8 @@ use symphonia_metadata::embedded::riff;
 
 use crate::common::{
     ByteOrder, ChunkParser, ChunksReader, FormatALaw, FormatAdpcm, FormatData, FormatExtensible,
-    FormatIeeeFloat, FormatMuLaw, FormatPcm, NullChunks, PacketInfo, ParseChunk, ParseChunkTag,
+    FormatIeeeFloat, FormatItuAdpcm, FormatMuLaw, FormatPcm, NullChunks, PacketInfo, ParseChunk,
+    ParseChunkTag,
 };
 
 use log::info;
@@ -147,6 +149,48 @@ impl WaveFormatChunk {
         Ok(FormatData::Adpcm(FormatAdpcm { block_align, bits_per_sample, channels, codec }))
     }
 
+    fn read_itu_adpcm_fmt<B: ReadBytes>(
+        reader: &mut B,
+        bits_per_sample: u16,
+        num_channels: u16,
+        sample_rate: u32,
+        avg_bytes_per_sec: u32,
+        len: u32,
+        codec: AudioCodecId,
+    ) -> Result<FormatData> {
+        // The extension data length field is optional.
+        if len >= 18 {
+            let extra_size = reader.read_u16()?;
+
+            if extra_size > 0 {
+                reader.ignore_bytes(u64::from(extra_size))?;
+            }
+        }
+
+        let bits_per_sample = match codec {
+            // G.722 always codes a pair of samples into one byte.
+            CODEC_ID_ADPCM_G722 => 4,
+            // G.726 codes each sample with 2 to 5 bits. If the bits per sample field is not valid,
+            // derive the code size from the bitrate instead.
+            _ => match bits_per_sample {
+                2..=5 => bits_per_sample,
+                _ if sample_rate > 0 && num_channels > 0 => {
+                    let bits = 8 * u64::from(avg_bytes_per_sec)
+                        / (u64::from(sample_rate) * u64::from(num_channels));
+
+                    match bits {
+                        2..=5 => bits as u16,
+                        _ => return decode_error("wav: invalid bits per sample for fmt_g726"),
+                    }
+                }
+                _ => return decode_error("wav: invalid bits per sample for fmt_g726"),
+            },
+        };
+
+        let channels = map_wave_channel_count(num_channels)?;
+        Ok(FormatData::ItuAdpcm(FormatItuAdpcm { bits_per_sample, channels, codec }))
+    }
+
     fn read_ieee_fmt<B: ReadBytes>(
         reader: &mut B,
         bits_per_sample: u16,
@@ -400,6 +444,7 @@ impl WaveFormatChunk {
         match &self.format_data {
             FormatData::Pcm(pcm) => pcm.make_packet_info(),
             FormatData::Adpcm(adpcm) => adpcm.make_packet_info(),
+            FormatData::ItuAdpcm(adpcm) => adpcm.make_packet_info(),
             FormatData::IeeeFloat(ieee) => ieee.make_packet_info(),
             FormatData::ALaw(alaw) => alaw.make_packet_info(),
             FormatData::MuLaw(mulaw) => mulaw.make_packet_info(),
@@ -431,6 +476,10 @@ impl ParseChunk for WaveFormatChunk {
         const WAVE_FORMAT_ALAW: u16 = 0x0006;
         const WAVE_FORMAT_MULAW: u16 = 0x0007;
         const WAVE_FORMAT_ADPCM_IMA: u16 = 0x0011;
+        const WAVE_FORMAT_G726_ADPCM: u16 = 0x0045;
+        const WAVE_FORMAT_APICOM_G726_ADPCM: u16 = 0x0064;
+        const WAVE_FORMAT_APICOM_G722_ADPCM: u16 = 0x0065;
+        const WAVE_FORMAT_G722_ADPCM: u16 = 0x028f;
         const WAVE_FORMAT_EXTENSIBLE: u16 = 0xfffe;
 
         let format_data = match format {
@@ -468,6 +517,26 @@ impl ParseChunk for WaveFormatChunk {
                 len,
                 CODEC_ID_ADPCM_IMA_WAV,
             ),
+            // The ITU-T G.726 ADPCM Format
+            WAVE_FORMAT_G726_ADPCM | WAVE_FORMAT_APICOM_G726_ADPCM => Self::read_itu_adpcm_fmt(
+                reader,
+                bits_per_sample,
+                num_channels,
+                sample_rate,
+                avg_bytes_per_sec,
+                len,
+                CODEC_ID_ADPCM_G726,
+            ),
+            // The ITU-T G.722 ADPCM Format
+            WAVE_FORMAT_G722_ADPCM | WAVE_FORMAT_APICOM_G722_ADPCM => Self::read_itu_adpcm_fmt(
+                reader,
+                bits_per_sample,
+                num_channels,
+                sample_rate,
+                avg_bytes_per_sec,
+                len,
+                CODEC_ID_ADPCM_G722,
+            ),
             // Unsupported format.
             _ => return unsupported_error("wav: unsupported wave format"),
         }?;
@@ -505,6 +574,12 @@ impl fmt::Display for WaveFormatChunk {
                 writeln!(f, "\t\tchannels: {},", adpcm.channels)?;
                 writeln!(f, "\t\tcodec: {},", adpcm.codec)?;
             }
+            FormatData::ItuAdpcm(ref adpcm) => {
+                writeln!(f, "\tformat_data: ItuAdpcm {{")?;
+                writeln!(f, "\t\tbits_per_sample: {},", adpcm.bits_per_sample)?;
+                writeln!(f, "\t\tchannels: {},", adpcm.channels)?;
+                writeln!(f, "\t\tcodec: {},", adpcm.codec)?;
+            }
             FormatData::IeeeFloat(ref ieee) => {
                 writeln!(f, "\tformat_data: IeeeFloat {{")?;
                 writeln!(f, "\t\tchannels: {},", ieee.channels)?;
@@ -598,7 +673,6 @@ pub struct InfoChunk {
 
 impl ParseChunk for InfoChunk {
     fn parse<B: ReadBytes>(reader: &mut B, tag: [u8; 4], len: u32) -> Result<InfoChunk> {
-        // TODO: Apply limit.
         let buf = reader.read_boxed_slice_exact(len as usize)?;
         Ok(InfoChunk { tag, buf })
     }
@@ -659,13 +733,17 @@ pub fn append_fact_params(track: &mut Track, fact: &FactChunk) {
     track.with_num_frames(u64::from(fact.num_frames));
 }
 
-pub fn read_info_chunk(source: &mut MediaSourceStream<'_>, len: u32) -> Result<MetadataRevision> {
+pub fn read_info_chunk(
+    source: &mut MediaSourceStream<'_>,
+    len: u32,
+    limit: Limit,
+) -> Result<MetadataRevision> {
     let mut builder = MetadataBuilder::new(WAVE_METADATA_INFO);
 
     let mut list = ChunksReader::<RiffInfoListChunks>::new(Some(len), ByteOrder::LittleEndian);
 
     while let Some(RiffInfoListChunks::Info(info)) = list.next(source)? {
-        let info = info.parse(source)?;
+        let info = info.parse_limited(source, limit)?;
         // Ignore errors while parsing one chunk.
         let _ = riff::parse_riff_info_chunk(info.tag, &info.buf, &mut builder);
     }
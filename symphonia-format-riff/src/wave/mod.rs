@@ -13,6 +13,7 @@ use symphonia_core::errors::{Error, Result, SeekErrorKind};
 use symphonia_core::errors::{decode_error, seek_error, unsupported_error};
 use symphonia_core::formats::prelude::*;
 use symphonia_core::formats::probe::{ProbeFormatData, ProbeableFormat, Score, Scoreable};
+use symphonia_core::formats::util::TrackFilter;
 use symphonia_core::formats::well_known::FORMAT_ID_WAVE;
 use symphonia_core::io::*;
 use symphonia_core::meta::well_known::METADATA_ID_WAVE;
@@ -22,7 +23,8 @@ use symphonia_core::support_format;
 use log::{debug, error};
 
 use crate::common::{
-    ByteOrder, ChunksReader, PacketInfo, append_data_params, append_format_params, next_packet,
+    ByteOrder, ChunksReader, PacketInfo, append_data_params, append_format_params,
+    estimate_bitrate, next_packet,
 };
 mod chunks;
 use chunks::*;
@@ -53,6 +55,7 @@ pub struct WavReader<'s> {
     tracks: Vec<Track>,
     chapters: Option<ChapterGroup>,
     metadata: MetadataLog,
+    track_filter: TrackFilter,
     packet_info: PacketInfo,
     data_start_pos: u64,
     data_end_pos: Option<u64>,
@@ -132,7 +135,11 @@ impl<'s> WavReader<'s> {
                     // Riff Lists can have many different forms, but WavReader only supports Info
                     // lists.
                     match &list.form {
-                        b"INFO" => metadata.push(read_info_chunk(&mut mss, list.len)?),
+                        b"INFO" => metadata.push(read_info_chunk(
+                            &mut mss,
+                            list.len,
+                            opts.limit_element_bytes,
+                        )?),
                         _ => list.skip(&mut mss)?,
                     }
                 }
@@ -170,6 +177,7 @@ impl<'s> WavReader<'s> {
                         tracks: vec![track],
                         chapters: opts.external_data.chapters,
                         metadata: opts.external_data.metadata.unwrap_or_default(),
+                        track_filter: Default::default(),
                         packet_info,
                         data_start_pos,
                         data_end_pos,
@@ -227,13 +235,19 @@ impl FormatReader for WavReader<'_> {
     }
 
     fn next_packet(&mut self) -> Result<Option<Packet>> {
-        next_packet(
-            &mut self.reader,
-            &self.packet_info,
-            &self.tracks,
-            self.data_start_pos,
-            self.data_end_pos.unwrap_or(u64::MAX),
-        )
+        // The packets of unselected tracks are discarded after reading them.
+        loop {
+            match next_packet(
+                &mut self.reader,
+                &self.packet_info,
+                &self.tracks,
+                self.data_start_pos,
+                self.data_end_pos.unwrap_or(u64::MAX),
+            )? {
+                Some(packet) if !self.track_filter.is_selected(packet.track_id) => (),
+                packet => return Ok(packet),
+            }
+        }
     }
 
     fn metadata(&mut self) -> Metadata<'_> {
@@ -248,9 +262,28 @@ impl FormatReader for WavReader<'_> {
         &self.tracks
     }
 
+    fn track_filter(&self) -> Option<&TrackFilter> {
+        Some(&self.track_filter)
+    }
+
+    fn track_filter_mut(&mut self) -> Option<&mut TrackFilter> {
+        Some(&mut self.track_filter)
+    }
+
+    fn estimate_track_bitrate(&mut self, track_id: u32) -> Option<u32> {
+        // The audio data spans to the end of the data chunk, or the end of the source if the
+        // length of the data chunk is unknown.
+        let data_end_pos = self.data_end_pos.or_else(|| self.reader.byte_len())?;
+        let data_len = data_end_pos.checked_sub(self.data_start_pos)?;
+
+        let track = self.tracks.iter().find(|track| track.id == track_id)?;
+
+        estimate_bitrate(track, data_len)
+    }
+
     fn seek(&mut self, _mode: SeekMode, to: SeekTo) -> Result<SeekedTo> {
         if self.tracks.is_empty() {
-            return seek_error(SeekErrorKind::Unseekable);
+            return seek_error(SeekErrorKind::Unsupported);
         }
 
         let track = &self.tracks[0];
@@ -261,7 +294,7 @@ impl FormatReader for WavReader<'_> {
             // Time value given, calculate frame timestamp using the time base.
             SeekTo::Time { time, .. } => {
                 // The timebase is required to calculate the timestamp.
-                let tb = track.time_base.ok_or(Error::SeekError(SeekErrorKind::Unseekable))?;
+                let tb = track.time_base.ok_or(Error::SeekError(SeekErrorKind::Unsupported))?;
 
                 // If the timestamp overflows, the seek if out-of-range.
                 tb.calc_timestamp(time).ok_or(Error::SeekError(SeekErrorKind::OutOfRange))?
@@ -321,6 +354,10 @@ impl FormatReader for WavReader<'_> {
         Ok(SeekedTo { track_id: 0, actual_ts, required_ts })
     }
 
+    fn source_position(&self) -> Option<u64> {
+        self.reader.is_seekable().then(|| self.reader.pos())
+    }
+
     fn into_inner<'s>(self: Box<Self>) -> MediaSourceStream<'s>
     where
         Self: 's,
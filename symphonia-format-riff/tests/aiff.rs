@@ -0,0 +1,70 @@
+#![cfg(feature = "aiff")]
+
+use symphonia_core::common::Limit;
+use symphonia_core::formats::FormatOptions;
+use symphonia_core::io::MediaSourceStream;
+use symphonia_format_riff::AiffReader;
+
+/// Write an AIFF chunk with the given tag and data. The chunk is padded to an even length.
+fn chunk(tag: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    buf.extend_from_slice(tag);
+    buf.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    buf.extend_from_slice(data);
+    if data.len() & 0x1 == 1 {
+        buf.push(0);
+    }
+    buf
+}
+
+/// Build an AIFF file containing one frame of 8 kHz, 16-bit, mono PCM audio, followed by the given
+/// chunks.
+fn make_aiff(chunks: &[u8]) -> Vec<u8> {
+    let mut comm = Vec::new();
+    // The number of channels, frames, and bits per sample.
+    comm.extend_from_slice(&1u16.to_be_bytes());
+    comm.extend_from_slice(&1u32.to_be_bytes());
+    comm.extend_from_slice(&16u16.to_be_bytes());
+    // The sample rate as an 80-bit extended precision float.
+    comm.extend_from_slice(&[0x40, 0x0b, 0xfa, 0, 0, 0, 0, 0, 0, 0]);
+
+    // The offset and block size, followed by the audio data.
+    let ssnd = [0u8; 10];
+
+    let form = [b"AIFF".to_vec(), chunk(b"COMM", &comm), chunk(b"SSND", &ssnd), chunks.to_vec()];
+
+    chunk(b"FORM", &form.concat())
+}
+
+fn open_aiff(data: Vec<u8>, limit: Limit) -> symphonia_core::errors::Result<AiffReader<'static>> {
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    AiffReader::try_new(mss, FormatOptions::default().limit_element_bytes(limit))
+}
+
+#[test]
+fn limit_element_bytes() {
+    // An application-specific chunk, and a name chunk, each 32 bytes long.
+    let appl = [b"abcd".as_slice(), &[0; 28]].concat();
+
+    for data in [chunk(b"APPL", &appl), chunk(b"NAME", &[b'a'; 32])] {
+        assert!(open_aiff(make_aiff(&data), Limit::Maximum(16)).is_err());
+        assert!(open_aiff(make_aiff(&data), Limit::Maximum(32)).is_ok());
+        assert!(open_aiff(make_aiff(&data), Limit::None).is_ok());
+    }
+
+    // An ID3v2.3 tag with a 32 byte title frame. Oversized frames are skipped unless in strict
+    // mode.
+    let frame = [b"TIT2".as_slice(), &32u32.to_be_bytes(), &[0; 2], &[0], &[b'a'; 31]].concat();
+    let id3 = chunk(b"ID3 ", &[b"ID3\x03\x00\x00\x00\x00\x00".as_slice(), &[42], &frame].concat());
+
+    let open = |limit, strict| {
+        let opts = FormatOptions::default().limit_element_bytes(limit).strict(strict);
+        let data = make_aiff(&id3);
+        let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+        AiffReader::try_new(mss, opts)
+    };
+
+    assert!(open(Limit::Maximum(16), false).is_ok());
+    assert!(open(Limit::Maximum(16), true).is_err());
+    assert!(open(Limit::Maximum(32), true).is_ok());
+}
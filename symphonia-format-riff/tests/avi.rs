@@ -0,0 +1,344 @@
+#![cfg(feature = "avi")]
+
+use symphonia_core::codecs::CodecParameters;
+use symphonia_core::codecs::audio::well_known::CODEC_ID_PCM_S16LE;
+use symphonia_core::codecs::video::well_known::profiles::{
+    CODEC_PROFILE_VC1_ADVANCED, CODEC_PROFILE_VC1_MAIN,
+};
+use symphonia_core::codecs::video::well_known::{CODEC_ID_MPEG4, CODEC_ID_VC1, CODEC_ID_WMV3};
+use symphonia_core::common::Limit;
+use symphonia_core::errors::{Error, SeekErrorKind};
+use symphonia_core::formats::well_known::FORMAT_ID_AVI;
+use symphonia_core::formats::{FormatOptions, FormatReader, SeekMode, SeekTo};
+use symphonia_core::io::{MediaSourceStream, ReadOnlySource};
+use symphonia_core::units::FrameRate;
+use symphonia_format_riff::AviReader;
+
+/// Write a RIFF chunk with the given tag and data. The chunk is padded to an even length.
+fn chunk(tag: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    buf.extend_from_slice(tag);
+    buf.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    buf.extend_from_slice(data);
+    if data.len() & 0x1 == 1 {
+        buf.push(0);
+    }
+    buf
+}
+
+/// Write a RIFF list with the given form type and child chunks.
+fn list(form: &[u8; 4], children: &[Vec<u8>]) -> Vec<u8> {
+    chunk(b"LIST", &[form.as_slice(), &children.concat()].concat())
+}
+
+fn u32s(values: &[u32]) -> Vec<u8> {
+    values.iter().flat_map(|value| value.to_le_bytes()).collect()
+}
+
+fn stream_header(
+    stream_type: &[u8; 4],
+    scale: u32,
+    rate: u32,
+    length: u32,
+    sample_size: u32,
+) -> Vec<u8> {
+    let mut buf = Vec::new();
+    buf.extend_from_slice(stream_type);
+    buf.extend_from_slice(&[0; 4]);
+    // Flags, priority and language, and initial frames.
+    buf.extend_from_slice(&u32s(&[0, 0, 0]));
+    buf.extend_from_slice(&u32s(&[scale, rate, 0, length, 0, u32::MAX, sample_size]));
+    // Destination rectangle.
+    buf.extend_from_slice(&[0; 8]);
+    chunk(b"strh", &buf)
+}
+
+/// Build a video stream list for 320x240 XviD video at 25 fps.
+fn video_stream_list() -> Vec<u8> {
+    let mut bih = u32s(&[40, 320, 240]);
+    bih.extend_from_slice(&1u16.to_le_bytes());
+    bih.extend_from_slice(&24u16.to_le_bytes());
+    bih.extend_from_slice(b"XVID");
+    bih.extend_from_slice(&[0; 20]);
+
+    list(b"strl", &[stream_header(b"vids", 1, 25, 3, 0), chunk(b"strf", &bih)])
+}
+
+/// Build an audio stream list for 16-bit mono PCM audio at 8 kHz.
+fn audio_stream_list() -> Vec<u8> {
+    let mut wfx = Vec::new();
+    wfx.extend_from_slice(&1u16.to_le_bytes());
+    wfx.extend_from_slice(&1u16.to_le_bytes());
+    wfx.extend_from_slice(&u32s(&[8000, 16000]));
+    wfx.extend_from_slice(&2u16.to_le_bytes());
+    wfx.extend_from_slice(&16u16.to_le_bytes());
+    wfx.extend_from_slice(&0u16.to_le_bytes());
+
+    list(b"strl", &[stream_header(b"auds", 2, 16000, 7, 2), chunk(b"strf", &wfx)])
+}
+
+/// Build an AVI file with a video and an audio stream.
+///
+/// The movi list contains a keyframe, audio, a dropped frame, a rec list with a keyframe and
+/// audio, and a JUNK chunk.
+fn make_avi() -> Vec<u8> {
+    make_avi_with_frame_flags(0x10)
+}
+
+/// Build the AVI file of `make_avi`, with the given index flags for the video frame in the rec
+/// list.
+fn make_avi_with_frame_flags(flags: u32) -> Vec<u8> {
+    let avih = chunk(b"avih", &u32s(&[40_000, 0, 0, 0x10, 3, 0, 2, 0, 320, 240, 0, 0, 0, 0]));
+    let hdrl = list(b"hdrl", &[avih, video_stream_list(), audio_stream_list()]);
+
+    let movi_chunks = [
+        chunk(b"00dc", &[1; 5]),
+        chunk(b"01wb", &[2; 8]),
+        chunk(b"00dc", &[]),
+        list(b"rec ", &[chunk(b"00dc", &[3; 4]), chunk(b"01wb", &[4; 6])]),
+        chunk(b"JUNK", &[0; 4]),
+    ];
+
+    // Index entries are relative to the movi list's form type.
+    let mut index = Vec::new();
+    let mut offset = 4;
+
+    for (ckid, flags, len) in [(b"00dc", 0x10, 5), (b"01wb", 0x10, 8), (b"00dc", 0, 0)] {
+        index.push((*ckid, flags, offset, len));
+        offset += 8 + len + (len & 0x1);
+    }
+
+    // The chunks in the rec list.
+    offset += 12;
+    index.push((*b"00dc", flags, offset, 4));
+    index.push((*b"01wb", 0x10, offset + 12, 6));
+
+    let idx1 = chunk(
+        b"idx1",
+        &index
+            .iter()
+            .flat_map(|(ckid, flags, offset, len)| {
+                [ckid.as_slice(), &u32s(&[*flags, *offset, *len])].concat()
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    let riff = [b"AVI ".as_slice(), &hdrl, &list(b"movi", &movi_chunks), &idx1].concat();
+
+    chunk(b"RIFF", &riff)
+}
+
+fn open_avi(data: Vec<u8>) -> AviReader<'static> {
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    AviReader::try_new(mss, Default::default()).unwrap()
+}
+
+/// Read the track ID, timestamp, duration, and length of all packets.
+fn read_packets(reader: &mut AviReader<'_>) -> Vec<(u32, i64, u64, usize)> {
+    let mut packets = Vec::new();
+    while let Some(packet) = reader.next_packet().unwrap() {
+        packets.push((packet.track_id, packet.pts.get(), packet.dur.get(), packet.data.len()));
+    }
+    packets
+}
+
+#[test]
+fn read_avi_tracks() {
+    let reader = open_avi(make_avi());
+
+    assert_eq!(reader.format_info().format, FORMAT_ID_AVI);
+
+    let tracks = reader.tracks();
+    assert_eq!(tracks.len(), 2);
+
+    match &tracks[0].codec_params {
+        Some(CodecParameters::Video(params)) => {
+            assert_eq!(params.codec, CODEC_ID_MPEG4);
+            assert_eq!((params.width, params.height), (Some(320), Some(240)));
+        }
+        _ => panic!("expected video codec parameters"),
+    }
+
+    let tb = tracks[0].time_base.unwrap();
+    assert_eq!((tb.numer.get(), tb.denom.get()), (1, 25));
+    assert_eq!(tracks[0].num_frames, Some(3));
+
+    match &tracks[1].codec_params {
+        Some(CodecParameters::Audio(params)) => {
+            assert_eq!(params.codec, CODEC_ID_PCM_S16LE);
+            assert_eq!(params.sample_rate, Some(8000));
+            assert_eq!(params.channels.as_ref().map(|channels| channels.count()), Some(1));
+        }
+        _ => panic!("expected audio codec parameters"),
+    }
+
+    // The time base of the audio stream is the sample rate.
+    let tb = tracks[1].time_base.unwrap();
+    assert_eq!((tb.numer.get(), tb.denom.get()), (1, 8000));
+    assert_eq!(tracks[1].duration.map(|dur| dur.get()), Some(7));
+}
+
+#[test]
+fn read_avi_packets() {
+    let mut reader = open_avi(make_avi());
+
+    // The dropped video frame is skipped, but advances the video timestamp.
+    assert_eq!(
+        read_packets(&mut reader),
+        vec![(0, 0, 1, 5), (1, 0, 4, 8), (0, 2, 1, 4), (1, 4, 3, 6)]
+    );
+}
+
+#[test]
+fn read_avi_packets_with_track_filter() {
+    let mut reader = open_avi(make_avi());
+
+    reader.set_track_filter(&[1]).unwrap();
+
+    assert_eq!(read_packets(&mut reader), vec![(1, 0, 4, 8), (1, 4, 3, 6)]);
+}
+
+#[test]
+fn read_avi_keyframes() {
+    let read_keyframes = |reader: &mut AviReader<'_>| {
+        let mut keyframes = Vec::new();
+        while let Some(packet) = reader.next_packet().unwrap() {
+            keyframes.push((packet.track_id, packet.is_keyframe));
+        }
+        keyframes
+    };
+
+    // The video frame in the rec list is not a keyframe.
+    let data = make_avi_with_frame_flags(0);
+
+    let mut reader = open_avi(data.clone());
+    assert_eq!(read_keyframes(&mut reader), vec![(0, true), (1, true), (0, false), (1, true)]);
+
+    // Seeking lands on the preceding keyframe.
+    let seeked =
+        reader.seek(SeekMode::Accurate, SeekTo::Timestamp { ts: 2.into(), track_id: 0 }).unwrap();
+    assert_eq!(seeked.actual_ts.get(), 0);
+
+    // Without the index, all data chunks are assumed to be keyframes.
+    let source = ReadOnlySource::new(std::io::Cursor::new(data));
+    let mss = MediaSourceStream::new(Box::new(source), Default::default());
+    let mut reader = AviReader::try_new(mss, Default::default()).unwrap();
+
+    assert_eq!(read_keyframes(&mut reader), vec![(0, true), (1, true), (0, true), (1, true)]);
+}
+
+#[test]
+fn seek_avi() {
+    let mut reader = open_avi(make_avi());
+
+    // Seeking lands on the keyframe at or preceding the required timestamp.
+    let seeked =
+        reader.seek(SeekMode::Accurate, SeekTo::Timestamp { ts: 2.into(), track_id: 0 }).unwrap();
+    assert_eq!((seeked.track_id, seeked.actual_ts.get()), (0, 2));
+
+    assert_eq!(read_packets(&mut reader), vec![(0, 2, 1, 4), (1, 4, 3, 6)]);
+
+    let seeked =
+        reader.seek(SeekMode::Accurate, SeekTo::Timestamp { ts: 1.into(), track_id: 0 }).unwrap();
+    assert_eq!(seeked.actual_ts.get(), 0);
+
+    assert_eq!(read_packets(&mut reader).len(), 4);
+}
+
+#[test]
+fn read_avi_unseekable() {
+    let source = ReadOnlySource::new(std::io::Cursor::new(make_avi()));
+    let mss = MediaSourceStream::new(Box::new(source), Default::default());
+    let mut reader = AviReader::try_new(mss, Default::default()).unwrap();
+
+    // An unseekable source cannot be seeked.
+    assert!(matches!(
+        reader.seek(SeekMode::Accurate, SeekTo::Timestamp { ts: 0.into(), track_id: 0 }),
+        Err(Error::SeekError(SeekErrorKind::Unseekable))
+    ));
+
+    // Reading continues after the movi list up to the end of the stream.
+    assert_eq!(read_packets(&mut reader).len(), 4);
+}
+
+#[test]
+fn read_avi_vc1_sequence_header() {
+    // A 1 byte prefix, and an Advanced profile, level 3, 1920x1080 (1920x1088 coded) sequence
+    // header signalling 29.97 fps, followed by an entry point header.
+    let extra_data = [
+        0x00, 0x00, 0x00, 0x01, 0x0f, 0xdb, 0xfe, 0x3b, 0xf2, 0x1f, 0x0a, 0x3b, 0xf8, 0x86, 0xf1,
+        0x80, 0xc8, 0x80, 0x00, 0x00, 0x01, 0x0e, 0x5a, 0x67, 0xf8,
+    ];
+
+    // The stream format signals the coded 1920x1088 size.
+    let mut bih = u32s(&[40, 1920, 1088]);
+    bih.extend_from_slice(&1u16.to_le_bytes());
+    bih.extend_from_slice(&24u16.to_le_bytes());
+    bih.extend_from_slice(b"WVC1");
+    bih.extend_from_slice(&[0; 20]);
+    bih.extend_from_slice(&extra_data);
+
+    let strl = list(b"strl", &[stream_header(b"vids", 1001, 30000, 1, 0), chunk(b"strf", &bih)]);
+
+    let avih = chunk(b"avih", &u32s(&[33_367, 0, 0, 0, 1, 0, 1, 0, 1920, 1088, 0, 0, 0, 0]));
+    let hdrl = list(b"hdrl", &[avih, strl]);
+    let movi = list(b"movi", &[chunk(b"00dc", &[0; 4])]);
+
+    let reader = open_avi(chunk(b"RIFF", &[b"AVI ".as_slice(), &hdrl, &movi].concat()));
+
+    match &reader.tracks()[0].codec_params {
+        Some(CodecParameters::Video(params)) => {
+            assert_eq!(params.codec, CODEC_ID_VC1);
+            assert_eq!(params.profile, Some(CODEC_PROFILE_VC1_ADVANCED));
+            assert_eq!(params.level, Some(3));
+            assert_eq!((params.width, params.height), (Some(1920), Some(1080)));
+            assert_eq!(params.container_size, Some((1920, 1088)));
+            assert_eq!(params.frame_rate, FrameRate::try_new(30000, 1001));
+        }
+        _ => panic!("expected video codec parameters"),
+    }
+}
+
+#[test]
+fn read_avi_wmv3_sequence_layer_data() {
+    // A Main profile sequence layer data structure.
+    let mut bih = u32s(&[40, 640, 480]);
+    bih.extend_from_slice(&1u16.to_le_bytes());
+    bih.extend_from_slice(&24u16.to_le_bytes());
+    bih.extend_from_slice(b"WMV3");
+    bih.extend_from_slice(&[0; 20]);
+    bih.extend_from_slice(&[0x4e, 0x0a, 0xa8, 0x01]);
+
+    let strl = list(b"strl", &[stream_header(b"vids", 1, 25, 1, 0), chunk(b"strf", &bih)]);
+
+    let avih = chunk(b"avih", &u32s(&[40_000, 0, 0, 0, 1, 0, 1, 0, 640, 480, 0, 0, 0, 0]));
+    let hdrl = list(b"hdrl", &[avih, strl]);
+    let movi = list(b"movi", &[chunk(b"00dc", &[0; 4])]);
+
+    let reader = open_avi(chunk(b"RIFF", &[b"AVI ".as_slice(), &hdrl, &movi].concat()));
+
+    match &reader.tracks()[0].codec_params {
+        Some(CodecParameters::Video(params)) => {
+            assert_eq!(params.codec, CODEC_ID_WMV3);
+            assert_eq!(params.profile, Some(CODEC_PROFILE_VC1_MAIN));
+            assert_eq!((params.width, params.height), (Some(640), Some(480)));
+            assert_eq!(params.container_size, None);
+        }
+        _ => panic!("expected video codec parameters"),
+    }
+}
+
+#[test]
+fn limit_element_bytes() {
+    let open = |limit| {
+        let mss =
+            MediaSourceStream::new(Box::new(std::io::Cursor::new(make_avi())), Default::default());
+        AviReader::try_new(mss, FormatOptions::default().limit_element_bytes(limit))
+    };
+
+    // The video stream format is 40 bytes long, and the legacy index is 80 bytes long.
+    assert!(open(Limit::Maximum(16)).is_err());
+    assert!(open(Limit::Maximum(40)).is_err());
+    assert!(open(Limit::Maximum(80)).is_ok());
+    assert!(open(Limit::None).is_ok());
+}
@@ -0,0 +1,214 @@
+#![cfg(feature = "wav")]
+
+use symphonia_core::codecs::CodecParameters;
+use symphonia_core::codecs::audio::AudioCodecParameters;
+use symphonia_core::codecs::audio::well_known::{
+    CODEC_ID_ADPCM_G722, CODEC_ID_ADPCM_G726, CODEC_ID_ADPCM_IMA_WAV,
+};
+use symphonia_core::common::Limit;
+use symphonia_core::errors::{Error, SeekErrorKind};
+use symphonia_core::formats::{FormatOptions, FormatReader, SeekMode, SeekTo};
+use symphonia_core::io::{MediaSourceStream, ReadOnlySource};
+use symphonia_format_riff::WavReader;
+
+/// Write a RIFF chunk with the given tag and data. The chunk is padded to an even length.
+fn chunk(tag: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    buf.extend_from_slice(tag);
+    buf.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    buf.extend_from_slice(data);
+    if data.len() & 0x1 == 1 {
+        buf.push(0);
+    }
+    buf
+}
+
+/// Build a Wave file containing the given number of frames of 44.1 kHz, 16-bit, stereo PCM audio.
+/// A junk chunk with the given length precedes the audio data.
+fn make_wav(num_frames: u32, junk_len: usize) -> Vec<u8> {
+    let mut fmt = Vec::new();
+    // Format tag, and the number of channels.
+    fmt.extend_from_slice(&1u16.to_le_bytes());
+    fmt.extend_from_slice(&2u16.to_le_bytes());
+    // Sample rate, and byte rate.
+    fmt.extend_from_slice(&44100u32.to_le_bytes());
+    fmt.extend_from_slice(&(44100u32 * 4).to_le_bytes());
+    // Block align, and bits per sample.
+    fmt.extend_from_slice(&4u16.to_le_bytes());
+    fmt.extend_from_slice(&16u16.to_le_bytes());
+
+    let form = [
+        b"WAVE".to_vec(),
+        chunk(b"fmt ", &fmt),
+        chunk(b"JUNK", &vec![0; junk_len]),
+        chunk(b"data", &vec![0; 4 * num_frames as usize]),
+    ]
+    .concat();
+
+    chunk(b"RIFF", &form)
+}
+
+/// Build a WaveFormatEx structure with the given fields.
+fn wave_format(
+    tag: u16,
+    channels: u16,
+    rate: u32,
+    byte_rate: u32,
+    align: u16,
+    bits: u16,
+) -> Vec<u8> {
+    [
+        &tag.to_le_bytes()[..],
+        &channels.to_le_bytes(),
+        &rate.to_le_bytes(),
+        &byte_rate.to_le_bytes(),
+        &align.to_le_bytes(),
+        &bits.to_le_bytes(),
+    ]
+    .concat()
+}
+
+/// Build a Wave file with the given format chunk, and the given number of bytes of audio data.
+fn make_wav_with_format(fmt: &[u8], data_len: usize) -> Vec<u8> {
+    let form = [b"WAVE".to_vec(), chunk(b"fmt ", fmt), chunk(b"data", &vec![0; data_len])].concat();
+
+    chunk(b"RIFF", &form)
+}
+
+/// Build a Wave file containing the given number of bytes of 22.05 kHz, stereo IMA ADPCM audio
+/// with a block align of 512 bytes.
+fn make_adpcm_wav(data_len: usize) -> Vec<u8> {
+    let mut fmt = wave_format(0x11, 2, 22050, 22311, 512, 4);
+    // Extra data size, and the number of frames per block.
+    fmt.extend_from_slice(&2u16.to_le_bytes());
+    fmt.extend_from_slice(&505u16.to_le_bytes());
+
+    make_wav_with_format(&fmt, data_len)
+}
+
+fn audio_params(reader: &WavReader<'_>) -> AudioCodecParameters {
+    match &reader.tracks()[0].codec_params {
+        Some(CodecParameters::Audio(params)) => params.clone(),
+        _ => panic!("expected audio codec parameters"),
+    }
+}
+
+fn open_wav(data: Vec<u8>) -> WavReader<'static> {
+    let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    WavReader::try_new(mss, Default::default()).unwrap()
+}
+
+#[test]
+fn estimate_wav_bitrate() {
+    // Half a second of 44.1 kHz, 16-bit, stereo audio is 1411.2 kbps. Chunks other than the data
+    // chunk do not affect the estimate.
+    let mut reader = open_wav(make_wav(22050, 1000));
+
+    assert_eq!(reader.estimate_track_bitrate(0), Some(1_411_200));
+
+    // Unknown tracks have no bitrate.
+    assert_eq!(reader.estimate_track_bitrate(1), None);
+}
+
+#[test]
+fn read_adpcm_blocks() {
+    // Three complete blocks followed by a truncated block.
+    let mut reader = open_wav(make_adpcm_wav(3 * 512 + 100));
+
+    assert_eq!(reader.tracks()[0].num_frames, Some(3 * 505));
+
+    let params = audio_params(&reader);
+    assert_eq!(params.codec, CODEC_ID_ADPCM_IMA_WAV);
+    assert_eq!(params.frames_per_block, Some(505));
+    assert_eq!(params.max_frames_per_packet, Some(505));
+
+    // Each packet contains exactly one block. The truncated block is not returned.
+    let mut packets = Vec::new();
+    while let Some(packet) = reader.next_packet().unwrap() {
+        packets.push((packet.pts.get(), packet.dur.get(), packet.data.len()));
+    }
+
+    assert_eq!(packets, vec![(0, 505, 512), (505, 505, 512), (1010, 505, 512)]);
+}
+
+#[test]
+fn read_g726() {
+    // One second of 8 kHz, mono, 24 kbps (3-bit) G.726 audio. Every 3 bytes code 8 samples.
+    let fmt = wave_format(0x45, 1, 8000, 3000, 1, 3);
+    let mut reader = open_wav(make_wav_with_format(&fmt, 3000));
+
+    let params = audio_params(&reader);
+    assert_eq!(params.codec, CODEC_ID_ADPCM_G726);
+    assert_eq!(params.bits_per_coded_sample, Some(3));
+    assert_eq!(reader.tracks()[0].num_frames, Some(8000));
+
+    let packet = reader.next_packet().unwrap().unwrap();
+    assert_eq!((packet.pts.get(), packet.dur.get(), packet.data.len()), (0, 1152, 432));
+
+    // If the bits per sample field is not valid, the code size is derived from the bitrate.
+    let fmt = wave_format(0x45, 1, 8000, 5000, 1, 0);
+    let reader = open_wav(make_wav_with_format(&fmt, 5000));
+
+    assert_eq!(audio_params(&reader).bits_per_coded_sample, Some(5));
+    assert_eq!(reader.tracks()[0].num_frames, Some(8000));
+}
+
+#[test]
+fn read_g722() {
+    // One second of 16 kHz, mono, 64 kbps G.722 audio.
+    let fmt = wave_format(0x28f, 1, 16000, 8000, 1, 4);
+    let reader = open_wav(make_wav_with_format(&fmt, 8000));
+
+    let params = audio_params(&reader);
+    assert_eq!(params.codec, CODEC_ID_ADPCM_G722);
+    assert_eq!(params.bits_per_coded_sample, Some(4));
+    assert_eq!(reader.tracks()[0].num_frames, Some(16000));
+}
+
+#[test]
+fn seek_wav_errors() {
+    let seek = |reader: &mut WavReader<'_>, ts: i64| {
+        reader.seek(SeekMode::Accurate, SeekTo::Timestamp { ts: ts.into(), track_id: 0 })
+    };
+
+    let mut reader = open_wav(make_wav(22050, 0));
+
+    // Seeking past the end of the stream is out-of-range.
+    assert!(matches!(seek(&mut reader, 22051), Err(Error::SeekError(SeekErrorKind::OutOfRange))));
+    assert!(matches!(seek(&mut reader, -1), Err(Error::SeekError(SeekErrorKind::OutOfRange))));
+    assert!(seek(&mut reader, 11025).is_ok());
+
+    // An unseekable source can only be seeked forward.
+    let source = ReadOnlySource::new(std::io::Cursor::new(make_wav(22050, 0)));
+    let mss = MediaSourceStream::new(Box::new(source), Default::default());
+    let mut reader = WavReader::try_new(mss, Default::default()).unwrap();
+
+    assert!(seek(&mut reader, 11025).is_ok());
+    assert!(matches!(seek(&mut reader, 0), Err(Error::SeekError(SeekErrorKind::ForwardOnly))));
+}
+
+#[test]
+fn limit_element_bytes() {
+    // An INFO list with a 32 byte title precedes the audio data.
+    let info = [b"INFO".as_slice(), &chunk(b"INAM", &[b'a'; 32])].concat();
+
+    let form = [
+        b"WAVE".to_vec(),
+        chunk(b"fmt ", &wave_format(1, 1, 8000, 16000, 2, 16)),
+        chunk(b"LIST", &info),
+        chunk(b"data", &[0; 4]),
+    ]
+    .concat();
+
+    let wav = chunk(b"RIFF", &form);
+
+    let open = |limit| {
+        let mss =
+            MediaSourceStream::new(Box::new(std::io::Cursor::new(wav.clone())), Default::default());
+        WavReader::try_new(mss, FormatOptions::default().limit_element_bytes(limit))
+    };
+
+    assert!(open(Limit::Maximum(16)).is_err());
+    assert!(open(Limit::Maximum(32)).is_ok());
+    assert!(open(Limit::None).is_ok());
+}
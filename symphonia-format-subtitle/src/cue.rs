@@ -0,0 +1,235 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Cue parsers for SubRip (SRT) and WebVTT subtitle files.
+
+use symphonia_core::errors::{Result, decode_error};
+
+use log::warn;
+
+/// A subtitle cue.
+#[derive(Debug, PartialEq)]
+pub struct Cue {
+    /// The start time in milliseconds, at most `i64::MAX`.
+    pub start: u64,
+    /// The end time in milliseconds, at most `i64::MAX`.
+    pub end: u64,
+    /// The cue payload. Lines are separated by line feeds.
+    pub text: String,
+}
+
+/// Returns true if the text starts with the WebVTT file signature.
+pub fn is_webvtt(text: &str) -> bool {
+    match text.strip_prefix("WEBVTT") {
+        // The signature must be followed by a space, tab, or line terminator.
+        Some(rest) => rest.is_empty() || rest.starts_with([' ', '\t', '\r', '\n']),
+        None => false,
+    }
+}
+
+/// Returns true if the text starts with a SubRip cue number followed by a cue timing line.
+pub fn is_srt(text: &str) -> bool {
+    let mut lines = text.lines();
+
+    match (lines.next(), lines.next()) {
+        (Some(number), Some(timing)) => {
+            parse_digits(number.trim_end()).is_some() && parse_cue_timing(timing).is_some()
+        }
+        _ => false,
+    }
+}
+
+/// Parse the cues of a SubRip (SRT) file.
+pub fn parse_srt(text: &str) -> Vec<Cue> {
+    let mut cues = Vec::new();
+
+    for block in blocks(text) {
+        // The cue timing line is preceded by the cue number. Some files omit the number.
+        match block.iter().position(|line| line.contains("-->")) {
+            Some(i) if i <= 1 => push_cue(&mut cues, block[i], &block[i + 1..]),
+            _ => warn!("subtitle (srt): skipping block without cue timing"),
+        }
+    }
+
+    cues.sort_by_key(|cue| cue.start);
+    cues
+}
+
+/// Parse the cues of a WebVTT file.
+pub fn parse_webvtt(text: &str) -> Result<Vec<Cue>> {
+    if !is_webvtt(text) {
+        return decode_error("subtitle (webvtt): missing webvtt signature");
+    }
+
+    let mut cues = Vec::new();
+
+    // The first block is the file header.
+    for block in blocks(text).skip(1) {
+        // The cue timing line may be preceded by a cue identifier. Comment, style, and region
+        // blocks do not contain a cue timing line.
+        match block.iter().position(|line| line.contains("-->")) {
+            Some(i) if i <= 1 && !is_webvtt_non_cue_block(block[0]) => {
+                push_cue(&mut cues, block[i], &block[i + 1..])
+            }
+            _ => (),
+        }
+    }
+
+    cues.sort_by_key(|cue| cue.start);
+    Ok(cues)
+}
+
+/// Returns true if the first line of a block starts a WebVTT comment, style, or region block.
+fn is_webvtt_non_cue_block(line: &str) -> bool {
+    ["NOTE", "STYLE", "REGION"].iter().any(|keyword| match line.strip_prefix(keyword) {
+        Some(rest) => rest.is_empty() || rest.starts_with([' ', '\t']),
+        None => false,
+    })
+}
+
+/// Split the text into blocks of lines separated by blank lines.
+fn blocks(text: &str) -> impl Iterator<Item = Vec<&str>> {
+    let mut lines = text.lines().peekable();
+
+    std::iter::from_fn(move || {
+        // Skip blank lines preceding the block.
+        while lines.next_if(|line| line.trim().is_empty()).is_some() {}
+
+        let mut block = Vec::new();
+        while let Some(line) = lines.next_if(|line| !line.trim().is_empty()) {
+            block.push(line);
+        }
+
+        (!block.is_empty()).then_some(block)
+    })
+}
+
+fn push_cue(cues: &mut Vec<Cue>, timing: &str, payload: &[&str]) {
+    match parse_cue_timing(timing) {
+        Some((start, end)) => cues.push(Cue { start, end, text: payload.join("\n") }),
+        None => warn!("subtitle: skipping cue with invalid timing"),
+    }
+}
+
+/// Parse a cue timing line into a start and end time in milliseconds.
+fn parse_cue_timing(line: &str) -> Option<(u64, u64)> {
+    let (start, rest) = line.split_once("-->")?;
+
+    // The end time may be followed by cue settings (WebVTT) or coordinates (SRT).
+    let end = rest.split_whitespace().next()?;
+
+    let start = parse_timestamp(start.trim())?;
+    let end = parse_timestamp(end)?;
+
+    // A cue with an end time before its start time is empty.
+    Some((start, end.max(start)))
+}
+
+/// Parse a `[hh:]mm:ss,ttt` or `[hh:]mm:ss.ttt` timestamp into milliseconds.
+fn parse_timestamp(value: &str) -> Option<u64> {
+    // SubRip uses a comma as the fractional separator, WebVTT uses a full stop. Both are accepted
+    // for either format since files mixing them are common.
+    let (time, millis) = value.split_once([',', '.'])?;
+
+    let mut parts = time.split(':').rev();
+    let seconds = parse_digits(parts.next()?)?;
+    let minutes = parse_digits(parts.next()?)?;
+    let hours = match parts.next() {
+        Some(hours) => parse_digits(hours)?,
+        None => 0,
+    };
+
+    if parts.next().is_some() || seconds > 59 || minutes > 59 || millis.len() != 3 {
+        return None;
+    }
+
+    let millis = parse_digits(millis)?;
+
+    // The number of hours is unbounded, so reject timestamps that overflow. Timestamps are signed,
+    // so the time must also fit in an i64.
+    hours
+        .checked_mul(60)
+        .and_then(|time| time.checked_add(minutes))
+        .and_then(|time| time.checked_mul(60))
+        .and_then(|time| time.checked_add(seconds))
+        .and_then(|time| time.checked_mul(1000))
+        .and_then(|time| time.checked_add(millis))
+        .filter(|&time| i64::try_from(time).is_ok())
+}
+
+fn parse_digits(value: &str) -> Option<u64> {
+    match value.bytes().all(|b| b.is_ascii_digit()) {
+        true if !value.is_empty() => value.parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_parse_timestamp() {
+        assert_eq!(parse_timestamp("00:00:01,500"), Some(1500));
+        assert_eq!(parse_timestamp("01:02:03.004"), Some(3_723_004));
+        assert_eq!(parse_timestamp("120:00:00.000"), Some(432_000_000));
+        // Hours are optional.
+        assert_eq!(parse_timestamp("02:03.004"), Some(123_004));
+
+        assert_eq!(parse_timestamp("00:00:01"), None);
+        assert_eq!(parse_timestamp("00:00:60.000"), None);
+        assert_eq!(parse_timestamp("00:00:01.5"), None);
+        assert_eq!(parse_timestamp("00:-1:01.500"), None);
+        assert_eq!(parse_timestamp("0:0:0:01.500"), None);
+        assert_eq!(parse_timestamp("9999999999999999999:00:00,000"), None);
+        assert_eq!(parse_timestamp("99999999999999999999:00:00,000"), None);
+        assert_eq!(parse_timestamp("3000000000000:00:00,000"), None);
+    }
+
+    #[test]
+    fn verify_parse_srt() {
+        let text = "1\r\n00:00:01,000 --> 00:00:02,500\r\nHello\r\nWorld\r\n\r\n\
+                    2\r\nnot a timing line\r\n\r\n\
+                    3\r\n9999999999999999999:00:00,000 --> 00:00:01,000\r\nOverflow\r\n\r\n\
+                    00:00:03,000 --> 00:00:04,000 X1:10 X2:20 Y1:10 Y2:20\r\n<i>Again</i>\r\n";
+
+        assert_eq!(
+            parse_srt(text),
+            [
+                Cue { start: 1000, end: 2500, text: "Hello\nWorld".into() },
+                Cue { start: 3000, end: 4000, text: "<i>Again</i>".into() },
+            ]
+        );
+    }
+
+    #[test]
+    fn verify_is_srt() {
+        assert!(is_srt("1\r\n00:00:01,000 --> 00:00:02,500\r\n"));
+        assert!(!is_srt("1\n00:00:01,000 -> 00:00:02,500\n"));
+        assert!(!is_srt("1\n"));
+    }
+
+    #[test]
+    fn verify_parse_webvtt() {
+        let text = "WEBVTT - Title\nKind: captions\n\n\
+                    NOTE a comment --> with an arrow\n\n\
+                    STYLE\n::cue { color: yellow }\n\n\
+                    intro\n00:01.000 --> 00:02.000 align:start line:0\nHello\n\n\
+                    00:00:02.000 --> 00:00:03.000\n- World\n";
+
+        assert_eq!(
+            parse_webvtt(text).unwrap(),
+            [
+                Cue { start: 1000, end: 2000, text: "Hello".into() },
+                Cue { start: 2000, end: 3000, text: "- World".into() },
+            ]
+        );
+
+        assert!(parse_webvtt("WEBVTTX\n").is_err());
+        assert!(parse_webvtt("1\n00:01.000 --> 00:02.000\nHello\n").is_err());
+    }
+}
@@ -0,0 +1,259 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::io::Read;
+
+use symphonia_core::codecs::CodecParameters;
+use symphonia_core::codecs::subtitle::SubtitleCodecParameters;
+use symphonia_core::codecs::subtitle::well_known::{CODEC_ID_SRT, CODEC_ID_WEBVTT};
+use symphonia_core::errors::{Error, Result, SeekErrorKind, seek_error, unsupported_error};
+use symphonia_core::formats::prelude::*;
+use symphonia_core::formats::probe::{ProbeFormatData, ProbeableFormat, Score, Scoreable};
+use symphonia_core::formats::well_known::{FORMAT_ID_SRT, FORMAT_ID_WEBVTT};
+use symphonia_core::io::*;
+use symphonia_core::meta::{Metadata, MetadataLog};
+use symphonia_core::support_format;
+
+use log::debug;
+
+use crate::cue::{Cue, is_srt, is_webvtt, parse_srt, parse_webvtt};
+
+/// The maximum size of a subtitle file.
+const MAX_FILE_SIZE: u64 = 16 * 1024 * 1024;
+
+/// The maximum number of bytes read while scoring.
+const MAX_SCORE_LEN: usize = 256;
+
+/// The UTF-8 byte order mark.
+const UTF8_BOM: char = '\u{feff}';
+
+const SRT_FORMAT_INFO: FormatInfo =
+    FormatInfo { format: FORMAT_ID_SRT, short_name: "srt", long_name: "SubRip Subtitles" };
+
+const WEBVTT_FORMAT_INFO: FormatInfo = FormatInfo {
+    format: FORMAT_ID_WEBVTT,
+    short_name: "webvtt",
+    long_name: "Web Video Text Tracks",
+};
+
+/// SubRip (SRT) and WebVTT subtitle format reader.
+///
+/// `SubtitleReader` implements a demuxer for standalone, UTF-8 encoded, SubRip and WebVTT files.
+/// The file contains a single subtitle track with a 1/1000 time base. Each cue is returned as a
+/// packet containing the cue payload text.
+pub struct SubtitleReader<'s> {
+    reader: MediaSourceStream<'s>,
+    format_info: FormatInfo,
+    media_info: MediaInfo,
+    tracks: Vec<Track>,
+    chapters: Option<ChapterGroup>,
+    metadata: MetadataLog,
+    /// All cues, sorted by their start time.
+    cues: Vec<Cue>,
+    /// The index of the next cue.
+    next_cue: usize,
+    /// If `false`, the subtitle track is not selected.
+    selected: bool,
+}
+
+impl<'s> SubtitleReader<'s> {
+    pub fn try_new(mut mss: MediaSourceStream<'s>, opts: FormatOptions) -> Result<Self> {
+        // Subtitle files are small, so read the whole file and parse all cues upfront.
+        let mut buf = Vec::new();
+        (&mut mss).take(MAX_FILE_SIZE + 1).read_to_end(&mut buf)?;
+
+        if buf.len() as u64 > MAX_FILE_SIZE {
+            return unsupported_error("subtitle: file is too large");
+        }
+
+        let text = String::from_utf8_lossy(&buf);
+        let text = text.strip_prefix(UTF8_BOM).unwrap_or(&text);
+
+        let (format_info, codec, cues) = if is_webvtt(text) {
+            (WEBVTT_FORMAT_INFO, CODEC_ID_WEBVTT, parse_webvtt(text)?)
+        }
+        else {
+            (SRT_FORMAT_INFO, CODEC_ID_SRT, parse_srt(text))
+        };
+
+        debug!("read {} cues", cues.len());
+
+        let time_base = TimeBase::try_new(1, 1000).unwrap();
+        let duration = Duration::new(cues.iter().map(|cue| cue.end).max().unwrap_or(0));
+
+        let mut codec_params = SubtitleCodecParameters::new();
+        codec_params.for_codec(codec);
+
+        let mut track = Track::new(0);
+        track
+            .with_codec_params(CodecParameters::Subtitle(codec_params))
+            .with_time_base(time_base)
+            .with_duration(duration);
+
+        let mut media_info = MediaInfo::new();
+        media_info.with_time_base(time_base).with_duration(duration);
+
+        Ok(SubtitleReader {
+            reader: mss,
+            format_info,
+            media_info,
+            tracks: vec![track],
+            chapters: opts.external_data.chapters,
+            metadata: opts.external_data.metadata.unwrap_or_default(),
+            cues,
+            next_cue: 0,
+            selected: true,
+        })
+    }
+}
+
+impl Scoreable for SubtitleReader<'_> {
+    fn score(mut src: ScopedStream<&mut MediaSourceStream<'_>>) -> Result<Score> {
+        // Read the first two lines.
+        let mut buf = Vec::new();
+
+        while buf.len() < MAX_SCORE_LEN && buf.iter().filter(|&&b| b == b'\n').count() < 2 {
+            match src.read_u8() {
+                Ok(byte) => buf.push(byte),
+                Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => break,
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        let text = String::from_utf8_lossy(&buf);
+        let text = text.strip_prefix(UTF8_BOM).unwrap_or(&text);
+
+        // A WebVTT file starts with a signature. A SubRip file has no signature, but starts with
+        // the cue number followed by a cue timing line.
+        match is_webvtt(text) || is_srt(text) {
+            true => Ok(Score::Supported(255)),
+            false => Ok(Score::Unsupported),
+        }
+    }
+}
+
+impl ProbeableFormat<'_> for SubtitleReader<'_> {
+    fn try_probe_new(
+        mss: MediaSourceStream<'_>,
+        opts: FormatOptions,
+    ) -> Result<Box<dyn FormatReader + '_>> {
+        Ok(Box::new(SubtitleReader::try_new(mss, opts)?))
+    }
+
+    fn probe_data() -> &'static [ProbeFormatData] {
+        &[
+            support_format!(
+                SRT_FORMAT_INFO,
+                &["srt"],
+                &["application/x-subrip", "text/srt"],
+                &[b"1\n", b"1\r\n", b"\xef\xbb\xbf1\n", b"\xef\xbb\xbf1\r\n"]
+            ),
+            support_format!(
+                WEBVTT_FORMAT_INFO,
+                &["vtt"],
+                &["text/vtt"],
+                &[b"WEBVTT", b"\xef\xbb\xbfWEBVTT"]
+            ),
+        ]
+    }
+}
+
+impl FormatReader for SubtitleReader<'_> {
+    fn format_info(&self) -> &FormatInfo {
+        &self.format_info
+    }
+
+    fn media_info(&self) -> &MediaInfo {
+        &self.media_info
+    }
+
+    fn next_packet(&mut self) -> Result<Option<Packet>> {
+        if !self.selected {
+            return Ok(None);
+        }
+
+        let Some(cue) = self.cues.get(self.next_cue)
+        else {
+            return Ok(None);
+        };
+
+        self.next_cue += 1;
+
+        Ok(Some(Packet::new(
+            0,
+            Timestamp::new(cue.start as i64),
+            Duration::new(cue.end - cue.start),
+            cue.text.as_bytes(),
+        )))
+    }
+
+    fn metadata(&mut self) -> Metadata<'_> {
+        self.metadata.metadata()
+    }
+
+    fn chapters(&self) -> Option<&ChapterGroup> {
+        self.chapters.as_ref()
+    }
+
+    fn tracks(&self) -> &[Track] {
+        &self.tracks
+    }
+
+    fn set_track_filter(&mut self, track_ids: &[u32]) -> Result<()> {
+        self.selected = track_ids.is_empty() || track_ids.contains(&0);
+        Ok(())
+    }
+
+    fn is_track_selected(&self, track_id: u32) -> bool {
+        track_id == 0 && self.selected
+    }
+
+    fn seek(&mut self, _mode: SeekMode, to: SeekTo) -> Result<SeekedTo> {
+        let required_ts = match to {
+            SeekTo::Time { time, track_id: None | Some(0) } => {
+                let tb = self.media_info.time_base.unwrap();
+                tb.calc_timestamp(time).ok_or(Error::SeekError(SeekErrorKind::OutOfRange))?
+            }
+            SeekTo::Timestamp { ts, track_id: 0 } => ts,
+            _ => return seek_error(SeekErrorKind::InvalidTrack),
+        };
+
+        // Seek to the first cue that is displayed at, or after, the required timestamp.
+        let Some(next_cue) = self.cues.iter().position(|cue| cue.end as i64 > required_ts.get())
+        else {
+            return seek_error(SeekErrorKind::OutOfRange);
+        };
+
+        self.next_cue = next_cue;
+
+        let actual_ts = Timestamp::new(self.cues[next_cue].start as i64);
+
+        debug!("seeked to cue {next_cue} with packet_ts={actual_ts}");
+
+        Ok(SeekedTo { track_id: 0, required_ts, actual_ts })
+    }
+
+    fn capabilities(&self) -> FormatCapabilities {
+        // All cues are read upfront, so seeking does not depend on the source.
+        let mut caps = FormatCapabilities::SEEKABLE | FormatCapabilities::KNOWN_DURATION;
+        caps.set(FormatCapabilities::CHAPTERS, self.chapters.is_some());
+        caps
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        // All cues are read upfront, so the source does not need to be seekable.
+        self.next_cue = 0;
+        Ok(())
+    }
+
+    fn into_inner<'s>(self: Box<Self>) -> MediaSourceStream<'s>
+    where
+        Self: 's,
+    {
+        self.reader
+    }
+}
@@ -0,0 +1,138 @@
+use symphonia_core::codecs::CodecParameters;
+use symphonia_core::codecs::subtitle::SubtitleCodecId;
+use symphonia_core::codecs::subtitle::well_known::{CODEC_ID_SRT, CODEC_ID_WEBVTT};
+use symphonia_core::formats::probe::{Hint, Probe};
+use symphonia_core::formats::well_known::{FORMAT_ID_SRT, FORMAT_ID_WEBVTT};
+use symphonia_core::formats::{FormatReader, SeekMode, SeekTo};
+use symphonia_core::io::MediaSourceStream;
+use symphonia_core::units::Time;
+use symphonia_format_subtitle::SubtitleReader;
+
+const SRT: &str = "\u{feff}1\r\n\
+                   00:00:01,000 --> 00:00:03,500\r\n\
+                   Hello, world!\r\n\
+                   \r\n\
+                   2\r\n\
+                   00:00:04,000 --> 00:00:06,000\r\n\
+                   <i>Two</i>\r\n\
+                   lines\r\n\
+                   \r\n\
+                   3\r\n\
+                   00:01:00,250 --> 00:01:02,000\r\n\
+                   The end\r\n";
+
+const WEBVTT: &str = "WEBVTT\n\
+                      \n\
+                      NOTE This is a comment.\n\
+                      \n\
+                      1\n\
+                      00:01.000 --> 00:03.500 position:10%\n\
+                      Hello, world!\n\
+                      \n\
+                      00:00:04.000 --> 00:00:06.000\n\
+                      <v Bob>Two</v>\n";
+
+fn probe(data: &str) -> Box<dyn FormatReader> {
+    let mut probe = Probe::new();
+    probe.register_format::<SubtitleReader<'_>>();
+
+    let source = Box::new(std::io::Cursor::new(data.as_bytes().to_vec()));
+    let mss = MediaSourceStream::new(source, Default::default());
+
+    probe.probe(&Hint::new(), mss, Default::default(), Default::default()).unwrap()
+}
+
+/// Read the timestamp, duration, and text of all cues.
+fn read_cues(reader: &mut dyn FormatReader) -> Vec<(i64, u64, String)> {
+    let mut cues = Vec::new();
+    while let Some(packet) = reader.next_packet().unwrap() {
+        let text = String::from_utf8(packet.data.to_vec()).unwrap();
+        cues.push((packet.pts.get(), packet.dur.get(), text));
+    }
+    cues
+}
+
+fn assert_subtitle_track(reader: &dyn FormatReader, codec: SubtitleCodecId, duration: u64) {
+    let tracks = reader.tracks();
+    assert_eq!(tracks.len(), 1);
+
+    match &tracks[0].codec_params {
+        Some(CodecParameters::Subtitle(params)) => assert_eq!(params.codec, codec),
+        _ => panic!("expected subtitle codec parameters"),
+    }
+
+    let tb = tracks[0].time_base.unwrap();
+    assert_eq!((tb.numer.get(), tb.denom.get()), (1, 1000));
+    assert_eq!(tracks[0].duration.map(|dur| dur.get()), Some(duration));
+}
+
+#[test]
+fn read_srt() {
+    let mut reader = probe(SRT);
+
+    assert_eq!(reader.format_info().format, FORMAT_ID_SRT);
+    assert_subtitle_track(reader.as_ref(), CODEC_ID_SRT, 62_000);
+
+    assert_eq!(
+        read_cues(reader.as_mut()),
+        [
+            (1000, 2500, "Hello, world!".to_string()),
+            (4000, 2000, "<i>Two</i>\nlines".to_string()),
+            (60_250, 1750, "The end".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn read_webvtt() {
+    let mut reader = probe(WEBVTT);
+
+    assert_eq!(reader.format_info().format, FORMAT_ID_WEBVTT);
+    assert_subtitle_track(reader.as_ref(), CODEC_ID_WEBVTT, 6000);
+
+    assert_eq!(
+        read_cues(reader.as_mut()),
+        [(1000, 2500, "Hello, world!".to_string()), (4000, 2000, "<v Bob>Two</v>".to_string())]
+    );
+}
+
+#[test]
+fn seek_srt() {
+    let mut reader = probe(SRT);
+
+    // Seeking lands on the first cue displayed at, or after, the required time.
+    let seeked = reader
+        .seek(SeekMode::Accurate, SeekTo::Time { time: Time::from_millis(5000), track_id: None })
+        .unwrap();
+    assert_eq!(seeked.actual_ts.get(), 4000);
+    assert_eq!(read_cues(reader.as_mut()).len(), 2);
+
+    let seeked = reader
+        .seek(SeekMode::Accurate, SeekTo::Timestamp { ts: 10_000.into(), track_id: 0 })
+        .unwrap();
+    assert_eq!(seeked.actual_ts.get(), 60_250);
+
+    assert!(
+        reader.seek(SeekMode::Accurate, SeekTo::Timestamp { ts: 0.into(), track_id: 1 }).is_err()
+    );
+    assert!(
+        reader
+            .seek(SeekMode::Accurate, SeekTo::Timestamp { ts: 62_000.into(), track_id: 0 })
+            .is_err()
+    );
+
+    // Resetting returns to the first cue.
+    reader.reset().unwrap();
+    assert_eq!(read_cues(reader.as_mut()).len(), 3);
+}
+
+#[test]
+fn read_srt_with_track_filter() {
+    let mut reader = probe(SRT);
+
+    reader.set_track_filter(&[1]).unwrap();
+    assert!(read_cues(reader.as_mut()).is_empty());
+
+    reader.set_track_filter(&[]).unwrap();
+    assert_eq!(read_cues(reader.as_mut()).len(), 3);
+}
@@ -7,9 +7,16 @@
 
 //! Reading and parsing of metadata formats that are embedded into other containers.
 
+#[cfg(feature = "caf-info")]
+pub mod caf;
 #[cfg(feature = "flac")]
 pub mod flac;
 #[cfg(any(feature = "riff-id3", feature = "riff-info"))]
 pub mod riff;
 #[cfg(feature = "vorbis")]
 pub mod vorbis;
+
+/// The maximum size in bytes of a single comment, picture, or frame, if the element size limit is
+/// defaulted.
+#[cfg(any(feature = "vorbis", feature = "id3v2"))]
+pub(crate) const DEFAULT_LIMIT_ELEMENT_BYTES: usize = 64 * 1024 * 1024;
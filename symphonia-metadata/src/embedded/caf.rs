@@ -0,0 +1,44 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! CAF information chunk metadata format reading.
+
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+
+use symphonia_core::meta::{MetadataBuilder, RawTag};
+
+use crate::utils::std_tag::*;
+
+lazy_static! {
+    static ref CAF_INFO_MAP: RawTagParserMap = {
+        let mut m: RawTagParserMap = HashMap::new();
+        m.insert("album", parse_album);
+        m.insert("artist", parse_artist);
+        m.insert("comments", parse_comment);
+        m.insert("composer", parse_composer);
+        m.insert("copyright", parse_copyright);
+        m.insert("encoding application", parse_encoder);
+        m.insert("genre", parse_genre);
+        m.insert("isrc", parse_ident_isrc);
+        m.insert("key signature", parse_initial_key);
+        m.insert("lyricist", parse_lyricist);
+        m.insert("recorded date", parse_recording_date);
+        m.insert("subtitle", parse_track_subtitle);
+        m.insert("tempo", parse_bpm);
+        m.insert("title", parse_track_title);
+        m.insert("track number", parse_track_number);
+        m.insert("year", parse_recording_year);
+        m
+    };
+}
+
+/// Parse a key-value string pair from a CAF information chunk into a `Tag`.
+pub fn parse_caf_info_string(key: &str, value: &str, builder: &mut MetadataBuilder) {
+    builder.add_mapped_tags(RawTag::new(key, value), &CAF_INFO_MAP);
+}
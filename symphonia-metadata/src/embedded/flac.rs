@@ -11,6 +11,7 @@ use std::num::NonZeroU8;
 use std::sync::Arc;
 
 use log::warn;
+use symphonia_core::common::Limit;
 use symphonia_core::errors::{Result, decode_error};
 use symphonia_core::formats::VendorDataAttachment;
 use symphonia_core::formats::util::SeekIndex;
@@ -22,7 +23,7 @@ use symphonia_core::meta::{
 };
 use symphonia_core::units::{TimeBase, Timestamp};
 
-use crate::embedded::vorbis;
+use crate::embedded::{DEFAULT_LIMIT_ELEMENT_BYTES, vorbis};
 use crate::utils::id3v2::get_visual_key_from_picture_type;
 use crate::utils::images::try_get_image_info;
 
@@ -48,24 +49,30 @@ fn printable_ascii_to_string(bytes: &[u8]) -> Option<String> {
     Some(result)
 }
 
-/// Read a comment metadata block.
+/// Read a comment metadata block. Comments larger than `limit` bytes are rejected. If `strict` is
+/// `true`, malformed comments that would otherwise be skipped are returned as an error.
 pub fn read_flac_comment_block<B: ReadBytes>(
     reader: &mut B,
     builder: &mut MetadataBuilder,
+    limit: Limit,
+    strict: bool,
 ) -> Result<()> {
     // Discard side data.
     let mut side_data = Default::default();
-    vorbis::read_vorbis_comment(reader, builder, &mut side_data)
+    vorbis::read_vorbis_comment(reader, builder, &mut side_data, limit, strict)
 }
 
-/// Read a picture metadata block.
-pub fn read_flac_picture_block<B: ReadBytes>(reader: &mut B) -> Result<Visual> {
+/// Read a picture metadata block. Pictures larger than `limit` bytes are rejected.
+pub fn read_flac_picture_block<B: ReadBytes>(reader: &mut B, limit: Limit) -> Result<Visual> {
     let type_enc = reader.read_be_u32()?;
 
     // Read the Media Type length in bytes.
-    // TODO: Apply a limit.
     let media_type_len = reader.read_be_u32()? as usize;
 
+    if limit.is_exceeded_by(media_type_len, DEFAULT_LIMIT_ELEMENT_BYTES) {
+        return decode_error("meta (flac): picture mime-type exceeds the size limit");
+    }
+
     // Read the Media Type bytes
     let media_type_buf = reader.read_boxed_slice_exact(media_type_len)?;
 
@@ -81,9 +88,12 @@ pub fn read_flac_picture_block<B: ReadBytes>(reader: &mut B) -> Result<Visual> {
     let mut tags = vec![];
 
     // Read the description length in bytes.
-    // TODO: Apply a limit.
     let desc_len = reader.read_be_u32()? as usize;
 
+    if limit.is_exceeded_by(desc_len, DEFAULT_LIMIT_ELEMENT_BYTES) {
+        return decode_error("meta (flac): picture description exceeds the size limit");
+    }
+
     // Read the description bytes.
     let desc_buf = reader.read_boxed_slice_exact(desc_len)?;
 
@@ -113,9 +123,12 @@ pub fn read_flac_picture_block<B: ReadBytes>(reader: &mut B) -> Result<Visual> {
     let _color_mode = reader.read_be_u32()?;
 
     // Read the image data length in bytes.
-    // TODO: Apply a limit.
     let data_len = reader.read_be_u32()? as usize;
 
+    if limit.is_exceeded_by(data_len, DEFAULT_LIMIT_ELEMENT_BYTES) {
+        return decode_error("meta (flac): picture data exceeds the size limit");
+    }
+
     // Read the image data.
     let data = reader.read_boxed_slice_exact(data_len)?;
 
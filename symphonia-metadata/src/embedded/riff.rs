@@ -94,17 +94,22 @@ mod info {
 mod id3 {
     //! RIFF ID3 chunk metadata format reading.
 
+    use symphonia_core::common::Limit;
     use symphonia_core::errors::Result;
     use symphonia_core::io::ReadBytes;
     use symphonia_core::meta::{MetadataBuilder, MetadataRevision, MetadataSideData};
 
-    /// Read a RIFF ID3 chunk payload.
+    /// Read a RIFF ID3 chunk payload. If `strict` is `true`, malformed ID3v2 frames that would
+    /// otherwise be skipped are returned as an error. Frames larger than `limit` bytes are
+    /// skipped, or rejected in strict mode.
     pub fn read_riff_id3_chunk<B: ReadBytes>(
         reader: &mut B,
         side_data: &mut Vec<MetadataSideData>,
+        strict: bool,
+        limit: Limit,
     ) -> Result<MetadataRevision> {
         let mut builder = MetadataBuilder::new(crate::id3v2::ID3V2_METADATA_INFO);
-        crate::id3v2::read_id3v2(reader, &mut builder, side_data)?;
+        crate::id3v2::read_id3v2(reader, &mut builder, side_data, strict, limit)?;
         Ok(builder.build())
     }
 }
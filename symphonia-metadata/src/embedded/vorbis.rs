@@ -13,19 +13,22 @@ use std::sync::Arc;
 use lazy_static::lazy_static;
 use log::warn;
 
+use symphonia_core::common::Limit;
 use symphonia_core::errors::{Error, Result, decode_error};
 use symphonia_core::io::{BufReader, ReadBytes};
 use symphonia_core::meta::well_known::METADATA_ID_VORBIS_COMMENT;
 use symphonia_core::meta::{
     Chapter, ChapterGroup, ChapterGroupItem, MetadataBuilder, MetadataInfo, MetadataSideData,
-    RawTag, StandardTag, Tag, Visual,
+    RawTag, RawValue, StandardTag, SyncedLyrics, Tag, Visual,
 };
 use symphonia_core::units::Time;
 use symphonia_core::util::text;
 
-use crate::embedded::flac;
+use crate::embedded::{DEFAULT_LIMIT_ELEMENT_BYTES, flac};
 use crate::utils::base64;
+use crate::utils::id3v2::get_visual_key_from_picture_type;
 use crate::utils::images::try_get_image_info;
+use crate::utils::lrc;
 use crate::utils::std_tag::*;
 
 pub const VORBIS_COMMENT_METADATA_INFO: MetadataInfo = MetadataInfo {
@@ -163,10 +166,13 @@ fn parse_base64_picture_block(b64: &str) -> Result<ParsedComment> {
         return decode_error("meta(vorbis): the base64 encoding of a picture block is invalid");
     };
 
-    flac::read_flac_picture_block(&mut BufReader::new(&data)).map(ParsedComment::Visual)
+    // The picture block cannot be larger than the comment it was decoded from.
+    let limit = Limit::Maximum(data.len());
+
+    flac::read_flac_picture_block(&mut BufReader::new(&data), limit).map(ParsedComment::Visual)
 }
 
-/// Parse a string containing a base64 encoding image file into a visual.
+/// Parse a string containing a base64 encoding image file into legacy cover art data.
 fn parse_base64_cover_art(b64: &str) -> Result<ParsedComment> {
     // Decode the Base64 encoded image data.
     let Some(data) = base64::decode(b64)
@@ -174,20 +180,86 @@ fn parse_base64_cover_art(b64: &str) -> Result<ParsedComment> {
         return decode_error("meta (vorbis): the base64 encoding of cover art is invalid");
     };
 
-    // Try to get image information.
-    let Some(image_info) = try_get_image_info(&data)
-    else {
-        return decode_error("meta (vorbis): could not detect cover art image format");
-    };
+    Ok(ParsedComment::CoverArt(CoverArtPart::Data(data)))
+}
+
+/// Parse a string containing an ID3v2 picture type into a legacy cover art picture type.
+fn parse_cover_art_type(value: &str) -> Result<ParsedComment> {
+    match value.trim().parse::<u32>() {
+        Ok(picture_type) => Ok(ParsedComment::CoverArt(CoverArtPart::PictureType(picture_type))),
+        Err(_) => decode_error("meta (vorbis): cover art type is invalid"),
+    }
+}
+
+/// Legacy cover art.
+///
+/// Legacy cover art is split across the "COVERART", "COVERARTMIME", and "COVERARTTYPE" comments.
+/// If multiple images are present, the media and picture types are matched to the image data by
+/// the order in which they appear.
+#[derive(Default)]
+struct LegacyCoverArt {
+    data: Vec<Box<[u8]>>,
+    media_types: Vec<String>,
+    picture_types: Vec<u32>,
+}
+
+impl LegacyCoverArt {
+    /// Add a part of the cover art.
+    fn push(&mut self, part: CoverArtPart) {
+        match part {
+            CoverArtPart::Data(data) => self.data.push(data),
+            CoverArtPart::MediaType(media_type) => self.media_types.push(media_type),
+            CoverArtPart::PictureType(picture_type) => self.picture_types.push(picture_type),
+        }
+    }
+
+    /// Assemble the cover art into visuals. If `strict` is `true`, cover art with an undetectable
+    /// image format, and no stated media type, is an error. Otherwise, it is skipped.
+    fn into_visuals(self, strict: bool) -> Result<Vec<Visual>> {
+        let mut media_types = self.media_types.into_iter();
+        let mut picture_types = self.picture_types.into_iter();
+
+        let mut visuals = Vec::new();
+
+        for data in self.data {
+            let media_type = media_types.next().filter(|media_type| !media_type.is_empty());
+            let usage = picture_types.next().and_then(get_visual_key_from_picture_type);
+
+            // Detected image characteristics are preferred over the stated media type. However, if
+            // the image format could not be detected, the stated media type is used as-is.
+            let image_info = try_get_image_info(&data);
+
+            if image_info.is_none() && media_type.is_none() {
+                if strict {
+                    return decode_error("meta (vorbis): could not detect cover art image format");
+                }
+                warn!("meta (vorbis): could not detect cover art image format");
+                continue;
+            }
+
+            visuals.push(Visual {
+                media_type: image_info.as_ref().map(|info| info.media_type.clone()).or(media_type),
+                dimensions: image_info.as_ref().map(|info| info.dimensions),
+                color_mode: image_info.as_ref().map(|info| info.color_mode),
+                usage,
+                tags: vec![],
+                data,
+            });
+        }
+
+        Ok(visuals)
+    }
+}
+
+/// Try to parse a lyrics tag containing LRC-formatted lyrics into synchronized lyrics.
+fn try_parse_synced_lyrics(raw: &RawTag) -> Option<SyncedLyrics> {
+    let is_lyrics =
+        raw.key.eq_ignore_ascii_case("lyrics") || raw.key.eq_ignore_ascii_case("unsyncedlyrics");
 
-    Ok(ParsedComment::Visual(Visual {
-        media_type: Some(image_info.media_type),
-        dimensions: Some(image_info.dimensions),
-        color_mode: Some(image_info.color_mode),
-        usage: None,
-        tags: vec![],
-        data,
-    }))
+    match &raw.value {
+        RawValue::String(text) if is_lyrics => lrc::parse_lrc(text),
+        _ => None,
+    }
 }
 
 /// Parse a chapter timestamp in the HH:MM:SS.SSS format.
@@ -317,6 +389,16 @@ struct ChapterInfo {
     value: String,
 }
 
+/// A part of legacy cover art.
+enum CoverArtPart {
+    /// The image data.
+    Data(Box<[u8]>),
+    /// The media type of the image.
+    MediaType(String),
+    /// The ID3v2 picture type of the image.
+    PictureType(u32),
+}
+
 /// A parsed Vorbis comment.
 enum ParsedComment {
     /// The comment yielded a tag.
@@ -325,6 +407,8 @@ enum ParsedComment {
     Visual(Visual),
     /// The comment yielded chapter information.
     ChapterInfo(ChapterInfo),
+    /// The comment yielded a part of legacy cover art.
+    CoverArt(CoverArtPart),
 }
 
 /// Parse the given Vorbis Comment string into a `Tag`.
@@ -355,6 +439,15 @@ fn parse_vorbis_comment(buf: &[u8]) -> Result<ParsedComment> {
             // such.
             parse_base64_cover_art(value)
         }
+        else if key.eq_ignore_ascii_case("coverartmime") {
+            // A comment with a key "COVERARTMIME" is the media type of a "COVERART" image.
+            Ok(ParsedComment::CoverArt(CoverArtPart::MediaType(value.trim().to_string())))
+        }
+        else if key.eq_ignore_ascii_case("coverarttype") {
+            // A comment with a key "COVERARTTYPE" is the ID3v2 picture type of a "COVERART"
+            // image.
+            parse_cover_art_type(value)
+        }
         else {
             // Add a tag created from the key-value pair, while also attempting to map it to a
             // standard tag.
@@ -366,10 +459,14 @@ fn parse_vorbis_comment(buf: &[u8]) -> Result<ParsedComment> {
     }
 }
 
+/// Read a Vorbis comment block. Comments larger than `limit` bytes are rejected. If `strict` is
+/// `true`, malformed comments that would otherwise be skipped are returned as an error.
 pub fn read_vorbis_comment<B: ReadBytes>(
     reader: &mut B,
     builder: &mut MetadataBuilder,
     side_data: &mut Vec<MetadataSideData>,
+    limit: Limit,
+    strict: bool,
 ) -> Result<()> {
     // Read the vendor string length in bytes.
     let vendor_len = reader.read_u32()?;
@@ -380,25 +477,35 @@ pub fn read_vorbis_comment<B: ReadBytes>(
     // Map of chapter number to a vector of chapter information.
     let mut chapters: BTreeMap<u32, Vec<ChapterInfo>> = Default::default();
 
+    // Legacy cover art split across multiple comments.
+    let mut cover_art = LegacyCoverArt::default();
+
     // Read the number of comments.
     let num_comments = reader.read_u32()? as usize;
 
     // Read each comment.
     for _ in 0..num_comments {
         // Read the comment string length in bytes.
-        let comment_length = reader.read_u32()?;
+        let comment_length = reader.read_u32()? as usize;
 
-        // TODO: Apply a limit.
+        if limit.is_exceeded_by(comment_length, DEFAULT_LIMIT_ELEMENT_BYTES) {
+            return decode_error("meta (vorbis): comment exceeds the size limit");
+        }
 
         // Read the comment string.
-        let mut comment_data = vec![0; comment_length as usize];
+        let mut comment_data = vec![0; comment_length];
         reader.read_buf_exact(&mut comment_data)?;
 
         // Parse the Vorbis comment and handle the parsed output.
         match parse_vorbis_comment(&comment_data) {
             Ok(parsed) => match parsed {
                 ParsedComment::Tag(raw) => {
-                    // Comment was a tag.
+                    // Comment was a tag. Lyrics in the LRC format are also added as synchronized
+                    // lyrics.
+                    if let Some(lyrics) = try_parse_synced_lyrics(&raw) {
+                        builder.add_synced_lyrics(lyrics);
+                    }
+
                     builder.add_mapped_tags(raw, &VORBIS_COMMENT_MAP);
                 }
                 ParsedComment::Visual(visual) => {
@@ -410,11 +517,21 @@ pub fn read_vorbis_comment<B: ReadBytes>(
                     // chapter group later.
                     chapters.entry(info.key.num).or_default().push(info);
                 }
+                ParsedComment::CoverArt(part) => {
+                    // Comment was a part of legacy cover art. Collect the parts to build visuals
+                    // once all comments are read.
+                    cover_art.push(part);
+                }
             },
+            Err(err) if strict => return Err(err),
             Err(err) => warn!("{err}"),
         }
     }
 
+    for visual in cover_art.into_visuals(strict)? {
+        builder.add_visual(visual);
+    }
+
     // If chapter information is present, try to build a chapter group.
     if !chapters.is_empty() {
         let items = chapters
@@ -475,8 +592,14 @@ pub fn read_vorbis_comment<B: ReadBytes>(
 
 #[cfg(test)]
 mod tests {
+    use symphonia_core::common::Limit;
+    use symphonia_core::errors::{Error, Result};
+    use symphonia_core::io::BufReader;
+    use symphonia_core::meta::{MetadataBuilder, MetadataRevision, StandardVisualKey};
     use symphonia_core::units::Time;
 
+    use super::{VORBIS_COMMENT_METADATA_INFO, read_vorbis_comment};
+
     #[test]
     fn verify_parse_chapter_timestamp() {
         use super::parse_chapter_timestamp;
@@ -570,4 +693,150 @@ mod tests {
         assert!(parse_chapter_timestamp("00:00:60.000").is_err());
         assert!(parse_chapter_timestamp("00:00:256.000").is_err());
     }
+
+    /// Write a Vorbis comment block with an empty vendor string and the given comments, each with
+    /// a stated length.
+    fn comment_block(comments: &[(u32, &[u8])]) -> Vec<u8> {
+        let mut buf = vec![0; 4];
+        buf.extend_from_slice(&(comments.len() as u32).to_le_bytes());
+        for (len, comment) in comments {
+            buf.extend_from_slice(&len.to_le_bytes());
+            buf.extend_from_slice(comment);
+        }
+        buf
+    }
+
+    fn read_comment_block(buf: &[u8], limit: Limit) -> Result<MetadataRevision> {
+        read_comment_block_with_strictness(buf, limit, false)
+    }
+
+    fn read_comment_block_with_strictness(
+        buf: &[u8],
+        limit: Limit,
+        strict: bool,
+    ) -> Result<MetadataRevision> {
+        let mut builder = MetadataBuilder::new(VORBIS_COMMENT_METADATA_INFO);
+        let mut side_data = Vec::new();
+        read_vorbis_comment(&mut BufReader::new(buf), &mut builder, &mut side_data, limit, strict)?;
+        Ok(builder.build())
+    }
+
+    /// Write a Vorbis comment block with an empty vendor string and the given comments.
+    fn comment_block_from_strs(comments: &[&str]) -> Vec<u8> {
+        let comments = comments
+            .iter()
+            .map(|comment| (comment.len() as u32, comment.as_bytes()))
+            .collect::<Vec<_>>();
+        comment_block(&comments)
+    }
+
+    #[test]
+    fn verify_read_vorbis_comment_limit() {
+        let buf = comment_block(&[(12, b"TITLE=Title!")]);
+
+        assert!(read_comment_block(&buf, Limit::Default).is_ok());
+        assert!(read_comment_block(&buf, Limit::None).is_ok());
+        assert!(read_comment_block(&buf, Limit::Maximum(12)).is_ok());
+        assert!(matches!(read_comment_block(&buf, Limit::Maximum(11)), Err(Error::DecodeError(_))));
+
+        // A comment stating a huge length is rejected before any memory is allocated for it.
+        let buf = comment_block(&[(u32::MAX, b"TITLE=Title!")]);
+
+        assert!(matches!(read_comment_block(&buf, Limit::Default), Err(Error::DecodeError(_))));
+    }
+
+    #[test]
+    fn verify_read_legacy_cover_art() {
+        // A 2x1 PNG image.
+        let buf = comment_block_from_strs(&[
+            "COVERARTMIME=image/png",
+            "TITLE=Title",
+            "COVERART=iVBORw0KGgoAAAANSUhEUgAAAAIAAAABCAIAAAAAAAAA",
+            "COVERARTTYPE=3",
+        ]);
+
+        let rev = read_comment_block(&buf, Limit::Default).unwrap();
+
+        assert_eq!(rev.media.tags.len(), 1);
+        assert_eq!(rev.media.visuals.len(), 1);
+
+        let visual = &rev.media.visuals[0];
+        assert_eq!(visual.media_type.as_deref(), Some("image/png"));
+        assert_eq!(visual.dimensions.map(|size| (size.width, size.height)), Some((2, 1)));
+        assert_eq!(visual.usage, Some(StandardVisualKey::FrontCover));
+        assert_eq!(visual.data.len(), 33);
+
+        // If the image format cannot be detected, the stated media type is used.
+        let buf = comment_block_from_strs(&["COVERART=AAECAw==", "COVERARTMIME=image/jpeg"]);
+
+        let rev = read_comment_block(&buf, Limit::Default).unwrap();
+
+        assert_eq!(rev.media.visuals.len(), 1);
+
+        let visual = &rev.media.visuals[0];
+        assert_eq!(visual.media_type.as_deref(), Some("image/jpeg"));
+        assert!(visual.dimensions.is_none());
+        assert_eq!(visual.usage, None);
+        assert_eq!(&visual.data[..], &[0, 1, 2, 3]);
+
+        // Without a media type, undetectable images are dropped.
+        let buf = comment_block_from_strs(&["COVERART=AAECAw=="]);
+
+        assert!(read_comment_block(&buf, Limit::Default).unwrap().media.visuals.is_empty());
+    }
+
+    #[test]
+    fn verify_read_lrc_lyrics() {
+        let buf = comment_block_from_strs(&[
+            "LYRICS=[00:01.00]Hello\n[00:02.50]World",
+            "UNSYNCEDLYRICS=Hello World",
+        ]);
+
+        let rev = read_comment_block(&buf, Limit::Default).unwrap();
+
+        // Both lyrics are kept as tags, but only LRC-formatted lyrics are synchronized lyrics.
+        assert_eq!(rev.media.tags.len(), 2);
+        assert_eq!(rev.media.synced_lyrics.len(), 1);
+        assert_eq!(
+            rev.media.synced_lyrics[0].lines,
+            [
+                (Time::from_millis(1000), "Hello".to_string()),
+                (Time::from_millis(2500), "World".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn verify_read_vorbis_comment_strict() {
+        let read = |comments: &[&str], strict| {
+            read_comment_block_with_strictness(
+                &comment_block_from_strs(comments),
+                Limit::Default,
+                strict,
+            )
+        };
+
+        // Each input is tolerated in lenient mode, but is an error in strict mode.
+        let inputs: [&[&str]; 4] = [
+            // A comment without a key-value separator.
+            &["TITLE=Title", "Malformed"],
+            // A picture block with invalid base64.
+            &["TITLE=Title", "METADATA_BLOCK_PICTURE=!!!!"],
+            // A truncated picture block.
+            &["TITLE=Title", "METADATA_BLOCK_PICTURE=AAAAAw=="],
+            // Cover art with an undetectable image format, and no media type.
+            &["TITLE=Title", "COVERART=AAECAw=="],
+        ];
+
+        for comments in inputs {
+            let rev = read(comments, false).unwrap();
+            assert_eq!(rev.media.tags.len(), 1);
+            assert!(rev.media.visuals.is_empty());
+
+            assert!(read(comments, true).is_err());
+        }
+
+        // Well-formed comments are read the same in strict mode.
+        assert_eq!(read(&["TITLE=Title", "ARTIST=Artist"], true).unwrap().media.tags.len(), 2);
+    }
 }
@@ -0,0 +1,259 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! An ICY (SHOUTcast) metadata source for internet radio streams.
+//!
+//! ICY servers interleave a metadata block into the audio stream after every `metaint` bytes of
+//! audio, where `metaint` is given by the `icy-metaint` HTTP response header. Each metadata block
+//! begins with a single byte giving its length in units of 16 bytes, followed by a NUL-padded
+//! string of `Key='value';` pairs, such as `StreamTitle='Artist - Title';`.
+
+use std::io;
+use std::sync::Arc;
+use std::sync::mpsc;
+
+use symphonia_core::io::MediaSource;
+use symphonia_core::meta::well_known::METADATA_ID_ICY;
+use symphonia_core::meta::{MetadataBuilder, MetadataInfo, MetadataRevision, StandardTag, Tag};
+use symphonia_core::util::text;
+
+const ICY_METADATA_INFO: MetadataInfo =
+    MetadataInfo { metadata: METADATA_ID_ICY, short_name: "icy", long_name: "ICY (SHOUTcast)" };
+
+/// The receiving half of an [`IcySource`], yielding a metadata revision each time the stream
+/// metadata changes.
+pub struct IcyMetadataReceiver {
+    receiver: mpsc::Receiver<MetadataRevision>,
+}
+
+impl IcyMetadataReceiver {
+    /// Gets the next pending metadata revision, if any, without blocking.
+    pub fn try_recv(&mut self) -> Option<MetadataRevision> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+/// `IcySource` wraps a reader of an ICY stream, stripping the interleaved metadata blocks such
+/// that only the audio bytes are passed through.
+///
+/// Each time a metadata block with new content is read, it is parsed into a metadata revision and
+/// sent to the [`IcyMetadataReceiver`] returned when the source was instantiated. Since the source
+/// is typically read by a format reader, the receiver should be polled periodically, such as after
+/// each packet.
+pub struct IcySource<R: io::Read> {
+    inner: R,
+    /// The number of audio bytes between metadata blocks, or 0 if the stream has no metadata.
+    metaint: usize,
+    /// The number of audio bytes remaining until the next metadata block.
+    remaining: usize,
+    /// The contents of the last metadata block.
+    last: Vec<u8>,
+    sender: mpsc::Sender<MetadataRevision>,
+}
+
+impl<R: io::Read + Send> IcySource<R> {
+    /// Instantiates a new `IcySource<R>` by taking ownership and wrapping the provided `Read`er.
+    /// The metadata interval, `metaint`, is the value of the `icy-metaint` HTTP response header. If
+    /// `metaint` is 0, the stream is assumed to have no metadata and is passed through unmodified.
+    ///
+    /// Returns the source and the receiver of the metadata revisions.
+    pub fn new(inner: R, metaint: usize) -> (Self, IcyMetadataReceiver) {
+        let (sender, receiver) = mpsc::channel();
+
+        let source = IcySource { inner, metaint, remaining: metaint, last: Vec::new(), sender };
+
+        (source, IcyMetadataReceiver { receiver })
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `IcySource<R>`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: io::Read> IcySource<R> {
+    /// Read the metadata block at the current position. Returns `false` if the end of the stream
+    /// was reached before the block.
+    fn read_metadata_block(&mut self) -> io::Result<bool> {
+        let mut len = [0u8; 1];
+
+        if self.inner.read(&mut len)? == 0 {
+            return Ok(false);
+        }
+
+        let mut block = vec![0u8; 16 * usize::from(len[0])];
+        self.inner.read_exact(&mut block)?;
+
+        // Strip the NUL padding.
+        let end = block.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+        block.truncate(end);
+
+        // An empty block indicates the metadata is unchanged.
+        if !block.is_empty() && block != self.last {
+            // The receiver may have been dropped if the metadata is not wanted.
+            let _ = self.sender.send(parse_metadata(&block));
+            self.last = block;
+        }
+
+        Ok(true)
+    }
+}
+
+impl<R: io::Read + Send + Sync> MediaSource for IcySource<R> {
+    fn is_seekable(&self) -> bool {
+        false
+    }
+
+    fn byte_len(&self) -> Option<u64> {
+        None
+    }
+}
+
+impl<R: io::Read> io::Read for IcySource<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.metaint == 0 {
+            return self.inner.read(buf);
+        }
+
+        if self.remaining == 0 {
+            if !self.read_metadata_block()? {
+                return Ok(0);
+            }
+            self.remaining = self.metaint;
+        }
+
+        let len = buf.len().min(self.remaining);
+        let read = self.inner.read(&mut buf[..len])?;
+
+        self.remaining -= read;
+        Ok(read)
+    }
+}
+
+impl<R: io::Read> io::Seek for IcySource<R> {
+    fn seek(&mut self, _: io::SeekFrom) -> io::Result<u64> {
+        Err(io::Error::other("source does not support seeking"))
+    }
+}
+
+/// Decode the text of a metadata block. The text should be UTF-8, but many servers send
+/// ISO/IEC 8859-1.
+fn decode_text(buf: &[u8]) -> String {
+    match std::str::from_utf8(buf) {
+        Ok(text) => text.to_string(),
+        Err(_) => text::decode_iso8859_1_lossy(buf).collect(),
+    }
+}
+
+/// Parse the `Key='value';` pairs of a metadata block into a metadata revision.
+fn parse_metadata(block: &[u8]) -> MetadataRevision {
+    let mut builder = MetadataBuilder::new(ICY_METADATA_INFO);
+
+    let content = decode_text(block);
+    let mut rem = content.as_str();
+
+    while let Some((key, value)) = rem.split_once("='") {
+        // The value is terminated by `';`. Since the value itself may contain quotes, the
+        // terminator is not just a single quote. The last value may not be terminated.
+        let (value, next) = match value.find("';") {
+            Some(end) => (&value[..end], &value[end + 2..]),
+            None => (value.strip_suffix('\'').unwrap_or(value), ""),
+        };
+
+        let key = key.trim();
+        let value = Arc::new(value.to_string());
+
+        let std_tag = match key {
+            "StreamTitle" => Some(StandardTag::TrackTitle(value.clone())),
+            "StreamUrl" => Some(StandardTag::Url(value.clone())),
+            _ => None,
+        };
+
+        builder.add_tag(Tag::new_from_parts(key, value, std_tag));
+
+        rem = next;
+    }
+
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use symphonia_core::meta::{MetadataRevision, StandardTag};
+
+    use super::IcySource;
+
+    /// Write a metadata block with the given content.
+    fn block(content: &str) -> Vec<u8> {
+        let len = content.len().div_ceil(16);
+        let mut buf = vec![len as u8];
+        buf.extend_from_slice(content.as_bytes());
+        buf.resize(1 + 16 * len, 0);
+        buf
+    }
+
+    fn title(rev: &MetadataRevision) -> &str {
+        rev.media
+            .tags
+            .iter()
+            .find_map(|tag| match &tag.std {
+                Some(StandardTag::TrackTitle(title)) => Some(title.as_str()),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn verify_icy_source() {
+        let audio: Vec<u8> = (0..36).collect();
+
+        // Metadata blocks every 8 bytes of audio. An empty block or a block with unchanged content
+        // does not produce a new revision.
+        let stream = [
+            &audio[0..8],
+            &block("StreamTitle='One';StreamUrl='http://example.com';"),
+            &audio[8..16],
+            &block(""),
+            &audio[16..24],
+            &block("StreamTitle='One';StreamUrl='http://example.com';"),
+            &audio[24..32],
+            &block("StreamTitle='It's Two';"),
+            &audio[32..36],
+        ]
+        .concat();
+
+        let (mut source, mut receiver) = IcySource::new(stream.as_slice(), 8);
+
+        let mut buf = Vec::new();
+        source.read_to_end(&mut buf).unwrap();
+
+        // Only the audio is passed through.
+        assert_eq!(buf, audio);
+
+        let first = receiver.try_recv().unwrap();
+        assert_eq!(title(&first), "One");
+        let url = first.media.tags.iter().find(|tag| tag.raw.key == "StreamUrl").unwrap();
+        assert!(
+            matches!(&url.std, Some(StandardTag::Url(url)) if url.as_str() == "http://example.com")
+        );
+
+        assert_eq!(title(&receiver.try_recv().unwrap()), "It's Two");
+        assert!(receiver.try_recv().is_none());
+    }
+}
@@ -10,15 +10,17 @@
 use std::collections::HashMap;
 use std::str;
 
+use symphonia_core::common::Limit;
 use symphonia_core::errors::{Result, decode_error};
 use symphonia_core::io::{BufReader, FiniteStream, ReadBytes};
 use symphonia_core::meta::RawTagSubField;
-use symphonia_core::meta::{Chapter, RawValue, Tag, Visual};
+use symphonia_core::meta::{Chapter, RawValue, SyncedLyrics, Tag, Visual};
 
 use lazy_static::lazy_static;
 use log::warn;
 use smallvec::SmallVec;
 
+use crate::embedded::DEFAULT_LIMIT_ELEMENT_BYTES;
 use crate::id3v2::sub_fields::*;
 use crate::id3v2::unsync::{decode_unsynchronisation, read_syncsafe_leq32};
 use crate::utils::std_tag::*;
@@ -200,6 +202,8 @@ pub enum FrameResult {
     Tag(Tag),
     /// A frame was parsed and yielded a single `Visual`.
     Visual(Visual),
+    /// A frame was parsed and yielded synchronized lyrics.
+    SyncedLyrics(SyncedLyrics),
     /// A frame was parsed and yielded many `Tag`s.
     MultipleTags(SmallVec<[Tag; 2]>),
     /// A frame was parsed and yielded a chapter.
@@ -327,7 +331,7 @@ lazy_static! {
             m.insert(b"RVRB", (read_raw_frame, None));
             m.insert(b"SEEK", (skip_frame, None));
             m.insert(b"SIGN", (read_sign_frame, None));
-            m.insert(b"SYLT", (read_raw_frame, None));
+            m.insert(b"SYLT", (read_sylt_frame, None));
             m.insert(b"SYTC", (read_raw_frame, None));
             m.insert(b"TALB", (read_text_frame, Some(parse_album)));
             m.insert(b"TBPM", (read_text_frame, Some(parse_bpm)));
@@ -446,8 +450,14 @@ fn find_legacy_frame_reader(id: [u8; 3]) -> (FrameReader, Option<RawTagParser>)
     }
 }
 
-/// Read an ID3v2.2 frame.
-pub fn read_id3v2p2_frame<B: ReadBytes>(reader: &mut B) -> Result<FrameResult> {
+/// Read an ID3v2.2 frame. If `strict` is `true`, malformed frames that would otherwise be
+/// skipped are returned as an error. Frames larger than `limit` bytes are skipped, or rejected in
+/// strict mode.
+pub fn read_id3v2p2_frame<B: ReadBytes>(
+    reader: &mut B,
+    strict: bool,
+    limit: Limit,
+) -> Result<FrameResult> {
     let id = reader.read_triple_bytes()?;
 
     // Check if the frame id contains valid characters. If it does not, then assume the rest of the
@@ -457,6 +467,9 @@ pub fn read_id3v2p2_frame<B: ReadBytes>(reader: &mut B) -> Result<FrameResult> {
         // As per the specification, padding should be all 0s, but there are some tags which don't
         // obey the specification.
         if id != [0, 0, 0] {
+            if strict {
+                return decode_error("id3v2: padding bytes not zero");
+            }
             warn!("padding bytes not zero");
         }
 
@@ -470,19 +483,34 @@ pub fn read_id3v2p2_frame<B: ReadBytes>(reader: &mut B) -> Result<FrameResult> {
 
     // A frame must be atleast 1 byte as per the specification.
     if size == 0 {
+        if strict {
+            return decode_error("id3v2: frame has a size of 0");
+        }
         warn!("'{}' was skipped because it has a size of 0", from_ascii(&id));
         return Ok(FrameResult::Skipped);
     }
 
+    // A frame exceeding the size limit is not read.
+    if limit.is_exceeded_by(size as usize, DEFAULT_LIMIT_ELEMENT_BYTES) {
+        if strict {
+            return decode_error("id3v2: frame exceeds the size limit");
+        }
+        reader.ignore_bytes(size)?;
+
+        warn!("'{}' was skipped because it exceeds the size limit", from_ascii(&id));
+        return Ok(FrameResult::Skipped);
+    }
+
     // Read the frame body into a frame buffer.
     let data = reader.read_boxed_slice_exact(size as usize)?;
 
     // Prepare frame information for the frame reader.
-    let info = FrameInfo::new(&id, 2, raw_tag_parser);
+    let info = FrameInfo::new(&id, 2, raw_tag_parser, strict);
 
     // An error while reading the frame from the frame buffer is not fatal.
     match read_frame(BufReader::new(&data), &info) {
         Ok(result) => Ok(result),
+        Err(err) if strict => Err(err),
         Err(err) => {
             // On error, skip the frame.
             warn!("{err}");
@@ -491,8 +519,14 @@ pub fn read_id3v2p2_frame<B: ReadBytes>(reader: &mut B) -> Result<FrameResult> {
     }
 }
 
-/// Read an ID3v2.3 frame.
-pub fn read_id3v2p3_frame<B: ReadBytes>(reader: &mut B) -> Result<FrameResult> {
+/// Read an ID3v2.3 frame. If `strict` is `true`, malformed frames that would otherwise be
+/// skipped are returned as an error. Frames larger than `limit` bytes are skipped, or rejected in
+/// strict mode.
+pub fn read_id3v2p3_frame<B: ReadBytes>(
+    reader: &mut B,
+    strict: bool,
+    limit: Limit,
+) -> Result<FrameResult> {
     let id = reader.read_quad_bytes()?;
 
     // Check if the frame id contains valid characters. If it does not, then assume the rest of the
@@ -502,6 +536,9 @@ pub fn read_id3v2p3_frame<B: ReadBytes>(reader: &mut B) -> Result<FrameResult> {
         // As per the specification, padding should be all 0s, but there are some tags which don't
         // obey the specification.
         if id != [0, 0, 0, 0] {
+            if strict {
+                return decode_error("id3v2: padding bytes not zero");
+            }
             warn!("padding bytes not zero");
         }
 
@@ -559,10 +596,24 @@ pub fn read_id3v2p3_frame<B: ReadBytes>(reader: &mut B) -> Result<FrameResult> {
 
     // A zero-length frame body is not allowed, but can be skipped.
     if data_size == 0 {
+        if strict {
+            return decode_error("id3v2: frame has a size of 0");
+        }
         warn!("'{}' was skipped because it has a size of 0", from_ascii(&id));
         return Ok(FrameResult::Skipped);
     }
 
+    // A frame exceeding the size limit is not read.
+    if limit.is_exceeded_by(data_size as usize, DEFAULT_LIMIT_ELEMENT_BYTES) {
+        if strict {
+            return decode_error("id3v2: frame exceeds the size limit");
+        }
+        reader.ignore_bytes(u64::from(data_size))?;
+
+        warn!("'{}' was skipped because it exceeds the size limit", from_ascii(&id));
+        return Ok(FrameResult::Skipped);
+    }
+
     // Read the frame body into a frame buffer.
     let data = reader.read_boxed_slice_exact(data_size as usize)?;
 
@@ -572,7 +623,7 @@ pub fn read_id3v2p3_frame<B: ReadBytes>(reader: &mut B) -> Result<FrameResult> {
         if !is_encrypted { find_frame_reader(id) } else { null_frame_reader() };
 
     // Prepare frame information for the frame reader.
-    let info = FrameInfo::new(&id, 3, raw_tag_parser);
+    let info = FrameInfo::new(&id, 3, raw_tag_parser, strict);
 
     // Read the frame from the frame buffer. An error here is not fatal.
     match read_frame(BufReader::new(&data), &info) {
@@ -583,6 +634,7 @@ pub fn read_id3v2p3_frame<B: ReadBytes>(reader: &mut B) -> Result<FrameResult> {
             }
             Ok(result)
         }
+        Err(err) if strict => Err(err),
         Err(err) => {
             // On error, skip the frame.
             warn!("{err}");
@@ -591,8 +643,14 @@ pub fn read_id3v2p3_frame<B: ReadBytes>(reader: &mut B) -> Result<FrameResult> {
     }
 }
 
-/// Read an ID3v2.4 frame.
-pub fn read_id3v2p4_frame<B: ReadBytes + FiniteStream>(reader: &mut B) -> Result<FrameResult> {
+/// Read an ID3v2.4 frame. If `strict` is `true`, malformed frames that would otherwise be
+/// skipped are returned as an error. Frames larger than `limit` bytes are skipped, or rejected in
+/// strict mode.
+pub fn read_id3v2p4_frame<B: ReadBytes + FiniteStream>(
+    reader: &mut B,
+    strict: bool,
+    limit: Limit,
+) -> Result<FrameResult> {
     let id = reader.read_quad_bytes()?;
 
     // Check if the frame id contains valid characters. If it does not, then assume the rest of the
@@ -601,6 +659,9 @@ pub fn read_id3v2p4_frame<B: ReadBytes + FiniteStream>(reader: &mut B) -> Result
         // As per the specification, padding should be all 0s, but there are some tags which don't
         // obey the specification.
         if id != [0, 0, 0, 0] {
+            if strict {
+                return decode_error("id3v2: padding bytes not zero");
+            }
             warn!("padding bytes not zero");
         }
 
@@ -671,10 +732,24 @@ pub fn read_id3v2p4_frame<B: ReadBytes + FiniteStream>(reader: &mut B) -> Result
 
     // A zero-length frame body is not allowed, but can be skipped.
     if data_size == 0 {
+        if strict {
+            return decode_error("id3v2: frame has a size of 0");
+        }
         warn!("'{}' was skipped because it has a size of 0", from_ascii(&id));
         return Ok(FrameResult::Skipped);
     }
 
+    // A frame exceeding the size limit is not read.
+    if limit.is_exceeded_by(data_size as usize, DEFAULT_LIMIT_ELEMENT_BYTES) {
+        if strict {
+            return decode_error("id3v2: frame exceeds the size limit");
+        }
+        reader.ignore_bytes(u64::from(data_size))?;
+
+        warn!("'{}' was skipped because it exceeds the size limit", from_ascii(&id));
+        return Ok(FrameResult::Skipped);
+    }
+
     // Read the frame body into a frame buffer.
     let mut data = reader.read_boxed_slice_exact(data_size as usize)?;
 
@@ -684,7 +759,7 @@ pub fn read_id3v2p4_frame<B: ReadBytes + FiniteStream>(reader: &mut B) -> Result
         if !is_encrypted { find_frame_reader(id) } else { null_frame_reader() };
 
     // Prepare frame information for the frame reader.
-    let info = FrameInfo::new(&id, 4, raw_tag_parser);
+    let info = FrameInfo::new(&id, 4, raw_tag_parser, strict);
 
     // Read the frame.
     let result = if is_unsynchronised {
@@ -708,6 +783,7 @@ pub fn read_id3v2p4_frame<B: ReadBytes + FiniteStream>(reader: &mut B) -> Result
             }
             Ok(result)
         }
+        Err(err) if strict => Err(err),
         Err(err) => {
             // On error, skip the frame.
             warn!("{err}");
@@ -13,11 +13,12 @@ use std::io;
 use std::str;
 use std::sync::Arc;
 
+use symphonia_core::common::Limit;
 use symphonia_core::errors::{Result, decode_error, unsupported_error};
 use symphonia_core::io::{BufReader, FiniteStream, ReadBytes};
 use symphonia_core::meta::RawTag;
 use symphonia_core::meta::RawTagSubField;
-use symphonia_core::meta::{Chapter, RawValue, StandardTag, Tag, Visual};
+use symphonia_core::meta::{Chapter, RawValue, StandardTag, SyncedLyrics, Tag, Visual};
 use symphonia_core::units::Time;
 use symphonia_core::util::text;
 
@@ -46,18 +47,26 @@ pub struct FrameInfo<'a> {
     major_version: u8,
     /// An optional raw tag parser to be applied if the frame is generic.
     raw_tag_parser: Option<RawTagParser>,
+    /// If `true`, malformed frames nested within the frame are returned as an error.
+    strict: bool,
 }
 
 impl<'a> FrameInfo<'a> {
-    /// Create new frame information from a pre-validated frame ID, tag version and optional
-    /// raw tag parser.
+    /// Create new frame information from a pre-validated frame ID, tag version, optional raw tag
+    /// parser, and strictness.
     ///
     /// Panics if the frame ID is invalid.
-    pub fn new(id: &'a [u8], major_version: u8, raw_tag_parser: Option<RawTagParser>) -> Self {
+    pub fn new(
+        id: &'a [u8],
+        major_version: u8,
+        raw_tag_parser: Option<RawTagParser>,
+        strict: bool,
+    ) -> Self {
         FrameInfo {
             id: std::str::from_utf8(id).expect("validated frame id bytes"),
             major_version,
             raw_tag_parser,
+            strict,
         }
     }
 }
@@ -327,8 +336,8 @@ pub fn read_apic_frame(mut reader: BufReader<'_>, frame: &FrameInfo<'_>) -> Resu
         tags.push(Tag::new_from_parts("", "", Some(StandardTag::Description(Arc::from(desc)))));
     }
 
-    // The remainder of the APIC frame is the image data.
-    // TODO: Apply a limit.
+    // The remainder of the APIC frame is the image data. The size of the frame, and therefore the
+    // image data, is bounded by the frame size limit.
     let data = Box::from(reader.read_buf_bytes_available_ref());
 
     // Try to get information about the image.
@@ -410,9 +419,10 @@ pub fn read_chap_frame(mut reader: BufReader<'_>, frame: &FrameInfo<'_>) -> Resu
 
     while reader.bytes_available() >= min_frame_size(frame.major_version) {
         let frame = match frame.major_version {
-            2 => read_id3v2p2_frame(&mut reader),
-            3 => read_id3v2p3_frame(&mut reader),
-            4 => read_id3v2p4_frame(&mut reader),
+            // Sub-frames are bounded by the size of this frame.
+            2 => read_id3v2p2_frame(&mut reader, frame.strict, Limit::None),
+            3 => read_id3v2p3_frame(&mut reader, frame.strict, Limit::None),
+            4 => read_id3v2p4_frame(&mut reader, frame.strict, Limit::None),
             _ => break,
         }?;
 
@@ -583,9 +593,10 @@ pub fn read_ctoc_frame(mut reader: BufReader<'_>, frame: &FrameInfo<'_>) -> Resu
 
     while reader.bytes_available() >= min_frame_size(frame.major_version) {
         let frame = match frame.major_version {
-            2 => read_id3v2p2_frame(&mut reader),
-            3 => read_id3v2p3_frame(&mut reader),
-            4 => read_id3v2p4_frame(&mut reader),
+            // Sub-frames are bounded by the size of this frame.
+            2 => read_id3v2p2_frame(&mut reader, frame.strict, Limit::None),
+            3 => read_id3v2p3_frame(&mut reader, frame.strict, Limit::None),
+            4 => read_id3v2p4_frame(&mut reader, frame.strict, Limit::None),
             _ => break,
         }?;
 
@@ -804,6 +815,44 @@ pub fn read_sign_frame(mut reader: BufReader<'_>, frame: &FrameInfo<'_>) -> Resu
     Ok(FrameResult::Tag(Tag::new(raw)))
 }
 
+/// Reads a `SYLT` (synchronised lyrics/text) frame.
+pub fn read_sylt_frame(mut reader: BufReader<'_>, _: &FrameInfo<'_>) -> Result<FrameResult> {
+    // The first byte of the frame is the encoding of the description and lyrics.
+    let encoding = read_encoding(&mut reader)?;
+
+    // The language code.
+    let language = read_lang_code(&mut reader)?;
+
+    // The timestamp format. Only timestamps in milliseconds can be converted to a time without
+    // knowledge of the audio stream.
+    let timestamp_format = reader.read_u8()?;
+
+    if timestamp_format != 2 {
+        return unsupported_error("id3v2: sylt timestamps in mpeg frames are not supported");
+    }
+
+    // The content type (lyrics, transcription, etc.) is not used.
+    let _content_type = reader.read_u8()?;
+
+    // Optional content description.
+    let description = read_string_ignore_empty(&mut reader, encoding)?;
+
+    // The remainder of the frame are lines of text, each followed by its timestamp.
+    let mut lines = Vec::new();
+
+    while reader.bytes_available() > 0 {
+        let text = read_string(&mut reader, encoding)?;
+        let timestamp = reader.read_be_u32()?;
+
+        lines.push((Time::from_millis(i64::from(timestamp)), text));
+    }
+
+    // Lines should be in chronological order, but this is not guaranteed.
+    lines.sort_by_key(|(time, _)| *time);
+
+    Ok(FrameResult::SyncedLyrics(SyncedLyrics { language, description, lines }))
+}
+
 /// Reads all text frames frame except for `TXXX`.
 pub fn read_text_frame(mut reader: BufReader<'_>, frame: &FrameInfo<'_>) -> Result<FrameResult> {
     // The first byte of the frame is the encoding.
@@ -1084,6 +1133,8 @@ lazy_static! {
 mod tests {
     use super::Encoding;
 
+    use symphonia_core::units::Time;
+
     use symphonia_core::io::BufReader;
 
     #[test]
@@ -1302,4 +1353,80 @@ mod tests {
             RawValue::StringList(Arc::new(vec!["".to_string(), "".to_string()]))
         );
     }
+
+    #[test]
+    fn verify_read_apic_frame() {
+        use super::{FrameInfo, FrameResult, read_apic_frame};
+        use symphonia_core::meta::{ColorMode, ColorModel, StandardVisualKey};
+
+        // A baseline JPEG start-of-frame segment for a 640x480 image.
+        let jpeg = [0xff, 0xd8, 0xff, 0xc0, 0x00, 0x11, 0x08, 0x01, 0xe0, 0x02, 0x80, 0x03];
+
+        let read_visual = |id: &[u8], header: &[u8]| {
+            let buf = [header, &[0x03, 0x00], &jpeg].concat();
+            let frame = FrameInfo::new(id, if id.len() == 3 { 2 } else { 4 }, None, false);
+
+            match read_apic_frame(BufReader::new(&buf), &frame).unwrap() {
+                FrameResult::Visual(visual) => visual,
+                _ => panic!("expected a visual"),
+            }
+        };
+
+        // The image characteristics are detected from the image data, and preferred over the
+        // stated media type.
+        for visual in [
+            read_visual(b"APIC", b"\x00image/png\x00"),
+            read_visual(b"APIC", b"\x00\x00"),
+            read_visual(b"PIC", b"\x00PNG"),
+        ] {
+            assert_eq!(visual.media_type.as_deref(), Some("image/jpeg"));
+            assert_eq!(visual.dimensions.map(|size| (size.width, size.height)), Some((640, 480)));
+            assert!(matches!(visual.color_mode, Some(ColorMode::Direct(ColorModel::RGB(_)))));
+            assert_eq!(visual.usage, Some(StandardVisualKey::FrontCover));
+            assert_eq!(&visual.data[..], &jpeg);
+        }
+    }
+
+    #[test]
+    fn verify_read_sylt_frame() {
+        use super::{FrameInfo, FrameResult, read_sylt_frame};
+
+        let read_lyrics = |buf: &[u8]| {
+            read_sylt_frame(BufReader::new(buf), &FrameInfo::new(b"SYLT", 4, None, false)).map(
+                |result| match result {
+                    FrameResult::SyncedLyrics(lyrics) => lyrics,
+                    _ => panic!("expected synced lyrics"),
+                },
+            )
+        };
+
+        // UTF-8 encoding, language, millisecond timestamps, lyrics content type, and description.
+        let mut buf = [&[3], b"eng".as_slice(), &[2, 1], b"Verse\0"].concat();
+        for (text, time) in [("Hello", 1500u32), ("World", 3250), ("\u{e9}t\u{e9}", 2000)] {
+            buf.extend_from_slice(text.as_bytes());
+            buf.push(0);
+            buf.extend_from_slice(&time.to_be_bytes());
+        }
+
+        let lyrics = read_lyrics(&buf).unwrap();
+
+        assert_eq!(lyrics.language.as_deref(), Some("eng"));
+        assert_eq!(lyrics.description.as_deref(), Some("Verse"));
+        assert_eq!(
+            lyrics.lines,
+            [
+                (Time::from_millis(1500), "Hello".to_string()),
+                (Time::from_millis(2000), "\u{e9}t\u{e9}".to_string()),
+                (Time::from_millis(3250), "World".to_string()),
+            ]
+        );
+
+        // Timestamps in MPEG frames are not supported.
+        buf[4] = 1;
+        assert!(read_lyrics(&buf).is_err());
+
+        // A truncated timestamp.
+        let buf = [&[0], b"eng".as_slice(), &[2, 1, 0], b"Hello\0", &[0, 0]].concat();
+        assert!(read_lyrics(&buf).is_err());
+    }
 }
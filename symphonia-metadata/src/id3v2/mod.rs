@@ -9,6 +9,7 @@
 
 use std::collections::HashMap;
 
+use symphonia_core::common::Limit;
 use symphonia_core::errors::{Result, decode_error, unsupported_error};
 use symphonia_core::formats::probe::{ProbeMetadataData, ProbeableMetadata, Score, Scoreable};
 use symphonia_core::io::*;
@@ -283,6 +284,8 @@ fn read_id3v2_body<B: ReadBytes + FiniteStream>(
     header: &Header,
     metadata: &mut MetadataBuilder,
     side_data: &mut Vec<MetadataSideData>,
+    strict: bool,
+    limit: Limit,
 ) -> Result<()> {
     // If there is an extended header, read and parse it based on the major version of the tag.
     if header.has_extended_header {
@@ -301,9 +304,9 @@ fn read_id3v2_body<B: ReadBytes + FiniteStream>(
     loop {
         // Read frames based on the major version of the tag.
         let frame = match header.major_version {
-            2 => read_id3v2p2_frame(reader),
-            3 => read_id3v2p3_frame(reader),
-            4 => read_id3v2p4_frame(reader),
+            2 => read_id3v2p2_frame(reader, strict, limit),
+            3 => read_id3v2p3_frame(reader, strict, limit),
+            4 => read_id3v2p4_frame(reader, strict, limit),
             _ => break,
         };
 
@@ -326,6 +329,10 @@ fn read_id3v2_body<B: ReadBytes + FiniteStream>(
             Ok(FrameResult::Visual(visual)) => {
                 metadata.add_visual(visual);
             }
+            // A frame was parsed into synchronized lyrics, add it to the lyrics collection.
+            Ok(FrameResult::SyncedLyrics(lyrics)) => {
+                metadata.add_synced_lyrics(lyrics);
+            }
             // A chapter was encountered, save it for post-processing.
             Ok(FrameResult::Chapter(chap)) => {
                 chap_builder.add_chapter(chap);
@@ -334,11 +341,14 @@ fn read_id3v2_body<B: ReadBytes + FiniteStream>(
             Ok(FrameResult::TableOfContents(toc)) => {
                 chap_builder.add_toc(toc);
             }
-            Err(_) => {
+            Err(err) => {
                 // The read frame functions suppress any errors that occur while reading the content
-                // of a frame. Any errors that are returned are related to the frame's structure and
-                // result in a fatal error since the structure of the overall tag becomes
-                // compromised.
+                // of a frame, unless in strict mode. Any errors that are returned are related to the
+                // frame's structure and result in a fatal error since the structure of the overall
+                // tag becomes compromised.
+                if strict {
+                    return Err(err);
+                }
                 warn!("fatal error reading id3v2 frame, skipping remainder of tag");
                 break;
             }
@@ -358,10 +368,14 @@ fn read_id3v2_body<B: ReadBytes + FiniteStream>(
     Ok(())
 }
 
+/// Read an ID3v2 tag. If `strict` is `true`, malformed frames that would otherwise be skipped are
+/// returned as an error. Frames larger than `limit` bytes are skipped, or rejected in strict mode.
 pub(crate) fn read_id3v2<B: ReadBytes>(
     reader: &mut B,
     metadata: &mut MetadataBuilder,
     side_data: &mut Vec<MetadataSideData>,
+    strict: bool,
+    limit: Limit,
 ) -> Result<()> {
     // Read the (sorta) version agnostic tag header.
     let header = read_id3v2_header(reader)?;
@@ -371,7 +385,7 @@ pub(crate) fn read_id3v2<B: ReadBytes>(
     let mut scoped = if header.unsynchronisation && header.major_version < 4 {
         let mut unsync = UnsyncStream::new(ScopedStream::new(reader, u64::from(header.size)));
 
-        read_id3v2_body(&mut unsync, &header, metadata, side_data)?;
+        read_id3v2_body(&mut unsync, &header, metadata, side_data, strict, limit)?;
 
         unsync.into_inner()
     }
@@ -380,7 +394,7 @@ pub(crate) fn read_id3v2<B: ReadBytes>(
     else {
         let mut scoped = ScopedStream::new(reader, u64::from(header.size));
 
-        read_id3v2_body(&mut scoped, &header, metadata, side_data)?;
+        read_id3v2_body(&mut scoped, &header, metadata, side_data, strict, limit)?;
 
         scoped
     };
@@ -551,11 +565,12 @@ pub(crate) const ID3V2_METADATA_INFO: MetadataInfo =
 /// ID3v2 tag reader.
 pub struct Id3v2Reader<'s> {
     reader: MediaSourceStream<'s>,
+    strict: bool,
 }
 
 impl<'s> Id3v2Reader<'s> {
-    pub fn try_new(mss: MediaSourceStream<'s>, _opts: MetadataOptions) -> Result<Self> {
-        Ok(Self { reader: mss })
+    pub fn try_new(mss: MediaSourceStream<'s>, opts: MetadataOptions) -> Result<Self> {
+        Ok(Self { reader: mss, strict: opts.strict })
     }
 }
 
@@ -590,7 +605,8 @@ impl MetadataReader for Id3v2Reader<'_> {
         let mut builder = MetadataBuilder::new(ID3V2_METADATA_INFO);
         let mut side_data = Vec::new();
 
-        read_id3v2(&mut self.reader, &mut builder, &mut side_data)?;
+        // The metadata options do not limit the size of a single frame, so use the default.
+        read_id3v2(&mut self.reader, &mut builder, &mut side_data, self.strict, Limit::Default)?;
 
         Ok(MetadataBuffer { revision: builder.build(), side_data })
     }
@@ -701,3 +717,79 @@ pub mod sub_fields {
 
     pub const WXXX_DESCRIPTION: &str = "DESCRIPTION";
 }
+
+#[cfg(test)]
+mod tests {
+    use symphonia_core::io::BufReader;
+    use symphonia_core::meta::MetadataRevision;
+
+    use super::*;
+
+    /// Write an ID3v2.4 frame with a body of less than 128 bytes.
+    fn frame(id: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        [id.as_slice(), &(body.len() as u32).to_be_bytes(), &[0, 0], body].concat()
+    }
+
+    /// Read an ID3v2.4 tag containing frames with a combined size of less than 128 bytes.
+    fn read_tag(frames: &[Vec<u8>], strict: bool, limit: Limit) -> Result<MetadataRevision> {
+        let frames = frames.concat();
+        let buf =
+            [b"ID3".as_slice(), &[4, 0, 0], &(frames.len() as u32).to_be_bytes(), &frames].concat();
+
+        let mut builder = MetadataBuilder::new(ID3V2_METADATA_INFO);
+        read_id3v2(&mut BufReader::new(&buf), &mut builder, &mut Vec::new(), strict, limit)?;
+        Ok(builder.build())
+    }
+
+    #[test]
+    fn verify_read_id3v2_strict() {
+        let title = frame(b"TIT2", b"\x03Song");
+
+        // A chapter with the element ID, start and end times, and no byte offsets, followed by the
+        // given sub-frames.
+        let chapter = |frames: &[Vec<u8>]| {
+            let body =
+                [b"ch0\0".as_slice(), &0u32.to_be_bytes(), &1000u32.to_be_bytes(), &[0xff; 8]];
+            frame(b"CHAP", &[&body.concat(), frames.concat().as_slice()].concat())
+        };
+
+        // Each tag is tolerated in lenient mode, but is an error in strict mode.
+        let inputs = [
+            // A frame with a size of 0.
+            vec![title.clone(), frame(b"TPE1", &[])],
+            // A frame that cannot be read.
+            vec![title.clone(), frame(b"TPE1", b"\x07Artist")],
+            // A chapter containing a frame with a size of 0.
+            vec![title.clone(), chapter(&[frame(b"TIT2", &[])])],
+            // A chapter containing a frame that cannot be read.
+            vec![title.clone(), chapter(&[frame(b"TIT2", b"\x07Intro")])],
+        ];
+
+        for frames in inputs {
+            let rev = read_tag(&frames, false, Limit::Default).unwrap();
+            assert_eq!(rev.media.tags.len(), 1);
+
+            assert!(read_tag(&frames, true, Limit::Default).is_err());
+        }
+
+        // Well-formed frames are read the same in strict mode.
+        let frames = [title, chapter(&[frame(b"TIT2", b"\x03Intro")])];
+        assert_eq!(read_tag(&frames, true, Limit::Default).unwrap().media.tags.len(), 1);
+    }
+
+    #[test]
+    fn verify_read_id3v2_limit() {
+        // A picture frame with a 32 byte body.
+        let picture = frame(b"APIC", &[b"\x00image/png\x00\x03\x00".as_slice(), &[0; 18]].concat());
+        let frames = [frame(b"TIT2", b"\x03Song"), picture];
+
+        // The picture frame is skipped if it exceeds the limit, or rejected in strict mode.
+        let rev = read_tag(&frames, false, Limit::Maximum(16)).unwrap();
+        assert_eq!(rev.media.tags.len(), 1);
+        assert!(rev.media.visuals.is_empty());
+        assert!(read_tag(&frames, true, Limit::Maximum(16)).is_err());
+
+        assert_eq!(read_tag(&frames, false, Limit::Maximum(32)).unwrap().media.visuals.len(), 1);
+        assert_eq!(read_tag(&frames, false, Limit::None).unwrap().media.visuals.len(), 1);
+    }
+}
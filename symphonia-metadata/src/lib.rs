@@ -33,6 +33,8 @@
 
 #[cfg(feature = "ape")]
 pub mod ape;
+#[cfg(feature = "icy")]
+pub mod icy;
 #[cfg(feature = "id3v1")]
 pub mod id3v1;
 #[cfg(feature = "id3v2")]
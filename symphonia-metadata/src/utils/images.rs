@@ -10,7 +10,7 @@
 use std::num::NonZeroU8;
 
 use symphonia_core::errors::{Result, decode_error, unsupported_error};
-use symphonia_core::io::{BufReader, ReadBytes};
+use symphonia_core::io::{BufReader, FiniteStream, ReadBytes};
 use symphonia_core::meta::{ColorMode, ColorModel, ColorPaletteInfo, Size};
 
 use log::debug;
@@ -47,11 +47,17 @@ pub fn try_get_image_info(buf: &[u8]) -> Option<ImageInfo> {
         Parser { marker: &[0x47, 0x49, 0x46, 0x38, 0x37, 0x61], parse: parse_gif },
         Parser { marker: &[0x47, 0x49, 0x46, 0x38, 0x39, 0x61], parse: parse_gif },
         Parser { marker: &[0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a], parse: parse_png },
-        // TODO: WebP
+        Parser { marker: &[0x52, 0x49, 0x46, 0x46], parse: parse_webp },
     ];
 
     debug!("detecting format of image starting with: {:02x?}", &buf[..8.min(buf.len())]);
 
+    // HEIF images, including AVIF, start with a file type box. Since the box starts with its size,
+    // the box type, and not a marker, is at the beginning of the image buffer.
+    if buf.get(4..8) == Some(b"ftyp") {
+        return parse_heif(BufReader::new(buf)).ok();
+    }
+
     // Find the first image parser that has a marker that matches the beginning of the image
     // buffer, and attempt to parse it for image information.
     IMAGE_PARSERS
@@ -293,3 +299,251 @@ fn parse_gif(mut reader: BufReader<'_>) -> Result<ImageInfo> {
 
     Ok(info)
 }
+
+/// Parse a WebP for image information.
+fn parse_webp(mut reader: BufReader<'_>) -> Result<ImageInfo> {
+    // The RIFF chunk size is followed by the WebP form type.
+    reader.ignore_bytes(4)?;
+    if reader.read_quad_bytes()? != *b"WEBP" {
+        return decode_error("meta (webp): invalid data");
+    }
+
+    // The first chunk determines the WebP format.
+    let chunk_type = reader.read_quad_bytes()?;
+    let _chunk_len = reader.read_u32()?;
+
+    let (width, height, has_alpha) = match &chunk_type {
+        // Simple format (lossy).
+        b"VP8 " => {
+            // Ignore the frame tag.
+            reader.ignore_bytes(3)?;
+
+            if reader.read_triple_bytes()? != [0x9d, 0x01, 0x2a] {
+                return decode_error("meta (webp): invalid vp8 start code");
+            }
+
+            // The upper 2 bits of the width and height are the scaling factors.
+            let width = reader.read_u16()? & 0x3fff;
+            let height = reader.read_u16()? & 0x3fff;
+
+            (u32::from(width), u32::from(height), false)
+        }
+        // Simple format (lossless).
+        b"VP8L" => {
+            if reader.read_u8()? != 0x2f {
+                return decode_error("meta (webp): invalid vp8l signature");
+            }
+
+            // The width and height (minus 1) are 14-bit fields, followed by the alpha hint.
+            let bits = reader.read_u32()?;
+
+            ((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1, bits & 0x1000_0000 != 0)
+        }
+        // Extended format.
+        b"VP8X" => {
+            let flags = reader.read_u8()?;
+            reader.ignore_bytes(3)?;
+
+            // The canvas width and height (minus 1) are 24-bit fields.
+            let width = reader.read_u24()? + 1;
+            let height = reader.read_u24()? + 1;
+
+            (width, height, flags & 0x10 != 0)
+        }
+        _ => return unsupported_error("meta (webp): unknown format"),
+    };
+
+    let color_model =
+        if has_alpha { ColorModel::RGBA(non_zero(8)) } else { ColorModel::RGB(non_zero(8)) };
+
+    let info = ImageInfo {
+        media_type: "image/webp".to_string(),
+        dimensions: Size { width, height },
+        color_mode: ColorMode::Direct(color_model),
+    };
+
+    Ok(info)
+}
+
+/// Read boxes until a box of the given type is found, and return the length of its payload. At
+/// most `len` bytes are read.
+fn find_heif_box(reader: &mut BufReader<'_>, box_type: &[u8; 4], mut len: u64) -> Result<u64> {
+    while len >= 8 {
+        let size = u64::from(reader.read_be_u32()?);
+        let found_type = reader.read_quad_bytes()?;
+
+        // Get the size of the box header, and the total size of the box.
+        let (header_size, size) = match size {
+            // The box extends to the end of the parent.
+            0 => (8, len),
+            // The box uses a 64-bit size.
+            1 => (16, reader.read_be_u64()?),
+            _ => (8, size),
+        };
+
+        if size < header_size || size > len {
+            return decode_error("meta (heif): invalid box size");
+        }
+
+        if found_type == *box_type {
+            return Ok(size - header_size);
+        }
+
+        reader.ignore_bytes(size - header_size)?;
+        len -= size;
+    }
+
+    decode_error("meta (heif): box not found")
+}
+
+/// Parse a HEIF, or AVIF, for image information.
+fn parse_heif(mut reader: BufReader<'_>) -> Result<ImageInfo> {
+    let len = reader.bytes_available();
+
+    // Read the brands of the file type box.
+    let ftyp_len = find_heif_box(&mut reader, b"ftyp", len)?;
+
+    if ftyp_len < 8 {
+        return decode_error("meta (heif): invalid file type box");
+    }
+
+    let major_brand = reader.read_quad_bytes()?;
+    let _minor_version = reader.read_be_u32()?;
+
+    let mut brands = vec![major_brand];
+
+    for _ in 0..(ftyp_len - 8) / 4 {
+        brands.push(reader.read_quad_bytes()?);
+    }
+
+    reader.ignore_bytes((ftyp_len - 8) % 4)?;
+
+    let has_brand = |names: &[&[u8; 4]]| brands.iter().any(|brand| names.contains(&brand));
+
+    let media_type = if has_brand(&[b"avif", b"avis"]) {
+        "image/avif"
+    }
+    else if has_brand(&[b"heic", b"heix", b"heim", b"heis"]) {
+        "image/heic"
+    }
+    else if has_brand(&[b"mif1", b"msf1"]) {
+        "image/heif"
+    }
+    else {
+        return unsupported_error("meta (heif): unsupported brand");
+    };
+
+    // The image dimensions are stored in the image spatial extents property of the primary image.
+    // Find the first such property at meta.iprp.ipco.ispe. The meta, and ispe, boxes are full boxes.
+    let len = reader.bytes_available();
+    let meta_len = find_heif_box(&mut reader, b"meta", len)?;
+    reader.ignore_bytes(4)?;
+
+    let iprp_len = find_heif_box(&mut reader, b"iprp", meta_len.saturating_sub(4))?;
+    let ipco_len = find_heif_box(&mut reader, b"ipco", iprp_len)?;
+    let _ispe_len = find_heif_box(&mut reader, b"ispe", ipco_len)?;
+    reader.ignore_bytes(4)?;
+
+    let width = reader.read_be_u32()?;
+    let height = reader.read_be_u32()?;
+
+    let info = ImageInfo {
+        media_type: media_type.to_string(),
+        dimensions: Size { width, height },
+        color_mode: ColorMode::Direct(ColorModel::RGB(non_zero(8))),
+    };
+
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use symphonia_core::meta::{ColorMode, ColorModel};
+
+    use super::{non_zero, try_get_image_info};
+
+    /// Write a RIFF WebP file with a single chunk.
+    fn webp(chunk_type: &[u8; 4], chunk: &[u8]) -> Vec<u8> {
+        let mut buf = b"RIFF".to_vec();
+        buf.extend_from_slice(&(12 + chunk.len() as u32).to_le_bytes());
+        buf.extend_from_slice(b"WEBP");
+        buf.extend_from_slice(chunk_type);
+        buf.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
+        buf.extend_from_slice(chunk);
+        buf
+    }
+
+    /// Write an ISO base media file format box.
+    fn heif_box(box_type: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+        let mut buf = (8 + payload.len() as u32).to_be_bytes().to_vec();
+        buf.extend_from_slice(box_type);
+        buf.extend_from_slice(payload);
+        buf
+    }
+
+    /// Write a HEIF file with the given brands, and an image spatial extents property.
+    fn heif(major_brand: &[u8; 4], brands: &[&[u8; 4]], width: u32, height: u32) -> Vec<u8> {
+        let mut ftyp = [*major_brand, [0; 4]].concat();
+        ftyp.extend(brands.iter().flat_map(|brand| brand.iter()));
+        let ftyp = heif_box(b"ftyp", &ftyp);
+
+        let ispe = heif_box(b"ispe", &[[0; 4], width.to_be_bytes(), height.to_be_bytes()].concat());
+        let ipco = heif_box(b"ipco", &[heif_box(b"colr", &[0; 4]), ispe].concat());
+        let iprp = heif_box(b"iprp", &ipco);
+        let meta =
+            heif_box(b"meta", &[[0; 4].as_slice(), &heif_box(b"hdlr", &[0; 24]), &iprp].concat());
+
+        [ftyp, meta, heif_box(b"mdat", &[0; 16])].concat()
+    }
+
+    fn assert_image_info(buf: &[u8], media_type: &str, size: (u32, u32), color_model: ColorModel) {
+        let info = try_get_image_info(buf).unwrap();
+        assert_eq!(info.media_type, media_type);
+        assert_eq!((info.dimensions.width, info.dimensions.height), size);
+        assert_eq!(info.color_mode, ColorMode::Direct(color_model));
+    }
+
+    #[test]
+    fn verify_webp_image_info() {
+        let rgb = ColorModel::RGB(non_zero(8));
+        let rgba = ColorModel::RGBA(non_zero(8));
+
+        // Lossy, with the frame tag, start code, and a 640x480 size with scaling factors.
+        let vp8 = [[0; 3].as_slice(), &[0x9d, 0x01, 0x2a], &[0x80, 0x42, 0xe0, 0x41]].concat();
+        assert_image_info(&webp(b"VP8 ", &vp8), "image/webp", (640, 480), rgb);
+
+        // Lossless, with the signature, and a 300x200 size with the alpha hint.
+        let bits: u32 = 299 | (199 << 14) | (1 << 28);
+        let vp8l = [[0x2f].as_slice(), &bits.to_le_bytes()].concat();
+        assert_image_info(&webp(b"VP8L", &vp8l), "image/webp", (300, 200), rgba);
+
+        // Extended, with the alpha flag, and a 1920x1080 canvas.
+        let vp8x =
+            [[0x10, 0, 0, 0].as_slice(), &1919u32.to_le_bytes()[..3], &1079u32.to_le_bytes()[..3]]
+                .concat();
+        assert_image_info(&webp(b"VP8X", &vp8x), "image/webp", (1920, 1080), rgba);
+
+        // Other RIFF forms are not images.
+        let mut wave = webp(b"VP8X", &vp8x);
+        wave[8..12].copy_from_slice(b"WAVE");
+        assert!(try_get_image_info(&wave).is_none());
+    }
+
+    #[test]
+    fn verify_heif_image_info() {
+        let rgb = ColorModel::RGB(non_zero(8));
+
+        let avif = heif(b"avif", &[b"mif1", b"miaf"], 1200, 800);
+        assert_image_info(&avif, "image/avif", (1200, 800), rgb);
+
+        // The brand may only be a compatible brand.
+        let avif = heif(b"mif1", &[b"avif"], 64, 32);
+        assert_image_info(&avif, "image/avif", (64, 32), rgb);
+
+        let heic = heif(b"heic", &[b"mif1"], 4032, 3024);
+        assert_image_info(&heic, "image/heic", (4032, 3024), rgb);
+
+        // An MP4 file is not an image.
+        assert!(try_get_image_info(&heif(b"isom", &[b"mp41"], 64, 32)).is_none());
+    }
+}
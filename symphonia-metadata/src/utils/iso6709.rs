@@ -0,0 +1,133 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Utilities for handling ISO 6709 location strings.
+
+use symphonia_core::meta::GeoLocation;
+
+/// Split a signed decimal number from the start of the string. Returns the number, including the
+/// sign, and the remainder of the string.
+fn split_signed_number(s: &str) -> Option<(&str, &str)> {
+    if !s.starts_with(['+', '-']) {
+        return None;
+    }
+
+    let end = s[1..].find(|c: char| !c.is_ascii_digit() && c != '.').map_or(s.len(), |i| i + 1);
+
+    Some(s.split_at(end))
+}
+
+/// Parse a signed angle in the `±D.D`, `±DM.M`, or `±DMS.S` form, where `D` is the given number of
+/// degree digits, and `M` and `S` are 2 digit minutes and seconds, respectively. The last component
+/// may have a fractional part.
+fn parse_angle(s: &str, deg_digits: usize, max: f64) -> Option<f64> {
+    let (sign, num) = s.split_at(1);
+
+    let int_len = num.find('.').unwrap_or(num.len());
+
+    if int_len < deg_digits || !num[..int_len].bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let deg = num[..deg_digits].parse::<f64>().ok()?;
+    let rem = &num[deg_digits..];
+
+    let angle = match int_len - deg_digits {
+        0 => num.parse::<f64>().ok()?,
+        2 => deg + rem.parse::<f64>().ok().filter(|&min| min < 60.0)? / 60.0,
+        4 => {
+            let min = rem[..2].parse::<f64>().ok().filter(|&min| min < 60.0)?;
+            let sec = rem[2..].parse::<f64>().ok().filter(|&sec| sec < 60.0)?;
+            deg + min / 60.0 + sec / 3600.0
+        }
+        _ => return None,
+    };
+
+    if angle > max {
+        return None;
+    }
+
+    Some(if sign == "-" { -angle } else { angle })
+}
+
+/// Parse an ISO 6709 location string, such as `+37.7858-122.4064+010.000/`, into a geographic
+/// location.
+///
+/// The latitude and longitude may be given in degrees, degrees and minutes, or degrees, minutes,
+/// and seconds. The altitude is optional. A coordinate reference system identifier is ignored.
+pub fn parse_iso6709(s: &str) -> Option<GeoLocation> {
+    let s = s.trim();
+
+    let (lat, rem) = split_signed_number(s)?;
+    let (lon, rem) = split_signed_number(rem)?;
+
+    let latitude = parse_angle(lat, 2, 90.0)?;
+    let longitude = parse_angle(lon, 3, 180.0)?;
+
+    let (altitude, rem) = match split_signed_number(rem) {
+        Some((alt, rem)) => (Some(alt.parse::<f64>().ok()?), rem),
+        None => (None, rem),
+    };
+
+    // The location may be terminated by a coordinate reference system identifier and/or a solidus.
+    if !(rem.is_empty() || rem.starts_with('/') || rem.starts_with("CRS")) {
+        return None;
+    }
+
+    Some(GeoLocation { latitude, longitude, altitude })
+}
+
+#[cfg(test)]
+mod tests {
+    use symphonia_core::meta::GeoLocation;
+
+    use super::parse_iso6709;
+
+    fn assert_location(s: &str, latitude: f64, longitude: f64, altitude: Option<f64>) {
+        let loc = parse_iso6709(s).unwrap();
+        assert!((loc.latitude - latitude).abs() < 1e-9, "{s}: latitude {}", loc.latitude);
+        assert!((loc.longitude - longitude).abs() < 1e-9, "{s}: longitude {}", loc.longitude);
+        assert_eq!(loc.altitude, altitude, "{s}: altitude");
+    }
+
+    #[test]
+    fn verify_parse_iso6709() {
+        // Degrees, with and without altitude.
+        assert_location("+37.7858-122.4064+010.000/", 37.7858, -122.4064, Some(10.0));
+        assert_location("+37.7858-122.4064/", 37.7858, -122.4064, None);
+        assert_location("-33.8688+151.2093-005.5/", -33.8688, 151.2093, Some(-5.5));
+        assert_location("+40-075/", 40.0, -75.0, None);
+        // Degrees and minutes.
+        assert_location("+4030.5-07545.0/", 40.0 + 30.5 / 60.0, -(75.0 + 45.0 / 60.0), None);
+        // Degrees, minutes, and seconds.
+        assert_location(
+            "+403036.6-0754512.3+120/",
+            40.0 + 30.0 / 60.0 + 36.6 / 3600.0,
+            -(75.0 + 45.0 / 60.0 + 12.3 / 3600.0),
+            Some(120.0),
+        );
+        // Without the terminating solidus, and with a coordinate reference system.
+        assert_location("+37.7858-122.4064", 37.7858, -122.4064, None);
+        assert_location("+35.36+138.73+3776CRSWGS_84/", 35.36, 138.73, Some(3776.0));
+
+        assert_eq!(
+            parse_iso6709("+00.0000+000.0000/"),
+            Some(GeoLocation { latitude: 0.0, longitude: 0.0, altitude: None })
+        );
+
+        // Invalid.
+        assert_eq!(parse_iso6709(""), None);
+        assert_eq!(parse_iso6709("/"), None);
+        assert_eq!(parse_iso6709("37.7858-122.4064/"), None);
+        assert_eq!(parse_iso6709("+37.7858/"), None);
+        assert_eq!(parse_iso6709("+97.0000-122.4064/"), None);
+        assert_eq!(parse_iso6709("+37.7858-192.4064/"), None);
+        assert_eq!(parse_iso6709("+4060.0-07545.0/"), None);
+        assert_eq!(parse_iso6709("+377-122.4064/"), None);
+        assert_eq!(parse_iso6709("+37.7858-122.4064 Somewhere"), None);
+    }
+}
@@ -0,0 +1,138 @@
+// Symphonia
+// Copyright (c) 2019-2026 The Project Symphonia Developers.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Utilities for handling LRC-formatted lyrics.
+
+use symphonia_core::meta::SyncedLyrics;
+use symphonia_core::units::Time;
+
+/// Parse a LRC timestamp in the `mm:ss`, `mm:ss.xx`, `mm:ss.xxx`, or `mm:ss:xx` format, into
+/// milliseconds.
+fn parse_lrc_timestamp(tag: &str) -> Option<i64> {
+    let is_num = |s: &str| !s.is_empty() && s.bytes().all(|c| c.is_ascii_digit());
+
+    let (min, rem) = tag.split_once(':')?;
+    let (sec, frac) = rem.split_once(['.', ':']).unwrap_or((rem, "0"));
+
+    if !is_num(min) || !is_num(sec) || !is_num(frac) || sec.len() > 2 || frac.len() > 3 {
+        return None;
+    }
+
+    let min = min.parse::<i64>().ok()?;
+    let sec = sec.parse::<i64>().ok().filter(|&sec| sec < 60)?;
+    // The number of fractional digits indicates the denominator.
+    let ms = frac.parse::<i64>().ok()? * 10i64.pow(3 - frac.len() as u32);
+
+    min.checked_mul(60_000)?.checked_add(1000 * sec)?.checked_add(ms)
+}
+
+/// Parse LRC-formatted lyrics into synchronized lyrics. If there are no timed lines, then `None`
+/// is returned.
+pub fn parse_lrc(text: &str) -> Option<SyncedLyrics> {
+    // The offset, in milliseconds, to apply to all timestamps. A positive offset makes lines
+    // appear sooner.
+    let mut offset = 0;
+
+    let mut lines = Vec::new();
+
+    for line in text.lines() {
+        let mut rem = line.trim();
+        let mut times = Vec::new();
+
+        // A line may be prefixed by many timestamps if it is repeated.
+        while let Some((tag, after)) = rem.strip_prefix('[').and_then(|rem| rem.split_once(']')) {
+            if let Some(time) = parse_lrc_timestamp(tag) {
+                times.push(time);
+            }
+            else {
+                // The only ID tag that affects the lyrics is the offset tag.
+                if let Some((key, value)) = tag.split_once(':') {
+                    if times.is_empty() && key.trim().eq_ignore_ascii_case("offset") {
+                        offset = value.trim().parse::<i64>().unwrap_or(0);
+                    }
+                }
+                break;
+            }
+
+            rem = after;
+        }
+
+        for time in times {
+            lines.push((time, rem.trim().to_string()));
+        }
+    }
+
+    if lines.is_empty() {
+        return None;
+    }
+
+    // Lines are not necessarily in chronological order.
+    lines.sort_by_key(|(time, _)| *time);
+
+    let lines = lines
+        .into_iter()
+        .map(|(time, text)| (Time::from_millis(time.saturating_sub(offset).max(0)), text))
+        .collect();
+
+    Some(SyncedLyrics { language: None, description: None, lines })
+}
+
+#[cfg(test)]
+mod tests {
+    use symphonia_core::units::Time;
+
+    use super::{parse_lrc, parse_lrc_timestamp};
+
+    #[test]
+    fn verify_parse_lrc_timestamp() {
+        assert_eq!(parse_lrc_timestamp("00:00"), Some(0));
+        assert_eq!(parse_lrc_timestamp("01:02"), Some(62_000));
+        assert_eq!(parse_lrc_timestamp("01:02.5"), Some(62_500));
+        assert_eq!(parse_lrc_timestamp("01:02.34"), Some(62_340));
+        assert_eq!(parse_lrc_timestamp("01:02.345"), Some(62_345));
+        assert_eq!(parse_lrc_timestamp("01:02:34"), Some(62_340));
+        assert_eq!(parse_lrc_timestamp("123:00.00"), Some(7_380_000));
+        // Invalid.
+        assert_eq!(parse_lrc_timestamp(""), None);
+        assert_eq!(parse_lrc_timestamp("ar:Artist"), None);
+        assert_eq!(parse_lrc_timestamp("00:60.00"), None);
+        assert_eq!(parse_lrc_timestamp("00:00.0000"), None);
+        assert_eq!(parse_lrc_timestamp("-1:00.00"), None);
+        assert_eq!(parse_lrc_timestamp(":00.00"), None);
+        // Overflow.
+        assert_eq!(parse_lrc_timestamp("153722867280912:59.999"), None);
+        assert_eq!(parse_lrc_timestamp("999999999999999999:00.00"), None);
+    }
+
+    #[test]
+    fn verify_parse_lrc() {
+        let lrc = "[ar:Artist]\n\
+                   [ti:Title]\n\
+                   [offset:+500]\n\
+                   \n\
+                   [00:12.00]First line\r\n\
+                   [00:17.20][01:17.20]Chorus\n\
+                   [00:21.10]\n\
+                   Not a timed line\n";
+
+        let lyrics = parse_lrc(lrc).unwrap();
+
+        assert_eq!(
+            lyrics.lines,
+            [
+                (Time::from_millis(11_500), "First line".to_string()),
+                (Time::from_millis(16_700), "Chorus".to_string()),
+                (Time::from_millis(20_600), "".to_string()),
+                (Time::from_millis(76_700), "Chorus".to_string()),
+            ]
+        );
+
+        // Plain text lyrics are not synchronized lyrics.
+        assert!(parse_lrc("First line\nSecond line").is_none());
+        assert!(parse_lrc("[ar:Artist]\nFirst line").is_none());
+    }
+}
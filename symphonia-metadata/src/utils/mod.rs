@@ -10,7 +10,10 @@
 pub mod id3v1;
 pub mod id3v2;
 pub mod images;
+pub mod iso6709;
 pub mod itunes;
 
 pub(crate) mod base64;
+#[cfg(feature = "vorbis")]
+pub(crate) mod lrc;
 pub(crate) mod std_tag;
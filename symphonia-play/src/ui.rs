@@ -11,7 +11,7 @@ use std::path::Path;
 
 use lazy_static::lazy_static;
 use symphonia::core::codecs::{CodecInfo, CodecParameters, CodecProfile};
-use symphonia::core::formats::{Attachment, FormatReader, MediaInfo, Track, TrackFlags};
+use symphonia::core::formats::{Attachment, FormatReader, MediaInfo, Track, TrackFlags, TrackType};
 use symphonia::core::meta::{
     Chapter, ChapterGroup, ChapterGroupItem, ColorMode, ColorModel, ContentAdvisory, MetadataInfo,
     MetadataRevision, StandardTag, Tag, Visual,
@@ -89,6 +89,10 @@ pub fn print_media(media_info: &MediaInfo) {
         }
     }
 
+    if let Some(start_timecode) = media_info.start_timecode {
+        print_pair("Start Timecode:", &start_timecode, Bullet::None, 1);
+    }
+
     if let Some(duration) = media_info.duration {
         if let Some(tb) = media_info.time_base {
             print_pair(
@@ -180,7 +184,17 @@ pub fn print_tracks(tracks: &[Track]) {
                     print_pair("Codec ID:", &params.codec, Bullet::None, 1);
                 }
                 _ => {
-                    print_pair("Track Type:", &"*Unsupported*", Bullet::Num(idx + 1), 1);
+                    // The codec is unsupported, but the container may signal the track type.
+                    let track_type = match track.track_type() {
+                        Some(TrackType::Audio) => "Audio (*Unsupported*)",
+                        Some(TrackType::Video) => "Video (*Unsupported*)",
+                        Some(TrackType::Subtitle) => "Subtitle (*Unsupported*)",
+                        Some(TrackType::Timecode) => "Timecode",
+                        Some(TrackType::Data) => "Data",
+                        _ => "*Unsupported*",
+                    };
+
+                    print_pair("Track Type:", &track_type, Bullet::Num(idx + 1), 1);
                 }
             }
 
@@ -27,11 +27,15 @@
 //! | Format   | Feature Flag | Gapless* | Default |
 //! |----------|--------------|----------|---------|
 //! | AIFF     | `aiff`       | Yes      | No      |
+//! | AVI      | `avi`        | No       | No      |
 //! | CAF      | `caf`        | No       | No      |
+//! | FLV      | `flv`        | No       | No      |
 //! | ISO/MP4  | `isomp4`     | No       | No      |
 //! | MKV/WebM | `mkv`        | No       | Yes     |
 //! | OGG      | `ogg`        | Yes      | Yes     |
+//! | SRT      | `subtitle`   | No       | No      |
 //! | Wave     | `wav`        | Yes      | Yes     |
+//! | WebVTT   | `subtitle`   | No       | No      |
 //!
 //! \* Gapless playback requires support from both the demuxer and decoder.
 //!
@@ -65,6 +69,7 @@
 //! |-----------------------|--------------|---------|
 //! | APEv1                 | `ape`        | Yes     |
 //! | APEv2                 | `ape`        | Yes     |
+//! | ICY (SHOUTcast)       | `icy`        | Yes     |
 //! | ID3v1                 | `id3v1`      | Yes     |
 //! | ID3v2                 | `id3v2`      | Yes     |
 //! | ISO/MP4               | N/A          | N/A     |
@@ -156,6 +161,8 @@ pub mod default {
         pub use symphonia_codec_aac::AdtsReader;
         #[cfg(feature = "caf")]
         pub use symphonia_format_caf::CafReader;
+        #[cfg(feature = "flv")]
+        pub use symphonia_format_flv::FlvReader;
         #[cfg(feature = "isomp4")]
         pub use symphonia_format_isomp4::IsoMp4Reader;
         #[cfg(feature = "mkv")]
@@ -164,8 +171,12 @@ pub mod default {
         pub use symphonia_format_ogg::OggReader;
         #[cfg(feature = "aiff")]
         pub use symphonia_format_riff::AiffReader;
+        #[cfg(feature = "avi")]
+        pub use symphonia_format_riff::AviReader;
         #[cfg(feature = "wav")]
         pub use symphonia_format_riff::WavReader;
+        #[cfg(feature = "subtitle")]
+        pub use symphonia_format_subtitle::SubtitleReader;
 
         #[deprecated = "use `default::formats::MpaReader` instead"]
         #[cfg(any(feature = "mp1", feature = "mp2", feature = "mp3"))]
@@ -177,6 +188,8 @@ pub mod default {
 
         #[cfg(feature = "ape")]
         pub use symphonia_metadata::ape::ApeReader;
+        #[cfg(feature = "icy")]
+        pub use symphonia_metadata::icy::IcySource;
         #[cfg(feature = "id3v1")]
         pub use symphonia_metadata::id3v1::Id3v1Reader;
         #[cfg(feature = "id3v2")]
@@ -270,6 +283,9 @@ pub mod default {
         #[cfg(feature = "flac")]
         probe.register_format::<formats::FlacReader<'_>>();
 
+        #[cfg(feature = "flv")]
+        probe.register_format::<formats::FlvReader<'_>>();
+
         #[cfg(feature = "isomp4")]
         probe.register_format::<formats::IsoMp4Reader<'_>>();
 
@@ -279,9 +295,15 @@ pub mod default {
         #[cfg(feature = "aiff")]
         probe.register_format::<formats::AiffReader<'_>>();
 
+        #[cfg(feature = "avi")]
+        probe.register_format::<formats::AviReader<'_>>();
+
         #[cfg(feature = "wav")]
         probe.register_format::<formats::WavReader<'_>>();
 
+        #[cfg(feature = "subtitle")]
+        probe.register_format::<formats::SubtitleReader<'_>>();
+
         #[cfg(feature = "ogg")]
         probe.register_format::<formats::OggReader<'_>>();
 